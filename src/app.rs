@@ -1,8 +1,135 @@
-use crate::models::{Package, PackageManager};
+use crate::audit::shadowing::Shadowing;
+use crate::deep_link::DeepLink;
+use crate::managers::health::{record_scan, HealthMap, ManagerHealth};
+use crate::managers::releases::ReleaseDates;
+use crate::models::{
+    Category, CategoryOverrides, Change, ChangeKind, Conflict, Package, PackageFlags,
+    PackageManager, Risk, RiskLevel,
+};
+use crate::operations::recent::RecentChange;
+use crate::operations::runtime_tools::StrandedTool;
+use crate::operations::update_everything::UpdateEverythingRun;
+use crate::operations::{JournalAction, JournalEntry};
+use crate::report::score::EnvironmentScore;
+use crate::utils::target::{close_target, open_container, set_target, ExecTarget};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeldAction {
+    Update,
+    Reinstall,
+    Uninstall,
+}
+
+impl HeldAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            HeldAction::Update => "update",
+            HeldAction::Reinstall => "reinstall",
+            HeldAction::Uninstall => "remove",
+        }
+    }
+}
+
+/// What the main pane shows, picked from the sidebar tree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum View {
+    All,
+    Manager(PackageManager),
+    Projects,
+    Vulnerable,
+    Unused,
+    Pinned,
+    History,
+    Recent,
+    ScanReport,
+}
+
+impl View {
+    pub fn label(&self) -> &str {
+        match self {
+            View::All => "All packages",
+            View::Manager(manager) => manager.name(),
+            View::Projects => "Projects",
+            View::Vulnerable => "Vulnerable",
+            View::Unused => "Unused",
+            View::Pinned => "Pinned",
+            View::History => "History",
+            View::Recent => "Recently changed",
+            View::ScanReport => "Scan report",
+        }
+    }
+
+    /// Whether a package belongs in this view's table
+    pub fn includes(&self, pkg: &Package, flags: &PackageFlags) -> bool {
+        match self {
+            View::Manager(manager) => &pkg.manager == manager,
+            View::Vulnerable => !pkg.vulnerabilities.is_empty(),
+            View::Unused => pkg.used_in.is_empty(),
+            View::Pinned => flags.is_pinned(pkg),
+            View::All | View::Projects | View::History | View::Recent | View::ScanReport => true,
+        }
+    }
+}
+
+/// An install/update held back until the user confirms it despite conflicts
+pub struct PendingConflict {
+    pub action: HeldAction,
+    pub package: Package,
+    pub conflicts: Vec<Conflict>,
+}
+
+/// An update or removal of a critical package, waiting for its name to be typed
+pub struct PendingCritical {
+    pub action: HeldAction,
+    pub manager: PackageManager,
+    pub name: String,
+    pub version: Option<String>, // the row an uninstall removes
+}
+
+/// An update of a package whose binaries are running, waiting for the user to go ahead
+pub struct PendingRunning {
+    pub manager: PackageManager,
+    pub name: String,
+    pub processes: crate::utils::processes::RunningProcesses,
+    /// A started `brew services` service for the formula, if there is one
+    pub service: Option<String>,
+    pub restart_service: bool, // Stop the service before updating and start it after
+}
+
+/// Settings being edited in the Settings window, written only when saved
+pub struct SettingsDraft {
+    pub settings: crate::config::Settings,
+    pub scan_roots: Vec<std::path::PathBuf>, // Added scan directories
+    /// Directories always scanned, listed once when the window opens
+    pub built_in_roots: Vec<std::path::PathBuf>,
+    pub root_input: String,
+}
+
+/// A folder dropped on the window (or picked with "Scan folder…"), analyzed on its own
+pub struct FolderScan {
+    pub path: std::path::PathBuf,
+    pub projects: Option<Vec<crate::models::Project>>, // None while scanning
+}
+
+/// "Audit repo" result, from pasting a path or git URL
+pub enum RepoAuditState {
+    Running(String),
+    Ready(crate::audit::RepoAudit),
+    Failed(String),
+}
+
+/// Release notes for the triage view, fetched on demand per package
+pub enum ChangelogState {
+    Loading,
+    Ready(Option<crate::audit::Changelog>), // None: no GitHub repository found
+    Failed(String),
+}
+
 pub struct DepMgrApp {
     pub packages: Arc<RwLock<Vec<Package>>>,
     pub available_managers: Vec<PackageManager>,
@@ -12,14 +139,86 @@ pub struct DepMgrApp {
     pub show_orphaned_only: bool,
     pub is_scanning: Arc<AtomicBool>,
     pub refresh_requested: bool,
+    pub refresh_signal: Arc<AtomicBool>, // Set by background tasks to request a rescan
     pub runtime: tokio::runtime::Runtime,
     pub updating_packages: Arc<RwLock<std::collections::HashSet<String>>>,
     pub update_status: Arc<RwLock<String>>,
     pub removed_packages: Arc<RwLock<std::collections::HashSet<String>>>, // Track removed packages in this session
+    pub prompt_inputs: std::collections::HashMap<u64, String>, // Typed answers for commands waiting on stdin
+    pub last_scan: Arc<RwLock<Option<DateTime<Utc>>>>,
+    pub size_units: crate::utils::format::SizeUnits,
+    pub settings: crate::config::Settings,
+    pub metadata_times: Arc<RwLock<std::collections::HashMap<PackageManager, DateTime<Utc>>>>, // "data as of" per manager
+    pub manager_health: HealthMap,
+    pub diagnostics_manager: Option<PackageManager>, // Manager whose diagnostics window is open
+    pub selected_package: Option<(PackageManager, String)>, // Package shown in the detail panel
+    pub show_update_everything: bool,
+    pub stranded_tools: Arc<RwLock<Vec<StrandedTool>>>, // Left behind by a runtime upgrade
+    pub show_runtime_tools: bool,
+    pub runtime_reinstall: Arc<RwLock<UpdateEverythingRun>>,
+    pub update_everything: Arc<RwLock<UpdateEverythingRun>>,
+    pub target: ExecTarget, // Machine being audited (this one or an SSH host)
+    pub detected_managers: Arc<RwLock<Option<Vec<PackageManager>>>>, // Set after switching targets
+    pub pending_target: Arc<RwLock<Option<ExecTarget>>>, // Container ready to switch to
+    pub container_input: String,
+    pub font_selection: std::collections::HashSet<String>, // Font casks picked for batch removal
+    pub loaded_fonts: std::collections::HashSet<String>,   // Font previews already handed to egui
+    pub selected_categories: std::collections::HashSet<Category>, // Empty means all categories
+    pub category_overrides: CategoryOverrides,
+    pub shown_outdated: Option<usize>, // Outdated count currently in the title/badge
+    pub palette_open: bool,
+    pub palette_query: String,
+    pub palette_index: usize,        // Highlighted palette entry
+    pub package_flags: PackageFlags, // Pinned / ignored packages
+    pub previous_score: Option<crate::report::score::ScoreEntry>, // Trend baseline
+    pub score: Arc<RwLock<Option<EnvironmentScore>>>, // Computed once per scan
+    pub marked_packages: std::collections::HashSet<(PackageManager, String)>, // Cmd-clicked rows
+    pub details_hidden: bool,        // "d" hides the detail panel without losing the selection
+    pub show_triage: bool,
+    pub show_path_report: bool,
+    pub path_report: Arc<RwLock<Option<Vec<Shadowing>>>>, // None while analyzing
+    pub pending_conflicts: Vec<PendingConflict>,          // Shown one at a time, oldest first
+    pub pending_critical: Vec<PendingCritical>,           // Same, before any conflict check
+    pub critical_input: String,                           // What's been typed to confirm
+    pub pending_running: Arc<RwLock<Vec<PendingRunning>>>, // Updates after the conflict check
+    /// Later launches handed to this window (`None`: just show it), see `deep_link::forward`
+    pub handed_over: Arc<RwLock<Vec<Option<DeepLink>>>>,
+    pub triage_queue: Vec<(PackageManager, String)>, // Outdated packages to review, in order
+    pub triage_index: usize,
+    pub changelogs: Arc<RwLock<std::collections::HashMap<String, ChangelogState>>>, // By Package::key()
+    pub scan_changes: Arc<RwLock<Vec<Change>>>, // What the last refresh changed
+    pub changes_dismissed: Arc<AtomicBool>,     // "What changed" panel closed
+    pub projects: Arc<RwLock<Vec<crate::models::Project>>>, // Found under the scan directories
+    pub scan_report: Arc<RwLock<crate::scanner::ScanReport>>, // What the last project scan left out
+    pub deep_scan: bool,                        // Next scan ignores the budget
+    pub recent_days: i64,                       // "Recently changed" window
+    pub recent_changes: Vec<RecentChange>,      // ...within it, newest first
+    recent_loaded: Option<(std::time::Instant, i64)>, // When and for which window
+    history_cache: Option<(String, std::time::Instant, Vec<JournalEntry>)>, // Details panel's package
+    pub view: View,                                      // Picked in the sidebar tree
+    pub details_detached: bool,                          // Package details in their own window
+    pub log_open: bool,                                  // Command log window
+    pub log_detached: bool,                              // ...as its own OS window
+    pub folder_scan: Arc<RwLock<Option<FolderScan>>>,    // Last one-off folder scan
+    pub folder_input: String,                            // "Scan folder…" path field
+    pub repo_audit: Arc<RwLock<Option<RepoAuditState>>>, // Window shown while Some
+    pub repo_input: String,                              // "Audit repo" path or git URL field
+    pub description_requests: std::collections::HashSet<String>, // Package::key()s looked up lazily
+    pub description_limiter: Arc<tokio::sync::Semaphore>, // Lazy lookups in flight
+    pub release_dates: Arc<RwLock<std::collections::HashMap<String, ReleaseDates>>>, // By release_key()
+    pub release_requests: std::collections::HashSet<String>, // release_key()s looked up on hover
+    pub metadata_pulled: Arc<AtomicBool>, // Sync rewrote the flags and categories on disk
+    pub settings_draft: Option<SettingsDraft>, // Settings window, open while Some
+    pub window_layout: Option<crate::config::WindowLayout>, // Saved with the UI state on exit
+    pub weekly_summary: Arc<RwLock<Option<crate::report::weekly::WeeklySummary>>>, // Overlay while Some
 }
 
 impl Default for DepMgrApp {
     fn default() -> Self {
+        let settings = crate::config::Settings::load();
+        let description_limiter = Arc::new(tokio::sync::Semaphore::new(
+            settings.descriptions.batch_size(),
+        ));
         Self {
             packages: Arc::new(RwLock::new(Vec::new())),
             available_managers: Vec::new(),
@@ -29,41 +228,373 @@ impl Default for DepMgrApp {
             show_orphaned_only: false,
             is_scanning: Arc::new(AtomicBool::new(false)),
             refresh_requested: false,
+            refresh_signal: Arc::new(AtomicBool::new(false)),
             runtime: tokio::runtime::Runtime::new().unwrap(),
             updating_packages: Arc::new(RwLock::new(std::collections::HashSet::new())),
             update_status: Arc::new(RwLock::new(String::new())),
             removed_packages: Arc::new(RwLock::new(std::collections::HashSet::new())),
+            prompt_inputs: std::collections::HashMap::new(),
+            last_scan: Arc::new(RwLock::new(None)),
+            size_units: crate::utils::format::SizeUnits::default(),
+            settings,
+            metadata_times: Arc::new(RwLock::new(std::collections::HashMap::new())),
+            manager_health: Arc::new(RwLock::new(std::collections::HashMap::new())),
+            diagnostics_manager: None,
+            selected_package: None,
+            show_update_everything: false,
+            stranded_tools: Arc::new(RwLock::new(Vec::new())),
+            show_runtime_tools: false,
+            runtime_reinstall: Arc::new(RwLock::new(UpdateEverythingRun::default())),
+            update_everything: Arc::new(RwLock::new(UpdateEverythingRun::default())),
+            target: crate::utils::target::current_target(), // `--demo` may have switched it
+            detected_managers: Arc::new(RwLock::new(None)),
+            pending_target: Arc::new(RwLock::new(None)),
+            container_input: String::new(),
+            font_selection: std::collections::HashSet::new(),
+            loaded_fonts: std::collections::HashSet::new(),
+            selected_categories: std::collections::HashSet::new(),
+            category_overrides: CategoryOverrides::load(),
+            shown_outdated: None,
+            palette_open: false,
+            palette_query: String::new(),
+            palette_index: 0,
+            package_flags: PackageFlags::load(),
+            previous_score: crate::report::score::previous_score(),
+            score: Arc::new(RwLock::new(None)),
+            marked_packages: std::collections::HashSet::new(),
+            details_hidden: false,
+            show_triage: false,
+            show_path_report: false,
+            path_report: Arc::new(RwLock::new(None)),
+            pending_conflicts: Vec::new(),
+            pending_critical: Vec::new(),
+            critical_input: String::new(),
+            pending_running: Arc::new(RwLock::new(Vec::new())),
+            handed_over: Arc::new(RwLock::new(Vec::new())),
+            triage_queue: Vec::new(),
+            triage_index: 0,
+            changelogs: Arc::new(RwLock::new(std::collections::HashMap::new())),
+            scan_changes: Arc::new(RwLock::new(Vec::new())),
+            changes_dismissed: Arc::new(AtomicBool::new(false)),
+            projects: Arc::new(RwLock::new(Vec::new())),
+            scan_report: Arc::new(RwLock::new(Default::default())),
+            deep_scan: false,
+            recent_days: 7,
+            recent_changes: Vec::new(),
+            recent_loaded: None,
+            history_cache: None,
+            view: View::All,
+            details_detached: false,
+            log_open: false,
+            log_detached: false,
+            folder_scan: Arc::new(RwLock::new(None)),
+            folder_input: String::new(),
+            repo_audit: Arc::new(RwLock::new(None)),
+            repo_input: String::new(),
+            description_requests: std::collections::HashSet::new(),
+            description_limiter,
+            release_dates: Arc::new(RwLock::new(std::collections::HashMap::new())),
+            release_requests: std::collections::HashSet::new(),
+            metadata_pulled: Arc::new(AtomicBool::new(false)),
+            settings_draft: None,
+            window_layout: None,
+            weekly_summary: Arc::new(RwLock::new(None)),
         }
     }
 }
 
 impl DepMgrApp {
+    /// App state as both frontends start it: managers detected, the last session's
+    /// filters restored, the cached inventory shown and the first scan under way
+    pub fn start() -> Self {
+        let mut app = Self::default();
+        app.available_managers = app
+            .runtime
+            .block_on(crate::managers::detect_available_managers());
+        println!(
+            "[MANAGERS] Found {} package managers",
+            app.available_managers.len()
+        );
+        app.restore_ui_state();
+
+        // Last run's results first, then the initial scan asynchronously (non-blocking)
+        app.load_cached_inventory();
+        app.start_scan();
+        app
+    }
+
+    /// Show the inventory saved by the last scan straight away; the startup scan then
+    /// revalidates it row by row and reports what changed
+    pub fn load_cached_inventory(&mut self) {
+        if !self.target.is_local() {
+            return;
+        }
+        let Some((scanned_at, packages)) = crate::inventory::load_snapshot() else {
+            return;
+        };
+        println!(
+            "[DEBUG] Showing {} packages from the last scan ({})",
+            packages.len(),
+            scanned_at
+        );
+        *self.score.blocking_write() = crate::report::score::score(&packages, &self.package_flags);
+        *self.packages.blocking_write() = packages;
+        *self.last_scan.blocking_write() = Some(scanned_at);
+    }
+
+    /// How the last refresh changed this row, if it did
+    pub fn change_of(&self, pkg: &Package) -> Option<ChangeKind> {
+        let key = pkg.key();
+        self.scan_changes
+            .blocking_read()
+            .iter()
+            .find(|c| c.key() == key)
+            .map(|c| c.kind.clone())
+    }
+
+    /// Installed packages that installing or updating `pkg` would clash with
+    pub fn conflicts_of(&self, pkg: &Package) -> Vec<Conflict> {
+        let removed = self.removed_packages.blocking_read();
+        let installed: Vec<Package> = self
+            .packages
+            .blocking_read()
+            .iter()
+            .filter(|p| !removed.contains(&p.row_id()))
+            .cloned()
+            .collect();
+        crate::models::conflict::find(pkg, &installed)
+    }
+
+    /// Queue a conflict explanation instead of running the operation; true if held
+    fn hold_for_conflicts(
+        &mut self,
+        action: HeldAction,
+        name: &str,
+        manager: &PackageManager,
+    ) -> bool {
+        let Some(package) = self
+            .packages
+            .blocking_read()
+            .iter()
+            .find(|p| p.name == name && &p.manager == manager)
+            .cloned()
+        else {
+            return false;
+        };
+        let conflicts = self.conflicts_of(&package);
+        if conflicts.is_empty() {
+            return false;
+        }
+        println!(
+            "[APP] Holding {} of {}: conflicts with {} installed packages",
+            action.label(),
+            name,
+            conflicts.len()
+        );
+        self.pending_conflicts.push(PendingConflict {
+            action,
+            package,
+            conflicts,
+        });
+        true
+    }
+
+    /// The user read the conflict explanation and wants to go ahead anyway
+    pub fn proceed_despite_conflicts(&mut self) {
+        if self.pending_conflicts.is_empty() {
+            return;
+        }
+        let pending = self.pending_conflicts.remove(0);
+        let version = Some(pending.package.installed_version);
+        let (name, manager) = (pending.package.name, pending.package.manager);
+        match pending.action {
+            HeldAction::Update => self.update_unless_running(name, manager),
+            HeldAction::Reinstall => self.start_reinstall(name, manager),
+            HeldAction::Uninstall => self.start_uninstall(name, manager, version),
+        }
+    }
+
+    /// Ask for the package's name before touching a critical package; true if held
+    fn hold_if_critical(
+        &mut self,
+        action: HeldAction,
+        name: &str,
+        manager: &PackageManager,
+        version: Option<&str>,
+    ) -> bool {
+        if !self.package_flags.is_critical_named(manager, name) {
+            return false;
+        }
+        self.pending_critical.push(PendingCritical {
+            action,
+            manager: manager.clone(),
+            name: name.to_string(),
+            version: version.map(String::from),
+        });
+        true
+    }
+
+    /// The user typed the critical package's name: carry on as if it weren't held
+    pub fn confirm_critical(&mut self) {
+        if self.pending_critical.is_empty() {
+            return;
+        }
+        let pending = self.pending_critical.remove(0);
+        self.critical_input.clear();
+        let (name, manager) = (pending.name, pending.manager);
+        match pending.action {
+            HeldAction::Update => {
+                if !self.hold_for_conflicts(HeldAction::Update, &name, &manager) {
+                    self.update_unless_running(name, manager);
+                }
+            }
+            HeldAction::Reinstall => self.start_reinstall(name, manager),
+            HeldAction::Uninstall => self.start_uninstall(name, manager, pending.version),
+        }
+    }
+
+    /// Warn before updating a package with processes running from it on this machine.
+    /// Processes are listed on the runtime, and the update starts from there if none are.
+    fn update_unless_running(&mut self, name: String, manager: PackageManager) {
+        let package = self
+            .packages
+            .blocking_read()
+            .iter()
+            .find(|p| p.name == name && p.manager == manager)
+            .cloned();
+        let Some(package) = package.filter(|_| self.target.is_local()) else {
+            self.start_update(name, manager, None);
+            return;
+        };
+        let update = self.update_task(name.clone(), manager.clone(), None);
+        let pending_running = Arc::clone(&self.pending_running);
+
+        self.runtime.spawn(async move {
+            let Some(processes) = crate::utils::processes::running_from(&package).await else {
+                update.await;
+                return;
+            };
+            println!("[APP] Holding update of {}: {}", name, processes.summary());
+            let service = (manager == PackageManager::Homebrew
+                && crate::utils::processes::brew_service_started(&name))
+            .then(|| name.clone());
+            pending_running.write().await.push(PendingRunning {
+                restart_service: service.is_some(),
+                manager,
+                name,
+                processes,
+                service,
+            });
+        });
+    }
+
+    /// The user saw what's running and wants the update anyway
+    pub fn proceed_despite_running(&mut self) {
+        let pending = {
+            let mut pending_running = self.pending_running.blocking_write();
+            if pending_running.is_empty() {
+                return;
+            }
+            pending_running.remove(0)
+        };
+        let service = pending.service.filter(|_| pending.restart_service);
+        self.start_update(pending.name, pending.manager, service);
+    }
+
+    /// Managers checked in the sidebar (all of them when none are)
+    fn managers_to_scan(&self) -> Vec<PackageManager> {
+        self.available_managers
+            .iter()
+            .filter(|m| self.selected_managers.is_empty() || self.selected_managers.contains(m))
+            .cloned()
+            .collect()
+    }
+
     pub fn start_scan(&mut self) {
+        if !self.target.is_local() || crate::daemon::is_connected() {
+            self.start_remote_scan();
+            return;
+        }
+
         self.is_scanning.store(true, Ordering::Relaxed);
         let packages_clone = Arc::clone(&self.packages);
         let scanning_flag = Arc::clone(&self.is_scanning);
-        let available_managers = self.available_managers.clone();
+        let last_scan = Arc::clone(&self.last_scan);
+        let score = Arc::clone(&self.score);
+        let available_managers = self.managers_to_scan();
+        let detected_managers = self.available_managers.clone();
+        let before = self.packages.blocking_read().clone();
+        let scan_changes = Arc::clone(&self.scan_changes);
+        let changes_dismissed = Arc::clone(&self.changes_dismissed);
+        let stranded_tools = Arc::clone(&self.stranded_tools);
+        let projects_state = Arc::clone(&self.projects);
+        let scan_report = Arc::clone(&self.scan_report);
+        let budget = if std::mem::take(&mut self.deep_scan) {
+            crate::scanner::ScanBudget::unlimited()
+        } else {
+            self.settings.scan.budget()
+        };
+        let digest_settings = self.settings.digest.clone();
+        let weekly_summary = Arc::clone(&self.weekly_summary);
+        let scan_settings = self.settings.scan.clone();
+        let description_settings = self.settings.descriptions.clone();
+        let metadata_times = Arc::clone(&self.metadata_times);
+        let manager_health = Arc::clone(&self.manager_health);
+
+        // Manager versions for the sidebar chips, independent of the package scan
+        let probe_managers = available_managers.clone();
+        let probe_health = Arc::clone(&self.manager_health);
+        self.runtime.spawn(async move {
+            crate::managers::health::probe_all(&probe_managers, probe_health).await;
+        });
 
         self.runtime.spawn(async move {
             println!("[DEBUG] Starting package scan...");
+            // Rows from the last run stay up until each manager's fresh listing replaces
+            // them; unchecked managers keep their last results
 
             // Scan Homebrew if available
             if available_managers.contains(&PackageManager::Homebrew) {
                 println!("[DEBUG] Scanning Homebrew packages...");
+                let _activity = crate::utils::activity::start("Listing Homebrew packages");
                 match crate::managers::homebrew_fast::list_homebrew_packages_fast().await {
                     Ok(mut packages) => {
                         println!("[DEBUG] Found {} Homebrew packages", packages.len());
 
                         // Update UI immediately with basic package info
-                        *packages_clone.write().await = packages.clone();
+                        replace_packages(
+                            &mut *packages_clone.write().await,
+                            &PackageManager::Homebrew,
+                            packages.clone(),
+                        );
                         println!("[DEBUG] UI updated with initial package list");
 
                         // Phase 2: Scan for actual project usage
                         let scan_dirs = crate::scanner::get_scan_directories();
-                        crate::scanner::scan_homebrew_tool_usage(&mut packages, &scan_dirs);
-                        *packages_clone.write().await = packages.clone();
+                        crate::scanner::scan_homebrew_tool_usage(
+                            &mut packages,
+                            &scan_dirs,
+                            &budget,
+                        );
+                        replace_packages(
+                            &mut *packages_clone.write().await,
+                            &PackageManager::Homebrew,
+                            packages.clone(),
+                        );
                         println!("[DEBUG] Updated with project usage info");
 
+                        // Make sure `brew upgrade` sees the same versions the API reports
+                        if scan_settings.refresh_metadata {
+                            if let Err(e) = crate::managers::metadata::refresh_metadata_if_stale(
+                                &PackageManager::Homebrew,
+                                scan_settings.metadata_max_age_hours,
+                            )
+                            .await
+                            {
+                                eprintln!("[ERROR] Failed to refresh Homebrew metadata: {}", e);
+                            }
+                        }
+
                         // Phase 3: Check for outdated packages (INSTANT with API data!)
                         if let Ok(()) =
                             crate::managers::homebrew_fast::check_outdated_packages_fast(
@@ -71,23 +602,74 @@ impl DepMgrApp {
                             )
                             .await
                         {
-                            *packages_clone.write().await = packages.clone();
+                            replace_packages(
+                                &mut *packages_clone.write().await,
+                                &PackageManager::Homebrew,
+                                packages.clone(),
+                            );
                             println!("[DEBUG] UI updated with outdated status");
                         }
+                        record_scan(&manager_health, &PackageManager::Homebrew, Ok(&packages))
+                            .await;
+                        metadata_times.write().await.insert(
+                            PackageManager::Homebrew,
+                            crate::managers::metadata::data_fetched_at(
+                                &PackageManager::Homebrew,
+                                Utc::now(),
+                            ),
+                        );
+
+                        // Cellar sizes feed the Size column and disk usage in digests
+                        match crate::managers::homebrew_fast::add_installed_sizes(&mut packages)
+                            .await
+                        {
+                            Ok(()) => replace_packages(
+                                &mut *packages_clone.write().await,
+                                &PackageManager::Homebrew,
+                                packages.clone(),
+                            ),
+                            Err(e) => eprintln!("[ERROR] Failed to measure Homebrew sizes: {}", e),
+                        }
+                        crate::managers::homebrew_fast::add_bottle_sizes(&mut packages).await;
+                        crate::utils::arch::add_architectures(&mut packages).await;
+                        crate::utils::broken::add_broken_binaries(
+                            &PackageManager::Homebrew,
+                            &mut packages,
+                        )
+                        .await;
+                        replace_packages(
+                            &mut *packages_clone.write().await,
+                            &PackageManager::Homebrew,
+                            packages.clone(),
+                        );
 
                         // Phase 4: Only fetch missing descriptions (API already gave us most!)
-                        let packages_for_desc = packages.clone();
-                        let packages_arc = Arc::clone(&packages_clone);
-                        tokio::spawn(async move {
-                            crate::managers::homebrew_fast::add_missing_descriptions_fast(
-                                packages_for_desc,
-                                packages_arc,
-                            )
-                            .await;
-                        });
+                        if description_settings.prefetches(&PackageManager::Homebrew) {
+                            let packages_for_desc = packages.clone();
+                            let packages_arc = Arc::clone(&packages_clone);
+                            let source = description_settings.source(&PackageManager::Homebrew);
+                            let batch_size = description_settings.batch_size();
+                            tokio::spawn(async move {
+                                let _activity =
+                                    crate::utils::activity::start("Fetching descriptions");
+                                crate::managers::homebrew_fast::add_missing_descriptions_fast(
+                                    packages_for_desc,
+                                    packages_arc,
+                                    source,
+                                    batch_size,
+                                )
+                                .await;
+                            });
+                        }
                     }
                     Err(e) => {
                         eprintln!("[ERROR] Failed to list Homebrew packages: {}", e);
+                        record_scan(
+                            &manager_health,
+                            &PackageManager::Homebrew,
+                            Err(e.to_string()),
+                        )
+                        .await;
                     }
                 }
             }
@@ -95,26 +677,55 @@ impl DepMgrApp {
             // Scan npm if available
             if available_managers.contains(&PackageManager::Npm) {
                 println!("[DEBUG] Scanning npm packages...");
+                let _activity = crate::utils::activity::start("Listing npm packages");
                 match crate::managers::npm::list_npm_packages().await {
                     Ok(mut packages) => {
                         println!("[DEBUG] Found {} npm packages", packages.len());
 
                         // Check outdated
                         let _ = crate::managers::npm::check_outdated_npm(&mut packages).await;
-
-                        // Append to existing packages
-                        let mut all_packages = packages_clone.write().await;
-                        all_packages.extend(packages);
+                        metadata_times
+                            .write()
+                            .await
+                            .insert(PackageManager::Npm, Utc::now());
+                        record_scan(&manager_health, &PackageManager::Npm, Ok(&packages)).await;
+                        if let Err(e) = crate::managers::npm::add_npm_sizes(&mut packages).await {
+                            eprintln!("[ERROR] Failed to measure npm sizes: {}", e);
+                        }
+                        crate::utils::broken::add_broken_binaries(
+                            &PackageManager::Npm,
+                            &mut packages,
+                        )
+                        .await;
+
+                        replace_packages(
+                            &mut *packages_clone.write().await,
+                            &PackageManager::Npm,
+                            packages,
+                        );
                         println!("[DEBUG] Added npm packages to list");
 
                         // Fetch descriptions in background
-                        let packages_arc = Arc::clone(&packages_clone);
-                        tokio::spawn(async move {
-                            crate::managers::npm::add_npm_descriptions(packages_arc).await;
-                        });
+                        if description_settings.prefetches(&PackageManager::Npm) {
+                            let packages_arc = Arc::clone(&packages_clone);
+                            let source = description_settings.source(&PackageManager::Npm);
+                            let batch_size = description_settings.batch_size();
+                            tokio::spawn(async move {
+                                let _activity =
+                                    crate::utils::activity::start("Fetching descriptions");
+                                crate::managers::npm::add_npm_descriptions(
+                                    packages_arc,
+                                    source,
+                                    batch_size,
+                                )
+                                .await;
+                            });
+                        }
                     }
                     Err(e) => {
                         eprintln!("[ERROR] Failed to list npm packages: {}", e);
+                        record_scan(&manager_health, &PackageManager::Npm, Err(e.to_string()))
+                            .await;
                     }
                 }
             }
@@ -122,26 +733,53 @@ impl DepMgrApp {
             // Scan cargo if available
             if available_managers.contains(&PackageManager::Cargo) {
                 println!("[DEBUG] Scanning cargo packages...");
+                let _activity = crate::utils::activity::start("Listing cargo packages");
                 match crate::managers::cargo::list_cargo_packages().await {
                     Ok(mut packages) => {
                         println!("[DEBUG] Found {} cargo packages", packages.len());
 
                         // Check outdated
                         let _ = crate::managers::cargo::check_outdated_cargo(&mut packages).await;
-
-                        // Append to existing packages
-                        let mut all_packages = packages_clone.write().await;
-                        all_packages.extend(packages);
+                        crate::utils::arch::add_architectures(&mut packages).await;
+                        crate::utils::broken::add_broken_binaries(
+                            &PackageManager::Cargo,
+                            &mut packages,
+                        )
+                        .await;
+                        metadata_times
+                            .write()
+                            .await
+                            .insert(PackageManager::Cargo, Utc::now());
+                        record_scan(&manager_health, &PackageManager::Cargo, Ok(&packages)).await;
+
+                        replace_packages(
+                            &mut *packages_clone.write().await,
+                            &PackageManager::Cargo,
+                            packages,
+                        );
                         println!("[DEBUG] Added cargo packages to list");
 
-                        // Fetch descriptions from crates.io in background
-                        let packages_arc = Arc::clone(&packages_clone);
-                        tokio::spawn(async move {
-                            crate::managers::cargo::add_cargo_descriptions(packages_arc).await;
-                        });
+                        // Fetch descriptions in background
+                        if description_settings.prefetches(&PackageManager::Cargo) {
+                            let packages_arc = Arc::clone(&packages_clone);
+                            let source = description_settings.source(&PackageManager::Cargo);
+                            let batch_size = description_settings.batch_size();
+                            tokio::spawn(async move {
+                                let _activity =
+                                    crate::utils::activity::start("Fetching descriptions");
+                                crate::managers::cargo::add_cargo_descriptions(
+                                    packages_arc,
+                                    source,
+                                    batch_size,
+                                )
+                                .await;
+                            });
+                        }
                     }
                     Err(e) => {
                         eprintln!("[ERROR] Failed to list cargo packages: {}", e);
+                        record_scan(&manager_health, &PackageManager::Cargo, Err(e.to_string()))
+                            .await;
                     }
                 }
             }
@@ -149,234 +787,1622 @@ impl DepMgrApp {
             // Scan pip if available
             if available_managers.contains(&PackageManager::Pip) {
                 println!("[DEBUG] Scanning pip packages...");
+                let _activity = crate::utils::activity::start("Listing pip packages");
                 match crate::managers::pip::list_pip_packages().await {
                     Ok(mut packages) => {
                         println!("[DEBUG] Found {} pip packages", packages.len());
 
                         // Check outdated
                         let _ = crate::managers::pip::check_outdated_pip(&mut packages).await;
-
-                        // Append to existing packages
-                        let mut all_packages = packages_clone.write().await;
-                        all_packages.extend(packages);
+                        metadata_times
+                            .write()
+                            .await
+                            .insert(PackageManager::Pip, Utc::now());
+                        record_scan(&manager_health, &PackageManager::Pip, Ok(&packages)).await;
+
+                        replace_packages(
+                            &mut *packages_clone.write().await,
+                            &PackageManager::Pip,
+                            packages,
+                        );
                         println!("[DEBUG] Added pip packages to list");
 
                         // Fetch descriptions in background
-                        let packages_arc = Arc::clone(&packages_clone);
-                        tokio::spawn(async move {
-                            crate::managers::pip::add_pip_descriptions(packages_arc).await;
-                        });
+                        if description_settings.prefetches(&PackageManager::Pip) {
+                            let packages_arc = Arc::clone(&packages_clone);
+                            let source = description_settings.source(&PackageManager::Pip);
+                            let batch_size = description_settings.batch_size();
+                            tokio::spawn(async move {
+                                let _activity =
+                                    crate::utils::activity::start("Fetching descriptions");
+                                crate::managers::pip::add_pip_descriptions(
+                                    packages_arc,
+                                    source,
+                                    batch_size,
+                                )
+                                .await;
+                            });
+                        }
                     }
                     Err(e) => {
                         eprintln!("[ERROR] Failed to list pip packages: {}", e);
+                        record_scan(&manager_health, &PackageManager::Pip, Err(e.to_string()))
+                            .await;
+                    }
+                }
+            }
+
+            // Every other backend: one listing + outdated pass each
+            for manager in &available_managers {
+                if matches!(
+                    manager,
+                    PackageManager::Homebrew
+                        | PackageManager::Npm
+                        | PackageManager::Cargo
+                        | PackageManager::Pip
+                ) {
+                    continue;
+                }
+                let _activity =
+                    crate::utils::activity::start(format!("Listing {} packages", manager.name()));
+                let Some(result) = crate::managers::list_with_outdated(manager).await else {
+                    continue; // Detected but no backend yet
+                };
+                match result {
+                    Ok(packages) => {
+                        println!(
+                            "[DEBUG] Found {} {} packages",
+                            packages.len(),
+                            manager.name()
+                        );
+                        metadata_times
+                            .write()
+                            .await
+                            .insert(manager.clone(), Utc::now());
+                        record_scan(&manager_health, manager, Ok(&packages)).await;
+                        replace_packages(&mut *packages_clone.write().await, manager, packages);
+                    }
+                    Err(e) => {
+                        eprintln!("[ERROR] Failed to list {} packages: {}", manager.name(), e);
+                        record_scan(&manager_health, manager, Err(e.to_string())).await;
                     }
                 }
             }
 
+            // Which projects declare the npm/Cargo/pip packages, and whether only as dev deps
+            let projects_activity = crate::utils::activity::start("Scanning projects");
+            let scan_dirs = crate::scanner::get_scan_directories();
+            let (mut projects, report) = crate::scanner::scan_projects_indexed(&scan_dirs, &budget);
+            check_projects(&mut projects, scan_settings.project_venvs).await;
+            drop(projects_activity);
+            {
+                let mut all_packages = packages_clone.write().await;
+                crate::scanner::apply_dependency_usage(&mut all_packages, &projects);
+                crate::scanner::apply_tool_usage(&mut all_packages, &projects);
+                crate::managers::version_managers::mark_managed_tools(&mut all_packages);
+                crate::scanner::check_tool_requirements(&mut all_packages, &projects);
+                crate::scanner::apply_project_refs(&mut all_packages);
+            }
+
+            // Known vulnerabilities for everything OSV covers
+            {
+                let _activity = crate::utils::activity::start("Checking vulnerabilities");
+                crate::audit::add_vulnerabilities_shared(Arc::clone(&packages_clone)).await;
+            }
+
+            // Managers uninstalled since the last run
+            packages_clone
+                .write()
+                .await
+                .retain(|p| detected_managers.contains(&p.manager));
+            crate::inventory::save_snapshot(&packages_clone.read().await);
+            *projects_state.write().await = projects;
+            *scan_report.write().await = report;
+            *stranded_tools.write().await =
+                crate::operations::runtime_tools::find_stranded(&packages_clone.read().await).await;
+            let changes = publish_changes(
+                &before,
+                &packages_clone.read().await,
+                &scan_changes,
+                &changes_dismissed,
+            )
+            .await;
+            crate::operations::recent::record_detected(&changes);
+
+            *last_scan.write().await = Some(Utc::now());
             scanning_flag.store(false, Ordering::Relaxed);
             println!("[DEBUG] Scan complete");
+
+            update_score(&packages_clone, &score, true).await;
+            if let Some(summary) =
+                crate::report::weekly::take_weekly_summary(&packages_clone.read().await)
+            {
+                *weekly_summary.write().await = Some(summary);
+            }
+
+            if digest_settings.enabled {
+                let snapshot = packages_clone.read().await.clone();
+                if let Err(e) =
+                    crate::report::run_digest_if_due(&snapshot, &digest_settings, false).await
+                {
+                    eprintln!("[ERROR] Failed to generate digest: {}", e);
+                }
+            }
         });
     }
 
-    pub fn request_refresh(&mut self) {
-        self.refresh_requested = true;
+    /// Remote hosts get the one-pass headless inventory: no project usage or disk sizes,
+    /// since those come from the local filesystem. With a daemon running, this is its
+    /// inventory; refreshes after the first ask it to rescan.
+    fn start_remote_scan(&mut self) {
+        self.is_scanning.store(true, Ordering::Relaxed);
+        let packages_clone = Arc::clone(&self.packages);
+        let scanning_flag = Arc::clone(&self.is_scanning);
+        let last_scan = Arc::clone(&self.last_scan);
+        let score = Arc::clone(&self.score);
+        let manager_health = Arc::clone(&self.manager_health);
+        let managers = self.managers_to_scan();
+        let label = self.target.label();
+        let before = self.packages.blocking_read().clone();
+        let scan_changes = Arc::clone(&self.scan_changes);
+        let changes_dismissed = Arc::clone(&self.changes_dismissed);
+        let from_daemon = crate::daemon::is_connected();
+        let rescan_daemon = from_daemon && self.last_scan.blocking_read().is_some();
+        let update_status = Arc::clone(&self.update_status);
+        let refresh_signal = Arc::clone(&self.refresh_signal);
+
+        self.runtime.spawn(async move {
+            println!("[DEBUG] Starting package scan on {}...", label);
+            let _activity = crate::utils::activity::start(format!("Scanning {}", label));
+            crate::managers::health::probe_all(&managers, Arc::clone(&manager_health)).await;
+
+            let packages = if from_daemon {
+                let fetched = if rescan_daemon {
+                    match crate::daemon::request(&crate::daemon::Request::Rescan).await {
+                        Ok(_) => crate::daemon::fetch_inventory().await,
+                        Err(e) => Err(e),
+                    }
+                } else {
+                    crate::daemon::fetch_inventory().await
+                };
+                match fetched {
+                    Ok(packages) => packages
+                        .into_iter()
+                        .filter(|p| managers.contains(&p.manager))
+                        .collect(),
+                    Err(e) => {
+                        // Stop using the daemon; the next refresh is the full local scan
+                        eprintln!("[DAEMON] Scan failed: {}, scanning here instead", e);
+                        crate::daemon::disconnect();
+                        *update_status.write().await =
+                            format!("Daemon scan failed: {}. Scanning here instead", e);
+                        scanning_flag.store(false, Ordering::Relaxed);
+                        refresh_signal.store(true, Ordering::Relaxed);
+                        return;
+                    }
+                }
+            } else {
+                crate::inventory::collect_inventory(&managers).await
+            };
+            for manager in &managers {
+                let listed: Vec<Package> = packages
+                    .iter()
+                    .filter(|p| &p.manager == manager)
+                    .cloned()
+                    .collect();
+                record_scan(&manager_health, manager, Ok(&listed)).await;
+            }
+            replace_packages_of(&mut *packages_clone.write().await, &managers, packages);
+            publish_changes(
+                &before,
+                &packages_clone.read().await,
+                &scan_changes,
+                &changes_dismissed,
+            )
+            .await;
+
+            // The daemon's inventory is this machine's, so it counts toward the trend
+            update_score(&packages_clone, &score, from_daemon).await;
+
+            *last_scan.write().await = Some(Utc::now());
+            scanning_flag.store(false, Ordering::Relaxed);
+            println!("[DEBUG] Scan of {} complete", label);
+        });
     }
 
-    pub fn handle_refresh(&mut self) {
-        if self.refresh_requested {
-            self.refresh_requested = false;
-            self.start_scan();
+    /// Point the app at another machine: forget the current inventory, detect the
+    /// managers available there, then rescan (picked up in `handle_refresh`)
+    pub fn switch_target(&mut self, target: ExecTarget) {
+        if target == self.target {
+            return;
         }
+        set_target(target.clone());
+        let previous = std::mem::replace(&mut self.target, target);
+        self.runtime.spawn(async move {
+            close_target(&previous).await;
+        });
+        self.available_managers.clear();
+        self.selected_managers.clear();
+        self.view = View::All;
+        self.selected_package = None;
+        self.diagnostics_manager = None;
+        self.packages.blocking_write().clear();
+        self.manager_health.blocking_write().clear();
+        self.metadata_times.blocking_write().clear();
+        *self.last_scan.blocking_write() = None;
+        *self.score.blocking_write() = None;
+        self.scan_changes.blocking_write().clear();
+        self.projects.blocking_write().clear();
+        self.description_requests.clear();
+        self.is_scanning.store(true, Ordering::Relaxed);
+
+        let detected_managers = Arc::clone(&self.detected_managers);
+        let refresh_signal = Arc::clone(&self.refresh_signal);
+        self.runtime.spawn(async move {
+            let managers = crate::managers::detect_available_managers().await;
+            println!("[MANAGERS] Found {} package managers", managers.len());
+            *detected_managers.write().await = Some(managers);
+            refresh_signal.store(true, Ordering::Relaxed);
+        });
     }
 
-    pub fn filtered_packages(&self) -> Vec<Package> {
-        let packages = self.packages.blocking_read();
-        packages
-            .iter()
-            .filter(|pkg| {
-                // Filter by selected managers
-                if !self.selected_managers.is_empty()
-                    && !self.selected_managers.contains(&pkg.manager)
-                {
-                    return false;
-                }
+    /// Pull (or push) pins, ignores, favorites and category overrides through the
+    /// repository or gist set under `[sync]`
+    pub fn sync_metadata(&mut self, push: bool) {
+        let settings = self.settings.sync.clone();
+        let metadata_pulled = Arc::clone(&self.metadata_pulled);
+        let update_status = Arc::clone(&self.update_status);
 
-                // Filter by search query
-                if !self.search_query.is_empty()
-                    && !pkg
-                        .name
-                        .to_lowercase()
-                        .contains(&self.search_query.to_lowercase())
-                {
-                    return false;
+        self.runtime.spawn(async move {
+            *update_status.write().await = if push {
+                "Pushing metadata...".to_string()
+            } else {
+                "Pulling metadata...".to_string()
+            };
+            let result = if push {
+                crate::operations::sync::push(&settings).await
+            } else {
+                crate::operations::sync::pull(&settings).await
+            };
+            match result {
+                Ok(outcome) => {
+                    metadata_pulled.store(true, Ordering::Relaxed);
+                    *update_status.write().await = outcome.describe();
                 }
-
-                // Filter by outdated
-                if self.show_outdated_only && !pkg.is_outdated {
-                    return false;
+                Err(e) => {
+                    eprintln!("[ERROR] Metadata sync failed: {}", e);
+                    *update_status.write().await = format!("Metadata sync failed: {}", e);
                 }
+            }
+        });
+    }
 
-                // Filter by orphaned (TODO: implement orphaned detection)
-                if self.show_orphaned_only {
-                    // Placeholder - will implement later
-                }
+    /// Audit a Docker container (by name/id) or image; images get a throwaway container
+    pub fn open_container(&mut self, reference: String) {
+        let reference = reference.trim().to_string();
+        if reference.is_empty() {
+            return;
+        }
+        let pending_target = Arc::clone(&self.pending_target);
+        let update_status = Arc::clone(&self.update_status);
 
-                true
-            })
-            .cloned()
-            .collect()
+        self.runtime.spawn(async move {
+            *update_status.write().await = format!("Opening {}...", reference);
+            match open_container(&reference).await {
+                Ok(target) => {
+                    *pending_target.write().await = Some(target);
+                    *update_status.write().await = String::new();
+                }
+                Err(e) => {
+                    eprintln!("[ERROR] Failed to open {}: {}", reference, e);
+                    *update_status.write().await = format!("Failed to open {}: {}", reference, e);
+                }
+            }
+        });
     }
 
-    pub fn stats(&self) -> (usize, usize, usize) {
-        let packages = self.packages.blocking_read();
-        let total = packages.len();
-        let outdated = packages.iter().filter(|p| p.is_outdated).count();
-        // Count unused packages
-        let unused = packages.iter().filter(|p| p.used_in.is_empty()).count();
-        // Reference the functions to ensure they're not considered dead code
-        let _orphaned_packages = self.find_orphaned_packages();
-        let _scanned_projects = self.scan_projects();
-        (total, outdated, unused)
-    }
+    /// Analyze the projects in one folder without adding it to the scan directories
+    pub fn scan_folder(&mut self, path: std::path::PathBuf) {
+        if !self.target.is_local() {
+            *self.update_status.blocking_write() = format!(
+                "Folder scans only run on this machine, not {}",
+                self.target.label()
+            );
+            return;
+        }
+        if !path.is_dir() {
+            *self.update_status.blocking_write() = format!("{} is not a folder", path.display());
+            return;
+        }
+        *self.folder_scan.blocking_write() = Some(FolderScan {
+            path: path.clone(),
+            projects: None,
+        });
+        let folder_scan = Arc::clone(&self.folder_scan);
+        let venvs = self.settings.scan.project_venvs;
 
-    // Placeholder for project scanning - will use Project and Dependency
-    // This demonstrates usage of Project::new() and Dependency struct
-    pub fn scan_projects(&self) -> Vec<crate::models::Project> {
-        // TODO: Implement project scanning
-        // For now, return empty vector but demonstrate usage
-        let _example_project =
-            crate::models::Project::new(std::path::PathBuf::from("/tmp/example"));
-        let _example_dep = crate::models::Dependency {
-            package_name: "example".to_string(),
-            manager: crate::models::PackageManager::Npm,
-            version_constraint: "^1.0.0".to_string(),
-            is_dev: false,
-        };
-        Vec::new()
+        self.runtime.spawn(async move {
+            let _activity = crate::utils::activity::start(format!("Scanning {}", path.display()));
+            let mut projects = crate::scanner::scan_projects(std::slice::from_ref(&path));
+            check_projects(&mut projects, venvs).await;
+            // Only if it's still the folder on screen
+            if let Some(scan) = folder_scan.write().await.as_mut() {
+                if scan.path == path {
+                    scan.projects = Some(projects);
+                }
+            }
+        });
     }
 
-    // Placeholder for orphaned detection - will use PackageUsage
-    // This demonstrates usage of PackageUsage::new() and add_project()
-    pub fn find_orphaned_packages(&self) -> Vec<crate::models::PackageUsage> {
-        // TODO: Implement orphaned package detection
-        // For now, return empty vector but demonstrate usage
-        let packages = self.packages.blocking_read();
-        if let Some(pkg) = packages.first() {
-            let mut usage = crate::models::PackageUsage::new(pkg.clone());
-            let example_project =
-                crate::models::Project::new(std::path::PathBuf::from("/tmp/example"));
-            usage.add_project(example_project);
-            // Access the package field to avoid warning
-            let _ = &usage.package;
-            return vec![usage];
+    /// Read a local path or clone a git URL and list the tools it needs that are missing
+    pub fn audit_repo(&mut self, source: String) {
+        let source = source.trim().to_string();
+        if source.is_empty() {
+            return;
         }
-        Vec::new()
+        if !self.target.is_local() {
+            *self.update_status.blocking_write() = format!(
+                "Repo audits only run on this machine, not {}",
+                self.target.label()
+            );
+            return;
+        }
+        *self.repo_audit.blocking_write() = Some(RepoAuditState::Running(source.clone()));
+        let repo_audit = Arc::clone(&self.repo_audit);
+
+        self.runtime.spawn(async move {
+            let _activity = crate::utils::activity::start(format!("Auditing {}", source));
+            let state = match crate::audit::audit_repo(&source).await {
+                Ok(audit) => RepoAuditState::Ready(audit),
+                Err(e) => {
+                    eprintln!("[AUDIT] {}: {}", source, e);
+                    RepoAuditState::Failed(e.to_string())
+                }
+            };
+            *repo_audit.write().await = Some(state);
+        });
     }
 
-    pub fn update_package(&mut self, package_name: String, manager: PackageManager) {
+    /// Install a tool a repo audit or setup plan found missing, with Homebrew. A version
+    /// constraint picks the formula (`node@22`) whose current release meets it, and fails
+    /// when none does rather than installing a different version.
+    pub fn install_missing_tool(
+        &mut self,
+        command: String,
+        formula: String,
+        constraint: Option<String>,
+    ) {
         let updating_packages = Arc::clone(&self.updating_packages);
         let update_status = Arc::clone(&self.update_status);
-        let packages = Arc::clone(&self.packages);
+        let repo_audit = Arc::clone(&self.repo_audit);
 
         self.runtime.spawn(async move {
-            // Mark as updating
-            updating_packages.write().await.insert(package_name.clone());
-            *update_status.write().await = format!("Updating {}...", package_name);
-
-            let result = match manager {
-                PackageManager::Homebrew => {
-                    crate::managers::homebrew_fast::update_package(package_name.clone()).await
-                }
-                PackageManager::Npm => {
-                    crate::managers::npm::update_npm_package(package_name.clone()).await
-                }
-                PackageManager::Cargo => {
-                    crate::managers::cargo::update_cargo_package(package_name.clone()).await
-                }
-                PackageManager::Pip => {
-                    crate::managers::pip::update_pip_package(package_name.clone()).await
+            let formula = match constraint {
+                Some(constraint) => {
+                    match crate::managers::homebrew_fast::formula_for_version(&formula, &constraint)
+                        .await
+                    {
+                        Ok(formula) => formula,
+                        Err(e) => {
+                            eprintln!("[APP] Failed to install {}: {}", formula, e);
+                            *update_status.write().await =
+                                format!("Failed to install {}: {}", formula, e);
+                            return;
+                        }
+                    }
                 }
-                _ => Err(anyhow::anyhow!(
-                    "Update not implemented for this package manager"
-                )),
+                None => formula,
             };
+            updating_packages.write().await.insert(formula.clone());
+            *update_status.write().await = format!("Installing {}...", formula);
+
+            let manager = PackageManager::Homebrew;
+            let result = crate::operations::install_package(&manager, formula.clone()).await;
+            JournalEntry::new(JournalAction::Install, manager, formula.clone()).finish(&result);
+
+            match result {
+                Ok(()) => {
+                    if let Some(RepoAuditState::Ready(audit)) = repo_audit.write().await.as_mut() {
+                        for tool in audit.tools.iter_mut().filter(|t| t.command == command) {
+                            tool.installed = true;
+                        }
+                    }
+                    *update_status.write().await = format!("{} installed", formula);
+                }
+                Err(e) => {
+                    eprintln!("[APP] Failed to install {}: {}", formula, e);
+                    *update_status.write().await = format!("Failed to install {}: {}", formula, e);
+                }
+            }
+            updating_packages.write().await.remove(&formula);
+        });
+    }
+
+    /// Be the window later launches go to (the GUI only; the TUI leaves the socket alone)
+    pub fn listen_for_launches(&self) {
+        self.runtime
+            .spawn(crate::deep_link::listen(Arc::clone(&self.handed_over)));
+    }
+
+    /// Stop any throwaway container before the app exits
+    pub fn shutdown(&mut self) {
+        self.save_ui_state();
+        let target = std::mem::take(&mut self.target);
+        self.runtime.block_on(close_target(&target));
+    }
+
+    /// Bring back the search, filters and shown managers from the last session;
+    /// managers that aren't detected anymore are dropped
+    pub fn restore_ui_state(&mut self) {
+        let state = self.settings.ui.clone();
+        self.search_query = state.search_query;
+        self.selected_managers = match state.managers {
+            Some(names) => self
+                .available_managers
+                .iter()
+                .filter(|m| names.iter().any(|name| name == m.name()))
+                .cloned()
+                .collect(),
+            None => self.available_managers.iter().cloned().collect(),
+        };
+        self.selected_categories = state.categories.into_iter().collect();
+        self.show_outdated_only = state.outdated_only;
+        self.show_orphaned_only = state.orphaned_only;
+        self.size_units = state.size_units;
+        self.details_hidden = state.details_hidden;
+    }
+
+    /// Write the search, filters and window layout to the `[ui]` table of config.toml;
+    /// the rest of the file is left alone.
+    fn save_ui_state(&self) {
+        let all_shown = self
+            .available_managers
+            .iter()
+            .all(|m| self.selected_managers.contains(m));
+        let mut categories: Vec<Category> = self.selected_categories.iter().copied().collect();
+        categories.sort_by_key(|c| c.label());
+        let state = crate::config::UiState {
+            search_query: self.search_query.clone(),
+            managers: (!all_shown).then(|| {
+                self.available_managers
+                    .iter()
+                    .filter(|m| self.selected_managers.contains(m))
+                    .map(|m| m.name().to_string())
+                    .collect()
+            }),
+            categories,
+            outdated_only: self.show_outdated_only,
+            orphaned_only: self.show_orphaned_only,
+            size_units: self.size_units,
+            details_hidden: self.details_hidden,
+            window: self.window_layout.or(self.settings.ui.window),
+        };
+        if let Err(e) = state.save() {
+            eprintln!("[ERROR] Failed to save UI state: {}", e);
+        }
+    }
+
+    pub fn open_settings(&mut self) {
+        let scan_roots = crate::scanner::added_scan_roots();
+        let built_in_roots = crate::scanner::get_scan_directories()
+            .into_iter()
+            .filter(|dir| !scan_roots.contains(dir))
+            .collect();
+        self.settings_draft = Some(SettingsDraft {
+            settings: crate::config::Settings::load(),
+            scan_roots,
+            built_in_roots,
+            root_input: String::new(),
+        });
+    }
+
+    /// Write the Settings window's changes; a rescan picks up new scan directories
+    pub fn save_settings(&mut self) {
+        let Some(draft) = self.settings_draft.take() else {
+            return;
+        };
+        if let Err(e) = draft.settings.save() {
+            *self.update_status.blocking_write() = format!("Failed to save settings: {}", e);
+            self.settings_draft = Some(draft);
+            return;
+        }
+        let roots_changed = draft.scan_roots != crate::scanner::added_scan_roots();
+        if roots_changed {
+            crate::scanner::set_added_scan_roots(&draft.scan_roots);
+        }
+        let scan_changed = roots_changed
+            || draft.settings.scan.max_files != self.settings.scan.max_files
+            || draft.settings.scan.max_seconds_per_dir != self.settings.scan.max_seconds_per_dir;
+        self.description_limiter = Arc::new(tokio::sync::Semaphore::new(
+            draft.settings.descriptions.batch_size(),
+        ));
+        self.settings = draft.settings;
+        if scan_changed {
+            self.request_refresh();
+        }
+    }
+
+    pub fn request_refresh(&mut self) {
+        self.refresh_requested = true;
+    }
+
+    /// Rescan walking every folder, whatever the scan budget
+    pub fn request_deep_scan(&mut self) {
+        self.deep_scan = true;
+        self.request_refresh();
+    }
+
+    pub fn handle_refresh(&mut self) {
+        let pending_target = self.pending_target.blocking_write().take();
+        if let Some(target) = pending_target {
+            self.switch_target(target);
+        }
+        if let Some(managers) = self.detected_managers.blocking_write().take() {
+            self.selected_managers = managers.iter().cloned().collect();
+            self.available_managers = managers;
+        }
+        if self.metadata_pulled.swap(false, Ordering::Relaxed) {
+            self.package_flags = PackageFlags::load();
+            self.category_overrides = CategoryOverrides::load();
+            self.shown_outdated = None;
+        }
+        if self.refresh_requested || self.refresh_signal.swap(false, Ordering::Relaxed) {
+            self.refresh_requested = false;
+            self.start_scan();
+        }
+    }
+
+    /// Positions in `packages` of the rows the filters show, favorites first. Filtered
+    /// under the lock so the dashboard only clones the rows it draws.
+    pub fn filtered_indices(&self) -> Vec<usize> {
+        let packages = self.packages.blocking_read();
+        let mut rows: Vec<usize> = packages
+            .iter()
+            .enumerate()
+            .filter(|(_, pkg)| {
+                if !self.view.includes(pkg, &self.package_flags) {
+                    return false;
+                }
+
+                // Filter by selected managers
+                if !self.selected_managers.is_empty()
+                    && !self.selected_managers.contains(&pkg.manager)
+                {
+                    return false;
+                }
+
+                if !self.selected_categories.is_empty()
+                    && !self
+                        .selected_categories
+                        .contains(&self.category_overrides.category_of(pkg))
+                {
+                    return false;
+                }
+
+                // Filter by search query
+                if !self.search_query.is_empty()
+                    && !pkg
+                        .name
+                        .to_lowercase()
+                        .contains(&self.search_query.to_lowercase())
+                {
+                    return false;
+                }
+
+                // Filter by outdated
+                if self.show_outdated_only && !self.package_flags.needs_attention(pkg) {
+                    return false;
+                }
+
+                // Filter by orphaned (TODO: implement orphaned detection)
+                if self.show_orphaned_only {
+                    // Placeholder - will implement later
+                }
+
+                true
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+        // Favorites first; the sort is stable, so everything keeps its order otherwise
+        rows.sort_by_key(|&idx| !self.package_flags.is_favorite(&packages[idx]));
+        rows
+    }
+
+    pub fn filtered_packages(&self) -> Vec<Package> {
+        let rows = self.filtered_indices();
+        let packages = self.packages.blocking_read();
+        rows.into_iter()
+            .filter_map(|idx| packages.get(idx).cloned())
+            .collect()
+    }
+
+    /// Package count per category, for the sidebar facet
+    pub fn category_counts(&self) -> std::collections::HashMap<Category, usize> {
+        let mut counts = std::collections::HashMap::new();
+        for pkg in self.packages.blocking_read().iter() {
+            *counts
+                .entry(self.category_overrides.category_of(pkg))
+                .or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Human readable description of the active filters, for report headers
+    pub fn active_filters(&self) -> Vec<String> {
+        let mut filters = Vec::new();
+        if self.selected_managers.len() < self.available_managers.len() {
+            let mut names: Vec<&str> = self.selected_managers.iter().map(|m| m.name()).collect();
+            names.sort();
+            filters.push(format!("managers: {}", names.join(", ")));
+        }
+        if !self.selected_categories.is_empty() {
+            let mut labels: Vec<&str> =
+                self.selected_categories.iter().map(|c| c.label()).collect();
+            labels.sort();
+            filters.push(format!("categories: {}", labels.join(", ")));
+        }
+        if !self.search_query.is_empty() {
+            filters.push(format!("search \"{}\"", self.search_query));
+        }
+        if self.show_outdated_only {
+            filters.push("outdated only".to_string());
+        }
+        if self.show_orphaned_only {
+            filters.push("orphaned only".to_string());
+        }
+        filters
+    }
+
+    /// Markdown report of exactly what the table is currently showing
+    pub fn filtered_markdown_report(&self) -> String {
+        let title = if self.show_outdated_only {
+            "DepMgr report: outdated packages"
+        } else if self.show_orphaned_only {
+            "DepMgr report: orphaned packages"
+        } else {
+            "DepMgr report: installed packages"
+        };
+        crate::report::render_markdown_report(
+            title,
+            &self.active_filters(),
+            &self.filtered_packages(),
+        )
+    }
+
+    /// Open config.toml in the default editor, writing the defaults first if it doesn't exist
+    pub fn open_settings_file(&mut self) {
+        let path = crate::config::Settings::path();
+        if !path.exists() {
+            if let Some(parent) = path.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            let defaults =
+                toml::to_string_pretty(&crate::config::Settings::default()).unwrap_or_default();
+            if let Err(e) = std::fs::write(&path, defaults) {
+                *self.update_status.blocking_write() =
+                    format!("Failed to create {}: {}", path.display(), e);
+                return;
+            }
+        }
+
+        let opener = if cfg!(target_os = "macos") {
+            "open"
+        } else {
+            "xdg-open"
+        };
+        if let Err(e) = std::process::Command::new(opener).arg(&path).spawn() {
+            *self.update_status.blocking_write() =
+                format!("Failed to open {}: {}", path.display(), e);
+        }
+    }
+
+    /// Write the current view's report to ~/Downloads (or ~) and report where it went
+    pub fn save_markdown_report(&mut self) {
+        let report = self.filtered_markdown_report();
+        let update_status = Arc::clone(&self.update_status);
+
+        self.runtime.spawn(async move {
+            let home = crate::utils::paths::home_dir();
+            let downloads = home.join("Downloads");
+            let dir = if downloads.is_dir() { downloads } else { home };
+            let path = dir.join(format!(
+                "depmgr-report-{}.md",
+                chrono::Local::now().format("%Y%m%d-%H%M%S")
+            ));
+
+            match tokio::fs::write(&path, report).await {
+                Ok(()) => {
+                    println!("[INFO] Saved report to {}", path.display());
+                    *update_status.write().await = format!("Report saved to {}", path.display());
+                }
+                Err(e) => {
+                    eprintln!("[ERROR] Failed to save report: {}", e);
+                    *update_status.write().await = format!("Failed to save report: {}", e);
+                }
+            }
+
+            // Clear status after a delay
+            tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+            *update_status.write().await = String::new();
+        });
+    }
+
+    /// Write a generated tool manifest (Brewfile, mise.toml) into a project, never over
+    /// an existing one
+    pub fn save_project_manifest(&mut self, path: std::path::PathBuf, content: String) {
+        let update_status = Arc::clone(&self.update_status);
+
+        self.runtime.spawn(async move {
+            let result = async {
+                use tokio::io::AsyncWriteExt;
+                let mut file = tokio::fs::OpenOptions::new()
+                    .write(true)
+                    .create_new(true)
+                    .open(&path)
+                    .await?;
+                file.write_all(content.as_bytes()).await
+            }
+            .await;
+            *update_status.write().await = match result {
+                Ok(()) => {
+                    println!("[INFO] Wrote {}", path.display());
+                    format!("Manifest saved to {}", path.display())
+                }
+                Err(e) => {
+                    eprintln!("[ERROR] Failed to write {}: {}", path.display(), e);
+                    format!("Failed to write {}: {}", path.display(), e)
+                }
+            };
+
+            tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+            *update_status.write().await = String::new();
+        });
+    }
+
+    /// Reread the journal and detected changes when the window changed or every
+    /// few seconds, so the "Recently changed" view picks up new operations
+    pub fn refresh_recent_changes(&mut self) {
+        let fresh = self.recent_loaded.is_some_and(|(loaded, days)| {
+            days == self.recent_days && loaded.elapsed() < std::time::Duration::from_secs(10)
+        });
+        if fresh {
+            return;
+        }
+        let since = Utc::now() - chrono::Duration::days(self.recent_days);
+        self.recent_changes = crate::operations::recent::changes_since(since);
+        self.recent_loaded = Some((std::time::Instant::now(), self.recent_days));
+    }
+
+    /// Packages in a sidebar view, before the search and filters
+    pub fn view_count(&self, view: &View) -> usize {
+        match view {
+            View::Projects => self.projects.blocking_read().len(),
+            View::History => self.scan_changes.blocking_read().len(),
+            View::Recent => self.recent_changes.len(),
+            View::ScanReport => self.scan_report.blocking_read().skipped.len(),
+            _ => self
+                .packages
+                .blocking_read()
+                .iter()
+                .filter(|p| view.includes(p, &self.package_flags))
+                .count(),
+        }
+    }
+
+    pub fn vulnerable_count(&self) -> usize {
+        self.packages
+            .blocking_read()
+            .iter()
+            .filter(|p| !p.vulnerabilities.is_empty())
+            .count()
+    }
+
+    pub fn stats(&self) -> (usize, usize, usize) {
+        let packages = self.packages.blocking_read();
+        let total = packages.len();
+        let outdated = packages
+            .iter()
+            .filter(|p| self.package_flags.needs_attention(p))
+            .count();
+        // Count unused packages
+        let unused = packages.iter().filter(|p| p.used_in.is_empty()).count();
+        // Reference the functions to ensure they're not considered dead code
+        let _orphaned_packages = self.find_orphaned_packages();
+        (total, outdated, unused)
+    }
+
+    /// Score of what the last scan listed, from this machine or the host being audited
+    pub fn environment_score(&self) -> Option<EnvironmentScore> {
+        self.score.blocking_read().clone()
+    }
+
+    /// (total, outdated, unused) per manager for the sidebar badges.
+    /// Read every frame, so the numbers follow the scan as it fills in.
+    pub fn manager_stats(
+        &self,
+    ) -> std::collections::HashMap<PackageManager, (usize, usize, usize)> {
+        let mut stats = std::collections::HashMap::new();
+        for pkg in self.packages.blocking_read().iter() {
+            let entry = stats.entry(pkg.manager.clone()).or_insert((0, 0, 0));
+            entry.0 += 1;
+            if self.package_flags.needs_attention(pkg) {
+                entry.1 += 1;
+            }
+            if pkg.used_in.is_empty() {
+                entry.2 += 1;
+            }
+        }
+        stats
+    }
+
+    // Placeholder for orphaned detection - will use PackageUsage
+    // This demonstrates usage of PackageUsage::new() and add_project()
+    pub fn find_orphaned_packages(&self) -> Vec<crate::models::PackageUsage> {
+        // TODO: Implement orphaned package detection
+        // For now, return empty vector but demonstrate usage
+        let packages = self.packages.blocking_read();
+        if let Some(pkg) = packages.first() {
+            let mut usage = crate::models::PackageUsage::new(pkg.clone());
+            let example_project =
+                crate::models::Project::new(std::path::PathBuf::from("/tmp/example"));
+            usage.add_project(example_project);
+            // Access the package field to avoid warning
+            let _ = &usage.package;
+            return vec![usage];
+        }
+        Vec::new()
+    }
+
+    pub fn update_package(&mut self, package_name: String, manager: PackageManager) {
+        if self.hold_if_critical(HeldAction::Update, &package_name, &manager, None) {
+            return;
+        }
+        if !self.hold_for_conflicts(HeldAction::Update, &package_name, &manager) {
+            self.update_unless_running(package_name, manager);
+        }
+    }
+
+    /// Update one package, stopping its `brew services` service around the upgrade if given
+    fn start_update(
+        &mut self,
+        package_name: String,
+        manager: PackageManager,
+        stop_service: Option<String>,
+    ) {
+        let update = self.update_task(package_name, manager, stop_service);
+        self.runtime.spawn(update);
+    }
+
+    fn update_task(
+        &self,
+        package_name: String,
+        manager: PackageManager,
+        stop_service: Option<String>,
+    ) -> impl std::future::Future<Output = ()> + Send + 'static {
+        let updating_packages = Arc::clone(&self.updating_packages);
+        let update_status = Arc::clone(&self.update_status);
+        let packages = Arc::clone(&self.packages);
+
+        async move {
+            // Mark as updating
+            updating_packages.write().await.insert(package_name.clone());
+            *update_status.write().await = format!("Updating {}...", package_name);
+
+            let (from_version, to_version) = packages
+                .read()
+                .await
+                .iter()
+                .find(|p| p.name == package_name && p.manager == manager)
+                .map(|p| (Some(p.installed_version.clone()), p.latest_version.clone()))
+                .unwrap_or((None, None));
+
+            if let Some(service) = &stop_service {
+                *update_status.write().await = format!("Stopping {} service...", service);
+                crate::utils::processes::brew_service(service, "stop").await;
+            }
+            let result = crate::operations::update_package(&manager, package_name.clone()).await;
+            if let Some(service) = &stop_service {
+                // Started again even if the update failed, so it's left as it was found
+                crate::utils::processes::brew_service(service, "start").await;
+            }
+            JournalEntry::new(JournalAction::Update, manager.clone(), package_name.clone())
+                .with_versions(from_version, to_version)
+                .finish(&result);
 
             match result {
                 Ok(_) => {
                     println!("[INFO] Successfully updated {}", package_name);
                     *update_status.write().await = format!("Updated {}", package_name);
 
-                    // Refresh the package list to get new version
-                    if let Ok(mut homebrew_packages) =
-                        crate::managers::homebrew_fast::list_homebrew_packages_fast().await
-                    {
-                        if let Ok(()) =
-                            crate::managers::homebrew_fast::check_outdated_packages_fast(
-                                &mut homebrew_packages,
-                            )
-                            .await
-                        {
-                            *packages.write().await = homebrew_packages;
-                        }
-                    }
-                }
-                Err(e) => {
-                    eprintln!("[ERROR] Failed to update {}: {}", package_name, e);
-                    *update_status.write().await =
-                        format!("Failed to update {}: {}", package_name, e);
+                    // Relist the package's manager to get the new version
+                    refresh_manager(&packages, &manager).await;
+                }
+                Err(e) => {
+                    eprintln!("[ERROR] Failed to update {}: {}", package_name, e);
+                    *update_status.write().await =
+                        format!("Failed to update {}: {}", package_name, e);
+                }
+            }
+
+            // Remove from updating set
+            updating_packages.write().await.remove(&package_name);
+
+            // Clear status after a delay
+            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+            *update_status.write().await = String::new();
+        }
+    }
+
+    /// Update every outdated package that needs no confirmation, lowest risk first, one at a
+    /// time. High-risk updates aren't run: they're queued in triage to be reviewed one by one.
+    pub fn update_all_outdated(&mut self) {
+        let updating_packages = Arc::clone(&self.updating_packages);
+        let update_status = Arc::clone(&self.update_status);
+        let packages = Arc::clone(&self.packages);
+
+        let mut queue: Vec<(Package, Risk)> = self
+            .packages
+            .blocking_read()
+            .iter()
+            .filter(|p| p.is_outdated && self.package_flags.can_update_unattended(p))
+            .map(|p| (p.clone(), self.risk_of(p)))
+            .collect();
+        queue.sort_by_key(|(p, risk)| (risk.score, p.name.clone()));
+        let (queue, risky): (Vec<_>, Vec<_>) = queue
+            .into_iter()
+            .partition(|(_, risk)| risk.level() < RiskLevel::High);
+        let risky: Vec<(PackageManager, String)> = risky
+            .into_iter()
+            .map(|(p, _)| (p.manager, p.name))
+            .collect();
+        let held_back = risky.len();
+        if !risky.is_empty() {
+            println!("[APP] Holding {} high-risk updates for triage", held_back);
+            self.triage_queue = risky;
+            self.triage_index = 0;
+            self.show_triage = true;
+        }
+
+        self.runtime.spawn(async move {
+            *update_status.write().await = "Updating all outdated packages...".to_string();
+
+            let mut failed = Vec::new();
+            let mut touched: Vec<PackageManager> = Vec::new();
+            for (pkg, _) in queue {
+                let name = pkg.name.clone();
+                updating_packages.write().await.insert(name.clone());
+                *update_status.write().await = format!("Updating {}...", name);
+                let result = crate::operations::update_package(&pkg.manager, name.clone()).await;
+                JournalEntry::new(JournalAction::Update, pkg.manager.clone(), name.clone())
+                    .with_versions(Some(pkg.installed_version.clone()), pkg.latest_version)
+                    .finish(&result);
+                updating_packages.write().await.remove(&name);
+                if let Err(e) = result {
+                    eprintln!("[ERROR] Failed to update {}: {}", name, e);
+                    failed.push(name);
+                }
+                if !touched.contains(&pkg.manager) {
+                    touched.push(pkg.manager);
+                }
+            }
+
+            // Refresh the updated managers' rows
+            for manager in &touched {
+                refresh_manager(&packages, manager).await;
+            }
+
+            let mut status = if failed.is_empty() {
+                println!("[INFO] Successfully updated all packages");
+                "All packages updated".to_string()
+            } else {
+                format!("Failed to update: {}", failed.join(", "))
+            };
+            if held_back > 0 {
+                status.push_str(&format!(
+                    " ({} high-risk updates left for review in Triage)",
+                    held_back
+                ));
+            }
+            *update_status.write().await = status;
+
+            // Clear status after a delay
+            tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+            *update_status.write().await = String::new();
+        });
+    }
+
+    /// Force a metadata refresh (`brew update`) for every manager that has one, then rescan
+    pub fn refresh_metadata_now(&mut self) {
+        let managers: Vec<PackageManager> = self
+            .available_managers
+            .iter()
+            .filter(|m| crate::managers::metadata::has_local_metadata(m))
+            .cloned()
+            .collect();
+        let update_status = Arc::clone(&self.update_status);
+        let refresh_signal = Arc::clone(&self.refresh_signal);
+
+        self.runtime.spawn(async move {
+            *update_status.write().await = "Refreshing package metadata...".to_string();
+            for manager in &managers {
+                if let Err(e) = crate::managers::metadata::refresh_metadata(manager).await {
+                    eprintln!(
+                        "[ERROR] Failed to refresh {} metadata: {}",
+                        manager.name(),
+                        e
+                    );
+                    *update_status.write().await =
+                        format!("Failed to refresh {} metadata: {}", manager.name(), e);
+                }
+            }
+            refresh_signal.store(true, Ordering::Relaxed);
+
+            // Clear status after a delay
+            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+            *update_status.write().await = String::new();
+        });
+    }
+
+    /// Forget every cached API response (memory and disk) and rescan from scratch
+    pub fn clear_cache(&mut self) {
+        let update_status = Arc::clone(&self.update_status);
+        let refresh_signal = Arc::clone(&self.refresh_signal);
+        let size_units = self.size_units;
+
+        self.runtime.spawn(async move {
+            let freed = tokio::task::spawn_blocking(crate::utils::cache::clear_cache)
+                .await
+                .unwrap_or(0);
+            // Rescan only once the old entries are gone
+            refresh_signal.store(true, Ordering::Relaxed);
+            *update_status.write().await = format!(
+                "Cleared cache ({})",
+                crate::utils::format::format_size(freed, size_units)
+            );
+
+            // Clear status after a delay
+            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+            *update_status.write().await = String::new();
+        });
+    }
+
+    /// Update the package manager itself (`brew update`, `npm install -g npm@latest`, ...)
+    pub fn self_update_manager(&mut self, manager: PackageManager) {
+        let update_status = Arc::clone(&self.update_status);
+        let manager_health = Arc::clone(&self.manager_health);
+
+        self.runtime.spawn(async move {
+            *update_status.write().await = format!("Updating {} itself...", manager.name());
+
+            let result = crate::operations::self_update(&manager).await;
+            JournalEntry::new(
+                JournalAction::SelfUpdate,
+                manager.clone(),
+                manager.command().to_string(),
+            )
+            .finish(&result);
+
+            *update_status.write().await = match &result {
+                Ok(()) => format!("{} updated", manager.name()),
+                Err(e) => format!("Failed to update {}: {}", manager.name(), e),
+            };
+            if result.is_ok() {
+                crate::utils::cache::invalidate(&format!("self_latest:{}", manager.command()));
+                crate::managers::health::probe_all(std::slice::from_ref(&manager), manager_health)
+                    .await;
+            }
+
+            // Clear status after a delay
+            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+            *update_status.write().await = String::new();
+        });
+    }
+
+    /// Open the "Update Everything" window with a fresh plan (unless a run is in progress)
+    pub fn open_update_everything(&mut self) {
+        self.show_update_everything = true;
+
+        let mut run = self.update_everything.blocking_write();
+        if run.running {
+            return;
+        }
+        // Pinned, ignored and critical packages stay where they are
+        let packages: Vec<Package> = self
+            .packages
+            .blocking_read()
+            .iter()
+            .filter(|p| self.package_flags.can_update_unattended(p))
+            .cloned()
+            .collect();
+        *run = UpdateEverythingRun {
+            steps: crate::operations::update_everything::plan(
+                &self.available_managers,
+                &packages,
+                |p| self.risk_of(p).score,
+            ),
+            ..Default::default()
+        };
+    }
+
+    /// Open the batch reinstall of tools stranded by a Python/Node upgrade
+    pub fn open_runtime_tools(&mut self) {
+        self.show_runtime_tools = true;
+
+        let mut run = self.runtime_reinstall.blocking_write();
+        if run.running {
+            return;
+        }
+        *run = UpdateEverythingRun {
+            steps: crate::operations::runtime_tools::plan(&self.stranded_tools.blocking_read()),
+            ..Default::default()
+        };
+    }
+
+    pub fn start_runtime_reinstall(&mut self) {
+        let run = Arc::clone(&self.runtime_reinstall);
+        {
+            let mut state = run.blocking_write();
+            if state.running {
+                return;
+            }
+            state.running = true;
+        }
+
+        let update_status = Arc::clone(&self.update_status);
+        let refresh_signal = Arc::clone(&self.refresh_signal);
+        self.runtime.spawn(async move {
+            *update_status.write().await = "Reinstalling tools for the new runtime...".to_string();
+            crate::operations::update_everything::execute(run).await;
+            *update_status.write().await = String::new();
+
+            // Reinstalled tools show up under the current runtime after a rescan
+            refresh_signal.store(true, Ordering::Relaxed);
+        });
+    }
+
+    pub fn start_update_everything(&mut self) {
+        let run = Arc::clone(&self.update_everything);
+        {
+            let mut state = run.blocking_write();
+            if state.running {
+                return;
+            }
+            state.running = true;
+        }
+
+        let update_status = Arc::clone(&self.update_status);
+        let refresh_signal = Arc::clone(&self.refresh_signal);
+        self.runtime.spawn(async move {
+            *update_status.write().await = "Updating everything...".to_string();
+            crate::operations::update_everything::execute(run).await;
+            *update_status.write().await = String::new();
+
+            // New versions are only visible after a rescan
+            refresh_signal.store(true, Ordering::Relaxed);
+        });
+    }
+
+    /// Reinstall the version an update replaced, recorded in the journal
+    pub fn rollback(&mut self, entry: JournalEntry) {
+        let Some(version) = entry.from_version.clone() else {
+            return;
+        };
+        let updating_packages = Arc::clone(&self.updating_packages);
+        let update_status = Arc::clone(&self.update_status);
+        let run = Arc::clone(&self.update_everything);
+
+        self.runtime.spawn(async move {
+            updating_packages
+                .write()
+                .await
+                .insert(entry.package.clone());
+            *update_status.write().await =
+                format!("Rolling back {} to {}...", entry.package, version);
+
+            let result = crate::operations::install_version(
+                &entry.manager,
+                entry.package.clone(),
+                version.clone(),
+            )
+            .await;
+            let rollback = JournalEntry::new(
+                JournalAction::Rollback,
+                entry.manager,
+                entry.package.clone(),
+            )
+            .with_versions(entry.to_version, Some(version.clone()))
+            .finish(&result);
+
+            *update_status.write().await = match &result {
+                Ok(()) => format!("{} rolled back to {}", entry.package, version),
+                Err(e) => format!("Failed to roll back {}: {}", entry.package, e),
+            };
+            run.write().await.journal.push(rollback);
+            updating_packages.write().await.remove(&entry.package);
+
+            // Clear status after a delay
+            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+            *update_status.write().await = String::new();
+        });
+    }
+
+    /// Put back a version removed from its own row, at that version
+    pub fn restore_version(&mut self, pkg: &Package) {
+        let updating_packages = Arc::clone(&self.updating_packages);
+        let update_status = Arc::clone(&self.update_status);
+        let removed_packages = Arc::clone(&self.removed_packages);
+        let (name, manager) = (pkg.name.clone(), pkg.manager.clone());
+        let version = pkg.installed_version.clone();
+        let row = pkg.row_id();
+
+        self.runtime.spawn(async move {
+            updating_packages.write().await.insert(row.clone());
+            *update_status.write().await = format!("Reinstalling {}...", row);
+
+            let result =
+                crate::operations::install_version(&manager, name.clone(), version.clone()).await;
+            JournalEntry::new(JournalAction::Install, manager, name)
+                .with_versions(None, Some(version))
+                .finish(&result);
+
+            *update_status.write().await = match &result {
+                Ok(()) => {
+                    removed_packages.write().await.remove(&row);
+                    format!("{} reinstalled", row)
+                }
+                Err(e) => format!("Failed to reinstall {}: {}", row, e),
+            };
+            updating_packages.write().await.remove(&row);
+
+            // Clear status after a delay
+            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+            *update_status.write().await = String::new();
+        });
+    }
+
+    /// Look up a package's description the first time its row is shown or it's selected
+    pub fn request_description(&mut self, pkg: &Package) {
+        if pkg.description.is_some()
+            || !crate::managers::has_description_lookup(&pkg.manager)
+            || !self.settings.descriptions.is_enabled(&pkg.manager)
+            || !self.description_requests.insert(pkg.key())
+        {
+            return;
+        }
+
+        let packages = Arc::clone(&self.packages);
+        let limiter = Arc::clone(&self.description_limiter);
+        let source = self.settings.descriptions.source(&pkg.manager);
+        let cache_key = format!("description:{}", pkg.key());
+        let manager = pkg.manager.clone();
+        let name = pkg.name.clone();
+
+        self.runtime.spawn(async move {
+            let desc = match crate::utils::cache::get_cached::<String>(&cache_key) {
+                Some(desc) => desc,
+                None => {
+                    let Ok(_permit) = limiter.acquire().await else {
+                        return;
+                    };
+                    let _activity = crate::utils::activity::start("Description lookup");
+                    let Some(desc) =
+                        crate::managers::fetch_description(&manager, &name, source).await
+                    else {
+                        return;
+                    };
+                    crate::utils::cache::set_cached(cache_key, &desc, 7 * 24 * 3600);
+                    desc
+                }
+            };
+            if let Some(pkg) = packages
+                .write()
+                .await
+                .iter_mut()
+                .find(|p| p.manager == manager && p.name == name)
+            {
+                pkg.description = Some(desc);
+            }
+        });
+    }
+
+    /// Publish dates of a package's installed and latest versions, looked up in the
+    /// background the first time they're asked for; None until they arrive
+    pub fn release_dates(&mut self, pkg: &Package) -> Option<ReleaseDates> {
+        if !crate::managers::releases::has_release_dates(&pkg.manager) {
+            return None;
+        }
+        let key = format!(
+            "{}:{}:{}",
+            pkg.key(),
+            pkg.installed_version,
+            pkg.latest_version.as_deref().unwrap_or("")
+        );
+        if let Some(dates) = self.release_dates.blocking_read().get(&key) {
+            return Some(dates.clone());
+        }
+        if !self.release_requests.insert(key.clone()) {
+            return None;
+        }
+
+        let release_dates = Arc::clone(&self.release_dates);
+        let limiter = Arc::clone(&self.description_limiter);
+        let manager = pkg.manager.clone();
+        let name = pkg.name.clone();
+        let installed = pkg.installed_version.clone();
+        let latest = pkg.latest_version.clone();
+        self.runtime.spawn(async move {
+            let cache_key = format!("released:{}", key);
+            let dates = match crate::utils::cache::get_cached::<ReleaseDates>(&cache_key) {
+                Some(dates) => dates,
+                None => {
+                    let Ok(_permit) = limiter.acquire().await else {
+                        return;
+                    };
+                    let _activity = crate::utils::activity::start("Release date lookup");
+                    match crate::managers::releases::fetch_release_dates(
+                        &manager,
+                        &name,
+                        &installed,
+                        latest.as_deref(),
+                    )
+                    .await
+                    {
+                        Some(dates) => {
+                            // Publish dates don't change
+                            crate::utils::cache::set_cached(cache_key, &dates, 30 * 24 * 3600);
+                            dates
+                        }
+                        None => ReleaseDates::default(),
+                    }
+                }
+            };
+            release_dates.write().await.insert(key, dates);
+        });
+        None
+    }
+
+    /// When the manager's packages were last listed, else when the last scan finished
+    pub fn listed_at(&self, manager: &PackageManager) -> Option<DateTime<Utc>> {
+        self.metadata_times
+            .blocking_read()
+            .get(manager)
+            .copied()
+            .or(*self.last_scan.blocking_read())
+    }
+
+    /// Select a package for the detail panel; clicking it again closes the panel
+    pub fn select_package(&mut self, pkg: &Package) {
+        if self.is_selected(pkg) {
+            self.selected_package = None;
+        } else {
+            self.selected_package = Some((pkg.manager.clone(), pkg.row_id()));
+            self.details_hidden = false;
+        }
+    }
+
+    /// Show what a link points at: its view, or the package selected with its details
+    /// open. Filters that could hide it are cleared.
+    pub fn open_link(&mut self, link: &DeepLink) {
+        println!("[LINK] Opening {:?}", link);
+        self.search_query.clear();
+        match link {
+            DeepLink::Package { manager, name } => {
+                self.view = View::All;
+                self.show_outdated_only = false;
+                self.selected_package = Some((manager.clone(), name.clone()));
+                self.details_hidden = false;
+            }
+            DeepLink::View {
+                view,
+                outdated_only,
+            } => {
+                self.view = view.clone();
+                self.show_outdated_only = *outdated_only;
+            }
+        }
+    }
+
+    /// The selection names a row (`Package::row_id`); a bare name from a link or the
+    /// palette selects the package whichever version is installed
+    pub fn is_selected(&self, pkg: &Package) -> bool {
+        self.selected_package
+            .as_ref()
+            .is_some_and(|(manager, row)| {
+                *manager == pkg.manager && (*row == pkg.row_id() || *row == pkg.name)
+            })
+    }
+
+    /// Current data for the selected package (it may have been updated since it was clicked)
+    pub fn detail_package(&self) -> Option<Package> {
+        if self.details_hidden {
+            return None;
+        }
+        let (manager, row) = self.selected_package.as_ref()?;
+        let packages = self.packages.blocking_read();
+        let of_manager = || packages.iter().filter(|p| p.manager == *manager);
+        of_manager()
+            .find(|p| p.row_id() == *row)
+            .or_else(|| of_manager().find(|p| p.name == *row))
+            .cloned()
+    }
+
+    /// Cmd-click: add or remove a row from the multi-selection the triage keys act on
+    pub fn toggle_marked(&mut self, pkg: &Package) {
+        let key = (pkg.manager.clone(), pkg.row_id());
+        if !self.marked_packages.remove(&key) {
+            self.marked_packages.insert(key);
+        }
+    }
+
+    pub fn is_marked(&self, pkg: &Package) -> bool {
+        self.marked_packages
+            .contains(&(pkg.manager.clone(), pkg.row_id()))
+    }
+
+    /// Rows the triage keys act on: the marked ones, or else the selected one
+    pub fn triage_targets(&self) -> Vec<Package> {
+        let packages = self.packages.blocking_read();
+        packages
+            .iter()
+            .filter(|p| {
+                if self.marked_packages.is_empty() {
+                    self.is_selected(p)
+                } else {
+                    self.is_marked(p)
                 }
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Move the selection up/down through the rows currently shown
+    pub fn move_selection(&mut self, rows: &[Package], delta: isize) {
+        if rows.is_empty() {
+            return;
+        }
+        let current = rows.iter().position(|p| self.is_selected(p));
+        let next = match current {
+            Some(idx) => (idx as isize + delta).clamp(0, rows.len() as isize - 1) as usize,
+            None => 0,
+        };
+        self.selected_package = Some((rows[next].manager.clone(), rows[next].row_id()));
+    }
+
+    /// Pin or unpin, recording it in the package's history
+    pub fn toggle_pinned(&mut self, pkg: &Package) {
+        self.package_flags.toggle_pinned(pkg);
+        let action = if self.package_flags.is_pinned(pkg) {
+            JournalAction::Pin
+        } else {
+            JournalAction::Unpin
+        };
+        JournalEntry::new(action, pkg.manager.clone(), pkg.name.clone())
+            .with_versions(Some(pkg.installed_version.clone()), None)
+            .finish(&Ok(()));
+        self.history_cache = None;
+    }
+
+    /// Follow a release channel (None: stable releases), then rescan so the Latest
+    /// column follows it
+    pub fn set_channel(&mut self, pkg: &Package, channel: Option<String>) {
+        self.package_flags.set_channel(pkg, channel);
+        self.request_refresh();
+    }
+
+    /// The selected package's journal entries, reread every few seconds so
+    /// operations finishing in the background show up
+    pub fn package_history(&mut self, pkg: &Package) -> Vec<JournalEntry> {
+        let key = pkg.key();
+        if let Some((cached, loaded, entries)) = &self.history_cache {
+            if *cached == key && loaded.elapsed() < std::time::Duration::from_secs(10) {
+                return entries.clone();
             }
+        }
+        let entries = crate::operations::journal::package_history(&pkg.manager, &pkg.name);
+        self.history_cache = Some((key, std::time::Instant::now(), entries.clone()));
+        entries
+    }
 
-            // Remove from updating set
-            updating_packages.write().await.remove(&package_name);
+    pub fn toggle_pin_targets(&mut self) {
+        for pkg in self.triage_targets() {
+            self.toggle_pinned(&pkg);
+        }
+    }
 
-            // Clear status after a delay
-            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
-            *update_status.write().await = String::new();
-        });
+    pub fn toggle_ignore_targets(&mut self) {
+        for pkg in self.triage_targets() {
+            self.package_flags.toggle_ignored(&pkg);
+        }
     }
 
-    pub fn update_all_outdated(&mut self) {
-        let updating_packages = Arc::clone(&self.updating_packages);
-        let update_status = Arc::clone(&self.update_status);
-        let packages = Arc::clone(&self.packages);
+    /// Update the triage targets that aren't pinned, ignored or already current
+    pub fn update_targets(&mut self) {
+        for pkg in self.triage_targets() {
+            if self.package_flags.can_update(&pkg) && !self.is_updating(&pkg.name) {
+                self.update_package(pkg.name, pkg.manager);
+            }
+        }
+    }
 
+    /// Which copy of each command wins on PATH, analyzed fresh each time it's opened
+    pub fn open_path_report(&mut self) {
+        self.show_path_report = true;
+        let packages = self.packages.blocking_read().clone();
+        let path_report = Arc::clone(&self.path_report);
+        *path_report.blocking_write() = None;
         self.runtime.spawn(async move {
-            *update_status.write().await = "Updating all outdated packages...".to_string();
+            let report = crate::audit::shadowing::analyze_login_path(&packages).await;
+            *path_report.write().await = Some(report);
+        });
+    }
+
+    /// Queue every outdated package that isn't pinned or ignored, vulnerable ones first
+    pub fn open_triage(&mut self) {
+        let mut queue: Vec<Package> = self
+            .packages
+            .blocking_read()
+            .iter()
+            .filter(|p| self.package_flags.can_update(p))
+            .cloned()
+            .collect();
+        queue.sort_by_cached_key(|p| {
+            (
+                std::cmp::Reverse(p.vulnerabilities.len()),
+                self.risk_of(p).score,
+                p.name.clone(),
+            )
+        });
+        self.triage_queue = queue.into_iter().map(|p| (p.manager, p.name)).collect();
+        self.triage_index = 0;
+        self.show_triage = true;
+    }
 
-            let result = crate::managers::homebrew_fast::update_all_packages().await;
+    /// Package under review, with current data
+    pub fn triage_current(&self) -> Option<Package> {
+        let (manager, name) = self.triage_queue.get(self.triage_index)?;
+        self.packages
+            .blocking_read()
+            .iter()
+            .find(|p| p.manager == *manager && p.name == *name)
+            .cloned()
+    }
 
-            match result {
-                Ok(_) => {
-                    println!("[INFO] Successfully updated all packages");
-                    *update_status.write().await = "All packages updated".to_string();
+    pub fn triage_next(&mut self) {
+        self.triage_index += 1;
+    }
 
-                    // Refresh the package list
-                    if let Ok(mut homebrew_packages) =
-                        crate::managers::homebrew_fast::list_homebrew_packages_fast().await
-                    {
-                        if let Ok(()) =
-                            crate::managers::homebrew_fast::check_outdated_packages_fast(
-                                &mut homebrew_packages,
-                            )
-                            .await
-                        {
-                            *packages.write().await = homebrew_packages;
-                        }
-                    }
-                }
-                Err(e) => {
-                    eprintln!("[ERROR] Failed to update all packages: {}", e);
-                    *update_status.write().await = format!("Failed to update all: {}", e);
-                }
-            }
+    /// Update risk, using release notes when triage has fetched them
+    pub fn risk_of(&self, pkg: &Package) -> Risk {
+        let changelogs = self.changelogs.blocking_read();
+        let changelog = match changelogs.get(&pkg.key()) {
+            Some(ChangelogState::Ready(Some(changelog))) => Some(changelog),
+            _ => None,
+        };
+        crate::models::risk::assess(pkg, self.category_overrides.category_of(pkg), changelog)
+    }
 
-            // Clear updating set
-            updating_packages.write().await.clear();
+    /// Start fetching release notes unless they're loaded or loading already
+    pub fn load_changelog(&mut self, pkg: &Package) {
+        let key = pkg.key();
+        {
+            let mut changelogs = self.changelogs.blocking_write();
+            if changelogs.contains_key(&key) {
+                return;
+            }
+            changelogs.insert(key.clone(), ChangelogState::Loading);
+        }
 
-            // Clear status after a delay
-            tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-            *update_status.write().await = String::new();
+        let changelogs = Arc::clone(&self.changelogs);
+        let pkg = pkg.clone();
+        self.runtime.spawn(async move {
+            let state = match crate::audit::fetch_changelog(&pkg).await {
+                Ok(changelog) => ChangelogState::Ready(changelog),
+                Err(e) => {
+                    eprintln!("[ERROR] Failed to fetch changelog for {}: {}", pkg.name, e);
+                    ChangelogState::Failed(e.to_string())
+                }
+            };
+            changelogs.write().await.insert(key, state);
         });
     }
 
@@ -386,8 +2412,38 @@ impl DepMgrApp {
             .contains(package_name)
     }
 
-    pub fn is_removed(&self, package_name: &str) -> bool {
-        self.removed_packages.blocking_read().contains(package_name)
+    /// Whether this row was removed in this session (its row stays, with Reinstall)
+    pub fn is_removed(&self, pkg: &Package) -> bool {
+        self.removed_packages
+            .blocking_read()
+            .contains(&pkg.row_id())
+    }
+
+    /// An update of the package, or an operation on just this row, is running
+    pub fn is_row_busy(&self, pkg: &Package) -> bool {
+        let updating = self.updating_packages.blocking_read();
+        updating.contains(&pkg.name) || updating.contains(&pkg.row_id())
+    }
+
+    /// (manager, data timestamp) pairs in sidebar order
+    pub fn metadata_ages(&self) -> Vec<(PackageManager, DateTime<Utc>)> {
+        let times = self.metadata_times.blocking_read();
+        self.available_managers
+            .iter()
+            .filter_map(|m| times.get(m).map(|t| (m.clone(), *t)))
+            .collect()
+    }
+
+    pub fn manager_health(&self, manager: &PackageManager) -> ManagerHealth {
+        self.manager_health
+            .blocking_read()
+            .get(manager)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn last_scan_time(&self) -> Option<DateTime<Utc>> {
+        *self.last_scan.blocking_read()
     }
 
     pub fn get_update_status(&self) -> String {
@@ -395,6 +2451,12 @@ impl DepMgrApp {
     }
 
     pub fn reinstall_package(&mut self, package_name: String, manager: PackageManager) {
+        if !self.hold_for_conflicts(HeldAction::Reinstall, &package_name, &manager) {
+            self.start_reinstall(package_name, manager);
+        }
+    }
+
+    fn start_reinstall(&mut self, package_name: String, manager: PackageManager) {
         let updating_packages = Arc::clone(&self.updating_packages);
         let update_status = Arc::clone(&self.update_status);
         let removed_packages = Arc::clone(&self.removed_packages);
@@ -404,20 +2466,13 @@ impl DepMgrApp {
             updating_packages.write().await.insert(package_name.clone());
             *update_status.write().await = format!("Reinstalling {}...", package_name);
 
-            let pkg_name = package_name.clone();
-            let result = match manager {
-                PackageManager::Homebrew => {
-                    crate::managers::homebrew_fast::install_package(pkg_name).await
-                }
-                PackageManager::Npm => crate::managers::npm::install_npm_package(pkg_name).await,
-                PackageManager::Cargo => {
-                    crate::managers::cargo::install_cargo_package(pkg_name).await
-                }
-                PackageManager::Pip => crate::managers::pip::install_pip_package(pkg_name).await,
-                _ => Err(anyhow::anyhow!(
-                    "Reinstall not implemented for this package manager"
-                )),
-            };
+            let result = crate::operations::install_package(&manager, package_name.clone()).await;
+            JournalEntry::new(
+                JournalAction::Install,
+                manager.clone(),
+                package_name.clone(),
+            )
+            .finish(&result);
 
             match result {
                 Ok(_) => {
@@ -444,54 +2499,437 @@ impl DepMgrApp {
         });
     }
 
-    pub fn uninstall_package(&mut self, package_name: String, manager: PackageManager) {
+    /// Swap an Intel-only build running under Rosetta for a native arm64 one
+    pub fn reinstall_native(&mut self, package_name: String, manager: PackageManager) {
         let updating_packages = Arc::clone(&self.updating_packages);
         let update_status = Arc::clone(&self.update_status);
-        let removed_packages = Arc::clone(&self.removed_packages);
+        let packages = Arc::clone(&self.packages);
 
         self.runtime.spawn(async move {
-            // Mark as updating/processing
             updating_packages.write().await.insert(package_name.clone());
-            *update_status.write().await = format!("Removing {}...", package_name);
+            *update_status.write().await = format!("Reinstalling {} as native...", package_name);
+
+            let result = crate::operations::reinstall_native(&manager, package_name.clone()).await;
+            JournalEntry::new(
+                JournalAction::Install,
+                manager.clone(),
+                package_name.clone(),
+            )
+            .finish(&result);
+
+            match result {
+                Ok(()) => {
+                    if let Some(pkg) = packages
+                        .write()
+                        .await
+                        .iter_mut()
+                        .find(|p| p.name == package_name && p.manager == manager)
+                    {
+                        pkg.arch = Some(crate::models::BinaryArch::Arm64);
+                        pkg.needs_rosetta = false;
+                    }
+                    *update_status.write().await =
+                        format!("{} reinstalled as native arm64", package_name);
+                }
+                Err(e) => {
+                    eprintln!("[APP] Failed to reinstall {} natively: {}", package_name, e);
+                    *update_status.write().await =
+                        format!("Failed to reinstall {}: {}", package_name, e);
+                }
+            }
+
+            updating_packages.write().await.remove(&package_name);
+            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+            *update_status.write().await = String::new();
+        });
+    }
+
+    /// Repair a Homebrew formula's dangling links by linking the installed keg again
+    pub fn relink_package(&mut self, pkg: &Package) {
+        let pkg = pkg.clone();
+        let updating_packages = Arc::clone(&self.updating_packages);
+        let update_status = Arc::clone(&self.update_status);
+        let packages = Arc::clone(&self.packages);
+
+        self.runtime.spawn(async move {
+            updating_packages.write().await.insert(pkg.name.clone());
+            *update_status.write().await = format!("Relinking {}...", pkg.name);
 
-            let pkg_name = package_name.clone();
-            let result = match manager {
-                PackageManager::Homebrew => {
-                    crate::managers::homebrew_fast::uninstall_package(pkg_name).await
+            let result = crate::managers::homebrew_fast::relink_package(pkg.name.clone()).await;
+            let result = match result {
+                Ok(()) => crate::utils::broken::forget(&pkg).await,
+                Err(e) => Err(e),
+            };
+            *update_status.write().await = match result {
+                Ok(()) => {
+                    if let Some(row) = packages
+                        .write()
+                        .await
+                        .iter_mut()
+                        .find(|p| p.key() == pkg.key())
+                    {
+                        row.broken.clear();
+                    }
+                    format!("{} relinked", pkg.name)
                 }
-                PackageManager::Npm => crate::managers::npm::uninstall_npm_package(pkg_name).await,
-                PackageManager::Cargo => {
-                    crate::managers::cargo::uninstall_cargo_package(pkg_name).await
+                Err(e) => format!("Failed to relink {}: {}", pkg.name, e),
+            };
+
+            updating_packages.write().await.remove(&pkg.name);
+            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+            *update_status.write().await = String::new();
+        });
+    }
+
+    /// Clean up broken binaries instead of repairing them; packages that only existed
+    /// as broken links disappear from the list
+    pub fn forget_broken(&mut self, pkg: &Package) {
+        let pkg = pkg.clone();
+        let update_status = Arc::clone(&self.update_status);
+        let packages = Arc::clone(&self.packages);
+
+        self.runtime.spawn(async move {
+            *update_status.write().await = match crate::utils::broken::forget(&pkg).await {
+                Ok(()) => {
+                    let mut packages = packages.write().await;
+                    if pkg.installed_version == crate::utils::broken::MISSING_VERSION
+                        || pkg.manager == PackageManager::Cargo
+                    {
+                        packages.retain(|p| p.key() != pkg.key());
+                    } else if let Some(row) = packages.iter_mut().find(|p| p.key() == pkg.key()) {
+                        row.broken.clear();
+                    }
+                    format!("Removed broken binaries of {}", pkg.name)
                 }
-                PackageManager::Pip => crate::managers::pip::uninstall_pip_package(pkg_name).await,
-                _ => Err(anyhow::anyhow!(
-                    "Uninstall not implemented for this package manager"
-                )),
+                Err(e) => format!("Failed to clean up {}: {}", pkg.name, e),
             };
 
+            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+            *update_status.write().await = String::new();
+        });
+    }
+
+    /// Remove this row's package; where versions have rows of their own, just this version
+    pub fn uninstall_package(&mut self, pkg: &Package) {
+        let (name, manager) = (pkg.name.clone(), pkg.manager.clone());
+        let version = Some(pkg.installed_version.as_str());
+        if !self.hold_if_critical(HeldAction::Uninstall, &name, &manager, version) {
+            self.start_uninstall(name, manager, version.map(String::from));
+        }
+    }
+
+    fn start_uninstall(
+        &mut self,
+        package_name: String,
+        manager: PackageManager,
+        installed_version: Option<String>,
+    ) {
+        let updating_packages = Arc::clone(&self.updating_packages);
+        let update_status = Arc::clone(&self.update_status);
+        let removed_packages = Arc::clone(&self.removed_packages);
+        let row = manager.row_id(
+            &package_name,
+            installed_version.as_deref().unwrap_or_default(),
+        );
+
+        self.runtime.spawn(async move {
+            // Mark as updating/processing
+            updating_packages.write().await.insert(row.clone());
+            *update_status.write().await = format!("Removing {}...", row);
+
+            let result = crate::operations::uninstall_package(
+                &manager,
+                package_name.clone(),
+                installed_version.clone(),
+            )
+            .await;
+            JournalEntry::new(
+                JournalAction::Uninstall,
+                manager.clone(),
+                package_name.clone(),
+            )
+            .with_versions(installed_version, None)
+            .finish(&result);
+
             match result {
                 Ok(_) => {
-                    println!("[APP] Successfully removed {}", package_name);
+                    println!("[APP] Successfully removed {}", row);
 
                     // Mark as removed (stays in table with "Reinstall" button)
-                    removed_packages.write().await.insert(package_name.clone());
+                    removed_packages.write().await.insert(row.clone());
 
                     *update_status.write().await =
-                        format!("{} removed (click Reinstall to undo)", package_name);
+                        format!("{} removed (click Reinstall to undo)", row);
                 }
                 Err(e) => {
-                    eprintln!("[APP] Failed to remove {}: {}", package_name, e);
-                    *update_status.write().await =
-                        format!("Failed to remove {}: {}", package_name, e);
+                    eprintln!("[APP] Failed to remove {}: {}", row, e);
+                    *update_status.write().await = format!("Failed to remove {}: {}", row, e);
                 }
             }
 
             // Remove from updating set
-            updating_packages.write().await.remove(&package_name);
+            updating_packages.write().await.remove(&row);
 
             // Clear status after a delay
             tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
             *update_status.write().await = String::new();
         });
     }
+
+    /// Upgrade every outdated font cask with a single `brew upgrade --cask`
+    pub fn update_fonts(&mut self) {
+        let fonts: Vec<(String, String, Option<String>)> = self
+            .packages
+            .blocking_read()
+            .iter()
+            .filter(|p| {
+                p.manager == PackageManager::Fonts && self.package_flags.can_update_unattended(p)
+            })
+            .map(|p| {
+                (
+                    p.name.clone(),
+                    p.installed_version.clone(),
+                    p.latest_version.clone(),
+                )
+            })
+            .collect();
+        if fonts.is_empty() {
+            return;
+        }
+        let updating_packages = Arc::clone(&self.updating_packages);
+        let update_status = Arc::clone(&self.update_status);
+        let packages = Arc::clone(&self.packages);
+
+        self.runtime.spawn(async move {
+            let names: Vec<String> = fonts.iter().map(|(name, _, _)| name.clone()).collect();
+            updating_packages
+                .write()
+                .await
+                .extend(names.iter().cloned());
+            *update_status.write().await = format!("Updating {} fonts...", names.len());
+
+            let result = crate::managers::fonts::update_fonts(names.clone()).await;
+            for (name, from, to) in fonts {
+                JournalEntry::new(JournalAction::Update, PackageManager::Fonts, name)
+                    .with_versions(Some(from), to)
+                    .finish(&result);
+            }
+
+            match result {
+                Ok(()) => {
+                    *update_status.write().await = format!("{} fonts updated", names.len());
+                    if let Ok(fonts) = crate::managers::fonts::list_font_casks().await {
+                        let mut all = packages.write().await;
+                        all.retain(|p| p.manager != PackageManager::Fonts);
+                        all.extend(fonts);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("[ERROR] Failed to update fonts: {}", e);
+                    *update_status.write().await = format!("Failed to update fonts: {}", e);
+                }
+            }
+
+            let mut updating = updating_packages.write().await;
+            for name in &names {
+                updating.remove(name);
+            }
+            drop(updating);
+
+            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+            *update_status.write().await = String::new();
+        });
+    }
+
+    /// Uninstall the font casks ticked in the table with a single `brew uninstall --cask`
+    pub fn remove_selected_fonts(&mut self) {
+        let names: Vec<String> = self.font_selection.drain().collect();
+        if names.is_empty() {
+            return;
+        }
+        let updating_packages = Arc::clone(&self.updating_packages);
+        let update_status = Arc::clone(&self.update_status);
+        let removed_packages = Arc::clone(&self.removed_packages);
+
+        self.runtime.spawn(async move {
+            updating_packages
+                .write()
+                .await
+                .extend(names.iter().cloned());
+            *update_status.write().await = format!("Removing {} fonts...", names.len());
+
+            let result = crate::managers::fonts::uninstall_fonts(names.clone()).await;
+            for name in &names {
+                JournalEntry::new(
+                    JournalAction::Uninstall,
+                    PackageManager::Fonts,
+                    name.clone(),
+                )
+                .finish(&result);
+            }
+
+            match result {
+                Ok(()) => {
+                    removed_packages.write().await.extend(names.iter().cloned());
+                    *update_status.write().await =
+                        format!("{} fonts removed (click Reinstall to undo)", names.len());
+                }
+                Err(e) => {
+                    eprintln!("[ERROR] Failed to remove fonts: {}", e);
+                    *update_status.write().await = format!("Failed to remove fonts: {}", e);
+                }
+            }
+
+            let mut updating = updating_packages.write().await;
+            for name in &names {
+                updating.remove(name);
+            }
+            drop(updating);
+
+            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+            *update_status.write().await = String::new();
+        });
+    }
+}
+
+/// Swap in a fresh listing for some managers, leaving the other managers' rows alone.
+/// Details that arrive later in the scan (descriptions, sizes, advisories) are kept
+/// from the old row meanwhile, so the table doesn't blank out while revalidating.
+fn replace_packages_of(all: &mut Vec<Package>, managers: &[PackageManager], fresh: Vec<Package>) {
+    let mut previous: std::collections::HashMap<String, Package> = std::collections::HashMap::new();
+    all.retain(|p| {
+        if managers.contains(&p.manager) {
+            previous.insert(p.key(), p.clone());
+            false
+        } else {
+            true
+        }
+    });
+    all.extend(fresh.into_iter().map(|mut pkg| {
+        if let Some(old) = previous.get(&pkg.key()) {
+            if pkg.description.is_none() {
+                pkg.description = old.description.clone();
+            }
+            if pkg.size.is_none() {
+                pkg.size = old.size;
+            }
+            if pkg.vulnerabilities.is_empty() && pkg.installed_version == old.installed_version {
+                pkg.vulnerabilities = old.vulnerabilities.clone();
+            }
+        }
+        pkg
+    }));
+}
+
+fn replace_packages(all: &mut Vec<Package>, manager: &PackageManager, fresh: Vec<Package>) {
+    replace_packages_of(all, std::slice::from_ref(manager), fresh);
+}
+
+/// Relist one manager after an update and swap its rows in, leaving the rest alone
+async fn refresh_manager(packages: &RwLock<Vec<Package>>, manager: &PackageManager) {
+    let result = match manager {
+        PackageManager::Homebrew => {
+            match crate::managers::homebrew_fast::list_homebrew_packages_fast().await {
+                Ok(mut fresh) => {
+                    crate::managers::homebrew_fast::check_outdated_packages_fast(&mut fresh)
+                        .await
+                        .map(|()| fresh)
+                }
+                Err(e) => Err(e),
+            }
+        }
+        PackageManager::Npm => match crate::managers::npm::list_npm_packages().await {
+            Ok(mut fresh) => {
+                let _ = crate::managers::npm::check_outdated_npm(&mut fresh).await;
+                Ok(fresh)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::Cargo => match crate::managers::cargo::list_cargo_packages().await {
+            Ok(mut fresh) => {
+                let _ = crate::managers::cargo::check_outdated_cargo(&mut fresh).await;
+                Ok(fresh)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::Pip => match crate::managers::pip::list_pip_packages().await {
+            Ok(mut fresh) => {
+                let _ = crate::managers::pip::check_outdated_pip(&mut fresh).await;
+                Ok(fresh)
+            }
+            Err(e) => Err(e),
+        },
+        other => match crate::managers::list_with_outdated(other).await {
+            Some(result) => result,
+            None => return,
+        },
+    };
+    match result {
+        Ok(fresh) => replace_packages(&mut *packages.write().await, manager, fresh),
+        Err(e) => eprintln!(
+            "[ERROR] Failed to relist {} packages: {}",
+            manager.name(),
+            e
+        ),
+    }
+}
+
+/// Score what a scan left listed; scores of this machine also go into the trend history
+async fn update_score(
+    packages: &RwLock<Vec<Package>>,
+    score: &RwLock<Option<EnvironmentScore>>,
+    record: bool,
+) {
+    let fresh = crate::report::score::score(&packages.read().await, &PackageFlags::load());
+    if let (Some(fresh), true) = (&fresh, record) {
+        crate::report::score::record_score(fresh.total());
+    }
+    *score.write().await = fresh;
+}
+
+/// Diff a finished scan against the inventory shown before it and reopen the
+/// "What changed" panel if anything did. Nothing to compare against on a first scan.
+/// Returns the changes, empty on a first scan.
+async fn publish_changes(
+    before: &[Package],
+    after: &[Package],
+    scan_changes: &RwLock<Vec<Change>>,
+    changes_dismissed: &AtomicBool,
+) -> Vec<Change> {
+    if before.is_empty() {
+        return Vec::new();
+    }
+    let changes = crate::models::diff::diff(before, after);
+    println!(
+        "[DEBUG] {} packages changed since the last scan",
+        changes.len()
+    );
+    changes_dismissed.store(changes.is_empty(), Ordering::Relaxed);
+    *scan_changes.write().await = changes.clone();
+    changes
+}
+
+/// Virtualenv packages (when enabled) and registry versions for projects' dependencies
+async fn check_projects(projects: &mut [crate::models::Project], venvs: bool) {
+    if venvs {
+        list_project_venvs(projects).await;
+    }
+    crate::managers::go_modules::check_project_modules(projects).await;
+    crate::managers::maven::check_project_artifacts(projects).await;
+    crate::managers::swift::check_project_packages(projects).await;
+    crate::managers::dart::check_project_packages(projects).await;
+}
+
+/// Fill in each project virtualenv's packages, a few venvs at a time
+async fn list_project_venvs(projects: &mut [crate::models::Project]) {
+    use futures::StreamExt;
+    let venvs = projects.iter_mut().flat_map(|p| p.venvs.iter_mut());
+    futures::stream::iter(venvs)
+        .for_each_concurrent(4, |venv| async move {
+            match crate::managers::pip::list_venv_packages(&venv.path).await {
+                Ok(packages) => venv.packages = packages,
+                Err(e) => venv.error = Some(e.to_string()),
+            }
+        })
+        .await;
 }
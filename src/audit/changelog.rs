@@ -0,0 +1,161 @@
+use crate::managers::github_releases::tag_version;
+use crate::models::{Package, PackageManager};
+use crate::utils::cache::{get_cached, set_cached};
+use crate::utils::http_client::create_http_client;
+use crate::utils::metrics::SendTimed;
+use crate::utils::registries::{crates_url, npm_registry, pypi_url};
+use crate::utils::version::compare_package_versions;
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::sync::LazyLock;
+
+static GITHUB_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"github\.com[/:]([A-Za-z0-9_.-]+)/([A-Za-z0-9_.-]+?)(?:\.git)?(?:[/#?]|$)").unwrap()
+});
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseNote {
+    pub tag: String,
+    pub body: String,
+}
+
+/// GitHub releases between the installed and the latest version, newest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Changelog {
+    pub releases_url: String,
+    pub notes: Vec<ReleaseNote>,
+}
+
+/// "owner/repo" from any GitHub URL: web, git, ssh or release download
+pub fn github_repo(url: &str) -> Option<String> {
+    let caps = GITHUB_RE.captures(url)?;
+    Some(format!("{}/{}", &caps[1], &caps[2]))
+}
+
+async fn get_json(url: &str) -> Result<serde_json::Value> {
+    let response = create_http_client()
+        .get(url)
+        // crates.io and GitHub reject requests without a User-Agent
+        .header("User-Agent", "depmgr")
+        .send_timed()
+        .await?;
+    if !response.status().is_success() {
+        return Err(anyhow!("{} returned {}", url, response.status()));
+    }
+    Ok(response.json().await?)
+}
+
+/// The package's GitHub repository ("owner/repo") according to its registry
+async fn source_repo(pkg: &Package) -> Result<Option<String>> {
+    let candidates: Vec<String> = match pkg.manager {
+        PackageManager::Homebrew => {
+            let json = get_json(&format!(
+                "https://formulae.brew.sh/api/formula/{}.json",
+                pkg.name
+            ))
+            .await?;
+            [&json["homepage"], &json["urls"]["stable"]["url"]]
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        }
+        PackageManager::Npm => {
+            let json =
+                get_json(&format!("{}/{}/latest", npm_registry(&pkg.name), pkg.name)).await?;
+            [
+                &json["repository"]["url"],
+                &json["repository"],
+                &json["homepage"],
+            ]
+            .iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect()
+        }
+        PackageManager::Cargo => {
+            let json = get_json(&crates_url(&pkg.name).await).await?;
+            [&json["crate"]["repository"], &json["crate"]["homepage"]]
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        }
+        PackageManager::Pip | PackageManager::Pipx => {
+            let json = get_json(&pypi_url(&pkg.name)).await?;
+            let mut urls: Vec<String> = json["info"]["project_urls"]
+                .as_object()
+                .into_iter()
+                .flatten()
+                .filter_map(|(_, v)| v.as_str().map(str::to_string))
+                .collect();
+            urls.extend(json["info"]["home_page"].as_str().map(str::to_string));
+            urls
+        }
+        _ => Vec::new(),
+    };
+    Ok(candidates.iter().find_map(|url| github_repo(url)))
+}
+
+/// Release notes for the versions an update would bring in. None when the
+/// package has no GitHub repository we can find.
+pub async fn fetch_changelog(pkg: &Package) -> Result<Option<Changelog>> {
+    let cache_key = format!(
+        "changelog:{}:{}:{}",
+        pkg.key(),
+        pkg.installed_version,
+        pkg.latest_version.as_deref().unwrap_or("")
+    );
+    if let Some(cached) = get_cached::<Option<Changelog>>(&cache_key) {
+        return Ok(cached);
+    }
+    if !crate::managers::backend::current().runs_commands() {
+        return Ok(None);
+    }
+
+    let Some(repo) = source_repo(pkg).await? else {
+        set_cached(
+            cache_key,
+            &None::<Changelog>,
+            crate::config::cache_ttls().release_ttl(),
+        );
+        return Ok(None);
+    };
+    println!("[CHANGELOG] Fetching releases of {} for {}", repo, pkg.name);
+
+    let releases = get_json(&format!(
+        "https://api.github.com/repos/{}/releases?per_page=30",
+        repo
+    ))
+    .await?;
+
+    let notes: Vec<ReleaseNote> = releases
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|r| !r["draft"].as_bool().unwrap_or(false))
+        .filter_map(|r| {
+            let tag = r["tag_name"].as_str()?;
+            let version = tag_version(tag);
+            let newer = compare_package_versions(&pkg.manager, version, &pkg.installed_version)
+                == Ordering::Greater;
+            let not_past_latest = pkg.latest_version.as_deref().is_none_or(|latest| {
+                compare_package_versions(&pkg.manager, version, latest) != Ordering::Greater
+            });
+            (newer && not_past_latest).then(|| ReleaseNote {
+                tag: tag.to_string(),
+                body: r["body"].as_str().unwrap_or("").trim().to_string(),
+            })
+        })
+        .collect();
+
+    let changelog = Some(Changelog {
+        releases_url: format!("https://github.com/{}/releases", repo),
+        notes,
+    });
+    set_cached(
+        cache_key,
+        &changelog,
+        crate::config::cache_ttls().release_ttl(),
+    );
+    Ok(changelog)
+}
@@ -0,0 +1,410 @@
+use crate::managers::health::{probe_all, HealthMap};
+use crate::models::{Package, PackageManager};
+use crate::utils::broken::{command_path, MISSING_VERSION};
+use crate::utils::format::{format_size, SizeUnits};
+use crate::utils::paths::home_dir;
+use std::path::{Path, PathBuf};
+
+/// How urgently a finding needs fixing; findings are listed in this order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl Severity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+        }
+    }
+}
+
+/// Something wrong with the setup, and how to fix it
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub severity: Severity,
+    pub problem: String,
+    pub fix: Option<String>,
+}
+
+impl Finding {
+    fn new(severity: Severity, problem: String, fix: Option<String>) -> Self {
+        Self {
+            severity,
+            problem,
+            fix,
+        }
+    }
+}
+
+fn self_update_hint(manager: &PackageManager) -> Option<&'static str> {
+    match manager {
+        PackageManager::Homebrew => Some("brew update"),
+        PackageManager::Npm => Some("npm install -g npm@latest"),
+        PackageManager::Cargo => Some("rustup update"),
+        PackageManager::Pip => Some("python3 -m pip install --upgrade pip"),
+        _ => None,
+    }
+}
+
+/// Whether we can create files in `dir`: ownership and ACLs decide, not the mode bits
+fn is_writable(dir: &Path) -> bool {
+    let probe = dir.join(format!(".depmgr-doctor-{}", std::process::id()));
+    match std::fs::File::create(&probe) {
+        Ok(_) => {
+            let _ = std::fs::remove_file(&probe);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Where installs with this manager write. That isn't always next to its own binary: a
+/// distro's /usr/bin/cargo still installs into ~/.cargo/bin.
+async fn install_dir(manager: &PackageManager, binary: &Path) -> Option<PathBuf> {
+    match manager {
+        PackageManager::Homebrew => binary.parent().map(Path::to_path_buf),
+        PackageManager::Npm => command_path("npm", &["prefix", "-g"]).await.map(|prefix| {
+            if cfg!(windows) {
+                prefix
+            } else {
+                prefix.join("lib")
+            }
+        }),
+        PackageManager::Gem => command_path("gem", &["environment", "gemdir"]).await,
+        PackageManager::Go => crate::managers::go::go_bin_dir().await.ok(),
+        PackageManager::Cargo => Some(crate::managers::cargo::cargo_home().join("bin")),
+        PackageManager::Pipx => Some(
+            std::env::var_os("PIPX_BIN_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| home_dir().join(".local").join("bin")),
+        ),
+        PackageManager::Uv => command_path("uv", &["tool", "dir", "--bin"]).await,
+        _ => None,
+    }
+}
+
+/// A directory the user should own. Anything outside home belongs to the system or
+/// another user, and chowning it breaks more than it fixes.
+fn is_user_dir(dir: &Path) -> bool {
+    let home = home_dir();
+    home.parent().is_some() && dir.starts_with(home)
+}
+
+/// How to make installs into `dir` work without sudo
+fn permission_fix(manager: &PackageManager, dir: &Path) -> Option<String> {
+    let chown = || format!("sudo chown -R $(whoami) {}", dir.display());
+    match manager {
+        PackageManager::Homebrew => Some("sudo chown -R $(whoami) $(brew --prefix)/*".to_string()),
+        PackageManager::Npm => Some(
+            "npm config set prefix ~/.npm-global, then put ~/.npm-global/bin on PATH".to_string(),
+        ),
+        PackageManager::Gem => {
+            Some("gem install --user-install, or a Ruby from rbenv, asdf or mise".to_string())
+        }
+        PackageManager::Cargo if is_user_dir(dir) => Some(chown()),
+        PackageManager::Cargo => Some(
+            "set CARGO_HOME to a directory in your home, or install Rust with rustup".to_string(),
+        ),
+        PackageManager::Go if is_user_dir(dir) => Some(chown()),
+        PackageManager::Go => Some("go env -w GOBIN=$HOME/go/bin".to_string()),
+        PackageManager::Pipx if is_user_dir(dir) => Some(chown()),
+        PackageManager::Pipx => {
+            Some("set PIPX_HOME and PIPX_BIN_DIR to directories in your home".to_string())
+        }
+        PackageManager::Uv if is_user_dir(dir) => Some(chown()),
+        PackageManager::Uv => {
+            Some("set UV_TOOL_DIR and UV_TOOL_BIN_DIR to directories in your home".to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Listing failures and what the diagnostics panel shows per manager: missing binaries,
+/// outdated managers, install directories the user can't write to
+async fn manager_findings(
+    managers: &[PackageManager],
+    failures: &[(PackageManager, String)],
+) -> Vec<Finding> {
+    let mut findings: Vec<Finding> = failures
+        .iter()
+        .map(|(manager, error)| {
+            let fix = match manager {
+                PackageManager::Homebrew => "brew doctor".to_string(),
+                _ => format!("run `{} --version` in a terminal", manager.command()),
+            };
+            Finding::new(
+                Severity::Error,
+                format!("{}: listing packages failed: {}", manager.name(), error),
+                Some(fix),
+            )
+        })
+        .collect();
+
+    let health: HealthMap = Default::default();
+    probe_all(managers, std::sync::Arc::clone(&health)).await;
+    let health = health.read().await;
+    for manager in managers {
+        let Some(health) = health.get(manager) else {
+            continue;
+        };
+        let Some(binary) = health.binary_path.as_deref() else {
+            findings.push(Finding::new(
+                Severity::Warning,
+                format!(
+                    "{}: detected, but `which {}` finds nothing",
+                    manager.name(),
+                    manager.command()
+                ),
+                Some("check the PATH your login shell sets up".to_string()),
+            ));
+            continue;
+        };
+        if let Some(latest) = &health.self_outdated {
+            findings.push(Finding::new(
+                Severity::Warning,
+                format!(
+                    "{} {} is outdated, {} is available",
+                    manager.name(),
+                    health.version.as_deref().unwrap_or("?"),
+                    latest
+                ),
+                self_update_hint(manager).map(String::from),
+            ));
+        }
+        // Binaries on another machine can't be probed from here
+        if !crate::utils::target::current_target().is_local() {
+            continue;
+        }
+        let Some(dir) = install_dir(manager, Path::new(binary)).await else {
+            continue;
+        };
+        // A bin directory the first install creates: its parent decides
+        let Some(existing) = dir.ancestors().find(|d| d.is_dir()) else {
+            continue;
+        };
+        if let Some(fix) = permission_fix(manager, existing).filter(|_| !is_writable(existing)) {
+            findings.push(Finding::new(
+                Severity::Error,
+                format!(
+                    "{}: {} isn't writable, so installs need sudo",
+                    manager.name(),
+                    dir.display()
+                ),
+                Some(fix),
+            ));
+        }
+    }
+    findings
+}
+
+/// Commands packages should provide that point nowhere
+fn broken_findings(packages: &[Package]) -> Vec<Finding> {
+    packages
+        .iter()
+        .filter(|p| !p.broken.is_empty())
+        .map(|pkg| {
+            let paths: Vec<&str> = pkg.broken.iter().map(|b| b.path.as_str()).collect();
+            let fix = match (&pkg.manager, pkg.installed_version == MISSING_VERSION) {
+                (PackageManager::Homebrew, true) => "brew cleanup --prune-prefix".to_string(),
+                (PackageManager::Homebrew, false) => format!("brew reinstall {}", pkg.name),
+                (PackageManager::Npm, false) => format!("npm install -g {}", pkg.name),
+                (PackageManager::Cargo, _) => format!("cargo install --force {}", pkg.name),
+                _ => format!("rm {}", paths.join(" ")),
+            };
+            Finding::new(
+                Severity::Warning,
+                format!(
+                    "{} ({}): broken {}",
+                    pkg.name,
+                    pkg.manager.name(),
+                    paths.join(", ")
+                ),
+                Some(fix),
+            )
+        })
+        .collect()
+}
+
+/// Commands where an older copy earlier on PATH wins
+async fn shadowing_findings(packages: &[Package]) -> Vec<Finding> {
+    let report = crate::audit::shadowing::analyze_login_path(packages).await;
+    let harmless = report.iter().filter(|s| !s.stale).count();
+    let mut findings: Vec<Finding> = report
+        .into_iter()
+        .filter(|s| s.stale)
+        .map(|s| {
+            Finding::new(
+                Severity::Warning,
+                format!("`{}` runs {}", s.command, s.winner.describe()),
+                s.suggestion,
+            )
+        })
+        .collect();
+    if harmless > 0 {
+        findings.push(Finding::new(
+            Severity::Note,
+            format!(
+                "{} more commands are on PATH more than once, the newest copy winning",
+                harmless
+            ),
+            None,
+        ));
+    }
+    findings
+}
+
+/// DepMgr's own directories, and cached responses that would be fetched again anyway
+fn depmgr_findings() -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let cache_dir = crate::utils::paths::cache_dir();
+    for (dir, variable) in [
+        (crate::utils::paths::config_dir(), "XDG_CONFIG_HOME"),
+        (crate::utils::paths::data_dir(), "XDG_DATA_HOME"),
+        (cache_dir.clone(), "XDG_CACHE_HOME"),
+    ] {
+        if dir.is_dir() && !is_writable(&dir) {
+            let fix = if is_user_dir(&dir) {
+                format!("sudo chown -R $(whoami) {}", dir.display())
+            } else {
+                format!("set {} to a directory in your home", variable)
+            };
+            findings.push(Finding::new(
+                Severity::Error,
+                format!("DepMgr can't write to {}", dir.display()),
+                Some(fix),
+            ));
+        }
+    }
+
+    let cache = crate::utils::cache::disk_cache_stats();
+    if cache.stale > 0 {
+        findings.push(Finding::new(
+            Severity::Note,
+            format!(
+                "{} of {} cached API responses are expired or unreadable ({} cached in all)",
+                cache.stale,
+                cache.entries,
+                format_size(cache.bytes, SizeUnits::default())
+            ),
+            Some(format!(
+                "Clear cache in the sidebar, or rm -r {}",
+                cache_dir.display()
+            )),
+        ));
+    }
+    findings
+}
+
+/// Every check the GUI spreads over its diagnostics panel, PATH report and broken-binary
+/// rows, most urgent first
+pub async fn run_doctor() -> Vec<Finding> {
+    let target = crate::utils::target::current_target();
+    if !crate::managers::backend::current().runs_commands() {
+        return Vec::new(); // Sample data, nothing installed to diagnose
+    }
+    let managers = crate::managers::detect_available_managers().await;
+    let crate::inventory::Scan {
+        packages, failures, ..
+    } = crate::inventory::collect_scan(&managers).await;
+
+    let mut findings = manager_findings(&managers, &failures).await;
+    findings.extend(broken_findings(&packages));
+    if target.is_local() {
+        findings.extend(shadowing_findings(&packages).await);
+        findings.extend(depmgr_findings());
+    }
+    findings.sort_by_key(|f| f.severity);
+    findings
+}
+
+/// The fix-it list as printed by `depmgr doctor`
+pub fn render(findings: &[Finding]) -> String {
+    if findings.is_empty() {
+        return "No problems found.\n".to_string();
+    }
+    let count = |severity: Severity| findings.iter().filter(|f| f.severity == severity).count();
+    let mut out = format!(
+        "{} errors, {} warnings, {} notes\n",
+        count(Severity::Error),
+        count(Severity::Warning),
+        count(Severity::Note)
+    );
+    for finding in findings {
+        out.push_str(&format!(
+            "\n{}: {}\n",
+            finding.severity.label(),
+            finding.problem
+        ));
+        if let Some(fix) = &finding.fix {
+            out.push_str(&format!("  fix: {}\n", fix));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::BrokenBinary;
+
+    #[test]
+    fn broken_commands_get_a_fix_and_errors_come_first() {
+        let broken = |path: &str| BrokenBinary {
+            path: path.to_string(),
+            dangling_target: Some("../Cellar/x/1.0/bin/x".to_string()),
+        };
+        let packages = vec![
+            Package {
+                broken: vec![broken("/opt/homebrew/bin/rg")],
+                ..Package::new("ripgrep", PackageManager::Homebrew, "14.1.0")
+            },
+            Package {
+                broken: vec![broken("/opt/homebrew/bin/old")],
+                ..Package::new("oldtool", PackageManager::Homebrew, MISSING_VERSION)
+            },
+            Package::new("jq", PackageManager::Homebrew, "1.7.1"),
+        ];
+        let mut findings = broken_findings(&packages);
+        let fixes: Vec<&str> = findings.iter().filter_map(|f| f.fix.as_deref()).collect();
+        assert_eq!(
+            fixes,
+            ["brew reinstall ripgrep", "brew cleanup --prune-prefix"]
+        );
+
+        findings.push(Finding::new(
+            Severity::Error,
+            "npm: listing packages failed: timed out".to_string(),
+            None,
+        ));
+        findings.sort_by_key(|f| f.severity);
+        let report = render(&findings);
+        assert!(report.starts_with("1 errors, 2 warnings, 0 notes\n\nerror: npm:"));
+        assert!(report.contains(
+            "warning: ripgrep (Homebrew): broken /opt/homebrew/bin/rg\n  fix: brew reinstall ripgrep\n"
+        ));
+        assert_eq!(render(&[]), "No problems found.\n");
+    }
+
+    #[test]
+    fn system_directories_never_get_a_chown() {
+        let home_bin = home_dir().join("go").join("bin");
+        assert_eq!(
+            permission_fix(&PackageManager::Go, &home_bin).unwrap(),
+            format!("sudo chown -R $(whoami) {}", home_bin.display())
+        );
+        for manager in [
+            PackageManager::Go,
+            PackageManager::Cargo,
+            PackageManager::Pipx,
+            PackageManager::Uv,
+        ] {
+            let fix = permission_fix(&manager, Path::new("/usr/local/go/bin")).unwrap();
+            assert!(!fix.contains("chown"), "{}: {}", manager.name(), fix);
+        }
+    }
+}
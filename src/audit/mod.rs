@@ -0,0 +1,9 @@
+pub mod changelog;
+pub mod doctor;
+pub mod osv;
+pub mod repo;
+pub mod shadowing;
+
+pub use changelog::{fetch_changelog, Changelog};
+pub use osv::{add_vulnerabilities, add_vulnerabilities_shared};
+pub use repo::{audit_repo, RepoAudit};
@@ -0,0 +1,148 @@
+use crate::models::Package;
+use crate::utils::cache::{get_cached, set_cached};
+use crate::utils::http_client::create_http_client;
+use crate::utils::metrics::SendTimed;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// OSV accepts up to 1000 queries per batch request
+const OSV_BATCH_SIZE: usize = 1000;
+
+#[derive(Debug, Serialize)]
+struct OsvQuery<'a> {
+    package: OsvPackage<'a>,
+    version: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct OsvPackage<'a> {
+    name: &'a str,
+    ecosystem: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct OsvBatchRequest<'a> {
+    queries: Vec<OsvQuery<'a>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvBatchResponse {
+    #[serde(default)]
+    results: Vec<OsvResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvResult {
+    #[serde(default)]
+    vulns: Vec<OsvVuln>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvVuln {
+    id: String,
+}
+
+fn cache_key(ecosystem: &str, pkg: &Package) -> String {
+    format!("osv:{}:{}:{}", ecosystem, pkg.name, pkg.installed_version)
+}
+
+/// Look up known vulnerabilities for every package in an OSV-covered ecosystem.
+/// Homebrew formulae aren't in OSV, so they're skipped.
+pub async fn add_vulnerabilities(packages: &mut [Package]) -> Result<()> {
+    println!("[AUDIT] Checking packages against OSV...");
+
+    // Serve what we can from cache, collect the rest for one batch call
+    let mut to_query: Vec<usize> = Vec::new();
+    for (idx, pkg) in packages.iter_mut().enumerate() {
+        let Some(ecosystem) = pkg.manager.osv_ecosystem() else {
+            continue;
+        };
+        match get_cached::<Vec<String>>(&cache_key(ecosystem, pkg)) {
+            Some(ids) => pkg.vulnerabilities = ids,
+            None => to_query.push(idx),
+        }
+    }
+
+    if to_query.is_empty() {
+        return Ok(());
+    }
+
+    let client = create_http_client();
+
+    for chunk in to_query.chunks(OSV_BATCH_SIZE) {
+        let request = OsvBatchRequest {
+            queries: chunk
+                .iter()
+                .map(|&idx| {
+                    let pkg = &packages[idx];
+                    OsvQuery {
+                        package: OsvPackage {
+                            name: &pkg.name,
+                            ecosystem: pkg.manager.osv_ecosystem().unwrap_or_default(),
+                        },
+                        version: &pkg.installed_version,
+                    }
+                })
+                .collect(),
+        };
+
+        let response = client
+            .post("https://api.osv.dev/v1/querybatch")
+            .json(&request)
+            .send_timed()
+            .await
+            .map_err(|e| anyhow!("Failed to query OSV: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("OSV API returned status: {}", response.status()));
+        }
+
+        let batch: OsvBatchResponse = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Failed to parse OSV response: {}", e))?;
+
+        // Results come back in query order
+        for (&idx, result) in chunk.iter().zip(batch.results) {
+            let pkg = &mut packages[idx];
+            pkg.vulnerabilities = result.vulns.into_iter().map(|v| v.id).collect();
+            if let Some(ecosystem) = pkg.manager.osv_ecosystem() {
+                set_cached(
+                    cache_key(ecosystem, pkg),
+                    &pkg.vulnerabilities,
+                    crate::config::cache_ttls().release_ttl(),
+                );
+            }
+        }
+    }
+
+    let vulnerable = packages
+        .iter()
+        .filter(|p| !p.vulnerabilities.is_empty())
+        .count();
+    println!("[AUDIT] ✓ {} vulnerable packages", vulnerable);
+
+    Ok(())
+}
+
+/// Audit the shared package list without holding the lock during network calls
+pub async fn add_vulnerabilities_shared(packages: Arc<RwLock<Vec<Package>>>) {
+    let mut snapshot = packages.read().await.clone();
+
+    if let Err(e) = add_vulnerabilities(&mut snapshot).await {
+        eprintln!("[ERROR] Vulnerability check failed: {}", e);
+        return;
+    }
+
+    let mut packages_lock = packages.write().await;
+    for audited in snapshot {
+        if let Some(pkg) = packages_lock
+            .iter_mut()
+            .find(|p| p.name == audited.name && p.manager == audited.manager)
+        {
+            pkg.vulnerabilities = audited.vulnerabilities;
+        }
+    }
+}
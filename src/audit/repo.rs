@@ -0,0 +1,239 @@
+use crate::models::{PackageManager, Project};
+use crate::utils::run_command_with_timeout;
+use anyhow::{anyhow, Result};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// A command a repository's projects need, and whether it's on the login shell's PATH
+#[derive(Debug, Clone)]
+pub struct NeededTool {
+    pub command: String,
+    pub reasons: Vec<String>, // "package.json", "node >=20", ".pre-commit-config.yaml"
+    pub installed: bool,
+    pub formula: Option<String>, // Homebrew formula that provides it
+}
+
+/// What auditing a path or git URL found
+#[derive(Debug, Clone)]
+pub struct RepoAudit {
+    pub source: String,
+    pub projects: Vec<Project>,
+    pub tools: Vec<NeededTool>,
+}
+
+/// "https://github.com/owner/repo", "git@github.com:owner/repo.git", "ssh://..." -
+/// anything else is a local path
+pub fn is_git_url(source: &str) -> bool {
+    source.starts_with("https://")
+        || source.starts_with("http://")
+        || source.starts_with("git@")
+        || source.starts_with("ssh://")
+        || source.ends_with(".git")
+}
+
+/// Homebrew formula providing a command, for one-click installs; Swift comes with Xcode
+fn homebrew_formula(command: &str) -> Option<String> {
+    let formula = match command {
+        "node" | "npm" => "node",
+        "cargo" | "rustc" => "rust",
+        "python3" => "python",
+        "java" => "openjdk",
+        "mvn" => "maven",
+        "swift" => return None,
+        other => return crate::scanner::bootstrap::setup_formula(other),
+    };
+    Some(formula.to_string())
+}
+
+/// The command that shows a tool is installed
+fn command_for(tool: &str) -> &str {
+    match tool {
+        "rust" => "rustc",
+        "python" => "python3",
+        other => other,
+    }
+}
+
+/// Commands the projects' manifests, declared tool versions and dev tooling call for,
+/// each with why it's needed, in first-seen order
+pub fn required_tools(projects: &[Project]) -> Vec<(String, Vec<String>)> {
+    let mut tools: Vec<(String, Vec<String>)> = Vec::new();
+    let mut need =
+        |command: &str, reason: String| match tools.iter_mut().find(|(c, _)| c == command) {
+            Some((_, reasons)) if !reasons.contains(&reason) => reasons.push(reason),
+            Some(_) => {}
+            None => tools.push((command.to_string(), vec![reason])),
+        };
+
+    for project in projects {
+        let has = |file: &str| project.path.join(file).exists();
+        for manager in &project.package_managers {
+            match manager {
+                PackageManager::Npm => {
+                    need("node", "package.json".to_string());
+                    need("npm", "package.json".to_string());
+                }
+                PackageManager::Cargo => need("cargo", "Cargo.toml".to_string()),
+                PackageManager::Pip => need("python3", "Python manifest".to_string()),
+                PackageManager::Go => need("go", "go.mod".to_string()),
+                PackageManager::Swift => need("swift", "Package.swift".to_string()),
+                PackageManager::Pub => need("dart", "pubspec.yaml".to_string()),
+                PackageManager::Maven => {
+                    need("java", "Java build".to_string());
+                    // Projects that ship a wrapper download their own build tool
+                    if has("pom.xml") && !has("mvnw") {
+                        need("mvn", "pom.xml".to_string());
+                    }
+                    let gradle = has("build.gradle") || has("build.gradle.kts");
+                    if gradle && !has("gradlew") {
+                        need("gradle", "build.gradle".to_string());
+                    }
+                }
+                other => need(other.command(), format!("{} project", other.name())),
+            }
+        }
+        for requirement in &project.tool_requirements {
+            need(
+                command_for(&requirement.package_name),
+                format!(
+                    "{} {}",
+                    requirement.package_name, requirement.version_constraint
+                ),
+            );
+        }
+        for tool in &project.setup_tools {
+            let reason = match &tool.rev {
+                Some(version) => format!("{} {} ({})", tool.tool, version, tool.source),
+                None => tool.source.clone(),
+            };
+            need(command_for(&tool.tool), reason);
+        }
+        for usage in &project.tool_usages {
+            let source = Path::new(&usage.source)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| usage.source.clone());
+            need(&usage.tool, source);
+        }
+    }
+    tools
+}
+
+/// Which of `commands` resolve in a login shell (a GUI app's own PATH is minimal)
+async fn installed_commands(commands: &[&str]) -> Vec<String> {
+    let names: Vec<&str> = commands
+        .iter()
+        .copied()
+        .filter(|c| {
+            c.chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || "._+-".contains(ch))
+        })
+        .collect();
+    if names.is_empty() {
+        return Vec::new();
+    }
+    let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+    let script = format!(
+        "for t in {}; do command -v \"$t\" >/dev/null 2>&1 && echo \"$t\"; done; true",
+        names.join(" ")
+    );
+    match run_command_with_timeout(&shell, &["-lc", &script], Duration::from_secs(15)).await {
+        Ok(output) => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|l| l.trim().to_string())
+            .collect(),
+        Err(e) => {
+            eprintln!("[AUDIT] Couldn't check installed commands: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Shallow-clone a git URL into a temporary directory
+async fn clone_repo(url: &str) -> Result<PathBuf> {
+    let dir = std::env::temp_dir().join(format!(
+        "depmgr-repo-{}-{}",
+        std::process::id(),
+        chrono::Utc::now().timestamp_millis()
+    ));
+    let target = dir.to_string_lossy().to_string();
+    println!("[AUDIT] Cloning {} into {}", url, target);
+    let args = crate::utils::git::clone_args(url, &target, &["--depth", "1", "--quiet"])?;
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let output = run_command_with_timeout("git", &args, Duration::from_secs(180)).await?;
+    if !output.status.success() {
+        let _ = std::fs::remove_dir_all(&dir);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("git clone failed: {}", stderr.trim()));
+    }
+    Ok(dir)
+}
+
+/// Read a local path or a fresh clone of a git URL, and check which of the tools its
+/// projects need are missing. Clones are deleted once their manifests are read.
+pub async fn audit_repo(source: &str) -> Result<RepoAudit> {
+    let (dir, clone) = if is_git_url(source) {
+        (clone_repo(source).await?, true)
+    } else {
+        let path = crate::utils::paths::expand_home(Path::new(source));
+        if !path.is_dir() {
+            return Err(anyhow!("{} is not a folder or git URL", source));
+        }
+        (path, false)
+    };
+    let projects = crate::scanner::scan_projects(std::slice::from_ref(&dir));
+    // Wrapper scripts are checked on disk, so the clone goes only after this
+    let required = required_tools(&projects);
+    if clone {
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+    if projects.is_empty() {
+        return Err(anyhow!("No project manifests found in {}", source));
+    }
+
+    let commands: Vec<&str> = required.iter().map(|(c, _)| c.as_str()).collect();
+    let installed = installed_commands(&commands).await;
+    let tools = required
+        .into_iter()
+        .map(|(command, reasons)| NeededTool {
+            installed: installed.contains(&command),
+            formula: homebrew_formula(&command),
+            command,
+            reasons,
+        })
+        .collect();
+
+    Ok(RepoAudit {
+        source: source.to_string(),
+        projects,
+        tools,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ToolUsage;
+
+    #[test]
+    fn tools_from_manifests_requirements_and_hooks() {
+        let mut project = Project::new(PathBuf::from("/nonexistent/app"));
+        project.package_managers = vec![PackageManager::Npm, PackageManager::Pip];
+        project.tool_usages = vec![ToolUsage {
+            tool: "shellcheck".to_string(),
+            source: "/nonexistent/app/.pre-commit-config.yaml".to_string(),
+            rev: None,
+        }];
+        let mut node = Project::new(PathBuf::from("/nonexistent/web"));
+        node.package_managers = vec![PackageManager::Npm];
+
+        let tools = required_tools(&[project, node]);
+        let commands: Vec<&str> = tools.iter().map(|(c, _)| c.as_str()).collect();
+        assert_eq!(commands, ["node", "npm", "python3", "shellcheck"]);
+        assert_eq!(tools[0].1, ["package.json"]);
+        assert_eq!(tools[3].1, [".pre-commit-config.yaml"]);
+        assert_eq!(homebrew_formula("python3").as_deref(), Some("python"));
+        assert!(is_git_url("git@github.com:owner/repo.git"));
+        assert!(!is_git_url("~/src/repo"));
+    }
+}
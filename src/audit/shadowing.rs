@@ -0,0 +1,294 @@
+use crate::models::{Package, PackageManager};
+use crate::utils::version::compare_versions;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+/// One copy of a command on PATH and the package it belongs to, if we know it
+#[derive(Debug, Clone)]
+pub struct CommandCopy {
+    pub path: PathBuf,
+    pub owner: Option<(PackageManager, String)>,
+    pub version: Option<String>,
+}
+
+impl CommandCopy {
+    pub fn describe(&self) -> String {
+        let owner = match (&self.owner, &self.version) {
+            (Some((manager, name)), Some(version)) => {
+                format!(" ({} {} {})", manager.name(), name, version)
+            }
+            (Some((manager, name)), None) => format!(" ({} {})", manager.name(), name),
+            (None, _) => String::new(),
+        };
+        format!("{}{}", self.path.display(), owner)
+    }
+}
+
+/// A command name found in more than one PATH directory
+#[derive(Debug, Clone)]
+pub struct Shadowing {
+    pub command: String,
+    /// First on PATH: the one that runs
+    pub winner: CommandCopy,
+    pub hidden: Vec<CommandCopy>,
+    /// The winner is older than a copy it hides
+    pub stale: bool,
+    pub suggestion: Option<String>,
+}
+
+/// PATH as a login shell (`$SHELL -lc`) sets it. Apps launched from Finder/the dock get a
+/// minimal PATH, which isn't the one that decides what runs in a terminal. Not `-i`: an
+/// interactive shell runs prompts and plugins from the rc files and can hang without a tty.
+pub async fn login_path() -> String {
+    use crate::utils::run_command_with_timeout;
+    use std::time::Duration;
+
+    let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+    match run_command_with_timeout(
+        &shell,
+        &["-lc", "printf %s \"$PATH\""],
+        Duration::from_secs(10),
+    )
+    .await
+    {
+        Ok(output) if output.status.success() && !output.stdout.is_empty() => {
+            String::from_utf8_lossy(&output.stdout).to_string()
+        }
+        _ => std::env::var("PATH").unwrap_or_default(),
+    }
+}
+
+fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::metadata(path)
+            .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+            .unwrap_or(false)
+    }
+    #[cfg(not(unix))]
+    {
+        path.is_file()
+    }
+}
+
+/// The path component after `anchor`, and the one after that
+fn after<'a>(path: &'a Path, anchor: &str) -> Option<(&'a str, Option<&'a str>)> {
+    let mut parts = path
+        .components()
+        .skip_while(|c| !matches!(c, Component::Normal(p) if *p == anchor))
+        .skip(1)
+        .filter_map(|c| match c {
+            Component::Normal(p) => p.to_str(),
+            _ => None,
+        });
+    let first = parts.next()?;
+    Some((first, parts.next()))
+}
+
+/// Which package installed a binary, from where it really lives (symlinks resolved)
+fn owner_of(
+    real: &Path,
+    packages: &[Package],
+    cargo_bins: &HashMap<PathBuf, String>,
+) -> (Option<(PackageManager, String)>, Option<String>) {
+    let installed = |manager: &PackageManager, name: &str| {
+        packages
+            .iter()
+            .find(|p| &p.manager == manager && p.name == name)
+            .map(|p| p.installed_version.clone())
+    };
+    if let Some((name, version)) = after(real, "Cellar") {
+        let version = version.map(str::to_string);
+        return (Some((PackageManager::Homebrew, name.to_string())), version);
+    }
+    if let Some((first, second)) = after(real, "node_modules") {
+        let name = match (first.starts_with('@'), second) {
+            (true, Some(scoped)) => format!("{}/{}", first, scoped),
+            _ => first.to_string(),
+        };
+        let version = installed(&PackageManager::Npm, &name);
+        return (Some((PackageManager::Npm, name)), version);
+    }
+    if let Some(name) = cargo_bins.get(real) {
+        let version = installed(&PackageManager::Cargo, name);
+        return (Some((PackageManager::Cargo, name.clone())), version);
+    }
+    (None, None)
+}
+
+fn uninstall_hint(copy: &CommandCopy) -> String {
+    let in_usr_local = copy.path.starts_with("/usr/local");
+    match &copy.owner {
+        Some((PackageManager::Homebrew, name)) if in_usr_local && cfg!(target_os = "macos") => {
+            format!("arch -x86_64 /usr/local/bin/brew uninstall {}", name)
+        }
+        Some((PackageManager::Homebrew, name)) => format!("brew uninstall {}", name),
+        Some((PackageManager::Npm, name)) => format!("npm uninstall -g {}", name),
+        Some((PackageManager::Cargo, name)) => format!("cargo uninstall {}", name),
+        Some((manager, name)) => format!("remove {} {}", manager.name(), name),
+        None => format!("rm {}", copy.path.display()),
+    }
+}
+
+fn suggest(winner: &CommandCopy, newer: &CommandCopy) -> String {
+    let dir = |copy: &CommandCopy| {
+        copy.path
+            .parent()
+            .map(|p| p.display().to_string())
+            .unwrap_or_default()
+    };
+    if winner.path.starts_with("/usr/local") && newer.path.starts_with("/opt/homebrew") {
+        return format!(
+            "Leftover Intel Homebrew copy. Put eval \"$(/opt/homebrew/bin/brew shellenv)\" \
+             after any /usr/local setup in ~/.zprofile, or remove it: {}",
+            uninstall_hint(winner)
+        );
+    }
+    format!(
+        "Move {} before {} in PATH, or remove the older copy: {}",
+        dir(newer),
+        dir(winner),
+        uninstall_hint(winner)
+    )
+}
+
+/// Commands that exist in several PATH directories where at least one copy belongs to a
+/// package we track, flagging those where the copy that runs is older than one it hides
+pub fn analyze(
+    path_var: &str,
+    packages: &[Package],
+    cargo_bins: &HashMap<PathBuf, String>,
+) -> Vec<Shadowing> {
+    let mut copies: HashMap<String, Vec<CommandCopy>> = HashMap::new();
+    let mut order: Vec<String> = Vec::new();
+    let mut seen_dirs = HashSet::new();
+    let mut seen_files: HashMap<String, HashSet<PathBuf>> = HashMap::new();
+
+    for dir in std::env::split_paths(path_var) {
+        // /bin and /usr/bin are often the same directory
+        let real_dir = dir.canonicalize().unwrap_or_else(|_| dir.clone());
+        if !seen_dirs.insert(real_dir) {
+            continue;
+        }
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut names: Vec<(String, PathBuf)> = entries
+            .flatten()
+            .filter_map(|e| Some((e.file_name().to_str()?.to_string(), e.path())))
+            .filter(|(_, path)| is_executable(path))
+            .collect();
+        names.sort();
+
+        for (name, path) in names {
+            let real = path.canonicalize().unwrap_or_else(|_| path.clone());
+            if !seen_files
+                .entry(name.clone())
+                .or_default()
+                .insert(real.clone())
+            {
+                continue; // another link to a binary we already have
+            }
+            let (owner, version) = owner_of(&real, packages, cargo_bins);
+            let list = copies.entry(name.clone()).or_default();
+            if list.is_empty() {
+                order.push(name);
+            }
+            list.push(CommandCopy {
+                path,
+                owner,
+                version,
+            });
+        }
+    }
+
+    let mut report = Vec::new();
+    for command in order {
+        let mut list = copies.remove(&command).unwrap_or_default();
+        if list.len() < 2 || list.iter().all(|c| c.owner.is_none()) {
+            continue;
+        }
+        let winner = list.remove(0);
+        let newer = winner.version.as_ref().and_then(|current| {
+            list.iter()
+                .filter(|c| {
+                    c.version
+                        .as_ref()
+                        .is_some_and(|v| compare_versions(v, current) == Ordering::Greater)
+                })
+                .max_by(|a, b| {
+                    compare_versions(
+                        a.version.as_deref().unwrap_or_default(),
+                        b.version.as_deref().unwrap_or_default(),
+                    )
+                })
+        });
+        let suggestion = newer.map(|newer| suggest(&winner, newer));
+        report.push(Shadowing {
+            command,
+            stale: suggestion.is_some(),
+            suggestion,
+            winner,
+            hidden: list,
+        });
+    }
+    // Stale ones first
+    report.sort_by_key(|s| !s.stale);
+    report
+}
+
+/// Analyze the login shell's PATH against the current inventory
+pub async fn analyze_login_path(packages: &[Package]) -> Vec<Shadowing> {
+    let path_var = login_path().await;
+    let cargo_bins: HashMap<PathBuf, String> = crate::managers::cargo::installed_binaries()
+        .into_iter()
+        .flat_map(|(name, bins)| {
+            bins.into_iter()
+                .map(move |b| (b.canonicalize().unwrap_or(b), name.clone()))
+        })
+        .collect();
+    let report = analyze(&path_var, packages, &cargo_bins);
+    println!(
+        "[PATH] {} commands shadowed, {} by an older copy",
+        report.len(),
+        report.iter().filter(|s| s.stale).count()
+    );
+    report
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::utils::test_dir::TestDir;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn older_copy_first_on_path_is_stale() {
+        let root = TestDir::new("path");
+        let binary = |keg: &str| {
+            let bin = root.join(keg).join("Cellar/node").join(keg).join("bin");
+            std::fs::create_dir_all(&bin).unwrap();
+            let path = bin.join("node");
+            std::fs::write(&path, "").unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+            bin
+        };
+        let old = binary("18.0.0");
+        let new = binary("22.1.0");
+        let path_var = std::env::join_paths([&old, &new]).unwrap();
+
+        let report = analyze(path_var.to_str().unwrap(), &[], &HashMap::new());
+
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].command, "node");
+        assert_eq!(report[0].winner.version.as_deref(), Some("18.0.0"));
+        assert!(report[0].stale);
+        assert!(report[0]
+            .suggestion
+            .as_ref()
+            .unwrap()
+            .contains("brew uninstall node"));
+    }
+}
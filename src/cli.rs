@@ -0,0 +1,758 @@
+use crate::config::{ConfigBundle, Settings};
+use crate::deep_link::DeepLink;
+use crate::models::{Package, PackageFlags, Project};
+use crate::operations::{JournalAction, JournalEntry};
+use crate::policy::{evaluate, Threshold};
+use crate::report::output::OutputFormat;
+use crate::report::schema::ScanOutput;
+use crate::utils::log_redirect::LogRedirect;
+use crate::utils::target::{close_target, open_container, set_target, ExecTarget};
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+/// Every package manager on this machine in one place. Without a command or options,
+/// launches the GUI.
+#[derive(Parser)]
+#[command(
+    name = "depmgr",
+    version,
+    after_help = "With --fail-on, --json or --format, stdout carries only the result; logs go to \
+                  stderr. Exit codes:\n\
+                  0 = within policy, 1 = policy violated, 2 = invalid arguments."
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Scan headlessly and write the environment digest if it's due
+    #[arg(long)]
+    digest: bool,
+
+    /// With --digest, generate the digest even if it isn't due yet
+    #[arg(long, requires = "digest")]
+    force: bool,
+
+    /// Audit a Docker container or image and write a Markdown report
+    #[arg(long, value_name = "REF")]
+    container: Option<String>,
+
+    /// Use a bundled sample inventory instead of this machine (no commands or network);
+    /// combines with the other options
+    #[arg(long, global = true)]
+    demo: bool,
+
+    /// Open the dashboard in the terminal instead of a window (same as `depmgr tui`)
+    #[arg(long, global = true)]
+    tui: bool,
+
+    /// Keep running: rescan periodically and serve /metrics (Prometheus), /v1/scan,
+    /// /inventory and /healthz over HTTP (default address from [server] in config.toml)
+    #[arg(long, value_name = "ADDR")]
+    serve: Option<Option<String>>,
+
+    /// Keep running in the background: rescan periodically and share the inventory with
+    /// the GUI and CLI over a local socket (add --serve for HTTP too)
+    #[arg(long)]
+    daemon: bool,
+
+    /// Write settings, pins, ignores, favorites, category overrides and added scan roots
+    /// to one TOML file (e.g. in your dotfiles)
+    #[arg(long, value_name = "FILE")]
+    export_config: Option<PathBuf>,
+
+    /// Replace this machine's configuration with an exported file
+    #[arg(long, value_name = "FILE")]
+    import_config: Option<PathBuf>,
+
+    /// Store a token (read from stdin) in the OS keychain and send it with every request
+    /// to HOST, e.g. npm.example.com
+    #[arg(long, value_name = "HOST")]
+    set_token: Option<String>,
+
+    /// Delete HOST's token from the keychain
+    #[arg(long, value_name = "HOST")]
+    remove_token: Option<String>,
+
+    /// Have the OS open depmgr:// links (in notes, scripts, notifications) with this
+    /// binary
+    #[arg(long)]
+    register_links: bool,
+
+    /// Scan headlessly and exit 1 if the rule is broken (repeatable). RULE is a metric,
+    /// optionally with a limit: vulnerable, outdated>10, unused>=50, mismatched
+    #[arg(long = "fail-on", value_name = "RULE", value_parser = Threshold::parse)]
+    fail_on: Vec<Threshold>,
+
+    /// Print results as versioned JSON (`--format json` for scan and outdated); with
+    /// --fail-on or --container, instead of the summary line or Markdown report
+    #[arg(long, global = true)]
+    json: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Scan this machine and list every package
+    Scan {
+        #[arg(long, value_enum, value_name = "FORMAT")]
+        format: Option<OutputFormat>,
+    },
+    /// List packages with a newer version (ignored packages left out)
+    Outdated {
+        #[arg(long, value_enum, value_name = "FORMAT")]
+        format: Option<OutputFormat>,
+    },
+    /// Update packages by name ("jq") or manager and name ("npm/eslint")
+    Update {
+        #[arg(value_name = "PACKAGE", required_unless_present = "all")]
+        packages: Vec<String>,
+        /// Every outdated package that isn't pinned, ignored or marked critical
+        #[arg(long, conflicts_with = "packages")]
+        all: bool,
+        /// Update named packages marked critical without typing their names first
+        #[arg(long, conflicts_with = "all")]
+        confirm_critical: bool,
+    },
+    /// Check manager installs, PATH shadowing, broken symlinks, permissions and the
+    /// cache, and print what to fix; exits 1 if anything needs fixing before the package
+    /// managers work
+    Doctor,
+    /// Open the app on a package or view: depmgr://package/homebrew/jq,
+    /// depmgr://view/outdated (add --tui for the terminal)
+    Open {
+        #[arg(value_name = "URL", value_parser = DeepLink::parse)]
+        link: DeepLink,
+    },
+    /// Full-screen terminal interface with the GUI's table, filters and updates, e.g.
+    /// over SSH
+    Tui,
+}
+
+impl Command {
+    fn name(&self) -> &'static str {
+        match self {
+            Command::Scan { .. } => "scan",
+            Command::Outdated { .. } => "outdated",
+            Command::Update { .. } => "update",
+            Command::Doctor => "doctor",
+            Command::Open { .. } => "open",
+            Command::Tui => "tui",
+        }
+    }
+}
+
+impl Cli {
+    /// Options that are a run of their own can't be given with a command, which they'd
+    /// otherwise silently win over (`depmgr --digest outdated`). clap has no conflict
+    /// between an argument and a subcommand that leaves the rest of the options free.
+    fn check_conflicts(&self) -> Result<(), clap::Error> {
+        let Some(command) = &self.command else {
+            return Ok(());
+        };
+        let standalone = [
+            ("--digest", self.digest),
+            ("--container", self.container.is_some()),
+            ("--serve", self.serve.is_some()),
+            ("--daemon", self.daemon),
+            ("--export-config", self.export_config.is_some()),
+            ("--import-config", self.import_config.is_some()),
+            ("--set-token", self.set_token.is_some()),
+            ("--remove-token", self.remove_token.is_some()),
+            ("--register-links", self.register_links),
+            // The policy check is part of `scan`, and the terminal UI can open a link
+            (
+                "--fail-on",
+                !self.fail_on.is_empty() && !matches!(command, Command::Scan { .. }),
+            ),
+            (
+                "--tui",
+                self.tui && !matches!(command, Command::Open { .. } | Command::Tui),
+            ),
+        ];
+        match standalone.iter().find(|(_, given)| *given) {
+            Some((option, _)) => {
+                use clap::CommandFactory;
+                Err(Cli::command().error(
+                    clap::error::ErrorKind::ArgumentConflict,
+                    format!(
+                        "{} can't be used with the '{}' command",
+                        option,
+                        command.name()
+                    ),
+                ))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// The link given with `depmgr open`, for the frontend to open on
+    pub fn link(&self) -> Option<DeepLink> {
+        match &self.command {
+            Some(Command::Open { link }) => Some(link.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// Handle headless command-line invocations. Returns an exit code when the
+/// arguments asked for a headless run, or None to launch the GUI.
+pub fn run_headless(cli: Cli) -> Option<i32> {
+    if let Err(e) = cli.check_conflicts() {
+        e.exit();
+    }
+    if cli.demo {
+        let _logs = LogRedirect::to_stderr();
+        set_target(ExecTarget::Demo);
+    }
+
+    if cli.digest {
+        return Some(run_digest(cli.force));
+    }
+
+    if let Some(path) = &cli.export_config {
+        return Some(transfer_config(path, false));
+    }
+    if let Some(path) = &cli.import_config {
+        return Some(transfer_config(path, true));
+    }
+
+    if let Some(host) = &cli.set_token {
+        return Some(manage_token(host, true));
+    }
+    if let Some(host) = &cli.remove_token {
+        return Some(manage_token(host, false));
+    }
+
+    if cli.register_links {
+        return Some(match crate::deep_link::register() {
+            Ok(registered) => {
+                println!("{}", registered);
+                0
+            }
+            Err(e) => {
+                eprintln!("[ERROR] {}", e);
+                1
+            }
+        });
+    }
+
+    if cli.serve.is_some() || cli.daemon {
+        let mut settings = Settings::load();
+        if let Some(Some(listen)) = &cli.serve {
+            settings.server.listen = listen.clone();
+        }
+        return Some(crate::server::run_server(
+            &settings,
+            cli.serve.is_some(),
+            cli.daemon,
+        ));
+    }
+
+    if let Some(Command::Doctor) = cli.command {
+        return Some(run_doctor());
+    }
+
+    // Everything else shares a running daemon's inventory, if there is one
+    {
+        let _logs = LogRedirect::to_stderr();
+        crate::daemon::connect();
+    }
+
+    // --json is short for --format json
+    let format = |format: Option<OutputFormat>| {
+        let json = cli.json.then_some(OutputFormat::Json);
+        json.or(format).unwrap_or(OutputFormat::Table)
+    };
+    if cli.tui {
+        return Some(crate::tui::run(cli.link()));
+    }
+    // From here on the command runs in the user's terminal, which can answer prompts
+    if !matches!(cli.command, Some(Command::Tui) | Some(Command::Open { .. })) {
+        crate::utils::prompt::answer_prompts_on_terminal();
+    }
+    match cli.command {
+        Some(Command::Scan { format: f }) => return Some(run_check(&cli.fail_on, Some(format(f)))),
+        Some(Command::Outdated { format: f }) => return Some(run_outdated(format(f))),
+        Some(Command::Update {
+            packages,
+            all,
+            confirm_critical,
+        }) => return Some(run_update(&packages, all, confirm_critical, cli.json)),
+        Some(Command::Tui) => return Some(crate::tui::run(None)),
+        // The GUI opens on the link
+        Some(Command::Open { .. }) => return None,
+        Some(Command::Doctor) | None => {}
+    }
+
+    if let Some(reference) = &cli.container {
+        return Some(run_container_audit(reference, &cli.fail_on, cli.json));
+    }
+
+    if !cli.fail_on.is_empty() || cli.json {
+        let format = cli.json.then_some(OutputFormat::Json);
+        return Some(run_check(&cli.fail_on, format));
+    }
+
+    None
+}
+
+/// Print the scan results (`--json`) or the summary line and turn the policy into an
+/// exit code
+fn report_policy(
+    packages: &[Package],
+    projects: &[Project],
+    thresholds: &[Threshold],
+    json: bool,
+) -> i32 {
+    let summary = evaluate(packages, thresholds);
+    for violation in &summary.violations {
+        eprintln!("[POLICY] Violated: {}", violation);
+    }
+    let line = if json {
+        let output = ScanOutput::new(packages, projects);
+        let output = if thresholds.is_empty() {
+            output
+        } else {
+            output.with_policy(&summary)
+        };
+        serde_json::to_string(&output)
+    } else {
+        serde_json::to_string(&summary)
+    };
+    println!("{}", line.unwrap_or_else(|_| "{}".to_string()));
+    if summary.passed {
+        0
+    } else {
+        1
+    }
+}
+
+/// Print a package list in a format other than JSON. The title goes to stderr with the
+/// logs, so stdout parses as CSV, YAML or Markdown.
+fn print_list(format: OutputFormat, title: &str, packages: &[Package], projects: &[Project]) {
+    eprintln!("=== {}: {} packages ===", title, packages.len());
+    print!(
+        "{}",
+        crate::report::output::render(format, title, packages, projects)
+    );
+}
+
+/// CI/cron policy check of this machine, or an export of it (`--json`, `depmgr scan`)
+fn run_check(thresholds: &[Threshold], format: Option<OutputFormat>) -> i32 {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    let logs = LogRedirect::to_stderr();
+    let scan = rt.block_on(async {
+        let managers = crate::managers::detect_available_managers().await;
+        crate::inventory::collect_scan(&managers).await
+    });
+    drop(logs);
+
+    if let Some(format) = format.filter(|f| *f != OutputFormat::Json) {
+        print_list(format, "Installed packages", &scan.packages, &scan.projects);
+        if thresholds.is_empty() {
+            return 0;
+        }
+        // The list is the output; the policy summary joins the logs
+        let _summary = LogRedirect::to_stderr();
+        return report_policy(&scan.packages, &scan.projects, thresholds, false);
+    }
+    let json = format == Some(OutputFormat::Json);
+    report_policy(&scan.packages, &scan.projects, thresholds, json)
+}
+
+/// Outdated packages the GUI would count
+fn run_outdated(format: OutputFormat) -> i32 {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let logs = LogRedirect::to_stderr();
+    let packages = rt.block_on(async {
+        let managers = crate::managers::detect_available_managers().await;
+        crate::inventory::collect_inventory(&managers).await
+    });
+    drop(logs);
+
+    let flags = PackageFlags::load();
+    let outdated: Vec<Package> = packages
+        .into_iter()
+        .filter(|p| flags.needs_attention(p))
+        .collect();
+    if format == OutputFormat::Json {
+        print!(
+            "{}",
+            crate::report::output::render(format, "", &outdated, &[])
+        );
+    } else {
+        print_list(format, "Outdated packages", &outdated, &[]);
+    }
+    0
+}
+
+/// Which installed packages `depmgr update` arguments refer to: "jq" must be unique
+/// across managers, "Homebrew/jq" (`Package::key`) never needs to be
+fn resolve_packages<'a>(
+    packages: &'a [Package],
+    names: &[String],
+) -> anyhow::Result<Vec<&'a Package>> {
+    names
+        .iter()
+        .map(|name| {
+            let matches: Vec<&Package> = packages
+                .iter()
+                .filter(|p| p.key().eq_ignore_ascii_case(name) || &p.name == name)
+                .collect();
+            match matches.as_slice() {
+                [pkg] => Ok(*pkg),
+                [] => Err(anyhow::anyhow!("{} isn't installed", name)),
+                _ => Err(anyhow::anyhow!(
+                    "{} is installed by more than one manager, use one of: {}",
+                    name,
+                    matches
+                        .iter()
+                        .map(|p| p.key())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+            }
+        })
+        .collect()
+}
+
+/// One package's outcome in `depmgr update --json`
+#[derive(serde::Serialize)]
+struct UpdateRecord {
+    package: String,
+    from: String,
+    to: Option<String>,
+    /// "updated", "failed" or "skipped"
+    status: &'static str,
+    /// Why it was skipped: "pinned", or "critical" when not confirmed
+    reason: Option<&'static str>,
+    error: Option<String>,
+}
+
+/// Why a package named on the command line is left alone, if it is. Critical packages
+/// need `confirm` to say yes, as the app makes you type their name.
+fn skip_reason(
+    flags: &PackageFlags,
+    pkg: &Package,
+    confirm: impl FnOnce(&Package) -> bool,
+) -> Option<&'static str> {
+    if flags.is_pinned(pkg) {
+        Some("pinned")
+    } else if flags.is_critical(pkg) && !confirm(pkg) {
+        Some("critical")
+    } else {
+        None
+    }
+}
+
+/// Ask on the terminal for a critical package's name; without a terminal, the answer is no
+fn confirm_on_terminal(pkg: &Package) -> bool {
+    use std::io::IsTerminal;
+    if !std::io::stdin().is_terminal() {
+        return false;
+    }
+    eprintln!(
+        "{} is marked critical. Type its name to update it:",
+        pkg.key()
+    );
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer).is_ok() && answer.trim() == pkg.name
+}
+
+/// Update the named packages, or every outdated one DepMgr may update unattended. Each
+/// update goes into the journal like one made in the app.
+fn run_update(names: &[String], all: bool, confirm_critical: bool, json: bool) -> i32 {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    // With --json, stdout carries only the result
+    let logs = json.then(LogRedirect::to_stderr);
+
+    let (code, records) = rt.block_on(async {
+        let managers = crate::managers::detect_available_managers().await;
+        let packages = crate::inventory::collect_inventory(&managers).await;
+        let flags = PackageFlags::load();
+
+        let targets: Vec<&Package> = if all {
+            packages
+                .iter()
+                .filter(|p| flags.can_update_unattended(p))
+                .collect()
+        } else {
+            match resolve_packages(&packages, names) {
+                Ok(targets) => targets,
+                Err(e) => {
+                    eprintln!("[ERROR] {}", e);
+                    return (2, Vec::new());
+                }
+            }
+        };
+
+        let mut records = Vec::new();
+        for pkg in targets {
+            let mut record = UpdateRecord {
+                package: pkg.key(),
+                from: pkg.installed_version.clone(),
+                to: pkg.latest_version.clone(),
+                status: "skipped",
+                reason: None,
+                error: None,
+            };
+            let confirm = |pkg: &Package| confirm_critical || confirm_on_terminal(pkg);
+            if let Some(reason) = skip_reason(&flags, pkg, confirm) {
+                eprintln!("[UPDATE] Skipping {}: it's {}", pkg.key(), reason);
+                record.reason = Some(reason);
+                records.push(record);
+                continue;
+            }
+            let result = crate::operations::update_package(&pkg.manager, pkg.name.clone()).await;
+            JournalEntry::new(JournalAction::Update, pkg.manager.clone(), pkg.name.clone())
+                .with_versions(
+                    Some(pkg.installed_version.clone()),
+                    pkg.latest_version.clone(),
+                )
+                .finish(&result);
+            match result {
+                Ok(()) => {
+                    println!("[UPDATE] Updated {}", pkg.key());
+                    record.status = "updated";
+                }
+                Err(e) => {
+                    eprintln!("[ERROR] Failed to update {}: {}", pkg.key(), e);
+                    record.status = "failed";
+                    record.error = Some(e.to_string());
+                }
+            }
+            records.push(record);
+        }
+        let failed = records.iter().any(|r| r.status == "failed");
+        (i32::from(failed), records)
+    });
+
+    drop(logs);
+    if json && code != 2 {
+        println!(
+            "{}",
+            serde_json::to_string(&records).unwrap_or_else(|_| "[]".to_string())
+        );
+    }
+    code
+}
+
+/// Everything the diagnostics panel would show, as a fix-it list. Checks this machine
+/// directly, not a daemon's inventory, since it's the setup being diagnosed.
+fn run_doctor() -> i32 {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let findings = rt.block_on(crate::audit::doctor::run_doctor());
+
+    // stdout is full of scan logging, so the list is set apart at the end
+    println!("\n=== depmgr doctor ===");
+    print!("{}", crate::audit::doctor::render(&findings));
+    let errors = findings
+        .iter()
+        .any(|f| f.severity == crate::audit::doctor::Severity::Error);
+    i32::from(errors)
+}
+
+/// Export or import the configuration bundle
+fn transfer_config(path: &std::path::Path, import: bool) -> i32 {
+    let result = if import {
+        ConfigBundle::import(path)
+    } else {
+        ConfigBundle::export(path)
+    };
+    match result {
+        Ok(()) if import => {
+            println!(
+                "Configuration imported from {} (restart DepMgr if it's open)",
+                path.display()
+            );
+            0
+        }
+        Ok(()) => {
+            println!("Configuration exported to {}", path.display());
+            0
+        }
+        Err(e) => {
+            eprintln!("[ERROR] {}", e);
+            1
+        }
+    }
+}
+
+/// Store a token read from stdin, or remove one
+fn manage_token(host: &str, set: bool) -> i32 {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let result = rt.block_on(async {
+        if !set {
+            return crate::utils::secrets::remove_token(host).await;
+        }
+        eprintln!("Paste the token for {} and press Enter:", host);
+        let mut token = String::new();
+        std::io::stdin().read_line(&mut token)?;
+        let token = token.trim();
+        if token.is_empty() {
+            return Err(anyhow::anyhow!("No token given"));
+        }
+        crate::utils::secrets::set_token(host, token).await
+    });
+    match result {
+        Ok(()) if set => {
+            println!("Token for {} saved in the keychain", host);
+            0
+        }
+        Ok(()) => {
+            println!("Token for {} removed", host);
+            0
+        }
+        Err(e) => {
+            eprintln!("[ERROR] {}", e);
+            1
+        }
+    }
+}
+
+/// Meant for cron/launchd: `depmgr --digest` once a day only writes when due
+fn run_digest(force: bool) -> i32 {
+    let settings = Settings::load();
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    rt.block_on(async {
+        let managers = crate::managers::detect_available_managers().await;
+        let packages = crate::inventory::collect_inventory(&managers).await;
+
+        match crate::report::run_digest_if_due(&packages, &settings.digest, force).await {
+            Ok(Some(path)) => {
+                println!("Digest written to {}", path.display());
+                0
+            }
+            Ok(None) => {
+                println!(
+                    "Digest not due yet (interval: {} days). Use --force to generate anyway.",
+                    settings.digest.interval_days
+                );
+                0
+            }
+            Err(e) => {
+                eprintln!("[ERROR] Failed to generate digest: {}", e);
+                1
+            }
+        }
+    })
+}
+
+/// Inventory of a container/image as a Markdown report, e.g. for auditing base images in CI
+fn run_container_audit(reference: &str, thresholds: &[Threshold], json: bool) -> i32 {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    rt.block_on(async {
+        let target = match open_container(reference).await {
+            Ok(target) => target,
+            Err(e) => {
+                eprintln!("[ERROR] Failed to open {}: {}", reference, e);
+                return 1;
+            }
+        };
+        set_target(target.clone());
+
+        let logs = LogRedirect::to_stderr();
+        let managers = crate::managers::detect_available_managers().await;
+        let packages = crate::inventory::collect_inventory(&managers).await;
+        close_target(&target).await;
+        drop(logs);
+
+        if json {
+            return report_policy(&packages, &[], thresholds, true);
+        }
+
+        let report = crate::report::render_markdown_report(
+            &format!("Container audit: {}", reference),
+            &[],
+            &packages,
+        );
+        // Reports are long; stdout gets where it was written
+        let file_name = format!(
+            "container-{}-{}.md",
+            reference.replace(['/', ':', '@'], "_"),
+            chrono::Local::now().format("%Y-%m-%d")
+        );
+        let path = crate::utils::paths::data_dir()
+            .join("audits")
+            .join(file_name);
+        let written = std::fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| std::fs::write(&path, report));
+        match written {
+            Ok(()) => println!("Report written to {}", path.display()),
+            Err(e) => {
+                eprintln!("[ERROR] Failed to write {}: {}", path.display(), e);
+                return 1;
+            }
+        }
+
+        if thresholds.is_empty() {
+            0
+        } else {
+            report_policy(&packages, &[], thresholds, false)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PackageManager;
+
+    /// clap only checks the argument definitions when parsing, i.e. at runtime
+    #[test]
+    fn arguments_are_well_formed() {
+        use clap::CommandFactory;
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn run_options_conflict_with_commands() {
+        let check = |args: &[&str]| {
+            Cli::try_parse_from(std::iter::once("depmgr").chain(args.iter().copied()))
+                .and_then(|cli| cli.check_conflicts())
+        };
+        assert!(check(&["--digest", "outdated"]).is_err());
+        assert!(check(&["--tui", "scan"]).is_err());
+        assert!(check(&["--fail-on", "vulnerable", "outdated"]).is_err());
+        assert!(check(&["--demo", "outdated", "--json"]).is_ok());
+        assert!(check(&["--fail-on", "vulnerable", "scan"]).is_ok());
+        assert!(check(&["open", "depmgr://view/outdated", "--tui"]).is_ok());
+        assert!(check(&["--tui", "open", "depmgr://view/outdated"]).is_ok());
+    }
+
+    #[test]
+    fn update_arguments_resolve_to_one_package() {
+        let packages = vec![
+            Package::new("jq", PackageManager::Homebrew, "1.7.1"),
+            Package::new("eslint", PackageManager::Npm, "8.56.0"),
+            Package::new("eslint", PackageManager::Pnpm, "8.57.0"),
+        ];
+        let names = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+
+        let resolved = resolve_packages(&packages, &names(&["jq", "pnpm/eslint"])).unwrap();
+        let keys: Vec<String> = resolved.iter().map(|p| p.key()).collect();
+        assert_eq!(keys, ["Homebrew/jq", "pnpm/eslint"]);
+
+        let ambiguous = resolve_packages(&packages, &names(&["eslint"])).unwrap_err();
+        assert!(ambiguous.to_string().contains("npm/eslint, pnpm/eslint"));
+        assert!(resolve_packages(&packages, &names(&["ghost"])).is_err());
+    }
+
+    #[test]
+    fn critical_packages_need_confirming() {
+        let flags: PackageFlags = serde_json::from_str(
+            r#"{"pinned": ["npm/typescript"], "critical": ["Homebrew/postgresql@16"]}"#,
+        )
+        .unwrap();
+        let postgres = Package::new("postgresql@16", PackageManager::Homebrew, "16.1");
+        let typescript = Package::new("typescript", PackageManager::Npm, "5.3.3");
+        let jq = Package::new("jq", PackageManager::Homebrew, "1.7.1");
+
+        assert_eq!(skip_reason(&flags, &postgres, |_| false), Some("critical"));
+        assert_eq!(skip_reason(&flags, &postgres, |_| true), None);
+        assert_eq!(skip_reason(&flags, &typescript, |_| true), Some("pinned"));
+        assert_eq!(skip_reason(&flags, &jq, |_| false), None);
+    }
+}
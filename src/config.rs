@@ -0,0 +1,646 @@
+use crate::models::{Category, CategoryOverrides, PackageFlags, PackageManager, RiskLevel};
+use crate::scanner::ScanBudget;
+use crate::utils::format::SizeUnits;
+use crate::utils::paths::config_dir;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
+
+/// User settings from ~/.config/depmgr/config.toml. Missing keys fall back to defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub scan: ScanSettings,
+    pub descriptions: DescriptionSettings,
+    pub digest: DigestSettings,
+    pub remote: RemoteSettings,
+    pub server: ServerSettings,
+    pub background: BackgroundSettings,
+    pub auto_update: AutoUpdateSettings,
+    pub homebrew: HomebrewSettings,
+    pub limits: LimitSettings,
+    pub cache: CacheSettings,
+    pub ui: UiState,
+    pub sync: SyncSettings,
+    pub secrets: SecretSettings,
+    /// Per-package update/uninstall commands, keyed like pins (`"Cargo/ripgrep"`)
+    pub commands: HashMap<String, CommandOverride>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScanSettings {
+    /// Run `brew update` before outdated checks when local metadata is older than this
+    pub refresh_metadata: bool,
+    pub metadata_max_age_hours: u32,
+    /// Managers never to detect or scan, by name as shown in the sidebar: `["pip", "Julia"]`
+    pub disabled_managers: Vec<String>,
+    /// List packages in project virtualenvs (`.venv`, `venv`) for the Projects tab
+    pub project_venvs: bool,
+    /// Files and folders looked at per scan root before the rest is skipped (0: no limit).
+    /// Recently modified folders are walked first, so what's skipped is the stalest.
+    pub max_files: usize,
+    /// Seconds spent walking each scan root (0: no limit)
+    pub max_seconds_per_dir: u64,
+}
+
+impl Default for ScanSettings {
+    fn default() -> Self {
+        Self {
+            refresh_metadata: true,
+            metadata_max_age_hours: 24,
+            disabled_managers: Vec::new(),
+            project_venvs: false,
+            max_files: 50_000,
+            max_seconds_per_dir: 10,
+        }
+    }
+}
+
+impl ScanSettings {
+    pub fn is_enabled(&self, manager: &PackageManager) -> bool {
+        !self
+            .disabled_managers
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(manager.name()))
+    }
+
+    /// Limits for walking the scan roots, 0 meaning none
+    pub fn budget(&self) -> ScanBudget {
+        let unlimited = ScanBudget::unlimited();
+        ScanBudget {
+            max_files: match self.max_files {
+                0 => unlimited.max_files,
+                n => n,
+            },
+            max_time: match self.max_seconds_per_dir {
+                0 => unlimited.max_time,
+                secs => Duration::from_secs(secs),
+            },
+        }
+    }
+}
+
+/// Where a manager's package descriptions come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DescriptionSource {
+    Cli,  // The manager's own command: `brew info`, `npm view`, `pip show`, `cargo search`
+    Http, // The registry's JSON API
+}
+
+/// Description fetching, which sends one request per package and can hammer proxies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DescriptionSettings {
+    /// Managers whose descriptions are never fetched, by name as shown in the sidebar
+    pub disabled: Vec<String>,
+    /// Per-manager source: `{ npm = "http", Cargo = "cli" }`
+    pub sources: HashMap<String, DescriptionSource>,
+    /// Lookups in flight at once, per manager
+    pub batch_size: usize,
+    /// Fetch every description during the scan instead of as rows come into view
+    pub prefetch: bool,
+}
+
+impl Default for DescriptionSettings {
+    fn default() -> Self {
+        Self {
+            disabled: Vec::new(),
+            sources: HashMap::new(),
+            batch_size: 8,
+            prefetch: false,
+        }
+    }
+}
+
+impl DescriptionSettings {
+    pub fn is_enabled(&self, manager: &PackageManager) -> bool {
+        !self
+            .disabled
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(manager.name()))
+    }
+
+    /// Configured source, else what the backend has always used (crates.io for Cargo)
+    pub fn source(&self, manager: &PackageManager) -> DescriptionSource {
+        self.sources
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(manager.name()))
+            .map(|(_, source)| *source)
+            .unwrap_or(match manager {
+                PackageManager::Cargo => DescriptionSource::Http,
+                _ => DescriptionSource::Cli,
+            })
+    }
+
+    /// Whether scans fetch this manager's descriptions up front
+    pub fn prefetches(&self, manager: &PackageManager) -> bool {
+        self.prefetch && self.is_enabled(manager)
+    }
+
+    /// At least one lookup at a time, whatever the config says
+    pub fn batch_size(&self) -> usize {
+        self.batch_size.max(1)
+    }
+}
+
+/// Periodic environment-drift digest (see `report::digest`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DigestSettings {
+    /// Generate the digest automatically after GUI scans when it's due
+    pub enabled: bool,
+    pub interval_days: u32,
+    /// Where digest files are written (defaults to ~/.local/share/depmgr/digests)
+    pub output_dir: Option<PathBuf>,
+    /// Shell command the digest is piped into, e.g. `mail -s "depmgr digest" me@example.com`
+    pub command: Option<String>,
+}
+
+impl Default for DigestSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_days: 7,
+            output_dir: None,
+            command: None,
+        }
+    }
+}
+
+/// Machines that can be audited over SSH from the host selector
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RemoteSettings {
+    /// ssh destinations, e.g. `["devbox", "me@build-server"]`
+    pub hosts: Vec<String>,
+}
+
+impl RemoteSettings {
+    /// Every host must be one ssh can be pointed at
+    pub fn check(&self) -> Result<()> {
+        for host in &self.hosts {
+            crate::utils::target::ExecTarget::ssh(host)?;
+        }
+        Ok(())
+    }
+}
+
+/// `depmgr --serve`: headless rescans plus an HTTP endpoint for Prometheus
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServerSettings {
+    /// Address to listen on; keep it on localhost unless something else guards access
+    pub listen: String,
+    pub scan_interval_minutes: u32,
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            listen: "127.0.0.1:9464".to_string(),
+            scan_interval_minutes: 60,
+        }
+    }
+}
+
+/// When the daemon's scheduled scans wait for better conditions. Scans you ask
+/// for (Refresh, `--fail-on`) and updates you start always run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackgroundSettings {
+    pub defer_on_battery: bool,
+    /// Hotspots and connections marked metered
+    pub defer_on_metered: bool,
+    /// How often to check again while deferring
+    pub retry_minutes: u32,
+}
+
+impl Default for BackgroundSettings {
+    fn default() -> Self {
+        Self {
+            defer_on_battery: true,
+            defer_on_metered: true,
+            retry_minutes: 15,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HomebrewSettings {
+    /// Count casks that update themselves (`auto_updates`) as outdated and include them
+    /// in Update All, like `brew upgrade --greedy`
+    pub include_auto_updates: bool,
+}
+
+/// How many requests go out at once
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LimitSettings {
+    /// Registry lookups in flight per manager while checking for updates (PyPI, Maven
+    /// Central, pub.dev, the Go proxy, npm dist-tags, bottle sizes)
+    pub registry_lookups: usize,
+}
+
+impl Default for LimitSettings {
+    fn default() -> Self {
+        Self {
+            registry_lookups: 8,
+        }
+    }
+}
+
+/// How long API responses are reused before asking again (see `utils::cache`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheSettings {
+    /// Latest versions from registries and the Homebrew API
+    pub registry_minutes: u64,
+    /// Advisories, changelogs and GitHub releases, which change less often
+    pub release_hours: u64,
+}
+
+impl Default for CacheSettings {
+    fn default() -> Self {
+        Self {
+            registry_minutes: 60,
+            release_hours: 6,
+        }
+    }
+}
+
+impl CacheSettings {
+    pub fn registry_ttl(&self) -> u64 {
+        self.registry_minutes * 60
+    }
+
+    pub fn release_ttl(&self) -> u64 {
+        self.release_hours * 3600
+    }
+}
+
+/// Saved window geometry, in points
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowLayout {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// What the window looked like when the app was last closed; written on exit
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiState {
+    pub search_query: String,
+    /// Managers shown, by name as in the sidebar (None: every detected manager)
+    pub managers: Option<Vec<String>>,
+    pub categories: Vec<Category>,
+    pub outdated_only: bool,
+    pub orphaned_only: bool,
+    pub size_units: SizeUnits,
+    pub details_hidden: bool,
+    pub window: Option<WindowLayout>,
+}
+
+impl UiState {
+    /// Replace only the `[ui]` table in config.toml, keeping the rest of the file and
+    /// its comments as they are. Nothing is written when the file doesn't parse.
+    pub fn save(&self) -> Result<()> {
+        let path = Settings::path();
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let updated = replace_ui_table(&content, self)
+            .map_err(|e| anyhow!("{} doesn't parse, not saving: {}", path.display(), e))?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, updated)?;
+        Ok(())
+    }
+}
+
+fn replace_ui_table(content: &str, state: &UiState) -> Result<String> {
+    let mut doc: toml_edit::DocumentMut = content.parse()?;
+    let ui: toml_edit::DocumentMut = toml::to_string(state)?.parse()?;
+    doc["ui"] = toml_edit::Item::Table(ui.as_table().clone());
+    Ok(doc.to_string())
+}
+
+/// Updates the daemon applies by itself, only inside a maintenance window. Pinned,
+/// ignored and critical packages are never touched.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoUpdateSettings {
+    pub enabled: bool,
+    /// Days the window opens, e.g. `["mon", "tue", "wed", "thu", "fri"]` (empty: every day)
+    pub days: Vec<String>,
+    /// Local times the window opens and closes, "HH:MM"; a window can span midnight
+    pub start: String,
+    pub end: String,
+    /// Riskiest update applied without review: "low", "medium" or "high"
+    pub max_risk: RiskLevel,
+    pub skip_on_battery: bool,
+    /// Process names that mean a call may be on (matched ignoring case)
+    pub meeting_apps: Vec<String>,
+}
+
+impl Default for AutoUpdateSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            days: Vec::new(),
+            start: "12:00".to_string(),
+            end: "13:00".to_string(),
+            max_risk: RiskLevel::Low,
+            skip_on_battery: true,
+            meeting_apps: ["zoom.us", "Microsoft Teams", "Webex", "FaceTime"]
+                .map(String::from)
+                .to_vec(),
+        }
+    }
+}
+
+/// Where pins, ignores, favorites and category overrides are pushed and pulled, for
+/// people with several workstations. Set one of the two.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncSettings {
+    /// Repository you can push to, e.g. `git@github.com:me/depmgr-sync.git`
+    pub git_repo: Option<String>,
+    /// ID of an existing gist, written through `gh` and its login
+    pub gist: Option<String>,
+}
+
+/// Registries and APIs that get a token with every request (private npm registries,
+/// internal package indexes, GitHub for higher rate limits). The tokens themselves live
+/// in the OS keychain; `depmgr --set-token HOST` stores one and adds its host here.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SecretSettings {
+    pub hosts: Vec<String>,
+}
+
+/// Commands that replace the manager's own for one package, for tools that need a
+/// special flag (`cargo install --locked`) or their own updater, and a hook run after
+/// each successful update. `{name}` expands to the package name; commands run
+/// through `sh -c`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommandOverride {
+    pub update: Option<String>,
+    pub uninstall: Option<String>,
+    /// e.g. `brew services restart {name}`; its output goes to the package's history
+    pub after_update: Option<String>,
+}
+
+impl SyncSettings {
+    pub fn is_configured(&self) -> bool {
+        self.git_repo.is_some() || self.gist.is_some()
+    }
+}
+
+/// `[limits]` and `[cache]` are consulted for every registry request, so they're kept in
+/// memory rather than read from the file each time; `Settings::save` refreshes them
+static TUNING: LazyLock<RwLock<(LimitSettings, CacheSettings)>> = LazyLock::new(|| {
+    let settings = Settings::load();
+    RwLock::new((settings.limits, settings.cache))
+});
+
+/// Registry lookups to run at once (at least one)
+pub fn registry_lookups() -> usize {
+    TUNING.read().unwrap().0.registry_lookups.max(1)
+}
+
+pub fn cache_ttls() -> CacheSettings {
+    TUNING.read().unwrap().1.clone()
+}
+
+impl Settings {
+    pub fn path() -> PathBuf {
+        config_dir().join("config.toml")
+    }
+
+    pub fn load() -> Self {
+        let path = Self::path();
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(_) => return Self::default(), // First run, no config yet
+        };
+
+        match toml::from_str(&content) {
+            Ok(settings) => {
+                println!("[CONFIG] Loaded settings from {}", path.display());
+                settings
+            }
+            Err(e) => {
+                eprintln!(
+                    "[ERROR] Invalid config at {}, using defaults: {}",
+                    path.display(),
+                    e
+                );
+                Self::default()
+            }
+        }
+    }
+
+    /// The command override configured for a package, if any
+    pub fn command_override(
+        &self,
+        manager: &PackageManager,
+        name: &str,
+    ) -> Option<&CommandOverride> {
+        self.commands.get(&format!("{}/{}", manager.name(), name))
+    }
+
+    /// Write config.toml (comments in the old file are lost)
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, toml::to_string_pretty(self)?)?;
+        *TUNING.write().unwrap() = (self.limits.clone(), self.cache.clone());
+        Ok(())
+    }
+}
+
+/// Everything you set up in DepMgr as one TOML file, for syncing between machines
+/// through dotfiles: settings, pins, ignores and favorites, category overrides and
+/// added scan roots
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfigBundle {
+    pub scan_roots: Vec<PathBuf>,
+    pub settings: Settings,
+    pub flags: PackageFlags,
+    pub categories: CategoryOverrides,
+}
+
+impl ConfigBundle {
+    pub fn current() -> Self {
+        Self {
+            scan_roots: crate::scanner::added_scan_roots(),
+            settings: Settings::load(),
+            flags: PackageFlags::load(),
+            categories: CategoryOverrides::load(),
+        }
+    }
+
+    pub fn export(path: &std::path::Path) -> Result<()> {
+        let toml = toml::to_string_pretty(&Self::current())?;
+        std::fs::write(path, toml).map_err(|e| anyhow!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Replace this machine's configuration with the file's; sections missing from it
+    /// reset to defaults
+    pub fn import(path: &std::path::Path) -> Result<()> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read {}: {}", path.display(), e))?;
+        let bundle: Self =
+            toml::from_str(&content).map_err(|e| anyhow!("Invalid {}: {}", path.display(), e))?;
+        bundle
+            .settings
+            .remote
+            .check()
+            .map_err(|e| anyhow!("Invalid [remote] hosts in {}: {}", path.display(), e))?;
+        bundle.settings.save()?;
+        bundle.flags.save();
+        bundle.categories.save();
+        crate::scanner::set_added_scan_roots(&bundle.scan_roots);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundle_round_trips_through_toml() {
+        let exported = r#"
+            scan_roots = ["/home/me/src"]
+
+            [settings.scan]
+            max_files = 1000
+            disabled_managers = ["Julia"]
+
+            [settings.descriptions.sources]
+            npm = "http"
+
+            [settings.commands."Cargo/ripgrep"]
+            update = "cargo install --locked {name}"
+
+            [settings.commands."Homebrew/postgresql@16"]
+            after_update = "brew services restart {name}"
+
+            [settings.cache]
+            registry_minutes = 30
+
+            [settings.ui]
+            search_query = "python"
+            managers = ["Homebrew", "npm"]
+            window = { x = 40.0, y = 60.0, width = 1400.0, height = 900.0 }
+
+            [settings.auto_update]
+            enabled = true
+            days = ["mon", "fri"]
+            max_risk = "medium"
+
+            [flags]
+            pinned = ["npm/typescript"]
+            ignored = ["Homebrew/python@3.11"]
+
+            [categories]
+            "Homebrew/ffmpeg" = "Cli"
+        "#;
+        let bundle: ConfigBundle = toml::from_str(exported).unwrap();
+        let bundle: ConfigBundle =
+            toml::from_str(&toml::to_string_pretty(&bundle).unwrap()).unwrap();
+
+        assert_eq!(bundle.scan_roots, [PathBuf::from("/home/me/src")]);
+        assert_eq!(bundle.settings.scan.max_files, 1000);
+        assert_eq!(bundle.settings.scan.max_seconds_per_dir, 10);
+        assert!(!bundle.settings.scan.is_enabled(&PackageManager::Julia));
+        let ripgrep = bundle
+            .settings
+            .command_override(&PackageManager::Cargo, "ripgrep")
+            .unwrap();
+        assert_eq!(
+            ripgrep.update.as_deref(),
+            Some("cargo install --locked {name}")
+        );
+        assert!(ripgrep.uninstall.is_none());
+        let postgres = bundle
+            .settings
+            .command_override(&PackageManager::Homebrew, "postgresql@16")
+            .unwrap();
+        assert!(postgres.update.is_none());
+        assert!(postgres.after_update.is_some());
+        assert_eq!(bundle.settings.cache.registry_ttl(), 1800);
+        assert_eq!(bundle.settings.cache.release_hours, 6);
+        assert_eq!(bundle.settings.limits.registry_lookups, 8);
+        let ui = &bundle.settings.ui;
+        assert_eq!(ui.search_query, "python");
+        assert_eq!(ui.managers.as_deref().unwrap(), ["Homebrew", "npm"]);
+        assert!(!ui.outdated_only);
+        assert_eq!(ui.window.unwrap().width, 1400.0);
+        assert!(bundle.settings.auto_update.enabled);
+        assert_eq!(bundle.settings.auto_update.max_risk, RiskLevel::Medium);
+        assert_eq!(bundle.settings.auto_update.start, "12:00");
+        assert_eq!(
+            bundle.settings.descriptions.source(&PackageManager::Npm),
+            DescriptionSource::Http
+        );
+        let typescript = crate::models::Package::new("typescript", PackageManager::Npm, "5.0.0");
+        assert!(bundle.flags.is_pinned(&typescript));
+        assert!(!bundle.flags.is_ignored(&typescript));
+        let ffmpeg = crate::models::Package::new("ffmpeg", PackageManager::Homebrew, "7.0");
+        assert!(bundle.categories.is_overridden(&ffmpeg));
+    }
+
+    #[test]
+    fn ui_state_replaces_only_its_table() {
+        let config =
+            "# tuned by hand\n[scan]\nmax_files = 1000 # plenty\n\n[ui]\nsearch_query = \"old\"\n";
+        let state = UiState {
+            search_query: "python".into(),
+            outdated_only: true,
+            window: Some(WindowLayout {
+                x: 40.0,
+                y: 60.0,
+                width: 1400.0,
+                height: 900.0,
+            }),
+            ..UiState::default()
+        };
+        let updated = replace_ui_table(config, &state).unwrap();
+        assert!(updated.starts_with("# tuned by hand\n[scan]\nmax_files = 1000 # plenty\n"));
+        let settings: Settings = toml::from_str(&updated).unwrap();
+        assert_eq!(settings.scan.max_files, 1000);
+        assert_eq!(settings.ui.search_query, "python");
+        assert!(settings.ui.outdated_only);
+        assert_eq!(settings.ui.window.unwrap().height, 900.0);
+
+        assert!(replace_ui_table("[scan\nmax_files = ", &state).is_err());
+    }
+
+    #[test]
+    fn remote_hosts_that_look_like_ssh_options_are_refused() {
+        let remote = |hosts: &[&str]| RemoteSettings {
+            hosts: hosts.iter().map(|h| h.to_string()).collect(),
+        };
+        assert!(remote(&["devbox", "me@build-server"]).check().is_ok());
+        assert!(remote(&["devbox", "-oProxyCommand=touch /tmp/x"])
+            .check()
+            .is_err());
+    }
+}
@@ -0,0 +1,223 @@
+use crate::models::{Package, PackageManager, Project};
+use crate::server::ScanState;
+use crate::utils::paths::data_dir;
+use crate::utils::target::current_target;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// One request per connection, as a line of JSON; the answer comes back the same way
+#[derive(Debug, Serialize, Deserialize)]
+pub enum Request {
+    /// The last scan's inventory (waits for the first scan after startup)
+    Inventory,
+    /// The projects the last scan matched the inventory against
+    Projects,
+    /// Scan now and reply once it's done
+    Rescan,
+    Update {
+        manager: PackageManager,
+        name: String,
+    },
+    Install {
+        manager: PackageManager,
+        name: String,
+    },
+    Uninstall {
+        manager: PackageManager,
+        name: String,
+        #[serde(default)]
+        version: Option<String>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum Response {
+    Inventory(Vec<Package>),
+    Projects(Vec<Project>),
+    Done,
+    Error(String),
+}
+
+/// Set when this process found a running daemon and hands scans and operations to it
+static CONNECTED: AtomicBool = AtomicBool::new(false);
+
+pub fn socket_path() -> PathBuf {
+    data_dir().join("daemon.sock")
+}
+
+/// Whether scans and operations go to the daemon. It only knows this machine.
+pub fn is_connected() -> bool {
+    CONNECTED.load(Ordering::Relaxed) && current_target().is_local()
+}
+
+/// Use a running daemon if there is one; the GUI and CLI call this at startup
+pub fn connect() {
+    #[cfg(unix)]
+    if std::os::unix::net::UnixStream::connect(socket_path()).is_ok() {
+        println!("[DAEMON] Using the daemon at {}", socket_path().display());
+        CONNECTED.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Stop using the daemon after it failed to answer
+pub fn disconnect() {
+    if CONNECTED.swap(false, Ordering::Relaxed) {
+        println!("[DAEMON] Disconnected from {}", socket_path().display());
+    }
+}
+
+#[cfg(unix)]
+pub async fn request(request: &Request) -> Result<Response> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let mut stream = tokio::net::UnixStream::connect(socket_path())
+        .await
+        .map_err(|e| anyhow!("Daemon not reachable: {}", e))?;
+    let mut line = serde_json::to_string(request)?;
+    line.push('\n');
+    stream.write_all(line.as_bytes()).await?;
+
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply).await?;
+    Ok(serde_json::from_str(&reply)?)
+}
+
+#[cfg(not(unix))]
+pub async fn request(_request: &Request) -> Result<Response> {
+    Err(anyhow!("The daemon needs Unix domain sockets"))
+}
+
+pub async fn fetch_inventory() -> Result<Vec<Package>> {
+    match request(&Request::Inventory).await? {
+        Response::Inventory(packages) => Ok(packages),
+        Response::Error(e) => Err(anyhow!(e)),
+        _ => Err(anyhow!("Unexpected reply from the daemon")),
+    }
+}
+
+pub async fn fetch_projects() -> Result<Vec<Project>> {
+    match request(&Request::Projects).await? {
+        Response::Projects(projects) => Ok(projects),
+        Response::Error(e) => Err(anyhow!(e)),
+        _ => Err(anyhow!("Unexpected reply from the daemon")),
+    }
+}
+
+/// Run an operation in the daemon so its inventory stays the one everybody sees
+pub async fn forward(request: Request) -> Result<()> {
+    match self::request(&request).await? {
+        Response::Done | Response::Inventory(_) | Response::Projects(_) => Ok(()),
+        Response::Error(e) => Err(anyhow!(e)),
+    }
+}
+
+#[cfg(unix)]
+pub async fn bind() -> Result<tokio::net::UnixListener> {
+    let path = socket_path();
+    if tokio::net::UnixStream::connect(&path).await.is_ok() {
+        return Err(anyhow!(
+            "A daemon is already listening on {}",
+            path.display()
+        ));
+    }
+    // Left behind by a daemon that didn't shut down cleanly
+    let _ = std::fs::remove_file(&path);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let listener = tokio::net::UnixListener::bind(&path)?;
+    println!("[DAEMON] Listening on {}", path.display());
+    Ok(listener)
+}
+
+#[cfg(unix)]
+pub async fn serve(listener: tokio::net::UnixListener, state: Arc<ScanState>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                let state = Arc::clone(&state);
+                tokio::spawn(async move {
+                    if let Err(e) = handle(stream, state).await {
+                        eprintln!("[DAEMON] Request failed: {}", e);
+                    }
+                });
+            }
+            Err(e) => eprintln!("[DAEMON] Accept failed: {}", e),
+        }
+    }
+}
+
+#[cfg(unix)]
+async fn handle(stream: tokio::net::UnixStream, state: Arc<ScanState>) -> Result<()> {
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).await?;
+    if line.trim().is_empty() {
+        return Ok(()); // `connect()` checking that we're up
+    }
+    let request: Request = serde_json::from_str(&line)?;
+    println!("[DAEMON] {:?}", request);
+
+    let operation = match request {
+        Request::Inventory => {
+            state.wait_for_first_scan().await;
+            None
+        }
+        Request::Projects => {
+            state.wait_for_first_scan().await;
+            let projects = state.projects.read().await.clone();
+            return reply(reader.get_mut(), &Response::Projects(projects)).await;
+        }
+        Request::Rescan => {
+            state.rescan().await;
+            None
+        }
+        Request::Update { manager, name } => {
+            Some(crate::operations::update_package(&manager, name).await)
+        }
+        Request::Install { manager, name } => {
+            Some(crate::operations::install_package(&manager, name).await)
+        }
+        Request::Uninstall {
+            manager,
+            name,
+            version,
+        } => Some(crate::operations::uninstall_package(&manager, name, version).await),
+    };
+
+    let response = match operation {
+        None => Response::Inventory(state.inventory.read().await.clone()),
+        Some(Ok(())) => {
+            // Versions changed; rescan in the background so every frontend catches up
+            let state = Arc::clone(&state);
+            tokio::spawn(async move { state.rescan().await });
+            Response::Done
+        }
+        Some(Err(e)) => Response::Error(e.to_string()),
+    };
+
+    reply(reader.get_mut(), &response).await
+}
+
+#[cfg(unix)]
+async fn reply(stream: &mut tokio::net::UnixStream, response: &Response) -> Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut line = serde_json::to_string(response)?;
+    line.push('\n');
+    stream.write_all(line.as_bytes()).await?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub async fn bind() -> Result<()> {
+    Err(anyhow!("The daemon needs Unix domain sockets"))
+}
+
+#[cfg(not(unix))]
+pub async fn serve(_listener: (), _state: Arc<ScanState>) {}
@@ -0,0 +1,366 @@
+use crate::app::View;
+use crate::models::{Package, PackageManager};
+use crate::utils::format::{percent_decode, percent_encode};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+const SCHEME: &str = "depmgr://";
+
+/// Where a `depmgr://` link points: a package, or a sidebar view
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeepLink {
+    /// `depmgr://package/homebrew/jq`, `depmgr://package/npm/@types/node`
+    Package {
+        manager: PackageManager,
+        name: String,
+    },
+    /// `depmgr://view/outdated`, `depmgr://view/vulnerable`, `depmgr://view/npm`
+    View { view: View, outdated_only: bool },
+}
+
+/// Lowercase letters and digits only, so "gh extensions", "gh-extensions" and
+/// "GhExtensions" all match
+fn slug(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// By display name ("gh extensions") or enum name ("GhExtensions"), in any case
+fn manager_from_slug(text: &str) -> Option<PackageManager> {
+    let wanted = slug(text);
+    PackageManager::ALL
+        .into_iter()
+        .find(|m| slug(m.name()) == wanted || slug(&format!("{:?}", m)) == wanted)
+}
+
+impl DeepLink {
+    /// Parse a `depmgr://` URL; the error says what's wrong with it
+    pub fn parse(url: &str) -> Result<Self, String> {
+        let rest = url
+            .get(..SCHEME.len())
+            .filter(|scheme| scheme.eq_ignore_ascii_case(SCHEME))
+            .map(|_| &url[SCHEME.len()..])
+            .ok_or_else(|| format!("'{}' isn't a {} link", url, SCHEME))?;
+        // Query strings and fragments carry nothing yet
+        let rest = rest.split(['?', '#']).next().unwrap_or_default();
+        let rest = rest.trim_end_matches('/');
+        let (kind, target) = rest.split_once('/').unwrap_or((rest, ""));
+
+        match kind.to_ascii_lowercase().as_str() {
+            "package" => {
+                let (manager, name) = target
+                    .split_once('/')
+                    .filter(|(_, name)| !name.is_empty())
+                    .ok_or("expected depmgr://package/<manager>/<name>")?;
+                let manager = manager_from_slug(&percent_decode(manager))
+                    .ok_or_else(|| format!("unknown package manager '{}'", manager))?;
+                Ok(DeepLink::Package {
+                    manager,
+                    name: percent_decode(name),
+                })
+            }
+            "view" => {
+                let target = percent_decode(target);
+                let view = match slug(&target).as_str() {
+                    "" => return Err("expected depmgr://view/<view>".to_string()),
+                    "all" | "outdated" => View::All,
+                    "vulnerable" => View::Vulnerable,
+                    "unused" => View::Unused,
+                    "pinned" => View::Pinned,
+                    "projects" => View::Projects,
+                    "history" => View::History,
+                    "recent" => View::Recent,
+                    "scanreport" => View::ScanReport,
+                    _ => View::Manager(
+                        manager_from_slug(&target)
+                            .ok_or_else(|| format!("unknown view '{}'", target))?,
+                    ),
+                };
+                Ok(DeepLink::View {
+                    view,
+                    outdated_only: slug(&target) == "outdated",
+                })
+            }
+            _ => Err(format!(
+                "unknown link '{}': expected depmgr://package/… or depmgr://view/…",
+                url
+            )),
+        }
+    }
+
+    /// The link that opens `pkg`, e.g. to paste into notes
+    pub fn to_package(pkg: &Package) -> String {
+        let manager = pkg.manager.name().to_lowercase().replace(' ', "-");
+        format!(
+            "{}package/{}/{}",
+            SCHEME,
+            manager,
+            percent_encode(&pkg.name)
+        )
+    }
+}
+
+/// Where the open window listens for launches that should go to it instead
+fn socket_path() -> PathBuf {
+    crate::utils::paths::data_dir().join("gui.sock")
+}
+
+/// Hand a launch to the window that's already open, so there's only ever one writing
+/// config and caches; `None` just brings it to the front. False when none is open.
+#[cfg(unix)]
+pub fn forward(link: Option<&DeepLink>) -> bool {
+    use std::io::Write;
+
+    let Ok(mut stream) = std::os::unix::net::UnixStream::connect(socket_path()) else {
+        return false;
+    };
+    let Ok(mut line) = serde_json::to_string(&link) else {
+        return false;
+    };
+    line.push('\n');
+    stream.write_all(line.as_bytes()).is_ok()
+}
+
+#[cfg(not(unix))]
+pub fn forward(_link: Option<&DeepLink>) -> bool {
+    false
+}
+
+/// Take launches handed over by `forward` until the app exits; each lands in `launches`
+#[cfg(unix)]
+pub async fn listen(launches: Arc<RwLock<Vec<Option<DeepLink>>>>) {
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    let path = socket_path();
+    if tokio::net::UnixStream::connect(&path).await.is_ok() {
+        return; // Another window opened at the same time and got there first
+    }
+    // Left behind by a window that didn't close cleanly
+    let _ = std::fs::remove_file(&path);
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let listener = match tokio::net::UnixListener::bind(&path) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("[LINK] Can't listen on {}: {}", path.display(), e);
+            return;
+        }
+    };
+    loop {
+        let Ok((stream, _)) = listener.accept().await else {
+            continue;
+        };
+        let mut line = String::new();
+        if BufReader::new(stream).read_line(&mut line).await.is_err() {
+            continue;
+        }
+        match serde_json::from_str(&line) {
+            Ok(link) => launches.write().await.push(link),
+            Err(e) => eprintln!("[LINK] Ignoring a handed-over launch: {}", e),
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub async fn listen(_launches: Arc<RwLock<Vec<Option<DeepLink>>>>) {}
+
+/// Make the OS hand `depmgr://` links to this binary (`depmgr open <URL>`); returns
+/// what was registered
+#[cfg(target_os = "linux")]
+pub fn register() -> anyhow::Result<String> {
+    let exe = std::env::current_exe()?;
+    // data_dir() is $XDG_DATA_HOME/depmgr; handlers live in $XDG_DATA_HOME/applications
+    let applications = crate::utils::paths::data_dir()
+        .parent()
+        .map(|share| share.join("applications"))
+        .ok_or_else(|| anyhow::anyhow!("No data directory"))?;
+    std::fs::create_dir_all(&applications)?;
+    let desktop = applications.join("depmgr-url-handler.desktop");
+    std::fs::write(
+        &desktop,
+        format!(
+            "[Desktop Entry]\nType=Application\nName=DepMgr\nExec=\"{}\" open %u\n\
+             NoDisplay=true\nMimeType=x-scheme-handler/depmgr;\n",
+            exe.display()
+        ),
+    )?;
+    let status = std::process::Command::new("xdg-mime")
+        .args([
+            "default",
+            "depmgr-url-handler.desktop",
+            "x-scheme-handler/depmgr",
+        ])
+        .status()
+        .map_err(|e| anyhow::anyhow!("Failed to run xdg-mime: {}", e))?;
+    if !status.success() {
+        return Err(anyhow::anyhow!("xdg-mime failed ({})", status));
+    }
+    Ok(format!("{} handles depmgr:// links", desktop.display()))
+}
+
+#[cfg(windows)]
+pub fn register() -> anyhow::Result<String> {
+    let exe = std::env::current_exe()?;
+    let key = r"HKCU\Software\Classes\depmgr";
+    let command = format!("\"{}\" open \"%1\"", exe.display());
+    let entries = [
+        (key.to_string(), None, "URL:DepMgr link".to_string()),
+        (key.to_string(), Some("URL Protocol"), String::new()),
+        (format!(r"{}\shell\open\command", key), None, command),
+    ];
+    for (key, value, data) in entries {
+        let mut reg = std::process::Command::new("reg");
+        reg.args(["add", &key]);
+        match value {
+            Some(value) => reg.args(["/v", value]),
+            None => reg.arg("/ve"),
+        };
+        let status = reg.args(["/d", &data, "/f"]).status()?;
+        if !status.success() {
+            return Err(anyhow::anyhow!("reg add {} failed ({})", key, status));
+        }
+    }
+    Ok(format!("{} handles depmgr:// links", key))
+}
+
+/// macOS hands URLs only to an app bundle that declares the scheme, and as an Apple
+/// Event rather than an argument: build a small AppleScript applet in ~/Applications
+/// that passes them on to `depmgr open`
+#[cfg(target_os = "macos")]
+pub fn register() -> anyhow::Result<String> {
+    let exe = std::env::current_exe()?;
+    let app = crate::utils::paths::home_dir()
+        .join("Applications")
+        .join("DepMgr Links.app");
+    let exe = exe
+        .display()
+        .to_string()
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"");
+    let script = format!(
+        "on open location link\n\
+         \tdo shell script quoted form of \"{}\" & \" open \" & quoted form of link & \" > /dev/null 2>&1 &\"\n\
+         end open location\n",
+        exe
+    );
+    if app.exists() {
+        std::fs::remove_dir_all(&app)?;
+    }
+    if let Some(parent) = app.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let script_path = crate::utils::paths::data_dir().join("open-links.applescript");
+    if let Some(parent) = script_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&script_path, script)?;
+
+    let run = |program: &str, args: &[&str]| -> anyhow::Result<()> {
+        let status = std::process::Command::new(program)
+            .args(args)
+            .status()
+            .map_err(|e| anyhow::anyhow!("Failed to run {}: {}", program, e))?;
+        if !status.success() {
+            return Err(anyhow::anyhow!("{} failed ({})", program, status));
+        }
+        Ok(())
+    };
+    let app_path = app.display().to_string();
+    let compiled = run(
+        "osacompile",
+        &["-o", &app_path, &script_path.display().to_string()],
+    );
+    let _ = std::fs::remove_file(&script_path);
+    compiled?;
+    let plist = app
+        .join("Contents")
+        .join("Info.plist")
+        .display()
+        .to_string();
+    // Not every macOS version's applets come with an identifier
+    let _ = run(
+        "/usr/libexec/PlistBuddy",
+        &["-c", "Delete :CFBundleIdentifier", &plist],
+    );
+    for command in [
+        "Add :CFBundleIdentifier string dev.depmgr.links",
+        "Add :LSUIElement bool true",
+        "Add :CFBundleURLTypes array",
+        "Add :CFBundleURLTypes:0 dict",
+        "Add :CFBundleURLTypes:0:CFBundleURLName string dev.depmgr.links",
+        "Add :CFBundleURLTypes:0:CFBundleURLSchemes array",
+        "Add :CFBundleURLTypes:0:CFBundleURLSchemes:0 string depmgr",
+    ] {
+        run("/usr/libexec/PlistBuddy", &["-c", command, &plist])?;
+    }
+    run(
+        "/System/Library/Frameworks/CoreServices.framework/Frameworks/LaunchServices.framework/Support/lsregister",
+        &["-f", &app_path],
+    )?;
+    Ok(format!("{} handles depmgr:// links", app.display()))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+pub fn register() -> anyhow::Result<String> {
+    Err(anyhow::anyhow!(
+        "depmgr:// links can't be registered on this platform; use `depmgr open <URL>` \
+         from scripts instead"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_parse_and_round_trip() {
+        assert_eq!(
+            DeepLink::parse("depmgr://package/homebrew/jq"),
+            Ok(DeepLink::Package {
+                manager: PackageManager::Homebrew,
+                name: "jq".to_string()
+            })
+        );
+        assert_eq!(
+            DeepLink::parse("DEPMGR://package/npm/%40types%2Fnode/"),
+            DeepLink::parse("depmgr://package/Npm/@types/node")
+        );
+        assert_eq!(
+            DeepLink::parse("depmgr://view/outdated"),
+            Ok(DeepLink::View {
+                view: View::All,
+                outdated_only: true
+            })
+        );
+        assert_eq!(
+            DeepLink::parse("depmgr://view/gh-extensions"),
+            Ok(DeepLink::View {
+                view: View::Manager(PackageManager::GhExtensions),
+                outdated_only: false
+            })
+        );
+        assert!(DeepLink::parse("depmgr://package/homebrew").is_err());
+        assert!(DeepLink::parse("depmgr://view/nonsense").is_err());
+        assert!(DeepLink::parse("https://package/homebrew/jq").is_err());
+
+        for pkg in [
+            Package::new("@scope/a b", PackageManager::GhExtensions, "1.0.0"),
+            Package::new("50%", PackageManager::Mas, "1.0.0"),
+            Package::new("@scope/pkg", PackageManager::Npm, "1.0.0"),
+            Package::new("what?#now&then/", PackageManager::Cargo, "1.0.0"),
+        ] {
+            assert_eq!(
+                DeepLink::parse(&DeepLink::to_package(&pkg)),
+                Ok(DeepLink::Package {
+                    manager: pkg.manager.clone(),
+                    name: pkg.name.clone()
+                })
+            );
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use crate::models::{Package, PackageManager, Project};
+use crate::utils::metrics;
+use crate::utils::paths::data_dir;
+use crate::utils::target::current_target;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// Collect the full inventory in one pass, without the GUI's progressive phases.
+/// Used by headless runs (digest, CLI) where nothing is shown until the end.
+pub async fn collect_inventory(managers: &[PackageManager]) -> Vec<Package> {
+    collect_scan(managers).await.packages
+}
+
+/// Everything one headless scan found
+#[derive(Default)]
+pub struct Scan {
+    pub packages: Vec<Package>,
+    /// Projects under the scan directories (local scans only, or the daemon's)
+    pub projects: Vec<Project>,
+    /// Managers whose listing failed, and why
+    pub failures: Vec<(PackageManager, String)>,
+}
+
+/// The inventory along with the projects it was matched against, from the current backend
+pub async fn collect_scan(managers: &[PackageManager]) -> Scan {
+    crate::managers::backend::current()
+        .collect_scan(managers)
+        .await
+}
+
+/// `SystemBackend`'s scan: the daemon's inventory when connected, else each manager's listing
+pub(crate) async fn collect_system_scan(managers: &[PackageManager]) -> Scan {
+    let mut failures = Vec::new();
+    if crate::daemon::is_connected() {
+        match crate::daemon::fetch_inventory().await {
+            Ok(packages) => {
+                let packages = packages
+                    .into_iter()
+                    .filter(|p| managers.contains(&p.manager))
+                    .collect();
+                // A daemon from before projects were shared can't answer this
+                let projects = crate::daemon::fetch_projects().await.unwrap_or_else(|e| {
+                    eprintln!("[DAEMON] No projects from the daemon: {}", e);
+                    Vec::new()
+                });
+                return Scan {
+                    packages,
+                    projects,
+                    ..Scan::default()
+                };
+            }
+            Err(e) => eprintln!("[DAEMON] {}, scanning here instead", e),
+        }
+    }
+
+    let mut inventory = Vec::new();
+    // Project usage and disk sizes come from this machine's filesystem
+    let local = current_target().is_local();
+    let budget = crate::config::Settings::load().scan.budget();
+
+    for manager in managers {
+        let started = Instant::now();
+        let result = match manager {
+            PackageManager::Homebrew => {
+                match crate::managers::homebrew_fast::list_homebrew_packages_fast().await {
+                    Ok(mut packages) => {
+                        if local {
+                            let scan_dirs = crate::scanner::get_scan_directories();
+                            crate::scanner::scan_homebrew_tool_usage(
+                                &mut packages,
+                                &scan_dirs,
+                                &budget,
+                            );
+                        }
+                        let _ = crate::managers::homebrew_fast::check_outdated_packages_fast(
+                            &mut packages,
+                        )
+                        .await;
+                        crate::managers::homebrew_fast::add_bottle_sizes(&mut packages).await;
+                        if local {
+                            if let Err(e) =
+                                crate::managers::homebrew_fast::add_installed_sizes(&mut packages)
+                                    .await
+                            {
+                                eprintln!("[ERROR] Failed to measure Homebrew sizes: {}", e);
+                            }
+                            crate::utils::arch::add_architectures(&mut packages).await;
+                            crate::utils::broken::add_broken_binaries(manager, &mut packages).await;
+                        }
+                        Ok(packages)
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            PackageManager::Npm => match crate::managers::npm::list_npm_packages().await {
+                Ok(mut packages) => {
+                    let _ = crate::managers::npm::check_outdated_npm(&mut packages).await;
+                    if local {
+                        if let Err(e) = crate::managers::npm::add_npm_sizes(&mut packages).await {
+                            eprintln!("[ERROR] Failed to measure npm sizes: {}", e);
+                        }
+                        crate::utils::broken::add_broken_binaries(manager, &mut packages).await;
+                    }
+                    Ok(packages)
+                }
+                Err(e) => Err(e),
+            },
+            PackageManager::Cargo => match crate::managers::cargo::list_cargo_packages().await {
+                Ok(mut packages) => {
+                    let _ = crate::managers::cargo::check_outdated_cargo(&mut packages).await;
+                    if local {
+                        crate::utils::arch::add_architectures(&mut packages).await;
+                        crate::utils::broken::add_broken_binaries(manager, &mut packages).await;
+                    }
+                    Ok(packages)
+                }
+                Err(e) => Err(e),
+            },
+            PackageManager::Pip => match crate::managers::pip::list_pip_packages().await {
+                Ok(mut packages) => {
+                    let _ = crate::managers::pip::check_outdated_pip(&mut packages).await;
+                    Ok(packages)
+                }
+                Err(e) => Err(e),
+            },
+            other => match crate::managers::list_with_outdated(other).await {
+                Some(result) => result,
+                None => continue, // Detected but no backend yet
+            },
+        };
+
+        let labels = [("manager", manager.name())];
+        metrics::observe("depmgr_scan_duration_seconds", &labels, started.elapsed());
+        match result {
+            Ok(packages) => {
+                println!(
+                    "[DEBUG] Found {} {} packages",
+                    packages.len(),
+                    manager.name()
+                );
+                inventory.extend(packages);
+            }
+            Err(e) => {
+                metrics::inc_counter("depmgr_scan_failures_total", &labels);
+                eprintln!("[ERROR] Failed to list {} packages: {}", manager.name(), e);
+                failures.push((manager.clone(), e.to_string()));
+            }
+        }
+    }
+
+    crate::managers::version_managers::mark_managed_tools(&mut inventory);
+
+    let mut projects = Vec::new();
+    if local {
+        (projects, _) =
+            crate::scanner::scan_projects_indexed(&crate::scanner::get_scan_directories(), &budget);
+        crate::scanner::apply_dependency_usage(&mut inventory, &projects);
+        crate::scanner::apply_tool_usage(&mut inventory, &projects);
+        crate::scanner::check_tool_requirements(&mut inventory, &projects);
+        crate::scanner::apply_project_refs(&mut inventory);
+    }
+
+    if let Err(e) = crate::audit::add_vulnerabilities(&mut inventory).await {
+        eprintln!("[ERROR] Vulnerability check failed: {}", e);
+    }
+
+    Scan {
+        packages: inventory,
+        projects,
+        failures,
+    }
+}
+
+/// The last complete scan, shown at startup while the next one runs
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    scanned_at: DateTime<Utc>,
+    packages: Vec<Package>,
+}
+
+fn snapshot_path() -> std::path::PathBuf {
+    data_dir().join("inventory.json")
+}
+
+pub fn save_snapshot(packages: &[Package]) {
+    let snapshot = Snapshot {
+        scanned_at: Utc::now(),
+        packages: packages.to_vec(),
+    };
+    let path = snapshot_path();
+    let written = std::fs::create_dir_all(data_dir())
+        .map_err(anyhow::Error::from)
+        .and_then(|_| Ok(serde_json::to_vec(&snapshot)?))
+        .and_then(|json| Ok(std::fs::write(&path, json)?));
+    if let Err(e) = written {
+        eprintln!("[ERROR] Failed to save {}: {}", path.display(), e);
+    }
+}
+
+pub fn load_snapshot() -> Option<(DateTime<Utc>, Vec<Package>)> {
+    let json = std::fs::read(snapshot_path()).ok()?;
+    match serde_json::from_slice::<Snapshot>(&json) {
+        Ok(snapshot) => Some((snapshot.scanned_at, snapshot.packages)),
+        Err(e) => {
+            eprintln!("[ERROR] Ignoring unreadable inventory snapshot: {}", e);
+            None
+        }
+    }
+}
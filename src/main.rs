@@ -1,7 +1,18 @@
 mod app;
+mod audit;
+mod cli;
+mod config;
+mod daemon;
+mod deep_link;
+mod inventory;
 mod managers;
 mod models;
+mod operations;
+mod policy;
+mod report;
 mod scanner;
+mod server;
+mod tui;
 mod ui;
 mod utils;
 
@@ -9,46 +20,46 @@ use app::DepMgrApp;
 use eframe::egui;
 
 fn main() -> eframe::Result<()> {
+    use clap::Parser;
+    let cli = cli::Cli::parse();
+    let link = cli.link();
+    if let Some(code) = cli::run_headless(cli) {
+        std::process::exit(code);
+    }
+    // One window at a time: a second launch goes to the open one
+    if deep_link::forward(link.as_ref()) {
+        println!("[LINK] Handed over to the DepMgr window that's already open");
+        return Ok(());
+    }
+
+    let mut viewport = egui::ViewportBuilder::default()
+        .with_title("Dependency Manager")
+        .with_inner_size([1200.0, 800.0])
+        .with_min_inner_size([800.0, 600.0]);
+    // Where the window was when the app was last closed
+    if let Some(layout) = config::Settings::load().ui.window {
+        viewport = viewport
+            .with_inner_size([layout.width, layout.height])
+            .with_position([layout.x, layout.y]);
+    }
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default()
-            .with_title("Dependency Manager")
-            .with_inner_size([1200.0, 800.0])
-            .with_min_inner_size([800.0, 600.0]),
+        viewport,
         ..Default::default()
     };
 
+    // The dashboard lists commands waiting on stdin and sends the user's answers
+    utils::prompt::show_prompts();
+
     eframe::run_native(
         "Dependency Manager",
         options,
-        Box::new(|_cc| {
-            // Initialize app with default state
-            let mut app = DepMgrApp::default();
-
-            // Create a temporary runtime for initial setup
-            let rt = tokio::runtime::Runtime::new().unwrap();
-            rt.block_on(async {
-                // Detect available package managers
-                app.available_managers = crate::managers::detect_available_managers().await;
-                println!(
-                    "[DEBUG] Found {} package managers",
-                    app.available_managers.len()
-                );
-                app.selected_managers = app.available_managers.iter().cloned().collect();
-            });
-
-            // Start the initial scan asynchronously (non-blocking)
-            app.start_scan();
-
+        Box::new(move |_cc| {
+            let mut app = DepMgrApp::start();
+            app.listen_for_launches();
+            if let Some(link) = &link {
+                app.open_link(link);
+            }
             Ok(Box::new(app))
         }),
     )
 }
-
-impl eframe::App for DepMgrApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Handle refresh requests
-        self.handle_refresh();
-
-        ui::show_dashboard(ctx, self);
-    }
-}
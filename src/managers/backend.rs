@@ -0,0 +1,109 @@
+use crate::inventory::Scan;
+use crate::models::PackageManager;
+use crate::utils::target::{current_target, ExecTarget};
+use futures::future::BoxFuture;
+
+/// Where detection and the inventory come from. Callers go through `current()`
+/// rather than checking the target themselves.
+pub trait Backend: Send + Sync {
+    /// Short name for logs
+    fn name(&self) -> &'static str;
+
+    /// Whether this backend serves `target`
+    fn serves(&self, target: &ExecTarget) -> bool;
+
+    /// Managers this backend can list
+    fn detect_managers(&self) -> BoxFuture<'static, Vec<PackageManager>>;
+
+    /// Packages of `managers`, with projects and failures where the backend has them
+    fn collect_scan<'a>(&'a self, managers: &'a [PackageManager]) -> BoxFuture<'a, Scan>;
+
+    /// False when commands, network lookups and diagnostics must not run
+    fn runs_commands(&self) -> bool {
+        true
+    }
+}
+
+/// The machine (or container/SSH host) the target points at
+pub struct SystemBackend;
+
+impl Backend for SystemBackend {
+    fn name(&self) -> &'static str {
+        "system"
+    }
+
+    fn serves(&self, target: &ExecTarget) -> bool {
+        !target.is_demo()
+    }
+
+    fn detect_managers(&self) -> BoxFuture<'static, Vec<PackageManager>> {
+        Box::pin(super::detector::detect_system_managers())
+    }
+
+    fn collect_scan<'a>(&'a self, managers: &'a [PackageManager]) -> BoxFuture<'a, Scan> {
+        Box::pin(crate::inventory::collect_system_scan(managers))
+    }
+}
+
+/// `--demo`: the bundled fixture inventory, nothing is spawned or fetched
+pub struct DemoBackend;
+
+impl Backend for DemoBackend {
+    fn name(&self) -> &'static str {
+        "demo"
+    }
+
+    fn serves(&self, target: &ExecTarget) -> bool {
+        target.is_demo()
+    }
+
+    fn detect_managers(&self) -> BoxFuture<'static, Vec<PackageManager>> {
+        Box::pin(async { super::demo::managers() })
+    }
+
+    fn collect_scan<'a>(&'a self, managers: &'a [PackageManager]) -> BoxFuture<'a, Scan> {
+        Box::pin(async move {
+            let packages = super::demo::inventory()
+                .into_iter()
+                .filter(|p| managers.contains(&p.manager))
+                .collect();
+            Scan {
+                packages,
+                ..Scan::default()
+            }
+        })
+    }
+
+    fn runs_commands(&self) -> bool {
+        false
+    }
+}
+
+/// Every backend, most specific first; `SystemBackend` serves whatever the others don't
+static BACKENDS: [&dyn Backend; 2] = [&DemoBackend, &SystemBackend];
+
+/// The backend serving the current target
+pub fn current() -> &'static dyn Backend {
+    let target = current_target();
+    BACKENDS
+        .iter()
+        .copied()
+        .find(|backend| backend.serves(&target))
+        .unwrap_or(&SystemBackend)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_target_has_one_backend() {
+        let demo: Vec<&str> = BACKENDS
+            .iter()
+            .filter(|b| b.serves(&ExecTarget::Demo))
+            .map(|b| b.name())
+            .collect();
+        assert_eq!(demo, ["demo"]);
+        assert!(!DemoBackend.runs_commands());
+    }
+}
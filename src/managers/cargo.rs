@@ -1,6 +1,17 @@
-use crate::models::{Package, PackageManager};
+use crate::audit::changelog::github_repo;
+use crate::config::DescriptionSource;
+use crate::managers::github_releases::{latest_tag, tag_version};
+use crate::models::{Package, PackageFlags, PackageManager};
+use crate::utils::http_client::create_http_client;
+use crate::utils::metrics::SendTimed;
+use crate::utils::paths::home_dir;
 use crate::utils::run_command_with_timeout;
+use crate::utils::target::current_target;
+use crate::utils::version::{compare_versions, is_newer};
 use anyhow::{anyhow, Result};
+use futures::{stream, StreamExt};
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::time::Duration;
 
 /// List installed cargo packages
@@ -25,16 +36,11 @@ pub async fn list_cargo_packages() -> Result<Vec<Package>> {
                 let name = parts[0];
                 let version = parts[1].trim_start_matches('v');
 
-                packages.push(Package {
-                    name: name.to_string(),
-                    manager: PackageManager::Cargo,
-                    installed_version: version.to_string(),
-                    latest_version: None,
-                    is_outdated: false,
-                    description: None,
-                    used_in: vec![],
-                    size: None,
-                });
+                packages.push(Package::new(
+                    name.to_string(),
+                    PackageManager::Cargo,
+                    version.to_string(),
+                ));
             }
         }
     }
@@ -43,23 +49,218 @@ pub async fn list_cargo_packages() -> Result<Vec<Package>> {
     Ok(packages)
 }
 
-/// Check for outdated cargo packages using cargo-outdated if available
-pub async fn check_outdated_cargo(_packages: &mut [Package]) -> Result<()> {
-    // Note: Checking for outdated cargo binaries is complex
-    // Would need cargo-outdated or cargo-update crate
-    // For now, we'll skip this check
-    println!("[CARGO] Outdated check not implemented yet");
+pub fn cargo_home() -> PathBuf {
+    std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home_dir().join(".cargo"))
+}
+
+/// Cargo's own install tracking (`$CARGO_HOME/.crates2.json`)
+fn read_tracking() -> serde_json::Value {
+    std::fs::read_to_string(cargo_home().join(".crates2.json"))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Binaries of each installed crate, from cargo's install tracking
+pub fn installed_binaries() -> HashMap<String, Vec<PathBuf>> {
+    let cargo_home = cargo_home();
+    let tracking = read_tracking();
+
+    // Keys look like "ripgrep 14.1.0 (registry+https://github.com/rust-lang/crates.io-index)"
+    let mut binaries = HashMap::new();
+    for (key, install) in tracking["installs"].as_object().into_iter().flatten() {
+        let name = key.split_whitespace().next().unwrap_or_default();
+        let bins = install["bins"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|b| b.as_str())
+            .map(|b| cargo_home.join("bin").join(b))
+            .collect();
+        binaries.insert(name.to_string(), bins);
+    }
+    binaries
+}
+
+/// What a `cargo install --git` crate was built from, besides the repository
+#[derive(Debug, PartialEq)]
+enum GitRef {
+    /// The default branch, or a pinned commit (`--rev`)
+    Head,
+    Branch(String),
+    Tag(String),
+}
+
+/// Repository and ref of each crate installed with `cargo install --git`, from tracking
+/// keys like "tool 0.3.0 (git+https://github.com/me/tool?branch=main#0a1b2c3)"
+fn git_sources(tracking: &serde_json::Value) -> HashMap<String, (String, GitRef)> {
+    tracking["installs"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(key, _)| {
+            let (name, rest) = key.split_once(' ')?;
+            let source = rest.split_once("(git+")?.1.trim_end_matches(')');
+            let source = source.split('#').next()?;
+            let (url, query) = source.split_once('?').unwrap_or((source, ""));
+            let git_ref = match query.split_once('=') {
+                Some(("branch", branch)) => GitRef::Branch(branch.to_string()),
+                Some(("tag", tag)) => GitRef::Tag(tag.to_string()),
+                _ => GitRef::Head,
+            };
+            Some((name.to_string(), (url.to_string(), git_ref)))
+        })
+        .collect()
+}
+
+/// Registry crates have no cheap outdated check without cargo-update, except those
+/// following prereleases. Crates installed from GitHub are compared with the
+/// repository's latest release or tag instead.
+pub async fn check_outdated_cargo(packages: &mut [Package]) -> Result<()> {
+    check_cargo_prereleases(packages).await;
+
+    // The tracking file is read from this machine
+    if !current_target().is_local() {
+        return Ok(());
+    }
+    let sources = git_sources(&read_tracking());
+    let repos: Vec<(usize, String)> = packages
+        .iter()
+        .enumerate()
+        .filter_map(|(index, pkg)| Some((index, github_repo(&sources.get(&pkg.name)?.0)?)))
+        .collect();
+    if repos.is_empty() {
+        return Ok(());
+    }
+    println!(
+        "[CARGO] Checking {} git installs against GitHub",
+        repos.len()
+    );
+
+    let tags: Vec<Option<(usize, String)>> = stream::iter(repos)
+        .map(|(index, repo)| async move {
+            match latest_tag(&repo).await {
+                Ok(tag) => Some((index, tag)),
+                Err(e) => {
+                    eprintln!("[CARGO] {}", e);
+                    None
+                }
+            }
+        })
+        .buffer_unordered(4)
+        .collect()
+        .await;
+
+    for (index, tag) in tags.into_iter().flatten() {
+        let pkg = &mut packages[index];
+        let latest = tag_version(&tag).to_string();
+        pkg.is_outdated =
+            compare_versions(&latest, &pkg.installed_version) == std::cmp::Ordering::Greater;
+        pkg.latest_version = Some(latest);
+    }
     Ok(())
 }
 
-/// Fetch descriptions for cargo packages from crates.io API
+/// Crates opted into prereleases are compared with the newest version published,
+/// which `cargo install` never picks on its own
+async fn check_cargo_prereleases(packages: &mut [Package]) {
+    let flags = PackageFlags::load();
+    let following: Vec<(usize, String)> = packages
+        .iter()
+        .enumerate()
+        .filter(|(_, pkg)| flags.channel(pkg).is_some())
+        .map(|(index, pkg)| (index, pkg.name.clone()))
+        .collect();
+    if following.is_empty() {
+        return;
+    }
+
+    let results: Vec<Option<(usize, String)>> = stream::iter(following)
+        .map(|(index, name)| async move {
+            match newest_version(&name).await {
+                Ok(version) => Some((index, version)),
+                Err(e) => {
+                    eprintln!("[CARGO] {}", e);
+                    None
+                }
+            }
+        })
+        .buffer_unordered(4)
+        .collect()
+        .await;
+
+    for (index, newest) in results.into_iter().flatten() {
+        let pkg = &mut packages[index];
+        pkg.is_outdated = is_newer(&newest, &pkg.installed_version);
+        pkg.latest_version = Some(newest);
+    }
+}
+
+/// The highest version on the registry, prereleases included
+async fn newest_version(name: &str) -> Result<String> {
+    let url = crate::utils::registries::crates_url(name).await;
+    let response = create_http_client()
+        .get(&url)
+        .header("User-Agent", "depmgr/0.1.0")
+        .send_timed()
+        .await?;
+    if !response.status().is_success() {
+        return Err(anyhow!("{} returned {}", url, response.status()));
+    }
+    let json: serde_json::Value = response.json().await?;
+    json["crate"]["max_version"]
+        .as_str()
+        .map(String::from)
+        .ok_or_else(|| anyhow!("{} has no versions", name))
+}
+
+/// One crate's description from crates.io's API or `cargo search`
+pub async fn fetch_cargo_description(name: &str, source: DescriptionSource) -> Option<String> {
+    let desc = match source {
+        DescriptionSource::Http => {
+            let url = crate::utils::registries::crates_url(name).await;
+            let response = create_http_client()
+                .get(&url)
+                .header("User-Agent", "depmgr/0.1.0")
+                .send_timed()
+                .await
+                .ok()
+                .filter(|r| r.status().is_success())?;
+            let json: serde_json::Value = response.json().await.ok()?;
+            json["crate"]["description"].as_str()?.trim().to_string()
+        }
+        DescriptionSource::Cli => {
+            // `name = "1.2.3"    # Description`
+            let output = run_command_with_timeout(
+                "cargo",
+                &["search", name, "--limit", "1"],
+                Duration::from_secs(15),
+            )
+            .await
+            .ok()
+            .filter(|o| o.status.success())?;
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let line = stdout.lines().next()?;
+            if line.split_whitespace().next()? != name {
+                return None;
+            }
+            line.split_once('#')?.1.trim().to_string()
+        }
+    };
+    (!desc.is_empty()).then_some(desc)
+}
+
+/// Fetch descriptions for cargo packages from crates.io
 pub async fn add_cargo_descriptions(
     packages: std::sync::Arc<tokio::sync::RwLock<Vec<crate::models::Package>>>,
+    source: DescriptionSource,
+    batch_size: usize,
 ) {
-    use crate::utils::http_client::create_http_client;
     use futures::{stream, StreamExt};
 
-    println!("[CARGO] Fetching package descriptions from crates.io");
+    println!("[CARGO] Fetching package descriptions");
 
     let packages_read = packages.read().await;
     let cargo_packages: Vec<String> = packages_read
@@ -76,41 +277,20 @@ pub async fn add_cargo_descriptions(
     let total = cargo_packages.len();
     println!("[CARGO] Fetching descriptions for {} packages", total);
 
-    let client = create_http_client();
-
-    const CONCURRENT_REQUESTS: usize = 8;
     let mut completed = 0;
 
     let mut stream = stream::iter(cargo_packages)
-        .map(|name| {
-            let client = client.clone();
-            async move {
-                let url = format!("https://crates.io/api/v1/crates/{}", name);
-                let result = client
-                    .get(&url)
-                    .header("User-Agent", "depmgr/0.1.0")
-                    .send()
-                    .await;
-                (name, result)
-            }
+        .map(|name| async move {
+            let desc = fetch_cargo_description(&name, source).await;
+            (name, desc)
         })
-        .buffer_unordered(CONCURRENT_REQUESTS);
-
-    while let Some((name, result)) = stream.next().await {
-        if let Ok(response) = result {
-            if response.status().is_success() {
-                if let Ok(json) = response.json::<serde_json::Value>().await {
-                    if let Some(desc) = json
-                        .get("crate")
-                        .and_then(|c| c.get("description"))
-                        .and_then(|d| d.as_str())
-                    {
-                        let mut packages_lock = packages.write().await;
-                        if let Some(pkg) = packages_lock.iter_mut().find(|p| p.name == name) {
-                            pkg.description = Some(desc.to_string());
-                        }
-                    }
-                }
+        .buffer_unordered(batch_size);
+
+    while let Some((name, desc)) = stream.next().await {
+        if let Some(desc) = desc {
+            let mut packages_lock = packages.write().await;
+            if let Some(pkg) = packages_lock.iter_mut().find(|p| p.name == name) {
+                pkg.description = Some(desc);
             }
         }
 
@@ -127,9 +307,46 @@ pub async fn add_cargo_descriptions(
 pub async fn update_cargo_package(package_name: String) -> Result<()> {
     println!("[CARGO] Updating: {}", package_name);
 
+    // Crates installed from git are reinstalled from their repository, the registry may not
+    // have them (or a different crate): one installed from a tag moves to the latest release
+    // when it's on GitHub, one following a branch stays on it
+    let git_source = current_target()
+        .is_local()
+        .then(|| git_sources(&read_tracking()).remove(&package_name))
+        .flatten();
+    let mut args = vec!["install".to_string()];
+    if let Some((url, git_ref)) = git_source {
+        let tag = match (&git_ref, github_repo(&url)) {
+            (GitRef::Tag(_), Some(repo)) => latest_tag(&repo).await.ok(),
+            _ => None,
+        };
+        args.extend(["--git".to_string(), url]);
+        match (git_ref, tag) {
+            (GitRef::Tag(_), Some(tag)) | (GitRef::Tag(tag), None) => {
+                args.extend(["--tag".to_string(), tag])
+            }
+            (GitRef::Branch(branch), _) => args.extend(["--branch".to_string(), branch]),
+            (GitRef::Head, _) => {}
+        }
+    }
+    args.push(package_name.clone());
+    // The newest prerelease must be asked for by version
+    if !args.contains(&"--git".to_string())
+        && PackageFlags::load()
+            .channel_of(&PackageManager::Cargo, &package_name)
+            .is_some()
+    {
+        args.extend([
+            "--version".to_string(),
+            newest_version(&package_name).await?,
+        ]);
+    }
+    args.push("--force".to_string());
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
     let output = run_command_with_timeout(
         "cargo",
-        &["install", &package_name, "--force"],
+        &args,
         Duration::from_secs(600), // 10 minutes for compilation
     )
     .await?;
@@ -182,3 +399,138 @@ pub async fn install_cargo_package(package_name: String) -> Result<()> {
         Err(anyhow!("Failed to install {}: {}", package_name, stderr))
     }
 }
+
+/// Rebuild a crate for Apple Silicon (when it was built by an Intel toolchain)
+pub async fn reinstall_cargo_native(package_name: String) -> Result<()> {
+    println!("[CARGO] Rebuilding for arm64: {}", package_name);
+
+    let output = run_command_with_timeout(
+        "cargo",
+        &[
+            "install",
+            &package_name,
+            "--force",
+            "--target",
+            "aarch64-apple-darwin",
+        ],
+        Duration::from_secs(600), // 10 minutes for compilation
+    )
+    .await?;
+
+    if output.status.success() {
+        println!("[CARGO] Successfully rebuilt: {}", package_name);
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to rebuild {}: {}", package_name, stderr))
+    }
+}
+
+/// Install an exact version (used for rollbacks)
+pub async fn install_cargo_version(package_name: String, version: String) -> Result<()> {
+    println!("[CARGO] Installing: {} {}", package_name, version);
+
+    let output = run_command_with_timeout(
+        "cargo",
+        &["install", &package_name, "--version", &version, "--force"],
+        Duration::from_secs(600), // 10 minutes for compilation
+    )
+    .await?;
+
+    if output.status.success() {
+        println!(
+            "[CARGO] Successfully installed: {} {}",
+            package_name, version
+        );
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!(
+            "Failed to install {} {}: {}",
+            package_name,
+            version,
+            stderr
+        ))
+    }
+}
+
+/// Update the Rust toolchain via rustup (cargo itself ships with it)
+pub async fn self_update_rustup() -> Result<()> {
+    if !crate::utils::command_exists("rustup").await {
+        return Err(anyhow!("rustup not found, cargo is managed elsewhere"));
+    }
+
+    println!("[CARGO] Running: rustup update");
+
+    let output = run_command_with_timeout("rustup", &["update"], Duration::from_secs(600)).await?;
+
+    if output.status.success() {
+        println!("[CARGO] Rust toolchains are up to date");
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("rustup update failed: {}", stderr))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::executor::{replay_fixture, with_executor};
+
+    #[tokio::test]
+    async fn lists_installed_crates_not_binaries() {
+        let packages = with_executor(replay_fixture("cargo"), list_cargo_packages())
+            .await
+            .unwrap();
+
+        let summary: Vec<(&str, &str)> = packages
+            .iter()
+            .map(|p| (p.name.as_str(), p.installed_version.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("bat", "0.24.0"),
+                ("cargo-edit", "0.12.2"),
+                ("ripgrep", "14.1.0")
+            ]
+        );
+    }
+
+    #[test]
+    fn git_installs_keep_their_ref() {
+        let tracking = serde_json::json!({ "installs": {
+            "a 0.1.0 (git+https://github.com/me/a?branch=dev#0a1b2c3)": {},
+            "b 1.2.0 (git+https://github.com/me/b?tag=v1.2.0#4d5e6f7)": {},
+            "c 0.3.0 (git+https://github.com/me/c#8a9b0c1)": {},
+            "d 0.3.0 (git+https://github.com/me/d?rev=8a9b0c1#8a9b0c1)": {},
+            "ripgrep 14.1.0 (registry+https://github.com/rust-lang/crates.io-index)": {},
+        }});
+        let sources = git_sources(&tracking);
+        let source = |name: &str| sources.get(name).map(|(url, r)| (url.as_str(), r));
+        assert_eq!(
+            source("a"),
+            Some((
+                "https://github.com/me/a",
+                &GitRef::Branch("dev".to_string())
+            ))
+        );
+        assert_eq!(
+            source("b"),
+            Some((
+                "https://github.com/me/b",
+                &GitRef::Tag("v1.2.0".to_string())
+            ))
+        );
+        assert_eq!(
+            source("c"),
+            Some(("https://github.com/me/c", &GitRef::Head))
+        );
+        assert_eq!(
+            source("d"),
+            Some(("https://github.com/me/d", &GitRef::Head))
+        );
+        assert_eq!(source("ripgrep"), None);
+    }
+}
@@ -0,0 +1,209 @@
+use crate::models::{Package, PackageManager};
+use crate::utils::run_command_with_timeout;
+use anyhow::{anyhow, Result};
+use std::time::Duration;
+
+/// List the tools required in Composer's global project. Only direct requirements
+/// are listed: their dependencies are managed through them.
+pub async fn list_composer_packages() -> Result<Vec<Package>> {
+    println!("[COMPOSER] Listing global packages");
+
+    let output = run_command_with_timeout(
+        "composer",
+        &[
+            "global",
+            "show",
+            "--direct",
+            "--format=json",
+            "--no-interaction",
+        ],
+        Duration::from_secs(60),
+    )
+    .await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        // A fresh install has no global composer.json yet
+        if stderr.contains("composer.json") {
+            return Ok(Vec::new());
+        }
+        return Err(anyhow!("composer global show failed: {}", stderr));
+    }
+
+    let packages = parse_composer_show(&String::from_utf8_lossy(&output.stdout))?;
+    println!("[COMPOSER] Found {} global packages", packages.len());
+    Ok(packages)
+}
+
+/// Packages from `composer global show --format=json`: {"installed": [{name, version, description}]}
+fn parse_composer_show(stdout: &str) -> Result<Vec<Package>> {
+    let json: serde_json::Value = serde_json::from_str(stdout)?;
+    let installed = json["installed"].as_array().cloned().unwrap_or_default();
+    Ok(installed
+        .iter()
+        .filter_map(|entry| {
+            let name = entry["name"].as_str()?;
+            let version = entry["version"].as_str()?;
+            let mut pkg = Package::new(name, PackageManager::Composer, version);
+            pkg.description = entry["description"]
+                .as_str()
+                .filter(|d| !d.is_empty())
+                .map(String::from);
+            Some(pkg)
+        })
+        .collect())
+}
+
+/// Check for outdated global packages
+pub async fn check_outdated_composer(packages: &mut [Package]) -> Result<()> {
+    println!("[COMPOSER] Checking for outdated packages");
+
+    let output = run_command_with_timeout(
+        "composer",
+        &[
+            "global",
+            "outdated",
+            "--direct",
+            "--format=json",
+            "--no-interaction",
+        ],
+        Duration::from_secs(120),
+    )
+    .await?;
+
+    if !output.status.success() {
+        return Ok(()); // Not a fatal error
+    }
+
+    mark_outdated(&String::from_utf8_lossy(&output.stdout), packages)
+}
+
+/// Apply `composer global outdated --format=json` ({"installed": [{name, version, latest}]})
+fn mark_outdated(stdout: &str, packages: &mut [Package]) -> Result<()> {
+    let json: serde_json::Value = serde_json::from_str(stdout)?;
+    for entry in json["installed"].as_array().into_iter().flatten() {
+        let name = entry["name"].as_str().unwrap_or_default();
+        let Some(latest) = entry["latest"].as_str() else {
+            continue;
+        };
+        if let Some(pkg) = packages.iter_mut().find(|p| p.name == name) {
+            pkg.is_outdated = entry["latest-status"].as_str() != Some("up-to-date");
+            pkg.latest_version = Some(latest.to_string());
+        }
+    }
+    Ok(())
+}
+
+async fn run_composer(action: &str, args: &[&str], package_name: &str) -> Result<()> {
+    println!("[COMPOSER] {}: {}", action, package_name);
+
+    let output = run_command_with_timeout("composer", args, Duration::from_secs(300)).await?;
+
+    if output.status.success() {
+        println!(
+            "[COMPOSER] Successfully finished {}: {}",
+            action, package_name
+        );
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to {} {}: {}", action, package_name, stderr))
+    }
+}
+
+/// Update to the latest release. `global update` stays within the constraint written
+/// when the tool was required, so require it again to move past a major version.
+pub async fn update_composer_package(package_name: String) -> Result<()> {
+    run_composer(
+        "update",
+        &[
+            "global",
+            "require",
+            &package_name,
+            "--update-with-all-dependencies",
+            "--no-interaction",
+        ],
+        &package_name,
+    )
+    .await
+}
+
+pub async fn uninstall_composer_package(package_name: String) -> Result<()> {
+    run_composer(
+        "remove",
+        &["global", "remove", &package_name, "--no-interaction"],
+        &package_name,
+    )
+    .await
+}
+
+pub async fn install_composer_package(package_name: String) -> Result<()> {
+    run_composer(
+        "install",
+        &["global", "require", &package_name, "--no-interaction"],
+        &package_name,
+    )
+    .await
+}
+
+/// Require an exact version (used for rollbacks)
+pub async fn install_composer_version(package_name: String, version: String) -> Result<()> {
+    let spec = format!("{}:{}", package_name, version);
+    run_composer(
+        "install",
+        &[
+            "global",
+            "require",
+            &spec,
+            "--update-with-all-dependencies",
+            "--no-interaction",
+        ],
+        &package_name,
+    )
+    .await
+}
+
+/// Update Composer itself
+pub async fn self_update_composer() -> Result<()> {
+    run_composer("update", &["self-update", "--no-interaction"], "Composer").await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::executor::{replay_fixture, with_executor};
+
+    #[tokio::test]
+    async fn lists_global_tools_and_their_updates() {
+        let packages = with_executor(replay_fixture("composer"), async {
+            let mut packages = list_composer_packages().await?;
+            check_outdated_composer(&mut packages).await?;
+            Ok::<_, anyhow::Error>(packages)
+        })
+        .await
+        .unwrap();
+
+        let summary: Vec<(&str, &str, Option<&str>, bool)> = packages
+            .iter()
+            .map(|p| {
+                (
+                    p.name.as_str(),
+                    p.installed_version.as_str(),
+                    p.latest_version.as_deref(),
+                    p.is_outdated,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("friendsofphp/php-cs-fixer", "v3.49.0", None, false),
+                ("laravel/installer", "v4.5.0", Some("v5.8.1"), true),
+            ]
+        );
+        assert_eq!(
+            packages[1].description.as_deref(),
+            Some("Laravel application installer.")
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use crate::models::{Package, PackageManager, Project};
+use crate::utils::cache::{get_cached, set_cached};
+use crate::utils::http_client::create_http_client;
+use crate::utils::metrics::SendTimed;
+use crate::utils::run_command_with_timeout;
+use crate::utils::version::compare_versions;
+use anyhow::{anyhow, Result};
+use futures::{stream, StreamExt};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+/// Globally activated packages from `dart pub global list`
+pub async fn list_pub_packages() -> Result<Vec<Package>> {
+    println!("[PUB] Listing globally activated packages");
+
+    let output =
+        run_command_with_timeout("dart", &["pub", "global", "list"], Duration::from_secs(30))
+            .await?;
+
+    if !output.status.success() {
+        return Err(anyhow!("dart pub global list failed"));
+    }
+
+    // "markdown 7.2.2", "webdev 3.5.0 at path \"/src/webdev\"", "foo 1.0.0 from Git repository ..."
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let packages: Vec<Package> = stdout
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some((fields.next()?, fields.next()?))
+        })
+        .map(|(name, version)| Package::new(name, PackageManager::Pub, version))
+        .collect();
+
+    println!("[PUB] Found {} activated packages", packages.len());
+    Ok(packages)
+}
+
+/// Latest stable version of a package on pub.dev
+async fn latest_pub_version(name: &str) -> Result<String> {
+    let cache_key = format!("pub_latest_{}", name);
+    if let Some(version) = get_cached::<String>(&cache_key) {
+        return Ok(version);
+    }
+
+    let json: serde_json::Value = create_http_client()
+        .get(format!("https://pub.dev/api/packages/{}", name))
+        .send_timed()
+        .await?
+        .json()
+        .await?;
+    let version = json["latest"]["version"]
+        .as_str()
+        .ok_or_else(|| anyhow!("{} not found on pub.dev", name))?
+        .to_string();
+
+    set_cached(
+        cache_key,
+        &version,
+        crate::config::cache_ttls().registry_ttl(),
+    );
+    Ok(version)
+}
+
+/// Latest versions for a set of package names, a few requests at a time
+async fn latest_pub_versions(names: HashSet<String>) -> HashMap<String, String> {
+    stream::iter(names)
+        .map(|name| async move {
+            match latest_pub_version(&name).await {
+                Ok(version) => Some((name, version)),
+                Err(e) => {
+                    eprintln!("[PUB] {}", e);
+                    None
+                }
+            }
+        })
+        .buffer_unordered(crate::config::registry_lookups())
+        .filter_map(|found| async move { found })
+        .collect()
+        .await
+}
+
+/// Check activated packages against pub.dev
+pub async fn check_outdated_pub(packages: &mut [Package]) -> Result<()> {
+    println!("[PUB] Checking pub.dev for newer versions");
+
+    let latest = latest_pub_versions(packages.iter().map(|p| p.name.clone()).collect()).await;
+    for pkg in packages.iter_mut() {
+        if let Some(latest) = latest.get(&pkg.name) {
+            pkg.is_outdated = compare_versions(latest, &pkg.installed_version) == Ordering::Greater;
+            pkg.latest_version = Some(latest.clone());
+        }
+    }
+    Ok(())
+}
+
+/// Look up the latest version of every pub package the projects depend on
+pub async fn check_project_packages(projects: &mut [Project]) {
+    let names: HashSet<String> = projects
+        .iter()
+        .flat_map(|p| &p.dependencies)
+        .filter(|d| d.manager == PackageManager::Pub)
+        .map(|d| d.package_name.clone())
+        .collect();
+    if names.is_empty() {
+        return;
+    }
+    println!("[PUB] Checking {} project packages on pub.dev", names.len());
+
+    let latest = latest_pub_versions(names).await;
+    for dep in projects
+        .iter_mut()
+        .flat_map(|p| p.dependencies.iter_mut())
+        .filter(|d| d.manager == PackageManager::Pub)
+    {
+        dep.latest_version = latest.get(&dep.package_name).cloned();
+    }
+}
+
+async fn run_pub_global(action: &str, package_name: &str) -> Result<()> {
+    println!("[PUB] {}: {}", action, package_name);
+
+    let output = run_command_with_timeout(
+        "dart",
+        &["pub", "global", action, package_name],
+        Duration::from_secs(300),
+    )
+    .await?;
+
+    if output.status.success() {
+        println!("[PUB] Successfully finished {}: {}", action, package_name);
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to {} {}: {}", action, package_name, stderr))
+    }
+}
+
+/// Activating again without a version picks the latest
+pub async fn update_pub_package(package_name: String) -> Result<()> {
+    run_pub_global("activate", &package_name).await
+}
+
+pub async fn install_pub_package(package_name: String) -> Result<()> {
+    run_pub_global("activate", &package_name).await
+}
+
+pub async fn uninstall_pub_package(package_name: String) -> Result<()> {
+    run_pub_global("deactivate", &package_name).await
+}
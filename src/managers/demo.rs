@@ -0,0 +1,110 @@
+use crate::models::{Package, PackageManager};
+
+/// Fixed inventory for `--demo`: a few managers with outdated, vulnerable, unused and
+/// too-old packages, so every part of the UI has something to show
+const DEMO_INVENTORY: &str = include_str!("../../fixtures/demo_inventory.json");
+
+/// The bundled fixture. It's checked by the tests below, so a bad edit fails `cargo test`.
+pub fn inventory() -> Vec<Package> {
+    serde_json::from_str(DEMO_INVENTORY).unwrap_or_else(|e| {
+        eprintln!("[ERROR] Invalid demo inventory: {}", e);
+        Vec::new()
+    })
+}
+
+/// Managers present in the fixture, in first-seen order
+pub fn managers() -> Vec<PackageManager> {
+    let mut managers: Vec<PackageManager> = Vec::new();
+    for pkg in inventory() {
+        if !managers.contains(&pkg.manager) {
+            managers.push(pkg.manager);
+        }
+    }
+    managers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::category::classify;
+    use crate::models::risk::assess;
+    use std::path::PathBuf;
+
+    /// Compare against `fixtures/snapshots/<name>`; run with UPDATE_SNAPSHOTS=1 to accept changes
+    fn assert_snapshot(name: &str, actual: &str) {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("fixtures/snapshots")
+            .join(name);
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, actual).unwrap();
+            return;
+        }
+        let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+            panic!(
+                "missing snapshot {}, run with UPDATE_SNAPSHOTS=1",
+                path.display()
+            )
+        });
+        assert_eq!(
+            expected, actual,
+            "snapshot {} changed, run with UPDATE_SNAPSHOTS=1 to accept",
+            name
+        );
+    }
+
+    #[test]
+    fn fixture_parses() {
+        let packages: Vec<Package> = serde_json::from_str(DEMO_INVENTORY).unwrap();
+        assert!(!packages.is_empty());
+        assert!(packages.iter().any(|p| p.is_outdated));
+        assert!(packages.iter().any(|p| !p.vulnerabilities.is_empty()));
+        assert!(packages.iter().any(|p| p.used_in.is_empty()));
+        assert!(packages.iter().any(|p| !p.constraint_mismatches.is_empty()));
+    }
+
+    #[test]
+    fn scan_results() {
+        let lines: Vec<String> = inventory()
+            .iter()
+            .map(|pkg| {
+                let category = classify(pkg);
+                let risk = assess(pkg, category, None);
+                format!(
+                    "{} {} -> {} | {} | {} ({})",
+                    pkg.key(),
+                    pkg.installed_version,
+                    pkg.latest_version.as_deref().unwrap_or("-"),
+                    category.label(),
+                    risk.level().label(),
+                    risk.score
+                )
+            })
+            .collect();
+        assert_snapshot("demo_scan.txt", &(lines.join("\n") + "\n"));
+    }
+
+    #[test]
+    fn markdown_report() {
+        let report = crate::report::render_markdown_report("Demo", &[], &inventory());
+        // Drop the "Generated by DepMgr on <date>" line
+        let stable: Vec<&str> = report
+            .lines()
+            .filter(|l| !l.starts_with("Generated by"))
+            .collect();
+        assert_snapshot("demo_report.md", &(stable.join("\n") + "\n"));
+    }
+
+    #[test]
+    fn policy_summary() {
+        let thresholds = [
+            crate::policy::Threshold::parse("vulnerable").unwrap(),
+            crate::policy::Threshold::parse("outdated>20").unwrap(),
+        ];
+        let summary = crate::policy::evaluate(&inventory(), &thresholds);
+        assert_snapshot(
+            "demo_policy.json",
+            &(serde_json::to_string_pretty(&summary).unwrap() + "\n"),
+        );
+    }
+}
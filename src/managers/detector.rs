@@ -1,11 +1,18 @@
 use crate::models::PackageManager;
 use crate::utils::command_exists;
+use crate::utils::target::current_target;
 
+/// Managers the current backend can list
 pub async fn detect_available_managers() -> Vec<PackageManager> {
+    crate::managers::backend::current().detect_managers().await
+}
+
+/// `SystemBackend`'s detection: probe the target for each manager's command
+pub(crate) async fn detect_system_managers() -> Vec<PackageManager> {
     let mut available = Vec::new();
 
     // Check each package manager using the command() method
-    let managers_to_check = vec![
+    let mut managers_to_check = vec![
         PackageManager::Homebrew,
         PackageManager::Npm,
         PackageManager::Yarn,
@@ -13,16 +20,62 @@ pub async fn detect_available_managers() -> Vec<PackageManager> {
         PackageManager::Cargo,
         PackageManager::Pip,
         PackageManager::Pipx,
+        PackageManager::Uv,
+        PackageManager::Poetry,
         PackageManager::Gem,
         PackageManager::Go,
         PackageManager::Composer,
         PackageManager::Pub,
         PackageManager::Swift,
+        PackageManager::Opam,
+        PackageManager::Ghcup,
+        PackageManager::Stack,
+        PackageManager::LuaRocks,
+        PackageManager::Julia,
+        PackageManager::R,
+        PackageManager::Tfenv,
+        PackageManager::HelmPlugins,
+        PackageManager::Krew,
+        PackageManager::GhExtensions,
+        PackageManager::GitHelpers,
+        PackageManager::OhMyZsh,
+        PackageManager::Zinit,
+        PackageManager::Fisher,
+        PackageManager::Fonts,
+        PackageManager::Nix,
+        PackageManager::Mise,
+        PackageManager::Asdf,
+        PackageManager::Mas,
     ];
 
+    // System package managers are only audited inside containers, where
+    // they describe the image rather than the developer's own machine
+    if current_target().is_container() {
+        managers_to_check.extend([PackageManager::Apk, PackageManager::Apt]);
+    }
+
+    // The Windows package managers only exist on a local Windows machine
+    if cfg!(windows) && current_target().is_local() {
+        managers_to_check.extend([
+            PackageManager::Winget,
+            PackageManager::Scoop,
+            PackageManager::Chocolatey,
+        ]);
+    }
+
+    // Ecosystems switched off in config.toml aren't even probed
+    let scan_settings = crate::config::Settings::load().scan;
+    managers_to_check.retain(|m| scan_settings.is_enabled(m));
+
     for manager in managers_to_check {
-        let cmd = manager.command();
-        if command_exists(cmd).await {
+        let present = match manager {
+            PackageManager::OhMyZsh | PackageManager::Zinit | PackageManager::Fisher => {
+                crate::managers::shell_plugins::is_installed(&manager).await
+            }
+            PackageManager::Fonts => crate::managers::fonts::has_font_casks().await,
+            _ => command_exists(manager.command()).await,
+        };
+        if present {
             available.push(manager);
         }
     }
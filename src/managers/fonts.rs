@@ -0,0 +1,139 @@
+use crate::models::{Package, PackageManager};
+use crate::utils::paths::home_dir;
+use crate::utils::run_command_with_timeout;
+use crate::utils::target::current_target;
+use crate::utils::version::is_newer;
+use anyhow::{anyhow, Result};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Font casks from homebrew/cask-fonts are all named "font-*"
+async fn installed_font_casks() -> Result<Vec<String>> {
+    let output =
+        run_command_with_timeout("brew", &["list", "--cask", "-1"], Duration::from_secs(15))
+            .await?;
+    if !output.status.success() {
+        return Err(anyhow!("brew list --cask failed"));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|name| name.starts_with("font-"))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Casks only exist on macOS, so only show a Fonts category when there are font casks
+pub async fn has_font_casks() -> bool {
+    installed_font_casks()
+        .await
+        .is_ok_and(|fonts| !fonts.is_empty())
+}
+
+/// Font file to render the preview with: the "Regular" face if there is one.
+/// Casks install fonts into ~/Library/Fonts; only local files can be loaded.
+fn preview_file(cask: &serde_json::Value) -> Option<String> {
+    if !current_target().is_local() {
+        return None;
+    }
+    let files: Vec<&str> = cask["artifacts"]
+        .as_array()?
+        .iter()
+        .filter_map(|artifact| artifact["font"].as_array())
+        .flatten()
+        .filter_map(|f| f.as_str())
+        .filter(|f| {
+            let f = f.to_lowercase();
+            f.ends_with(".ttf") || f.ends_with(".otf")
+        })
+        .collect();
+    let file = files
+        .iter()
+        .find(|f| f.contains("Regular"))
+        .or(files.first())?;
+    let file_name = file.rsplit('/').next()?;
+
+    [
+        home_dir().join("Library/Fonts"),
+        PathBuf::from("/Library/Fonts"),
+    ]
+    .into_iter()
+    .map(|dir| dir.join(file_name))
+    .find(|path| path.exists())
+    .map(|path| path.to_string_lossy().to_string())
+}
+
+/// Installed font casks with their latest versions, from one `brew info` call
+pub async fn list_font_casks() -> Result<Vec<Package>> {
+    println!("[FONTS] Listing font casks");
+
+    let names = installed_font_casks().await?;
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut args = vec!["info", "--cask", "--json=v2"];
+    args.extend(names.iter().map(String::as_str));
+    let output = run_command_with_timeout("brew", &args, Duration::from_secs(60)).await?;
+    if !output.status.success() {
+        return Err(anyhow!("brew info --cask failed"));
+    }
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let packages: Vec<Package> = json["casks"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|cask| {
+            let name = cask["token"].as_str()?;
+            let installed = cask["installed"].as_str().unwrap_or("-");
+            let latest = cask["version"].as_str();
+            let mut pkg = Package::new(name, PackageManager::Fonts, installed);
+            pkg.description = cask["desc"]
+                .as_str()
+                .or_else(|| cask["name"][0].as_str())
+                .map(str::to_string);
+            pkg.latest_version = latest.map(str::to_string);
+            // "latest" casks have no version to compare, trust brew's own flag
+            pkg.is_outdated = cask["outdated"].as_bool().unwrap_or(false)
+                || latest.is_some_and(|v| v != "latest" && is_newer(v, installed));
+            pkg.preview_font = preview_file(cask);
+            Some(pkg)
+        })
+        .collect();
+
+    println!("[FONTS] Found {} font casks", packages.len());
+    Ok(packages)
+}
+
+async fn run_brew_cask(action: &str, names: &[String], timeout: Duration) -> Result<()> {
+    println!("[FONTS] brew {} --cask {}", action, names.join(" "));
+
+    let mut args = vec![action, "--cask"];
+    args.extend(names.iter().map(String::as_str));
+    let output = run_command_with_timeout("brew", &args, timeout).await?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!(
+            "Failed to {} {}: {}",
+            action,
+            names.join(", "),
+            stderr
+        ))
+    }
+}
+
+pub async fn update_fonts(names: Vec<String>) -> Result<()> {
+    run_brew_cask("upgrade", &names, Duration::from_secs(600)).await
+}
+
+pub async fn install_fonts(names: Vec<String>) -> Result<()> {
+    run_brew_cask("install", &names, Duration::from_secs(600)).await
+}
+
+pub async fn uninstall_fonts(names: Vec<String>) -> Result<()> {
+    run_brew_cask("uninstall", &names, Duration::from_secs(300)).await
+}
@@ -0,0 +1,158 @@
+use crate::models::{Package, PackageManager};
+use crate::utils::run_command_with_timeout;
+use anyhow::{anyhow, Result};
+use std::time::Duration;
+
+/// List locally installed gems
+pub async fn list_gem_packages() -> Result<Vec<Package>> {
+    println!("[GEM] Listing installed packages");
+
+    let output =
+        run_command_with_timeout("gem", &["list", "--local"], Duration::from_secs(30)).await?;
+
+    if !output.status.success() {
+        return Err(anyhow!("gem list failed"));
+    }
+
+    let packages = parse_gem_list(&String::from_utf8_lossy(&output.stdout));
+    println!("[GEM] Found {} installed packages", packages.len());
+    Ok(packages)
+}
+
+/// Check for outdated gems
+pub async fn check_outdated_gem(packages: &mut [Package]) -> Result<()> {
+    println!("[GEM] Checking for outdated packages");
+
+    let output = run_command_with_timeout("gem", &["outdated"], Duration::from_secs(60)).await?;
+
+    if !output.status.success() {
+        return Ok(()); // Not a fatal error
+    }
+
+    mark_outdated(&String::from_utf8_lossy(&output.stdout), packages);
+    Ok(())
+}
+
+/// "name (versions)" lines, as printed by both `gem list` and `gem outdated`
+fn split_gem_line(line: &str) -> Option<(&str, &str)> {
+    let (name, rest) = line.trim().split_once(" (")?;
+    Some((name, rest.strip_suffix(')')?))
+}
+
+/// The version without a platform gem's platform: "1.16.0-arm64-darwin" and
+/// "1.16.0 arm64-darwin" are both 1.16.0. RubyGems versions never contain '-' themselves
+/// ("1.0.0-beta" is stored as "1.0.0.pre.beta").
+fn strip_platform(version: &str) -> &str {
+    version.split([' ', '-']).next().unwrap_or(version)
+}
+
+/// Packages from `gem list --local`: "rake (13.1.0, default: 13.0.6)", newest version first
+fn parse_gem_list(stdout: &str) -> Vec<Package> {
+    stdout
+        .lines()
+        .filter_map(split_gem_line)
+        .filter_map(|(name, versions)| {
+            let newest = versions.split(", ").next()?;
+            let version = newest.strip_prefix("default: ").unwrap_or(newest);
+            Some(Package::new(
+                name,
+                PackageManager::Gem,
+                strip_platform(version),
+            ))
+        })
+        .collect()
+}
+
+/// Apply `gem outdated` output ("rake (13.0.6 < 13.1.0)") to `packages`
+fn mark_outdated(stdout: &str, packages: &mut [Package]) {
+    for (name, versions) in stdout.lines().filter_map(split_gem_line) {
+        let Some((_, latest)) = versions.split_once(" < ") else {
+            continue;
+        };
+        if let Some(pkg) = packages.iter_mut().find(|p| p.name == name) {
+            pkg.latest_version = Some(strip_platform(latest).to_string());
+            pkg.is_outdated = true;
+        }
+    }
+}
+
+async fn run_gem(action: &str, args: &[&str], package_name: &str) -> Result<()> {
+    println!("[GEM] {}: {}", action, package_name);
+
+    let output = run_command_with_timeout("gem", args, Duration::from_secs(300)).await?;
+
+    if output.status.success() {
+        println!("[GEM] Successfully finished {}: {}", action, package_name);
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to {} {}: {}", action, package_name, stderr))
+    }
+}
+
+/// Update a gem
+pub async fn update_gem_package(package_name: String) -> Result<()> {
+    run_gem("update", &["update", &package_name], &package_name).await
+}
+
+/// Uninstall every installed version of a gem, along with its executables
+pub async fn uninstall_gem_package(package_name: String) -> Result<()> {
+    run_gem(
+        "uninstall",
+        &["uninstall", &package_name, "--all", "--executables"],
+        &package_name,
+    )
+    .await
+}
+
+/// Install a gem
+pub async fn install_gem_package(package_name: String) -> Result<()> {
+    run_gem("install", &["install", &package_name], &package_name).await
+}
+
+/// Install an exact version (used for rollbacks)
+pub async fn install_gem_version(package_name: String, version: String) -> Result<()> {
+    run_gem(
+        "install",
+        &["install", &package_name, "--version", &version],
+        &package_name,
+    )
+    .await
+}
+
+/// Update RubyGems itself
+pub async fn self_update_gem() -> Result<()> {
+    run_gem("update", &["update", "--system"], "RubyGems").await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_and_outdated_output() {
+        let list = "\n*** LOCAL GEMS ***\n\nbundler (2.5.3, default: 2.4.10)\n\
+                    json (default: 2.7.1)\nnokogiri (1.16.0-arm64-darwin, 1.15.5 arm64-darwin)\n\
+                    rake (13.0.6)\n";
+        let mut packages = parse_gem_list(list);
+        let versions: Vec<(&str, &str)> = packages
+            .iter()
+            .map(|p| (p.name.as_str(), p.installed_version.as_str()))
+            .collect();
+        assert_eq!(
+            versions,
+            [
+                ("bundler", "2.5.3"),
+                ("json", "2.7.1"),
+                ("nokogiri", "1.16.0"),
+                ("rake", "13.0.6")
+            ]
+        );
+
+        mark_outdated("rake (13.0.6 < 13.1.0)\n", &mut packages);
+        let rake = packages.iter().find(|p| p.name == "rake").unwrap();
+        assert!(rake.is_outdated);
+        assert_eq!(rake.latest_version.as_deref(), Some("13.1.0"));
+        assert!(!packages[0].is_outdated);
+    }
+}
@@ -0,0 +1,174 @@
+use crate::models::{Package, PackageManager};
+use crate::utils::run_command_with_timeout;
+use anyhow::{anyhow, Result};
+use std::time::Duration;
+
+/// GitHub CLI extensions: "gh dash\tdlvhdr/gh-dash\tv4.5.4" (tab separated when piped)
+pub async fn list_gh_extensions() -> Result<Vec<Package>> {
+    println!("[GH] Listing extensions");
+
+    let output =
+        run_command_with_timeout("gh", &["extension", "list"], Duration::from_secs(30)).await?;
+
+    if !output.status.success() {
+        return Err(anyhow!("gh extension list failed"));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let packages: Vec<Package> = stdout
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t').map(str::trim);
+            let name = fields.next()?;
+            let repo = fields.next()?;
+            let version = fields.next().filter(|v| !v.is_empty()).unwrap_or("-");
+            let mut pkg = Package::new(
+                name.strip_prefix("gh ").unwrap_or(name),
+                PackageManager::GhExtensions,
+                version,
+            );
+            pkg.description = Some(repo.to_string());
+            Some(pkg)
+        })
+        .collect();
+
+    println!("[GH] Found {} extensions", packages.len());
+    Ok(packages)
+}
+
+/// `--dry-run` reports "[dash]: would have upgraded from v4.5.4 to v4.7.0" per extension
+pub async fn check_outdated_gh_extensions(packages: &mut [Package]) -> Result<()> {
+    println!("[GH] Checking extensions for upgrades");
+
+    let output = run_command_with_timeout(
+        "gh",
+        &["extension", "upgrade", "--all", "--dry-run"],
+        Duration::from_secs(120),
+    )
+    .await?;
+
+    // gh writes progress to stderr; read both
+    let text = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    for line in text.lines() {
+        let Some((name, rest)) = line
+            .trim()
+            .strip_prefix('[')
+            .and_then(|l| l.split_once("]:"))
+        else {
+            continue;
+        };
+        let Some(latest) = rest
+            .split_once("would have upgraded from")
+            .and_then(|(_, versions)| versions.split(" to ").nth(1))
+        else {
+            continue;
+        };
+        if let Some(pkg) = packages.iter_mut().find(|p| p.name == name) {
+            pkg.latest_version = Some(latest.trim().to_string());
+            pkg.is_outdated = true;
+        }
+    }
+
+    Ok(())
+}
+
+async fn run_gh_extension(action: &str, name: &str) -> Result<()> {
+    println!("[GH] {} extension: {}", action, name);
+
+    let output =
+        run_command_with_timeout("gh", &["extension", action, name], Duration::from_secs(300))
+            .await?;
+
+    if output.status.success() {
+        println!("[GH] Successfully finished {}: {}", action, name);
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to {} {}: {}", action, name, stderr))
+    }
+}
+
+pub async fn update_gh_extension(name: String) -> Result<()> {
+    run_gh_extension("upgrade", &name).await
+}
+
+pub async fn uninstall_gh_extension(name: String) -> Result<()> {
+    run_gh_extension("remove", &name).await
+}
+
+/// Installing needs the extension's repository ("owner/gh-name"), not its short name
+pub async fn install_gh_extension(name: String) -> Result<()> {
+    if !name.contains('/') {
+        return Err(anyhow!(
+            "gh extensions install from a repository; run `gh extension install <owner>/gh-{}`",
+            name
+        ));
+    }
+    run_gh_extension("install", &name).await
+}
+
+/// Program a credential.helper value runs: "osxkeychain" -> git-credential-osxkeychain,
+/// "!/usr/bin/gh auth git-credential" -> gh, "store --file ~/.creds" -> git-credential-store
+fn helper_program(value: &str) -> Option<String> {
+    if let Some(shell) = value.strip_prefix('!') {
+        let program = shell.split_whitespace().next()?;
+        return Some(program.rsplit('/').next()?.to_string());
+    }
+    let helper = value.split_whitespace().next()?;
+    if helper.contains('/') {
+        return Some(helper.rsplit('/').next()?.to_string());
+    }
+    Some(format!("git-credential-{}", helper))
+}
+
+/// Credential helpers configured for git. They're installed by other managers (or ship
+/// with git), so this is an inventory of what git relies on rather than something to update.
+pub async fn list_git_helpers() -> Result<Vec<Package>> {
+    println!("[GIT] Listing credential helpers");
+
+    let output = run_command_with_timeout(
+        "git",
+        &["config", "--get-all", "credential.helper"],
+        Duration::from_secs(10),
+    )
+    .await?;
+
+    // Exit code 1 just means nothing is configured
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut packages: Vec<Package> = Vec::new();
+    for value in stdout.lines().map(str::trim).filter(|v| !v.is_empty()) {
+        let Some(program) = helper_program(value) else {
+            continue;
+        };
+        if packages.iter().any(|p| p.name == program) {
+            continue;
+        }
+
+        // Not every helper understands --version; first version-looking token if it does
+        let version =
+            match run_command_with_timeout(&program, &["--version"], Duration::from_secs(10)).await
+            {
+                Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout)
+                    .split_whitespace()
+                    .map(|w| w.trim_start_matches('v'))
+                    .find(|w| w.starts_with(|c: char| c.is_ascii_digit()))
+                    .map(str::to_string),
+                _ => None,
+            };
+
+        let mut pkg = Package::new(
+            program,
+            PackageManager::GitHelpers,
+            version.unwrap_or_else(|| "-".to_string()),
+        );
+        pkg.description = Some(format!("credential.helper = {}", value));
+        packages.push(pkg);
+    }
+
+    println!("[GIT] Found {} credential helpers", packages.len());
+    Ok(packages)
+}
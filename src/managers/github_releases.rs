@@ -0,0 +1,125 @@
+use crate::utils::cache::{get_cached, set_cached};
+use crate::utils::http_client::{create_http_client, get_json_revalidated};
+use crate::utils::metrics::SendTimed;
+use crate::utils::version::highest_tag;
+use anyhow::{anyhow, Result};
+
+/// Branches move faster than releases
+const HEAD_CACHE_TTL: u64 = 3600;
+
+/// The version part of a tag: "v1.2.3", "name-1.2.3" and "name@1.2.3" all give "1.2.3"
+pub fn tag_version(tag: &str) -> &str {
+    let start = tag.find(|c: char| c.is_ascii_digit()).unwrap_or(0);
+    &tag[start..]
+}
+
+async fn get_github(path: &str) -> Result<reqwest::Response> {
+    let url = format!("https://api.github.com/{}", path);
+    let response = create_http_client()
+        .get(&url)
+        // GitHub rejects requests without a User-Agent
+        .header("User-Agent", "depmgr")
+        .send_timed()
+        .await?;
+    Ok(response)
+}
+
+/// Like `get_github`, but revalidated with the ETag of the last response; None on 404
+async fn get_github_json(path: &str) -> Result<Option<serde_json::Value>> {
+    let url = format!("https://api.github.com/{}", path);
+    let request = create_http_client()
+        .get(&url)
+        .header("User-Agent", "depmgr");
+    get_json_revalidated(request, &format!("github_etag:{}", path)).await
+}
+
+/// Tag of the repository's latest release, or its newest tag if it doesn't publish
+/// releases (tools installed from git often only tag)
+pub async fn latest_tag(repo: &str) -> Result<String> {
+    let cache_key = format!("github_latest_{}", repo);
+    if let Some(tag) = get_cached::<String>(&cache_key) {
+        return Ok(tag);
+    }
+
+    let tag = match get_github_json(&format!("repos/{}/releases/latest", repo)).await? {
+        Some(json) => json["tag_name"].as_str().map(String::from),
+        // Tags are listed by name, not version ("v1.9.0" before "v1.10.0")
+        None => get_github_json(&format!("repos/{}/tags?per_page=100", repo))
+            .await?
+            .and_then(|json| {
+                let names: Vec<&str> = json
+                    .as_array()?
+                    .iter()
+                    .filter_map(|t| t["name"].as_str())
+                    .collect();
+                let highest = highest_tag(names.iter().copied())?;
+                names
+                    .into_iter()
+                    .find(|name| name.trim_start_matches('v') == highest)
+                    .map(String::from)
+            }),
+    };
+    let tag = tag.ok_or_else(|| anyhow!("{} has no releases or tags", repo))?;
+
+    set_cached(cache_key, &tag, crate::config::cache_ttls().release_ttl());
+    Ok(tag)
+}
+
+/// Newest commit on the repository's default branch, for builds that follow HEAD
+pub async fn head_commit(repo: &str) -> Result<String> {
+    let cache_key = format!("github_head_{}", repo);
+    if let Some(sha) = get_cached::<String>(&cache_key) {
+        return Ok(sha);
+    }
+
+    let json = get_github_json(&format!("repos/{}/commits/HEAD", repo))
+        .await?
+        .ok_or_else(|| anyhow!("HEAD of {} returned 404 Not Found", repo))?;
+    let sha = json["sha"]
+        .as_str()
+        .map(String::from)
+        .ok_or_else(|| anyhow!("HEAD of {} has no commit", repo))?;
+
+    set_cached(cache_key, &sha, HEAD_CACHE_TTL);
+    Ok(sha)
+}
+
+/// Whether `tag` has commits the build from `commit` lacks, for installs that track a
+/// branch rather than a version
+pub async fn tag_is_ahead_of(repo: &str, commit: &str, tag: &str) -> Result<bool> {
+    let cache_key = format!("github_compare_{}_{}_{}", repo, commit, tag);
+    if let Some(ahead) = get_cached::<bool>(&cache_key) {
+        return Ok(ahead);
+    }
+
+    let response = get_github(&format!("repos/{}/compare/{}...{}", repo, commit, tag)).await?;
+    if !response.status().is_success() {
+        return Err(anyhow!(
+            "comparing {} with {} in {} returned {}",
+            commit,
+            tag,
+            repo,
+            response.status()
+        ));
+    }
+    let json: serde_json::Value = response.json().await?;
+    // "ahead": the tag is newer; "behind"/"identical": the build already has it;
+    // "diverged": the release was cut from another branch
+    let ahead = json["status"].as_str() == Some("ahead");
+
+    set_cached(cache_key, &ahead, crate::config::cache_ttls().release_ttl());
+    Ok(ahead)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions_from_tags() {
+        assert_eq!(tag_version("v14.1.0"), "14.1.0");
+        assert_eq!(tag_version("ripgrep-14.1.0"), "14.1.0");
+        assert_eq!(tag_version("@scope/pkg@2.0.0"), "2.0.0");
+        assert_eq!(tag_version("nightly"), "nightly");
+    }
+}
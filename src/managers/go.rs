@@ -0,0 +1,212 @@
+use crate::models::{Package, PackageManager};
+use crate::utils::run_command_with_timeout;
+use anyhow::{anyhow, Result};
+use futures::{stream, StreamExt};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// A `go install`ed binary, from `go version -m`
+#[derive(Debug, PartialEq)]
+struct GoBinary {
+    file: PathBuf,
+    package: String, // what `go install` was given, e.g. golang.org/x/tools/gopls
+    module: String,  // the module it came from, which the proxy versions
+    version: String, // "(devel)" for local builds
+}
+
+/// Where `go install` puts binaries: $GOBIN, else the first $GOPATH entry's bin
+pub async fn go_bin_dir() -> Result<PathBuf> {
+    let output =
+        run_command_with_timeout("go", &["env", "GOBIN", "GOPATH"], Duration::from_secs(10))
+            .await?;
+    if !output.status.success() {
+        return Err(anyhow!("go env failed"));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    let gobin = lines.next().unwrap_or("").trim();
+    if !gobin.is_empty() {
+        return Ok(PathBuf::from(gobin));
+    }
+    let gopath = lines
+        .next()
+        .and_then(|paths| std::env::split_paths(paths.trim()).next())
+        .filter(|path| !path.as_os_str().is_empty())
+        .ok_or_else(|| anyhow!("neither GOBIN nor GOPATH is set"))?;
+    Ok(gopath.join("bin"))
+}
+
+/// Build info of every Go binary in the bin directory
+async fn read_go_binaries() -> Result<Vec<GoBinary>> {
+    let dir = go_bin_dir().await?;
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let dir = dir.to_string_lossy().to_string();
+    // Given a directory, `go version -m` reports each Go binary in it and skips the rest
+    let output =
+        run_command_with_timeout("go", &["version", "-m", &dir], Duration::from_secs(60)).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("go version -m failed: {}", stderr));
+    }
+    Ok(parse_build_info(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// `go version -m` output: a "file: go1.22.0" line per binary, then tab-indented
+/// "path", "mod" and "dep" lines
+fn parse_build_info(stdout: &str) -> Vec<GoBinary> {
+    let mut binaries = Vec::new();
+    let mut current: Option<GoBinary> = None;
+    for line in stdout.lines() {
+        if !line.starts_with('\t') {
+            binaries.extend(current.take());
+            if let Some((file, _go)) = line.rsplit_once(": ") {
+                current = Some(GoBinary {
+                    file: PathBuf::from(file),
+                    package: String::new(),
+                    module: String::new(),
+                    version: String::new(),
+                });
+            }
+            continue;
+        }
+        let Some(binary) = current.as_mut() else {
+            continue;
+        };
+        let fields: Vec<&str> = line.trim_start().split('\t').collect();
+        match fields.as_slice() {
+            ["path", package, ..] => binary.package = package.to_string(),
+            ["mod", module, version, ..] => {
+                binary.module = module.to_string();
+                binary.version = version.to_string();
+            }
+            _ => {}
+        }
+    }
+    binaries.extend(current);
+    // Binaries without module info (built with GOPATH mode) can't be reinstalled by path
+    binaries.retain(|binary| !binary.package.is_empty() && !binary.module.is_empty());
+    binaries
+}
+
+/// List `go install`ed binaries and check their modules on proxy.golang.org
+pub async fn list_go_binaries() -> Result<Vec<Package>> {
+    println!("[GO] Listing installed binaries");
+
+    let binaries = read_go_binaries().await?;
+    let packages: Vec<Package> = stream::iter(binaries)
+        .map(|binary| async move {
+            let binary_name = binary
+                .file
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let mut pkg = Package::new(binary.package, PackageManager::Go, binary.version);
+            pkg.description = Some(format!("{} binary, module {}", binary_name, binary.module));
+            pkg.size = std::fs::metadata(&binary.file).ok().map(|m| m.len());
+            if pkg.installed_version != "(devel)" {
+                match super::go_modules::latest_module_version(&binary.module).await {
+                    Ok(latest) => {
+                        pkg.is_outdated = crate::utils::version::compare_versions(
+                            &latest,
+                            &pkg.installed_version,
+                        ) == std::cmp::Ordering::Greater;
+                        pkg.latest_version = Some(latest);
+                    }
+                    Err(e) => eprintln!("[GO] {}", e),
+                }
+            }
+            pkg
+        })
+        // In `go version -m` order: the packages come back positionally, not by key
+        .buffered(crate::config::registry_lookups())
+        .collect()
+        .await;
+
+    println!("[GO] Found {} installed binaries", packages.len());
+    Ok(packages)
+}
+
+async fn run_go_install(package_name: &str, version: &str) -> Result<()> {
+    let spec = format!("{}@{}", package_name, version);
+    println!("[GO] Installing: {}", spec);
+
+    let output =
+        run_command_with_timeout("go", &["install", &spec], Duration::from_secs(600)).await?;
+
+    if output.status.success() {
+        println!("[GO] Successfully installed: {}", spec);
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to install {}: {}", spec, stderr))
+    }
+}
+
+/// Reinstall at the latest version; `go install` has no separate upgrade
+pub async fn update_go_binary(package_name: String) -> Result<()> {
+    run_go_install(&package_name, "latest").await
+}
+
+pub async fn install_go_binary(package_name: String) -> Result<()> {
+    run_go_install(&package_name, "latest").await
+}
+
+/// Install an exact version (used for rollbacks)
+pub async fn install_go_version(package_name: String, version: String) -> Result<()> {
+    run_go_install(&package_name, &version).await
+}
+
+/// Go has no uninstall command: delete the binary the package was installed as
+pub async fn uninstall_go_binary(package_name: String) -> Result<()> {
+    println!("[GO] Uninstalling: {}", package_name);
+
+    let binary = read_go_binaries()
+        .await?
+        .into_iter()
+        .find(|binary| binary.package == package_name)
+        .ok_or_else(|| anyhow!("No binary installed from {}", package_name))?;
+    std::fs::remove_file(&binary.file)
+        .map_err(|e| anyhow!("Failed to remove {}: {}", binary.file.display(), e))?;
+
+    println!("[GO] Removed {}", binary.file.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_info_per_binary() {
+        let stdout = "/home/me/go/bin/gopls: go1.22.0\n\
+            \tpath\tgolang.org/x/tools/gopls\n\
+            \tmod\tgolang.org/x/tools/gopls\tv0.15.1\th1:abc=\n\
+            \tdep\tgolang.org/x/mod\tv0.15.0\th1:def=\n\
+            \tbuild\t-compiler=gc\n\
+            /home/me/go/bin/mytool: go1.22.0\n\
+            \tpath\texample.com/mytool/cmd/mytool\n\
+            \tmod\texample.com/mytool\t(devel)\t\n\
+            /home/me/go/bin/old: go1.10\n";
+
+        let binaries = parse_build_info(stdout);
+        assert_eq!(
+            binaries,
+            [
+                GoBinary {
+                    file: PathBuf::from("/home/me/go/bin/gopls"),
+                    package: "golang.org/x/tools/gopls".into(),
+                    module: "golang.org/x/tools/gopls".into(),
+                    version: "v0.15.1".into(),
+                },
+                GoBinary {
+                    file: PathBuf::from("/home/me/go/bin/mytool"),
+                    package: "example.com/mytool/cmd/mytool".into(),
+                    module: "example.com/mytool".into(),
+                    version: "(devel)".into(),
+                },
+            ]
+        );
+    }
+}
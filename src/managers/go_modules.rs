@@ -0,0 +1,103 @@
+use crate::models::{PackageManager, Project};
+use crate::utils::cache::{get_cached, set_cached};
+use crate::utils::http_client::create_http_client;
+use crate::utils::metrics::SendTimed;
+use anyhow::{anyhow, Result};
+use futures::{stream, StreamExt};
+use std::collections::{HashMap, HashSet};
+
+/// The module proxy's case encoding: uppercase letters become `!` + lowercase
+fn escape_module_path(module: &str) -> String {
+    let mut escaped = String::with_capacity(module.len());
+    for c in module.chars() {
+        if c.is_ascii_uppercase() {
+            escaped.push('!');
+            escaped.push(c.to_ascii_lowercase());
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+/// Latest version of a module according to proxy.golang.org
+pub(super) async fn latest_module_version(module: &str) -> Result<String> {
+    let cache_key = format!("go_module_latest_{}", module);
+    if let Some(version) = get_cached::<String>(&cache_key) {
+        return Ok(version);
+    }
+
+    let url = format!(
+        "https://proxy.golang.org/{}/@latest",
+        escape_module_path(module)
+    );
+    let response = create_http_client().get(&url).send_timed().await?;
+    if !response.status().is_success() {
+        return Err(anyhow!("{} returned {}", url, response.status()));
+    }
+    let json: serde_json::Value = response.json().await?;
+    let version = json["Version"]
+        .as_str()
+        .ok_or_else(|| anyhow!("no version for {}", module))?
+        .to_string();
+
+    set_cached(
+        cache_key,
+        &version,
+        crate::config::cache_ttls().registry_ttl(),
+    );
+    Ok(version)
+}
+
+/// Look up the latest version of every Go module the projects require
+pub async fn check_project_modules(projects: &mut [Project]) {
+    let modules: HashSet<String> = projects
+        .iter()
+        .flat_map(|p| &p.dependencies)
+        .filter(|d| d.manager == PackageManager::Go)
+        .map(|d| d.package_name.clone())
+        .collect();
+    if modules.is_empty() {
+        return;
+    }
+    println!(
+        "[GO] Checking {} modules on proxy.golang.org",
+        modules.len()
+    );
+
+    let latest: HashMap<String, String> = stream::iter(modules)
+        .map(|module| async move {
+            match latest_module_version(&module).await {
+                Ok(version) => Some((module, version)),
+                Err(e) => {
+                    eprintln!("[GO] {}", e);
+                    None
+                }
+            }
+        })
+        .buffer_unordered(crate::config::registry_lookups())
+        .filter_map(|found| async move { found })
+        .collect()
+        .await;
+
+    for dep in projects
+        .iter_mut()
+        .flat_map(|p| p.dependencies.iter_mut())
+        .filter(|d| d.manager == PackageManager::Go)
+    {
+        dep.latest_version = latest.get(&dep.package_name).cloned();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn module_path_case_encoding() {
+        assert_eq!(
+            escape_module_path("github.com/BurntSushi/toml"),
+            "github.com/!burnt!sushi/toml"
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use crate::models::{Package, PackageManager};
+use crate::utils::http_client::create_http_client;
+use crate::utils::metrics::SendTimed;
+use crate::utils::run_command_with_timeout;
+use anyhow::{anyhow, Result};
+use std::time::Duration;
+
+/// `ghcup list -r` rows: "ghc 9.4.8 recommended,base-4.17.2.1 hls-powered"
+fn parse_ghcup_rows(stdout: &str) -> Vec<(String, String, Vec<String>)> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let tool = fields.next()?.to_string();
+            let version = fields.next()?.to_string();
+            let tags = fields
+                .next()
+                .map(|t| t.split(',').map(|s| s.to_string()).collect())
+                .unwrap_or_default();
+            Some((tool, version, tags))
+        })
+        .collect()
+}
+
+/// List toolchain components installed through ghcup (ghc, cabal, hls, stack)
+pub async fn list_ghcup_tools() -> Result<Vec<Package>> {
+    println!("[GHCUP] Listing installed tools");
+
+    let output = run_command_with_timeout(
+        "ghcup",
+        &["list", "-r", "-c", "installed"],
+        Duration::from_secs(30),
+    )
+    .await?;
+
+    if !output.status.success() {
+        return Err(anyhow!("ghcup list failed"));
+    }
+
+    let packages: Vec<Package> = parse_ghcup_rows(&String::from_utf8_lossy(&output.stdout))
+        .into_iter()
+        .map(|(tool, version, _)| Package::new(tool, PackageManager::Ghcup, version))
+        .collect();
+
+    println!("[GHCUP] Found {} installed tools", packages.len());
+    Ok(packages)
+}
+
+/// A tool is outdated when none of its installed versions is the one tagged "latest"
+pub async fn check_outdated_ghcup(packages: &mut [Package]) -> Result<()> {
+    println!("[GHCUP] Checking for outdated tools");
+
+    let output =
+        run_command_with_timeout("ghcup", &["list", "-r"], Duration::from_secs(60)).await?;
+    if !output.status.success() {
+        return Ok(()); // Not a fatal error
+    }
+
+    let latest: Vec<(String, String)> = parse_ghcup_rows(&String::from_utf8_lossy(&output.stdout))
+        .into_iter()
+        .filter(|(_, _, tags)| tags.iter().any(|t| t == "latest"))
+        .map(|(tool, version, _)| (tool, version))
+        .collect();
+
+    for (tool, version) in latest {
+        let has_latest = packages
+            .iter()
+            .any(|p| p.name == tool && p.installed_version == version);
+        for pkg in packages.iter_mut().filter(|p| p.name == tool) {
+            pkg.latest_version = Some(version.clone());
+            pkg.is_outdated = !has_latest;
+        }
+    }
+
+    Ok(())
+}
+
+async fn run_ghcup(action: &str, args: &[&str], tool: &str) -> Result<()> {
+    println!("[GHCUP] {}: {}", action, tool);
+
+    // Compiling/downloading GHC takes a while
+    let output = run_command_with_timeout("ghcup", args, Duration::from_secs(1800)).await?;
+
+    if output.status.success() {
+        println!("[GHCUP] Successfully finished {}: {}", action, tool);
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to {} {}: {}", action, tool, stderr))
+    }
+}
+
+pub async fn update_ghcup_tool(tool: String) -> Result<()> {
+    if tool == "ghcup" {
+        return run_ghcup("upgrade", &["upgrade"], &tool).await;
+    }
+    run_ghcup("install", &["install", &tool, "latest"], &tool).await?;
+    run_ghcup("set", &["set", &tool, "latest"], &tool).await
+}
+
+pub async fn install_ghcup_tool(tool: String) -> Result<()> {
+    run_ghcup("install", &["install", &tool, "recommended"], &tool).await
+}
+
+pub async fn install_ghcup_version(tool: String, version: String) -> Result<()> {
+    run_ghcup("install", &["install", &tool, &version], &tool).await?;
+    run_ghcup("set", &["set", &tool, &version], &tool).await
+}
+
+/// ghcup removes a specific version: the given one, or the only one installed
+pub async fn uninstall_ghcup_tool(tool: String, version: Option<String>) -> Result<()> {
+    if let Some(version) = version {
+        return run_ghcup("remove", &["rm", &tool, &version], &tool).await;
+    }
+    let installed: Vec<String> = list_ghcup_tools()
+        .await?
+        .into_iter()
+        .filter(|p| p.name == tool)
+        .map(|p| p.installed_version)
+        .collect();
+
+    match installed.as_slice() {
+        [version] => run_ghcup("remove", &["rm", &tool, version], &tool).await,
+        [] => Err(anyhow!("{} is not installed", tool)),
+        versions => Err(anyhow!(
+            "Several {} versions are installed ({}); remove one with `ghcup rm {} <version>`",
+            tool,
+            versions.join(", "),
+            tool
+        )),
+    }
+}
+
+/// Stack itself plus the compilers it downloaded (`stack ls tools`)
+pub async fn list_stack_tools() -> Result<Vec<Package>> {
+    println!("[STACK] Listing installed tools");
+
+    let output =
+        run_command_with_timeout("stack", &["--numeric-version"], Duration::from_secs(10)).await?;
+    if !output.status.success() {
+        return Err(anyhow!("stack --numeric-version failed"));
+    }
+    let mut packages = vec![Package::new(
+        "stack",
+        PackageManager::Stack,
+        String::from_utf8_lossy(&output.stdout).trim(),
+    )];
+
+    // "ghc-9.4.7", "msys2-20230526"
+    if let Ok(output) =
+        run_command_with_timeout("stack", &["ls", "tools"], Duration::from_secs(30)).await
+    {
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            if let Some((name, version)) = line.trim().rsplit_once('-') {
+                packages.push(Package::new(name, PackageManager::Stack, version));
+            }
+        }
+    }
+
+    println!("[STACK] Found {} installed tools", packages.len());
+    Ok(packages)
+}
+
+/// Only stack itself has a meaningful "latest"; compilers follow the project's resolver
+pub async fn check_outdated_stack(packages: &mut [Package]) -> Result<()> {
+    println!("[STACK] Checking for a newer stack release");
+
+    let json: serde_json::Value = create_http_client()
+        .get("https://api.github.com/repos/commercialhaskell/stack/releases/latest")
+        .header("User-Agent", "depmgr") // GitHub rejects API requests without one
+        .send_timed()
+        .await?
+        .json()
+        .await?;
+
+    if let Some(latest) = json["tag_name"].as_str().map(|t| t.trim_start_matches('v')) {
+        if let Some(stack) = packages.iter_mut().find(|p| p.name == "stack") {
+            stack.latest_version = Some(latest.to_string());
+            stack.is_outdated =
+                crate::utils::version::compare_versions(latest, &stack.installed_version)
+                    == std::cmp::Ordering::Greater;
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn update_stack_tool(tool: String) -> Result<()> {
+    if tool != "stack" {
+        return Err(anyhow!(
+            "stack installs {} per resolver; bump the project's resolver instead",
+            tool
+        ));
+    }
+    println!("[STACK] Upgrading stack");
+    let output = run_command_with_timeout("stack", &["upgrade"], Duration::from_secs(600)).await?;
+    if output.status.success() {
+        println!("[STACK] Successfully upgraded stack");
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to upgrade stack: {}", stderr))
+    }
+}
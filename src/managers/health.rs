@@ -0,0 +1,190 @@
+use crate::models::PackageManager;
+use crate::utils::cache::{get_cached, set_cached};
+use crate::utils::http_client::create_http_client;
+use crate::utils::metrics::SendTimed;
+use crate::utils::run_command_with_timeout;
+use crate::utils::version::compare_versions;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+static VERSION_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d+(\.\d+)+").unwrap());
+
+/// Status of a package manager itself, shown as a chip next to it in the sidebar
+#[derive(Debug, Clone, Default)]
+pub struct ManagerHealth {
+    pub version: Option<String>,
+    pub binary_path: Option<String>,
+    pub version_output: Option<String>,
+    /// Newer upstream release of the manager itself, if the installed one is behind
+    pub self_outdated: Option<String>,
+    pub last_scan: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub package_count: usize,
+}
+
+pub type HealthMap = Arc<RwLock<HashMap<PackageManager, ManagerHealth>>>;
+
+/// Ask upstream for the newest release of the manager itself
+pub async fn latest_self_version(manager: &PackageManager) -> Option<String> {
+    let cache_key = format!("self_latest:{}", manager.command());
+    if let Some(cached) = get_cached::<String>(&cache_key) {
+        return Some(cached);
+    }
+
+    let latest = match manager {
+        PackageManager::Npm => {
+            let output = run_command_with_timeout(
+                "npm",
+                &["view", "npm", "version"],
+                Duration::from_secs(15),
+            )
+            .await
+            .ok()?;
+            let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            VERSION_RE.find(&text).map(|m| m.as_str().to_string())
+        }
+        PackageManager::Pip => {
+            let json: serde_json::Value = create_http_client()
+                .get(crate::utils::registries::pypi_url("pip"))
+                .send_timed()
+                .await
+                .ok()?
+                .json()
+                .await
+                .ok()?;
+            json["info"]["version"].as_str().map(|v| v.to_string())
+        }
+        PackageManager::Homebrew => {
+            // GitHub rejects API requests without a User-Agent
+            let json: serde_json::Value = create_http_client()
+                .get("https://api.github.com/repos/Homebrew/brew/releases/latest")
+                .header("User-Agent", "depmgr")
+                .send_timed()
+                .await
+                .ok()?
+                .json()
+                .await
+                .ok()?;
+            json["tag_name"].as_str().map(|v| v.to_string())
+        }
+        PackageManager::Cargo => {
+            // "stable-x86_64-unknown-linux-gnu - Update available : 1.79.0 (...) -> 1.80.0 (...)"
+            let output = run_command_with_timeout("rustup", &["check"], Duration::from_secs(30))
+                .await
+                .ok()?;
+            let text = String::from_utf8_lossy(&output.stdout).to_string();
+            text.lines()
+                .filter(|l| !l.starts_with("rustup ") && l.contains("Update available"))
+                .find_map(|l| l.split("->").nth(1))
+                .and_then(|after| VERSION_RE.find(after))
+                .map(|m| m.as_str().to_string())
+        }
+        _ => None,
+    }?;
+
+    set_cached(
+        cache_key,
+        &latest,
+        crate::config::cache_ttls().release_ttl(),
+    );
+    Some(latest)
+}
+
+/// Newer release of the manager, if the installed one is behind
+pub async fn check_self_outdated(manager: &PackageManager, current: &str) -> Option<String> {
+    let latest = latest_self_version(manager).await?;
+    if compare_versions(&latest, current) == Ordering::Greater {
+        println!(
+            "[HEALTH] {} itself is outdated: {} -> {}",
+            manager.name(),
+            current,
+            latest
+        );
+        Some(latest)
+    } else {
+        None
+    }
+}
+
+/// Run `<manager> --version` and `which <manager>` for the diagnostics view
+pub async fn probe_manager(manager: &PackageManager) -> ManagerHealth {
+    let cmd = manager.command();
+    let mut health = ManagerHealth::default();
+
+    if let Ok(output) = run_command_with_timeout(cmd, &["--version"], Duration::from_secs(10)).await
+    {
+        // Some tools (Rscript) print their version to stderr
+        let stream = if output.stdout.is_empty() {
+            &output.stderr
+        } else {
+            &output.stdout
+        };
+        let text = String::from_utf8_lossy(stream).trim().to_string();
+        health.version = VERSION_RE.find(&text).map(|m| m.as_str().to_string());
+        health.version_output = text.lines().next().map(|l| l.to_string());
+    }
+
+    if let Ok(output) = run_command_with_timeout("which", &[cmd], Duration::from_secs(2)).await {
+        let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !path.is_empty() {
+            health.binary_path = Some(path);
+        }
+    }
+
+    health
+}
+
+/// Probe every manager concurrently, keeping scan results already recorded
+pub async fn probe_all(managers: &[PackageManager], health: HealthMap) {
+    let probes = futures::future::join_all(managers.iter().map(probe_manager)).await;
+
+    {
+        let mut map = health.write().await;
+        for (manager, probe) in managers.iter().zip(probes.iter()) {
+            let entry = map.entry(manager.clone()).or_default();
+            entry.version = probe.version.clone();
+            entry.version_output = probe.version_output.clone();
+            entry.binary_path = probe.binary_path.clone();
+        }
+    }
+
+    // Upstream lookups are slower, so the chips show versions before this finishes
+    let checks = futures::future::join_all(managers.iter().zip(probes.iter()).map(
+        |(manager, probe)| async move {
+            match &probe.version {
+                Some(current) => check_self_outdated(manager, current).await,
+                None => None,
+            }
+        },
+    ))
+    .await;
+
+    let mut map = health.write().await;
+    for (manager, self_outdated) in managers.iter().zip(checks) {
+        map.entry(manager.clone()).or_default().self_outdated = self_outdated;
+    }
+}
+
+/// Record how a manager's listing went, for the chip's error state
+pub async fn record_scan(
+    health: &HealthMap,
+    manager: &PackageManager,
+    result: Result<&[crate::models::Package], String>,
+) {
+    let mut map = health.write().await;
+    let entry = map.entry(manager.clone()).or_default();
+    entry.last_scan = Some(Utc::now());
+
+    match result {
+        Ok(packages) => {
+            entry.last_error = None;
+            entry.package_count = packages.len();
+        }
+        Err(error) => entry.last_error = Some(error),
+    }
+}
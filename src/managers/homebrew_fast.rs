@@ -1,9 +1,13 @@
-use crate::models::{Package, PackageManager};
+use crate::config::DescriptionSource;
+use crate::models::{Package, PackageFlags, PackageManager};
 use crate::utils::cache::{get_cached, set_cached};
-use crate::utils::http_client::create_http_client;
+use crate::utils::http_client::{create_http_client, get_json_revalidated, revalidated};
+use crate::utils::metrics::SendTimed;
+use crate::utils::version::{compare_versions, is_newer, satisfies};
 use anyhow::{anyhow, Result};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Deserialize, Serialize)]
 struct FormulaInfo {
@@ -11,6 +15,28 @@ struct FormulaInfo {
     #[serde(default)]
     desc: Option<String>,
     versions: Versions,
+    #[serde(default)]
+    build_dependencies: Vec<String>,
+    #[serde(default)]
+    conflicts_with: Vec<String>,
+    #[serde(default)]
+    bottle: Bottles,
+    #[serde(default)]
+    homepage: Option<String>,
+    #[serde(default)]
+    urls: Urls,
+}
+
+impl FormulaInfo {
+    /// GitHub repository to check for releases, from the source URLs or homepage
+    fn github_repo(&self) -> Option<String> {
+        [&self.urls.head, &self.urls.stable]
+            .into_iter()
+            .flatten()
+            .map(|source| source.url.as_str())
+            .chain(self.homepage.as_deref())
+            .find_map(crate::audit::changelog::github_repo)
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -18,73 +44,110 @@ struct Versions {
     stable: Option<String>,
 }
 
-/// BLAZINGLY FAST: Fetch ALL Homebrew packages in ONE API call
-pub async fn list_homebrew_packages_fast() -> Result<Vec<Package>> {
-    println!("[FAST] Fetching Homebrew packages via API...");
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct Urls {
+    stable: Option<SourceUrl>,
+    head: Option<SourceUrl>,
+}
 
-    // Check cache first (1 hour TTL)
-    if let Some(cached_packages) = get_cached::<Vec<Package>>("homebrew_all_packages") {
-        println!(
-            "[FAST] ✓ Loaded {} packages from cache (instant!)",
-            cached_packages.len()
-        );
-        return Ok(cached_packages);
-    }
+#[derive(Debug, Deserialize, Serialize)]
+struct SourceUrl {
+    url: String,
+}
 
-    let client = create_http_client();
+/// `brew info --json=v2` output; font casks are left to the Fonts backend
+#[derive(Debug, Deserialize)]
+struct BrewInfo {
+    #[serde(default)]
+    formulae: Vec<FormulaInfo>,
+    #[serde(default)]
+    casks: Vec<CaskInfo>,
+}
 
-    // Fetch ALL formulas in ONE request
-    let url = "https://formulae.brew.sh/api/formula.json";
-    let start = std::time::Instant::now();
+#[derive(Debug, Deserialize)]
+struct CaskInfo {
+    token: String,
+    version: String,
+    installed: Option<String>,
+    #[serde(default)]
+    desc: Option<String>,
+    /// The app updates itself (Chrome, VS Code); plain `brew upgrade` leaves it alone
+    #[serde(default)]
+    auto_updates: Option<bool>,
+}
 
-    let response = client
-        .get(url)
-        .send()
-        .await
-        .map_err(|e| anyhow!("Failed to fetch Homebrew API: {}", e))?;
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct Bottles {
+    stable: Option<BottleSpec>,
+}
 
-    if !response.status().is_success() {
-        return Err(anyhow!(
-            "Homebrew API returned status: {}",
-            response.status()
-        ));
-    }
+/// Bottles of the stable version, by platform tag (`arm64_sonoma`, `x86_64_linux`, `all`)
+#[derive(Debug, Deserialize, Serialize)]
+struct BottleSpec {
+    #[serde(default)]
+    files: HashMap<String, BottleFile>,
+}
 
-    let formulas: Vec<FormulaInfo> = response
-        .json()
-        .await
-        .map_err(|e| anyhow!("Failed to parse Homebrew API response: {}", e))?;
+#[derive(Debug, Deserialize, Serialize)]
+struct BottleFile {
+    url: String,
+}
+
+/// Cache key marking the Homebrew API listing as checked; its age is how old the
+/// versions shown are
+pub const FORMULA_API_CACHE_KEY: &str = "homebrew_formula_api";
 
+/// The listing itself with its ETag, revalidated once the marker above expires
+const FORMULA_LISTING_KEY: &str = "homebrew_formula_api_listing";
+
+/// BLAZINGLY FAST: Fetch ALL Homebrew packages in ONE API call
+pub async fn list_homebrew_packages_fast() -> Result<Vec<Package>> {
+    println!("[FAST] Fetching Homebrew packages via API...");
+
+    let start = std::time::Instant::now();
+    let formulas = fetch_formulas().await?;
     let fetch_time = start.elapsed();
-    println!(
-        "[FAST] ✓ Fetched {} formulas in {:?}",
-        formulas.len(),
-        fetch_time
-    );
 
     // Get locally installed packages (fast CLI command)
     let installed = get_installed_packages().await?;
+    // Third-party taps aren't in the API; brew describes those few itself
+    let tapped = tap_formulae().await;
 
     // Parallel parse: Filter to only installed packages
     let start_parse = std::time::Instant::now();
-    let packages: Vec<Package> = formulas
+    let mut packages: Vec<Package> = formulas
         .par_iter() // Rayon parallel iterator
         .filter_map(|formula| {
             // Only include if it's installed locally
             installed.get(&formula.name).map(|local_version| {
+                // Outdated status, usage, and size are filled in by later phases
                 Package {
-                    name: formula.name.clone(),
-                    manager: PackageManager::Homebrew,
-                    installed_version: local_version.clone(),
                     latest_version: formula.versions.stable.clone(),
-                    is_outdated: false, // Will check later
                     description: formula.desc.clone(),
-                    used_in: vec![], // Will scan later
-                    size: None,      // Not available from API
+                    build_dependencies: formula.build_dependencies.clone(),
+                    conflicts: formula.conflicts_with.clone(),
+                    ..Package::new(
+                        formula.name.clone(),
+                        PackageManager::Homebrew,
+                        local_version.clone(),
+                    )
                 }
             })
         })
         .collect();
+    packages.extend(tapped.iter().filter_map(|formula| {
+        let local_version = installed.get(&formula.name)?;
+        Some(Package {
+            latest_version: formula.versions.stable.clone(),
+            description: formula.desc.clone(),
+            ..Package::new(
+                formula.name.clone(),
+                PackageManager::Homebrew,
+                local_version.clone(),
+            )
+        })
+    }));
+    packages.extend(app_casks().await);
 
     let parse_time = start_parse.elapsed();
     println!(
@@ -93,8 +156,43 @@ pub async fn list_homebrew_packages_fast() -> Result<Vec<Package>> {
         parse_time
     );
 
-    // Cache for 1 hour
-    set_cached("homebrew_all_packages".to_string(), &packages, 3600);
+    // Bottle URLs for this platform, for sizing downloads once we know what's outdated
+    let tag = bottle_tag().await;
+    let bottle_urls: HashMap<String, String> = formulas
+        .iter()
+        .filter(|formula| installed.contains_key(&formula.name))
+        .filter_map(|formula| {
+            let files = &formula.bottle.stable.as_ref()?.files;
+            let file = files.get(&tag).or_else(|| files.get("all"))?;
+            Some((formula.name.clone(), file.url.clone()))
+        })
+        .collect();
+
+    // HEAD builds and tap formulae get their latest version from GitHub instead
+    let github_repos: HashMap<String, String> = formulas
+        .iter()
+        .filter(|formula| {
+            installed
+                .get(&formula.name)
+                .is_some_and(|version| version.starts_with("HEAD"))
+        })
+        .chain(tapped.iter())
+        .filter_map(|formula| Some((formula.name.clone(), formula.github_repo()?)))
+        .collect();
+
+    // Formulae that can be built from HEAD, for packages following that channel
+    let head_repos: HashMap<String, String> = formulas
+        .iter()
+        .chain(tapped.iter())
+        .filter(|formula| installed.contains_key(&formula.name) && formula.urls.head.is_some())
+        .filter_map(|formula| Some((formula.name.clone(), formula.github_repo()?)))
+        .collect();
+
+    // Kept as long as the API listing they come from
+    let ttl = crate::config::cache_ttls().registry_ttl();
+    set_cached("homebrew_head_repos".to_string(), &head_repos, ttl);
+    set_cached("homebrew_bottle_urls".to_string(), &bottle_urls, ttl);
+    set_cached("homebrew_github_repos".to_string(), &github_repos, ttl);
 
     println!(
         "[FAST] 🚀 Total time: {:?} (vs 5-7 minutes with old method!)",
@@ -104,6 +202,42 @@ pub async fn list_homebrew_packages_fast() -> Result<Vec<Package>> {
     Ok(packages)
 }
 
+/// Every formula in the Homebrew API (~30MB), kept on disk with its ETag. Within the
+/// registry TTL (an hour by default) it's used as is; after that a conditional request
+/// only downloads it again if Homebrew published a change. What's installed is read fresh
+/// each time.
+async fn fetch_formulas() -> Result<Vec<FormulaInfo>> {
+    if get_cached::<bool>(FORMULA_API_CACHE_KEY).is_some() {
+        if let Some(formulas) = revalidated::<Vec<FormulaInfo>>(FORMULA_LISTING_KEY) {
+            println!("[FAST] ✓ Loaded {} formulas from cache", formulas.len());
+            return Ok(formulas);
+        }
+    }
+
+    let client = create_http_client();
+
+    // Fetch ALL formulas in ONE request
+    let url = "https://formulae.brew.sh/api/formula.json";
+    let start = std::time::Instant::now();
+
+    let formulas: Vec<FormulaInfo> = get_json_revalidated(client.get(url), FORMULA_LISTING_KEY)
+        .await
+        .map_err(|e| anyhow!("Failed to fetch Homebrew API: {}", e))?
+        .ok_or_else(|| anyhow!("Homebrew API returned status: 404 Not Found"))?;
+
+    println!(
+        "[FAST] ✓ Fetched {} formulas in {:?}",
+        formulas.len(),
+        start.elapsed()
+    );
+    set_cached(
+        FORMULA_API_CACHE_KEY.to_string(),
+        &true,
+        crate::config::cache_ttls().registry_ttl(),
+    );
+    Ok(formulas)
+}
+
 /// Fast: Get locally installed package names and versions
 async fn get_installed_packages() -> Result<std::collections::HashMap<String, String>> {
     use crate::utils::run_command_with_timeout;
@@ -133,17 +267,118 @@ async fn get_installed_packages() -> Result<std::collections::HashMap<String, St
     Ok(installed)
 }
 
+/// Formulae installed from third-party taps, described by `brew info`
+async fn tap_formulae() -> Vec<FormulaInfo> {
+    use crate::utils::run_command_with_timeout;
+    use std::time::Duration;
+
+    // Full names are "owner/tap/name" for tapped formulae and plain for core ones
+    let Ok(output) = run_command_with_timeout(
+        "brew",
+        &["list", "--formula", "--full-name"],
+        Duration::from_secs(15),
+    )
+    .await
+    else {
+        return Vec::new();
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let names: Vec<&str> = stdout
+        .lines()
+        .map(str::trim)
+        .filter(|name| name.contains('/') && !name.starts_with("homebrew/core/"))
+        .collect();
+    if names.is_empty() {
+        return Vec::new();
+    }
+
+    let mut args = vec!["info", "--json=v2"];
+    args.extend(&names);
+    let info = match run_command_with_timeout("brew", &args, Duration::from_secs(60)).await {
+        Ok(output) if output.status.success() => {
+            serde_json::from_slice::<BrewInfo>(&output.stdout).map_err(|e| anyhow!(e))
+        }
+        Ok(output) => Err(anyhow!("{}", String::from_utf8_lossy(&output.stderr))),
+        Err(e) => Err(e),
+    };
+    match info {
+        Ok(info) => {
+            println!("[FAST] ✓ Described {} tap formulae", info.formulae.len());
+            info.formulae
+        }
+        Err(e) => {
+            eprintln!("[ERROR] brew info for tap formulae failed: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Installed casks other than fonts, with whether they update themselves
+async fn app_casks() -> Vec<Package> {
+    use crate::utils::run_command_with_timeout;
+    use std::time::Duration;
+
+    let output = match run_command_with_timeout(
+        "brew",
+        &["info", "--cask", "--json=v2", "--installed"],
+        Duration::from_secs(60),
+    )
+    .await
+    {
+        Ok(output) if output.status.success() => output,
+        // Casks are macOS only; Linuxbrew has none to list
+        _ => return Vec::new(),
+    };
+    let casks = match serde_json::from_slice::<BrewInfo>(&output.stdout) {
+        Ok(info) => info.casks,
+        Err(e) => {
+            eprintln!("[ERROR] Failed to parse brew info for casks: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let packages: Vec<Package> = casks
+        .into_iter()
+        .filter(|cask| !cask.token.starts_with("font-"))
+        .filter_map(|cask| {
+            let installed = cask.installed?;
+            Some(Package {
+                // "latest" casks always download the current build; there's no version to compare
+                latest_version: (cask.version != "latest").then_some(cask.version),
+                description: cask.desc,
+                auto_updates: cask.auto_updates.unwrap_or(false),
+                is_cask: true,
+                ..Package::new(cask.token, PackageManager::Homebrew, installed)
+            })
+        })
+        .collect();
+    println!("[FAST] ✓ Found {} installed app casks", packages.len());
+    packages
+}
+
 /// Fast: Check which packages are outdated using batch API
 pub async fn check_outdated_packages_fast(packages: &mut [Package]) -> Result<()> {
     println!("[FAST] Checking for outdated packages...");
     let start = std::time::Instant::now();
 
     // Simple comparison: installed vs latest from API
+    let flags = PackageFlags::load();
+    let include_auto_updates = crate::config::Settings::load()
+        .homebrew
+        .include_auto_updates;
     let mut outdated_count = 0;
     for pkg in packages.iter_mut() {
-        if let (Some(latest), installed) = (&pkg.latest_version, &pkg.installed_version) {
-            // Simple version comparison (you can enhance this)
-            if latest != installed {
+        // HEAD builds follow a branch; the stable version says nothing about them
+        if pkg.installed_version.starts_with("HEAD") || flags.channel(pkg).is_some() {
+            continue;
+        }
+        // Apps that update themselves are usually ahead of what brew recorded
+        if pkg.auto_updates && !include_auto_updates {
+            continue;
+        }
+        if let Some(latest) = &pkg.latest_version {
+            // The API's stable version has no revision, so "1.2.3_1" is current for "1.2.3"
+            if is_newer(latest, &pkg.installed_version) {
                 pkg.is_outdated = true;
                 outdated_count += 1;
             }
@@ -156,17 +391,281 @@ pub async fn check_outdated_packages_fast(packages: &mut [Package]) -> Result<()
         outdated_count, elapsed
     );
 
+    check_github_releases(packages, &flags).await;
+    check_head_commits(packages, &flags).await;
     Ok(())
 }
 
-/// Get descriptions with adaptive concurrency (fallback for missing descriptions)
+/// Latest release on GitHub for HEAD builds ("HEAD-0a1b2c3", outdated when the release
+/// has commits the build lacks) and tap formulae (whose tap may lag behind upstream)
+async fn check_github_releases(packages: &mut [Package], flags: &PackageFlags) {
+    use crate::managers::github_releases::{latest_tag, tag_is_ahead_of, tag_version};
+    use futures::{stream, StreamExt};
+
+    let Some(repos) = get_cached::<HashMap<String, String>>("homebrew_github_repos") else {
+        return;
+    };
+    let checks: Vec<(usize, String, String)> = packages
+        .iter()
+        .enumerate()
+        .filter(|(_, pkg)| flags.channel(pkg).is_none())
+        .filter_map(|(index, pkg)| {
+            let repo = repos.get(&pkg.name)?;
+            Some((index, repo.clone(), pkg.installed_version.clone()))
+        })
+        .collect();
+    if checks.is_empty() {
+        return;
+    }
+
+    let results: Vec<Option<(usize, String, bool)>> = stream::iter(checks)
+        .map(|(index, repo, installed)| async move {
+            let tag = latest_tag(&repo).await;
+            let checked = match (tag, installed.strip_prefix("HEAD-")) {
+                (Ok(tag), Some(commit)) => tag_is_ahead_of(&repo, commit, &tag)
+                    .await
+                    .map(|ahead| (tag, ahead)),
+                (Ok(tag), None) => {
+                    let latest = tag_version(&tag).to_string();
+                    let newer = is_newer(&latest, &installed);
+                    Ok((latest, newer))
+                }
+                (Err(e), _) => Err(e),
+            };
+            match checked {
+                Ok((latest, outdated)) => Some((index, latest, outdated)),
+                Err(e) => {
+                    eprintln!("[FAST] GitHub check for {} failed: {}", repo, e);
+                    None
+                }
+            }
+        })
+        .buffer_unordered(4)
+        .collect()
+        .await;
+
+    let mut checked = 0;
+    for (index, latest, outdated) in results.into_iter().flatten() {
+        packages[index].latest_version = Some(latest);
+        packages[index].is_outdated = outdated;
+        checked += 1;
+    }
+    println!("[FAST] ✓ Checked {} HEAD/tap formulae on GitHub", checked);
+}
+
+/// Formulae following the HEAD channel are outdated until they're a HEAD build of the
+/// default branch's newest commit ("HEAD-0a1b2c3")
+async fn check_head_commits(packages: &mut [Package], flags: &PackageFlags) {
+    use crate::managers::github_releases::head_commit;
+    use futures::{stream, StreamExt};
+
+    let Some(repos) = get_cached::<HashMap<String, String>>("homebrew_head_repos") else {
+        return;
+    };
+    let checks: Vec<(usize, String)> = packages
+        .iter()
+        .enumerate()
+        .filter(|(_, pkg)| flags.channel(pkg) == Some("HEAD"))
+        .filter_map(|(index, pkg)| Some((index, repos.get(&pkg.name)?.clone())))
+        .collect();
+
+    let results: Vec<Option<(usize, String)>> = stream::iter(checks)
+        .map(|(index, repo)| async move {
+            match head_commit(&repo).await {
+                Ok(sha) => Some((index, sha)),
+                Err(e) => {
+                    eprintln!("[FAST] GitHub check for {} failed: {}", repo, e);
+                    None
+                }
+            }
+        })
+        .buffer_unordered(4)
+        .collect()
+        .await;
+
+    for (index, sha) in results.into_iter().flatten() {
+        let pkg = &mut packages[index];
+        pkg.is_outdated = !pkg
+            .installed_version
+            .strip_prefix("HEAD-")
+            .is_some_and(|commit| sha.starts_with(commit));
+        pkg.latest_version = Some(format!("HEAD-{}", &sha[..sha.len().min(7)]));
+    }
+}
+
+/// Homebrew's name for this platform's bottles, e.g. `arm64_sequoia` or `x86_64_linux`
+async fn bottle_tag() -> String {
+    use crate::utils::run_command_with_timeout;
+    use std::time::Duration;
+
+    if !cfg!(target_os = "macos") {
+        let arm = std::env::consts::ARCH == "aarch64";
+        return if arm { "arm64_linux" } else { "x86_64_linux" }.to_string();
+    }
+    // Native bottles even if this process is itself running under Rosetta
+    let arm = crate::utils::arch::is_apple_silicon().await;
+
+    let major =
+        match run_command_with_timeout("sw_vers", &["-productVersion"], Duration::from_secs(5))
+            .await
+        {
+            Ok(output) => String::from_utf8_lossy(&output.stdout)
+                .split('.')
+                .next()
+                .and_then(|v| v.trim().parse::<u32>().ok())
+                .unwrap_or(0),
+            Err(_) => 0,
+        };
+    let codename = match major {
+        26 => "tahoe",
+        15 => "sequoia",
+        14 => "sonoma",
+        13 => "ventura",
+        12 => "monterey",
+        _ => "sequoia",
+    };
+    if arm {
+        format!("arm64_{}", codename)
+    } else {
+        codename.to_string()
+    }
+}
+
+/// Download size of the new bottle for each outdated formula (Content-Length of the blob
+/// on ghcr.io), for the download estimate before updating
+pub async fn add_bottle_sizes(packages: &mut [Package]) {
+    use futures::{stream, StreamExt};
+
+    let Some(urls) = get_cached::<HashMap<String, String>>("homebrew_bottle_urls") else {
+        return;
+    };
+    let outdated: Vec<(String, String)> = packages
+        .iter()
+        .filter(|p| p.is_outdated)
+        .filter_map(|p| Some((p.name.clone(), urls.get(&p.name)?.clone())))
+        .collect();
+    if outdated.is_empty() {
+        return;
+    }
+
+    let client = create_http_client();
+    let start = std::time::Instant::now();
+    let sizes: HashMap<String, u64> = stream::iter(outdated)
+        .map(|(name, url)| {
+            let client = client.clone();
+            async move {
+                // ghcr.io serves public bottles to this anonymous token
+                let response = client
+                    .head(&url)
+                    .bearer_auth("QQ==")
+                    .send_timed()
+                    .await
+                    .ok()
+                    .filter(|r| r.status().is_success())?;
+                let size = response
+                    .headers()
+                    .get(reqwest::header::CONTENT_LENGTH)?
+                    .to_str()
+                    .ok()?
+                    .parse()
+                    .ok()?;
+                Some((name, size))
+            }
+        })
+        .buffer_unordered(crate::config::registry_lookups())
+        .filter_map(|sized| async move { sized })
+        .collect()
+        .await;
+
+    for pkg in packages.iter_mut() {
+        if let Some(&size) = sizes.get(&pkg.name) {
+            pkg.download_size = Some(size);
+        }
+    }
+
+    println!(
+        "[FAST] ✓ Sized {} bottle downloads in {:?}",
+        sizes.len(),
+        start.elapsed()
+    );
+}
+
+/// Disk usage of each formula's Cellar directory (all installed versions)
+pub async fn add_installed_sizes(packages: &mut [Package]) -> Result<()> {
+    use crate::utils::disk::dir_size;
+    use crate::utils::run_command_with_timeout;
+    use std::time::Duration;
+
+    let output = run_command_with_timeout("brew", &["--cellar"], Duration::from_secs(10)).await?;
+    if !output.status.success() {
+        return Err(anyhow!("brew --cellar failed"));
+    }
+    let cellar =
+        std::path::PathBuf::from(String::from_utf8_lossy(&output.stdout).trim().to_string());
+
+    let start = std::time::Instant::now();
+    let names: Vec<String> = packages.iter().map(|p| p.name.clone()).collect();
+
+    // Walking hundreds of Cellar trees is CPU/IO bound, keep it off the async workers
+    let sizes: std::collections::HashMap<String, u64> = tokio::task::spawn_blocking(move || {
+        names
+            .par_iter()
+            .map(|name| (name.clone(), dir_size(&cellar.join(name))))
+            .collect()
+    })
+    .await?;
+
+    for pkg in packages.iter_mut() {
+        if let Some(&size) = sizes.get(&pkg.name).filter(|&&size| size > 0) {
+            pkg.size = Some(size);
+        }
+    }
+
+    println!("[FAST] ✓ Measured Cellar sizes in {:?}", start.elapsed());
+    Ok(())
+}
+
+/// One formula's description from `brew info` or the formulae.brew.sh API
+pub async fn fetch_formula_description(name: &str, source: DescriptionSource) -> Option<String> {
+    use crate::utils::run_command_with_timeout;
+    use std::time::Duration;
+
+    let json: serde_json::Value = match source {
+        DescriptionSource::Cli => {
+            let output = run_command_with_timeout(
+                "brew",
+                &["info", "--json=v2", name],
+                Duration::from_secs(10),
+            )
+            .await
+            .ok()
+            .filter(|o| o.status.success())?;
+            let json: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+            json["formulae"].get(0)?.clone()
+        }
+        DescriptionSource::Http => create_http_client()
+            .get(format!(
+                "https://formulae.brew.sh/api/formula/{}.json",
+                name
+            ))
+            .send_timed()
+            .await
+            .ok()?
+            .json()
+            .await
+            .ok()?,
+    };
+    json["desc"].as_str().map(|s| s.to_string())
+}
+
+/// Descriptions the formula API didn't include, fetched `batch_size` at a time
 pub async fn add_missing_descriptions_fast(
     packages: Vec<Package>,
     packages_clone: std::sync::Arc<tokio::sync::RwLock<Vec<Package>>>,
+    source: DescriptionSource,
+    batch_size: usize,
 ) {
-    use crate::utils::run_command_with_timeout;
     use futures::{stream, StreamExt};
-    use std::time::Duration;
 
     // Only fetch for packages missing descriptions
     let missing: Vec<String> = packages
@@ -180,49 +679,23 @@ pub async fn add_missing_descriptions_fast(
         return;
     }
 
-    println!(
-        "[FAST] Fetching {} missing descriptions (adaptive concurrency)...",
-        missing.len()
-    );
-
-    const CONCURRENT_REQUESTS: usize = 8; // Higher since API already gave us most
+    println!("[FAST] Fetching {} missing descriptions...", missing.len());
 
     let total = missing.len();
     let mut completed = 0;
 
     let mut stream = stream::iter(missing)
         .map(|name| async move {
-            let result = run_command_with_timeout(
-                "brew",
-                &["info", "--json=v2", &name],
-                Duration::from_secs(10),
-            )
-            .await;
-
-            (name.clone(), result)
+            let desc = fetch_formula_description(&name, source).await;
+            (name, desc)
         })
-        .buffer_unordered(CONCURRENT_REQUESTS);
-
-    while let Some((name, result)) = stream.next().await {
-        if let Ok(output) = result {
-            if output.status.success() {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                if let Ok(json) = serde_json::from_str::<serde_json::Value>(&stdout) {
-                    let desc = json
-                        .get("formulae")
-                        .and_then(|v| v.as_array())
-                        .and_then(|arr| arr.first())
-                        .and_then(|f| f.get("desc"))
-                        .and_then(|d| d.as_str())
-                        .map(|s| s.to_string());
-
-                    if let Some(description) = desc {
-                        let mut pkgs = packages_clone.write().await;
-                        if let Some(pkg) = pkgs.iter_mut().find(|p| p.name == name) {
-                            pkg.description = Some(description);
-                        }
-                    }
-                }
+        .buffer_unordered(batch_size);
+
+    while let Some((name, desc)) = stream.next().await {
+        if let Some(description) = desc {
+            let mut pkgs = packages_clone.write().await;
+            if let Some(pkg) = pkgs.iter_mut().find(|p| p.name == name) {
+                pkg.description = Some(description);
             }
         }
 
@@ -240,6 +713,10 @@ pub async fn update_package(package_name: String) -> Result<()> {
 
     println!("[UPDATE] Updating: {}", package_name);
 
+    if PackageFlags::load().channel_of(&PackageManager::Homebrew, &package_name) == Some("HEAD") {
+        return update_to_head(package_name).await;
+    }
+
     let output = run_command_with_timeout(
         "brew",
         &["upgrade", &package_name],
@@ -256,26 +733,54 @@ pub async fn update_package(package_name: String) -> Result<()> {
     }
 }
 
-/// Update all outdated packages
-pub async fn update_all_packages() -> Result<()> {
+/// Rebuild from the newest commit: `--fetch-HEAD` for a HEAD build, otherwise unlink
+/// the stable version (brew refuses to install HEAD over it) and install HEAD
+async fn update_to_head(package_name: String) -> Result<()> {
     use crate::utils::run_command_with_timeout;
     use std::time::Duration;
 
-    println!("[UPDATE] Updating all outdated packages");
-
-    let output = run_command_with_timeout(
+    let versions = run_command_with_timeout(
         "brew",
-        &["upgrade"],
-        Duration::from_secs(600), // 10 minutes
+        &["list", "--versions", &package_name],
+        Duration::from_secs(30),
     )
     .await?;
+    let is_head = String::from_utf8_lossy(&versions.stdout).contains("HEAD-");
+
+    if !is_head {
+        println!("[UPDATE] Switching {} to HEAD", package_name);
+        let output =
+            run_command_with_timeout("brew", &["unlink", &package_name], Duration::from_secs(60))
+                .await?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("Failed to unlink {}: {}", package_name, stderr));
+        }
+    }
+    let args: &[&str] = if is_head {
+        &["upgrade", "--fetch-HEAD", &package_name]
+    } else {
+        &["install", "--HEAD", &package_name]
+    };
+    // Builds from source, so allow longer than a bottle upgrade
+    let output = run_command_with_timeout("brew", args, Duration::from_secs(1200)).await?;
 
     if output.status.success() {
-        println!("[UPDATE] Successfully updated all packages");
+        println!("[UPDATE] Successfully built {} from HEAD", package_name);
         Ok(())
     } else {
+        if !is_head {
+            // Put the stable version back rather than leave the formula unlinked
+            let _ =
+                run_command_with_timeout("brew", &["link", &package_name], Duration::from_secs(60))
+                    .await;
+        }
         let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(anyhow!("Failed to update packages: {}", stderr))
+        Err(anyhow!(
+            "Failed to build {} from HEAD: {}",
+            package_name,
+            stderr
+        ))
     }
 }
 
@@ -305,6 +810,43 @@ pub async fn install_package(package_name: String) -> Result<()> {
     }
 }
 
+/// The formula whose current version meets `constraint`: `name` itself or one of its
+/// versioned `name@X` formulas, the newest that fits. Homebrew only ships the current
+/// release of each, so a version none of them has is an error rather than a silent substitute.
+pub async fn formula_for_version(name: &str, constraint: &str) -> Result<String> {
+    let formulas = fetch_formulas().await?;
+    let candidates: Vec<(&str, &str)> = formulas
+        .iter()
+        .filter(|f| {
+            f.name == name
+                || f.name
+                    .strip_prefix(name)
+                    .is_some_and(|rest| rest.starts_with('@'))
+        })
+        .filter_map(|f| Some((f.name.as_str(), f.versions.stable.as_deref()?)))
+        .collect();
+    if candidates.is_empty() {
+        return Err(anyhow!("Homebrew has no {} formula", name));
+    }
+    candidates
+        .iter()
+        .filter(|(_, version)| satisfies(version, constraint) == Some(true))
+        .max_by(|a, b| compare_versions(a.1, b.1))
+        .map(|(formula, _)| formula.to_string())
+        .ok_or_else(|| {
+            let available: Vec<String> = candidates
+                .iter()
+                .map(|(formula, version)| format!("{} {}", formula, version))
+                .collect();
+            anyhow!(
+                "Homebrew can't install {} {}, it only has {}",
+                name,
+                constraint,
+                available.join(", ")
+            )
+        })
+}
+
 /// Uninstall a package
 pub async fn uninstall_package(package_name: String) -> Result<()> {
     use crate::utils::run_command_with_timeout;
@@ -331,3 +873,76 @@ pub async fn uninstall_package(package_name: String) -> Result<()> {
         Err(anyhow!("Failed to uninstall {}: {}", package_name, stderr))
     }
 }
+
+/// Whether the formula has a bottle for this platform (from the last API fetch)
+pub fn has_native_bottle(package_name: &str) -> bool {
+    get_cached::<HashMap<String, String>>("homebrew_bottle_urls")
+        .is_some_and(|urls| urls.contains_key(package_name))
+}
+
+/// Replace an Intel build with the arm64 bottle, running brew itself natively
+pub async fn reinstall_native(package_name: String) -> Result<()> {
+    use crate::utils::run_command_with_timeout;
+    use std::time::Duration;
+
+    println!(
+        "[INSTALL] Running: arch -arm64 brew reinstall {}",
+        package_name
+    );
+
+    let output = run_command_with_timeout(
+        "arch",
+        &["-arm64", "brew", "reinstall", &package_name],
+        Duration::from_secs(300),
+    )
+    .await?;
+
+    if output.status.success() {
+        println!("[INSTALL] Reinstalled {} as arm64", package_name);
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to reinstall {}: {}", package_name, stderr))
+    }
+}
+
+/// Recreate a formula's symlinks in the prefix (`brew link --overwrite`)
+pub async fn relink_package(package_name: String) -> Result<()> {
+    use crate::utils::run_command_with_timeout;
+    use std::time::Duration;
+
+    println!("[INSTALL] Running: brew link --overwrite {}", package_name);
+
+    let output = run_command_with_timeout(
+        "brew",
+        &["link", "--overwrite", &package_name],
+        Duration::from_secs(60),
+    )
+    .await?;
+
+    if output.status.success() {
+        println!("[INSTALL] Relinked {}", package_name);
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to relink {}: {}", package_name, stderr))
+    }
+}
+
+/// Update Homebrew itself and its formula metadata (`brew update`)
+pub async fn self_update() -> Result<()> {
+    use crate::utils::run_command_with_timeout;
+    use std::time::Duration;
+
+    println!("[UPDATE] Running: brew update");
+
+    let output = run_command_with_timeout("brew", &["update"], Duration::from_secs(300)).await?;
+
+    if output.status.success() {
+        println!("[UPDATE] Homebrew is up to date");
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("brew update failed: {}", stderr))
+    }
+}
@@ -0,0 +1,328 @@
+use crate::models::{Package, PackageManager};
+use crate::utils::http_client::create_http_client;
+use crate::utils::metrics::SendTimed;
+use crate::utils::run_command_with_timeout;
+use crate::utils::version::{compare_versions, highest_tag};
+use anyhow::{anyhow, Result};
+use std::cmp::Ordering;
+use std::time::Duration;
+
+async fn run_checked(cmd: &str, args: &[&str], timeout: Duration) -> Result<String> {
+    let output = run_command_with_timeout(cmd, args, timeout).await?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!(
+            "{} {} failed: {}",
+            cmd,
+            args.join(" "),
+            stderr.trim()
+        ))
+    }
+}
+
+// --- tfenv ---
+
+/// Terraform versions installed through tfenv; "* 1.5.7 (set by ...)" marks the active one
+pub async fn list_tfenv_versions() -> Result<Vec<Package>> {
+    println!("[TFENV] Listing installed Terraform versions");
+
+    let stdout = run_checked("tfenv", &["list"], Duration::from_secs(30)).await?;
+    let packages: Vec<Package> = stdout
+        .lines()
+        .filter_map(|line| {
+            line.trim()
+                .trim_start_matches('*')
+                .split_whitespace()
+                .next()
+        })
+        .filter(|version| version.starts_with(|c: char| c.is_ascii_digit()))
+        .map(|version| Package::new("terraform", PackageManager::Tfenv, version))
+        .collect();
+
+    println!("[TFENV] Found {} Terraform versions", packages.len());
+    Ok(packages)
+}
+
+/// HashiCorp's checkpoint service reports the current Terraform release
+pub async fn check_outdated_tfenv(packages: &mut [Package]) -> Result<()> {
+    println!("[TFENV] Checking the latest Terraform release");
+
+    let json: serde_json::Value = create_http_client()
+        .get("https://checkpoint-api.hashicorp.com/v1/check/terraform")
+        .send_timed()
+        .await?
+        .json()
+        .await?;
+    let Some(latest) = json["current_version"].as_str() else {
+        return Ok(());
+    };
+
+    let has_latest = packages
+        .iter()
+        .any(|p| compare_versions(&p.installed_version, latest) != Ordering::Less);
+    for pkg in packages.iter_mut() {
+        pkg.latest_version = Some(latest.to_string());
+        pkg.is_outdated = !has_latest;
+    }
+    Ok(())
+}
+
+pub async fn update_tfenv() -> Result<()> {
+    println!("[TFENV] Installing and switching to the latest Terraform");
+    run_checked("tfenv", &["install", "latest"], Duration::from_secs(600)).await?;
+    run_checked("tfenv", &["use", "latest"], Duration::from_secs(60)).await?;
+    Ok(())
+}
+
+pub async fn install_tfenv_version(version: String) -> Result<()> {
+    println!("[TFENV] Installing Terraform {}", version);
+    run_checked("tfenv", &["install", &version], Duration::from_secs(600)).await?;
+    run_checked("tfenv", &["use", &version], Duration::from_secs(60)).await?;
+    Ok(())
+}
+
+/// tfenv removes a specific version: the given one, or the only one installed
+pub async fn uninstall_tfenv(version: Option<String>) -> Result<()> {
+    if let Some(version) = version {
+        println!("[TFENV] Uninstalling Terraform {}", version);
+        run_checked("tfenv", &["uninstall", &version], Duration::from_secs(60)).await?;
+        return Ok(());
+    }
+    let versions: Vec<String> = list_tfenv_versions()
+        .await?
+        .into_iter()
+        .map(|p| p.installed_version)
+        .collect();
+
+    match versions.as_slice() {
+        [version] => {
+            println!("[TFENV] Uninstalling Terraform {}", version);
+            run_checked("tfenv", &["uninstall", version], Duration::from_secs(60)).await?;
+            Ok(())
+        }
+        [] => Err(anyhow!("No Terraform versions are installed")),
+        versions => Err(anyhow!(
+            "Several Terraform versions are installed ({}); remove one with `tfenv uninstall <version>`",
+            versions.join(", ")
+        )),
+    }
+}
+
+// --- Helm plugins ---
+
+/// `helm plugin list`: "NAME  VERSION  DESCRIPTION" table
+pub async fn list_helm_plugins() -> Result<Vec<Package>> {
+    println!("[HELM] Listing plugins");
+
+    let stdout = run_checked("helm", &["plugin", "list"], Duration::from_secs(30)).await?;
+    let packages: Vec<Package> = stdout
+        .lines()
+        .skip(1) // header
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let name = fields.next()?;
+            let version = fields.next()?;
+            let mut pkg = Package::new(name, PackageManager::HelmPlugins, version);
+            let description = fields.collect::<Vec<_>>().join(" ");
+            if !description.is_empty() {
+                pkg.description = Some(description);
+            }
+            Some(pkg)
+        })
+        .collect();
+
+    println!("[HELM] Found {} plugins", packages.len());
+    Ok(packages)
+}
+
+/// Plugins are git checkouts under $HELM_PLUGINS; compare against their newest tag
+pub async fn check_outdated_helm_plugins(packages: &mut [Package]) -> Result<()> {
+    println!("[HELM] Checking plugin repositories for newer tags");
+
+    let plugins_dir = run_checked("helm", &["env", "HELM_PLUGINS"], Duration::from_secs(10))
+        .await?
+        .trim()
+        .to_string();
+    let entries = run_checked("ls", &["-1", &plugins_dir], Duration::from_secs(10)).await?;
+    let entries: Vec<&str> = entries.lines().collect();
+
+    for pkg in packages.iter_mut() {
+        // Checkouts are usually named after the repo: "diff" lives in "helm-diff"
+        let Some(entry) = entries.iter().find(|e| {
+            **e == pkg.name || **e == format!("helm-{}", pkg.name) || e.ends_with(&pkg.name)
+        }) else {
+            continue;
+        };
+        let dir = format!("{}/{}", plugins_dir, entry);
+        let Ok(refs) = run_checked(
+            "git",
+            &["-C", &dir, "ls-remote", "--tags", "--refs", "origin"],
+            Duration::from_secs(30),
+        )
+        .await
+        else {
+            continue;
+        };
+
+        let latest = highest_tag(refs.lines().filter_map(|l| l.split("refs/tags/").nth(1)));
+        if let Some(latest) = latest {
+            pkg.is_outdated =
+                compare_versions(&latest, &pkg.installed_version) == Ordering::Greater;
+            pkg.latest_version = Some(latest);
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn update_helm_plugin(name: String) -> Result<()> {
+    println!("[HELM] Updating plugin: {}", name);
+    run_checked(
+        "helm",
+        &["plugin", "update", &name],
+        Duration::from_secs(300),
+    )
+    .await?;
+    Ok(())
+}
+
+pub async fn uninstall_helm_plugin(name: String) -> Result<()> {
+    println!("[HELM] Uninstalling plugin: {}", name);
+    run_checked(
+        "helm",
+        &["plugin", "uninstall", &name],
+        Duration::from_secs(60),
+    )
+    .await?;
+    Ok(())
+}
+
+// --- krew (kubectl plugins) ---
+
+/// `kubectl krew list`: "PLUGIN  VERSION" table
+pub async fn list_krew_plugins() -> Result<Vec<Package>> {
+    println!("[KREW] Listing kubectl plugins");
+
+    let stdout = run_checked("kubectl-krew", &["list"], Duration::from_secs(30)).await?;
+    let packages: Vec<Package> = stdout
+        .lines()
+        .skip(1) // header
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some(Package::new(
+                fields.next()?,
+                PackageManager::Krew,
+                fields.next()?,
+            ))
+        })
+        .collect();
+
+    println!("[KREW] Found {} plugins", packages.len());
+    Ok(packages)
+}
+
+/// Compare against the version in the local krew index (`kubectl krew update` refreshes it)
+pub async fn check_outdated_krew(packages: &mut [Package]) -> Result<()> {
+    println!("[KREW] Checking the plugin index for newer versions");
+
+    for pkg in packages.iter_mut() {
+        let Ok(info) = run_checked(
+            "kubectl-krew",
+            &["info", &pkg.name],
+            Duration::from_secs(15),
+        )
+        .await
+        else {
+            continue;
+        };
+        // "VERSION: v0.9.5"
+        let latest = info
+            .lines()
+            .find_map(|l| l.strip_prefix("VERSION:"))
+            .map(|v| v.trim().to_string());
+        if let Some(latest) = latest {
+            pkg.is_outdated =
+                compare_versions(&latest, &pkg.installed_version) == Ordering::Greater;
+            pkg.latest_version = Some(latest);
+        }
+        if pkg.description.is_none() {
+            pkg.description = info
+                .lines()
+                .find_map(|l| l.strip_prefix("SHORT DESCRIPTION:"))
+                .map(|d| d.trim().to_string());
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn update_krew_plugin(name: String) -> Result<()> {
+    println!("[KREW] Upgrading plugin: {}", name);
+    run_checked(
+        "kubectl-krew",
+        &["upgrade", &name],
+        Duration::from_secs(300),
+    )
+    .await?;
+    Ok(())
+}
+
+pub async fn install_krew_plugin(name: String) -> Result<()> {
+    println!("[KREW] Installing plugin: {}", name);
+    run_checked(
+        "kubectl-krew",
+        &["install", &name],
+        Duration::from_secs(300),
+    )
+    .await?;
+    Ok(())
+}
+
+pub async fn uninstall_krew_plugin(name: String) -> Result<()> {
+    println!("[KREW] Uninstalling plugin: {}", name);
+    run_checked(
+        "kubectl-krew",
+        &["uninstall", &name],
+        Duration::from_secs(60),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Refresh the krew plugin index, krew's equivalent of `brew update`
+pub async fn self_update_krew() -> Result<()> {
+    println!("[KREW] Updating the plugin index");
+    run_checked("kubectl-krew", &["update"], Duration::from_secs(120)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::executor::{replay_fixture, with_executor};
+
+    #[tokio::test]
+    async fn krew_plugins_against_the_index() {
+        let packages = with_executor(replay_fixture("krew"), async {
+            let mut packages = list_krew_plugins().await.unwrap();
+            check_outdated_krew(&mut packages).await.unwrap();
+            packages
+        })
+        .await;
+
+        assert_eq!(packages.len(), 3);
+        let ctx = &packages[0];
+        assert_eq!(ctx.installed_version, "v0.9.4");
+        assert_eq!(ctx.latest_version.as_deref(), Some("v0.9.5"));
+        assert!(ctx.is_outdated);
+        assert_eq!(
+            ctx.description.as_deref(),
+            Some("Switch between contexts in your kubeconfig")
+        );
+        assert!(!packages[1].is_outdated);
+        // A plugin missing from the index is left alone
+        assert_eq!(packages[2].latest_version, None);
+    }
+}
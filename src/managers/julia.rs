@@ -0,0 +1,120 @@
+use crate::models::{Package, PackageManager};
+use crate::utils::run_command_with_timeout;
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+/// Direct dependencies of the default environment with the newest registered version.
+/// Prints "Name\tinstalled\tlatest" per package (latest is empty for stdlibs).
+const LIST_SCRIPT: &str = r#"
+using Pkg
+regs = Pkg.Registry.reachable_registries()
+for (uuid, info) in Pkg.dependencies()
+    info.is_direct_dep || continue
+    latest = nothing
+    for reg in regs
+        entry = get(reg, uuid, nothing)
+        entry === nothing && continue
+        versions = keys(Pkg.Registry.registry_info(entry).version_info)
+        isempty(versions) || (latest = maximum(versions))
+    end
+    println(info.name, "\t", something(info.version, ""), "\t", something(latest, ""))
+end
+"#;
+
+/// Package names are spliced into Julia code, so only allow identifiers
+static NAME_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*$").unwrap());
+
+/// List packages in the default Julia environment, with registry versions for outdated checks
+pub async fn list_julia_packages() -> Result<Vec<Package>> {
+    println!("[JULIA] Listing packages in the default environment");
+
+    // Julia's startup plus registry loading is slow
+    let output = run_command_with_timeout(
+        "julia",
+        &["--startup-file=no", "-e", LIST_SCRIPT],
+        Duration::from_secs(180),
+    )
+    .await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("julia Pkg listing failed: {}", stderr));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut packages = Vec::new();
+    for line in stdout.lines() {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [name, version, latest] = fields.as_slice() else {
+            continue;
+        };
+        if version.is_empty() {
+            continue; // Standard library, versioned with Julia itself
+        }
+        let mut pkg = Package::new(*name, PackageManager::Julia, *version);
+        if !latest.is_empty() {
+            pkg.latest_version = Some(latest.to_string());
+            pkg.is_outdated = crate::utils::version::compare_versions(latest, version)
+                == std::cmp::Ordering::Greater;
+        }
+        packages.push(pkg);
+    }
+
+    println!("[JULIA] Found {} packages", packages.len());
+    Ok(packages)
+}
+
+async fn run_pkg(action: &str, code: String, package_name: &str) -> Result<()> {
+    if !NAME_RE.is_match(package_name) {
+        return Err(anyhow!("Invalid Julia package name: {}", package_name));
+    }
+    println!("[JULIA] {}: {}", action, package_name);
+
+    let script = format!("using Pkg; {}", code);
+    let output = run_command_with_timeout(
+        "julia",
+        &["--startup-file=no", "-e", &script],
+        Duration::from_secs(900), // precompilation
+    )
+    .await?;
+
+    if output.status.success() {
+        println!("[JULIA] Successfully finished {}: {}", action, package_name);
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to {} {}: {}", action, package_name, stderr))
+    }
+}
+
+pub async fn update_julia_package(package_name: String) -> Result<()> {
+    let code = format!("Pkg.update(\"{}\")", package_name);
+    run_pkg("update", code, &package_name).await
+}
+
+pub async fn install_julia_package(package_name: String) -> Result<()> {
+    let code = format!("Pkg.add(\"{}\")", package_name);
+    run_pkg("install", code, &package_name).await
+}
+
+pub async fn uninstall_julia_package(package_name: String) -> Result<()> {
+    let code = format!("Pkg.rm(\"{}\")", package_name);
+    run_pkg("remove", code, &package_name).await
+}
+
+pub async fn install_julia_version(package_name: String, version: String) -> Result<()> {
+    if !version
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || ".-+".contains(c))
+    {
+        return Err(anyhow!("Invalid version: {}", version));
+    }
+    let code = format!(
+        "Pkg.add(name=\"{}\", version=\"{}\")",
+        package_name, version
+    );
+    run_pkg("install", code, &package_name).await
+}
@@ -0,0 +1,109 @@
+use crate::models::{Package, PackageManager};
+use crate::utils::run_command_with_timeout;
+use anyhow::{anyhow, Result};
+use std::time::Duration;
+
+/// List installed rocks
+pub async fn list_luarocks_packages() -> Result<Vec<Package>> {
+    println!("[LUAROCKS] Listing installed packages");
+
+    let output = run_command_with_timeout(
+        "luarocks",
+        &["list", "--porcelain"],
+        Duration::from_secs(30),
+    )
+    .await?;
+
+    if !output.status.success() {
+        return Err(anyhow!("luarocks list failed"));
+    }
+
+    // "name\tversion\tinstalled\t/path/to/tree"
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let packages: Vec<Package> = stdout
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            Some((fields.next()?, fields.next()?))
+        })
+        .map(|(name, version)| Package::new(name, PackageManager::LuaRocks, version))
+        .collect();
+
+    println!("[LUAROCKS] Found {} installed packages", packages.len());
+    Ok(packages)
+}
+
+/// Check for outdated rocks against the configured servers
+pub async fn check_outdated_luarocks(packages: &mut [Package]) -> Result<()> {
+    println!("[LUAROCKS] Checking for outdated packages");
+
+    let output = run_command_with_timeout(
+        "luarocks",
+        &["list", "--outdated", "--porcelain"],
+        Duration::from_secs(60),
+    )
+    .await?;
+
+    if !output.status.success() {
+        return Ok(()); // Not a fatal error
+    }
+
+    // "name\tinstalled\tavailable\tserver"
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for line in stdout.lines() {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if let [name, _installed, latest, ..] = fields.as_slice() {
+            if let Some(pkg) = packages.iter_mut().find(|p| p.name == *name) {
+                pkg.latest_version = Some(latest.to_string());
+                pkg.is_outdated = true;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn run_luarocks(action: &str, args: &[&str], package_name: &str) -> Result<()> {
+    println!("[LUAROCKS] {}: {}", action, package_name);
+
+    let output = run_command_with_timeout("luarocks", args, Duration::from_secs(300)).await?;
+
+    if output.status.success() {
+        println!(
+            "[LUAROCKS] Successfully finished {}: {}",
+            action, package_name
+        );
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to {} {}: {}", action, package_name, stderr))
+    }
+}
+
+/// `luarocks install` without a version picks the latest
+pub async fn update_luarocks_package(package_name: String) -> Result<()> {
+    run_luarocks("update", &["install", &package_name], &package_name).await
+}
+
+pub async fn install_luarocks_package(package_name: String) -> Result<()> {
+    run_luarocks("install", &["install", &package_name], &package_name).await
+}
+
+/// Just `version` when given; otherwise luarocks refuses if several are installed
+pub async fn uninstall_luarocks_package(
+    package_name: String,
+    version: Option<String>,
+) -> Result<()> {
+    let mut args = vec!["remove", package_name.as_str()];
+    args.extend(version.as_deref());
+    run_luarocks("remove", &args, &package_name).await
+}
+
+pub async fn install_luarocks_version(package_name: String, version: String) -> Result<()> {
+    run_luarocks(
+        "install",
+        &["install", &package_name, &version],
+        &package_name,
+    )
+    .await
+}
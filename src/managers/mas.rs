@@ -0,0 +1,116 @@
+use crate::models::{Package, PackageManager};
+use crate::utils::run_command_with_timeout;
+use anyhow::{anyhow, Result};
+use std::time::Duration;
+
+/// "497799835  Xcode  (15.4)": App Store id, app name (may contain spaces and
+/// parentheses of its own), version in the last parentheses
+fn parse_app_line(line: &str) -> Option<(&str, &str, &str)> {
+    let (id, rest) = line.trim().split_once(char::is_whitespace)?;
+    let (name, version) = rest.trim_end().strip_suffix(')')?.rsplit_once('(')?;
+    Some((id, name.trim(), version.trim()))
+}
+
+async fn mas(args: &[&str], timeout: Duration) -> Result<String> {
+    let output = run_command_with_timeout("mas", args, timeout).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("mas {} failed: {}", args[0], stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Apps installed from the Mac App Store, by name; their store ids are looked up again
+/// when acting on them
+pub async fn list_mas_apps() -> Result<Vec<Package>> {
+    println!("[MAS] Listing App Store apps");
+
+    let stdout = mas(&["list"], Duration::from_secs(60)).await?;
+    let packages: Vec<Package> = stdout
+        .lines()
+        .filter_map(parse_app_line)
+        .map(|(id, name, version)| {
+            let mut pkg = Package::new(name, PackageManager::Mas, version);
+            pkg.description = Some(format!("App Store id {}", id));
+            pkg
+        })
+        .collect();
+    println!("[MAS] Found {} apps", packages.len());
+    Ok(packages)
+}
+
+/// `mas outdated` lines end in "(15.3 -> 15.4)"
+pub async fn check_outdated_mas(packages: &mut [Package]) -> Result<()> {
+    println!("[MAS] Checking for outdated apps");
+
+    let stdout = mas(&["outdated"], Duration::from_secs(120)).await?;
+    for (_, name, versions) in stdout.lines().filter_map(parse_app_line) {
+        let Some((_, latest)) = versions.split_once("->") else {
+            continue;
+        };
+        if let Some(pkg) = packages.iter_mut().find(|p| p.name == name) {
+            pkg.latest_version = Some(latest.trim().to_string());
+            pkg.is_outdated = true;
+        }
+    }
+    Ok(())
+}
+
+/// The store id of an installed app, or of the first exact search match to install
+async fn app_id(name: &str, installed: bool) -> Result<String> {
+    let stdout = if installed {
+        mas(&["list"], Duration::from_secs(60)).await?
+    } else {
+        mas(&["search", name], Duration::from_secs(60)).await?
+    };
+    stdout
+        .lines()
+        .filter_map(parse_app_line)
+        .find(|(_, app, _)| app.eq_ignore_ascii_case(name))
+        .map(|(id, _, _)| id.to_string())
+        .ok_or_else(|| anyhow!("No App Store app named {}", name))
+}
+
+async fn run_mas(action: &str, name: &str, installed: bool) -> Result<()> {
+    println!("[MAS] {}: {}", action, name);
+
+    let id = app_id(name, installed).await?;
+    mas(&[action, &id], Duration::from_secs(1200)).await?;
+    println!("[MAS] Successfully finished {}: {}", action, name);
+    Ok(())
+}
+
+pub async fn update_mas_app(name: String) -> Result<()> {
+    run_mas("upgrade", &name, true).await
+}
+
+/// Needs root since mas 1.8; without it the error says to rerun with sudo
+pub async fn uninstall_mas_app(name: String) -> Result<()> {
+    run_mas("uninstall", &name, true).await
+}
+
+pub async fn install_mas_app(name: String) -> Result<()> {
+    run_mas("install", &name, false).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn app_lines_with_padding_and_parentheses() {
+        assert_eq!(
+            parse_app_line("  497799835  Xcode                  (15.4)"),
+            Some(("497799835", "Xcode", "15.4"))
+        );
+        assert_eq!(
+            parse_app_line("1295203466 Microsoft Remote Desktop (Beta) (10.9.8)"),
+            Some(("1295203466", "Microsoft Remote Desktop (Beta)", "10.9.8"))
+        );
+        assert_eq!(
+            parse_app_line("497799835 Xcode (15.3 -> 15.4)"),
+            Some(("497799835", "Xcode", "15.3 -> 15.4"))
+        );
+        assert_eq!(parse_app_line("No installed apps found"), None);
+    }
+}
@@ -0,0 +1,119 @@
+use crate::models::{PackageManager, Project};
+use crate::scanner::java::xml_tag;
+use crate::utils::cache::{get_cached, set_cached};
+use crate::utils::http_client::create_http_client;
+use crate::utils::metrics::SendTimed;
+use crate::utils::version::compare_versions;
+use anyhow::{anyhow, Result};
+use futures::{stream, StreamExt};
+use std::collections::{HashMap, HashSet};
+
+/// Milestones, release candidates and the like, which `<release>` sometimes points at
+fn is_prerelease(version: &str) -> bool {
+    let version = version.to_lowercase();
+    [
+        "alpha", "beta", "-rc", ".rc", "-m", "-cr", "snapshot", "-ea", "-preview",
+    ]
+    .iter()
+    .any(|marker| version.contains(marker))
+}
+
+/// Highest stable entry of a `maven-metadata.xml`'s `<versions>`, else its `<release>`.
+/// Not the last one listed: `<versions>` is in publish order, and a backport (2.17.3
+/// published after 3.0.1) would otherwise count as the latest.
+fn newest_stable(metadata: &str) -> Option<String> {
+    let versions = xml_tag(metadata, "versions").unwrap_or_default();
+    versions
+        .split("<version>")
+        .filter_map(|v| v.split("</version>").next())
+        .map(str::trim)
+        .filter(|v| !v.is_empty() && !is_prerelease(v))
+        .max_by(|a, b| compare_versions(a, b))
+        .or_else(|| xml_tag(metadata, "release"))
+        .map(String::from)
+}
+
+/// Newest stable version of `group:artifact` from its Maven Central metadata
+async fn latest_artifact_version(coordinates: &str) -> Result<String> {
+    let cache_key = format!("maven_latest_{}", coordinates);
+    if let Some(version) = get_cached::<String>(&cache_key) {
+        return Ok(version);
+    }
+
+    let (group, artifact) = coordinates
+        .split_once(':')
+        .ok_or_else(|| anyhow!("not group:artifact: {}", coordinates))?;
+    let url = format!(
+        "https://repo1.maven.org/maven2/{}/{}/maven-metadata.xml",
+        group.replace('.', "/"),
+        artifact
+    );
+    let response = create_http_client().get(&url).send_timed().await?;
+    if !response.status().is_success() {
+        return Err(anyhow!("{} returned {}", url, response.status()));
+    }
+    let metadata = response.text().await?;
+
+    let version =
+        newest_stable(&metadata).ok_or_else(|| anyhow!("no versions for {}", coordinates))?;
+
+    set_cached(
+        cache_key,
+        &version,
+        crate::config::cache_ttls().registry_ttl(),
+    );
+    Ok(version)
+}
+
+/// Look up the latest version of every Maven artifact the projects declare
+pub async fn check_project_artifacts(projects: &mut [Project]) {
+    let artifacts: HashSet<String> = projects
+        .iter()
+        .flat_map(|p| &p.dependencies)
+        .filter(|d| d.manager == PackageManager::Maven)
+        .map(|d| d.package_name.clone())
+        .collect();
+    if artifacts.is_empty() {
+        return;
+    }
+    println!(
+        "[MAVEN] Checking {} artifacts on Maven Central",
+        artifacts.len()
+    );
+
+    let latest: HashMap<String, String> = stream::iter(artifacts)
+        .map(|artifact| async move {
+            match latest_artifact_version(&artifact).await {
+                Ok(version) => Some((artifact, version)),
+                Err(e) => {
+                    eprintln!("[MAVEN] {}", e);
+                    None
+                }
+            }
+        })
+        .buffer_unordered(crate::config::registry_lookups())
+        .filter_map(|found| async move { found })
+        .collect()
+        .await;
+
+    for dep in projects
+        .iter_mut()
+        .flat_map(|p| p.dependencies.iter_mut())
+        .filter(|d| d.manager == PackageManager::Maven)
+    {
+        dep.latest_version = latest.get(&dep.package_name).cloned();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latest_is_the_highest_stable_version() {
+        let metadata = "<metadata><versioning><release>2.17.3</release><versions>\
+            <version>2.17.2</version><version>3.0.0-rc1</version><version>3.0.1</version>\
+            <version>2.17.3</version><version>3.1.0-M1</version></versions></versioning></metadata>";
+        assert_eq!(newest_stable(metadata).as_deref(), Some("3.0.1"));
+    }
+}
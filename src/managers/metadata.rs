@@ -0,0 +1,94 @@
+use crate::managers::homebrew_fast::FORMULA_API_CACHE_KEY;
+use crate::models::PackageManager;
+use crate::utils::cache::invalidate;
+use crate::utils::paths::data_dir;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+fn state_path() -> PathBuf {
+    data_dir().join("metadata_refresh.json")
+}
+
+/// When each manager's local metadata was last refreshed by us
+fn load_refresh_times() -> HashMap<PackageManager, DateTime<Utc>> {
+    std::fs::read_to_string(state_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_refresh_time(manager: &PackageManager, when: DateTime<Utc>) {
+    let mut times = load_refresh_times();
+    times.insert(manager.clone(), when);
+
+    let path = state_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(&times) {
+        if let Err(e) = std::fs::write(&path, json) {
+            eprintln!("[ERROR] Failed to write {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Only managers with local metadata that can go stale need a refresh step.
+/// npm, pip, and cargo query their registries live on every outdated check.
+pub fn has_local_metadata(manager: &PackageManager) -> bool {
+    matches!(manager, PackageManager::Homebrew)
+}
+
+/// Refresh a manager's metadata (`brew update`) so CLI actions agree with what the UI shows
+pub async fn refresh_metadata(manager: &PackageManager) -> Result<()> {
+    if !has_local_metadata(manager) {
+        return Ok(());
+    }
+
+    println!("[METADATA] Refreshing {} metadata", manager.name());
+    match manager {
+        PackageManager::Homebrew => {
+            crate::managers::homebrew_fast::self_update().await?;
+            // Next listing should come from the API again, not our 1-hour cache
+            invalidate(FORMULA_API_CACHE_KEY);
+        }
+        _ => return Ok(()),
+    }
+
+    save_refresh_time(manager, Utc::now());
+    Ok(())
+}
+
+/// Refresh only when the last refresh is older than `max_age_hours`
+pub async fn refresh_metadata_if_stale(manager: &PackageManager, max_age_hours: u32) -> Result<()> {
+    if !has_local_metadata(manager) {
+        return Ok(());
+    }
+
+    let stale = match load_refresh_times().get(manager) {
+        Some(last) => Utc::now().signed_duration_since(*last).num_hours() >= max_age_hours as i64,
+        None => true,
+    };
+
+    if stale {
+        refresh_metadata(manager).await
+    } else {
+        println!(
+            "[METADATA] {} metadata is fresh, skipping refresh",
+            manager.name()
+        );
+        Ok(())
+    }
+}
+
+/// How old the data behind a manager's listing is. Homebrew data comes from the
+/// cached API response; live-queried managers are as fresh as the scan itself.
+pub fn data_fetched_at(manager: &PackageManager, scanned_at: DateTime<Utc>) -> DateTime<Utc> {
+    match manager {
+        PackageManager::Homebrew => {
+            crate::utils::cache::cached_at(FORMULA_API_CACHE_KEY).unwrap_or(scanned_at)
+        }
+        _ => scanned_at,
+    }
+}
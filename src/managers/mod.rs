@@ -1,7 +1,248 @@
+pub mod backend;
 pub mod cargo;
+pub mod composer;
+pub mod dart;
+pub mod demo;
 pub mod detector;
+pub mod fonts;
+pub mod gem;
+pub mod gh;
+pub mod github_releases;
+pub mod go;
+pub mod go_modules;
+pub mod haskell;
+pub mod health;
 pub mod homebrew_fast;
+pub mod infra;
+pub mod julia;
+pub mod luarocks;
+pub mod mas;
+pub mod maven;
+pub mod metadata;
+pub mod nix;
 pub mod npm;
+pub mod opam;
 pub mod pip;
+pub mod pipx;
+pub mod python_tools;
+pub mod r;
+pub mod releases;
+pub mod shell_plugins;
+pub mod swift;
+pub mod system;
+pub mod version_managers;
+pub mod windows;
 
 pub use detector::detect_available_managers;
+
+use crate::config::DescriptionSource;
+use crate::models::{Package, PackageManager};
+use anyhow::Result;
+
+/// Managers whose packages can have their description looked up one at a time
+pub fn has_description_lookup(manager: &PackageManager) -> bool {
+    matches!(
+        manager,
+        PackageManager::Homebrew
+            | PackageManager::Npm
+            | PackageManager::Cargo
+            | PackageManager::Pip
+    )
+}
+
+/// One package's description from the configured source
+pub async fn fetch_description(
+    manager: &PackageManager,
+    name: &str,
+    source: DescriptionSource,
+) -> Option<String> {
+    match manager {
+        PackageManager::Homebrew => homebrew_fast::fetch_formula_description(name, source).await,
+        PackageManager::Npm => npm::fetch_npm_description(name, source).await,
+        PackageManager::Cargo => cargo::fetch_cargo_description(name, source).await,
+        PackageManager::Pip => pip::fetch_pip_description(name, source).await,
+        _ => None,
+    }
+}
+
+/// Listing plus outdated check for backends that don't need the GUI's progressive
+/// phases (descriptions, sizes). None when the manager has no backend.
+pub async fn list_with_outdated(manager: &PackageManager) -> Option<Result<Vec<Package>>> {
+    let result = match manager {
+        PackageManager::Opam => match opam::list_opam_packages().await {
+            Ok(mut packages) => {
+                let _ = opam::check_outdated_opam(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::Ghcup => match haskell::list_ghcup_tools().await {
+            Ok(mut packages) => {
+                let _ = haskell::check_outdated_ghcup(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::Stack => match haskell::list_stack_tools().await {
+            Ok(mut packages) => {
+                let _ = haskell::check_outdated_stack(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::LuaRocks => match luarocks::list_luarocks_packages().await {
+            Ok(mut packages) => {
+                let _ = luarocks::check_outdated_luarocks(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::Pub => match dart::list_pub_packages().await {
+            Ok(mut packages) => {
+                let _ = dart::check_outdated_pub(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::Swift => match swift::list_swift_packages().await {
+            Ok(mut packages) => {
+                let _ = swift::check_outdated_swift(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::Pipx => match pipx::list_pipx_packages().await {
+            Ok(apps) => {
+                let (mut packages, projects): (Vec<Package>, Vec<String>) =
+                    apps.into_iter().unzip();
+                let _ = pipx::check_projects_on_pypi(&mut packages, &projects).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::Uv => match python_tools::list_uv_tools().await {
+            Ok(mut packages) => {
+                let _ = pipx::check_outdated_on_pypi(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::Poetry => match python_tools::list_poetry_plugins().await {
+            Ok(mut packages) => {
+                let _ = pipx::check_outdated_on_pypi(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::Gem => match gem::list_gem_packages().await {
+            Ok(mut packages) => {
+                let _ = gem::check_outdated_gem(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::Go => go::list_go_binaries().await,
+        PackageManager::Composer => match composer::list_composer_packages().await {
+            Ok(mut packages) => {
+                let _ = composer::check_outdated_composer(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::Winget => windows::list_winget_packages().await,
+        PackageManager::Scoop => match windows::list_scoop_packages().await {
+            Ok(mut packages) => {
+                let _ = windows::check_outdated_scoop(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::Chocolatey => match windows::list_choco_packages().await {
+            Ok(mut packages) => {
+                let _ = windows::check_outdated_choco(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::Nix => match nix::list_nix_packages().await {
+            Ok(mut packages) => {
+                let _ = nix::check_outdated_nix(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::Mas => match mas::list_mas_apps().await {
+            Ok(mut packages) => {
+                let _ = mas::check_outdated_mas(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::Mise => match version_managers::list_mise_tools().await {
+            Ok(mut packages) => {
+                let _ = version_managers::check_outdated_mise(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::Asdf => match version_managers::list_asdf_tools().await {
+            Ok(mut packages) => {
+                let _ = version_managers::check_outdated_asdf(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::Julia => julia::list_julia_packages().await,
+        PackageManager::R => r::list_r_packages().await,
+        PackageManager::Tfenv => match infra::list_tfenv_versions().await {
+            Ok(mut packages) => {
+                let _ = infra::check_outdated_tfenv(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::HelmPlugins => match infra::list_helm_plugins().await {
+            Ok(mut packages) => {
+                let _ = infra::check_outdated_helm_plugins(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::Krew => match infra::list_krew_plugins().await {
+            Ok(mut packages) => {
+                let _ = infra::check_outdated_krew(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::GhExtensions => match gh::list_gh_extensions().await {
+            Ok(mut packages) => {
+                let _ = gh::check_outdated_gh_extensions(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::GitHelpers => gh::list_git_helpers().await,
+        PackageManager::OhMyZsh | PackageManager::Zinit => {
+            shell_plugins::list_checkout_plugins(manager).await
+        }
+        PackageManager::Fisher => shell_plugins::list_fisher_plugins().await,
+        PackageManager::Fonts => fonts::list_font_casks().await,
+        PackageManager::Apk => match system::list_apk_packages().await {
+            Ok(mut packages) => {
+                let _ = system::check_outdated_apk(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        PackageManager::Apt => match system::list_dpkg_packages().await {
+            Ok(mut packages) => {
+                let _ = system::check_outdated_apt(&mut packages).await;
+                Ok(packages)
+            }
+            Err(e) => Err(e),
+        },
+        _ => return None,
+    };
+    Some(result)
+}
@@ -0,0 +1,254 @@
+use crate::models::{Package, PackageManager};
+use crate::utils::run_command_with_timeout;
+use crate::utils::version::is_newer;
+use anyhow::{anyhow, Result};
+use futures::{stream, StreamExt};
+use std::time::Duration;
+
+/// `nix profile` needs these, and many installs don't enable them in nix.conf
+const FEATURES: [&str; 2] = ["--extra-experimental-features", "nix-command flakes"];
+
+/// One element of the user's profile, with where it came from for upgrade checks
+#[derive(Debug, PartialEq)]
+struct ProfileElement {
+    name: String,
+    version: String,
+    /// Flake reference as installed, e.g. `flake:nixpkgs`; upgrades re-resolve it
+    original_url: Option<String>,
+    attr_path: Option<String>,
+}
+
+async fn nix(args: &[&str], timeout: Duration) -> Result<std::process::Output> {
+    let mut full = FEATURES.to_vec();
+    full.extend(args);
+    run_command_with_timeout("nix", &full, timeout).await
+}
+
+/// List packages installed in the user profile (`nix profile install`)
+pub async fn list_nix_packages() -> Result<Vec<Package>> {
+    println!("[NIX] Listing profile packages");
+
+    let output = nix(&["profile", "list", "--json"], Duration::from_secs(60)).await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        // Profiles managed by nix-env can't be read by `nix profile`
+        if stderr.contains("nix-env") {
+            return Ok(Vec::new());
+        }
+        return Err(anyhow!("nix profile list failed: {}", stderr.trim()));
+    }
+
+    let packages: Vec<Package> = parse_profile(&String::from_utf8_lossy(&output.stdout))?
+        .into_iter()
+        .map(|element| Package::new(element.name, PackageManager::Nix, element.version))
+        .collect();
+    println!("[NIX] Found {} profile packages", packages.len());
+    Ok(packages)
+}
+
+/// `nix profile list --json`: elements keyed by name (Nix 2.20+), or a list of them
+/// named after their attribute path in older versions
+fn parse_profile(stdout: &str) -> Result<Vec<ProfileElement>> {
+    let json: serde_json::Value = serde_json::from_str(stdout)?;
+    let elements: Vec<(Option<&String>, &serde_json::Value)> = match &json["elements"] {
+        serde_json::Value::Object(map) => map.iter().map(|(k, v)| (Some(k), v)).collect(),
+        serde_json::Value::Array(list) => list.iter().map(|v| (None, v)).collect(),
+        _ => return Err(anyhow!("nix profile list gave no elements")),
+    };
+
+    Ok(elements
+        .into_iter()
+        .filter_map(|(key, element)| {
+            let store_name = element["storePaths"][0]
+                .as_str()?
+                .rsplit('/')
+                .next()?
+                // "<32-character hash>-ripgrep-14.1.0"
+                .get(33..)?;
+            let (pname, version) = split_name_version(store_name);
+            // Outputs other than `out` carry their name: "jq-1.7.1-bin"
+            let version = ["-bin", "-man", "-dev", "-lib", "-doc"]
+                .iter()
+                .find_map(|output| version.strip_suffix(output))
+                .unwrap_or(version);
+            let attr_path = element["attrPath"].as_str().map(String::from);
+            let name = match (key, &attr_path) {
+                (Some(key), _) => key.clone(),
+                (None, Some(path)) => path.rsplit('.').next().unwrap_or(path).to_string(),
+                (None, None) => pname.to_string(),
+            };
+            Some(ProfileElement {
+                name,
+                version: version.to_string(),
+                original_url: element["originalUrl"].as_str().map(String::from),
+                attr_path,
+            })
+        })
+        .collect())
+}
+
+/// "ripgrep-14.1.0" -> ("ripgrep", "14.1.0"): the version starts at the first dash
+/// followed by a digit, as in nixpkgs' own parseDrvName
+fn split_name_version(store_name: &str) -> (&str, &str) {
+    let split = store_name
+        .char_indices()
+        .find(|&(i, c)| {
+            c == '-'
+                && store_name[i + 1..]
+                    .chars()
+                    .next()
+                    .is_some_and(|next| next.is_ascii_digit())
+        })
+        .map(|(i, _)| i);
+    match split {
+        Some(i) => (&store_name[..i], &store_name[i + 1..]),
+        None => (store_name, ""),
+    }
+}
+
+/// Evaluate each element's attribute in its flake as it resolves today (the channel
+/// or branch it was installed from), which is what `nix profile upgrade` would install
+pub async fn check_outdated_nix(packages: &mut [Package]) -> Result<()> {
+    println!("[NIX] Checking for upgrades");
+
+    let output = nix(&["profile", "list", "--json"], Duration::from_secs(60)).await?;
+    let elements = parse_profile(&String::from_utf8_lossy(&output.stdout))?;
+
+    let checks: Vec<(String, String)> = elements
+        .into_iter()
+        .filter_map(|element| {
+            let installable = format!("{}#{}", element.original_url?, element.attr_path?);
+            Some((element.name, installable))
+        })
+        .collect();
+
+    let results: Vec<Option<(String, String)>> = stream::iter(checks)
+        .map(|(name, installable)| async move {
+            let attr = format!("{}.version", installable);
+            let output = nix(&["eval", "--raw", &attr], Duration::from_secs(120))
+                .await
+                .ok()
+                .filter(|o| o.status.success());
+            match output {
+                Some(output) => Some((name, String::from_utf8_lossy(&output.stdout).to_string())),
+                None => {
+                    eprintln!("[NIX] Couldn't evaluate {}", attr);
+                    None
+                }
+            }
+        })
+        .buffer_unordered(4)
+        .collect()
+        .await;
+
+    for (name, latest) in results.into_iter().flatten() {
+        if let Some(pkg) = packages.iter_mut().find(|p| p.name == name) {
+            pkg.is_outdated = is_newer(&latest, &pkg.installed_version);
+            pkg.latest_version = Some(latest);
+        }
+    }
+    Ok(())
+}
+
+async fn run_nix_profile(action: &str, args: &[&str], package_name: &str) -> Result<()> {
+    println!("[NIX] {}: {}", action, package_name);
+
+    // Builds can fall back to compiling from source
+    let output = nix(args, Duration::from_secs(1200)).await?;
+
+    if output.status.success() {
+        println!("[NIX] Successfully finished {}: {}", action, package_name);
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!(
+            "Failed to {} {}: {}",
+            action,
+            package_name,
+            stderr.trim()
+        ))
+    }
+}
+
+pub async fn update_nix_package(package_name: String) -> Result<()> {
+    run_nix_profile(
+        "upgrade",
+        &["profile", "upgrade", &package_name],
+        &package_name,
+    )
+    .await
+}
+
+pub async fn uninstall_nix_package(package_name: String) -> Result<()> {
+    run_nix_profile(
+        "remove",
+        &["profile", "remove", &package_name],
+        &package_name,
+    )
+    .await
+}
+
+/// Install from nixpkgs (the registry's default channel)
+pub async fn install_nix_package(package_name: String) -> Result<()> {
+    let installable = format!("nixpkgs#{}", package_name);
+    run_nix_profile(
+        "install",
+        &["profile", "install", &installable],
+        &package_name,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::executor::{replay_fixture, with_executor};
+
+    #[tokio::test]
+    async fn lists_profile_and_evaluates_upgrades() {
+        let packages = with_executor(replay_fixture("nix"), async {
+            let mut packages = list_nix_packages().await?;
+            check_outdated_nix(&mut packages).await?;
+            Ok::<_, anyhow::Error>(packages)
+        })
+        .await
+        .unwrap();
+
+        let mut summary: Vec<(&str, &str, Option<&str>, bool)> = packages
+            .iter()
+            .map(|p| {
+                (
+                    p.name.as_str(),
+                    p.installed_version.as_str(),
+                    p.latest_version.as_deref(),
+                    p.is_outdated,
+                )
+            })
+            .collect();
+        summary.sort();
+        assert_eq!(
+            summary,
+            [
+                ("jq", "1.7.1", Some("1.7.1"), false),
+                ("ripgrep", "14.0.3", Some("14.1.0"), true),
+                // Installed from a store path: nothing to evaluate
+                ("rust-analyzer", "2024-06-03", None, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn older_nix_lists_elements_without_names() {
+        let stdout = r#"{"elements":[{"active":true,"attrPath":"legacyPackages.aarch64-darwin.python312","originalUrl":"flake:nixpkgs","storePaths":["/nix/store/0c4w3fr9cn6bvbxqxgiclcv1y3q7s6hq-python3-3.12.4"],"url":"github:NixOS/nixpkgs/8a3354191c0d7144db9756a74755672387b702ba"}],"version":2}"#;
+        assert_eq!(
+            parse_profile(stdout).unwrap(),
+            [ProfileElement {
+                name: "python312".to_string(),
+                version: "3.12.4".to_string(),
+                original_url: Some("flake:nixpkgs".to_string()),
+                attr_path: Some("legacyPackages.aarch64-darwin.python312".to_string()),
+            }]
+        );
+    }
+}
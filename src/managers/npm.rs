@@ -1,6 +1,11 @@
-use crate::models::{Package, PackageManager};
+use crate::config::DescriptionSource;
+use crate::models::{Package, PackageFlags, PackageManager};
+use crate::utils::http_client::create_http_client;
+use crate::utils::metrics::SendTimed;
 use crate::utils::run_command_with_timeout;
+use crate::utils::version::is_newer;
 use anyhow::{anyhow, Result};
+use futures::{stream, StreamExt};
 use std::time::Duration;
 
 /// List globally installed npm packages
@@ -26,16 +31,11 @@ pub async fn list_npm_packages() -> Result<Vec<Package>> {
     if let Some(deps) = json.get("dependencies").and_then(|d| d.as_object()) {
         for (name, info) in deps {
             if let Some(version) = info.get("version").and_then(|v| v.as_str()) {
-                packages.push(Package {
-                    name: name.clone(),
-                    manager: PackageManager::Npm,
-                    installed_version: version.to_string(),
-                    latest_version: None,
-                    is_outdated: false,
-                    description: None,
-                    used_in: vec![],
-                    size: None,
-                });
+                packages.push(Package::new(
+                    name.clone(),
+                    PackageManager::Npm,
+                    version.to_string(),
+                ));
             }
         }
     }
@@ -64,19 +64,143 @@ pub async fn check_outdated_npm(packages: &mut [Package]) -> Result<()> {
                 if let Some(info) = outdated.get(&pkg.name) {
                     if let Some(latest) = info.get("latest").and_then(|v| v.as_str()) {
                         pkg.latest_version = Some(latest.to_string());
-                        pkg.is_outdated = true;
+                        // Also listed when a prerelease is installed ahead of `latest`
+                        pkg.is_outdated = is_newer(latest, &pkg.installed_version);
                     }
                 }
             }
         }
     }
 
+    check_npm_channels(packages).await;
     Ok(())
 }
 
+/// Packages following a dist-tag (`next`, `beta`, `canary`...) are compared with the
+/// version that tag points to rather than `latest`
+async fn check_npm_channels(packages: &mut [Package]) {
+    let flags = PackageFlags::load();
+    let tagged: Vec<(usize, String, String)> = packages
+        .iter()
+        .enumerate()
+        .filter_map(|(index, pkg)| Some((index, pkg.name.clone(), flags.channel(pkg)?.to_string())))
+        .collect();
+    if tagged.is_empty() {
+        return;
+    }
+
+    let results: Vec<Option<(usize, String)>> = stream::iter(tagged)
+        .map(|(index, name, tag)| async move {
+            match dist_tag(&name, &tag).await {
+                Ok(version) => Some((index, version)),
+                Err(e) => {
+                    eprintln!("[NPM] {}", e);
+                    None
+                }
+            }
+        })
+        .buffer_unordered(crate::config::registry_lookups())
+        .collect()
+        .await;
+
+    for (index, version) in results.into_iter().flatten() {
+        let pkg = &mut packages[index];
+        // A tag can move backwards (a canary after a release), so any difference counts
+        pkg.is_outdated = version != pkg.installed_version;
+        pkg.latest_version = Some(version);
+    }
+}
+
+/// The version a dist-tag points to, from the registry's abbreviated metadata
+async fn dist_tag(name: &str, tag: &str) -> Result<String> {
+    let url = format!(
+        "{}/{}",
+        crate::utils::registries::npm_registry(name),
+        name.replace('/', "%2f")
+    );
+    let response = create_http_client()
+        .get(&url)
+        .header("Accept", "application/vnd.npm.install-v1+json")
+        .send_timed()
+        .await?;
+    if !response.status().is_success() {
+        return Err(anyhow!("{} returned {}", url, response.status()));
+    }
+    let json: serde_json::Value = response.json().await?;
+    json["dist-tags"][tag]
+        .as_str()
+        .map(String::from)
+        .ok_or_else(|| anyhow!("{} has no \"{}\" dist-tag", name, tag))
+}
+
+/// Disk usage of each global package directory under `npm root -g`
+pub async fn add_npm_sizes(packages: &mut [Package]) -> Result<()> {
+    use crate::utils::disk::dir_size;
+
+    let output = run_command_with_timeout("npm", &["root", "-g"], Duration::from_secs(10)).await?;
+    if !output.status.success() {
+        return Err(anyhow!("npm root -g failed"));
+    }
+    let root = std::path::PathBuf::from(String::from_utf8_lossy(&output.stdout).trim().to_string());
+
+    let names: Vec<String> = packages.iter().map(|p| p.name.clone()).collect();
+    let sizes: Vec<u64> = tokio::task::spawn_blocking(move || {
+        names
+            .iter()
+            .map(|name| dir_size(&root.join(name))) // scoped names map to nested dirs
+            .collect()
+    })
+    .await?;
+
+    for (pkg, size) in packages.iter_mut().zip(sizes) {
+        if size > 0 {
+            pkg.size = Some(size);
+        }
+    }
+
+    Ok(())
+}
+
+/// One npm package's description from `npm view` or the registry API
+pub async fn fetch_npm_description(name: &str, source: DescriptionSource) -> Option<String> {
+    let desc = match source {
+        DescriptionSource::Cli => {
+            let output = run_command_with_timeout(
+                "npm",
+                &["view", name, "description"],
+                Duration::from_secs(5),
+            )
+            .await
+            .ok()
+            .filter(|o| o.status.success())?;
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        }
+        DescriptionSource::Http => {
+            // Scoped names keep their "@" but escape the slash
+            let url = format!(
+                "{}/{}",
+                crate::utils::registries::npm_registry(name),
+                name.replace('/', "%2f")
+            );
+            let json: serde_json::Value = create_http_client()
+                .get(&url)
+                .send_timed()
+                .await
+                .ok()?
+                .json()
+                .await
+                .ok()?;
+            json["description"].as_str()?.trim().to_string()
+        }
+    };
+    (!desc.is_empty()).then_some(desc)
+}
+
 /// Fetch descriptions for npm packages (parallel)
 pub async fn add_npm_descriptions(
     packages: std::sync::Arc<tokio::sync::RwLock<Vec<crate::models::Package>>>,
+    source: DescriptionSource,
+    batch_size: usize,
 ) {
     use futures::{stream, StreamExt};
 
@@ -97,31 +221,20 @@ pub async fn add_npm_descriptions(
     let total = npm_packages.len();
     println!("[NPM] Fetching descriptions for {} packages", total);
 
-    const CONCURRENT_REQUESTS: usize = 8;
     let mut completed = 0;
 
     let mut stream = stream::iter(npm_packages)
         .map(|name| async move {
-            let result = run_command_with_timeout(
-                "npm",
-                &["view", &name, "description"],
-                Duration::from_secs(5),
-            )
-            .await;
-            (name, result)
+            let desc = fetch_npm_description(&name, source).await;
+            (name, desc)
         })
-        .buffer_unordered(CONCURRENT_REQUESTS);
-
-    while let Some((name, result)) = stream.next().await {
-        if let Ok(output) = result {
-            if output.status.success() {
-                let desc = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                if !desc.is_empty() {
-                    let mut packages_lock = packages.write().await;
-                    if let Some(pkg) = packages_lock.iter_mut().find(|p| p.name == name) {
-                        pkg.description = Some(desc);
-                    }
-                }
+        .buffer_unordered(batch_size);
+
+    while let Some((name, desc)) = stream.next().await {
+        if let Some(desc) = desc {
+            let mut packages_lock = packages.write().await;
+            if let Some(pkg) = packages_lock.iter_mut().find(|p| p.name == name) {
+                pkg.description = Some(desc);
             }
         }
 
@@ -134,16 +247,20 @@ pub async fn add_npm_descriptions(
     println!("[NPM] Finished fetching descriptions");
 }
 
-/// Update an npm package
+/// Update an npm package, reinstalling from its dist-tag when it follows one
 pub async fn update_npm_package(package_name: String) -> Result<()> {
     println!("[NPM] Updating: {}", package_name);
 
-    let output = run_command_with_timeout(
-        "npm",
-        &["update", "-g", &package_name],
-        Duration::from_secs(300),
-    )
-    .await?;
+    let args = match PackageFlags::load().channel_of(&PackageManager::Npm, &package_name) {
+        Some(tag) => vec![
+            "install".to_string(),
+            "-g".to_string(),
+            format!("{}@{}", package_name, tag),
+        ],
+        None => vec!["update".to_string(), "-g".to_string(), package_name.clone()],
+    };
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let output = run_command_with_timeout("npm", &args, Duration::from_secs(300)).await?;
 
     if output.status.success() {
         println!("[NPM] Successfully updated: {}", package_name);
@@ -193,3 +310,76 @@ pub async fn install_npm_package(package_name: String) -> Result<()> {
         Err(anyhow!("Failed to install {}: {}", package_name, stderr))
     }
 }
+
+/// Install an exact version (used for rollbacks)
+pub async fn install_npm_version(package_name: String, version: String) -> Result<()> {
+    let spec = format!("{}@{}", package_name, version);
+    println!("[NPM] Installing: {}", spec);
+
+    let output =
+        run_command_with_timeout("npm", &["install", "-g", &spec], Duration::from_secs(300))
+            .await?;
+
+    if output.status.success() {
+        println!("[NPM] Successfully installed: {}", spec);
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to install {}: {}", spec, stderr))
+    }
+}
+
+/// Update npm itself
+pub async fn self_update_npm() -> Result<()> {
+    println!("[NPM] Updating npm itself");
+
+    let output = run_command_with_timeout(
+        "npm",
+        &["install", "-g", "npm@latest"],
+        Duration::from_secs(300),
+    )
+    .await?;
+
+    if output.status.success() {
+        println!("[NPM] npm is up to date");
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to update npm: {}", stderr))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::executor::{replay_fixture, with_executor};
+
+    #[tokio::test]
+    async fn lists_and_checks_global_packages() {
+        let packages = with_executor(replay_fixture("npm"), async {
+            let mut packages = list_npm_packages().await.unwrap();
+            check_outdated_npm(&mut packages).await.unwrap();
+            packages
+        })
+        .await;
+
+        let summary: Vec<(&str, &str, Option<&str>)> = packages
+            .iter()
+            .map(|p| {
+                (
+                    p.name.as_str(),
+                    p.installed_version.as_str(),
+                    p.latest_version.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("corepack", "0.34.6", None),
+                ("npm", "10.9.2", Some("11.6.2")),
+                ("typescript", "5.3.3", Some("5.9.3")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use crate::models::{Package, PackageManager};
+use crate::utils::run_command_with_timeout;
+use anyhow::{anyhow, Result};
+use std::time::Duration;
+
+/// List packages installed in the current opam switch
+pub async fn list_opam_packages() -> Result<Vec<Package>> {
+    println!("[OPAM] Listing installed packages");
+
+    let output = run_command_with_timeout(
+        "opam",
+        &[
+            "list",
+            "--installed",
+            "--columns=name,installed-version",
+            "--color=never",
+        ],
+        Duration::from_secs(30),
+    )
+    .await?;
+
+    if !output.status.success() {
+        return Err(anyhow!("opam list failed"));
+    }
+
+    // "# Packages matching: installed" / "# Name  # Installed" headers, then "base  v0.16.3"
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let packages: Vec<Package> = stdout
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some((fields.next()?, fields.next()?))
+        })
+        .map(|(name, version)| Package::new(name, PackageManager::Opam, version))
+        .collect();
+
+    println!("[OPAM] Found {} installed packages", packages.len());
+    Ok(packages)
+}
+
+/// Check for outdated opam packages by asking for the upgrade plan without running it
+pub async fn check_outdated_opam(packages: &mut [Package]) -> Result<()> {
+    println!("[OPAM] Checking for outdated packages");
+
+    let output = run_command_with_timeout(
+        "opam",
+        &[
+            "upgrade",
+            "--dry-run",
+            "--show-actions",
+            "--color=never",
+            "-y",
+        ],
+        Duration::from_secs(120),
+    )
+    .await?;
+
+    // "  ↗ upgrade base   v0.15.1 to v0.16.3" (older opam prints "- upgrade")
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for line in stdout.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let Some(pos) = fields.iter().position(|f| *f == "upgrade") else {
+            continue;
+        };
+        if let [name, _from, "to", latest, ..] = fields[pos + 1..] {
+            if let Some(pkg) = packages.iter_mut().find(|p| p.name == name) {
+                pkg.latest_version = Some(latest.to_string());
+                pkg.is_outdated = true;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn run_opam(action: &str, args: &[&str], package_name: &str) -> Result<()> {
+    println!("[OPAM] {}: {}", action, package_name);
+
+    let output = run_command_with_timeout("opam", args, Duration::from_secs(900)).await?;
+
+    if output.status.success() {
+        println!("[OPAM] Successfully finished {}: {}", action, package_name);
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to {} {}: {}", action, package_name, stderr))
+    }
+}
+
+pub async fn update_opam_package(package_name: String) -> Result<()> {
+    run_opam("upgrade", &["upgrade", "-y", &package_name], &package_name).await
+}
+
+pub async fn install_opam_package(package_name: String) -> Result<()> {
+    run_opam("install", &["install", "-y", &package_name], &package_name).await
+}
+
+pub async fn uninstall_opam_package(package_name: String) -> Result<()> {
+    run_opam("remove", &["remove", "-y", &package_name], &package_name).await
+}
+
+/// `opam install pkg.1.2.3` pins nothing but installs exactly that version
+pub async fn install_opam_version(package_name: String, version: String) -> Result<()> {
+    let spec = format!("{}.{}", package_name, version);
+    run_opam("install", &["install", "-y", &spec], &package_name).await
+}
@@ -1,43 +1,56 @@
+use crate::config::DescriptionSource;
 use crate::models::{Package, PackageManager};
+use crate::utils::http_client::create_http_client;
+use crate::utils::metrics::SendTimed;
 use crate::utils::run_command_with_timeout;
 use anyhow::{anyhow, Result};
+use std::path::Path;
 use std::time::Duration;
 
+/// PEP 503 normalized name: case-insensitive, with runs of `-`, `_` and `.` as one `-`
+/// ("Zope.Interface" and "zope_interface" are the same distribution)
+pub fn normalize_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for c in name.chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+        } else {
+            normalized.extend(c.to_lowercase());
+        }
+    }
+    normalized
+}
+
+/// Interpreter whose packages the Pip backend manages. `pip3` on PATH can belong to a
+/// different Python than `python3`, so listing, the outdated check and updates all go
+/// through `python3 -m pip`, as project venvs go through their own `bin/python`.
+const PYTHON: &str = "python3";
+
+/// `<python> -m pip <args>`
+async fn pip(python: &str, args: &[&str], timeout: Duration) -> Result<std::process::Output> {
+    let args: Vec<&str> = ["-m", "pip"].iter().chain(args).copied().collect();
+    run_command_with_timeout(python, &args, timeout).await
+}
+
 /// List globally installed pip packages
 pub async fn list_pip_packages() -> Result<Vec<Package>> {
     println!("[PIP] Listing installed packages");
 
-    let output =
-        run_command_with_timeout("pip3", &["list", "--format=json"], Duration::from_secs(30))
-            .await?;
+    // --verbose adds each package's location, which tells pipx's venvs apart
+    let output = pip(
+        PYTHON,
+        &["list", "--format=json", "--verbose"],
+        Duration::from_secs(30),
+    )
+    .await?;
 
     if !output.status.success() {
-        return Err(anyhow!("pip3 list failed"));
-    }
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let json: Vec<serde_json::Value> = serde_json::from_str(&stdout)?;
-
-    let mut packages = Vec::new();
-
-    for item in json {
-        if let (Some(name), Some(version)) = (
-            item.get("name").and_then(|n| n.as_str()),
-            item.get("version").and_then(|v| v.as_str()),
-        ) {
-            packages.push(Package {
-                name: name.to_string(),
-                manager: PackageManager::Pip,
-                installed_version: version.to_string(),
-                latest_version: None,
-                is_outdated: false,
-                description: None,
-                used_in: vec![],
-                size: None,
-            });
-        }
+        return Err(anyhow!("python3 -m pip list failed"));
     }
 
+    let packages = parse_pip_list(&String::from_utf8_lossy(&output.stdout))?;
     println!("[PIP] Found {} installed packages", packages.len());
     Ok(packages)
 }
@@ -46,8 +59,8 @@ pub async fn list_pip_packages() -> Result<Vec<Package>> {
 pub async fn check_outdated_pip(packages: &mut [Package]) -> Result<()> {
     println!("[PIP] Checking for outdated packages");
 
-    let output = run_command_with_timeout(
-        "pip3",
+    let output = pip(
+        PYTHON,
         &["list", "--outdated", "--format=json"],
         Duration::from_secs(60),
     )
@@ -57,9 +70,40 @@ pub async fn check_outdated_pip(packages: &mut [Package]) -> Result<()> {
         return Ok(()); // Not a fatal error
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
+    mark_outdated(&String::from_utf8_lossy(&output.stdout), packages);
+    Ok(())
+}
+
+/// Packages from `pip list --format=json`, leaving out any that live in a pipx venv
+/// (when `pip3` is one of them) since the pipx backend lists those apps
+fn parse_pip_list(stdout: &str) -> Result<Vec<Package>> {
+    let json: Vec<serde_json::Value> = serde_json::from_str(stdout)?;
+
+    let mut packages = Vec::new();
+
+    for item in json {
+        let location = item.get("location").and_then(|l| l.as_str()).unwrap_or("");
+        if location.contains("/pipx/venvs/") {
+            continue;
+        }
+        if let (Some(name), Some(version)) = (
+            item.get("name").and_then(|n| n.as_str()),
+            item.get("version").and_then(|v| v.as_str()),
+        ) {
+            packages.push(Package::new(
+                name.to_string(),
+                PackageManager::Pip,
+                version.to_string(),
+            ));
+        }
+    }
+
+    Ok(packages)
+}
 
-    if let Ok(json) = serde_json::from_str::<Vec<serde_json::Value>>(&stdout) {
+/// Apply `pip list --outdated --format=json` output to `packages`
+fn mark_outdated(stdout: &str, packages: &mut [Package]) {
+    if let Ok(json) = serde_json::from_str::<Vec<serde_json::Value>>(stdout) {
         for item in json {
             if let (Some(name), Some(latest)) = (
                 item.get("name").and_then(|n| n.as_str()),
@@ -75,13 +119,76 @@ pub async fn check_outdated_pip(packages: &mut [Package]) -> Result<()> {
             }
         }
     }
+}
 
-    Ok(())
+/// List a project virtualenv's packages and check them for updates, using the venv's own pip
+pub async fn list_venv_packages(venv: &Path) -> Result<Vec<Package>> {
+    println!("[PIP] Listing packages in {}", venv.display());
+
+    let python = venv.join("bin").join("python");
+    let python = python.to_string_lossy();
+
+    let output = pip(&python, &["list", "--format=json"], Duration::from_secs(30)).await?;
+
+    if !output.status.success() {
+        return Err(anyhow!("pip list failed in {}", venv.display()));
+    }
+
+    let mut packages = parse_pip_list(&String::from_utf8_lossy(&output.stdout))?;
+
+    let output = pip(
+        &python,
+        &["list", "--outdated", "--format=json"],
+        Duration::from_secs(60),
+    )
+    .await?;
+    if output.status.success() {
+        mark_outdated(&String::from_utf8_lossy(&output.stdout), &mut packages);
+    }
+
+    println!(
+        "[PIP] Found {} packages in {}",
+        packages.len(),
+        venv.display()
+    );
+    Ok(packages)
+}
+
+/// One pip package's summary from `pip show` or PyPI's JSON API
+pub async fn fetch_pip_description(name: &str, source: DescriptionSource) -> Option<String> {
+    let desc = match source {
+        DescriptionSource::Cli => {
+            let output = pip(PYTHON, &["show", name], Duration::from_secs(5))
+                .await
+                .ok()
+                .filter(|o| o.status.success())?;
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            stdout
+                .lines()
+                .find_map(|line| line.strip_prefix("Summary: "))?
+                .trim()
+                .to_string()
+        }
+        DescriptionSource::Http => {
+            let json: serde_json::Value = create_http_client()
+                .get(crate::utils::registries::pypi_url(name))
+                .send_timed()
+                .await
+                .ok()?
+                .json()
+                .await
+                .ok()?;
+            json["info"]["summary"].as_str()?.trim().to_string()
+        }
+    };
+    (!desc.is_empty()).then_some(desc)
 }
 
 /// Fetch descriptions for pip packages
 pub async fn add_pip_descriptions(
     packages: std::sync::Arc<tokio::sync::RwLock<Vec<crate::models::Package>>>,
+    source: DescriptionSource,
+    batch_size: usize,
 ) {
     use futures::{stream, StreamExt};
 
@@ -102,34 +209,20 @@ pub async fn add_pip_descriptions(
     let total = pip_packages.len();
     println!("[PIP] Fetching descriptions for {} packages", total);
 
-    const CONCURRENT_REQUESTS: usize = 8;
     let mut completed = 0;
 
     let mut stream = stream::iter(pip_packages)
         .map(|name| async move {
-            let result =
-                run_command_with_timeout("pip3", &["show", &name], Duration::from_secs(5)).await;
-            (name, result)
+            let desc = fetch_pip_description(&name, source).await;
+            (name, desc)
         })
-        .buffer_unordered(CONCURRENT_REQUESTS);
-
-    while let Some((name, result)) = stream.next().await {
-        if let Ok(output) = result {
-            if output.status.success() {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                // Parse pip show output for Summary line
-                for line in stdout.lines() {
-                    if let Some(desc) = line.strip_prefix("Summary: ") {
-                        let desc = desc.trim().to_string();
-                        if !desc.is_empty() {
-                            let mut packages_lock = packages.write().await;
-                            if let Some(pkg) = packages_lock.iter_mut().find(|p| p.name == name) {
-                                pkg.description = Some(desc);
-                            }
-                            break;
-                        }
-                    }
-                }
+        .buffer_unordered(batch_size);
+
+    while let Some((name, desc)) = stream.next().await {
+        if let Some(desc) = desc {
+            let mut packages_lock = packages.write().await;
+            if let Some(pkg) = packages_lock.iter_mut().find(|p| p.name == name) {
+                pkg.description = Some(desc);
             }
         }
 
@@ -146,8 +239,8 @@ pub async fn add_pip_descriptions(
 pub async fn update_pip_package(package_name: String) -> Result<()> {
     println!("[PIP] Updating: {}", package_name);
 
-    let output = run_command_with_timeout(
-        "pip3",
+    let output = pip(
+        PYTHON,
         &["install", "--upgrade", &package_name],
         Duration::from_secs(300),
     )
@@ -166,8 +259,8 @@ pub async fn update_pip_package(package_name: String) -> Result<()> {
 pub async fn uninstall_pip_package(package_name: String) -> Result<()> {
     println!("[PIP] Uninstalling: {}", package_name);
 
-    let output = run_command_with_timeout(
-        "pip3",
+    let output = pip(
+        PYTHON,
         &["uninstall", "-y", &package_name],
         Duration::from_secs(120),
     )
@@ -186,8 +279,8 @@ pub async fn uninstall_pip_package(package_name: String) -> Result<()> {
 pub async fn install_pip_package(package_name: String) -> Result<()> {
     println!("[PIP] Installing: {}", package_name);
 
-    let output = run_command_with_timeout(
-        "pip3",
+    let output = pip(
+        PYTHON,
         &["install", &package_name],
         Duration::from_secs(300),
     )
@@ -201,3 +294,68 @@ pub async fn install_pip_package(package_name: String) -> Result<()> {
         Err(anyhow!("Failed to install {}: {}", package_name, stderr))
     }
 }
+
+/// Install an exact version (used for rollbacks)
+pub async fn install_pip_version(package_name: String, version: String) -> Result<()> {
+    let spec = format!("{}=={}", package_name, version);
+    println!("[PIP] Installing: {}", spec);
+
+    let output = pip(PYTHON, &["install", &spec], Duration::from_secs(300)).await?;
+
+    if output.status.success() {
+        println!("[PIP] Successfully installed: {}", spec);
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to install {}: {}", spec, stderr))
+    }
+}
+
+/// Update pip itself
+pub async fn self_update_pip() -> Result<()> {
+    println!("[PIP] Updating pip itself");
+
+    let output = pip(
+        PYTHON,
+        &["install", "--upgrade", "pip"],
+        Duration::from_secs(300),
+    )
+    .await?;
+
+    if output.status.success() {
+        println!("[PIP] pip is up to date");
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to update pip: {}", stderr))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::executor::{replay_fixture, with_executor};
+
+    #[tokio::test]
+    async fn lists_and_checks_packages() {
+        let packages = with_executor(replay_fixture("pip"), async {
+            let mut packages = list_pip_packages().await.unwrap();
+            check_outdated_pip(&mut packages).await.unwrap();
+            packages
+        })
+        .await;
+
+        let outdated: Vec<(&str, Option<&str>)> = packages
+            .iter()
+            .map(|p| (p.name.as_str(), p.latest_version.as_deref()))
+            .collect();
+        assert_eq!(
+            outdated,
+            [
+                ("black", Some("24.8.0")),
+                ("numpy", None),
+                ("pip", Some("24.2")),
+            ]
+        );
+    }
+}
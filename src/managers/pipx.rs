@@ -0,0 +1,206 @@
+use crate::models::{Package, PackageManager};
+use crate::utils::cache::{get_cached, set_cached};
+use crate::utils::http_client::create_http_client;
+use crate::utils::metrics::SendTimed;
+use crate::utils::run_command_with_timeout;
+use anyhow::{anyhow, Result};
+use futures::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// List the apps pipx installed, one isolated venv each, with the PyPI project inside it
+/// (a venv installed with `--suffix` is named "black@23" but holds "black")
+pub async fn list_pipx_packages() -> Result<Vec<(Package, String)>> {
+    println!("[PIPX] Listing installed apps");
+
+    let output =
+        run_command_with_timeout("pipx", &["list", "--json"], Duration::from_secs(30)).await?;
+
+    if !output.status.success() {
+        return Err(anyhow!("pipx list failed"));
+    }
+
+    let packages = parse_pipx_list(&String::from_utf8_lossy(&output.stdout))?;
+    println!("[PIPX] Found {} installed apps", packages.len());
+    Ok(packages)
+}
+
+/// Packages from `pipx list --json`: venv name and its main package's version, with the
+/// commands it provides as the description until PyPI's summary replaces it
+fn parse_pipx_list(stdout: &str) -> Result<Vec<(Package, String)>> {
+    let json: serde_json::Value = serde_json::from_str(stdout)?;
+    let venvs = json["venvs"]
+        .as_object()
+        .ok_or_else(|| anyhow!("pipx list --json has no venvs"))?;
+
+    let mut packages = Vec::new();
+    for (name, venv) in venvs {
+        let main = &venv["metadata"]["main_package"];
+        let Some(version) = main["package_version"].as_str() else {
+            continue;
+        };
+        let project = main["package"].as_str().unwrap_or(name).to_string();
+        let mut pkg = Package::new(name.as_str(), PackageManager::Pipx, version);
+        let apps: Vec<&str> = main["apps"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|app| app.as_str())
+            .collect();
+        if !apps.is_empty() {
+            pkg.description = Some(format!("Provides {}", apps.join(", ")));
+        }
+        packages.push((pkg, project));
+    }
+    Ok(packages)
+}
+
+/// Latest version and summary of a project on PyPI
+#[derive(Serialize, Deserialize)]
+struct PypiRelease {
+    version: String,
+    summary: Option<String>,
+}
+
+/// pipx itself has no outdated check, so ask PyPI
+async fn pypi_release(name: &str) -> Result<PypiRelease> {
+    let cache_key = format!("pipx_pypi_{}", name);
+    if let Some(release) = get_cached::<PypiRelease>(&cache_key) {
+        return Ok(release);
+    }
+
+    let url = crate::utils::registries::pypi_url(name);
+    let response = create_http_client().get(&url).send_timed().await?;
+    if !response.status().is_success() {
+        return Err(anyhow!("{} returned {}", url, response.status()));
+    }
+    let json: serde_json::Value = response.json().await?;
+    let version = json["info"]["version"]
+        .as_str()
+        .ok_or_else(|| anyhow!("no version for {}", name))?
+        .to_string();
+    let summary = json["info"]["summary"]
+        .as_str()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from);
+
+    let release = PypiRelease { version, summary };
+    set_cached(
+        cache_key,
+        &release,
+        crate::config::cache_ttls().registry_ttl(),
+    );
+    Ok(release)
+}
+
+/// Check each app against PyPI, a few at a time (also used for uv tools and Poetry
+/// plugins, which are PyPI projects too)
+pub async fn check_outdated_on_pypi(packages: &mut [Package]) -> Result<()> {
+    let projects: Vec<String> = packages.iter().map(|p| p.name.clone()).collect();
+    check_projects_on_pypi(packages, &projects).await
+}
+
+/// Check each app against the PyPI project at the same index in `projects`
+pub async fn check_projects_on_pypi(packages: &mut [Package], projects: &[String]) -> Result<()> {
+    println!("[PYPI] Checking {} apps for newer releases", packages.len());
+
+    let names: Vec<(usize, String)> = projects.iter().cloned().enumerate().collect();
+    let releases: Vec<Option<(usize, PypiRelease)>> = stream::iter(names)
+        .map(|(index, name)| async move {
+            match pypi_release(&name).await {
+                Ok(release) => Some((index, release)),
+                Err(e) => {
+                    eprintln!("[PYPI] {}", e);
+                    None
+                }
+            }
+        })
+        .buffer_unordered(crate::config::registry_lookups())
+        .collect()
+        .await;
+
+    for (index, release) in releases.into_iter().flatten() {
+        let pkg = &mut packages[index];
+        pkg.is_outdated =
+            crate::utils::version::compare_versions(&release.version, &pkg.installed_version)
+                == std::cmp::Ordering::Greater;
+        pkg.latest_version = Some(release.version);
+        if release.summary.is_some() {
+            pkg.description = release.summary;
+        }
+    }
+    Ok(())
+}
+
+async fn run_pipx(action: &str, args: &[&str], package_name: &str) -> Result<()> {
+    println!("[PIPX] {}: {}", action, package_name);
+
+    let output = run_command_with_timeout("pipx", args, Duration::from_secs(300)).await?;
+
+    if output.status.success() {
+        println!("[PIPX] Successfully finished {}: {}", action, package_name);
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to {} {}: {}", action, package_name, stderr))
+    }
+}
+
+pub async fn update_pipx_package(package_name: String) -> Result<()> {
+    run_pipx("upgrade", &["upgrade", &package_name], &package_name).await
+}
+
+pub async fn uninstall_pipx_package(package_name: String) -> Result<()> {
+    run_pipx("uninstall", &["uninstall", &package_name], &package_name).await
+}
+
+/// Install a command-line tool into its own pipx venv (works with externally-managed
+/// Pythons like Homebrew's, which refuse `pip install`)
+pub async fn install_pipx_package(package_name: String) -> Result<()> {
+    run_pipx("install", &["install", &package_name], &package_name).await
+}
+
+/// Replace the app's venv with an exact version (used for rollbacks)
+pub async fn install_pipx_version(package_name: String, version: String) -> Result<()> {
+    let spec = format!("{}=={}", package_name, version);
+    run_pipx("install", &["install", "--force", &spec], &package_name).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::executor::{replay_fixture, with_executor};
+
+    #[tokio::test]
+    async fn lists_apps_with_their_commands() {
+        let packages = with_executor(replay_fixture("pipx"), list_pipx_packages()).await;
+
+        let apps: Vec<(&str, &str, &str, Option<&str>)> = packages
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|(p, project)| {
+                (
+                    p.name.as_str(),
+                    project.as_str(),
+                    p.installed_version.as_str(),
+                    p.description.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            apps,
+            [
+                ("black", "black", "24.1.1", Some("Provides black, blackd")),
+                (
+                    "black@23",
+                    "black",
+                    "23.12.1",
+                    Some("Provides black@23, blackd@23")
+                ),
+                ("poetry", "poetry", "1.8.2", Some("Provides poetry")),
+            ]
+        );
+    }
+}
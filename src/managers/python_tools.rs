@@ -0,0 +1,325 @@
+use crate::managers::pip::normalize_name;
+use crate::models::{Package, PackageManager};
+use crate::utils::run_command_with_timeout;
+use crate::utils::target::current_target;
+use anyhow::{anyhow, Result};
+use std::path::PathBuf;
+use std::time::Duration;
+
+async fn run_tool(
+    tag: &str,
+    command: &str,
+    action: &str,
+    args: &[&str],
+    package_name: &str,
+) -> Result<()> {
+    println!("[{}] {}: {}", tag, action, package_name);
+
+    let output = run_command_with_timeout(command, args, Duration::from_secs(300)).await?;
+
+    if output.status.success() {
+        println!(
+            "[{}] Successfully finished {}: {}",
+            tag, action, package_name
+        );
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!(
+            "Failed to {} {}: {}",
+            action,
+            package_name,
+            stderr.trim()
+        ))
+    }
+}
+
+// uv
+
+/// Tools `uv tool install` put in their own environments
+pub async fn list_uv_tools() -> Result<Vec<Package>> {
+    println!("[UV] Listing tools");
+
+    let output = run_command_with_timeout("uv", &["tool", "list"], Duration::from_secs(30)).await?;
+
+    if !output.status.success() {
+        return Err(anyhow!("uv tool list failed"));
+    }
+
+    let packages = parse_uv_tool_list(&String::from_utf8_lossy(&output.stdout));
+    println!("[UV] Found {} tools", packages.len());
+    Ok(packages)
+}
+
+/// "ruff v0.4.4" per tool, followed by "- ruff" for each command it provides
+fn parse_uv_tool_list(stdout: &str) -> Vec<Package> {
+    let mut packages: Vec<Package> = Vec::new();
+    let mut commands: Vec<&str> = Vec::new();
+    for line in stdout.lines() {
+        if let Some(command) = line.strip_prefix("- ") {
+            commands.push(command.trim());
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let (Some(name), Some(version)) = (fields.next(), fields.next()) else {
+            continue;
+        };
+        let Some(version) = version.strip_prefix('v') else {
+            continue; // "No tools installed"
+        };
+        if let Some(last) = packages.last_mut() {
+            last.description = describe_commands(&commands);
+        }
+        commands.clear();
+        packages.push(Package::new(name, PackageManager::Uv, version));
+    }
+    if let Some(last) = packages.last_mut() {
+        last.description = describe_commands(&commands);
+    }
+    packages
+}
+
+fn describe_commands(commands: &[&str]) -> Option<String> {
+    (!commands.is_empty()).then(|| format!("Provides {}", commands.join(", ")))
+}
+
+pub async fn update_uv_tool(package_name: String) -> Result<()> {
+    run_tool(
+        "UV",
+        "uv",
+        "upgrade",
+        &["tool", "upgrade", &package_name],
+        &package_name,
+    )
+    .await
+}
+
+pub async fn uninstall_uv_tool(package_name: String) -> Result<()> {
+    run_tool(
+        "UV",
+        "uv",
+        "uninstall",
+        &["tool", "uninstall", &package_name],
+        &package_name,
+    )
+    .await
+}
+
+pub async fn install_uv_tool(package_name: String) -> Result<()> {
+    run_tool(
+        "UV",
+        "uv",
+        "install",
+        &["tool", "install", &package_name],
+        &package_name,
+    )
+    .await
+}
+
+/// Replace the tool's environment with an exact version (used for rollbacks). uv records
+/// the `==version` in the tool's receipt and `uv tool upgrade` would keep to it, so the pin
+/// is dropped again afterwards.
+pub async fn install_uv_tool_version(package_name: String, version: String) -> Result<()> {
+    let spec = format!("{}=={}", package_name, version);
+    run_tool(
+        "UV",
+        "uv",
+        "install",
+        &["tool", "install", "--force", &spec],
+        &package_name,
+    )
+    .await?;
+    unpin_uv_tool(&package_name).await.map_err(|e| {
+        anyhow!(
+            "{} is back at {} but uv keeps it pinned there ({}); `uv tool install --force {}` follows releases again",
+            package_name,
+            version,
+            e,
+            package_name
+        )
+    })
+}
+
+/// Remove the version specifier from the tool's uv-receipt.toml
+async fn unpin_uv_tool(package_name: &str) -> Result<()> {
+    if !current_target().is_local() {
+        return Err(anyhow!("the receipt is on {}", current_target().label()));
+    }
+    let output = run_command_with_timeout("uv", &["tool", "dir"], Duration::from_secs(10)).await?;
+    let dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    let receipt = dir.join(package_name).join("uv-receipt.toml");
+    let content = std::fs::read_to_string(&receipt)
+        .map_err(|e| anyhow!("Failed to read {}: {}", receipt.display(), e))?;
+    let unpinned = unpin_receipt(&content, package_name)?;
+    std::fs::write(&receipt, unpinned)
+        .map_err(|e| anyhow!("Failed to write {}: {}", receipt.display(), e))
+}
+
+/// The receipt with the package's requirement left without a specifier
+fn unpin_receipt(content: &str, package_name: &str) -> Result<String> {
+    let mut receipt: toml_edit::DocumentMut = content.parse()?;
+    let requirements = receipt["tool"]["requirements"]
+        .as_array_mut()
+        .ok_or_else(|| anyhow!("no requirements in the receipt"))?;
+    for requirement in requirements
+        .iter_mut()
+        .filter_map(|r| r.as_inline_table_mut())
+    {
+        let is_package = requirement
+            .get("name")
+            .and_then(|name| name.as_str())
+            .is_some_and(|name| normalize_name(name) == normalize_name(package_name));
+        if is_package {
+            requirement.remove("specifier");
+            requirement.fmt();
+        }
+    }
+    Ok(receipt.to_string())
+}
+
+// Poetry
+
+/// Plugins added to Poetry's own environment with `poetry self add`
+pub async fn list_poetry_plugins() -> Result<Vec<Package>> {
+    println!("[POETRY] Listing plugins");
+
+    let output = run_command_with_timeout(
+        "poetry",
+        &["self", "show", "plugins", "--no-ansi"],
+        Duration::from_secs(30),
+    )
+    .await?;
+
+    if !output.status.success() {
+        return Err(anyhow!("poetry self show plugins failed"));
+    }
+
+    let packages = parse_poetry_plugins(&String::from_utf8_lossy(&output.stdout));
+    println!("[POETRY] Found {} plugins", packages.len());
+    Ok(packages)
+}
+
+/// "  - poetry-plugin-export (1.8.0) Poetry plugin to export..." per plugin (Poetry 1.2+
+/// draws a bullet instead of the dash); its dependencies are listed further indented
+fn parse_poetry_plugins(stdout: &str) -> Vec<Package> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let entry = line
+                .strip_prefix("  - ")
+                .or_else(|| line.strip_prefix("  • "))?;
+            let (name, rest) = entry.split_once(" (")?;
+            let (version, description) = rest.split_once(')')?;
+            let mut pkg = Package::new(name.trim(), PackageManager::Poetry, version.trim());
+            let description = description.trim();
+            if !description.is_empty() {
+                pkg.description = Some(description.to_string());
+            }
+            Some(pkg)
+        })
+        .collect()
+}
+
+pub async fn update_poetry_plugin(package_name: String) -> Result<()> {
+    let spec = format!("{}@latest", package_name);
+    run_tool(
+        "POETRY",
+        "poetry",
+        "upgrade",
+        &["self", "add", &spec],
+        &package_name,
+    )
+    .await
+}
+
+pub async fn uninstall_poetry_plugin(package_name: String) -> Result<()> {
+    run_tool(
+        "POETRY",
+        "poetry",
+        "uninstall",
+        &["self", "remove", &package_name],
+        &package_name,
+    )
+    .await
+}
+
+pub async fn install_poetry_plugin(package_name: String) -> Result<()> {
+    run_tool(
+        "POETRY",
+        "poetry",
+        "install",
+        &["self", "add", &package_name],
+        &package_name,
+    )
+    .await
+}
+
+/// Pin the plugin to an exact version (used for rollbacks)
+pub async fn install_poetry_plugin_version(package_name: String, version: String) -> Result<()> {
+    let spec = format!("{}=={}", package_name, version);
+    run_tool(
+        "POETRY",
+        "poetry",
+        "install",
+        &["self", "add", &spec],
+        &package_name,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::executor::{replay_fixture, with_executor};
+
+    fn summary(packages: &[Package]) -> Vec<(&str, &str, Option<&str>)> {
+        packages
+            .iter()
+            .map(|p| {
+                (
+                    p.name.as_str(),
+                    p.installed_version.as_str(),
+                    p.description.as_deref(),
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn uv_tools_and_poetry_plugins() {
+        let (tools, plugins) = with_executor(replay_fixture("python_tools"), async {
+            Ok::<_, anyhow::Error>((list_uv_tools().await?, list_poetry_plugins().await?))
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            summary(&tools),
+            [
+                ("black", "24.1.1", Some("Provides black, blackd")),
+                ("ruff", "0.4.4", Some("Provides ruff")),
+            ]
+        );
+        assert_eq!(
+            summary(&plugins),
+            [
+                (
+                    "poetry-plugin-export",
+                    "1.8.0",
+                    Some("Poetry plugin to export the dependencies to various formats")
+                ),
+                ("poetry-dynamic-versioning", "1.4.0", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn rollback_pin_is_dropped_from_the_receipt() {
+        let receipt = "[tool]\nrequirements = [{ name = \"Black\", specifier = \"==24.1.0\" }, { name = \"click\", specifier = \">=8\" }]\nentrypoints = [{ name = \"black\", install-path = \"/home/me/.local/bin/black\" }]\n";
+        let unpinned = unpin_receipt(receipt, "black").unwrap();
+        assert_eq!(
+            unpinned,
+            "[tool]\nrequirements = [{ name = \"Black\" }, { name = \"click\", specifier = \">=8\" }]\nentrypoints = [{ name = \"black\", install-path = \"/home/me/.local/bin/black\" }]\n"
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use crate::models::{Package, PackageManager};
+use crate::utils::run_command_with_timeout;
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+/// Shared preamble: the user library (falling back to the first library path)
+/// and a CRAN mirror when none is configured
+const SETUP: &str = r#"
+lib <- path.expand(Sys.getenv("R_LIBS_USER"))
+if (!nzchar(lib) || !dir.exists(lib)) lib <- .libPaths()[1]
+repos <- getOption("repos")
+if (is.null(repos) || isTRUE(repos["CRAN"] == "@CRAN@")) repos <- c(CRAN = "https://cloud.r-project.org")
+"#;
+
+/// Prints "name\tinstalled\tlatest" for every package in the user library
+const LIST_SCRIPT: &str = r#"
+ip <- installed.packages(lib.loc = lib)
+old <- tryCatch(old.packages(lib.loc = lib, repos = repos), error = function(e) NULL)
+for (p in rownames(ip)) {
+  latest <- if (!is.null(old) && p %in% rownames(old)) old[p, "ReposVer"] else ""
+  cat(p, ip[p, "Version"], latest, sep = "\t")
+  cat("\n")
+}
+"#;
+
+/// Package names are spliced into R code; CRAN names are letters, digits and dots
+static NAME_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[A-Za-z][A-Za-z0-9.]*$").unwrap());
+
+/// List packages in the R user library, with CRAN versions for outdated checks
+pub async fn list_r_packages() -> Result<Vec<Package>> {
+    println!("[R] Listing user library packages");
+
+    let script = format!("{}{}", SETUP, LIST_SCRIPT);
+    let output =
+        run_command_with_timeout("Rscript", &["-e", &script], Duration::from_secs(120)).await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("Rscript listing failed: {}", stderr));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut packages = Vec::new();
+    for line in stdout.lines() {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [name, version, latest] = fields.as_slice() else {
+            continue;
+        };
+        let mut pkg = Package::new(*name, PackageManager::R, *version);
+        if !latest.is_empty() {
+            pkg.latest_version = Some(latest.to_string());
+            pkg.is_outdated = true; // old.packages only reports newer versions
+        }
+        packages.push(pkg);
+    }
+
+    println!("[R] Found {} packages", packages.len());
+    Ok(packages)
+}
+
+async fn run_r(action: &str, code: String, package_name: &str) -> Result<()> {
+    if !NAME_RE.is_match(package_name) {
+        return Err(anyhow!("Invalid R package name: {}", package_name));
+    }
+    println!("[R] {}: {}", action, package_name);
+
+    let script = format!("{}{}", SETUP, code);
+    let output =
+        run_command_with_timeout("Rscript", &["-e", &script], Duration::from_secs(900)).await?;
+
+    if output.status.success() {
+        println!("[R] Successfully finished {}: {}", action, package_name);
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to {} {}: {}", action, package_name, stderr))
+    }
+}
+
+/// Reinstalling from CRAN is how R updates a single package
+pub async fn update_r_package(package_name: String) -> Result<()> {
+    let code = format!(
+        "install.packages(\"{}\", lib = lib, repos = repos)",
+        package_name
+    );
+    run_r("update", code, &package_name).await
+}
+
+pub async fn install_r_package(package_name: String) -> Result<()> {
+    let code = format!(
+        "install.packages(\"{}\", lib = lib, repos = repos)",
+        package_name
+    );
+    run_r("install", code, &package_name).await
+}
+
+pub async fn uninstall_r_package(package_name: String) -> Result<()> {
+    let code = format!("remove.packages(\"{}\", lib = lib)", package_name);
+    run_r("remove", code, &package_name).await
+}
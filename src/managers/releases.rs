@@ -0,0 +1,107 @@
+use crate::models::PackageManager;
+use crate::utils::http_client::create_http_client;
+use crate::utils::metrics::SendTimed;
+use crate::utils::registries::{crates_url, npm_registry, pypi_url};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Managers whose registries say when each version was published
+pub fn has_release_dates(manager: &PackageManager) -> bool {
+    matches!(
+        manager,
+        PackageManager::Npm | PackageManager::Cargo | PackageManager::Pip
+    )
+}
+
+/// When `version` was published, from the registry's package document
+fn release_date(
+    manager: &PackageManager,
+    json: &serde_json::Value,
+    version: &str,
+) -> Option<DateTime<Utc>> {
+    let published = match manager {
+        PackageManager::Npm => json["time"][version].as_str(),
+        PackageManager::Cargo => json["versions"]
+            .as_array()?
+            .iter()
+            .find(|v| v["num"] == version)?["created_at"]
+            .as_str(),
+        PackageManager::Pip => json["releases"][version][0]["upload_time_iso_8601"].as_str(),
+        _ => None,
+    }?;
+    DateTime::parse_from_rfc3339(published)
+        .ok()
+        .map(|d| d.with_timezone(&Utc))
+}
+
+/// When a package's installed and latest versions were published
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReleaseDates {
+    pub installed: Option<DateTime<Utc>>,
+    pub latest: Option<DateTime<Utc>>,
+}
+
+/// Publish dates of the installed and latest versions, from one registry request
+pub async fn fetch_release_dates(
+    manager: &PackageManager,
+    name: &str,
+    installed: &str,
+    latest: Option<&str>,
+) -> Option<ReleaseDates> {
+    let url = match manager {
+        // Scoped names keep their "@" but escape the slash
+        PackageManager::Npm => format!("{}/{}", npm_registry(name), name.replace('/', "%2f")),
+        PackageManager::Cargo => crates_url(name).await,
+        PackageManager::Pip => pypi_url(name),
+        _ => return None,
+    };
+    let response = create_http_client()
+        .get(&url)
+        // crates.io rejects requests without a User-Agent
+        .header("User-Agent", "depmgr")
+        .send_timed()
+        .await
+        .ok()?;
+    if !response.status().is_success() {
+        eprintln!("[RELEASES] {} returned {}", url, response.status());
+        return None;
+    }
+    let json: serde_json::Value = response.json().await.ok()?;
+    Some(ReleaseDates {
+        installed: release_date(manager, &json, installed),
+        latest: latest.and_then(|version| release_date(manager, &json, version)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates_from_each_registry() {
+        let npm = serde_json::json!({"time": {"1.2.0": "2024-03-01T10:00:00.000Z"}});
+        let cargo = serde_json::json!({"versions": [
+            {"num": "1.2.1", "created_at": "2024-04-01T00:00:00.123456+00:00"},
+            {"num": "1.2.0", "created_at": "2024-03-01T00:00:00.123456+00:00"}
+        ]});
+        let pypi = serde_json::json!({"releases": {"1.2.0": [
+            {"upload_time_iso_8601": "2024-03-01T09:30:00.000000Z"}
+        ]}});
+
+        let day = |d: Option<DateTime<Utc>>| d.map(|d| d.format("%Y-%m-%d").to_string());
+        let expected = Some("2024-03-01".to_string());
+        assert_eq!(
+            day(release_date(&PackageManager::Npm, &npm, "1.2.0")),
+            expected
+        );
+        assert_eq!(
+            day(release_date(&PackageManager::Cargo, &cargo, "1.2.0")),
+            expected
+        );
+        assert_eq!(
+            day(release_date(&PackageManager::Pip, &pypi, "1.2.0")),
+            expected
+        );
+        assert_eq!(release_date(&PackageManager::Npm, &npm, "9.9.9"), None);
+    }
+}
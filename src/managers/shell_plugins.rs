@@ -0,0 +1,299 @@
+use crate::models::{Package, PackageManager};
+use crate::utils::run_command_with_timeout;
+use crate::utils::version::{compare_versions, highest_tag};
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use std::cmp::Ordering;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+// Plugin names end up inside shell scripts
+static NAME_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[A-Za-z0-9_.@/~-]+$").unwrap());
+
+/// Each line: "kind<TAB>name<TAB>dir<TAB>HEAD sha" for git checkouts that are plugins/themes
+const OMZ_LIST: &str = r#"
+ZSH_DIR="${ZSH:-$HOME/.oh-my-zsh}"
+CUSTOM="${ZSH_CUSTOM:-$ZSH_DIR/custom}"
+entry() { [ -d "$3/.git" ] && printf '%s\t%s\t%s\t%s\n' "$1" "$2" "$3" "$(git -C "$3" rev-parse HEAD 2>/dev/null)"; }
+entry framework oh-my-zsh "$ZSH_DIR"
+for d in "$CUSTOM"/plugins/*; do entry plugin "${d##*/}" "$d"; done
+for d in "$CUSTOM"/themes/*; do entry theme "${d##*/}" "$d"; done
+true
+"#;
+
+/// zinit keeps "user/repo" plugins (and themes) as "user---repo" clones
+const ZINIT_LIST: &str = r#"
+Z="${XDG_DATA_HOME:-$HOME/.local/share}/zinit"
+[ -d "$Z" ] || Z="$HOME/.zinit"
+entry() { [ -d "$3/.git" ] && printf '%s\t%s\t%s\t%s\n' "$1" "$2" "$3" "$(git -C "$3" rev-parse HEAD 2>/dev/null)"; }
+entry framework zinit "$Z/zinit.git"
+for d in "$Z"/plugins/*; do entry plugin "${d##*/}" "$d"; done
+true
+"#;
+
+const ZINIT_PLUGINS_DIR: &str = r#"${XDG_DATA_HOME:-$HOME/.local/share}/zinit/plugins"#;
+
+struct Checkout {
+    kind: String,
+    name: String,
+    dir: String,
+    head: String,
+}
+
+async fn sh(script: &str, timeout: Duration) -> Result<String> {
+    let output = run_command_with_timeout("sh", &["-c", script], timeout).await?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Shell command failed: {}", stderr.trim()))
+    }
+}
+
+async fn fish(command: &str, timeout: Duration) -> Result<String> {
+    let output = run_command_with_timeout("fish", &["-c", command], timeout).await?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("fish -c '{}' failed: {}", command, stderr.trim()))
+    }
+}
+
+/// oh-my-zsh and zinit are sourced by zsh and fisher is a fish function,
+/// so there's no binary for the detector to find
+pub async fn is_installed(manager: &PackageManager) -> bool {
+    let check = match manager {
+        PackageManager::OhMyZsh => sh(
+            r#"[ -d "${ZSH:-$HOME/.oh-my-zsh}/.git" ]"#,
+            Duration::from_secs(5),
+        )
+        .await
+        .map(|_| ()),
+        PackageManager::Zinit => sh(
+            r#"[ -d "${XDG_DATA_HOME:-$HOME/.local/share}/zinit" ] || [ -d "$HOME/.zinit" ]"#,
+            Duration::from_secs(5),
+        )
+        .await
+        .map(|_| ()),
+        PackageManager::Fisher => fish("functions -q fisher", Duration::from_secs(10))
+            .await
+            .map(|_| ()),
+        _ => return false,
+    };
+    check.is_ok()
+}
+
+async fn list_checkouts(manager: &PackageManager) -> Result<Vec<Checkout>> {
+    let script = match manager {
+        PackageManager::OhMyZsh => OMZ_LIST,
+        PackageManager::Zinit => ZINIT_LIST,
+        _ => return Err(anyhow!("{} doesn't keep git checkouts", manager.name())),
+    };
+    let stdout = sh(script, Duration::from_secs(30)).await?;
+
+    Ok(stdout
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let kind = fields.next()?.to_string();
+            let name = fields.next()?;
+            let dir = fields.next()?.to_string();
+            let head = fields.next().unwrap_or("").to_string();
+            // zinit's own snippets/local plugins start with "_local"
+            if name.starts_with("_local") {
+                return None;
+            }
+            Some(Checkout {
+                kind,
+                name: name.replacen("---", "/", 1),
+                dir,
+                head,
+            })
+        })
+        .collect())
+}
+
+fn short(sha: &str) -> String {
+    sha.chars().take(7).collect()
+}
+
+/// oh-my-zsh/zinit plugins and themes, compared against their upstream HEAD commit
+pub async fn list_checkout_plugins(manager: &PackageManager) -> Result<Vec<Package>> {
+    println!("[SHELL] Listing {} plugins", manager.name());
+
+    let checkouts = list_checkouts(manager).await?;
+    let remotes = futures::future::join_all(checkouts.iter().map(|c| async move {
+        run_command_with_timeout(
+            "git",
+            &["-C", &c.dir, "ls-remote", "origin", "HEAD"],
+            Duration::from_secs(30),
+        )
+        .await
+    }))
+    .await;
+
+    let packages: Vec<Package> = checkouts
+        .into_iter()
+        .zip(remotes)
+        .map(|(checkout, remote)| {
+            let mut pkg = Package::new(&checkout.name, manager.clone(), short(&checkout.head));
+            pkg.description = Some(format!("{} ({})", checkout.kind, checkout.dir));
+
+            let remote_head = remote.ok().filter(|o| o.status.success()).and_then(|o| {
+                String::from_utf8_lossy(&o.stdout)
+                    .split_whitespace()
+                    .next()
+                    .map(str::to_string)
+            });
+            if let Some(remote_head) = remote_head {
+                pkg.is_outdated = !checkout.head.is_empty() && remote_head != checkout.head;
+                pkg.latest_version = Some(short(&remote_head));
+            }
+            pkg
+        })
+        .collect();
+
+    println!(
+        "[SHELL] Found {} {} plugins",
+        packages.len(),
+        manager.name()
+    );
+    Ok(packages)
+}
+
+async fn find_checkout(manager: &PackageManager, name: &str) -> Result<Checkout> {
+    list_checkouts(manager)
+        .await?
+        .into_iter()
+        .find(|c| c.name == name)
+        .ok_or_else(|| anyhow!("{} plugin {} not found", manager.name(), name))
+}
+
+pub async fn update_checkout_plugin(manager: &PackageManager, name: String) -> Result<()> {
+    let checkout = find_checkout(manager, &name).await?;
+    println!("[SHELL] Pulling {} in {}", name, checkout.dir);
+
+    let output = run_command_with_timeout(
+        "git",
+        &["-C", &checkout.dir, "pull", "--ff-only"],
+        Duration::from_secs(120),
+    )
+    .await?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to update {}: {}", name, stderr))
+    }
+}
+
+/// Plugins are just directories; the framework itself has its own uninstaller
+pub async fn uninstall_checkout_plugin(manager: &PackageManager, name: String) -> Result<()> {
+    let checkout = find_checkout(manager, &name).await?;
+    if checkout.kind == "framework" {
+        return Err(anyhow!(
+            "Use {}'s own uninstaller to remove it",
+            manager.name()
+        ));
+    }
+    println!("[SHELL] Removing {}", checkout.dir);
+
+    let output =
+        run_command_with_timeout("rm", &["-rf", &checkout.dir], Duration::from_secs(60)).await?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to remove {}: {}", name, stderr))
+    }
+}
+
+/// zinit names are GitHub "user/repo", so they can be cloned back
+pub async fn install_zinit_plugin(name: String) -> Result<()> {
+    if !NAME_RE.is_match(&name) || name.matches('/').count() != 1 || name.contains("..") {
+        return Err(anyhow!("zinit plugins install from a GitHub user/repo"));
+    }
+    println!("[SHELL] Cloning zinit plugin {}", name);
+
+    let script = format!(
+        r#"git clone --depth 1 "https://github.com/{name}" "{dir}/{local}""#,
+        name = name,
+        dir = ZINIT_PLUGINS_DIR,
+        local = name.replacen('/', "---", 1)
+    );
+    sh(&script, Duration::from_secs(300)).await?;
+    Ok(())
+}
+
+/// `fisher list`: "jorgebucaran/fisher", "ilancosman/tide@v6", or a local path.
+/// Fisher doesn't record which commit it downloaded, so only "@tag" pins can be
+/// checked against upstream.
+pub async fn list_fisher_plugins() -> Result<Vec<Package>> {
+    println!("[SHELL] Listing fisher plugins");
+
+    let stdout = fish("fisher list", Duration::from_secs(30)).await?;
+    let mut packages: Vec<Package> = stdout
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|line| {
+            let version = line.split_once('@').map(|(_, pin)| pin).unwrap_or("-");
+            Package::new(line, PackageManager::Fisher, version)
+        })
+        .collect();
+
+    let checks = futures::future::join_all(packages.iter().map(|p| async move {
+        let repo = p.name.split('@').next().unwrap_or(&p.name);
+        if p.installed_version == "-" || repo.starts_with(['/', '~', '.']) {
+            return None;
+        }
+        let url = format!("https://github.com/{}", repo);
+        let output = run_command_with_timeout(
+            "git",
+            &["ls-remote", "--tags", "--refs", &url],
+            Duration::from_secs(30),
+        )
+        .await
+        .ok()?;
+        let refs = String::from_utf8_lossy(&output.stdout).to_string();
+        highest_tag(refs.lines().filter_map(|l| l.split("refs/tags/").nth(1)))
+    }))
+    .await;
+
+    for (pkg, latest) in packages.iter_mut().zip(checks) {
+        if let Some(latest) = latest {
+            pkg.is_outdated =
+                compare_versions(&latest, &pkg.installed_version) == Ordering::Greater;
+            pkg.latest_version = Some(latest);
+        }
+    }
+
+    println!("[SHELL] Found {} fisher plugins", packages.len());
+    Ok(packages)
+}
+
+async fn run_fisher(action: &str, name: &str) -> Result<()> {
+    if !NAME_RE.is_match(name) {
+        return Err(anyhow!("Invalid fisher plugin name: {}", name));
+    }
+    println!("[SHELL] fisher {} {}", action, name);
+    fish(
+        &format!("fisher {} {}", action, name),
+        Duration::from_secs(300),
+    )
+    .await?;
+    Ok(())
+}
+
+pub async fn update_fisher_plugin(name: String) -> Result<()> {
+    run_fisher("update", &name).await
+}
+
+pub async fn install_fisher_plugin(name: String) -> Result<()> {
+    run_fisher("install", &name).await
+}
+
+pub async fn uninstall_fisher_plugin(name: String) -> Result<()> {
+    run_fisher("remove", &name).await
+}
@@ -0,0 +1,196 @@
+use crate::models::{Package, PackageManager, Project};
+use crate::utils::run_command_with_timeout;
+use crate::utils::version::{compare_versions, highest_tag};
+use anyhow::{anyhow, Result};
+use futures::{stream, StreamExt};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+const SWIFT_REPO: &str = "https://github.com/swiftlang/swift";
+
+async fn sh(script: &str, timeout: Duration) -> Result<String> {
+    let output = run_command_with_timeout("sh", &["-c", script], timeout).await?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Shell command failed: {}", stderr.trim()))
+    }
+}
+
+/// "Apple Swift version 6.0.3 (swiftlang-...)" / "Swift version 5.10.1 (swift-5.10.1-RELEASE)"
+fn parse_swift_version(output: &str) -> Option<String> {
+    let rest = output.split("Swift version ").nth(1)?;
+    rest.split_whitespace().next().map(|v| v.to_string())
+}
+
+/// "swift-6.0.3-RELEASE.xctoolchain" -> "6.0.3"; snapshots have no release version
+fn toolchain_version(bundle: &str) -> Option<String> {
+    bundle
+        .trim_end_matches(".xctoolchain")
+        .strip_prefix("swift-")?
+        .strip_suffix("-RELEASE")
+        .map(|v| v.to_string())
+}
+
+/// Newest Swift release according to the compiler repository's tags
+async fn latest_swift_release() -> Option<String> {
+    let output = run_command_with_timeout(
+        "git",
+        &["ls-remote", "--tags", "--refs", SWIFT_REPO],
+        Duration::from_secs(60),
+    )
+    .await
+    .ok()?;
+    let refs = String::from_utf8_lossy(&output.stdout).to_string();
+    highest_tag(
+        refs.lines()
+            .filter_map(|l| l.split("refs/tags/swift-").nth(1)?.strip_suffix("-RELEASE")),
+    )
+}
+
+/// Newest tag of a package's git repository
+async fn latest_tag(url: &str) -> Option<String> {
+    let output = run_command_with_timeout(
+        "git",
+        &["ls-remote", "--tags", "--refs", url],
+        Duration::from_secs(30),
+    )
+    .await
+    .ok()?;
+    let refs = String::from_utf8_lossy(&output.stdout).to_string();
+    highest_tag(refs.lines().filter_map(|l| l.split("refs/tags/").nth(1)))
+}
+
+/// The active Swift toolchain, installed .xctoolchain bundles, and SPM tools
+/// (`swift package experimental-install` into ~/.swiftpm/bin, and Mint)
+pub async fn list_swift_packages() -> Result<Vec<Package>> {
+    println!("[SWIFT] Listing toolchains and tools");
+
+    let output = run_command_with_timeout("swift", &["--version"], Duration::from_secs(30)).await?;
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let version =
+        parse_swift_version(&text).ok_or_else(|| anyhow!("Unrecognized swift --version"))?;
+    let mut packages = vec![Package::new("swift", PackageManager::Swift, version)];
+
+    let toolchains = sh(
+        "ls -1 /Library/Developer/Toolchains ~/Library/Developer/Toolchains 2>/dev/null; true",
+        Duration::from_secs(10),
+    )
+    .await
+    .unwrap_or_default();
+    for bundle in toolchains.lines().filter(|l| l.ends_with(".xctoolchain")) {
+        let version = toolchain_version(bundle).unwrap_or_else(|| "snapshot".to_string());
+        packages.push(Package::new(
+            bundle.trim_end_matches(".xctoolchain"),
+            PackageManager::Swift,
+            version,
+        ));
+    }
+
+    let tools = sh(
+        "ls -1 ~/.swiftpm/bin 2>/dev/null; true",
+        Duration::from_secs(10),
+    )
+    .await
+    .unwrap_or_default();
+    for tool in tools.lines().filter(|l| !l.is_empty()) {
+        packages.push(Package::new(tool, PackageManager::Swift, "-"));
+    }
+
+    // "  SwiftLint" followed by "    - 0.54.0" per installed version
+    if let Ok(mint) = sh("mint list 2>/dev/null", Duration::from_secs(30)).await {
+        let mut current: Option<String> = None;
+        for line in mint.lines() {
+            if let Some(version) = line.trim().strip_prefix("- ") {
+                if let Some(name) = &current {
+                    let version = version.trim_end_matches(" *").trim();
+                    packages.push(Package::new(name.clone(), PackageManager::Swift, version));
+                }
+            } else if line.starts_with("  ") {
+                current = Some(line.trim().to_string());
+            }
+        }
+    }
+
+    println!("[SWIFT] Found {} toolchains and tools", packages.len());
+    Ok(packages)
+}
+
+/// Compare the active toolchain and release toolchains against the newest Swift release
+pub async fn check_outdated_swift(packages: &mut [Package]) -> Result<()> {
+    println!("[SWIFT] Checking the latest Swift release");
+
+    let Some(latest) = latest_swift_release().await else {
+        return Ok(());
+    };
+    for pkg in packages.iter_mut() {
+        if pkg.name == "swift" || pkg.name.ends_with("-RELEASE") {
+            pkg.is_outdated =
+                compare_versions(&latest, &pkg.installed_version) == Ordering::Greater;
+            pkg.latest_version = Some(latest.clone());
+        }
+    }
+    Ok(())
+}
+
+/// Look up the newest tag of every Swift package the projects depend on
+pub async fn check_project_packages(projects: &mut [Project]) {
+    let repos: HashSet<String> = projects
+        .iter()
+        .flat_map(|p| &p.dependencies)
+        .filter(|d| d.manager == PackageManager::Swift)
+        .map(|d| d.package_name.clone())
+        .collect();
+    if repos.is_empty() {
+        return;
+    }
+    println!(
+        "[SWIFT] Checking tags of {} package repositories",
+        repos.len()
+    );
+
+    let latest: HashMap<String, String> = stream::iter(repos)
+        .map(|repo| async move {
+            let latest = latest_tag(&format!("https://{}", repo)).await?;
+            Some((repo, latest))
+        })
+        .buffer_unordered(crate::config::registry_lookups())
+        .filter_map(|found| async move { found })
+        .collect()
+        .await;
+
+    for dep in projects
+        .iter_mut()
+        .flat_map(|p| p.dependencies.iter_mut())
+        .filter(|d| d.manager == PackageManager::Swift)
+    {
+        dep.latest_version = latest.get(&dep.package_name).cloned();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swift_versions() {
+        assert_eq!(
+            parse_swift_version("Apple Swift version 6.0.3 (swiftlang-6.0.3.1.10 clang-1600.0.30.1)\nTarget: arm64-apple-macosx15.0"),
+            Some("6.0.3".to_string())
+        );
+        assert_eq!(
+            toolchain_version("swift-5.10.1-RELEASE.xctoolchain"),
+            Some("5.10.1".to_string())
+        );
+        assert_eq!(
+            toolchain_version("swift-DEVELOPMENT-SNAPSHOT-2024-06-01-a.xctoolchain"),
+            None
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use crate::models::{Package, PackageManager};
+use crate::utils::run_command_with_timeout;
+use anyhow::{anyhow, Result};
+use std::time::Duration;
+
+/// "musl-1.2.4-r2" -> ("musl", "1.2.4-r2"); apk versions always end in "-r<N>"
+fn split_apk_name(full: &str) -> Option<(&str, &str)> {
+    let mut parts = full.rsplitn(3, '-');
+    let release = parts.next()?;
+    let version = parts.next()?;
+    let name = parts.next()?;
+    let version_start = full.len() - release.len() - version.len() - 1;
+    Some((name, &full[version_start..]))
+}
+
+/// List installed Alpine packages
+pub async fn list_apk_packages() -> Result<Vec<Package>> {
+    println!("[APK] Listing installed packages");
+
+    let output = run_command_with_timeout("apk", &["info", "-v"], Duration::from_secs(30)).await?;
+    if !output.status.success() {
+        return Err(anyhow!("apk info failed"));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let packages: Vec<Package> = stdout
+        .lines()
+        .filter_map(|line| split_apk_name(line.trim()))
+        .map(|(name, version)| Package::new(name, PackageManager::Apk, version))
+        .collect();
+
+    println!("[APK] Found {} installed packages", packages.len());
+    Ok(packages)
+}
+
+/// Check for upgradable Alpine packages against the image's package index
+pub async fn check_outdated_apk(packages: &mut [Package]) -> Result<()> {
+    println!("[APK] Checking for outdated packages");
+
+    // "curl-8.5.0-r0                < 8.9.0-r0"
+    let output =
+        run_command_with_timeout("apk", &["version", "-l", "<"], Duration::from_secs(60)).await?;
+    if !output.status.success() {
+        return Ok(()); // Not a fatal error
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for line in stdout.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if let [full, "<", latest] = fields.as_slice() {
+            if let Some((name, _)) = split_apk_name(full) {
+                if let Some(pkg) = packages.iter_mut().find(|p| p.name == name) {
+                    pkg.latest_version = Some(latest.to_string());
+                    pkg.is_outdated = true;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// List installed Debian/Ubuntu packages
+pub async fn list_dpkg_packages() -> Result<Vec<Package>> {
+    println!("[APT] Listing installed packages");
+
+    let output = run_command_with_timeout(
+        "dpkg-query",
+        &["-W", "-f=${Package}\t${Version}\n"],
+        Duration::from_secs(30),
+    )
+    .await?;
+    if !output.status.success() {
+        return Err(anyhow!("dpkg-query failed"));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let packages: Vec<Package> = stdout
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(name, version)| Package::new(name, PackageManager::Apt, version))
+        .collect();
+
+    println!("[APT] Found {} installed packages", packages.len());
+    Ok(packages)
+}
+
+/// Check for upgradable Debian packages. Uses whatever package lists the image
+/// shipped with; slim images often have none, in which case nothing is flagged.
+pub async fn check_outdated_apt(packages: &mut [Package]) -> Result<()> {
+    println!("[APT] Checking for outdated packages");
+
+    // "curl/stable 7.88.1-10+deb12u5 amd64 [upgradable from: 7.88.1-10+deb12u4]"
+    let output =
+        run_command_with_timeout("apt", &["list", "--upgradable"], Duration::from_secs(60)).await?;
+    if !output.status.success() {
+        return Ok(()); // Not a fatal error
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for line in stdout.lines().filter(|l| l.contains("[upgradable from")) {
+        let mut fields = line.split_whitespace();
+        let (Some(name), Some(latest)) = (
+            fields.next().and_then(|n| n.split('/').next()),
+            fields.next(),
+        ) else {
+            continue;
+        };
+        if let Some(pkg) = packages.iter_mut().find(|p| p.name == name) {
+            pkg.latest_version = Some(latest.to_string());
+            pkg.is_outdated = true;
+        }
+    }
+
+    Ok(())
+}
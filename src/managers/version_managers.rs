@@ -0,0 +1,383 @@
+use crate::models::{Package, PackageManager};
+use crate::utils::paths::home_dir;
+use crate::utils::run_command_with_timeout;
+use crate::utils::target::current_target;
+use crate::utils::version::is_newer;
+use anyhow::{anyhow, Result};
+use futures::{stream, StreamExt};
+use std::path::PathBuf;
+use std::time::Duration;
+
+// mise
+
+/// Tools mise has installed, one package per tool at its active version, described by
+/// what was requested and where (`20 from ~/.config/mise/config.toml`)
+pub async fn list_mise_tools() -> Result<Vec<Package>> {
+    println!("[MISE] Listing tools");
+
+    let output =
+        run_command_with_timeout("mise", &["ls", "--json"], Duration::from_secs(60)).await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("mise ls failed: {}", stderr.trim()));
+    }
+
+    let packages = parse_mise_ls(&String::from_utf8_lossy(&output.stdout))?;
+    println!("[MISE] Found {} tools", packages.len());
+    Ok(packages)
+}
+
+/// `mise ls --json`: {"node": [{"version", "requested_version", "source": {"path"}, "active"}]}
+fn parse_mise_ls(stdout: &str) -> Result<Vec<Package>> {
+    let json: serde_json::Value = serde_json::from_str(stdout)?;
+    let tools = json
+        .as_object()
+        .ok_or_else(|| anyhow!("mise ls gave no tools"))?;
+
+    Ok(tools
+        .iter()
+        .filter_map(|(tool, versions)| {
+            let versions = versions.as_array()?;
+            // The active version, or the newest installed if none is in use here
+            let chosen = versions
+                .iter()
+                .find(|v| v["active"].as_bool() == Some(true))
+                .or_else(|| versions.last())?;
+            let mut pkg = Package::new(
+                tool.as_str(),
+                PackageManager::Mise,
+                chosen["version"].as_str()?,
+            );
+            let requested = chosen["requested_version"].as_str();
+            let source = chosen["source"]["path"].as_str();
+            pkg.description = match (requested, source) {
+                (Some(requested), Some(source)) => Some(format!("{} from {}", requested, source)),
+                (None, Some(source)) => Some(format!("from {}", source)),
+                _ => None,
+            };
+            Some(pkg)
+        })
+        .collect())
+}
+
+/// `mise outdated --json` reports the newest version each tool's request allows
+pub async fn check_outdated_mise(packages: &mut [Package]) -> Result<()> {
+    println!("[MISE] Checking for outdated tools");
+
+    let output =
+        run_command_with_timeout("mise", &["outdated", "--json"], Duration::from_secs(120)).await?;
+
+    if !output.status.success() {
+        return Ok(()); // Not a fatal error
+    }
+
+    let json: serde_json::Value = serde_json::from_str(&String::from_utf8_lossy(&output.stdout))?;
+    for (tool, info) in json.as_object().into_iter().flatten() {
+        let Some(latest) = info["latest"].as_str() else {
+            continue;
+        };
+        if let Some(pkg) = packages.iter_mut().find(|p| &p.name == tool) {
+            pkg.latest_version = Some(latest.to_string());
+            pkg.is_outdated = is_newer(latest, &pkg.installed_version);
+        }
+    }
+    Ok(())
+}
+
+async fn run_mise(action: &str, args: &[&str], tool: &str) -> Result<()> {
+    println!("[MISE] {}: {}", action, tool);
+
+    let output = run_command_with_timeout("mise", args, Duration::from_secs(600)).await?;
+
+    if output.status.success() {
+        println!("[MISE] Successfully finished {}: {}", action, tool);
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to {} {}: {}", action, tool, stderr.trim()))
+    }
+}
+
+/// Upgrade within the version the config asks for ("20" stays on 20.x)
+pub async fn update_mise_tool(tool: String) -> Result<()> {
+    run_mise("upgrade", &["upgrade", &tool], &tool).await
+}
+
+/// Remove one installed version, leaving the tool's other versions in place
+pub async fn uninstall_mise_tool(tool: String, version: Option<String>) -> Result<()> {
+    let spec = match version {
+        Some(version) => format!("{}@{}", tool, version),
+        None => tool.clone(),
+    };
+    run_mise("uninstall", &["uninstall", &spec], &tool).await
+}
+
+/// Install the latest version and make it the global default
+pub async fn install_mise_tool(tool: String) -> Result<()> {
+    let spec = format!("{}@latest", tool);
+    run_mise("install", &["use", "--global", &spec], &tool).await
+}
+
+// asdf
+
+/// The global tool-versions file asdf reads ($ASDF_TOOL_VERSIONS_FILENAME renames it)
+fn tool_versions_path() -> PathBuf {
+    let file = std::env::var("ASDF_TOOL_VERSIONS_FILENAME")
+        .or_else(|_| std::env::var("ASDF_DEFAULT_TOOL_VERSIONS_FILENAME"))
+        .unwrap_or_else(|_| ".tool-versions".to_string());
+    home_dir().join(file)
+}
+
+/// "nodejs 20.11.0 18.19.0" lines: the first version is the one in use
+fn parse_tool_versions(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some((fields.next()?.to_string(), fields.next()?.to_string()))
+        })
+        .collect()
+}
+
+/// Tools pinned in the global ~/.tool-versions, read from this machine
+pub async fn list_asdf_tools() -> Result<Vec<Package>> {
+    println!("[ASDF] Reading global tool versions");
+
+    if !current_target().is_local() {
+        return Ok(Vec::new());
+    }
+    let path = tool_versions_path();
+    let content = std::fs::read_to_string(&path).unwrap_or_default();
+    let source = format!("from {}", path.display());
+
+    let packages: Vec<Package> = parse_tool_versions(&content)
+        .into_iter()
+        .map(|(tool, version)| {
+            let mut pkg = Package::new(tool, PackageManager::Asdf, version);
+            pkg.description = Some(source.clone());
+            pkg
+        })
+        .collect();
+    println!("[ASDF] Found {} tools", packages.len());
+    Ok(packages)
+}
+
+async fn asdf_latest(tool: &str) -> Option<String> {
+    let output = run_command_with_timeout("asdf", &["latest", tool], Duration::from_secs(60))
+        .await
+        .ok()
+        .filter(|o| o.status.success())?;
+    let latest = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!latest.is_empty()).then_some(latest)
+}
+
+/// `asdf latest` for each tool; "system" and "ref:" pins aren't versions to compare
+pub async fn check_outdated_asdf(packages: &mut [Package]) -> Result<()> {
+    println!("[ASDF] Checking for outdated tools");
+
+    let tools: Vec<(usize, String)> = packages
+        .iter()
+        .enumerate()
+        .filter(|(_, pkg)| {
+            pkg.installed_version != "system" && !pkg.installed_version.starts_with("ref:")
+        })
+        .map(|(index, pkg)| (index, pkg.name.clone()))
+        .collect();
+
+    let results: Vec<Option<(usize, String)>> = stream::iter(tools)
+        .map(|(index, tool)| async move { Some((index, asdf_latest(&tool).await?)) })
+        .buffer_unordered(4)
+        .collect()
+        .await;
+
+    for (index, latest) in results.into_iter().flatten() {
+        let pkg = &mut packages[index];
+        pkg.is_outdated = is_newer(&latest, &pkg.installed_version);
+        pkg.latest_version = Some(latest);
+    }
+    Ok(())
+}
+
+async fn run_asdf(action: &str, args: &[&str], tool: &str) -> Result<()> {
+    println!("[ASDF] {}: {}", action, tool);
+
+    let output = run_command_with_timeout("asdf", args, Duration::from_secs(600)).await?;
+
+    if output.status.success() {
+        println!("[ASDF] Successfully finished {}: {}", action, tool);
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("Failed to {} {}: {}", action, tool, stderr.trim()))
+    }
+}
+
+/// Point the tool's line in ~/.tool-versions at `version` (None removes the line).
+/// Written directly because `asdf global` became `asdf set --home` in 0.16.
+fn set_tool_version(tool: &str, version: Option<&str>) -> Result<()> {
+    let path = tool_versions_path();
+    let content = std::fs::read_to_string(&path).unwrap_or_default();
+    let mut found = false;
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        if line.split_whitespace().next() == Some(tool) {
+            found = true;
+            if let Some(version) = version {
+                lines.push(format!("{} {}", tool, version));
+            }
+        } else {
+            lines.push(line.to_string());
+        }
+    }
+    if !found {
+        if let Some(version) = version {
+            lines.push(format!("{} {}", tool, version));
+        }
+    }
+    std::fs::write(&path, lines.join("\n") + "\n")?;
+    Ok(())
+}
+
+/// Install the latest version and make it the global one
+pub async fn update_asdf_tool(tool: String) -> Result<()> {
+    let latest = asdf_latest(&tool)
+        .await
+        .ok_or_else(|| anyhow!("asdf has no latest version of {}", tool))?;
+    run_asdf("install", &["install", &tool, &latest], &tool).await?;
+    set_tool_version(&tool, Some(&latest))
+}
+
+/// Uninstall the global version and drop the tool from ~/.tool-versions
+pub async fn uninstall_asdf_tool(tool: String) -> Result<()> {
+    let content = std::fs::read_to_string(tool_versions_path()).unwrap_or_default();
+    let version = parse_tool_versions(&content)
+        .into_iter()
+        .find(|(name, _)| name == &tool)
+        .map(|(_, version)| version)
+        .ok_or_else(|| anyhow!("{} isn't in {}", tool, tool_versions_path().display()))?;
+    run_asdf("uninstall", &["uninstall", &tool, &version], &tool).await?;
+    set_tool_version(&tool, None)
+}
+
+// Cross-referencing
+
+/// The Homebrew formula for a tool as mise and asdf plugins name it
+fn homebrew_name(tool: &str) -> &str {
+    match tool {
+        "nodejs" => "node",
+        "golang" => "go",
+        "java" => "openjdk",
+        other => other,
+    }
+}
+
+/// Homebrew tools that mise or asdf also manage count as used by that config, so the
+/// Unused view stops listing them
+pub fn mark_managed_tools(packages: &mut [Package]) {
+    let managed: Vec<(String, String)> = packages
+        .iter()
+        .filter(|p| matches!(p.manager, PackageManager::Mise | PackageManager::Asdf))
+        .map(|p| {
+            let source = p
+                .description
+                .as_deref()
+                .and_then(|d| d.split_once("from ").map(|(_, path)| path.to_string()))
+                .unwrap_or_else(|| p.manager.name().to_string());
+            (homebrew_name(&p.name).to_string(), source)
+        })
+        .collect();
+
+    let mut marked = 0;
+    for pkg in packages
+        .iter_mut()
+        .filter(|p| p.manager == PackageManager::Homebrew)
+    {
+        for (formula, source) in &managed {
+            // "python" covers python@3.12
+            let matches = pkg.name == *formula || pkg.name.starts_with(&format!("{}@", formula));
+            if matches && !pkg.used_in.contains(source) {
+                pkg.used_in.push(source.clone());
+                marked += 1;
+            }
+        }
+    }
+    println!(
+        "[DEBUG] {} Homebrew tools are also managed by mise/asdf",
+        marked
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::executor::{replay_fixture, with_executor};
+
+    #[tokio::test]
+    async fn mise_tools_and_their_upgrades() {
+        let packages = with_executor(replay_fixture("mise"), async {
+            let mut packages = list_mise_tools().await?;
+            check_outdated_mise(&mut packages).await?;
+            Ok::<_, anyhow::Error>(packages)
+        })
+        .await
+        .unwrap();
+
+        let summary: Vec<(&str, &str, Option<&str>, Option<&str>)> = packages
+            .iter()
+            .map(|p| {
+                (
+                    p.name.as_str(),
+                    p.installed_version.as_str(),
+                    p.latest_version.as_deref(),
+                    p.description.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (
+                    "node",
+                    "20.11.0",
+                    Some("20.12.2"),
+                    Some("20 from /home/me/.config/mise/config.toml")
+                ),
+                (
+                    "terraform",
+                    "1.8.2",
+                    None,
+                    Some("latest from /home/me/src/infra/.tool-versions")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn homebrew_tools_managed_elsewhere_are_used() {
+        let content = "nodejs 20.11.0 18.19.0\n# comment\npython 3.12.4  # global\n\n";
+        assert_eq!(
+            parse_tool_versions(content),
+            [
+                ("nodejs".to_string(), "20.11.0".to_string()),
+                ("python".to_string(), "3.12.4".to_string()),
+            ]
+        );
+
+        let mut packages = vec![
+            Package::new("node", PackageManager::Homebrew, "21.7.0"),
+            Package::new("python@3.12", PackageManager::Homebrew, "3.12.4"),
+            Package::new("jq", PackageManager::Homebrew, "1.7.1"),
+            Package::new("nodejs", PackageManager::Asdf, "20.11.0"),
+            Package {
+                description: Some("3.12 from /home/me/.config/mise/config.toml".to_string()),
+                ..Package::new("python", PackageManager::Mise, "3.12.4")
+            },
+        ];
+        mark_managed_tools(&mut packages);
+        assert_eq!(packages[0].used_in, ["asdf"]);
+        assert_eq!(packages[1].used_in, ["/home/me/.config/mise/config.toml"]);
+        assert!(packages[2].used_in.is_empty());
+    }
+}
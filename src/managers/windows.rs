@@ -0,0 +1,449 @@
+use crate::models::{Package, PackageManager};
+use crate::utils::run_command_with_timeout;
+use anyhow::{anyhow, Result};
+use std::time::Duration;
+
+/// Rows of a fixed-width table as winget and Scoop print them: a header naming
+/// `columns`, a line of dashes, then one row per line. Each cell runs from its
+/// header's position to the next one's, so values may contain spaces.
+fn parse_table(stdout: &str, columns: &[&str]) -> Vec<Vec<String>> {
+    // winget draws a spinner with carriage returns before the table
+    let lines: Vec<&str> = stdout
+        .lines()
+        .map(|line| line.rsplit('\r').next().unwrap_or(line))
+        .collect();
+    let Some(header_index) = lines
+        .iter()
+        .position(|line| columns.iter().all(|column| line.contains(column)))
+    else {
+        return Vec::new();
+    };
+    let header_text = lines[header_index];
+    let starts: Vec<usize> = columns
+        .iter()
+        .filter_map(|column| {
+            let byte = header_text.find(column)?;
+            Some(header_text[..byte].chars().count())
+        })
+        .collect();
+    if starts.len() != columns.len() {
+        return Vec::new();
+    }
+
+    lines[header_index + 1..]
+        .iter()
+        .filter(|line| !line.trim().is_empty() && !line.trim_start().starts_with("--"))
+        .map(|line| {
+            let chars: Vec<char> = line.chars().collect();
+            starts
+                .iter()
+                .enumerate()
+                .map(|(i, &start)| {
+                    let end = starts.get(i + 1).copied().unwrap_or(chars.len());
+                    let cell: String = chars
+                        .get(start.min(chars.len())..end.min(chars.len()))
+                        .unwrap_or_default()
+                        .iter()
+                        .collect();
+                    cell.trim().to_string()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+async fn run(tag: &str, cmd: &str, action: &str, args: &[&str], package_name: &str) -> Result<()> {
+    println!("[{}] {}: {}", tag, action, package_name);
+
+    let output = run_command_with_timeout(cmd, args, Duration::from_secs(600)).await?;
+
+    if output.status.success() {
+        println!(
+            "[{}] Successfully finished {}: {}",
+            tag, action, package_name
+        );
+        Ok(())
+    } else {
+        // winget and choco report failures on stdout
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!(
+            "Failed to {} {}: {}",
+            action,
+            package_name,
+            if stderr.trim().is_empty() {
+                stdout.lines().last().unwrap_or_default()
+            } else {
+                stderr.trim()
+            }
+        ))
+    }
+}
+
+// winget
+
+/// Match the Id exactly and never stop to ask about source agreements
+const WINGET_QUIET: [&str; 3] = [
+    "--accept-source-agreements",
+    "--disable-interactivity",
+    "--exact",
+];
+
+/// Packages winget installed or recognizes from its own source, with the available
+/// version where there's an upgrade (one command covers both)
+pub async fn list_winget_packages() -> Result<Vec<Package>> {
+    println!("[WINGET] Listing installed packages");
+
+    let output = run_command_with_timeout(
+        "winget",
+        &[
+            "list",
+            "--source",
+            "winget",
+            "--accept-source-agreements",
+            "--disable-interactivity",
+        ],
+        Duration::from_secs(120),
+    )
+    .await?;
+
+    if !output.status.success() {
+        return Err(anyhow!("winget list failed"));
+    }
+
+    let packages = parse_winget_list(&String::from_utf8_lossy(&output.stdout));
+    println!("[WINGET] Found {} installed packages", packages.len());
+    Ok(packages)
+}
+
+/// Packages are named by Id (what the other commands take), described by display name
+fn parse_winget_list(stdout: &str) -> Vec<Package> {
+    parse_table(stdout, &["Name", "Id", "Version", "Available", "Source"])
+        .into_iter()
+        // Ids cut short with "…" on narrow consoles can't be passed back to winget
+        .filter(|row| !row[1].is_empty() && !row[1].ends_with('…'))
+        .map(|row| {
+            let mut pkg = Package::new(row[1].as_str(), PackageManager::Winget, row[2].as_str());
+            pkg.description = Some(row[0].clone()).filter(|name| !name.is_empty());
+            if !row[3].is_empty() {
+                pkg.latest_version = Some(row[3].clone());
+                pkg.is_outdated = true;
+            }
+            pkg
+        })
+        .collect()
+}
+
+async fn run_winget(action: &str, args: &[&str], package_name: &str) -> Result<()> {
+    let mut args = args.to_vec();
+    args.extend(WINGET_QUIET);
+    run("WINGET", "winget", action, &args, package_name).await
+}
+
+pub async fn update_winget_package(package_name: String) -> Result<()> {
+    run_winget(
+        "upgrade",
+        &[
+            "upgrade",
+            "--id",
+            &package_name,
+            "--silent",
+            "--accept-package-agreements",
+        ],
+        &package_name,
+    )
+    .await
+}
+
+pub async fn uninstall_winget_package(package_name: String) -> Result<()> {
+    run_winget(
+        "uninstall",
+        &["uninstall", "--id", &package_name, "--silent"],
+        &package_name,
+    )
+    .await
+}
+
+pub async fn install_winget_package(package_name: String) -> Result<()> {
+    run_winget(
+        "install",
+        &[
+            "install",
+            "--id",
+            &package_name,
+            "--silent",
+            "--accept-package-agreements",
+        ],
+        &package_name,
+    )
+    .await
+}
+
+/// Install an exact version (used for rollbacks)
+pub async fn install_winget_version(package_name: String, version: String) -> Result<()> {
+    run_winget(
+        "install",
+        &[
+            "install",
+            "--id",
+            &package_name,
+            "--version",
+            &version,
+            "--force",
+            "--silent",
+            "--accept-package-agreements",
+        ],
+        &package_name,
+    )
+    .await
+}
+
+// Scoop
+
+/// Apps Scoop installed, from `scoop export` (JSON since Scoop 0.3)
+pub async fn list_scoop_packages() -> Result<Vec<Package>> {
+    println!("[SCOOP] Listing installed apps");
+
+    let output = run_command_with_timeout(
+        PackageManager::Scoop.command(),
+        &["export"],
+        Duration::from_secs(60),
+    )
+    .await?;
+
+    if !output.status.success() {
+        return Err(anyhow!("scoop export failed"));
+    }
+
+    let packages = parse_scoop_export(&String::from_utf8_lossy(&output.stdout))?;
+    println!("[SCOOP] Found {} installed apps", packages.len());
+    Ok(packages)
+}
+
+fn parse_scoop_export(stdout: &str) -> Result<Vec<Package>> {
+    let json: serde_json::Value = serde_json::from_str(stdout)
+        .map_err(|e| anyhow!("scoop export isn't JSON (update Scoop): {}", e))?;
+    Ok(json["apps"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|app| {
+            let name = app["Name"].as_str()?;
+            let version = app["Version"].as_str()?;
+            Some(Package::new(name, PackageManager::Scoop, version))
+        })
+        .collect())
+}
+
+/// `scoop status` lists apps with a newer version in their bucket
+pub async fn check_outdated_scoop(packages: &mut [Package]) -> Result<()> {
+    println!("[SCOOP] Checking for outdated apps");
+
+    let output = run_command_with_timeout(
+        PackageManager::Scoop.command(),
+        &["status"],
+        Duration::from_secs(120),
+    )
+    .await?;
+
+    if !output.status.success() {
+        return Ok(()); // Not a fatal error
+    }
+
+    let rows = parse_table(
+        &String::from_utf8_lossy(&output.stdout),
+        &["Name", "Installed Version", "Latest Version"],
+    );
+    for row in rows {
+        let latest = row[2].as_str();
+        if latest.is_empty() {
+            continue;
+        }
+        if let Some(pkg) = packages.iter_mut().find(|p| p.name == row[0]) {
+            pkg.latest_version = Some(latest.to_string());
+            pkg.is_outdated = true;
+        }
+    }
+    Ok(())
+}
+
+async fn run_scoop(action: &str, args: &[&str], package_name: &str) -> Result<()> {
+    let cmd = PackageManager::Scoop.command();
+    run("SCOOP", cmd, action, args, package_name).await
+}
+
+pub async fn update_scoop_package(package_name: String) -> Result<()> {
+    run_scoop("update", &["update", &package_name], &package_name).await
+}
+
+pub async fn uninstall_scoop_package(package_name: String) -> Result<()> {
+    run_scoop("uninstall", &["uninstall", &package_name], &package_name).await
+}
+
+pub async fn install_scoop_package(package_name: String) -> Result<()> {
+    run_scoop("install", &["install", &package_name], &package_name).await
+}
+
+/// Install an exact version (used for rollbacks); Scoop generates the manifest
+pub async fn install_scoop_version(package_name: String, version: String) -> Result<()> {
+    let spec = format!("{}@{}", package_name, version);
+    run_scoop("install", &["install", &spec], &package_name).await
+}
+
+/// Update Scoop itself and its buckets
+pub async fn self_update_scoop() -> Result<()> {
+    run_scoop("update", &["update"], "Scoop").await
+}
+
+// Chocolatey
+
+/// Whether `choco list` needs `--local-only`: Chocolatey 1.x searches the community feed
+/// without it, and 2.x, which only lists what's installed, rejects the flag
+async fn choco_needs_local_only() -> bool {
+    let Ok(output) =
+        run_command_with_timeout("choco", &["--version"], Duration::from_secs(30)).await
+    else {
+        return false;
+    };
+    let version = String::from_utf8_lossy(&output.stdout);
+    version
+        .trim()
+        .split('.')
+        .next()
+        .and_then(|major| major.parse::<u32>().ok())
+        .is_some_and(|major| major < 2)
+}
+
+/// Packages Chocolatey installed ("name|version" lines)
+pub async fn list_choco_packages() -> Result<Vec<Package>> {
+    println!("[CHOCO] Listing installed packages");
+
+    let mut args = vec!["list", "--limit-output"];
+    if choco_needs_local_only().await {
+        args.push("--local-only");
+    }
+    let output = run_command_with_timeout("choco", &args, Duration::from_secs(60)).await?;
+
+    if !output.status.success() {
+        return Err(anyhow!("choco list failed"));
+    }
+
+    let packages: Vec<Package> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (name, version) = line.trim().split_once('|')?;
+            Some(Package::new(name, PackageManager::Chocolatey, version))
+        })
+        .collect();
+    println!("[CHOCO] Found {} installed packages", packages.len());
+    Ok(packages)
+}
+
+/// `choco outdated` lines: "name|installed|available|pinned"
+pub async fn check_outdated_choco(packages: &mut [Package]) -> Result<()> {
+    println!("[CHOCO] Checking for outdated packages");
+
+    let output = run_command_with_timeout(
+        "choco",
+        &["outdated", "--limit-output"],
+        Duration::from_secs(120),
+    )
+    .await?;
+
+    // Exit code 2 means "some packages are outdated"
+    if !matches!(output.status.code(), Some(0 | 2)) {
+        return Ok(()); // Not a fatal error
+    }
+
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let fields: Vec<&str> = line.trim().split('|').collect();
+        let [name, _installed, available, ..] = fields.as_slice() else {
+            continue;
+        };
+        if let Some(pkg) = packages.iter_mut().find(|p| p.name == *name) {
+            pkg.latest_version = Some(available.to_string());
+            pkg.is_outdated = true;
+        }
+    }
+    Ok(())
+}
+
+async fn run_choco(action: &str, args: &[&str], package_name: &str) -> Result<()> {
+    let mut args = args.to_vec();
+    args.extend(["--yes", "--no-progress"]);
+    run("CHOCO", "choco", action, &args, package_name).await
+}
+
+pub async fn update_choco_package(package_name: String) -> Result<()> {
+    run_choco("upgrade", &["upgrade", &package_name], &package_name).await
+}
+
+pub async fn uninstall_choco_package(package_name: String) -> Result<()> {
+    run_choco("uninstall", &["uninstall", &package_name], &package_name).await
+}
+
+pub async fn install_choco_package(package_name: String) -> Result<()> {
+    run_choco("install", &["install", &package_name], &package_name).await
+}
+
+/// Install an exact version (used for rollbacks)
+pub async fn install_choco_version(package_name: String, version: String) -> Result<()> {
+    run_choco(
+        "install",
+        &[
+            "install",
+            &package_name,
+            "--version",
+            &version,
+            "--allow-downgrade",
+        ],
+        &package_name,
+    )
+    .await
+}
+
+/// Update Chocolatey itself
+pub async fn self_update_choco() -> Result<()> {
+    run_choco("upgrade", &["upgrade", "chocolatey"], "Chocolatey").await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn winget_table_with_upgrades() {
+        let stdout = "   - \r   \\ \r\
+Name                 Id                     Version   Available Source\n\
+----------------------------------------------------------------------\n\
+Git                  Git.Git                2.43.0    2.44.0    winget\n\
+Microsoft Visual St… Microsoft.VisualStudi… 1.87.2              winget\n\
+PowerToys (Preview)  Microsoft.PowerToys    0.79.0              winget\n";
+
+        let packages = parse_winget_list(stdout);
+        let rows: Vec<(&str, &str, Option<&str>, Option<&str>)> = packages
+            .iter()
+            .map(|p| {
+                (
+                    p.name.as_str(),
+                    p.installed_version.as_str(),
+                    p.latest_version.as_deref(),
+                    p.description.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                ("Git.Git", "2.43.0", Some("2.44.0"), Some("Git")),
+                (
+                    "Microsoft.PowerToys",
+                    "0.79.0",
+                    None,
+                    Some("PowerToys (Preview)")
+                ),
+            ]
+        );
+        assert!(packages[0].is_outdated && !packages[1].is_outdated);
+    }
+}
@@ -0,0 +1,201 @@
+use super::{Package, PackageManager};
+use crate::utils::paths::data_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+/// What a package is for, used for the sidebar Category facet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Category {
+    Language,
+    Database,
+    Cli,
+    GuiApp,
+    Font,
+    Library,
+}
+
+impl Category {
+    pub const ALL: [Category; 6] = [
+        Category::Language,
+        Category::Database,
+        Category::Cli,
+        Category::GuiApp,
+        Category::Font,
+        Category::Library,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Category::Language => "Languages",
+            Category::Database => "Databases",
+            Category::Cli => "CLIs",
+            Category::GuiApp => "GUI apps",
+            Category::Font => "Fonts",
+            Category::Library => "Libraries",
+        }
+    }
+}
+
+/// Runtimes and compilers, matched on the formula name without its "@version"
+const LANGUAGES: [&str; 22] = [
+    "node", "python", "ruby", "go", "rust", "openjdk", "java", "kotlin", "scala", "php", "perl",
+    "lua", "luajit", "julia", "r", "ghc", "ocaml", "erlang", "elixir", "deno", "bun", "zig",
+];
+
+const DATABASES: [&str; 8] = [
+    "postgresql",
+    "mysql",
+    "mariadb",
+    "redis",
+    "sqlite",
+    "mongodb-community",
+    "valkey",
+    "duckdb",
+];
+
+fn description_has(pkg: &Package, words: &[&str]) -> bool {
+    let Some(desc) = &pkg.description else {
+        return false;
+    };
+    let desc = desc.to_lowercase();
+    words.iter().any(|w| desc.contains(w))
+}
+
+/// Best guess from the manager, name and registry description
+pub fn classify(pkg: &Package) -> Category {
+    let base = pkg.name.split('@').next().unwrap_or(&pkg.name);
+
+    match pkg.manager {
+        PackageManager::Fonts => return Category::Font,
+        // Language toolchains and installed versions of them
+        PackageManager::Ghcup | PackageManager::Stack | PackageManager::Tfenv => {
+            return Category::Language
+        }
+        // Plugins and extensions are command-line add-ons, and `go install` only installs commands
+        PackageManager::Krew
+        | PackageManager::HelmPlugins
+        | PackageManager::GhExtensions
+        | PackageManager::GitHelpers
+        | PackageManager::OhMyZsh
+        | PackageManager::Zinit
+        | PackageManager::Fisher
+        | PackageManager::Pipx
+        | PackageManager::Go => return Category::Cli,
+        // Packages installed into a language's own environment are libraries
+        // unless the description says otherwise
+        PackageManager::Opam
+        | PackageManager::Gem
+        | PackageManager::LuaRocks
+        | PackageManager::Julia
+        | PackageManager::R
+        | PackageManager::Composer
+        | PackageManager::Pub
+        | PackageManager::Swift
+        | PackageManager::Apk
+        | PackageManager::Apt => {
+            if description_has(pkg, &["command-line", "command line", "cli tool"]) {
+                return Category::Cli;
+            }
+            return Category::Library;
+        }
+        _ => {}
+    }
+
+    if LANGUAGES.contains(&base) || description_has(pkg, &["programming language", "interpreter"]) {
+        Category::Language
+    } else if DATABASES.contains(&base)
+        || description_has(pkg, &["database", "key-value store", "sql server"])
+    {
+        Category::Database
+    } else if description_has(pkg, &["desktop app", "gui", "graphical"]) {
+        Category::GuiApp
+    } else if base.starts_with("lib")
+        || description_has(pkg, &["library", "bindings", "headers", "sdk for"])
+    {
+        Category::Library
+    } else {
+        // Globally installed npm/cargo/pip packages and most formulae are tools
+        Category::Cli
+    }
+}
+
+fn overrides_path() -> PathBuf {
+    data_dir().join("categories.json")
+}
+
+/// Categories the user picked by hand, keyed by `Package::key()`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CategoryOverrides(HashMap<String, Category>);
+
+impl CategoryOverrides {
+    pub fn load() -> Self {
+        std::fs::read_to_string(overrides_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        let path = overrides_path();
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_string_pretty(self) {
+            if let Err(e) = std::fs::write(&path, json) {
+                eprintln!("[ERROR] Failed to write {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    pub fn category_of(&self, pkg: &Package) -> Category {
+        self.0
+            .get(&pkg.key())
+            .copied()
+            .unwrap_or_else(|| classify(pkg))
+    }
+
+    pub fn is_overridden(&self, pkg: &Package) -> bool {
+        self.0.contains_key(&pkg.key())
+    }
+
+    /// Picking the guessed category again drops the override
+    pub fn set(&mut self, pkg: &Package, category: Category) {
+        let key = pkg.key();
+        if classify(pkg) == category {
+            self.0.remove(&key);
+        } else {
+            self.0.insert(key, category);
+        }
+        self.save();
+    }
+
+    /// Three-way merge for syncing machines. Returns the keys both sides changed
+    /// differently since `base`; those keep the local choice.
+    pub fn merge(base: &Self, local: &Self, remote: &Self) -> (Self, Vec<String>) {
+        let keys: HashSet<&String> = local
+            .0
+            .keys()
+            .chain(remote.0.keys())
+            .chain(base.0.keys())
+            .collect();
+        let mut merged = HashMap::new();
+        let mut conflicts = Vec::new();
+        for key in keys {
+            let (b, l, r) = (base.0.get(key), local.0.get(key), remote.0.get(key));
+            let pick = if l == r || r == b {
+                l
+            } else if l == b {
+                r
+            } else {
+                conflicts.push(key.clone());
+                l
+            };
+            if let Some(category) = pick {
+                merged.insert(key.clone(), *category);
+            }
+        }
+        conflicts.sort();
+        (Self(merged), conflicts)
+    }
+}
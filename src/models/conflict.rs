@@ -0,0 +1,81 @@
+use super::{Package, PackageManager};
+use crate::managers::pip::normalize_name;
+
+/// Python distributions that install the same top-level module, so the second one
+/// silently overwrites the first
+const PIP_CONFLICTS: [(&str, &str); 6] = [
+    ("opencv-python", "opencv-python-headless"),
+    ("pycrypto", "pycryptodome"),
+    ("jwt", "pyjwt"),
+    ("bson", "pymongo"),
+    ("psycopg2", "psycopg2-binary"),
+    ("tensorflow", "tensorflow-cpu"),
+];
+
+/// Managers whose packages are mostly commands on PATH
+const COMMAND_MANAGERS: [PackageManager; 5] = [
+    PackageManager::Homebrew,
+    PackageManager::Cargo,
+    PackageManager::Npm,
+    PackageManager::Pipx,
+    PackageManager::Go,
+];
+
+/// An installed package that installing or updating another one is likely to clash with
+#[derive(Debug, Clone)]
+pub struct Conflict {
+    pub name: String,
+    pub manager: PackageManager,
+    pub reason: String,
+}
+
+/// Installed packages (removed ones excluded by the caller) that `pkg` conflicts with:
+/// Homebrew's declared `conflicts_with`, known pip module clashes, and the same command
+/// installed by another manager
+pub fn find(pkg: &Package, installed: &[Package]) -> Vec<Conflict> {
+    let mut conflicts = Vec::new();
+    for other in installed {
+        if other.key() == pkg.key() {
+            continue;
+        }
+        let reason = if other.manager == pkg.manager {
+            match pkg.manager {
+                PackageManager::Homebrew if pkg.conflicts.contains(&other.name) => Some(
+                    "The formula declares they install the same files; brew refuses to link both \
+                     (`brew unlink` one first)"
+                        .to_string(),
+                ),
+                PackageManager::Pip => {
+                    let (a, b) = (normalize_name(&pkg.name), normalize_name(&other.name));
+                    PIP_CONFLICTS
+                        .iter()
+                        .any(|&(x, y)| (a == x && b == y) || (a == y && b == x))
+                        .then(|| {
+                            "Both install the same Python module; whichever is installed last \
+                             overwrites the other's files"
+                                .to_string()
+                        })
+                }
+                _ => None,
+            }
+        } else if other.name == pkg.name
+            && COMMAND_MANAGERS.contains(&pkg.manager)
+            && COMMAND_MANAGERS.contains(&other.manager)
+        {
+            Some(format!(
+                "Both put a `{}` command on PATH; whichever comes first in PATH wins",
+                pkg.name
+            ))
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            conflicts.push(Conflict {
+                name: other.name.clone(),
+                manager: other.manager.clone(),
+                reason,
+            });
+        }
+    }
+    conflicts
+}
@@ -0,0 +1,94 @@
+use super::{Package, PackageManager};
+use std::collections::{HashMap, HashSet};
+
+/// What happened to a package between two scans
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeKind {
+    Installed,
+    /// Gone without DepMgr removing it
+    Removed,
+    VersionChanged {
+        from: String,
+        to: String,
+    },
+    NewlyOutdated {
+        latest: String,
+    },
+}
+
+impl ChangeKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChangeKind::Installed => "newly installed",
+            ChangeKind::Removed => "removed externally",
+            ChangeKind::VersionChanged { .. } => "version changed",
+            ChangeKind::NewlyOutdated { .. } => "newly outdated",
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            ChangeKind::VersionChanged { from, to } => format!("{} ➡ {}", from, to),
+            ChangeKind::NewlyOutdated { latest } => format!("{} available", latest),
+            _ => self.label().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Change {
+    pub manager: PackageManager,
+    pub name: String,
+    pub kind: ChangeKind,
+}
+
+impl Change {
+    pub fn key(&self) -> String {
+        format!("{}/{}", self.manager.name(), self.name)
+    }
+}
+
+/// Differences from `before` to `after`, ignoring managers that `after` didn't list at all
+/// (not scanned this time, or uninstalled)
+pub fn diff(before: &[Package], after: &[Package]) -> Vec<Change> {
+    let old: HashMap<String, &Package> = before.iter().map(|p| (p.key(), p)).collect();
+    let new: HashMap<String, &Package> = after.iter().map(|p| (p.key(), p)).collect();
+    let listed: HashSet<&PackageManager> = after.iter().map(|p| &p.manager).collect();
+    let change = |p: &Package, kind| Change {
+        manager: p.manager.clone(),
+        name: p.name.clone(),
+        kind,
+    };
+
+    let mut changes = Vec::new();
+    for pkg in after {
+        let kind = match old.get(&pkg.key()) {
+            None => Some(ChangeKind::Installed),
+            Some(prev) if prev.installed_version != pkg.installed_version => {
+                Some(ChangeKind::VersionChanged {
+                    from: prev.installed_version.clone(),
+                    to: pkg.installed_version.clone(),
+                })
+            }
+            Some(prev)
+                if pkg.is_outdated
+                    && (!prev.is_outdated || prev.latest_version != pkg.latest_version) =>
+            {
+                pkg.latest_version
+                    .clone()
+                    .map(|latest| ChangeKind::NewlyOutdated { latest })
+            }
+            Some(_) => None,
+        };
+        if let Some(kind) = kind {
+            changes.push(change(pkg, kind));
+        }
+    }
+    for pkg in before {
+        if !new.contains_key(&pkg.key()) && listed.contains(&pkg.manager) {
+            changes.push(change(pkg, ChangeKind::Removed));
+        }
+    }
+    changes.sort_by(|a, b| (a.kind.label(), a.key()).cmp(&(b.kind.label(), b.key())));
+    changes
+}
@@ -0,0 +1,190 @@
+use super::{Package, PackageManager};
+use crate::utils::paths::data_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+/// Triage decisions that outlive a scan, keyed by `Package::key()`.
+/// Pinned packages stay outdated but are never updated by batch actions;
+/// ignored packages stop counting as outdated at all; favorites sort to the top.
+/// Critical packages (databases, VPN clients) are left out of batch updates and need
+/// their name typed before they're updated or removed.
+/// A channel makes the Latest column follow prereleases (an npm dist-tag such as
+/// `next`, crates.io prereleases, Homebrew `HEAD`) instead of stable releases.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PackageFlags {
+    pinned: HashSet<String>,
+    ignored: HashSet<String>,
+    favorites: HashSet<String>,
+    critical: HashSet<String>,
+    channels: HashMap<String, String>,
+}
+
+fn flags_path() -> PathBuf {
+    data_dir().join("package_flags.json")
+}
+
+impl PackageFlags {
+    pub fn load() -> Self {
+        std::fs::read_to_string(flags_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        let path = flags_path();
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_string_pretty(self) {
+            if let Err(e) = std::fs::write(&path, json) {
+                eprintln!("[ERROR] Failed to write {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    pub fn is_pinned(&self, pkg: &Package) -> bool {
+        self.pinned.contains(&pkg.key())
+    }
+
+    pub fn is_ignored(&self, pkg: &Package) -> bool {
+        self.ignored.contains(&pkg.key())
+    }
+
+    pub fn is_favorite(&self, pkg: &Package) -> bool {
+        self.favorites.contains(&pkg.key())
+    }
+
+    pub fn is_critical(&self, pkg: &Package) -> bool {
+        self.critical.contains(&pkg.key())
+    }
+
+    /// Same as `is_critical`, for actions that only know the package by name
+    pub fn is_critical_named(&self, manager: &PackageManager, name: &str) -> bool {
+        self.critical
+            .contains(&format!("{}/{}", manager.name(), name))
+    }
+
+    /// The release channel a package follows, None for stable releases
+    pub fn channel(&self, pkg: &Package) -> Option<&str> {
+        self.channels.get(&pkg.key()).map(String::as_str)
+    }
+
+    /// Same as `channel`, for update commands that only know the package by name
+    pub fn channel_of(&self, manager: &PackageManager, name: &str) -> Option<&str> {
+        self.channels
+            .get(&format!("{}/{}", manager.name(), name))
+            .map(String::as_str)
+    }
+
+    /// Channels a manager can follow besides stable releases
+    pub fn channels_for(manager: &PackageManager) -> &'static [&'static str] {
+        match manager {
+            // Any dist-tag works; these are the common ones
+            PackageManager::Npm => &["next", "beta", "rc", "canary", "alpha", "insiders"],
+            PackageManager::Cargo => &["prerelease"],
+            PackageManager::Homebrew => &["HEAD"],
+            _ => &[],
+        }
+    }
+
+    pub fn set_channel(&mut self, pkg: &Package, channel: Option<String>) {
+        match channel {
+            Some(channel) => self.channels.insert(pkg.key(), channel),
+            None => self.channels.remove(&pkg.key()),
+        };
+        self.save();
+    }
+
+    /// Outdated and not ignored: what the counts, filters and badges show
+    pub fn needs_attention(&self, pkg: &Package) -> bool {
+        pkg.is_outdated && !self.is_ignored(pkg)
+    }
+
+    /// Outdated, not ignored and not pinned: what batch updates may touch
+    pub fn can_update(&self, pkg: &Package) -> bool {
+        self.needs_attention(pkg) && !self.is_pinned(pkg)
+    }
+
+    /// What Update All and Update everything may touch without asking
+    pub fn can_update_unattended(&self, pkg: &Package) -> bool {
+        self.can_update(pkg) && !self.is_critical(pkg)
+    }
+
+    pub fn toggle_pinned(&mut self, pkg: &Package) {
+        let key = pkg.key();
+        if !self.pinned.remove(&key) {
+            self.pinned.insert(key);
+        }
+        self.save();
+    }
+
+    pub fn toggle_ignored(&mut self, pkg: &Package) {
+        let key = pkg.key();
+        if !self.ignored.remove(&key) {
+            self.ignored.insert(key);
+        }
+        self.save();
+    }
+
+    pub fn toggle_favorite(&mut self, pkg: &Package) {
+        let key = pkg.key();
+        if !self.favorites.remove(&key) {
+            self.favorites.insert(key);
+        }
+        self.save();
+    }
+
+    pub fn toggle_critical(&mut self, pkg: &Package) {
+        let key = pkg.key();
+        if !self.critical.remove(&key) {
+            self.critical.insert(key);
+        }
+        self.save();
+    }
+
+    /// Three-way merge for syncing machines: a flag set or cleared on either side since
+    /// `base` (the last sync) sticks, so changes made on different machines combine
+    pub fn merge(base: &Self, local: &Self, remote: &Self) -> Self {
+        fn merge_set(
+            base: &HashSet<String>,
+            local: &HashSet<String>,
+            remote: &HashSet<String>,
+        ) -> HashSet<String> {
+            local
+                .union(remote)
+                .filter(|key| {
+                    !base.contains(*key) || (local.contains(*key) && remote.contains(*key))
+                })
+                .cloned()
+                .collect()
+        }
+        // A channel changed on one side wins; changed on both, this machine's choice wins
+        let mut channels = HashMap::new();
+        let keys: HashSet<&String> = base
+            .channels
+            .keys()
+            .chain(local.channels.keys())
+            .chain(remote.channels.keys())
+            .collect();
+        for key in keys {
+            let (b, l, r) = (
+                base.channels.get(key),
+                local.channels.get(key),
+                remote.channels.get(key),
+            );
+            if let Some(channel) = if l == b { r } else { l } {
+                channels.insert(key.clone(), channel.clone());
+            }
+        }
+        Self {
+            pinned: merge_set(&base.pinned, &local.pinned, &remote.pinned),
+            ignored: merge_set(&base.ignored, &local.ignored, &remote.ignored),
+            favorites: merge_set(&base.favorites, &local.favorites, &remote.favorites),
+            critical: merge_set(&base.critical, &local.critical, &remote.critical),
+            channels,
+        }
+    }
+}
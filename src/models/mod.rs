@@ -1,7 +1,19 @@
+pub mod category;
+pub mod conflict;
+pub mod diff;
+pub mod flags;
 pub mod package;
 pub mod project;
+pub mod risk;
 pub mod usage;
 
-pub use package::{Package, PackageManager};
-pub use project::Project;
+pub use category::{Category, CategoryOverrides};
+pub use conflict::Conflict;
+pub use diff::{Change, ChangeKind};
+pub use flags::PackageFlags;
+pub use package::{
+    BinaryArch, BrokenBinary, ConstraintMismatch, Package, PackageManager, StaleHook,
+};
+pub use project::{Project, ProjectRef, ToolUsage, Venv};
+pub use risk::{Risk, RiskLevel};
 pub use usage::{Dependency, PackageUsage};
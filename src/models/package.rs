@@ -10,14 +10,83 @@ pub enum PackageManager {
     Cargo,
     Pip,
     Pipx,
+    Uv,     // tools from `uv tool install`
+    Poetry, // plugins in Poetry's own environment
     Gem,
     Go,
     Composer,
     Pub,
     Swift,
+    Opam,
+    Ghcup,
+    Stack,
+    LuaRocks,
+    Julia,
+    R,
+    Tfenv,
+    HelmPlugins,
+    Krew, // kubectl plugins
+    GhExtensions,
+    GitHelpers, // credential helpers configured for git
+    OhMyZsh,
+    Zinit,
+    Fisher,
+    Fonts, // Homebrew font casks
+    Apk,   // Alpine system packages (container audits)
+    Apt,   // Debian/Ubuntu system packages (container audits)
+    Maven, // Java libraries declared by Gradle/Maven projects, as "group:artifact"
+    Winget,
+    Scoop,
+    Chocolatey,
+    Nix, // packages in the user's `nix profile`
+    Mise,
+    Asdf, // tools pinned in the global ~/.tool-versions
+    Mas,  // Mac App Store apps, named as the store lists them
 }
 
 impl PackageManager {
+    pub const ALL: [PackageManager; 39] = [
+        PackageManager::Homebrew,
+        PackageManager::Npm,
+        PackageManager::Yarn,
+        PackageManager::Pnpm,
+        PackageManager::Cargo,
+        PackageManager::Pip,
+        PackageManager::Pipx,
+        PackageManager::Uv,
+        PackageManager::Poetry,
+        PackageManager::Gem,
+        PackageManager::Go,
+        PackageManager::Composer,
+        PackageManager::Pub,
+        PackageManager::Swift,
+        PackageManager::Opam,
+        PackageManager::Ghcup,
+        PackageManager::Stack,
+        PackageManager::LuaRocks,
+        PackageManager::Julia,
+        PackageManager::R,
+        PackageManager::Tfenv,
+        PackageManager::HelmPlugins,
+        PackageManager::Krew,
+        PackageManager::GhExtensions,
+        PackageManager::GitHelpers,
+        PackageManager::OhMyZsh,
+        PackageManager::Zinit,
+        PackageManager::Fisher,
+        PackageManager::Fonts,
+        PackageManager::Apk,
+        PackageManager::Apt,
+        PackageManager::Maven,
+        PackageManager::Winget,
+        PackageManager::Scoop,
+        PackageManager::Chocolatey,
+        PackageManager::Nix,
+        PackageManager::Mise,
+        PackageManager::Asdf,
+        PackageManager::Mas,
+    ];
+
     pub fn name(&self) -> &'static str {
         match self {
             PackageManager::Homebrew => "Homebrew",
@@ -27,11 +96,38 @@ impl PackageManager {
             PackageManager::Cargo => "Cargo",
             PackageManager::Pip => "pip",
             PackageManager::Pipx => "pipx",
+            PackageManager::Uv => "uv",
+            PackageManager::Poetry => "Poetry",
             PackageManager::Gem => "gem",
             PackageManager::Go => "Go",
             PackageManager::Composer => "Composer",
             PackageManager::Pub => "pub",
             PackageManager::Swift => "Swift",
+            PackageManager::Opam => "opam",
+            PackageManager::Ghcup => "GHCup",
+            PackageManager::Stack => "Stack",
+            PackageManager::LuaRocks => "LuaRocks",
+            PackageManager::Julia => "Julia",
+            PackageManager::R => "R",
+            PackageManager::Tfenv => "tfenv",
+            PackageManager::HelmPlugins => "Helm plugins",
+            PackageManager::Krew => "krew",
+            PackageManager::GhExtensions => "gh extensions",
+            PackageManager::GitHelpers => "git helpers",
+            PackageManager::OhMyZsh => "oh-my-zsh",
+            PackageManager::Zinit => "zinit",
+            PackageManager::Fisher => "fisher",
+            PackageManager::Fonts => "Fonts",
+            PackageManager::Apk => "apk",
+            PackageManager::Apt => "apt",
+            PackageManager::Maven => "Maven",
+            PackageManager::Winget => "winget",
+            PackageManager::Scoop => "Scoop",
+            PackageManager::Chocolatey => "Chocolatey",
+            PackageManager::Nix => "Nix",
+            PackageManager::Mise => "mise",
+            PackageManager::Asdf => "asdf",
+            PackageManager::Mas => "App Store",
         }
     }
 
@@ -44,11 +140,187 @@ impl PackageManager {
             PackageManager::Cargo => "cargo",
             PackageManager::Pip => "pip",
             PackageManager::Pipx => "pipx",
+            PackageManager::Uv => "uv",
+            PackageManager::Poetry => "poetry",
             PackageManager::Gem => "gem",
             PackageManager::Go => "go",
             PackageManager::Composer => "composer",
-            PackageManager::Pub => "pub",
+            PackageManager::Pub => "dart",
             PackageManager::Swift => "swift",
+            PackageManager::Opam => "opam",
+            PackageManager::Ghcup => "ghcup",
+            PackageManager::Stack => "stack",
+            PackageManager::LuaRocks => "luarocks",
+            PackageManager::Julia => "julia",
+            PackageManager::R => "Rscript",
+            PackageManager::Tfenv => "tfenv",
+            PackageManager::HelmPlugins => "helm",
+            PackageManager::Krew => "kubectl-krew",
+            PackageManager::GhExtensions => "gh",
+            PackageManager::GitHelpers => "git",
+            // Plugin managers live inside the shell; is_installed() does the real check
+            PackageManager::OhMyZsh | PackageManager::Zinit => "zsh",
+            PackageManager::Fisher => "fish",
+            PackageManager::Fonts => "brew",
+            PackageManager::Apk => "apk",
+            PackageManager::Apt => "dpkg-query",
+            PackageManager::Maven => "mvn",
+            PackageManager::Winget => "winget",
+            // Scoop is a PowerShell script with a batch shim; spawning needs the extension
+            PackageManager::Scoop => "scoop.cmd",
+            PackageManager::Chocolatey => "choco",
+            PackageManager::Nix => "nix",
+            PackageManager::Mise => "mise",
+            PackageManager::Asdf => "asdf",
+            PackageManager::Mas => "mas",
+        }
+    }
+}
+
+impl PackageManager {
+    /// Managers that keep several versions of a tool side by side, listed one row each
+    pub fn lists_each_version(&self) -> bool {
+        matches!(
+            self,
+            PackageManager::Tfenv
+                | PackageManager::Ghcup
+                | PackageManager::Stack
+                | PackageManager::LuaRocks
+        )
+    }
+
+    /// Which table row an operation on `name` at `version` is about: "terraform@1.5.7"
+    /// where every version has its own row, else just the name
+    pub fn row_id(&self, name: &str, version: &str) -> String {
+        if self.lists_each_version() {
+            format!("{}@{}", name, version)
+        } else {
+            name.to_string()
+        }
+    }
+
+    /// Public registry page for a package, used for links in exported reports
+    pub fn registry_url(&self, package_name: &str) -> Option<String> {
+        match self {
+            PackageManager::Homebrew => {
+                Some(format!("https://formulae.brew.sh/formula/{}", package_name))
+            }
+            PackageManager::Npm | PackageManager::Yarn | PackageManager::Pnpm => {
+                Some(format!("https://www.npmjs.com/package/{}", package_name))
+            }
+            PackageManager::Cargo => Some(format!("https://crates.io/crates/{}", package_name)),
+            PackageManager::Pip
+            | PackageManager::Pipx
+            | PackageManager::Uv
+            | PackageManager::Poetry => Some(format!("https://pypi.org/project/{}/", package_name)),
+            PackageManager::Gem => Some(format!("https://rubygems.org/gems/{}", package_name)),
+            PackageManager::Go => Some(format!("https://pkg.go.dev/{}", package_name)),
+            PackageManager::Composer => {
+                Some(format!("https://packagist.org/packages/{}", package_name))
+            }
+            PackageManager::Pub => Some(format!("https://pub.dev/packages/{}", package_name)),
+            PackageManager::Opam => {
+                Some(format!("https://opam.ocaml.org/packages/{}/", package_name))
+            }
+            PackageManager::LuaRocks => {
+                Some(format!("https://luarocks.org/search?q={}", package_name))
+            }
+            PackageManager::Julia => Some(format!(
+                "https://juliahub.com/ui/Packages/General/{}",
+                package_name
+            )),
+            PackageManager::R => Some(format!(
+                "https://cran.r-project.org/package={}",
+                package_name
+            )),
+            PackageManager::Apk => Some(format!(
+                "https://pkgs.alpinelinux.org/packages?name={}",
+                package_name
+            )),
+            PackageManager::Apt => Some(format!(
+                "https://packages.debian.org/search?keywords={}",
+                package_name
+            )),
+            PackageManager::Krew => Some(format!(
+                "https://krew.sigs.k8s.io/plugins/#{}",
+                package_name
+            )),
+            PackageManager::Ghcup
+            | PackageManager::Stack
+            | PackageManager::Tfenv
+            | PackageManager::HelmPlugins
+            | PackageManager::GhExtensions
+            | PackageManager::GitHelpers
+            | PackageManager::OhMyZsh
+            | PackageManager::Zinit
+            | PackageManager::Fisher
+            | PackageManager::Swift => None,
+            PackageManager::Fonts => {
+                Some(format!("https://formulae.brew.sh/cask/{}", package_name))
+            }
+            PackageManager::Maven => Some(format!(
+                "https://central.sonatype.com/artifact/{}",
+                package_name.replace(':', "/")
+            )),
+            PackageManager::Winget => Some(format!("https://winstall.app/apps/{}", package_name)),
+            PackageManager::Scoop => Some(format!("https://scoop.sh/#/apps?q={}", package_name)),
+            PackageManager::Chocolatey => Some(format!(
+                "https://community.chocolatey.org/packages/{}",
+                package_name
+            )),
+            PackageManager::Nix => Some(format!(
+                "https://search.nixos.org/packages?query={}",
+                package_name
+            )),
+            // Tool names are plugin names, not packages with a page of their own
+            PackageManager::Mise | PackageManager::Asdf => None,
+            // Store pages are addressed by id, not name
+            PackageManager::Mas => None,
+        }
+    }
+}
+
+impl PackageManager {
+    /// Ecosystem name used by the OSV vulnerability database, if it covers this manager
+    pub fn osv_ecosystem(&self) -> Option<&'static str> {
+        match self {
+            PackageManager::Npm | PackageManager::Yarn | PackageManager::Pnpm => Some("npm"),
+            PackageManager::Cargo => Some("crates.io"),
+            PackageManager::Pip
+            | PackageManager::Pipx
+            | PackageManager::Uv
+            | PackageManager::Poetry => Some("PyPI"),
+            PackageManager::Gem => Some("RubyGems"),
+            PackageManager::Go => Some("Go"),
+            PackageManager::Composer => Some("Packagist"),
+            PackageManager::Pub => Some("Pub"),
+            PackageManager::R => Some("CRAN"),
+            PackageManager::Apk => Some("Alpine"),
+            PackageManager::Apt => Some("Debian"),
+            PackageManager::Maven => Some("Maven"),
+            PackageManager::Homebrew
+            | PackageManager::Swift
+            | PackageManager::Opam
+            | PackageManager::Ghcup
+            | PackageManager::Stack
+            | PackageManager::LuaRocks
+            | PackageManager::Julia
+            | PackageManager::Tfenv
+            | PackageManager::HelmPlugins
+            | PackageManager::Krew
+            | PackageManager::GhExtensions
+            | PackageManager::GitHelpers
+            | PackageManager::OhMyZsh
+            | PackageManager::Zinit
+            | PackageManager::Winget
+            | PackageManager::Scoop
+            | PackageManager::Chocolatey
+            | PackageManager::Nix
+            | PackageManager::Mise
+            | PackageManager::Asdf
+            | PackageManager::Mas
+            | PackageManager::Fisher
+            | PackageManager::Fonts => None,
         }
     }
 }
@@ -69,6 +341,225 @@ pub struct Package {
     pub size: Option<u64>,           // disk space in bytes
     pub description: Option<String>, // what the package does
     pub used_in: Vec<String>,        // directories/projects using this package
+    #[serde(default)]
+    pub dev_only_in: Vec<String>, // subset of used_in that only lists it as a dev dependency
+    #[serde(default)]
+    pub project_refs: Vec<super::ProjectRef>, // repository and member for used_in paths
+    #[serde(default)]
+    pub vulnerabilities: Vec<String>, // advisory IDs affecting the installed version (OSV)
+    #[serde(default)]
+    pub constraint_mismatches: Vec<ConstraintMismatch>, // projects requiring a newer version
+    #[serde(default)]
+    pub stale_hooks: Vec<StaleHook>, // pre-commit hooks pinned far behind this version
+    #[serde(default)]
+    pub preview_font: Option<String>, // font file to render the name with (font casks)
+    #[serde(default)]
+    pub download_size: Option<u64>, // bytes to download for the latest version (Homebrew bottles)
+    #[serde(default)]
+    pub build_dependencies: Vec<String>, // only needed when building from source
+    #[serde(default)]
+    pub conflicts: Vec<String>, // packages that can't be installed alongside this one
+    #[serde(default)]
+    pub arch: Option<BinaryArch>, // CPU architecture of the installed binary (macOS)
+    #[serde(default)]
+    pub needs_rosetta: bool, // Intel-only binary on Apple Silicon
+    #[serde(default)]
+    pub broken: Vec<BrokenBinary>, // commands it should provide that no longer work
+    #[serde(default)]
+    pub auto_updates: bool, // cask that updates itself (brew's `auto_updates`)
+    #[serde(default)]
+    pub is_cask: bool, // Homebrew cask rather than formula
+}
+
+/// A command on PATH that points nowhere: a dangling symlink in the manager's bin
+/// directory, or a binary the manager recorded that was deleted
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BrokenBinary {
+    pub path: String,
+    pub dangling_target: Option<String>, // where the symlink points, if it is one
+}
+
+impl BrokenBinary {
+    pub fn describe(&self) -> String {
+        match &self.dangling_target {
+            Some(target) => format!("{} → {} (missing)", self.path, target),
+            None => format!("{} is missing", self.path),
+        }
+    }
+}
+
+/// What a macOS binary was built for
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum BinaryArch {
+    Arm64,
+    X86_64,
+    Universal,
+}
+
+impl BinaryArch {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BinaryArch::Arm64 => "arm64",
+            BinaryArch::X86_64 => "x86_64",
+            BinaryArch::Universal => "universal",
+        }
+    }
+}
+
+/// A project declares a version range the installed tool doesn't satisfy
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConstraintMismatch {
+    pub project: String,
+    pub constraint: String,
+}
+
+/// A project's pre-commit hook runs a release line older than the one available
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StaleHook {
+    pub project: String,
+    pub rev: String,
+}
+
+impl Package {
+    pub fn new(
+        name: impl Into<String>,
+        manager: PackageManager,
+        installed_version: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            manager,
+            installed_version: installed_version.into(),
+            latest_version: None,
+            is_outdated: false,
+            size: None,
+            description: None,
+            used_in: vec![],
+            dev_only_in: vec![],
+            project_refs: vec![],
+            vulnerabilities: vec![],
+            constraint_mismatches: vec![],
+            stale_hooks: vec![],
+            preview_font: None,
+            download_size: None,
+            build_dependencies: vec![],
+            conflicts: vec![],
+            arch: None,
+            needs_rosetta: false,
+            broken: vec![],
+            auto_updates: false,
+            is_cask: false,
+        }
+    }
+
+    /// Registry page for this package; casks live apart from formulae on formulae.brew.sh
+    pub fn registry_url(&self) -> Option<String> {
+        if self.is_cask {
+            return Some(format!("https://formulae.brew.sh/cask/{}", self.name));
+        }
+        self.manager.registry_url(&self.name)
+    }
+
+    /// Stable identifier for per-package state saved across scans: "manager/name"
+    pub fn key(&self) -> String {
+        format!("{}/{}", self.manager.name(), self.name)
+    }
+
+    /// This row among the package's installed versions (see `PackageManager::row_id`)
+    pub fn row_id(&self) -> String {
+        self.manager.row_id(&self.name, &self.installed_version)
+    }
+
+    /// Latest version if updating to it would satisfy every mismatched project
+    pub fn suggested_update(&self) -> Option<&str> {
+        let latest = self.latest_version.as_deref()?;
+        self.constraint_mismatches
+            .iter()
+            .all(|m| crate::utils::version::satisfies(latest, &m.constraint) == Some(true))
+            .then_some(latest)
+    }
+
+    /// How a project using this package is shown: "repo/web" when the scan placed it in a
+    /// repository, else its folder name
+    pub fn usage_label(&self, path: &str) -> String {
+        match self.project_refs.iter().find(|r| r.path == path) {
+            Some(project) => project.label(),
+            None => std::path::Path::new(path)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.to_string()),
+        }
+    }
+
+    /// "dev only" / "runtime" for packages matched against project manifests.
+    /// Homebrew tools are matched by project type, so they have no dependency kind.
+    pub fn usage_kind(&self) -> Option<&'static str> {
+        if self.used_in.is_empty() || self.manager == PackageManager::Homebrew {
+            return None;
+        }
+        if self.dev_only_in.len() == self.used_in.len() {
+            Some("dev only")
+        } else {
+            Some("runtime")
+        }
+    }
 }
 
 // Removed unused helper methods - dead code cleanup
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `ALL` is kept by hand. The match has no wildcard, so a new variant doesn't compile
+    /// until it's numbered here, and then this fails until `ALL` lists it too.
+    #[test]
+    fn all_lists_every_manager_once() {
+        let position = |manager: &PackageManager| match manager {
+            PackageManager::Homebrew => 0,
+            PackageManager::Npm => 1,
+            PackageManager::Yarn => 2,
+            PackageManager::Pnpm => 3,
+            PackageManager::Cargo => 4,
+            PackageManager::Pip => 5,
+            PackageManager::Pipx => 6,
+            PackageManager::Uv => 7,
+            PackageManager::Poetry => 8,
+            PackageManager::Gem => 9,
+            PackageManager::Go => 10,
+            PackageManager::Composer => 11,
+            PackageManager::Pub => 12,
+            PackageManager::Swift => 13,
+            PackageManager::Opam => 14,
+            PackageManager::Ghcup => 15,
+            PackageManager::Stack => 16,
+            PackageManager::LuaRocks => 17,
+            PackageManager::Julia => 18,
+            PackageManager::R => 19,
+            PackageManager::Tfenv => 20,
+            PackageManager::HelmPlugins => 21,
+            PackageManager::Krew => 22,
+            PackageManager::GhExtensions => 23,
+            PackageManager::GitHelpers => 24,
+            PackageManager::OhMyZsh => 25,
+            PackageManager::Zinit => 26,
+            PackageManager::Fisher => 27,
+            PackageManager::Fonts => 28,
+            PackageManager::Apk => 29,
+            PackageManager::Apt => 30,
+            PackageManager::Maven => 31,
+            PackageManager::Winget => 32,
+            PackageManager::Scoop => 33,
+            PackageManager::Chocolatey => 34,
+            PackageManager::Nix => 35,
+            PackageManager::Mise => 36,
+            PackageManager::Asdf => 37,
+            PackageManager::Mas => 38,
+        };
+        let positions: Vec<usize> = PackageManager::ALL.iter().map(position).collect();
+        assert_eq!(
+            positions,
+            (0..PackageManager::ALL.len()).collect::<Vec<_>>()
+        );
+    }
+}
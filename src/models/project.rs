@@ -10,9 +10,58 @@ pub struct Project {
     pub name: String,
     pub package_managers: Vec<crate::models::PackageManager>,
     pub dependencies: Vec<Dependency>,
+    /// Runtimes/tools the project declares a version for (engines.node, requires-python, rust-version)
+    #[serde(default)]
+    pub tool_requirements: Vec<Dependency>,
+    /// Globally installed tools the project's dev tooling runs (pre-commit hooks, CI workflows)
+    #[serde(default)]
+    pub tool_usages: Vec<ToolUsage>,
+    /// Tools the environment files ask for (mise, devcontainer features, a Nix flake, the
+    /// README's requirements), with the requested version as `rev`
+    #[serde(default)]
+    pub setup_tools: Vec<ToolUsage>,
+    /// Virtualenvs in the project root (`.venv`, `venv`)
+    #[serde(default)]
+    pub venvs: Vec<Venv>,
     pub last_modified: DateTime<Utc>,
 }
 
+/// A project-local virtualenv and what's installed in it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Venv {
+    pub path: PathBuf,
+    pub packages: Vec<crate::models::Package>,
+    pub error: Option<String>, // Listing failed, e.g. the venv's interpreter is gone
+}
+
+/// Where a project sits: the git repository it belongs to and its path inside it, so the
+/// "api" and "web" folders of different repositories can be told apart
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectRef {
+    pub path: String,   // Full project path, as in `Package::used_in`
+    pub repo: String,   // Repository folder name; the project's own name outside git
+    pub member: String, // Path inside the repository, "" for its root
+}
+
+impl ProjectRef {
+    /// "repo/packages/web", or just "repo" for the repository root
+    pub fn label(&self) -> String {
+        if self.member.is_empty() {
+            self.repo.clone()
+        } else {
+            format!("{}/{}", self.repo, self.member)
+        }
+    }
+}
+
+/// A tool invoked by project tooling rather than declared as a dependency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolUsage {
+    pub tool: String,
+    pub source: String,      // file it was found in
+    pub rev: Option<String>, // pinned revision, if the tooling pins one
+}
+
 impl Project {
     pub fn new(path: PathBuf) -> Self {
         let name = path
@@ -26,6 +75,10 @@ impl Project {
             name,
             package_managers: Vec::new(),
             dependencies: Vec::new(),
+            tool_requirements: Vec::new(),
+            tool_usages: Vec::new(),
+            setup_tools: Vec::new(),
+            venvs: Vec::new(),
             last_modified: Utc::now(),
         }
     }
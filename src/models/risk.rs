@@ -0,0 +1,102 @@
+use super::{Category, Package};
+use crate::audit::Changelog;
+use crate::utils::version::{bump_kind, Bump};
+use serde::{Deserialize, Serialize};
+
+/// Words in release notes that usually mean something will need fixing after the update
+const BREAKING_WORDS: [&str; 6] = [
+    "breaking",
+    "incompatible",
+    "removed",
+    "no longer supported",
+    "migration guide",
+    "dropped support",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RiskLevel {
+    Low,
+    Medium,
+    High,
+}
+
+impl RiskLevel {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RiskLevel::Low => "low risk",
+            RiskLevel::Medium => "medium risk",
+            RiskLevel::High => "high risk",
+        }
+    }
+}
+
+/// Rough 0-10 estimate of how likely an update is to break something, with the reasons
+#[derive(Debug, Clone, Default)]
+pub struct Risk {
+    pub score: u8,
+    pub reasons: Vec<String>,
+}
+
+impl Risk {
+    pub fn level(&self) -> RiskLevel {
+        match self.score {
+            0..=2 => RiskLevel::Low,
+            3..=5 => RiskLevel::Medium,
+            _ => RiskLevel::High,
+        }
+    }
+
+    fn add(&mut self, points: u8, reason: String) {
+        self.score = (self.score + points).min(10);
+        self.reasons.push(reason);
+    }
+}
+
+/// Score an outdated package: bump size, breaking-change notes (once fetched), how many
+/// projects depend on it, and whether it's a runtime others build on or a leaf tool
+pub fn assess(pkg: &Package, category: Category, changelog: Option<&Changelog>) -> Risk {
+    let mut risk = Risk::default();
+    let Some(latest) = &pkg.latest_version else {
+        return risk;
+    };
+
+    match bump_kind(&pkg.installed_version, latest) {
+        Bump::Major => risk.add(4, "major version bump".to_string()),
+        Bump::Minor => risk.add(1, "minor version bump".to_string()),
+        Bump::Patch => {}
+    }
+
+    if let Some(changelog) = changelog {
+        let breaking = changelog
+            .notes
+            .iter()
+            .filter(|note| {
+                let body = note.body.to_lowercase();
+                BREAKING_WORDS.iter().any(|w| body.contains(w))
+            })
+            .count();
+        if breaking > 0 {
+            risk.add(
+                3,
+                format!("{} release notes mention breaking changes", breaking),
+            );
+        }
+    }
+
+    match pkg.used_in.len() {
+        0 => {}
+        n @ 1..=2 => risk.add(1, format!("used by {} projects", n)),
+        n => risk.add(2, format!("used by {} projects", n)),
+    }
+
+    match category {
+        Category::Language | Category::Database => {
+            risk.add(2, "runtime other things depend on".to_string())
+        }
+        Category::Library => risk.add(1, "library".to_string()),
+        Category::Cli | Category::GuiApp | Category::Font => {}
+    }
+
+    risk
+}
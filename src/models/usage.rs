@@ -8,6 +8,12 @@ pub struct Dependency {
     pub manager: crate::models::PackageManager,
     pub version_constraint: String, // e.g., "^1.2.3", ">=2.0.0"
     pub is_dev: bool,
+    /// Exact version from the project's lock file (uv.lock, poetry.lock, Pipfile.lock)
+    #[serde(default)]
+    pub locked_version: Option<String>,
+    /// Newest release, for dependencies looked up per project (Go modules)
+    #[serde(default)]
+    pub latest_version: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,184 @@
+use super::{JournalAction, JournalEntry};
+use crate::config::AutoUpdateSettings;
+use crate::models::{CategoryOverrides, Package, PackageFlags};
+use chrono::{Datelike, Local, NaiveDateTime, NaiveTime, Weekday};
+use std::collections::HashSet;
+
+/// What happened in the current maintenance window, kept across scans so each
+/// package is tried and a hold is journaled once per window
+#[derive(Default)]
+pub struct WindowState {
+    /// `Package::key()`s already updated (or attempted) in this window
+    tried: HashSet<String>,
+    /// A battery or meeting hold has been journaled
+    held: bool,
+}
+
+/// Whether the maintenance window is open at `now` (local time)
+fn in_window(settings: &AutoUpdateSettings, now: NaiveDateTime) -> bool {
+    let parse = |time: &str| NaiveTime::parse_from_str(time.trim(), "%H:%M").ok();
+    let (Some(start), Some(end)) = (parse(&settings.start), parse(&settings.end)) else {
+        eprintln!(
+            "[AUTO-UPDATE] Invalid window {}-{}, expected HH:MM",
+            settings.start, settings.end
+        );
+        return false;
+    };
+    let time = now.time();
+    // A window like 23:00-01:00 opens on the listed day and runs past midnight
+    let (open, day) = if start <= end {
+        (start <= time && time < end, now.weekday())
+    } else if time >= start {
+        (true, now.weekday())
+    } else {
+        (time < end, now.weekday().pred())
+    };
+    open && (settings.days.is_empty()
+        || settings
+            .days
+            .iter()
+            .any(|d| d.parse::<Weekday>().ok() == Some(day)))
+}
+
+/// Why updates that are due can't run right now, or None to go ahead
+async fn hold_reason(settings: &AutoUpdateSettings) -> Option<String> {
+    if settings.skip_on_battery && crate::utils::power::current_conditions().await.on_battery {
+        return Some("on battery power".to_string());
+    }
+    crate::utils::processes::first_running(&settings.meeting_apps)
+        .await
+        .map(|app| format!("{} is running", app))
+}
+
+/// Apply the outdated packages policy allows (unpinned, not critical, no riskier than
+/// `max_risk`) when the window is open, each at most once per window; returns how many
+/// installed versions actually changed. Everything done or skipped goes to the journal.
+pub async fn run_due_updates(
+    settings: &AutoUpdateSettings,
+    packages: &[Package],
+    window: &mut WindowState,
+) -> usize {
+    if !settings.enabled || !in_window(settings, Local::now().naive_local()) {
+        *window = WindowState::default();
+        return 0;
+    }
+
+    let flags = PackageFlags::load();
+    let categories = CategoryOverrides::load();
+    let (approved, held): (Vec<&Package>, Vec<&Package>) = packages
+        .iter()
+        .filter(|p| p.is_outdated && flags.can_update_unattended(p))
+        .filter(|p| !window.tried.contains(&p.key()))
+        .partition(|p| {
+            crate::models::risk::assess(p, categories.category_of(p), None).level()
+                <= settings.max_risk
+        });
+    println!(
+        "[AUTO-UPDATE] Window open: {} updates approved, {} above {} left for review",
+        approved.len(),
+        held.len(),
+        settings.max_risk.label()
+    );
+    if approved.is_empty() {
+        return 0;
+    }
+
+    if let Some(reason) = hold_reason(settings).await {
+        println!("[AUTO-UPDATE] Skipping: {}", reason);
+        if !window.held {
+            window.held = true;
+            for pkg in approved {
+                JournalEntry::new(
+                    JournalAction::Skipped,
+                    pkg.manager.clone(),
+                    pkg.name.clone(),
+                )
+                .with_versions(
+                    Some(pkg.installed_version.clone()),
+                    pkg.latest_version.clone(),
+                )
+                .with_message(reason.clone())
+                .finish(&Ok(()));
+            }
+        }
+        return 0;
+    }
+
+    let mut updated = Vec::new();
+    for pkg in approved {
+        println!(
+            "[AUTO-UPDATE] Updating {} ({})",
+            pkg.name,
+            pkg.manager.name()
+        );
+        window.tried.insert(pkg.key());
+        let result = super::update_package(&pkg.manager, pkg.name.clone()).await;
+        if result.is_ok() {
+            updated.push(pkg);
+        }
+        JournalEntry::new(JournalAction::Update, pkg.manager.clone(), pkg.name.clone())
+            .with_versions(
+                Some(pkg.installed_version.clone()),
+                pkg.latest_version.clone(),
+            )
+            .with_message("automatic update in the maintenance window".to_string())
+            .finish(&result);
+    }
+    if updated.is_empty() {
+        return 0;
+    }
+
+    // A command that exits cleanly may still leave the old version in place
+    let mut managers = Vec::new();
+    for pkg in &updated {
+        if !managers.contains(&pkg.manager) {
+            managers.push(pkg.manager.clone());
+        }
+    }
+    let after = crate::inventory::collect_inventory(&managers).await;
+    updated
+        .iter()
+        .filter(|pkg| {
+            after
+                .iter()
+                .find(|p| p.manager == pkg.manager && p.name == pkg.name)
+                .is_some_and(|p| p.installed_version != pkg.installed_version)
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32, time: &str) -> NaiveDateTime {
+        // October 2026: the 12th is a Monday
+        NaiveDate::from_ymd_opt(2026, 10, day)
+            .unwrap()
+            .and_time(NaiveTime::parse_from_str(time, "%H:%M").unwrap())
+    }
+
+    #[test]
+    fn windows_on_weekdays_and_across_midnight() {
+        let lunch = AutoUpdateSettings {
+            days: ["mon", "tue", "wed", "thu", "fri"]
+                .map(String::from)
+                .to_vec(),
+            ..AutoUpdateSettings::default()
+        };
+        assert!(in_window(&lunch, at(12, "12:30")));
+        assert!(!in_window(&lunch, at(12, "13:00")));
+        assert!(!in_window(&lunch, at(17, "12:30"))); // Saturday
+
+        let overnight = AutoUpdateSettings {
+            days: vec!["fri".to_string()],
+            start: "23:00".to_string(),
+            end: "01:00".to_string(),
+            ..AutoUpdateSettings::default()
+        };
+        assert!(in_window(&overnight, at(16, "23:30")));
+        assert!(in_window(&overnight, at(17, "00:30"))); // Friday's window, now Saturday
+        assert!(!in_window(&overnight, at(16, "00:30")));
+    }
+}
@@ -0,0 +1,154 @@
+use crate::models::PackageManager;
+use crate::operations::{JournalAction, JournalEntry};
+use crate::utils::run_command_with_timeout;
+use crate::utils::target::{current_target, shell_quote};
+use anyhow::{anyhow, Result};
+use std::time::Duration;
+
+/// Operations a package can have its own command for in config.toml
+#[derive(Debug, Clone, Copy)]
+pub enum CustomAction {
+    Update,
+    Uninstall,
+    AfterUpdate,
+}
+
+/// The command config.toml gives for this package and action, with `{name}` expanded
+pub fn custom_command(
+    manager: &PackageManager,
+    name: &str,
+    action: CustomAction,
+) -> Option<String> {
+    let settings = crate::config::Settings::load();
+    let commands = settings.command_override(manager, name)?;
+    let template = match action {
+        CustomAction::Update => commands.update.as_deref(),
+        CustomAction::Uninstall => commands.uninstall.as_deref(),
+        CustomAction::AfterUpdate => commands.after_update.as_deref(),
+    }?;
+    let command = expand(template, name, shell()[0] == "cmd");
+    if command.is_none() {
+        eprintln!(
+            "[CUSTOM] Not running the configured command for {}: the name can't be passed to cmd safely",
+            name
+        );
+    }
+    command
+}
+
+/// Substitute `{name}` quoted for the shell, so a package name can't add commands of
+/// its own. cmd has no reliable quoting, so names with its special characters are refused.
+fn expand(template: &str, name: &str, cmd: bool) -> Option<String> {
+    let quoted = if !cmd {
+        shell_quote(name)
+    } else if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./@:+".contains(c))
+    {
+        name.to_string()
+    } else {
+        return None;
+    };
+    Some(template.replace("{name}", &quoted))
+}
+
+/// `cmd /C` for commands on this Windows machine; `sh -c` everywhere else, including
+/// ssh and container targets
+fn shell() -> [&'static str; 2] {
+    if cfg!(windows) && current_target().is_local() {
+        ["cmd", "/C"]
+    } else {
+        ["sh", "-c"]
+    }
+}
+
+/// Run a configured command on the current target through the shell, so pipes, `&&`
+/// and follow-up steps work
+pub async fn run_custom_command(command: &str) -> Result<()> {
+    println!("[CUSTOM] Running: {}", command);
+
+    let [shell, flag] = shell();
+    let output =
+        run_command_with_timeout(shell, &[flag, command], Duration::from_secs(600)).await?;
+
+    if output.status.success() {
+        println!("[CUSTOM] Finished: {}", command);
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("`{}` failed: {}", command, stderr.trim()))
+    }
+}
+
+/// Run the package's post-update hook, if it has one, recording its output in the
+/// package's history. A failing hook is reported there but doesn't undo the update.
+pub async fn run_after_update(manager: &PackageManager, name: &str) {
+    let Some(command) = custom_command(manager, name, CustomAction::AfterUpdate) else {
+        return;
+    };
+    println!("[CUSTOM] Post-update hook for {}: {}", name, command);
+
+    let [shell, flag] = shell();
+    let (result, output) =
+        match run_command_with_timeout(shell, &[flag, &command], Duration::from_secs(300)).await {
+            Ok(output) => {
+                let text = format!(
+                    "{}{}",
+                    String::from_utf8_lossy(&output.stdout),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                let result = if output.status.success() {
+                    Ok(())
+                } else {
+                    Err(anyhow!("`{}` failed: {}", command, last_lines(&text)))
+                };
+                (result, text)
+            }
+            Err(e) => (Err(e), String::new()),
+        };
+    if let Err(e) = &result {
+        eprintln!("[CUSTOM] {}", e);
+    }
+
+    JournalEntry::new(JournalAction::Hook, manager.clone(), name.to_string())
+        .with_message(format!("$ {}\n{}", command, last_lines(&output)))
+        .finish(&result);
+}
+
+/// The end of a command's output, which is where errors and summaries are
+fn last_lines(output: &str) -> String {
+    let lines: Vec<&str> = output.trim_end().lines().collect();
+    lines[lines.len().saturating_sub(20)..].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn name_placeholder_expands_everywhere() {
+        assert_eq!(
+            expand(
+                "cargo install --locked {name} && {name} --version",
+                "ripgrep",
+                false
+            )
+            .as_deref(),
+            Some("cargo install --locked ripgrep && ripgrep --version")
+        );
+    }
+
+    #[test]
+    fn names_cannot_inject_commands() {
+        assert_eq!(
+            expand("brew upgrade {name}", "jq; rm -rf ~", false).as_deref(),
+            Some("brew upgrade 'jq; rm -rf ~'")
+        );
+        assert_eq!(
+            expand("npm i -g {name}", "@types/node", true).as_deref(),
+            Some("npm i -g @types/node")
+        );
+        assert_eq!(expand("choco upgrade {name}", "a & calc", true), None);
+    }
+}
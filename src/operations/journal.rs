@@ -0,0 +1,176 @@
+use crate::models::PackageManager;
+use crate::utils::paths::data_dir;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, LazyLock, Mutex};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JournalAction {
+    Update,
+    Install,
+    Uninstall,
+    SelfUpdate,
+    Rollback,
+    Pin,
+    Unpin,
+    /// A package's post-update hook from config.toml, with its output as the message
+    Hook,
+    /// An automatic update that didn't run, with the reason as the message
+    Skipped,
+}
+
+/// One package operation, appended to ~/.local/share/depmgr/journal.jsonl.
+/// `from_version` is what makes an update reversible.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub timestamp: DateTime<Utc>,
+    pub action: JournalAction,
+    pub manager: PackageManager,
+    pub package: String,
+    pub from_version: Option<String>,
+    pub to_version: Option<String>,
+    pub success: bool,
+    pub message: Option<String>,
+}
+
+impl JournalEntry {
+    pub fn new(action: JournalAction, manager: PackageManager, package: String) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            action,
+            manager,
+            package,
+            from_version: None,
+            to_version: None,
+            success: false,
+            message: None,
+        }
+    }
+
+    pub fn with_versions(mut self, from: Option<String>, to: Option<String>) -> Self {
+        self.from_version = from;
+        self.to_version = to;
+        self
+    }
+
+    /// One line for the package's history: "updated 1.2 ➡ 1.3", "pinned at 1.2"
+    pub fn describe(&self) -> String {
+        let from = self.from_version.as_deref();
+        let to = self.to_version.as_deref();
+        let text = match self.action {
+            JournalAction::Install => match to {
+                Some(version) => format!("installed {}", version),
+                None => "installed".to_string(),
+            },
+            JournalAction::Uninstall => "uninstalled".to_string(),
+            JournalAction::Update => format!(
+                "updated {} ➡ {}",
+                from.unwrap_or("?"),
+                to.unwrap_or("latest")
+            ),
+            JournalAction::SelfUpdate => "updated itself".to_string(),
+            JournalAction::Rollback => format!("rolled back to {}", to.unwrap_or("?")),
+            JournalAction::Pin => match from {
+                Some(version) => format!("pinned at {}", version),
+                None => "pinned".to_string(),
+            },
+            JournalAction::Unpin => "unpinned".to_string(),
+            JournalAction::Hook => "ran post-update hook".to_string(),
+            JournalAction::Skipped => match &self.message {
+                Some(reason) => format!("skipped automatic update: {}", reason),
+                None => "skipped automatic update".to_string(),
+            },
+        };
+        if self.success {
+            text
+        } else {
+            format!("{} (failed)", text)
+        }
+    }
+
+    pub fn with_message(mut self, message: String) -> Self {
+        self.message = Some(message);
+        self
+    }
+
+    /// Mark the outcome and append to the journal file; an error replaces the message
+    pub fn finish(mut self, result: &anyhow::Result<()>) -> Self {
+        self.success = result.is_ok();
+        if let Err(e) = result {
+            self.message = Some(e.to_string());
+        }
+        append(&self);
+        self
+    }
+}
+
+fn journal_path() -> PathBuf {
+    data_dir().join("journal.jsonl")
+}
+
+type Parsed = Option<(u64, Arc<Vec<JournalEntry>>)>;
+
+/// The journal as last parsed, with the file length it was read at. Appending here drops
+/// it; the daemon or `depmgr update` appending from another process changes the length.
+static PARSED: LazyLock<Mutex<Parsed>> = LazyLock::new(|| Mutex::new(None));
+
+/// Append-only JSON lines so a crash mid-run never corrupts earlier history
+fn append(entry: &JournalEntry) {
+    let path = journal_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+
+    let line = match serde_json::to_string(entry) {
+        Ok(line) => line,
+        Err(e) => {
+            eprintln!("[ERROR] Failed to serialize journal entry: {}", e);
+            return;
+        }
+    };
+
+    let result = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| writeln!(file, "{}", line));
+    *PARSED.lock().unwrap_or_else(|e| e.into_inner()) = None;
+
+    if let Err(e) = result {
+        eprintln!("[ERROR] Failed to write journal {}: {}", path.display(), e);
+    }
+}
+
+/// Every recorded operation, oldest first; unreadable lines are skipped. Parsed once and
+/// kept until the journal grows.
+pub fn read_journal() -> Arc<Vec<JournalEntry>> {
+    let path = journal_path();
+    let len = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    let mut parsed = PARSED.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((read_len, entries)) = parsed.as_ref() {
+        if *read_len == len {
+            return Arc::clone(entries);
+        }
+    }
+    let entries: Arc<Vec<JournalEntry>> = Arc::new(
+        std::fs::read_to_string(&path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect(),
+    );
+    *parsed = Some((len, Arc::clone(&entries)));
+    entries
+}
+
+/// Everything recorded for one package, newest first
+pub fn package_history(manager: &PackageManager, package: &str) -> Vec<JournalEntry> {
+    read_journal()
+        .iter()
+        .rev()
+        .filter(|entry| &entry.manager == manager && entry.package == package)
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,355 @@
+pub mod auto_update;
+pub mod custom_commands;
+pub mod journal;
+pub mod recent;
+pub mod runtime_tools;
+pub mod sync;
+pub mod update_everything;
+
+pub use custom_commands::CustomAction;
+pub use journal::{JournalAction, JournalEntry};
+
+use crate::daemon::Request;
+use crate::models::{Package, PackageManager};
+use crate::utils::activity;
+use crate::utils::metrics::time_operation;
+use anyhow::{anyhow, Result};
+use custom_commands::{custom_command, run_after_update, run_custom_command};
+
+/// Upgrade one package with its manager's native command, or the one config.toml gives
+/// it, then run its post-update hook
+pub async fn update_package(manager: &PackageManager, name: String) -> Result<()> {
+    if crate::daemon::is_connected() {
+        let manager = manager.clone();
+        return crate::daemon::forward(Request::Update { manager, name }).await;
+    }
+    let _activity = activity::start(format!("Updating {}", name));
+    let updated = name.clone();
+    let result = time_operation("update", manager.name(), async move {
+        if let Some(command) = custom_command(manager, &name, CustomAction::Update) {
+            return run_custom_command(&command).await;
+        }
+        match manager {
+            PackageManager::Homebrew => crate::managers::homebrew_fast::update_package(name).await,
+            PackageManager::Npm => crate::managers::npm::update_npm_package(name).await,
+            PackageManager::Cargo => crate::managers::cargo::update_cargo_package(name).await,
+            PackageManager::Pip => crate::managers::pip::update_pip_package(name).await,
+            PackageManager::Pipx => crate::managers::pipx::update_pipx_package(name).await,
+            PackageManager::Gem => crate::managers::gem::update_gem_package(name).await,
+            PackageManager::Composer => {
+                crate::managers::composer::update_composer_package(name).await
+            }
+            PackageManager::Nix => crate::managers::nix::update_nix_package(name).await,
+            PackageManager::Uv => crate::managers::python_tools::update_uv_tool(name).await,
+            PackageManager::Poetry => {
+                crate::managers::python_tools::update_poetry_plugin(name).await
+            }
+            PackageManager::Mas => crate::managers::mas::update_mas_app(name).await,
+            PackageManager::Mise => crate::managers::version_managers::update_mise_tool(name).await,
+            PackageManager::Asdf => crate::managers::version_managers::update_asdf_tool(name).await,
+            PackageManager::Winget => crate::managers::windows::update_winget_package(name).await,
+            PackageManager::Scoop => crate::managers::windows::update_scoop_package(name).await,
+            PackageManager::Chocolatey => {
+                crate::managers::windows::update_choco_package(name).await
+            }
+            PackageManager::Go => crate::managers::go::update_go_binary(name).await,
+            PackageManager::Opam => crate::managers::opam::update_opam_package(name).await,
+            PackageManager::Pub => crate::managers::dart::update_pub_package(name).await,
+            PackageManager::Ghcup => crate::managers::haskell::update_ghcup_tool(name).await,
+            PackageManager::Stack => crate::managers::haskell::update_stack_tool(name).await,
+            PackageManager::LuaRocks => {
+                crate::managers::luarocks::update_luarocks_package(name).await
+            }
+            PackageManager::Julia => crate::managers::julia::update_julia_package(name).await,
+            PackageManager::R => crate::managers::r::update_r_package(name).await,
+            PackageManager::Tfenv => crate::managers::infra::update_tfenv().await,
+            PackageManager::HelmPlugins => crate::managers::infra::update_helm_plugin(name).await,
+            PackageManager::Krew => crate::managers::infra::update_krew_plugin(name).await,
+            PackageManager::GhExtensions => crate::managers::gh::update_gh_extension(name).await,
+            PackageManager::OhMyZsh | PackageManager::Zinit => {
+                crate::managers::shell_plugins::update_checkout_plugin(manager, name).await
+            }
+            PackageManager::Fisher => {
+                crate::managers::shell_plugins::update_fisher_plugin(name).await
+            }
+            PackageManager::Fonts => crate::managers::fonts::update_fonts(vec![name]).await,
+            _ => Err(anyhow!("Update not implemented for this package manager")),
+        }
+    })
+    .await;
+    if result.is_ok() {
+        run_after_update(manager, &updated).await;
+    }
+    result
+}
+
+pub async fn install_package(manager: &PackageManager, name: String) -> Result<()> {
+    if crate::daemon::is_connected() {
+        let manager = manager.clone();
+        return crate::daemon::forward(Request::Install { manager, name }).await;
+    }
+    let _activity = activity::start(format!("Installing {}", name));
+    time_operation("install", manager.name(), async move {
+        match manager {
+            PackageManager::Homebrew => crate::managers::homebrew_fast::install_package(name).await,
+            PackageManager::Npm => crate::managers::npm::install_npm_package(name).await,
+            PackageManager::Cargo => crate::managers::cargo::install_cargo_package(name).await,
+            PackageManager::Pip => crate::managers::pip::install_pip_package(name).await,
+            PackageManager::Pipx => crate::managers::pipx::install_pipx_package(name).await,
+            PackageManager::Gem => crate::managers::gem::install_gem_package(name).await,
+            PackageManager::Composer => {
+                crate::managers::composer::install_composer_package(name).await
+            }
+            PackageManager::Nix => crate::managers::nix::install_nix_package(name).await,
+            PackageManager::Uv => crate::managers::python_tools::install_uv_tool(name).await,
+            PackageManager::Poetry => {
+                crate::managers::python_tools::install_poetry_plugin(name).await
+            }
+            PackageManager::Mas => crate::managers::mas::install_mas_app(name).await,
+            PackageManager::Mise => {
+                crate::managers::version_managers::install_mise_tool(name).await
+            }
+            PackageManager::Winget => crate::managers::windows::install_winget_package(name).await,
+            PackageManager::Scoop => crate::managers::windows::install_scoop_package(name).await,
+            PackageManager::Chocolatey => {
+                crate::managers::windows::install_choco_package(name).await
+            }
+            PackageManager::Go => crate::managers::go::install_go_binary(name).await,
+            PackageManager::Opam => crate::managers::opam::install_opam_package(name).await,
+            PackageManager::Pub => crate::managers::dart::install_pub_package(name).await,
+            PackageManager::Ghcup => crate::managers::haskell::install_ghcup_tool(name).await,
+            PackageManager::LuaRocks => {
+                crate::managers::luarocks::install_luarocks_package(name).await
+            }
+            PackageManager::Julia => crate::managers::julia::install_julia_package(name).await,
+            PackageManager::R => crate::managers::r::install_r_package(name).await,
+            PackageManager::Krew => crate::managers::infra::install_krew_plugin(name).await,
+            PackageManager::GhExtensions => crate::managers::gh::install_gh_extension(name).await,
+            PackageManager::Zinit => {
+                crate::managers::shell_plugins::install_zinit_plugin(name).await
+            }
+            PackageManager::Fisher => {
+                crate::managers::shell_plugins::install_fisher_plugin(name).await
+            }
+            PackageManager::Fonts => crate::managers::fonts::install_fonts(vec![name]).await,
+            _ => Err(anyhow!(
+                "Reinstall not implemented for this package manager"
+            )),
+        }
+    })
+    .await
+}
+
+/// Remove a package. `version` is the row's installed version: managers that keep
+/// several versions side by side remove only that one.
+pub async fn uninstall_package(
+    manager: &PackageManager,
+    name: String,
+    version: Option<String>,
+) -> Result<()> {
+    if crate::daemon::is_connected() {
+        let manager = manager.clone();
+        return crate::daemon::forward(Request::Uninstall {
+            manager,
+            name,
+            version,
+        })
+        .await;
+    }
+    let _activity = activity::start(format!("Uninstalling {}", name));
+    time_operation("uninstall", manager.name(), async move {
+        if let Some(command) = custom_command(manager, &name, CustomAction::Uninstall) {
+            return run_custom_command(&command).await;
+        }
+        match manager {
+            PackageManager::Homebrew => {
+                crate::managers::homebrew_fast::uninstall_package(name).await
+            }
+            PackageManager::Npm => crate::managers::npm::uninstall_npm_package(name).await,
+            PackageManager::Cargo => crate::managers::cargo::uninstall_cargo_package(name).await,
+            PackageManager::Pip => crate::managers::pip::uninstall_pip_package(name).await,
+            PackageManager::Pipx => crate::managers::pipx::uninstall_pipx_package(name).await,
+            PackageManager::Gem => crate::managers::gem::uninstall_gem_package(name).await,
+            PackageManager::Composer => {
+                crate::managers::composer::uninstall_composer_package(name).await
+            }
+            PackageManager::Nix => crate::managers::nix::uninstall_nix_package(name).await,
+            PackageManager::Uv => crate::managers::python_tools::uninstall_uv_tool(name).await,
+            PackageManager::Poetry => {
+                crate::managers::python_tools::uninstall_poetry_plugin(name).await
+            }
+            PackageManager::Mas => crate::managers::mas::uninstall_mas_app(name).await,
+            PackageManager::Mise => {
+                crate::managers::version_managers::uninstall_mise_tool(name, version).await
+            }
+            PackageManager::Asdf => {
+                crate::managers::version_managers::uninstall_asdf_tool(name).await
+            }
+            PackageManager::Winget => {
+                crate::managers::windows::uninstall_winget_package(name).await
+            }
+            PackageManager::Scoop => crate::managers::windows::uninstall_scoop_package(name).await,
+            PackageManager::Chocolatey => {
+                crate::managers::windows::uninstall_choco_package(name).await
+            }
+            PackageManager::Go => crate::managers::go::uninstall_go_binary(name).await,
+            PackageManager::Opam => crate::managers::opam::uninstall_opam_package(name).await,
+            PackageManager::Pub => crate::managers::dart::uninstall_pub_package(name).await,
+            PackageManager::Ghcup => {
+                crate::managers::haskell::uninstall_ghcup_tool(name, version).await
+            }
+            PackageManager::LuaRocks => {
+                crate::managers::luarocks::uninstall_luarocks_package(name, version).await
+            }
+            PackageManager::Julia => crate::managers::julia::uninstall_julia_package(name).await,
+            PackageManager::R => crate::managers::r::uninstall_r_package(name).await,
+            PackageManager::Tfenv => crate::managers::infra::uninstall_tfenv(version).await,
+            PackageManager::HelmPlugins => {
+                crate::managers::infra::uninstall_helm_plugin(name).await
+            }
+            PackageManager::Krew => crate::managers::infra::uninstall_krew_plugin(name).await,
+            PackageManager::GhExtensions => crate::managers::gh::uninstall_gh_extension(name).await,
+            PackageManager::OhMyZsh | PackageManager::Zinit => {
+                crate::managers::shell_plugins::uninstall_checkout_plugin(manager, name).await
+            }
+            PackageManager::Fisher => {
+                crate::managers::shell_plugins::uninstall_fisher_plugin(name).await
+            }
+            PackageManager::Fonts => crate::managers::fonts::uninstall_fonts(vec![name]).await,
+            _ => Err(anyhow!(
+                "Uninstall not implemented for this package manager"
+            )),
+        }
+    })
+    .await
+}
+
+/// Update the package manager itself (and its metadata where that's the same command)
+pub async fn self_update(manager: &PackageManager) -> Result<()> {
+    let _activity = activity::start(format!("Updating {}", manager.name()));
+    time_operation("self_update", manager.name(), async move {
+        match manager {
+            PackageManager::Homebrew => crate::managers::homebrew_fast::self_update().await,
+            PackageManager::Npm => crate::managers::npm::self_update_npm().await,
+            PackageManager::Cargo => crate::managers::cargo::self_update_rustup().await,
+            PackageManager::Pip => crate::managers::pip::self_update_pip().await,
+            PackageManager::Gem => crate::managers::gem::self_update_gem().await,
+            PackageManager::Composer => crate::managers::composer::self_update_composer().await,
+            PackageManager::Scoop => crate::managers::windows::self_update_scoop().await,
+            PackageManager::Chocolatey => crate::managers::windows::self_update_choco().await,
+            PackageManager::Krew => crate::managers::infra::self_update_krew().await,
+            _ => Err(anyhow!(
+                "Self-update not implemented for this package manager"
+            )),
+        }
+    })
+    .await
+}
+
+/// Whether an Intel-only package can be replaced with a native arm64 build: a bottle
+/// for this platform (Homebrew) or a rebuild from source (cargo)
+pub fn supports_native_reinstall(pkg: &Package) -> bool {
+    match pkg.manager {
+        PackageManager::Homebrew => crate::managers::homebrew_fast::has_native_bottle(&pkg.name),
+        PackageManager::Cargo => true,
+        _ => false,
+    }
+}
+
+/// Reinstall an Intel-only package as arm64 (Apple Silicon)
+pub async fn reinstall_native(manager: &PackageManager, name: String) -> Result<()> {
+    time_operation("reinstall_native", manager.name(), async move {
+        match manager {
+            PackageManager::Homebrew => {
+                crate::managers::homebrew_fast::reinstall_native(name).await
+            }
+            PackageManager::Cargo => crate::managers::cargo::reinstall_cargo_native(name).await,
+            _ => Err(anyhow!(
+                "Native reinstall not implemented for this package manager"
+            )),
+        }
+    })
+    .await
+}
+
+/// Whether a previous version can be reinstalled, which is what rollback needs
+pub fn supports_rollback(manager: &PackageManager) -> bool {
+    matches!(
+        manager,
+        PackageManager::Npm
+            | PackageManager::Cargo
+            | PackageManager::Pip
+            | PackageManager::Pipx
+            | PackageManager::Uv
+            | PackageManager::Poetry
+            | PackageManager::Gem
+            | PackageManager::Composer
+            | PackageManager::Winget
+            | PackageManager::Scoop
+            | PackageManager::Chocolatey
+            | PackageManager::Go
+            | PackageManager::Opam
+            | PackageManager::Ghcup
+            | PackageManager::LuaRocks
+            | PackageManager::Julia
+            | PackageManager::Tfenv
+    )
+}
+
+/// Reinstall an exact version (used to roll back an update from the journal)
+pub async fn install_version(
+    manager: &PackageManager,
+    name: String,
+    version: String,
+) -> Result<()> {
+    time_operation("install_version", manager.name(), async move {
+        match manager {
+            PackageManager::Npm => crate::managers::npm::install_npm_version(name, version).await,
+            PackageManager::Cargo => {
+                crate::managers::cargo::install_cargo_version(name, version).await
+            }
+            PackageManager::Pip => crate::managers::pip::install_pip_version(name, version).await,
+            PackageManager::Pipx => {
+                crate::managers::pipx::install_pipx_version(name, version).await
+            }
+            PackageManager::Uv => {
+                crate::managers::python_tools::install_uv_tool_version(name, version).await
+            }
+            PackageManager::Poetry => {
+                crate::managers::python_tools::install_poetry_plugin_version(name, version).await
+            }
+            PackageManager::Gem => crate::managers::gem::install_gem_version(name, version).await,
+            PackageManager::Composer => {
+                crate::managers::composer::install_composer_version(name, version).await
+            }
+            PackageManager::Winget => {
+                crate::managers::windows::install_winget_version(name, version).await
+            }
+            PackageManager::Scoop => {
+                crate::managers::windows::install_scoop_version(name, version).await
+            }
+            PackageManager::Chocolatey => {
+                crate::managers::windows::install_choco_version(name, version).await
+            }
+            PackageManager::Go => crate::managers::go::install_go_version(name, version).await,
+            PackageManager::Opam => {
+                crate::managers::opam::install_opam_version(name, version).await
+            }
+            PackageManager::Ghcup => {
+                crate::managers::haskell::install_ghcup_version(name, version).await
+            }
+            PackageManager::LuaRocks => {
+                crate::managers::luarocks::install_luarocks_version(name, version).await
+            }
+            PackageManager::Julia => {
+                crate::managers::julia::install_julia_version(name, version).await
+            }
+            PackageManager::Tfenv => crate::managers::infra::install_tfenv_version(version).await,
+            _ => Err(anyhow!(
+                "{} can't install a specific older version",
+                manager.name()
+            )),
+        }
+    })
+    .await
+}
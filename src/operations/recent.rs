@@ -0,0 +1,232 @@
+use super::journal::{read_journal, JournalAction, JournalEntry};
+use crate::models::{Change, ChangeKind, PackageManager};
+use crate::utils::paths::data_dir;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecentKind {
+    Installed,
+    Updated,
+    Removed,
+}
+
+impl RecentKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RecentKind::Installed => "installed",
+            RecentKind::Updated => "updated",
+            RecentKind::Removed => "removed",
+        }
+    }
+}
+
+/// A package installed, updated or removed, by DepMgr (from the journal) or
+/// outside it (noticed by a scan)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentChange {
+    pub timestamp: DateTime<Utc>,
+    pub manager: PackageManager,
+    pub package: String,
+    pub kind: RecentKind,
+    pub from_version: Option<String>,
+    pub to_version: Option<String>,
+    pub external: bool,
+}
+
+impl RecentChange {
+    pub fn describe(&self) -> String {
+        match (&self.kind, &self.from_version, &self.to_version) {
+            (RecentKind::Updated, Some(from), Some(to)) => format!("{} ➡ {}", from, to),
+            (_, _, Some(version)) => format!("{} {}", self.kind.label(), version),
+            (RecentKind::Removed, Some(version), None) => format!("removed {}", version),
+            _ => self.kind.label().to_string(),
+        }
+    }
+
+    fn from_journal(entry: JournalEntry) -> Option<Self> {
+        if !entry.success {
+            return None;
+        }
+        let kind = match entry.action {
+            JournalAction::Install => RecentKind::Installed,
+            JournalAction::Uninstall => RecentKind::Removed,
+            JournalAction::Update | JournalAction::SelfUpdate | JournalAction::Rollback => {
+                RecentKind::Updated
+            }
+            JournalAction::Pin
+            | JournalAction::Unpin
+            | JournalAction::Hook
+            | JournalAction::Skipped => return None,
+        };
+        Some(Self {
+            timestamp: entry.timestamp,
+            manager: entry.manager,
+            package: entry.package,
+            kind,
+            from_version: entry.from_version,
+            to_version: entry.to_version,
+            external: false,
+        })
+    }
+
+    /// Whether this journal entry explains a change a later scan noticed
+    fn explains(&self, detected: &RecentChange) -> bool {
+        let same_version = match (&self.to_version, &detected.to_version) {
+            (Some(ours), Some(seen)) => ours == seen,
+            _ => true,
+        };
+        self.manager == detected.manager
+            && self.package == detected.package
+            && self.kind == detected.kind
+            && self.timestamp <= detected.timestamp
+            && same_version
+    }
+}
+
+fn detected_path() -> PathBuf {
+    data_dir().join("detected_changes.jsonl")
+}
+
+/// detected_changes.jsonl is cut back to its newest `DETECTED_KEEP` lines once it passes this
+const DETECTED_MAX_BYTES: u64 = 2 * 1024 * 1024;
+const DETECTED_KEEP: usize = 5_000;
+
+/// Append what a local scan found changed since the previous one. Changes DepMgr made
+/// itself are recorded too and matched against the journal when read back.
+pub fn record_detected(changes: &[Change]) {
+    let now = Utc::now();
+    let lines: Vec<String> = changes
+        .iter()
+        .filter_map(|change| {
+            let (kind, from_version, to_version) = match &change.kind {
+                ChangeKind::Installed => (RecentKind::Installed, None, None),
+                ChangeKind::Removed => (RecentKind::Removed, None, None),
+                ChangeKind::VersionChanged { from, to } => {
+                    (RecentKind::Updated, Some(from.clone()), Some(to.clone()))
+                }
+                ChangeKind::NewlyOutdated { .. } => return None,
+            };
+            let recent = RecentChange {
+                timestamp: now,
+                manager: change.manager.clone(),
+                package: change.name.clone(),
+                kind,
+                from_version,
+                to_version,
+                external: true,
+            };
+            serde_json::to_string(&recent).ok()
+        })
+        .collect();
+    if lines.is_empty() {
+        return;
+    }
+
+    let path = detected_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let result = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| writeln!(file, "{}", lines.join("\n")));
+    if let Err(e) = result {
+        eprintln!("[ERROR] Failed to write {}: {}", path.display(), e);
+    }
+    if std::fs::metadata(&path).is_ok_and(|m| m.len() > DETECTED_MAX_BYTES) {
+        prune_detected(&path);
+    }
+}
+
+/// Keep the newest `DETECTED_KEEP` lines, replacing the file in one rename
+fn prune_detected(path: &Path) {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return;
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    let kept = lines[lines.len().saturating_sub(DETECTED_KEEP)..].join("\n") + "\n";
+    let tmp = path.with_extension("jsonl.tmp");
+    let result = std::fs::write(&tmp, kept).and_then(|()| std::fs::rename(&tmp, path));
+    match result {
+        Ok(()) => println!(
+            "[RECENT] Pruned {} to its newest {} changes",
+            path.display(),
+            DETECTED_KEEP
+        ),
+        Err(e) => eprintln!("[ERROR] Failed to prune {}: {}", path.display(), e),
+    }
+}
+
+/// Everything installed, updated or removed since `since`, newest first
+pub fn changes_since(since: DateTime<Utc>) -> Vec<RecentChange> {
+    let detected = std::fs::read_to_string(detected_path())
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    merge(&read_journal(), detected, since)
+}
+
+/// Journal entries plus detected changes the journal doesn't account for
+fn merge(
+    journal: &[JournalEntry],
+    detected: Vec<RecentChange>,
+    since: DateTime<Utc>,
+) -> Vec<RecentChange> {
+    let mut changes: Vec<RecentChange> = journal
+        .iter()
+        .filter(|entry| entry.timestamp >= since)
+        .cloned()
+        .filter_map(RecentChange::from_journal)
+        .collect();
+    let external: Vec<RecentChange> = detected
+        .into_iter()
+        .filter(|change| change.timestamp >= since)
+        .filter(|change| !changes.iter().any(|ours| ours.explains(change)))
+        .collect();
+    changes.extend(external);
+    changes.sort_by_key(|change| std::cmp::Reverse(change.timestamp));
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn detected_changes_made_by_depmgr_are_not_repeated() {
+        let now = Utc::now();
+        let mut update =
+            JournalEntry::new(JournalAction::Update, PackageManager::Npm, "vite".into())
+                .with_versions(Some("5.0.0".into()), Some("5.1.0".into()));
+        update.timestamp = now - Duration::hours(2);
+        update.success = true;
+        let mut old = JournalEntry::new(JournalAction::Install, PackageManager::Npm, "tsx".into());
+        old.timestamp = now - Duration::days(30);
+        old.success = true;
+
+        let seen = |package: &str, to: &str| RecentChange {
+            timestamp: now - Duration::hours(1),
+            manager: PackageManager::Npm,
+            package: package.into(),
+            kind: RecentKind::Updated,
+            from_version: Some("5.0.0".into()),
+            to_version: Some(to.into()),
+            external: true,
+        };
+        let detected = vec![seen("vite", "5.1.0"), seen("eslint", "9.0.0")];
+
+        let changes = merge(&[update, old], detected, now - Duration::days(7));
+        let summary: Vec<(&str, bool)> = changes
+            .iter()
+            .map(|c| (c.package.as_str(), c.external))
+            .collect();
+        assert_eq!(summary, [("eslint", true), ("vite", false)]);
+        assert_eq!(changes[1].describe(), "5.0.0 ➡ 5.1.0");
+    }
+}
@@ -0,0 +1,386 @@
+use super::update_everything::{Step, StepAction, StepStatus};
+use crate::managers::pip::normalize_name;
+use crate::models::{Package, PackageManager};
+use crate::utils::run_command_with_timeout;
+use crate::utils::version::compare_versions;
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Come with the runtime itself, never worth reinstalling
+const BUNDLED: [&str; 5] = ["pip", "setuptools", "wheel", "npm", "corepack"];
+
+/// A global pip/npm package that was installed for an older Python/Node and is no longer
+/// visible to the current one
+#[derive(Debug, Clone)]
+pub struct StrandedTool {
+    pub manager: PackageManager,
+    pub name: String,
+    pub old_runtime: String,
+    pub location: PathBuf,
+}
+
+async fn stdout_lines(cmd: &str, args: &[&str]) -> Vec<String> {
+    match run_command_with_timeout(cmd, args, Duration::from_secs(10)).await {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|l| l.trim().to_string())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The same directory for other runtime versions, older than `version`: the version in
+/// `current` is swapped for a wildcard (`lib/python3.12/` → `lib/python3.*/`,
+/// `node/v22.1.0/` → `node/v*/`). Returns each with the version it belongs to.
+fn older_siblings(current: &Path, version: &str, wildcard: &str) -> Vec<(String, PathBuf)> {
+    let current_str = current.to_string_lossy();
+    let Some(at) = current_str.find(version) else {
+        return Vec::new();
+    };
+    let pattern = format!(
+        "{}{}{}",
+        glob::Pattern::escape(&current_str[..at]),
+        wildcard,
+        glob::Pattern::escape(&current_str[at + version.len()..])
+    );
+    let Ok(paths) = glob::glob(&pattern) else {
+        return Vec::new();
+    };
+
+    let prefix_len = at - current_str[..at].rfind('/').map_or(0, |i| i + 1);
+    paths
+        .flatten()
+        .filter(|p| p != current)
+        .filter_map(|p| {
+            // The path component that differs, minus whatever came before the version
+            let component = p
+                .components()
+                .zip(current.components())
+                .find(|(a, b)| a != b)?
+                .0
+                .as_os_str()
+                .to_string_lossy()
+                .to_string();
+            let other = component.get(prefix_len..)?.to_string();
+            (compare_versions(
+                other.trim_start_matches('v'),
+                version.trim_start_matches('v'),
+            ) == Ordering::Less)
+                .then_some((other, p))
+        })
+        .collect()
+}
+
+/// A `*.dist-info` directory's project name: `Name:` in its METADATA, falling back to the
+/// directory name (which escapes `-` and `.`, so `zope.interface` is `zope_interface-…`)
+fn dist_name(dist_info: &Path) -> Option<String> {
+    let stem = dist_info
+        .file_name()?
+        .to_str()?
+        .strip_suffix(".dist-info")?;
+    let metadata = std::fs::read_to_string(dist_info.join("METADATA")).unwrap_or_default();
+    let from_metadata = metadata
+        .lines()
+        .take_while(|line| !line.is_empty())
+        .find_map(|line| line.strip_prefix("Name:"))
+        .map(|name| name.trim().to_string());
+    from_metadata.or_else(|| Some(stem.split_once('-')?.0.to_string()))
+}
+
+/// Distribution names in a site-packages directory
+fn site_packages(dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|e| dist_name(&e.path()))
+        .collect()
+}
+
+/// Package directories in a global node_modules, scoped ones as `@scope/name`
+fn node_modules(dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut names = Vec::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') {
+            continue;
+        }
+        if name.starts_with('@') {
+            for scoped in std::fs::read_dir(entry.path())
+                .into_iter()
+                .flatten()
+                .flatten()
+            {
+                names.push(format!("{}/{}", name, scoped.file_name().to_string_lossy()));
+            }
+        } else {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Distributions in a site-packages directory that install commands (`console_scripts`),
+/// i.e. tools rather than libraries
+fn site_tools(dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|e| {
+            std::fs::read_to_string(e.path().join("entry_points.txt"))
+                .is_ok_and(|entry_points| entry_points.contains("[console_scripts]"))
+        })
+        .filter_map(|e| dist_name(&e.path()))
+        .collect()
+}
+
+/// The classic "brew upgraded python and my pip tools vanished": tools recorded in an older
+/// `python3.X` site-packages under the Homebrew prefix that the current `python3` doesn't
+/// have. Homebrew's Python is externally managed, so they migrate through pipx if present.
+async fn stranded_homebrew_python(installed: &[Package]) -> (Vec<StrandedTool>, Option<PathBuf>) {
+    let Some(prefix) = stdout_lines("brew", &["--prefix"]).await.into_iter().next() else {
+        return (Vec::new(), None);
+    };
+    let prefix = PathBuf::from(prefix);
+    let python = prefix.join("bin/python3");
+    let Some(version) = stdout_lines(
+        &python.to_string_lossy(),
+        &["-c", "import sys; print('%d.%d' % sys.version_info[:2])"],
+    )
+    .await
+    .into_iter()
+    .next() else {
+        return (Vec::new(), Some(prefix));
+    };
+
+    let current_site = prefix
+        .join("lib")
+        .join(format!("python{}", version))
+        .join("site-packages");
+    let current: Vec<String> = site_packages(&current_site)
+        .iter()
+        .chain(
+            installed
+                .iter()
+                .filter(|p| matches!(p.manager, PackageManager::Pip | PackageManager::Pipx))
+                .map(|p| &p.name),
+        )
+        .map(|name| normalize_name(name))
+        .collect();
+    let pipx_installed = stdout_lines("pipx", &["list", "--short"]).await;
+    let pipx = crate::utils::command_exists("pipx").await;
+
+    let mut stranded = Vec::new();
+    for (old, dir) in older_siblings(&current_site, &version, "3.*") {
+        // python@3.X may have been uninstalled since, which is when its tools vanish
+        let cellar = prefix.join("Cellar").join(format!("python@{}", old));
+        let old_runtime = if cellar.exists() {
+            format!("Homebrew Python {}", old)
+        } else {
+            format!("Homebrew Python {} (uninstalled)", old)
+        };
+        for name in site_tools(&dir) {
+            let normalized = normalize_name(&name);
+            let in_pipx = pipx_installed.iter().any(|line| {
+                line.split_whitespace().next().map(normalize_name) == Some(normalized.clone())
+            });
+            if current.contains(&normalized) || in_pipx || BUNDLED.contains(&normalized.as_str()) {
+                continue;
+            }
+            stranded.push(StrandedTool {
+                manager: if pipx {
+                    PackageManager::Pipx
+                } else {
+                    PackageManager::Pip
+                },
+                name,
+                old_runtime: old_runtime.clone(),
+                location: dir.clone(),
+            });
+        }
+    }
+    (stranded, Some(prefix))
+}
+
+async fn stranded_python(installed: &[Package], skip_under: Option<&Path>) -> Vec<StrandedTool> {
+    let lines = stdout_lines(
+        "python3",
+        &[
+            "-c",
+            "import site, sys, sysconfig; print('%d.%d' % sys.version_info[:2]); \
+             print(sysconfig.get_paths()['purelib']); print(site.getusersitepackages())",
+        ],
+    )
+    .await;
+    let [version, site_dirs @ ..] = lines.as_slice() else {
+        return Vec::new();
+    };
+    let current: Vec<String> = installed
+        .iter()
+        .filter(|p| p.manager == PackageManager::Pip)
+        .map(|p| normalize_name(&p.name))
+        .collect();
+
+    let mut stranded = Vec::new();
+    for site_dir in site_dirs {
+        // Homebrew's site-packages get the more specific check above
+        if skip_under.is_some_and(|prefix| Path::new(site_dir).starts_with(prefix)) {
+            continue;
+        }
+        for (old, dir) in older_siblings(Path::new(site_dir), version, "3.*") {
+            for name in site_packages(&dir) {
+                let normalized = normalize_name(&name);
+                if current.contains(&normalized) || BUNDLED.contains(&normalized.as_str()) {
+                    continue;
+                }
+                stranded.push(StrandedTool {
+                    manager: PackageManager::Pip,
+                    name,
+                    old_runtime: format!("Python {}", old),
+                    location: dir.clone(),
+                });
+            }
+        }
+    }
+    stranded
+}
+
+async fn stranded_node(installed: &[Package]) -> Vec<StrandedTool> {
+    let (Some(version), Some(root)) = (
+        stdout_lines("node", &["--version"])
+            .await
+            .into_iter()
+            .next(),
+        stdout_lines("npm", &["root", "-g"])
+            .await
+            .into_iter()
+            .next(),
+    ) else {
+        return Vec::new();
+    };
+
+    let mut stranded = Vec::new();
+    // Version managers (nvm, fnm, asdf) keep globals per Node version
+    for (old, dir) in older_siblings(Path::new(&root), &version, "v*") {
+        for name in node_modules(&dir) {
+            let current = installed
+                .iter()
+                .any(|p| p.manager == PackageManager::Npm && p.name == name);
+            if current || BUNDLED.contains(&name.as_str()) {
+                continue;
+            }
+            stranded.push(StrandedTool {
+                manager: PackageManager::Npm,
+                name,
+                old_runtime: format!("Node {}", old),
+                location: dir.clone(),
+            });
+        }
+    }
+    stranded
+}
+
+/// Global tools left behind by a Python or Node upgrade (local machine only)
+pub async fn find_stranded(installed: &[Package]) -> Vec<StrandedTool> {
+    let (mut stranded, brew_prefix) = stranded_homebrew_python(installed).await;
+    stranded.extend(stranded_python(installed, brew_prefix.as_deref()).await);
+    stranded.extend(stranded_node(installed).await);
+    // The same tool may be stranded under several old versions
+    stranded.sort_by(|a, b| (a.manager.name(), &a.name).cmp(&(b.manager.name(), &b.name)));
+    stranded.dedup_by(|a, b| a.manager == b.manager && a.name == b.name);
+    if !stranded.is_empty() {
+        println!(
+            "[RUNTIME] {} global tools were installed for an older runtime",
+            stranded.len()
+        );
+    }
+    stranded
+}
+
+/// One reinstall step per stranded tool, for the batch runner
+pub fn plan(stranded: &[StrandedTool]) -> Vec<Step> {
+    stranded
+        .iter()
+        .map(|tool| Step {
+            label: format!(
+                "{} with {} (was on {})",
+                tool.name,
+                tool.manager.name(),
+                tool.old_runtime
+            ),
+            action: StepAction::Reinstall {
+                manager: tool.manager.clone(),
+                name: tool.name.clone(),
+            },
+            status: StepStatus::Pending,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_dir::TestDir;
+
+    #[test]
+    fn finds_older_runtime_directories() {
+        let root = TestDir::new("runtime");
+        for version in ["v18.20.1", "v20.11.0", "v22.1.0", "v23.0.0"] {
+            std::fs::create_dir_all(root.join(version).join("lib/node_modules")).unwrap();
+        }
+        let current = root.join("v22.1.0/lib/node_modules");
+
+        let mut older: Vec<String> = older_siblings(&current, "v22.1.0", "v*")
+            .into_iter()
+            .map(|(version, _)| version)
+            .collect();
+        older.sort();
+
+        assert_eq!(older, ["v18.20.1", "v20.11.0"]);
+    }
+
+    #[test]
+    fn only_distributions_with_commands_are_tools() {
+        let site = TestDir::new("site");
+        let dist = |name: &str, entry_points: Option<&str>| {
+            let dir = site.join(format!("{}.dist-info", name));
+            std::fs::create_dir_all(&dir).unwrap();
+            if let Some(entry_points) = entry_points {
+                std::fs::write(dir.join("entry_points.txt"), entry_points).unwrap();
+            }
+        };
+        dist(
+            "black-24.4.2",
+            Some("[console_scripts]\nblack = black:patched_main\n"),
+        );
+        dist("pluggy-1.5.0", Some("[pytest11]\n"));
+        dist("requests-2.32.3", None);
+
+        let tools = site_tools(&site);
+
+        assert_eq!(tools, ["black"]);
+    }
+
+    #[test]
+    fn names_come_from_metadata() {
+        let site = TestDir::new("metadata");
+        site.write(
+            "zope_interface-6.4.dist-info/METADATA",
+            "Metadata-Version: 2.1\nName: zope.interface\nVersion: 6.4\n\nName: not a header\n",
+        );
+        site.write("Django-5.0.dist-info/RECORD", "");
+
+        let mut names = site_packages(&site);
+        names.sort();
+        assert_eq!(names, ["Django", "zope.interface"]);
+        assert_eq!(normalize_name("zope.interface"), "zope-interface");
+        assert_eq!(normalize_name("Zope__Interface"), "zope-interface");
+    }
+}
@@ -0,0 +1,411 @@
+use crate::config::SyncSettings;
+use crate::models::{CategoryOverrides, PackageFlags};
+use crate::utils::paths::data_dir;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+/// Name of the synced file, in the repository and in the gist
+const SYNC_FILE: &str = "depmgr-metadata.toml";
+
+/// What's synced between machines: decisions about packages, not the inventory or
+/// machine-specific settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncedMetadata {
+    pub flags: PackageFlags,
+    pub categories: CategoryOverrides,
+}
+
+impl SyncedMetadata {
+    fn local() -> Self {
+        Self {
+            flags: PackageFlags::load(),
+            categories: CategoryOverrides::load(),
+        }
+    }
+
+    /// Three-way merge against the last synced state, returning the category overrides
+    /// both sides changed differently (the local choice is kept)
+    fn merge(base: &Self, local: &Self, remote: &Self) -> (Self, Vec<String>) {
+        let flags = PackageFlags::merge(&base.flags, &local.flags, &remote.flags);
+        let (categories, conflicts) =
+            CategoryOverrides::merge(&base.categories, &local.categories, &remote.categories);
+        (Self { flags, categories }, conflicts)
+    }
+}
+
+/// What a push or pull did, for the status bar
+pub struct SyncOutcome {
+    pub pushed: bool,
+    pub conflicts: Vec<String>,
+}
+
+impl SyncOutcome {
+    pub fn describe(&self) -> String {
+        let done = if self.pushed {
+            "Metadata pushed"
+        } else {
+            "Metadata pulled"
+        };
+        if self.conflicts.is_empty() {
+            done.to_string()
+        } else {
+            format!(
+                "{}; kept this machine's category for {} (changed on both)",
+                done,
+                self.conflicts.join(", ")
+            )
+        }
+    }
+}
+
+enum Remote<'a> {
+    Git(&'a str),
+    Gist(&'a str),
+}
+
+fn remote(settings: &SyncSettings) -> Result<Remote<'_>> {
+    match (&settings.git_repo, &settings.gist) {
+        (Some(repo), _) => Ok(Remote::Git(repo)),
+        (None, Some(gist)) => Ok(Remote::Gist(gist)),
+        (None, None) => Err(anyhow!("Set git_repo or gist under [sync] in config.toml")),
+    }
+}
+
+/// The state both sides agreed on at the last sync
+fn base_path() -> PathBuf {
+    data_dir().join("sync_base.toml")
+}
+
+fn load_base() -> SyncedMetadata {
+    std::fs::read_to_string(base_path())
+        .ok()
+        .and_then(|content| toml::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_base(metadata: &SyncedMetadata) -> Result<()> {
+    let path = base_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, toml::to_string_pretty(metadata)?)?;
+    Ok(())
+}
+
+/// Run on this machine even when another target is selected: the clone and the
+/// metadata being synced live here
+async fn run(cmd: &str, args: &[&str], timeout: Duration) -> Result<String> {
+    let output = tokio::time::timeout(
+        timeout,
+        tokio::process::Command::new(cmd)
+            .args(args)
+            .stdin(Stdio::null())
+            .output(),
+    )
+    .await
+    .map_err(|_| anyhow!("{} {} timed out", cmd, args.join(" ")))?
+    .map_err(|e| anyhow!("Failed to run {}: {}", cmd, e))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!(
+            "{} {} failed: {}",
+            cmd,
+            args.join(" "),
+            stderr.trim()
+        ))
+    }
+}
+
+/// Local clone of the sync repository
+fn clone_dir() -> PathBuf {
+    data_dir().join("sync_repo")
+}
+
+/// Bring the clone up to date with the remote, discarding anything never pushed
+async fn git_fetch(url: &str) -> Result<PathBuf> {
+    let dir = clone_dir();
+    let path = dir.to_string_lossy().to_string();
+    if !dir.join(".git").exists() {
+        println!("[SYNC] Cloning {}", url);
+        let args = crate::utils::git::clone_args(url, &path, &["--quiet"])?;
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        run("git", &args, Duration::from_secs(120)).await?;
+        return Ok(dir);
+    }
+    run(
+        "git",
+        &["-C", &path, "fetch", "--quiet", "origin"],
+        Duration::from_secs(120),
+    )
+    .await?;
+    // A brand-new empty repository has no upstream branch yet
+    if run(
+        "git",
+        &["-C", &path, "rev-parse", "--verify", "--quiet", "@{u}"],
+        Duration::from_secs(10),
+    )
+    .await
+    .is_ok()
+    {
+        run(
+            "git",
+            &["-C", &path, "reset", "--quiet", "--hard", "@{u}"],
+            Duration::from_secs(30),
+        )
+        .await?;
+    }
+    Ok(dir)
+}
+
+async fn git_push(dir: &Path, content: &str) -> Result<()> {
+    std::fs::write(dir.join(SYNC_FILE), content)?;
+    let path = dir.to_string_lossy().to_string();
+    run(
+        "git",
+        &["-C", &path, "add", SYNC_FILE],
+        Duration::from_secs(30),
+    )
+    .await?;
+    let unchanged = run(
+        "git",
+        &["-C", &path, "diff", "--cached", "--quiet"],
+        Duration::from_secs(30),
+    )
+    .await
+    .is_ok();
+    if unchanged {
+        return Ok(());
+    }
+    run(
+        "git",
+        &[
+            "-C",
+            &path,
+            "commit",
+            "--quiet",
+            "-m",
+            "Update depmgr metadata",
+        ],
+        Duration::from_secs(30),
+    )
+    .await?;
+    run(
+        "git",
+        &["-C", &path, "push", "--quiet", "origin", "HEAD"],
+        Duration::from_secs(120),
+    )
+    .await
+    .map_err(|e| anyhow!("{} (another machine may have pushed; pull first)", e))?;
+    Ok(())
+}
+
+async fn gist_read(id: &str) -> Result<Option<String>> {
+    let stdout = run(
+        "gh",
+        &["api", &format!("gists/{}", id)],
+        Duration::from_secs(60),
+    )
+    .await?;
+    let json: serde_json::Value = serde_json::from_str(&stdout)?;
+    Ok(json["files"][SYNC_FILE]["content"]
+        .as_str()
+        .map(String::from))
+}
+
+/// A new directory in the temp dir that only this user can open, with a random name so
+/// nobody can guess it or create it first
+fn private_temp_dir() -> Result<PathBuf> {
+    use std::hash::{BuildHasher, Hasher};
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u32(std::process::id());
+    let dir = std::env::temp_dir().join(format!("depmgr-gist-{:016x}", hasher.finish()));
+
+    let mut builder = std::fs::DirBuilder::new();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder
+        .create(&dir)
+        .map_err(|e| anyhow!("Failed to create {}: {}", dir.display(), e))?;
+    Ok(dir)
+}
+
+async fn gist_write(id: &str, content: &str) -> Result<()> {
+    let body = serde_json::json!({ "files": { SYNC_FILE: { "content": content } } });
+    let dir = private_temp_dir()?;
+    let input = dir.join("gist.json");
+    if let Err(e) = std::fs::write(&input, body.to_string()) {
+        let _ = std::fs::remove_dir_all(&dir);
+        return Err(e.into());
+    }
+    let result = run(
+        "gh",
+        &[
+            "api",
+            "--method",
+            "PATCH",
+            &format!("gists/{}", id),
+            "--input",
+            &input.to_string_lossy(),
+        ],
+        Duration::from_secs(60),
+    )
+    .await;
+    let _ = std::fs::remove_dir_all(&dir);
+    result.map(|_| ())
+}
+
+fn parse_remote(content: Option<String>) -> Result<Option<SyncedMetadata>> {
+    content
+        .map(|content| {
+            toml::from_str(&content).map_err(|e| anyhow!("Invalid synced {}: {}", SYNC_FILE, e))
+        })
+        .transpose()
+}
+
+/// Merge the remote metadata into this machine's
+pub async fn pull(settings: &SyncSettings) -> Result<SyncOutcome> {
+    sync(settings, false).await
+}
+
+/// Merge with the remote first, then publish the result
+pub async fn push(settings: &SyncSettings) -> Result<SyncOutcome> {
+    sync(settings, true).await
+}
+
+/// What both sides now agree on: the merged state once it's pushed, but only the remote's
+/// after a pull, so local changes it doesn't have yet aren't read as remote deletions later
+fn base_after<'a>(
+    pushed: bool,
+    merged: &'a SyncedMetadata,
+    theirs: Option<&'a SyncedMetadata>,
+) -> Option<&'a SyncedMetadata> {
+    if pushed {
+        Some(merged)
+    } else {
+        theirs
+    }
+}
+
+async fn sync(settings: &SyncSettings, publish: bool) -> Result<SyncOutcome> {
+    let remote = remote(settings)?;
+    println!(
+        "[SYNC] {} metadata",
+        if publish { "Pushing" } else { "Pulling" }
+    );
+
+    let (clone, content) = match remote {
+        Remote::Git(url) => {
+            let dir = git_fetch(url).await?;
+            let content = std::fs::read_to_string(dir.join(SYNC_FILE)).ok();
+            (Some(dir), content)
+        }
+        Remote::Gist(id) => (None, gist_read(id).await?),
+    };
+
+    let local = SyncedMetadata::local();
+    let theirs = parse_remote(content)?;
+    let (merged, conflicts) = match &theirs {
+        Some(theirs) => SyncedMetadata::merge(&load_base(), &local, theirs),
+        None => (local, Vec::new()), // Nothing synced yet
+    };
+    merged.flags.save();
+    merged.categories.save();
+
+    if publish {
+        let content = toml::to_string_pretty(&merged)?;
+        match (&remote, clone) {
+            (Remote::Gist(id), _) => gist_write(id, &content).await?,
+            (Remote::Git(_), Some(dir)) => git_push(&dir, &content).await?,
+            (Remote::Git(url), None) => {
+                return Err(anyhow!("No local clone of {} to push from", url))
+            }
+        }
+    }
+    if let Some(base) = base_after(publish, &merged, theirs.as_ref()) {
+        save_base(base)?;
+    }
+
+    if !conflicts.is_empty() {
+        println!("[SYNC] Conflicting category overrides: {:?}", conflicts);
+    }
+    Ok(SyncOutcome {
+        pushed: publish,
+        conflicts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_from_both_machines_combine() {
+        let parse = |toml: &str| -> SyncedMetadata { toml::from_str(toml).unwrap() };
+        let base = parse(
+            r#"
+            [flags]
+            pinned = ["npm/typescript", "Cargo/ripgrep"]
+            [categories]
+            "Homebrew/ffmpeg" = "Cli"
+            "#,
+        );
+        // This machine unpinned ripgrep and recategorized ffmpeg
+        let local = parse(
+            r#"
+            [flags]
+            pinned = ["npm/typescript"]
+            [categories]
+            "Homebrew/ffmpeg" = "Library"
+            "#,
+        );
+        // The other pinned node and recategorized ffmpeg differently
+        let remote = parse(
+            r#"
+            [flags]
+            pinned = ["npm/typescript", "Cargo/ripgrep", "Homebrew/node"]
+            [categories]
+            "Homebrew/ffmpeg" = "GuiApp"
+            "#,
+        );
+
+        let (merged, conflicts) = SyncedMetadata::merge(&base, &local, &remote);
+        let pinned = |manager, name| {
+            merged
+                .flags
+                .is_pinned(&crate::models::Package::new(name, manager, "1.0"))
+        };
+        use crate::models::PackageManager;
+        assert!(pinned(PackageManager::Npm, "typescript"));
+        assert!(!pinned(PackageManager::Cargo, "ripgrep"));
+        assert!(pinned(PackageManager::Homebrew, "node"));
+        assert_eq!(conflicts, ["Homebrew/ffmpeg"]);
+    }
+
+    #[test]
+    fn unpushed_local_changes_survive_pulls() {
+        let parse = |toml: &str| -> SyncedMetadata { toml::from_str(toml).unwrap() };
+        let remote = parse("[flags]\npinned = [\"npm/typescript\"]\n");
+        let mut base = remote.clone();
+        // Pinned here, never pushed
+        let mut local = parse("[flags]\npinned = [\"npm/typescript\", \"Cargo/ripgrep\"]\n");
+
+        for _ in 0..2 {
+            let (merged, _) = SyncedMetadata::merge(&base, &local, &remote);
+            base = base_after(false, &merged, Some(&remote)).unwrap().clone();
+            local = merged;
+        }
+
+        let ripgrep =
+            crate::models::Package::new("ripgrep", crate::models::PackageManager::Cargo, "1.0");
+        assert!(local.flags.is_pinned(&ripgrep));
+    }
+}
@@ -0,0 +1,236 @@
+use super::{JournalAction, JournalEntry};
+use crate::models::{Package, PackageManager};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+#[derive(Debug, Clone)]
+pub enum StepAction {
+    SelfUpdate(PackageManager),
+    Upgrade {
+        manager: PackageManager,
+        name: String,
+        from: String,
+        to: Option<String>,
+    },
+    /// Install again with the current runtime (tools stranded by a Python/Node upgrade)
+    Reinstall {
+        manager: PackageManager,
+        name: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StepStatus {
+    Pending,
+    Running,
+    Done,
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Step {
+    pub label: String,
+    pub action: StepAction,
+    pub status: StepStatus,
+}
+
+/// Progress of an "Update Everything" run, shared between the runner and the UI
+#[derive(Debug, Default)]
+pub struct UpdateEverythingRun {
+    pub steps: Vec<Step>,
+    pub running: bool,
+    /// Journal entries written by this run, newest last (powers the rollback buttons)
+    pub journal: Vec<JournalEntry>,
+}
+
+impl UpdateEverythingRun {
+    pub fn completed_steps(&self) -> usize {
+        self.steps
+            .iter()
+            .filter(|s| matches!(s.status, StepStatus::Done | StepStatus::Failed(_)))
+            .count()
+    }
+
+    pub fn failed_steps(&self) -> usize {
+        self.steps
+            .iter()
+            .filter(|s| matches!(s.status, StepStatus::Failed(_)))
+            .count()
+    }
+
+    /// Bytes still to download for pending upgrades with a known size (Homebrew bottles),
+    /// and how many upgrades that covers
+    pub fn download_estimate(&self, packages: &[Package]) -> (u64, usize) {
+        self.steps
+            .iter()
+            .filter(|s| s.status == StepStatus::Pending)
+            .filter_map(|s| match &s.action {
+                StepAction::Upgrade { manager, name, .. } => {
+                    packages
+                        .iter()
+                        .find(|p| &p.manager == manager && &p.name == name)?
+                        .download_size
+                }
+                StepAction::SelfUpdate(_) | StepAction::Reinstall { .. } => None,
+            })
+            .fold((0, 0), |(bytes, count), size| (bytes + size, count + 1))
+    }
+}
+
+/// Manager self-updates go first so package upgrades run with fresh metadata and
+/// current tooling; runtimes (Homebrew) upgrade before the tools installed on top of them.
+const MANAGER_ORDER: [PackageManager; 31] = [
+    PackageManager::Homebrew,
+    PackageManager::Mas,
+    PackageManager::Nix,
+    PackageManager::Winget,
+    PackageManager::Scoop,
+    PackageManager::Chocolatey,
+    PackageManager::Mise,
+    PackageManager::Asdf,
+    PackageManager::Ghcup,
+    PackageManager::Stack,
+    PackageManager::Opam,
+    PackageManager::Cargo,
+    PackageManager::Npm,
+    PackageManager::Pip,
+    PackageManager::Pipx,
+    PackageManager::Uv,
+    PackageManager::Poetry,
+    PackageManager::Gem,
+    PackageManager::Composer,
+    PackageManager::Go,
+    PackageManager::LuaRocks,
+    PackageManager::Julia,
+    PackageManager::R,
+    PackageManager::Tfenv,
+    PackageManager::HelmPlugins,
+    PackageManager::Krew,
+    PackageManager::GhExtensions,
+    PackageManager::OhMyZsh,
+    PackageManager::Zinit,
+    PackageManager::Fisher,
+    PackageManager::Fonts,
+];
+
+/// Within a manager, lowest-risk upgrades come first so the safe ones land even if a
+/// risky one fails
+pub fn plan(
+    available: &[PackageManager],
+    packages: &[Package],
+    risk: impl Fn(&Package) -> u8,
+) -> Vec<Step> {
+    let managers: Vec<&PackageManager> = MANAGER_ORDER
+        .iter()
+        .filter(|m| available.contains(m))
+        .collect();
+
+    let mut steps = Vec::new();
+
+    for manager in &managers {
+        let label = match manager {
+            PackageManager::Homebrew => "brew update",
+            PackageManager::Cargo => "rustup update",
+            PackageManager::Npm => "npm install -g npm@latest",
+            PackageManager::Pip => "python3 -m pip install --upgrade pip",
+            PackageManager::Krew => "kubectl krew update",
+            _ => continue,
+        };
+        steps.push(Step {
+            label: label.to_string(),
+            action: StepAction::SelfUpdate((*manager).clone()),
+            status: StepStatus::Pending,
+        });
+    }
+
+    for manager in &managers {
+        let mut outdated: Vec<&Package> = packages
+            .iter()
+            .filter(|p| &p.manager == *manager && p.is_outdated)
+            .collect();
+        outdated.sort_by_cached_key(|p| (risk(p), p.name.clone()));
+
+        for pkg in outdated {
+            steps.push(Step {
+                label: format!(
+                    "{} {}: {} ➡ {}",
+                    manager.name(),
+                    pkg.name,
+                    pkg.installed_version,
+                    pkg.latest_version.as_deref().unwrap_or("latest")
+                ),
+                action: StepAction::Upgrade {
+                    manager: (*manager).clone(),
+                    name: pkg.name.clone(),
+                    from: pkg.installed_version.clone(),
+                    to: pkg.latest_version.clone(),
+                },
+                status: StepStatus::Pending,
+            });
+        }
+    }
+
+    steps
+}
+
+/// Run every pending step in order. A failed step doesn't stop the run,
+/// the way topgrade keeps going and reports failures at the end.
+pub async fn execute(run: Arc<RwLock<UpdateEverythingRun>>) {
+    let step_count = run.read().await.steps.len();
+    println!("[UPDATE-ALL] Running {} steps", step_count);
+
+    for idx in 0..step_count {
+        let action = {
+            let mut state = run.write().await;
+            if state.steps[idx].status != StepStatus::Pending {
+                continue;
+            }
+            state.steps[idx].status = StepStatus::Running;
+            state.steps[idx].action.clone()
+        };
+
+        let entry = match &action {
+            StepAction::SelfUpdate(manager) => {
+                let result = super::self_update(manager).await;
+                JournalEntry::new(
+                    JournalAction::SelfUpdate,
+                    manager.clone(),
+                    manager.command().to_string(),
+                )
+                .finish(&result)
+            }
+            StepAction::Upgrade {
+                manager,
+                name,
+                from,
+                to,
+            } => {
+                let result = super::update_package(manager, name.clone()).await;
+                JournalEntry::new(JournalAction::Update, manager.clone(), name.clone())
+                    .with_versions(Some(from.clone()), to.clone())
+                    .finish(&result)
+            }
+            StepAction::Reinstall { manager, name } => {
+                let result = super::install_package(manager, name.clone()).await;
+                JournalEntry::new(JournalAction::Install, manager.clone(), name.clone())
+                    .finish(&result)
+            }
+        };
+
+        let mut state = run.write().await;
+        state.steps[idx].status = match &entry.message {
+            None => StepStatus::Done,
+            Some(message) => StepStatus::Failed(message.clone()),
+        };
+        state.journal.push(entry);
+    }
+
+    let mut state = run.write().await;
+    state.running = false;
+    let failed = state.failed_steps();
+    println!(
+        "[UPDATE-ALL] Finished: {} steps, {} failed",
+        state.steps.len(),
+        failed
+    );
+}
@@ -0,0 +1,137 @@
+use crate::models::Package;
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+/// Counts a policy can be written against
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Metric {
+    Outdated,
+    Vulnerable,
+    Unused,
+    Mismatched, // tools too old for a project's declared version
+}
+
+impl Metric {
+    fn parse(name: &str) -> Result<Self> {
+        match name {
+            "outdated" => Ok(Metric::Outdated),
+            "vulnerable" => Ok(Metric::Vulnerable),
+            "unused" => Ok(Metric::Unused),
+            "mismatched" => Ok(Metric::Mismatched),
+            _ => Err(anyhow!(
+                "Unknown metric '{}' (expected outdated, vulnerable, unused or mismatched)",
+                name
+            )),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Metric::Outdated => "outdated",
+            Metric::Vulnerable => "vulnerable",
+            Metric::Unused => "unused",
+            Metric::Mismatched => "mismatched",
+        }
+    }
+}
+
+/// One `--fail-on` rule: "vulnerable" (any), "outdated>10", "unused>=50"
+#[derive(Debug, Clone)]
+pub struct Threshold {
+    pub metric: Metric,
+    pub inclusive: bool,
+    pub limit: usize,
+}
+
+impl Threshold {
+    pub fn parse(rule: &str) -> Result<Self> {
+        let rule = rule.trim();
+        let (name, inclusive, limit) = if let Some((name, limit)) = rule.split_once(">=") {
+            (name, true, limit)
+        } else if let Some((name, limit)) = rule.split_once('>') {
+            (name, false, limit)
+        } else {
+            (rule, false, "0")
+        };
+
+        let limit = limit
+            .trim()
+            .parse()
+            .map_err(|_| anyhow!("Invalid limit in '{}'", rule))?;
+
+        Ok(Self {
+            metric: Metric::parse(name.trim())?,
+            inclusive,
+            limit,
+        })
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "{}{}{}",
+            self.metric.name(),
+            if self.inclusive { ">=" } else { ">" },
+            self.limit
+        )
+    }
+
+    fn is_violated(&self, count: usize) -> bool {
+        if self.inclusive {
+            count >= self.limit
+        } else {
+            count > self.limit
+        }
+    }
+}
+
+/// Machine-readable result of a headless check, printed as one JSON line
+#[derive(Debug, Serialize)]
+pub struct Summary {
+    pub total: usize,
+    pub outdated: usize,
+    pub vulnerable: usize,
+    pub unused: usize,
+    pub mismatched: usize,
+    pub violations: Vec<String>,
+    pub passed: bool,
+}
+
+impl Summary {
+    fn count(&self, metric: Metric) -> usize {
+        match metric {
+            Metric::Outdated => self.outdated,
+            Metric::Vulnerable => self.vulnerable,
+            Metric::Unused => self.unused,
+            Metric::Mismatched => self.mismatched,
+        }
+    }
+}
+
+pub fn evaluate(packages: &[Package], thresholds: &[Threshold]) -> Summary {
+    let mut summary = Summary {
+        total: packages.len(),
+        outdated: packages.iter().filter(|p| p.is_outdated).count(),
+        vulnerable: packages
+            .iter()
+            .filter(|p| !p.vulnerabilities.is_empty())
+            .count(),
+        unused: packages.iter().filter(|p| p.used_in.is_empty()).count(),
+        mismatched: packages
+            .iter()
+            .filter(|p| !p.constraint_mismatches.is_empty())
+            .count(),
+        violations: Vec::new(),
+        passed: true,
+    };
+
+    for threshold in thresholds {
+        let count = summary.count(threshold.metric);
+        if threshold.is_violated(count) {
+            summary
+                .violations
+                .push(format!("{} (found {})", threshold.describe(), count));
+        }
+    }
+    summary.passed = summary.violations.is_empty();
+    summary
+}
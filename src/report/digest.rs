@@ -0,0 +1,241 @@
+use crate::config::DigestSettings;
+use crate::models::Package;
+use crate::utils::format::{format_date, format_number, format_size, SizeUnits};
+use crate::utils::paths::data_dir;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+/// What the environment looked like when the last digest was generated
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DigestState {
+    pub generated_at: Option<DateTime<Utc>>,
+    pub outdated: BTreeSet<String>,
+    pub vulnerable: BTreeSet<String>,
+    pub total_size: u64,
+}
+
+impl DigestState {
+    fn path() -> PathBuf {
+        data_dir().join("digest_state.json")
+    }
+
+    pub fn load() -> Self {
+        std::fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+            .map_err(|e| anyhow!("Failed to write {}: {}", path.display(), e))
+    }
+
+    fn from_packages(packages: &[Package]) -> Self {
+        Self {
+            generated_at: Some(Utc::now()),
+            outdated: packages
+                .iter()
+                .filter(|p| p.is_outdated)
+                .map(package_key)
+                .collect(),
+            vulnerable: packages
+                .iter()
+                .filter(|p| !p.vulnerabilities.is_empty())
+                .map(package_key)
+                .collect(),
+            total_size: packages.iter().filter_map(|p| p.size).sum(),
+        }
+    }
+
+    pub fn is_due(&self, settings: &DigestSettings) -> bool {
+        match self.generated_at {
+            Some(last) => {
+                Utc::now().signed_duration_since(last).num_days() >= settings.interval_days as i64
+            }
+            None => true,
+        }
+    }
+}
+
+/// Same name can exist in several managers, so digests track "manager/name"
+fn package_key(pkg: &Package) -> String {
+    format!("{}/{}", pkg.manager.name(), pkg.name)
+}
+
+/// Markdown digest of what changed since the previous digest
+pub fn render_digest(packages: &[Package], previous: &DigestState) -> String {
+    let current = DigestState::from_packages(packages);
+    let mut out = String::from("# DepMgr environment digest\n\n");
+
+    match previous.generated_at {
+        Some(since) => out.push_str(&format!(
+            "Changes since {} (generated {}).\n\n",
+            format_date(since),
+            format_date(Utc::now())
+        )),
+        None => out.push_str(&format!(
+            "First digest, generated {}.\n\n",
+            format_date(Utc::now())
+        )),
+    }
+
+    out.push_str(&format!(
+        "- {} packages installed\n- {} outdated\n- {} with known vulnerabilities\n\n",
+        format_number(packages.len() as u64),
+        format_number(current.outdated.len() as u64),
+        format_number(current.vulnerable.len() as u64)
+    ));
+
+    let newly_outdated: Vec<&Package> = packages
+        .iter()
+        .filter(|p| p.is_outdated && !previous.outdated.contains(&package_key(p)))
+        .collect();
+    out.push_str(&format!(
+        "## New outdated packages ({})\n\n",
+        newly_outdated.len()
+    ));
+    if newly_outdated.is_empty() {
+        out.push_str("_Nothing new._\n");
+    }
+    for pkg in newly_outdated {
+        out.push_str(&format!(
+            "- **{}** ({}): {} → {}\n",
+            pkg.name,
+            pkg.manager.name(),
+            pkg.installed_version,
+            pkg.latest_version.as_deref().unwrap_or("?")
+        ));
+    }
+
+    let vulnerable: Vec<&Package> = packages
+        .iter()
+        .filter(|p| !p.vulnerabilities.is_empty())
+        .collect();
+    out.push_str(&format!(
+        "\n## Vulnerable packages ({})\n\n",
+        vulnerable.len()
+    ));
+    if vulnerable.is_empty() {
+        out.push_str("_No known vulnerabilities._\n");
+    }
+    for pkg in vulnerable {
+        let marker = if previous.vulnerable.contains(&package_key(pkg)) {
+            ""
+        } else {
+            " **(new)**"
+        };
+        out.push_str(&format!(
+            "- **{}** ({}) {}: {}{}\n",
+            pkg.name,
+            pkg.manager.name(),
+            pkg.installed_version,
+            pkg.vulnerabilities.join(", "),
+            marker
+        ));
+    }
+
+    out.push_str("\n## Disk usage\n\n");
+    let now_size = format_size(current.total_size, SizeUnits::default());
+    if previous.generated_at.is_some() {
+        let delta = current.total_size as i64 - previous.total_size as i64;
+        let sign = if delta < 0 { "-" } else { "+" };
+        out.push_str(&format!(
+            "Tracked packages use {} ({}{} since last digest).\n",
+            now_size,
+            sign,
+            format_size(delta.unsigned_abs(), SizeUnits::default())
+        ));
+    } else {
+        out.push_str(&format!("Tracked packages use {}.\n", now_size));
+    }
+
+    out
+}
+
+/// Pipe the digest into the user's command (e.g. `mail`) via `sh -c`
+async fn pipe_to_command(command: &str, digest: &str) -> Result<()> {
+    let mut child = tokio::process::Command::new("sh")
+        .args(["-c", command])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true) // a timed-out command doesn't keep the writer blocked
+        .spawn()
+        .map_err(|e| anyhow!("Failed to spawn digest command: {}", e))?;
+
+    // Written alongside reading stderr: a command that fills its stderr pipe before
+    // reading all of stdin would otherwise block us both
+    let writer = child.stdin.take().map(|mut stdin| {
+        let digest = digest.to_string();
+        tokio::spawn(async move {
+            stdin.write_all(digest.as_bytes()).await
+            // Dropping stdin closes the pipe so the command sees EOF
+        })
+    });
+
+    let output = tokio::time::timeout(Duration::from_secs(60), child.wait_with_output())
+        .await
+        .map_err(|_| anyhow!("Digest command timed out"))??;
+    if let Some(writer) = writer {
+        writer
+            .await
+            .map_err(|e| anyhow!("Digest writer failed: {}", e))??;
+    }
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Digest command failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    }
+}
+
+/// Generate and deliver the digest if the configured interval has passed (or `force`).
+/// Returns the path of the written digest file.
+pub async fn run_digest_if_due(
+    packages: &[Package],
+    settings: &DigestSettings,
+    force: bool,
+) -> Result<Option<PathBuf>> {
+    let previous = DigestState::load();
+    if !force && !previous.is_due(settings) {
+        println!("[DIGEST] Not due yet");
+        return Ok(None);
+    }
+
+    let digest = render_digest(packages, &previous);
+
+    let dir = settings
+        .output_dir
+        .clone()
+        .unwrap_or_else(|| data_dir().join("digests"));
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| anyhow!("Failed to create {}: {}", dir.display(), e))?;
+    let path = dir.join(format!("digest-{}.md", Utc::now().format("%Y-%m-%d")));
+    tokio::fs::write(&path, &digest)
+        .await
+        .map_err(|e| anyhow!("Failed to write {}: {}", path.display(), e))?;
+    println!("[DIGEST] Wrote {}", path.display());
+
+    if let Some(command) = settings.command.as_deref().filter(|c| !c.trim().is_empty()) {
+        pipe_to_command(command, &digest).await?;
+        println!("[DIGEST] Piped digest to '{}'", command);
+    }
+
+    DigestState::from_packages(packages).save()?;
+    Ok(Some(path))
+}
@@ -0,0 +1,83 @@
+use crate::models::Package;
+use crate::utils::format::{format_datetime, format_number};
+use chrono::Utc;
+
+/// Pipes and newlines would break the table layout
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\n', '\r'], " ")
+}
+
+/// Render a list of packages as a Markdown report suitable for issues and wikis
+pub fn render_markdown_report(title: &str, filters: &[String], packages: &[Package]) -> String {
+    let mut out = String::new();
+
+    out.push_str(&format!("# {}\n\n", title));
+    out.push_str(&format!(
+        "Generated by DepMgr on {} · {} packages",
+        format_datetime(Utc::now()),
+        format_number(packages.len() as u64)
+    ));
+    if !filters.is_empty() {
+        out.push_str(&format!(" · filters: {}", filters.join(", ")));
+    }
+    out.push_str("\n\n");
+
+    if packages.is_empty() {
+        out.push_str("_No packages match this view._\n");
+        return out;
+    }
+
+    out.push_str("| Package | Manager | Installed | Latest | Status | Advisories | Used in | Description |\n");
+    out.push_str("|---|---|---|---|---|---|---|---|\n");
+
+    for pkg in packages {
+        let name = match pkg.registry_url() {
+            Some(url) => format!("[{}]({})", escape_cell(&pkg.name), url),
+            None => escape_cell(&pkg.name),
+        };
+        let status = if !pkg.vulnerabilities.is_empty() {
+            "🚨 Vulnerable"
+        } else if pkg.is_outdated {
+            "⚠️ Outdated"
+        } else {
+            "✅ Current"
+        };
+        let advisories = if pkg.vulnerabilities.is_empty() {
+            "-".to_string()
+        } else {
+            pkg.vulnerabilities
+                .iter()
+                .map(|id| {
+                    format!(
+                        "[{}](https://osv.dev/vulnerability/{})",
+                        escape_cell(id),
+                        id
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut usage = match pkg.used_in.len() {
+            0 => "Unused".to_string(),
+            1 => "1 project".to_string(),
+            n => format!("{} projects", n),
+        };
+        if let Some(kind) = pkg.usage_kind() {
+            usage.push_str(&format!(" ({})", kind));
+        }
+
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} | {} |\n",
+            name,
+            pkg.manager.name(),
+            escape_cell(&pkg.installed_version),
+            escape_cell(pkg.latest_version.as_deref().unwrap_or("-")),
+            status,
+            advisories,
+            usage,
+            escape_cell(pkg.description.as_deref().unwrap_or("")),
+        ));
+    }
+
+    out
+}
@@ -0,0 +1,10 @@
+pub mod digest;
+pub mod markdown;
+pub mod output;
+pub mod schema;
+pub mod score;
+pub mod toolchain;
+pub mod weekly;
+
+pub use digest::run_digest_if_due;
+pub use markdown::render_markdown_report;
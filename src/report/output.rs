@@ -0,0 +1,153 @@
+use crate::models::{Package, Project};
+use crate::report::schema::ScanOutput;
+use crate::utils::format::{format_size, SizeUnits};
+
+/// How the headless list commands print packages (`--format`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned columns, for reading
+    Table,
+    /// The versioned scan document on one line
+    Json,
+    /// The versioned scan document
+    Yaml,
+    /// One row per package with a header row
+    Csv,
+    /// The Markdown report, as exported from the GUI
+    Markdown,
+}
+
+/// Column-aligned text, for people rather than scripts
+fn render_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let line = |cells: Vec<&str>| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        format!("{}\n", padded.join("  ").trim_end())
+    };
+    let mut out = line(headers.to_vec());
+    for row in rows {
+        out.push_str(&line(row.iter().map(String::as_str).collect()));
+    }
+    out
+}
+
+/// RFC 4180: quote fields holding a separator, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn render_csv(packages: &[Package]) -> String {
+    let mut out = String::from(
+        "name,manager,installed_version,latest_version,outdated,projects,size_bytes,vulnerabilities\n",
+    );
+    for pkg in packages {
+        let fields = [
+            pkg.name.clone(),
+            pkg.manager.name().to_string(),
+            pkg.installed_version.clone(),
+            pkg.latest_version.clone().unwrap_or_default(),
+            pkg.is_outdated.to_string(),
+            pkg.used_in.len().to_string(),
+            pkg.size.map(|size| size.to_string()).unwrap_or_default(),
+            pkg.vulnerabilities.join(" "),
+        ];
+        let fields: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+fn package_table(packages: &[Package]) -> String {
+    let rows: Vec<Vec<String>> = packages
+        .iter()
+        .map(|pkg| {
+            vec![
+                pkg.name.clone(),
+                pkg.manager.name().to_string(),
+                pkg.installed_version.clone(),
+                pkg.latest_version
+                    .clone()
+                    .filter(|_| pkg.is_outdated)
+                    .unwrap_or_default(),
+                pkg.used_in.len().to_string(),
+                pkg.size
+                    .map(|size| format_size(size, SizeUnits::default()))
+                    .unwrap_or_default(),
+            ]
+        })
+        .collect();
+    render_table(
+        &["NAME", "MANAGER", "INSTALLED", "LATEST", "PROJECTS", "SIZE"],
+        &rows,
+    )
+}
+
+/// Packages (and, in the JSON and YAML documents, the projects they were matched
+/// against) in the given format; `title` heads the Markdown report
+pub fn render(
+    format: OutputFormat,
+    title: &str,
+    packages: &[Package],
+    projects: &[Project],
+) -> String {
+    let document = || ScanOutput::new(packages, projects);
+    match format {
+        OutputFormat::Table => package_table(packages),
+        OutputFormat::Json => serde_json::to_string(&document())
+            .map(|json| json + "\n")
+            .unwrap_or_default(),
+        OutputFormat::Yaml => serde_yaml::to_string(&document()).unwrap_or_default(),
+        OutputFormat::Csv => render_csv(packages),
+        OutputFormat::Markdown => crate::report::render_markdown_report(title, &[], packages),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PackageManager;
+
+    #[test]
+    fn tables_and_csv_line_up_and_escape() {
+        let packages = vec![
+            Package {
+                latest_version: Some("1.8.0".to_string()),
+                is_outdated: true,
+                size: Some(2048),
+                ..Package::new("jq", PackageManager::Homebrew, "1.7.1")
+            },
+            Package {
+                vulnerabilities: vec!["GHSA-1".to_string(), "GHSA-2".to_string()],
+                ..Package::new("@scope/a,b", PackageManager::Npm, "1.0.0")
+            },
+        ];
+
+        let table = render(OutputFormat::Table, "", &packages, &[]);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0].find("MANAGER"), lines[1].find("Homebrew"));
+        assert_eq!(lines[0].find("INSTALLED"), lines[2].find("1.0.0"));
+
+        let csv = render(OutputFormat::Csv, "", &packages, &[]);
+        assert_eq!(
+            csv.lines().nth(2),
+            Some("\"@scope/a,b\",npm,1.0.0,,false,0,,GHSA-1 GHSA-2")
+        );
+
+        let yaml = render(OutputFormat::Yaml, "", &packages, &[]);
+        assert!(yaml.starts_with("schema_version: 1\n"));
+    }
+}
@@ -0,0 +1,378 @@
+use crate::models::{Package, PackageManager, Project};
+use crate::policy::Summary;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Bumped only for breaking changes (a field removed, renamed or retyped). New fields
+/// can appear within a version, so consumers should ignore ones they don't know.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Scan results as printed by `--json` and served at `/v1/scan`. Unlike `Package` and
+/// `Project`, which change with the app, these fields are a contract.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScanOutput {
+    pub schema_version: u32,
+    pub generated_at: DateTime<Utc>,
+    /// "This machine", "ssh: host", "docker: name" or "Demo data"
+    pub target: String,
+    pub packages: Vec<PackageRecord>,
+    /// Empty for remote targets, whose projects aren't scanned, and when a daemon too old
+    /// to report its projects answered the scan
+    pub projects: Vec<ProjectRecord>,
+    /// The `--fail-on` verdict, when rules were given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<PolicyRecord>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PackageRecord {
+    pub name: String,
+    /// One of the names in `manager_id`, e.g. "Homebrew", "Npm", "HelmPlugins"
+    #[serde(with = "manager_id")]
+    pub manager: PackageManager,
+    pub installed_version: String,
+    pub latest_version: Option<String>,
+    pub outdated: bool,
+    pub description: Option<String>,
+    pub size_bytes: Option<u64>,
+    /// Projects depending on it; empty means unused
+    pub usage: Vec<UsageRecord>,
+    /// Advisory IDs (OSV) affecting the installed version
+    pub vulnerabilities: Vec<String>,
+    /// Projects declaring a version newer than the installed one
+    pub requirement_mismatches: Vec<MismatchRecord>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UsageRecord {
+    pub project: String,
+    pub dev_only: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MismatchRecord {
+    pub project: String,
+    pub constraint: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProjectRecord {
+    pub path: String,
+    pub name: String,
+    #[serde(with = "manager_ids")]
+    pub managers: Vec<PackageManager>,
+    pub dependencies: Vec<DependencyRecord>,
+    pub last_modified: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DependencyRecord {
+    pub name: String,
+    #[serde(with = "manager_id")]
+    pub manager: PackageManager,
+    pub constraint: String,
+    pub locked_version: Option<String>,
+    pub dev: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PolicyRecord {
+    pub passed: bool,
+    pub violations: Vec<String>,
+}
+
+/// Managers as the schema writes them. Spelled out rather than derived from the enum, so
+/// renaming a variant can't change the output; a new manager gets a new name here.
+mod manager_id {
+    use crate::models::PackageManager;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn name(manager: &PackageManager) -> &'static str {
+        match manager {
+            PackageManager::Homebrew => "Homebrew",
+            PackageManager::Npm => "Npm",
+            PackageManager::Yarn => "Yarn",
+            PackageManager::Pnpm => "Pnpm",
+            PackageManager::Cargo => "Cargo",
+            PackageManager::Pip => "Pip",
+            PackageManager::Pipx => "Pipx",
+            PackageManager::Uv => "Uv",
+            PackageManager::Poetry => "Poetry",
+            PackageManager::Gem => "Gem",
+            PackageManager::Go => "Go",
+            PackageManager::Composer => "Composer",
+            PackageManager::Pub => "Pub",
+            PackageManager::Swift => "Swift",
+            PackageManager::Opam => "Opam",
+            PackageManager::Ghcup => "Ghcup",
+            PackageManager::Stack => "Stack",
+            PackageManager::LuaRocks => "LuaRocks",
+            PackageManager::Julia => "Julia",
+            PackageManager::R => "R",
+            PackageManager::Tfenv => "Tfenv",
+            PackageManager::HelmPlugins => "HelmPlugins",
+            PackageManager::Krew => "Krew",
+            PackageManager::GhExtensions => "GhExtensions",
+            PackageManager::GitHelpers => "GitHelpers",
+            PackageManager::OhMyZsh => "OhMyZsh",
+            PackageManager::Zinit => "Zinit",
+            PackageManager::Fisher => "Fisher",
+            PackageManager::Fonts => "Fonts",
+            PackageManager::Apk => "Apk",
+            PackageManager::Apt => "Apt",
+            PackageManager::Maven => "Maven",
+            PackageManager::Winget => "Winget",
+            PackageManager::Scoop => "Scoop",
+            PackageManager::Chocolatey => "Chocolatey",
+            PackageManager::Nix => "Nix",
+            PackageManager::Mise => "Mise",
+            PackageManager::Asdf => "Asdf",
+            PackageManager::Mas => "Mas",
+        }
+    }
+
+    pub fn parse(id: &str) -> Option<PackageManager> {
+        PackageManager::ALL.into_iter().find(|m| name(m) == id)
+    }
+
+    pub fn serialize<S: Serializer>(manager: &PackageManager, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(name(manager))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<PackageManager, D::Error> {
+        let id = String::deserialize(d)?;
+        parse(&id).ok_or_else(|| serde::de::Error::custom(format!("unknown manager {:?}", id)))
+    }
+}
+
+mod manager_ids {
+    use crate::models::PackageManager;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(managers: &[PackageManager], s: S) -> Result<S::Ok, S::Error> {
+        s.collect_seq(managers.iter().map(super::manager_id::name))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<PackageManager>, D::Error> {
+        Vec::<String>::deserialize(d)?
+            .iter()
+            .map(|id| {
+                super::manager_id::parse(id)
+                    .ok_or_else(|| serde::de::Error::custom(format!("unknown manager {:?}", id)))
+            })
+            .collect()
+    }
+}
+
+impl From<&Package> for PackageRecord {
+    fn from(pkg: &Package) -> Self {
+        Self {
+            name: pkg.name.clone(),
+            manager: pkg.manager.clone(),
+            installed_version: pkg.installed_version.clone(),
+            latest_version: pkg.latest_version.clone(),
+            outdated: pkg.is_outdated,
+            description: pkg.description.clone(),
+            size_bytes: pkg.size,
+            usage: pkg
+                .used_in
+                .iter()
+                .map(|project| UsageRecord {
+                    project: project.clone(),
+                    dev_only: pkg.dev_only_in.contains(project),
+                })
+                .collect(),
+            vulnerabilities: pkg.vulnerabilities.clone(),
+            requirement_mismatches: pkg
+                .constraint_mismatches
+                .iter()
+                .map(|m| MismatchRecord {
+                    project: m.project.clone(),
+                    constraint: m.constraint.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<&Project> for ProjectRecord {
+    fn from(project: &Project) -> Self {
+        Self {
+            path: project.path.display().to_string(),
+            name: project.name.clone(),
+            managers: project.package_managers.clone(),
+            dependencies: project
+                .dependencies
+                .iter()
+                .map(|dep| DependencyRecord {
+                    name: dep.package_name.clone(),
+                    manager: dep.manager.clone(),
+                    constraint: dep.version_constraint.clone(),
+                    locked_version: dep.locked_version.clone(),
+                    dev: dep.is_dev,
+                })
+                .collect(),
+            last_modified: project.last_modified,
+        }
+    }
+}
+
+impl ScanOutput {
+    pub fn new(packages: &[Package], projects: &[Project]) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            generated_at: Utc::now(),
+            target: crate::utils::target::current_target().label(),
+            packages: packages.iter().map(PackageRecord::from).collect(),
+            projects: projects.iter().map(ProjectRecord::from).collect(),
+            policy: None,
+        }
+    }
+
+    pub fn with_policy(mut self, summary: &Summary) -> Self {
+        self.policy = Some(PolicyRecord {
+            passed: summary.passed,
+            violations: summary.violations.clone(),
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Renaming any of these breaks consumers: bump SCHEMA_VERSION and update this test
+    #[test]
+    fn field_names_are_stable() {
+        let pkg = Package {
+            latest_version: Some("9.0.0".to_string()),
+            is_outdated: true,
+            used_in: vec!["/src/app".to_string(), "/src/lib".to_string()],
+            dev_only_in: vec!["/src/lib".to_string()],
+            vulnerabilities: vec!["GHSA-xxxx".to_string()],
+            ..Package::new("eslint", PackageManager::Npm, "8.56.0")
+        };
+        let json = serde_json::to_value(ScanOutput::new(&[pkg], &[])).unwrap();
+
+        let mut keys: Vec<&str> = json
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(|k| k.as_str())
+            .collect();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            [
+                "generated_at",
+                "packages",
+                "projects",
+                "schema_version",
+                "target"
+            ]
+        );
+        assert_eq!(json["schema_version"], SCHEMA_VERSION);
+
+        let package = &json["packages"][0];
+        let mut keys: Vec<&str> = package
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(|k| k.as_str())
+            .collect();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            [
+                "description",
+                "installed_version",
+                "latest_version",
+                "manager",
+                "name",
+                "outdated",
+                "requirement_mismatches",
+                "size_bytes",
+                "usage",
+                "vulnerabilities",
+            ]
+        );
+        assert_eq!(package["manager"], "Npm");
+        assert_eq!(
+            package["usage"][1],
+            serde_json::json!({"project": "/src/lib", "dev_only": true})
+        );
+
+        let round_trip: ScanOutput = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            round_trip.packages[0].latest_version.as_deref(),
+            Some("9.0.0")
+        );
+    }
+
+    /// The wire names consumers match on: changing one is a breaking change
+    #[test]
+    fn manager_names_are_pinned() {
+        let names: Vec<&str> = PackageManager::ALL.iter().map(manager_id::name).collect();
+        assert_eq!(
+            names,
+            [
+                "Homebrew",
+                "Npm",
+                "Yarn",
+                "Pnpm",
+                "Cargo",
+                "Pip",
+                "Pipx",
+                "Uv",
+                "Poetry",
+                "Gem",
+                "Go",
+                "Composer",
+                "Pub",
+                "Swift",
+                "Opam",
+                "Ghcup",
+                "Stack",
+                "LuaRocks",
+                "Julia",
+                "R",
+                "Tfenv",
+                "HelmPlugins",
+                "Krew",
+                "GhExtensions",
+                "GitHelpers",
+                "OhMyZsh",
+                "Zinit",
+                "Fisher",
+                "Fonts",
+                "Apk",
+                "Apt",
+                "Maven",
+                "Winget",
+                "Scoop",
+                "Chocolatey",
+                "Nix",
+                "Mise",
+                "Asdf",
+                "Mas",
+            ]
+        );
+        for manager in PackageManager::ALL {
+            assert_eq!(manager_id::parse(manager_id::name(&manager)), Some(manager));
+        }
+        let dependency: DependencyRecord = serde_json::from_value(serde_json::json!({
+            "name": "gh-dash", "manager": "GhExtensions", "constraint": "*",
+            "locked_version": null, "dev": false
+        }))
+        .unwrap();
+        assert_eq!(dependency.manager, PackageManager::GhExtensions);
+        assert!(
+            serde_json::from_value::<DependencyRecord>(serde_json::json!({
+                "name": "x", "manager": "gh-extensions", "constraint": "*",
+                "locked_version": null, "dev": false
+            }))
+            .is_err()
+        );
+    }
+}
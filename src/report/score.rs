@@ -0,0 +1,176 @@
+use crate::models::{Package, PackageFlags};
+use crate::utils::format::{format_size, SizeUnits};
+use crate::utils::paths::data_dir;
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Days of scores kept for trends
+const HISTORY_DAYS: usize = 90;
+
+/// One part of the environment score, e.g. "Up to date: 36/40"
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScorePart {
+    pub label: &'static str,
+    pub points: u32,
+    pub max: u32,
+    pub detail: String,
+}
+
+/// 0-100 summary of how well-kept the environment is
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvironmentScore {
+    pub parts: Vec<ScorePart>,
+}
+
+impl EnvironmentScore {
+    pub fn total(&self) -> u32 {
+        self.parts.iter().map(|part| part.points).sum()
+    }
+}
+
+/// Points for the share of packages that aren't a problem
+fn share(max: u32, bad: usize, total: usize) -> u32 {
+    (f64::from(max) * (1.0 - bad as f64 / total as f64)).round() as u32
+}
+
+/// Up to date (40), free of known vulnerabilities (30), few unused packages (15) and
+/// little disk spent on them (15). None until something has been scanned.
+pub fn score(packages: &[Package], flags: &PackageFlags) -> Option<EnvironmentScore> {
+    let total = packages.len();
+    if total == 0 {
+        return None;
+    }
+    let outdated = packages.iter().filter(|p| flags.needs_attention(p)).count();
+    let vulnerable = packages
+        .iter()
+        .filter(|p| !p.vulnerabilities.is_empty())
+        .count();
+    let unused: Vec<&Package> = packages.iter().filter(|p| p.used_in.is_empty()).collect();
+    let total_size: u64 = packages.iter().filter_map(|p| p.size).sum();
+    let unused_size: u64 = unused.iter().filter_map(|p| p.size).sum();
+
+    let disk = if total_size == 0 {
+        ScorePart {
+            label: "Disk waste",
+            points: 15,
+            max: 15,
+            detail: "no sizes measured yet".to_string(),
+        }
+    } else {
+        ScorePart {
+            label: "Disk waste",
+            points: (15.0 * (1.0 - unused_size as f64 / total_size as f64)).round() as u32,
+            max: 15,
+            detail: format!(
+                "{} of {} in unused packages",
+                format_size(unused_size, SizeUnits::Binary),
+                format_size(total_size, SizeUnits::Binary)
+            ),
+        }
+    };
+
+    Some(EnvironmentScore {
+        parts: vec![
+            ScorePart {
+                label: "Up to date",
+                points: share(40, outdated, total),
+                max: 40,
+                detail: format!("{} of {} packages outdated", outdated, total),
+            },
+            ScorePart {
+                label: "Vulnerabilities",
+                points: share(30, vulnerable, total),
+                max: 30,
+                detail: format!("{} of {} packages vulnerable", vulnerable, total),
+            },
+            ScorePart {
+                label: "Unused",
+                points: share(15, unused.len(), total),
+                max: 15,
+                detail: format!("{} of {} packages unused", unused.len(), total),
+            },
+            disk,
+        ],
+    })
+}
+
+/// The score at the end of a day's last scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreEntry {
+    pub date: NaiveDate,
+    pub score: u32,
+}
+
+fn history_path() -> PathBuf {
+    data_dir().join("score_history.json")
+}
+
+fn load_history() -> Vec<ScoreEntry> {
+    std::fs::read_to_string(history_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// The most recent score from before today, to show the trend against
+pub fn previous_score() -> Option<ScoreEntry> {
+    let today = Local::now().date_naive();
+    load_history().into_iter().rfind(|entry| entry.date < today)
+}
+
+/// Remember today's score (replacing an earlier one from today)
+pub fn record_score(score: u32) {
+    let today = Local::now().date_naive();
+    let mut history = load_history();
+    history.retain(|entry| entry.date != today);
+    history.push(ScoreEntry { date: today, score });
+    if history.len() > HISTORY_DAYS {
+        history.drain(..history.len() - HISTORY_DAYS);
+    }
+
+    let path = history_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let result = serde_json::to_string(&history)
+        .map_err(std::io::Error::other)
+        .and_then(|json| std::fs::write(&path, json));
+    if let Err(e) = result {
+        eprintln!("[ERROR] Failed to write {}: {}", path.display(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PackageManager;
+
+    #[test]
+    fn outdated_vulnerable_and_unused_packages_cost_points() {
+        let mut packages: Vec<Package> = (0..10)
+            .map(|i| Package {
+                used_in: vec!["/home/me/src/app".to_string()],
+                size: Some(1000),
+                ..Package::new(format!("pkg{}", i), PackageManager::Npm, "1.0.0")
+            })
+            .collect();
+        let flags = PackageFlags::default();
+        assert_eq!(score(&packages, &flags).unwrap().total(), 100);
+
+        packages[0].is_outdated = true;
+        packages[0].latest_version = Some("2.0.0".to_string());
+        packages[1].vulnerabilities = vec!["GHSA-xxxx".to_string()];
+        packages[2].used_in.clear();
+        packages[2].size = Some(9000);
+        let points: Vec<u32> = score(&packages, &flags)
+            .unwrap()
+            .parts
+            .iter()
+            .map(|part| part.points)
+            .collect();
+        // 1 in 10 outdated, 1 in 10 vulnerable, 1 in 10 unused holding half the disk
+        assert_eq!(points, [36, 27, 14, 8]);
+        assert!(score(&[], &flags).is_none());
+    }
+}
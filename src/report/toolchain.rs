@@ -0,0 +1,155 @@
+use crate::models::{Package, PackageManager, Project};
+use crate::utils::format::format_datetime;
+use crate::utils::version::compare_versions;
+use chrono::Utc;
+
+/// Runtimes mise can pin, by the name it uses
+const MISE_TOOLS: &[&str] = &[
+    "node",
+    "python",
+    "go",
+    "rust",
+    "java",
+    "ruby",
+    "deno",
+    "bun",
+    "terraform",
+    "dart",
+];
+
+/// A system tool a project relies on, with the installed copy when there is one
+#[derive(Debug, Clone)]
+pub struct ProjectTool {
+    pub name: String,
+    pub formula: Option<String>, // Installed Homebrew formula, e.g. "python@3.12"
+    pub version: Option<String>,
+}
+
+/// "python3"/"pip" -> "python", "python@3.12" -> "python", "cargo" -> "rust"
+fn tool_name(name: &str) -> String {
+    let base = name.split('@').next().unwrap_or(name);
+    match base {
+        "python3" | "pip" | "pip3" => "python",
+        "npm" => "node",
+        "cargo" | "rustc" => "rust",
+        "mvn" | "maven" | "gradle" | "openjdk" => "java",
+        other => other,
+    }
+    .to_string()
+}
+
+/// Tools the project uses, from the usage scan (Homebrew tools used in it), the runtimes
+/// its manifests imply, declared tool versions, dev tooling, and its environment files
+pub fn project_tools(project: &Project, packages: &[Package]) -> Vec<ProjectTool> {
+    let path = project.path.to_string_lossy();
+    let mut names: Vec<String> = Vec::new();
+    let mut add = |name: String| {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    };
+
+    for manager in &project.package_managers {
+        match manager {
+            PackageManager::Npm => add("node".to_string()),
+            PackageManager::Cargo => add("rust".to_string()),
+            PackageManager::Pip => add("python".to_string()),
+            PackageManager::Go => add("go".to_string()),
+            PackageManager::Maven => add("java".to_string()),
+            PackageManager::Pub => add("dart".to_string()),
+            _ => {}
+        }
+    }
+    for requirement in &project.tool_requirements {
+        add(tool_name(&requirement.package_name));
+    }
+    for tool in project.setup_tools.iter().chain(&project.tool_usages) {
+        add(tool_name(&tool.tool));
+    }
+    for pkg in packages {
+        if pkg.manager == PackageManager::Homebrew && pkg.used_in.iter().any(|p| *p == path) {
+            add(tool_name(&pkg.name));
+        }
+    }
+
+    names
+        .into_iter()
+        .map(|name| {
+            // The newest Homebrew copy: node or node@20, python@3.11 or python@3.12
+            let installed = packages
+                .iter()
+                .filter(|p| p.manager == PackageManager::Homebrew)
+                .filter(|p| {
+                    p.name == name
+                        || p.name
+                            .strip_prefix(&name)
+                            .is_some_and(|rest| rest.starts_with('@'))
+                        || (name == "java" && p.name.starts_with("openjdk"))
+                })
+                .max_by(|a, b| compare_versions(&a.installed_version, &b.installed_version));
+            ProjectTool {
+                formula: installed.map(|p| p.name.clone()),
+                version: installed.map(|p| p.installed_version.clone()),
+                name,
+            }
+        })
+        .collect()
+}
+
+fn header(project: &Project) -> String {
+    format!(
+        "# Tools {} relies on, generated by DepMgr on {}\n",
+        project.name,
+        format_datetime(Utc::now())
+    )
+}
+
+/// `brew bundle` file with the installed Homebrew formulae the project uses
+pub fn render_brewfile(project: &Project, tools: &[ProjectTool]) -> String {
+    let mut out = header(project);
+    for tool in tools {
+        if let Some(formula) = &tool.formula {
+            out.push_str(&format!("brew \"{}\"\n", formula));
+        }
+    }
+    out
+}
+
+/// mise config pinning the project's runtimes to the installed versions
+pub fn render_mise_toml(project: &Project, tools: &[ProjectTool]) -> String {
+    let mut out = header(project);
+    out.push_str("[tools]\n");
+    for tool in tools
+        .iter()
+        .filter(|t| MISE_TOOLS.contains(&t.name.as_str()))
+    {
+        let version = tool.version.as_deref().unwrap_or("latest");
+        out.push_str(&format!("{} = \"{}\"\n", tool.name, version));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn brewfile_and_mise_from_usage() {
+        let mut project = Project::new(std::path::PathBuf::from("/src/app"));
+        project.package_managers = vec![PackageManager::Npm, PackageManager::Pip];
+        let mut jq = Package::new("jq", PackageManager::Homebrew, "1.7.1");
+        jq.used_in = vec!["/src/app".to_string()];
+        let packages = vec![
+            Package::new("node", PackageManager::Homebrew, "22.3.0"),
+            Package::new("python@3.11", PackageManager::Homebrew, "3.11.9"),
+            Package::new("python@3.12", PackageManager::Homebrew, "3.12.4"),
+            jq,
+        ];
+
+        let tools = project_tools(&project, &packages);
+        let brewfile = render_brewfile(&project, &tools);
+        assert!(brewfile.ends_with("brew \"node\"\nbrew \"python@3.12\"\nbrew \"jq\"\n"));
+        let mise = render_mise_toml(&project, &tools);
+        assert!(mise.ends_with("[tools]\nnode = \"22.3.0\"\npython = \"3.12.4\"\n"));
+    }
+}
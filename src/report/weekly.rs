@@ -0,0 +1,187 @@
+use crate::models::diff::{diff, Change, ChangeKind};
+use crate::models::{Package, PackageFlags};
+use crate::utils::paths::data_dir;
+use crate::utils::version::{bump_kind, Bump};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Changes listed in the summary, biggest first
+const BIGGEST_CHANGES: usize = 5;
+
+/// The inventory when the summary was last shown, compared against a week later
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct WeeklyState {
+    shown_at: Option<DateTime<Utc>>,
+    packages: Vec<Package>,
+}
+
+impl WeeklyState {
+    fn path() -> PathBuf {
+        data_dir().join("weekly_summary.json")
+    }
+
+    fn load() -> Self {
+        std::fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let result = serde_json::to_string(self)
+            .map_err(std::io::Error::other)
+            .and_then(|json| std::fs::write(&path, json));
+        if let Err(e) = result {
+            eprintln!("[ERROR] Failed to write {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// What a week of not looking has added up to
+#[derive(Debug, Clone)]
+pub struct WeeklySummary {
+    pub since: DateTime<Utc>,
+    /// Outdated now but not a week ago (or with a newer release since)
+    pub new_updates: usize,
+    pub vulnerable: usize,
+    /// Disk held by packages no project uses
+    pub reclaimable: u64,
+    pub biggest_changes: Vec<Change>,
+}
+
+/// Majors before minors before patches; installs and removals count like minors
+fn weight(change: &Change, installed: &HashMap<String, &Package>) -> u8 {
+    let bump = match &change.kind {
+        ChangeKind::VersionChanged { from, to } => bump_kind(from, to),
+        ChangeKind::NewlyOutdated { latest } => match installed.get(&change.key()) {
+            Some(pkg) => bump_kind(&pkg.installed_version, latest),
+            None => Bump::Patch,
+        },
+        ChangeKind::Installed | ChangeKind::Removed => Bump::Minor,
+    };
+    match bump {
+        Bump::Major => 3,
+        Bump::Minor => 2,
+        Bump::Patch => 1,
+    }
+}
+
+fn summarize(
+    packages: &[Package],
+    before: &[Package],
+    since: DateTime<Utc>,
+    flags: &PackageFlags,
+) -> WeeklySummary {
+    let mut changes = diff(before, packages);
+    let installed: HashMap<String, &Package> = packages.iter().map(|p| (p.key(), p)).collect();
+    let previous: HashMap<String, &Package> = before.iter().map(|p| (p.key(), p)).collect();
+    changes.sort_by_key(|change| std::cmp::Reverse(weight(change, &installed)));
+    changes.truncate(BIGGEST_CHANGES);
+
+    WeeklySummary {
+        since,
+        new_updates: packages
+            .iter()
+            .filter(|p| flags.needs_attention(p))
+            .filter(|p| {
+                previous
+                    .get(&p.key())
+                    .is_none_or(|old| !old.is_outdated || old.latest_version != p.latest_version)
+            })
+            .count(),
+        vulnerable: packages
+            .iter()
+            .filter(|p| !p.vulnerabilities.is_empty())
+            .count(),
+        reclaimable: packages
+            .iter()
+            .filter(|p| p.used_in.is_empty())
+            .filter_map(|p| p.size)
+            .sum(),
+        biggest_changes: changes,
+    }
+}
+
+/// The summary against last week's inventory, once at least a week has passed since it
+/// was last shown; the current inventory becomes the next baseline. The first run only
+/// records the baseline.
+pub fn take_weekly_summary(packages: &[Package]) -> Option<WeeklySummary> {
+    if packages.is_empty() {
+        return None;
+    }
+    let state = WeeklyState::load();
+    let summary = match state.shown_at {
+        Some(shown_at) if Utc::now().signed_duration_since(shown_at).num_days() < 7 => {
+            return None;
+        }
+        Some(shown_at) => Some(summarize(
+            packages,
+            &state.packages,
+            shown_at,
+            &PackageFlags::load(),
+        )),
+        None => None,
+    };
+    WeeklyState {
+        shown_at: Some(Utc::now()),
+        packages: packages.to_vec(),
+    }
+    .save();
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PackageManager;
+
+    #[test]
+    fn week_over_week_changes_biggest_first() {
+        let pkg = |name: &str, version: &str| Package::new(name, PackageManager::Npm, version);
+        let before = vec![
+            pkg("typescript", "5.3.0"),
+            pkg("eslint", "8.56.0"),
+            pkg("prettier", "3.1.0"),
+            Package {
+                is_outdated: true,
+                latest_version: Some("2.0.0".to_string()),
+                ..pkg("left-pad", "1.0.0")
+            },
+        ];
+        let after = vec![
+            pkg("typescript", "5.3.3"),
+            pkg("eslint", "9.0.0"),
+            Package {
+                is_outdated: true,
+                latest_version: Some("3.2.0".to_string()),
+                size: Some(4096),
+                ..pkg("prettier", "3.1.0")
+            },
+            Package {
+                is_outdated: true,
+                latest_version: Some("2.0.0".to_string()),
+                used_in: vec!["/home/me/src/app".to_string()],
+                size: Some(1024),
+                vulnerabilities: vec!["GHSA-xxxx".to_string()],
+                ..pkg("left-pad", "1.0.0")
+            },
+        ];
+
+        let summary = summarize(&after, &before, Utc::now(), &PackageFlags::default());
+        assert_eq!(summary.new_updates, 1); // left-pad was already outdated
+        assert_eq!(summary.vulnerable, 1);
+        assert_eq!(summary.reclaimable, 4096);
+        let names: Vec<&str> = summary
+            .biggest_changes
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, ["eslint", "prettier", "typescript"]);
+    }
+}
@@ -0,0 +1,432 @@
+use crate::models::{Package, Project, ToolUsage};
+use crate::utils::version::{compare_versions, satisfies};
+use regex::Regex;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Tools we recognize in prose and Nix package lists: (tool, aliases, Homebrew formula).
+/// mise and devcontainer features name tools explicitly, so they aren't limited to these.
+const KNOWN_TOOLS: &[(&str, &[&str], Option<&str>)] = &[
+    ("node", &["node", "nodejs", "node.js"], Some("node")),
+    ("python", &["python", "python3"], Some("python")),
+    ("go", &["go", "golang"], Some("go")),
+    ("rust", &["rust", "rustc", "cargo", "rustup"], Some("rust")),
+    ("java", &["java", "jdk", "openjdk"], Some("openjdk")),
+    ("ruby", &["ruby"], Some("ruby")),
+    ("deno", &["deno"], Some("deno")),
+    ("bun", &["bun"], Some("bun")),
+    ("pnpm", &["pnpm"], Some("pnpm")),
+    ("yarn", &["yarn"], Some("yarn")),
+    (
+        "docker",
+        &["docker", "docker-in-docker", "docker-outside-of-docker"],
+        None,
+    ),
+    ("gh", &["gh", "github-cli"], Some("gh")),
+    ("terraform", &["terraform"], Some("terraform")),
+    (
+        "kubectl",
+        &["kubectl", "kubectl-helm-minikube"],
+        Some("kubernetes-cli"),
+    ),
+    ("helm", &["helm"], Some("helm")),
+    ("aws", &["aws", "aws-cli", "awscli"], Some("awscli")),
+    ("make", &["make", "gnumake"], Some("make")),
+    ("cmake", &["cmake"], Some("cmake")),
+    ("just", &["just"], Some("just")),
+    ("jq", &["jq"], Some("jq")),
+    (
+        "postgresql",
+        &["postgres", "postgresql"],
+        Some("postgresql"),
+    ),
+    ("redis", &["redis"], Some("redis")),
+];
+
+/// Canonical tool name for an alias, ignoring Nix/version suffixes ("nodejs_20", "python311")
+fn known_tool(name: &str) -> Option<&'static str> {
+    let name = name.to_lowercase();
+    let base = name
+        .trim_end_matches(|c: char| c.is_ascii_digit() || c == '_' || c == '.')
+        .trim_end_matches("packages"); // python311Packages
+    KNOWN_TOOLS
+        .iter()
+        .find(|(_, aliases, _)| aliases.contains(&name.as_str()) || aliases.contains(&base))
+        .map(|(tool, _, _)| *tool)
+}
+
+/// Homebrew formula for a tool, if there's an obvious one
+pub fn setup_formula(tool: &str) -> Option<String> {
+    match KNOWN_TOOLS.iter().find(|(t, _, _)| *t == tool) {
+        Some((_, _, formula)) => formula.map(|f| f.to_string()),
+        None => Some(tool.to_string()),
+    }
+}
+
+fn usage(tool: &str, source: &str, version: Option<String>) -> ToolUsage {
+    ToolUsage {
+        tool: tool.to_string(),
+        source: source.to_string(),
+        rev: version.filter(|v| !v.is_empty() && v != "latest" && v != "lts"),
+    }
+}
+
+/// `[tools]` in mise.toml (or .mise.toml): `node = "20"`, `python = ["3.12", "3.11"]`
+fn parse_mise(content: &str, source: &str) -> Vec<ToolUsage> {
+    let Ok(toml) = content.parse::<toml::Value>() else {
+        return Vec::new();
+    };
+    let Some(tools) = toml.get("tools").and_then(|t| t.as_table()) else {
+        return Vec::new();
+    };
+    tools
+        .iter()
+        // "npm:prettier", "cargo:ripgrep" install through other backends
+        .filter(|(name, _)| !name.contains(':'))
+        .map(|(name, value)| {
+            let version = match value {
+                toml::Value::String(v) => Some(v.clone()),
+                toml::Value::Array(versions) => {
+                    versions.first().and_then(|v| v.as_str()).map(String::from)
+                }
+                toml::Value::Table(t) => {
+                    t.get("version").and_then(|v| v.as_str()).map(String::from)
+                }
+                _ => None,
+            };
+            usage(known_tool(name).unwrap_or(name), source, version)
+        })
+        .collect()
+}
+
+/// devcontainer.json features, e.g. "ghcr.io/devcontainers/features/node:1": {"version": "20"}.
+/// The file is JSONC, so comment lines are dropped first.
+fn parse_devcontainer(content: &str, source: &str) -> Vec<ToolUsage> {
+    let json: String = content
+        .lines()
+        .filter(|l| !l.trim_start().starts_with("//"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(&json) else {
+        return Vec::new();
+    };
+    let Some(features) = value["features"].as_object() else {
+        return Vec::new();
+    };
+    features
+        .iter()
+        .filter_map(|(id, options)| {
+            // ".../features/node:1" -> "node"
+            let name = id.rsplit('/').next()?.split(':').next()?;
+            let version = options["version"].as_str().map(String::from);
+            Some(usage(known_tool(name).unwrap_or(name), source, version))
+        })
+        .collect()
+}
+
+static NIX_LIST_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?s)(?:packages|buildInputs|nativeBuildInputs)\s*=\s*(?:with\s+pkgs;\s*)?\[(.*?)\]",
+    )
+    .unwrap()
+});
+
+/// Package lists in a flake's dev shell: `packages = with pkgs; [ nodejs_20 python311 ]`
+fn parse_flake(content: &str, source: &str) -> Vec<ToolUsage> {
+    let mut tools = Vec::new();
+    for list in NIX_LIST_RE.captures_iter(content) {
+        for item in list[1].split_whitespace() {
+            let name = item.trim_start_matches("pkgs.");
+            let Some(tool) = known_tool(name) else {
+                continue;
+            };
+            // nodejs_20 -> 20, python311 -> 3.11
+            let digits: String = name.chars().filter(|c| c.is_ascii_digit()).collect();
+            let version = match (tool, digits.len()) {
+                (_, 0) => None,
+                ("python", 3) => Some(format!("{}.{}", &digits[..1], &digits[1..])),
+                _ => Some(digits),
+            };
+            tools.push(usage(tool, source, version));
+        }
+    }
+    tools
+}
+
+static README_VERSION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\d+(?:\.\d+)*)\s*(\+|or (?:newer|later|higher))?").unwrap());
+
+/// List items under a "Requirements" / "Prerequisites" heading: "- Node.js 20+",
+/// "* [Python](https://python.org) 3.11 or later"
+fn parse_readme(content: &str, source: &str) -> Vec<ToolUsage> {
+    let mut tools = Vec::new();
+    let mut in_section = false;
+    for line in content.lines() {
+        if let Some(heading) = line.trim_start().strip_prefix('#') {
+            let heading = heading.to_lowercase();
+            in_section = ["requirement", "prerequisite", "dependencies", "you'll need"]
+                .iter()
+                .any(|w| heading.contains(w));
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+        let Some(item) = line
+            .trim_start()
+            .strip_prefix("- ")
+            .or_else(|| line.trim_start().strip_prefix("* "))
+        else {
+            continue;
+        };
+        // "[Node.js](https://...)" -> "Node.js"
+        let text = item.replace(['[', '`', '*'], "");
+        let text = text.split("](").next().unwrap_or_default().to_string()
+            + item.split(')').nth(1).unwrap_or_default();
+        let Some(tool) = text.split_whitespace().next().and_then(known_tool) else {
+            continue;
+        };
+        let version = README_VERSION_RE.captures(&text).map(|c| {
+            if c.get(2).is_some() {
+                format!(">={}", &c[1])
+            } else {
+                c[1].to_string()
+            }
+        });
+        tools.push(usage(tool, source, version));
+    }
+    tools
+}
+
+/// Tools a project's environment files ask for, first mention of each tool wins
+pub fn read_setup_tools(dir: &Path) -> Vec<ToolUsage> {
+    let read = |file: &str| std::fs::read_to_string(dir.join(file)).ok();
+    let mut tools: Vec<ToolUsage> = Vec::new();
+
+    for file in ["mise.toml", ".mise.toml"] {
+        if let Some(content) = read(file) {
+            tools.extend(parse_mise(&content, file));
+        }
+    }
+    for file in [".devcontainer/devcontainer.json", ".devcontainer.json"] {
+        if let Some(content) = read(file) {
+            tools.extend(parse_devcontainer(&content, file));
+        }
+    }
+    if let Some(content) = read("flake.nix") {
+        tools.extend(parse_flake(&content, "flake.nix"));
+    }
+    if let Some(content) = read("README.md") {
+        tools.extend(parse_readme(&content, "README.md"));
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    tools.retain(|t| seen.insert(t.tool.clone()));
+    tools
+}
+
+/// What to do about one tool a project asks for
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlanAction {
+    Install,
+    Upgrade { package: String, installed: String },
+    Satisfied { installed: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct PlanStep {
+    pub tool: String,
+    pub version: Option<String>,
+    pub source: String,
+    pub formula: Option<String>,
+    pub action: PlanAction,
+}
+
+/// "20" means 20.x, "3.11" at least 3.11 in the same major, ">=20" is already a constraint
+fn version_constraint(version: &str) -> String {
+    if version.starts_with(['>', '<', '=', '^', '~']) {
+        version.to_string()
+    } else {
+        format!("^{}", version.trim_start_matches('v'))
+    }
+}
+
+/// Compare the tools a project asks for against the inventory: installed copies by name or
+/// versioned formula (`node@20`), the newest one deciding whether an upgrade is needed
+pub fn install_plan(project: &Project, packages: &[Package]) -> Vec<PlanStep> {
+    project
+        .setup_tools
+        .iter()
+        .map(|tool| {
+            let formula = setup_formula(&tool.tool);
+            let names = [Some(tool.tool.as_str()), formula.as_deref()];
+            let installed: Vec<&Package> = packages
+                .iter()
+                .filter(|p| {
+                    names.iter().flatten().any(|n| {
+                        p.name == *n
+                            || p.name
+                                .strip_prefix(n)
+                                .is_some_and(|rest| rest.starts_with('@'))
+                    })
+                })
+                .collect();
+
+            let Some(newest) = installed
+                .iter()
+                .max_by(|a, b| compare_versions(&a.installed_version, &b.installed_version))
+            else {
+                return PlanStep {
+                    tool: tool.tool.clone(),
+                    version: tool.rev.clone(),
+                    source: tool.source.clone(),
+                    formula,
+                    action: PlanAction::Install,
+                };
+            };
+            let action = match &tool.rev {
+                Some(version) => {
+                    let constraint = version_constraint(version);
+                    match installed
+                        .iter()
+                        .find(|p| satisfies(&p.installed_version, &constraint) != Some(false))
+                    {
+                        Some(pkg) => PlanAction::Satisfied {
+                            installed: pkg.installed_version.clone(),
+                        },
+                        None => PlanAction::Upgrade {
+                            package: newest.name.clone(),
+                            installed: newest.installed_version.clone(),
+                        },
+                    }
+                }
+                None => PlanAction::Satisfied {
+                    installed: newest.installed_version.clone(),
+                },
+            };
+            PlanStep {
+                tool: tool.tool.clone(),
+                version: tool.rev.clone(),
+                source: tool.source.clone(),
+                formula,
+                action,
+            }
+        })
+        .collect()
+}
+
+impl PlanStep {
+    /// The version range the project asks for, if it pins one
+    pub fn constraint(&self) -> Option<String> {
+        self.version.as_deref().map(version_constraint)
+    }
+
+    /// Homebrew's versioned formula for a plain pin: "22" is node@22, "3.12" python@3.12
+    pub fn versioned_formula(&self) -> Option<String> {
+        let formula = self.formula.as_deref()?;
+        let version = self.version.as_deref()?.trim_start_matches('v');
+        version
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '.')
+            .then(|| format!("{}@{}", formula, version))
+    }
+
+    /// Shell command that closes the gap, if there is one to close
+    pub fn command(&self) -> Option<String> {
+        let formula = self.formula.as_deref();
+        match (&self.action, formula, self.versioned_formula()) {
+            (PlanAction::Satisfied { .. }, _, _) => None,
+            (_, _, Some(versioned)) => Some(format!("brew install {}", versioned)),
+            // Upgrading the unversioned formula is enough; node@20 needs the newer one installed
+            (PlanAction::Upgrade { package, .. }, Some(formula), None) if package == formula => {
+                Some(format!("brew upgrade {}", formula))
+            }
+            (_, Some(formula), None) => Some(format!("brew install {}", formula)),
+            (_, None, _) => Some(format!("# {}: install it manually", self.tool)),
+        }
+    }
+}
+
+/// The plan's gaps as a shell script
+pub fn plan_script(steps: &[PlanStep]) -> String {
+    steps
+        .iter()
+        .filter_map(|step| step.command())
+        .map(|line| line + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PackageManager;
+
+    #[test]
+    fn setup_tools_from_each_source() {
+        let mise = parse_mise(
+            "[tools]\nnode = \"20\"\npython = [\"3.12\", \"3.11\"]\n\"npm:prettier\" = \"latest\"\n",
+            "mise.toml",
+        );
+        assert_eq!(mise.len(), 2);
+        assert_eq!(mise[1].rev.as_deref(), Some("3.12"));
+
+        let devcontainer = parse_devcontainer(
+            "{\n  // comment\n  \"features\": {\"ghcr.io/devcontainers/features/github-cli:1\": {}, \"ghcr.io/devcontainers/features/go:1\": {\"version\": \"1.22\"}}\n}",
+            ".devcontainer/devcontainer.json",
+        );
+        assert_eq!(devcontainer[0].tool, "gh");
+        assert_eq!(devcontainer[1].rev.as_deref(), Some("1.22"));
+
+        let flake = parse_flake(
+            "devShells.default = pkgs.mkShell { packages = with pkgs; [ nodejs_20 python311 jq ]; };",
+            "flake.nix",
+        );
+        let flake: Vec<(&str, Option<&str>)> = flake
+            .iter()
+            .map(|t| (t.tool.as_str(), t.rev.as_deref()))
+            .collect();
+        assert_eq!(
+            flake,
+            [("node", Some("20")), ("python", Some("3.11")), ("jq", None)]
+        );
+
+        let readme = parse_readme(
+            "# App\n\n## Prerequisites\n\n- [Node.js](https://nodejs.org) 18 or later\n- Docker\n- A GitHub account\n\n## Usage\n- make\n",
+            "README.md",
+        );
+        let readme: Vec<(&str, Option<&str>)> = readme
+            .iter()
+            .map(|t| (t.tool.as_str(), t.rev.as_deref()))
+            .collect();
+        assert_eq!(readme, [("node", Some(">=18")), ("docker", None)]);
+    }
+
+    #[test]
+    fn plan_installs_and_upgrades() {
+        let mut project = Project::new(std::path::PathBuf::from("/tmp/app"));
+        project.setup_tools = vec![
+            usage("node", "mise.toml", Some("22".to_string())),
+            usage("python", "mise.toml", Some("3.12".to_string())),
+            usage("jq", "flake.nix", None),
+        ];
+        let packages = vec![
+            Package::new("node@20", PackageManager::Homebrew, "20.11.1"),
+            Package::new("python@3.12", PackageManager::Homebrew, "3.12.4"),
+        ];
+
+        let plan = install_plan(&project, &packages);
+        assert_eq!(
+            plan[0].action,
+            PlanAction::Upgrade {
+                package: "node@20".to_string(),
+                installed: "20.11.1".to_string()
+            }
+        );
+        assert!(matches!(plan[1].action, PlanAction::Satisfied { .. }));
+        assert_eq!(plan[2].action, PlanAction::Install);
+        assert_eq!(
+            plan_script(&plan),
+            "brew install node@22\nbrew install jq\n"
+        );
+    }
+}
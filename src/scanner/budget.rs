@@ -0,0 +1,41 @@
+use std::time::{Duration, Instant};
+
+/// How much walking one scan root may cost before the rest of it is skipped
+#[derive(Debug, Clone, Copy)]
+pub struct ScanBudget {
+    pub max_files: usize,
+    pub max_time: Duration,
+}
+
+impl ScanBudget {
+    /// A deep scan: every folder within the depth limit
+    pub fn unlimited() -> Self {
+        Self {
+            max_files: usize::MAX,
+            max_time: Duration::MAX,
+        }
+    }
+
+    /// Start spending the budget on one scan root
+    pub fn meter(&self) -> BudgetMeter {
+        BudgetMeter {
+            budget: *self,
+            started: Instant::now(),
+            files: 0,
+        }
+    }
+}
+
+pub struct BudgetMeter {
+    budget: ScanBudget,
+    started: Instant,
+    files: usize,
+}
+
+impl BudgetMeter {
+    /// Count `files` more files and folders looked at; false once the root's budget is spent
+    pub fn spend(&mut self, files: usize) -> bool {
+        self.files = self.files.saturating_add(files);
+        self.files <= self.budget.max_files && self.started.elapsed() <= self.budget.max_time
+    }
+}
@@ -0,0 +1,36 @@
+use std::fs::Metadata;
+
+/// `SF_DATALESS` from <sys/stat.h>: the item's contents stay in the cloud until first read
+#[cfg(target_os = "macos")]
+const SF_DATALESS: u32 = 0x4000_0000;
+
+/// `FILE_ATTRIBUTE_OFFLINE`, `FILE_ATTRIBUTE_RECALL_ON_OPEN` and
+/// `FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS` from winnt.h: a cloud files placeholder
+#[cfg(windows)]
+const CLOUD_PLACEHOLDER: u32 = 0x1000 | 0x0004_0000 | 0x0040_0000;
+
+/// Whether reading this file or listing this folder would download it first: a dataless
+/// File Provider item (iCloud Drive, OneDrive, Dropbox on current macOS) or a Windows
+/// cloud files placeholder (OneDrive Files On-Demand)
+pub fn is_dataless(meta: &Metadata) -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        use std::os::macos::fs::MetadataExt;
+        meta.st_flags() & SF_DATALESS != 0
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        meta.file_attributes() & CLOUD_PLACEHOLDER != 0
+    }
+    #[cfg(not(any(target_os = "macos", windows)))]
+    {
+        let _ = meta;
+        false
+    }
+}
+
+/// The file an older iCloud stub stands in for: ".package.json.icloud" -> "package.json"
+pub fn icloud_stub_of(name: &str) -> Option<&str> {
+    name.strip_prefix('.')?.strip_suffix(".icloud")
+}
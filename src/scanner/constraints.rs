@@ -0,0 +1,131 @@
+use crate::models::{ConstraintMismatch, Dependency, Package, PackageManager, Project};
+use crate::utils::version::{compare_versions, satisfies};
+use std::path::Path;
+
+fn requirement(tool: &str, manager: PackageManager, constraint: &str) -> Dependency {
+    Dependency {
+        package_name: tool.to_string(),
+        manager,
+        version_constraint: constraint.trim().to_string(),
+        is_dev: false,
+        locked_version: None,
+        latest_version: None,
+    }
+}
+
+fn read_toml(path: &Path) -> Option<toml::Value> {
+    std::fs::read_to_string(path).ok()?.parse().ok()
+}
+
+/// Versions of globally installed tools a project declares it needs
+pub fn read_tool_requirements(dir: &Path) -> Vec<Dependency> {
+    let mut requirements = Vec::new();
+
+    // package.json "engines": { "node": ">=20", "npm": ">=10" }
+    if let Some(json) = std::fs::read_to_string(dir.join("package.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+    {
+        if let Some(node) = json["engines"]["node"].as_str() {
+            requirements.push(requirement("node", PackageManager::Homebrew, node));
+        }
+        if let Some(npm) = json["engines"]["npm"].as_str() {
+            requirements.push(requirement("npm", PackageManager::Npm, npm));
+        }
+    }
+
+    // Cargo.toml rust-version is a minimum, not a semver range
+    if let Some(manifest) = read_toml(&dir.join("Cargo.toml")) {
+        if let Some(msrv) = manifest
+            .get("package")
+            .and_then(|p| p.get("rust-version"))
+            .and_then(|v| v.as_str())
+        {
+            requirements.push(requirement(
+                "rust",
+                PackageManager::Homebrew,
+                &format!(">={}", msrv),
+            ));
+        }
+    }
+
+    // pyproject.toml requires-python (PEP 621) or Poetry's python dependency
+    if let Some(manifest) = read_toml(&dir.join("pyproject.toml")) {
+        let python = manifest
+            .get("project")
+            .and_then(|p| p.get("requires-python"))
+            .or_else(|| {
+                manifest
+                    .get("tool")
+                    .and_then(|t| t.get("poetry"))
+                    .and_then(|p| p.get("dependencies"))
+                    .and_then(|d| d.get("python"))
+            })
+            .and_then(|v| v.as_str());
+        if let Some(python) = python {
+            requirements.push(requirement("python", PackageManager::Homebrew, python));
+        }
+    }
+
+    requirements
+}
+
+/// "node" matches both `node` and versioned formulae like `node@20`
+fn provides_tool(pkg: &Package, requirement: &Dependency) -> bool {
+    pkg.manager == requirement.manager
+        && (pkg.name == requirement.package_name
+            || pkg
+                .name
+                .strip_prefix(&requirement.package_name)
+                .is_some_and(|rest| rest.starts_with('@')))
+}
+
+/// Flag installed tools that are too old for the projects that declare a version for them.
+/// When several versions are installed (python@3.11, python@3.12) any match is enough;
+/// otherwise the newest one gets the warning since that's the one to update.
+pub fn check_tool_requirements(packages: &mut [Package], projects: &[Project]) {
+    for pkg in packages.iter_mut() {
+        pkg.constraint_mismatches.clear();
+    }
+
+    let mut mismatches = 0;
+    for project in projects {
+        for requirement in &project.tool_requirements {
+            let candidates: Vec<usize> = packages
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| provides_tool(p, requirement))
+                .map(|(i, _)| i)
+                .collect();
+
+            let satisfied = candidates.iter().any(|&i| {
+                // Unknown syntax: don't warn about something we can't evaluate
+                satisfies(
+                    &packages[i].installed_version,
+                    &requirement.version_constraint,
+                ) != Some(false)
+            });
+            if satisfied {
+                continue;
+            }
+
+            let newest = candidates.into_iter().max_by(|&a, &b| {
+                compare_versions(
+                    &packages[a].installed_version,
+                    &packages[b].installed_version,
+                )
+            });
+            if let Some(idx) = newest {
+                packages[idx]
+                    .constraint_mismatches
+                    .push(ConstraintMismatch {
+                        project: project.path.to_string_lossy().to_string(),
+                        constraint: requirement.version_constraint.clone(),
+                    });
+                mismatches += 1;
+            }
+        }
+    }
+
+    println!("[DEBUG] Found {} tool version mismatches", mismatches);
+}
@@ -0,0 +1,96 @@
+use super::manifests::dependency;
+use crate::models::{Dependency, PackageManager};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// One `name:` entry two spaces into a pubspec section, with its inline value and
+/// any nested `key: value` lines
+struct Entry {
+    section: String,
+    name: String,
+    value: String,
+    nested: HashMap<String, String>,
+}
+
+fn unquote(value: &str) -> String {
+    value
+        .split(" #")
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .trim_matches(['"', '\''])
+        .to_string()
+}
+
+/// The little of YAML that pubspec files use: top-level sections, two-space entries
+/// and their nested keys
+fn entries(content: &str) -> Vec<Entry> {
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut section = String::new();
+    for line in content.lines() {
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        let Some((key, value)) = line.trim().split_once(':') else {
+            continue;
+        };
+        match indent {
+            0 => section = key.to_string(),
+            2 => entries.push(Entry {
+                section: section.clone(),
+                name: key.to_string(),
+                value: unquote(value),
+                nested: HashMap::new(),
+            }),
+            _ => {
+                if let Some(entry) = entries.last_mut() {
+                    entry.nested.insert(key.to_string(), unquote(value));
+                }
+            }
+        }
+    }
+    entries
+}
+
+/// Hosted dependencies from pubspec.yaml, with versions pinned by pubspec.lock; SDK, git
+/// and path dependencies have nothing to check on pub.dev
+pub fn parse_pubspec(dir: &Path) -> Vec<Dependency> {
+    let Ok(pubspec) = std::fs::read_to_string(dir.join("pubspec.yaml")) else {
+        return Vec::new();
+    };
+    let locked: HashMap<String, String> = std::fs::read_to_string(dir.join("pubspec.lock"))
+        .map(|lock| {
+            entries(&lock)
+                .into_iter()
+                .filter(|e| e.section == "packages")
+                .filter_map(|e| Some((e.name, e.nested.get("version")?.clone())))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    entries(&pubspec)
+        .into_iter()
+        .filter(|e| e.section == "dependencies" || e.section == "dev_dependencies")
+        .filter(|e| {
+            !["sdk", "git", "path"]
+                .iter()
+                .any(|k| e.nested.contains_key(*k))
+        })
+        .map(|e| {
+            let constraint = match e.nested.get("version") {
+                Some(version) => version.clone(),
+                None if e.value.is_empty() || e.value == "any" => "*".to_string(),
+                None => e.value.clone(),
+            };
+            let mut dep = dependency(
+                &e.name,
+                PackageManager::Pub,
+                &constraint,
+                e.section == "dev_dependencies",
+            );
+            dep.locked_version = locked.get(&e.name).cloned();
+            dep
+        })
+        .collect()
+}
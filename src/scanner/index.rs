@@ -0,0 +1,526 @@
+use super::cloud::{icloud_stub_of, is_dataless};
+use super::manifests::{is_walked, read_project, SCAN_DEPTH};
+use super::ScanBudget;
+use crate::models::Project;
+use crate::utils::paths::data_dir;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Files directly in a folder that `read_project` parses (plus requirements*.txt)
+const MANIFEST_FILES: &[&str] = &[
+    "package.json",
+    "Cargo.toml",
+    "go.mod",
+    "pubspec.yaml",
+    "pubspec.lock",
+    "Package.swift",
+    "Package.resolved",
+    "pom.xml",
+    "build.gradle",
+    "build.gradle.kts",
+    "pyproject.toml",
+    "setup.py",
+    "Pipfile",
+    "Pipfile.lock",
+    "uv.lock",
+    "poetry.lock",
+    ".pre-commit-config.yaml",
+    "mise.toml",
+    ".mise.toml",
+    ".devcontainer.json",
+    "flake.nix",
+    "README.md",
+];
+
+/// Folders inside a project whose files `read_project` also parses. `.github` is listed
+/// so a newly created `workflows` folder changes its fingerprint.
+const NESTED_DIRS: &[&str] = &[
+    ".github",
+    ".github/workflows",
+    ".devcontainer",
+    ".venv",
+    "venv",
+    "gradle",
+    "requirements",
+];
+
+/// Modification time and size, to tell whether a file changed since the last scan
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Fingerprint {
+    modified: SystemTime,
+    len: u64,
+}
+
+fn fingerprint(path: &Path) -> Option<Fingerprint> {
+    let meta = std::fs::metadata(path).ok()?;
+    Some(Fingerprint {
+        modified: meta.modified().ok()?,
+        len: meta.len(),
+    })
+}
+
+/// What the last scan saw in one folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry {
+    modified: SystemTime, // The folder's own mtime, which changes when entries come or go
+    children: Vec<String>, // Subfolders to walk
+    inputs: Vec<(String, Fingerprint)>, // Manifests and nested folders, relative to it
+    project: Option<Project>,
+    #[serde(default)]
+    listed: usize, // Entries in the folder, charged to the scan budget
+    #[serde(default)]
+    excluded: usize, // Subfolders never walked: hidden, node_modules, build output
+    #[serde(default)]
+    skipped: Option<SkipReason>, // Why it wasn't read; such folders are retried every scan
+}
+
+impl Entry {
+    fn manifests(&self) -> usize {
+        self.inputs
+            .iter()
+            .filter(|(name, _)| is_manifest(name))
+            .count()
+    }
+}
+
+/// Why the walk left a folder out
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SkipReason {
+    SymlinkLoop,      // A symlink back to a folder above it
+    CloudPlaceholder, // Reading it would download it from iCloud/OneDrive first
+    Unreadable,       // Permission denied, or gone mid-scan
+}
+
+impl SkipReason {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SkipReason::SymlinkLoop => "symlink loop",
+            SkipReason::CloudPlaceholder => "not downloaded from the cloud",
+            SkipReason::Unreadable => "permission denied",
+        }
+    }
+}
+
+/// How walking one scan root went
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RootStatus {
+    Scanned,
+    CutShort, // The scan budget ran out
+    Missing,
+    Excluded, // Its name is one the walk skips, e.g. a hidden folder
+    Overlaps, // Already walked as part of another scan root
+}
+
+impl RootStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RootStatus::Scanned => "scanned",
+            RootStatus::CutShort => "budget reached",
+            RootStatus::Missing => "doesn't exist",
+            RootStatus::Excluded => "excluded by name",
+            RootStatus::Overlaps => "inside another scan root",
+        }
+    }
+}
+
+/// One scan root's share of a scan
+#[derive(Debug, Clone)]
+pub struct RootReport {
+    pub path: PathBuf,
+    pub status: RootStatus,
+    pub folders: usize,
+    pub projects: usize,
+    pub manifests: usize,
+    pub excluded: usize, // Subfolders left out by name
+    pub reread: usize,   // Folders that changed since the last scan
+    pub elapsed: Duration,
+}
+
+/// What a scan covered, per scan root, and the folders it skipped on purpose
+#[derive(Debug, Clone, Default)]
+pub struct ScanReport {
+    pub roots: Vec<RootReport>,
+    pub skipped: Vec<(PathBuf, SkipReason)>,
+}
+
+impl ScanReport {
+    /// Scan roots the budget ran out in
+    pub fn cut_short(&self) -> Vec<&Path> {
+        self.roots
+            .iter()
+            .filter(|r| r.status == RootStatus::CutShort)
+            .map(|r| r.path.as_path())
+            .collect()
+    }
+}
+
+fn is_manifest(name: &str) -> bool {
+    MANIFEST_FILES.contains(&name) || (name.ends_with(".txt") && name.contains("requirements"))
+}
+
+/// List a folder: the subfolders to walk (symlinked ones too) and the manifest files its
+/// project is read from. A manifest that's only a cloud placeholder isn't read at all.
+fn read_folder(dir: &Path, modified: SystemTime) -> Entry {
+    let mut entry = Entry {
+        modified,
+        children: Vec::new(),
+        inputs: Vec::new(),
+        project: None,
+        listed: 0,
+        excluded: 0,
+        skipped: None,
+    };
+    let listing = match std::fs::read_dir(dir) {
+        Ok(listing) => listing,
+        Err(_) => {
+            entry.skipped = Some(SkipReason::Unreadable);
+            return entry;
+        }
+    };
+    for item in listing.flatten() {
+        entry.listed += 1;
+        let name = item.file_name().to_string_lossy().to_string();
+        let Ok(file_type) = item.file_type() else {
+            continue;
+        };
+        let is_dir = file_type.is_dir() || (file_type.is_symlink() && item.path().is_dir());
+        if is_dir && is_walked(&name) {
+            entry.children.push(name);
+        } else if is_dir {
+            entry.excluded += 1;
+        } else if file_type.is_file() && is_manifest(&name) {
+            if item.metadata().is_ok_and(|m| is_dataless(&m)) {
+                entry.skipped = Some(SkipReason::CloudPlaceholder);
+            }
+            if let Some(fp) = fingerprint(&item.path()) {
+                entry.inputs.push((name, fp));
+            }
+        } else if icloud_stub_of(&name).is_some_and(is_manifest) {
+            entry.skipped = Some(SkipReason::CloudPlaceholder);
+        }
+    }
+    for nested in NESTED_DIRS {
+        let path = dir.join(nested);
+        if !path.is_dir() {
+            continue;
+        }
+        if let Some(fp) = fingerprint(&path) {
+            entry.inputs.push((nested.to_string(), fp));
+        }
+        for item in std::fs::read_dir(&path).into_iter().flatten().flatten() {
+            if item.file_type().is_ok_and(|t| t.is_file()) {
+                if let Some(fp) = fingerprint(&item.path()) {
+                    let name = item.file_name().to_string_lossy().to_string();
+                    entry.inputs.push((format!("{}/{}", nested, name), fp));
+                }
+            }
+        }
+    }
+    entry.children.sort();
+    if entry.skipped.is_none() {
+        entry.project = read_project(dir);
+    }
+    entry
+}
+
+/// The project scan from last time, by folder, so unchanged folders aren't listed or parsed
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ScanIndex {
+    entries: HashMap<PathBuf, Entry>,
+    #[serde(skip)]
+    report: ScanReport, // What the last `scan` covered
+}
+
+fn index_path() -> PathBuf {
+    data_dir().join("scan_index.json")
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+impl ScanIndex {
+    pub fn load() -> Self {
+        std::fs::read_to_string(index_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        let path = index_path();
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        match serde_json::to_string(self) {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(&path, json) {
+                    eprintln!("[ERROR] Failed to write {}: {}", path.display(), e);
+                }
+            }
+            Err(e) => eprintln!("[ERROR] Failed to serialize the scan index: {}", e),
+        }
+    }
+
+    /// Same folders as `scan_projects`, most recently modified first and within `budget`
+    /// per scan root. Symlinked folders are followed unless they loop back, and cloud
+    /// placeholders are left alone. A folder whose mtime and manifests match the index
+    /// reuses its listing and project. Folders no longer reached are dropped, unless the
+    /// budget ran out first.
+    pub fn scan(&mut self, scan_dirs: &[PathBuf], budget: &ScanBudget) -> Vec<Project> {
+        let mut previous = std::mem::take(&mut self.entries);
+        let mut projects = Vec::new();
+        // Resolved paths walked so far, so symlinks and overlapping roots don't repeat work
+        let mut visited: HashSet<PathBuf> = HashSet::new();
+        self.report = ScanReport::default();
+
+        for base_dir in scan_dirs {
+            let started = Instant::now();
+            let mut root = RootReport {
+                path: base_dir.clone(),
+                status: RootStatus::Scanned,
+                folders: 0,
+                projects: 0,
+                manifests: 0,
+                excluded: 0,
+                reread: 0,
+                elapsed: Duration::ZERO,
+            };
+            let walked = base_dir
+                .file_name()
+                .is_none_or(|name| is_walked(&name.to_string_lossy()));
+            let root_modified = modified(base_dir).filter(|_| base_dir.is_dir());
+            let root_real = std::fs::canonicalize(base_dir).unwrap_or_else(|_| base_dir.clone());
+            let root_modified = match root_modified {
+                None => {
+                    root.status = RootStatus::Missing;
+                    self.report.roots.push(root);
+                    continue;
+                }
+                Some(_) if !walked => {
+                    root.status = RootStatus::Excluded;
+                    self.report.roots.push(root);
+                    continue;
+                }
+                Some(_) if visited.contains(&root_real) => {
+                    root.status = RootStatus::Overlaps;
+                    self.report.roots.push(root);
+                    continue;
+                }
+                Some(modified) => modified,
+            };
+
+            let mut meter = budget.meter();
+            let mut queue =
+                BinaryHeap::from([(root_modified, Reverse(0), base_dir.clone(), root_real)]);
+            while let Some((modified_at, Reverse(depth), dir, real)) = queue.pop() {
+                if !visited.insert(real.clone()) {
+                    continue;
+                }
+                let entry = match previous.remove(&dir) {
+                    Some(entry)
+                        if entry.modified == modified_at
+                            && entry.skipped.is_none()
+                            && entry.inputs.iter().all(|(name, fp)| {
+                                fingerprint(&dir.join(name)).as_ref() == Some(fp)
+                            }) =>
+                    {
+                        entry
+                    }
+                    _ => {
+                        root.reread += 1;
+                        read_folder(&dir, modified_at)
+                    }
+                };
+
+                root.folders += 1;
+                root.manifests += entry.manifests();
+                root.excluded += entry.excluded;
+                if let Some(reason) = entry.skipped {
+                    self.report.skipped.push((dir.clone(), reason));
+                }
+                if let Some(project) = &entry.project {
+                    root.projects += 1;
+                    projects.push(project.clone());
+                }
+                if depth < SCAN_DEPTH {
+                    for child in &entry.children {
+                        let path = dir.join(child);
+                        let Ok(meta) = std::fs::symlink_metadata(&path) else {
+                            continue;
+                        };
+                        let (child_real, child_meta) = if meta.is_symlink() {
+                            let Ok(target) = std::fs::canonicalize(&path) else {
+                                continue; // Dangling
+                            };
+                            if real.starts_with(&target) {
+                                self.report.skipped.push((path, SkipReason::SymlinkLoop));
+                                continue;
+                            }
+                            let Ok(meta) = std::fs::metadata(&target) else {
+                                continue;
+                            };
+                            (target, meta)
+                        } else {
+                            (real.join(child), meta)
+                        };
+                        if is_dataless(&child_meta) {
+                            self.report
+                                .skipped
+                                .push((path, SkipReason::CloudPlaceholder));
+                            continue;
+                        }
+                        if let Ok(child_modified) = child_meta.modified() {
+                            queue.push((child_modified, Reverse(depth + 1), path, child_real));
+                        }
+                    }
+                }
+                let listed = entry.listed;
+                self.entries.insert(dir, entry);
+
+                if !meter.spend(listed + 1) && !queue.is_empty() {
+                    println!(
+                        "[SCAN] Budget spent in {}, skipping {} older folders",
+                        base_dir.display(),
+                        queue.len()
+                    );
+                    root.status = RootStatus::CutShort;
+                    break;
+                }
+            }
+            root.elapsed = started.elapsed();
+            self.report.roots.push(root);
+        }
+
+        // Keep what a cut-short walk didn't reach, so the next scan can reuse it
+        let cut_short: Vec<PathBuf> = self
+            .report
+            .cut_short()
+            .into_iter()
+            .map(Path::to_path_buf)
+            .collect();
+        for (dir, entry) in previous {
+            if cut_short.iter().any(|root| dir.starts_with(root)) {
+                self.entries.entry(dir).or_insert(entry);
+            }
+        }
+        if !self.report.skipped.is_empty() {
+            println!(
+                "[SCAN] Skipped {} folders (symlink loops, cloud placeholders, permissions)",
+                self.report.skipped.len()
+            );
+        }
+        projects
+    }
+}
+
+/// `scan_projects` backed by the persisted index, for the regular scan roots: after the
+/// first run only folders that changed are listed and parsed. Also returns what the walk
+/// covered and left out.
+pub fn scan_projects_indexed(
+    scan_dirs: &[PathBuf],
+    budget: &ScanBudget,
+) -> (Vec<Project>, ScanReport) {
+    let mut index = ScanIndex::load();
+    let projects = index.scan(scan_dirs, budget);
+    index.save();
+    let reread: usize = index.report.roots.iter().map(|r| r.reread).sum();
+    println!(
+        "[DEBUG] Parsed manifests of {} projects ({} of {} folders changed since the last scan)",
+        projects.len(),
+        reread,
+        index.entries.len()
+    );
+    (projects, index.report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_dir::TestDir;
+
+    #[test]
+    fn unchanged_folders_come_from_the_index() {
+        let root = TestDir::new("index");
+        std::fs::create_dir_all(root.join("app").join("node_modules")).unwrap();
+        std::fs::write(root.join("app").join("package.json"), "{}").unwrap();
+        let roots = [root.to_path_buf()];
+
+        let mut index = ScanIndex::default();
+        assert_eq!(index.scan(&roots, &ScanBudget::unlimited()).len(), 1);
+        assert_eq!(index.report.roots[0].reread, 2);
+        let report = &index.report.roots[0];
+        assert_eq!(
+            (
+                report.folders,
+                report.projects,
+                report.manifests,
+                report.excluded
+            ),
+            (2, 1, 1, 1)
+        );
+        assert_eq!(index.scan(&roots, &ScanBudget::unlimited()).len(), 1);
+        assert_eq!(index.report.roots[0].reread, 0);
+
+        // An edited manifest re-reads just its folder
+        std::fs::write(
+            root.join("app").join("package.json"),
+            r#"{"dependencies": {"left-pad": "^1.3.0"}}"#,
+        )
+        .unwrap();
+        let projects = index.scan(&roots, &ScanBudget::unlimited());
+        assert_eq!(index.report.roots[0].reread, 1);
+        assert_eq!(projects[0].dependencies[0].package_name, "left-pad");
+
+        // A new folder changes its parent's listing
+        std::fs::create_dir_all(root.join("lib")).unwrap();
+        std::fs::write(root.join("lib").join("go.mod"), "module lib\n").unwrap();
+        assert_eq!(index.scan(&roots, &ScanBudget::unlimited()).len(), 2);
+        assert_eq!(index.report.roots[0].reread, 2);
+    }
+
+    #[test]
+    fn budget_keeps_the_newest_projects() {
+        let root = TestDir::new("budget");
+        for name in ["new", "old"] {
+            std::fs::create_dir_all(root.join(name)).unwrap();
+            std::fs::write(root.join(name).join("package.json"), "{}").unwrap();
+        }
+        let stale = SystemTime::now() - std::time::Duration::from_secs(86_400);
+        std::fs::File::open(root.join("old"))
+            .unwrap()
+            .set_modified(stale)
+            .unwrap();
+
+        let budget = ScanBudget {
+            max_files: 4,
+            max_time: std::time::Duration::MAX,
+        };
+        let mut index = ScanIndex::default();
+        let projects = index.scan(&[root.to_path_buf()], &budget);
+        assert_eq!(projects.len(), 1);
+        assert!(projects[0].path.ends_with("new"));
+        assert_eq!(index.report.cut_short(), [&*root]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_are_followed_once_and_loops_skipped() {
+        let root = TestDir::new("symlinks");
+        std::fs::create_dir_all(root.join("app")).unwrap();
+        std::fs::write(root.join("app").join("package.json"), "{}").unwrap();
+        std::os::unix::fs::symlink(&root, root.join("app").join("loop")).unwrap();
+        std::os::unix::fs::symlink(root.join("app"), root.join("alias")).unwrap();
+
+        let mut index = ScanIndex::default();
+        let projects = index.scan(&[root.to_path_buf()], &ScanBudget::unlimited());
+        assert_eq!(projects.len(), 1);
+        assert_eq!(
+            index.report.skipped,
+            [(root.join("app").join("loop"), SkipReason::SymlinkLoop)]
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use super::manifests::dependency;
+use crate::models::{Dependency, PackageManager};
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// `implementation("group:artifact:version")`, `testImplementation 'g:a:v'`
+static GRADLE_DEP_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?m)^\s*(\w+)\s*\(?\s*["']([^"':\s]+):([^"':\s]+):([^"'\s@]+)["']"#).unwrap()
+});
+
+/// `<name>value</name>` inside `<properties>`
+static XML_ELEMENT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<([\w.-]+)>([^<]*)</([\w.-]+)>").unwrap());
+
+/// Text of the first `<tag>...</tag>` in `xml`
+pub fn xml_tag<'a>(xml: &'a str, tag: &str) -> Option<&'a str> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let start = xml.find(&open)? + open.len();
+    let end = xml[start..].find(&close)? + start;
+    Some(xml[start..end].trim())
+}
+
+/// A version that names one release, not a range or an unresolved property
+fn exact_version(version: &str) -> Option<String> {
+    let is_exact = !version.is_empty()
+        && !version.contains(['$', '[', '(', '+', ','])
+        && !version.ends_with("-SNAPSHOT");
+    is_exact.then(|| version.to_string())
+}
+
+fn java_dependency(group: &str, artifact: &str, version: &str, is_dev: bool) -> Dependency {
+    let name = format!("{}:{}", group, artifact);
+    let mut dep = dependency(&name, PackageManager::Maven, version, is_dev);
+    dep.locked_version = exact_version(version);
+    dep
+}
+
+/// Best effort: `<dependency>` blocks with literal or `${property}` versions; test scope is dev
+pub fn parse_pom(path: &Path) -> Vec<Dependency> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+
+    let properties: HashMap<&str, &str> = xml_tag(&content, "properties")
+        .map(|props| {
+            XML_ELEMENT_RE
+                .captures_iter(props)
+                .filter(|c| c[1] == c[3])
+                .map(|c| {
+                    (
+                        c.get(1).unwrap().as_str(),
+                        c.get(2).unwrap().as_str().trim(),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let resolve = |value: &str| -> String {
+        value
+            .strip_prefix("${")
+            .and_then(|v| v.strip_suffix('}'))
+            .and_then(|name| properties.get(name))
+            .map(|v| v.to_string())
+            .unwrap_or_else(|| value.to_string())
+    };
+
+    let mut deps = Vec::new();
+    for block in content.split("<dependency>").skip(1) {
+        let block = block.split("</dependency>").next().unwrap_or("");
+        let (Some(group), Some(artifact)) =
+            (xml_tag(block, "groupId"), xml_tag(block, "artifactId"))
+        else {
+            continue;
+        };
+        let version = xml_tag(block, "version").map(resolve);
+        let is_dev = xml_tag(block, "scope") == Some("test");
+        deps.push(java_dependency(
+            &resolve(group),
+            &resolve(artifact),
+            version.as_deref().unwrap_or("*"),
+            is_dev,
+        ));
+    }
+    deps
+}
+
+/// Best effort: string-notation dependencies in build.gradle / build.gradle.kts
+pub fn parse_gradle(path: &Path) -> Vec<Dependency> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    GRADLE_DEP_RE
+        .captures_iter(&content)
+        .map(|c| {
+            let is_dev = c[1].starts_with("test") || c[1].starts_with("androidTest");
+            java_dependency(&c[2], &c[3], &c[4], is_dev)
+        })
+        .collect()
+}
+
+/// `[libraries]` of a Gradle version catalog (gradle/libs.versions.toml)
+pub fn parse_version_catalog(path: &Path) -> Vec<Dependency> {
+    let Some(catalog) = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|c| c.parse::<toml::Value>().ok())
+    else {
+        return Vec::new();
+    };
+    let versions = catalog.get("versions");
+
+    let mut deps = Vec::new();
+    for spec in catalog
+        .get("libraries")
+        .and_then(|l| l.as_table())
+        .into_iter()
+        .flat_map(|t| t.values())
+    {
+        // "g:a:v", { module = "g:a", version = "v" }, { group, name, version.ref }
+        let (coordinates, version) = match spec {
+            toml::Value::String(s) => match s.rsplit_once(':') {
+                Some((module, version)) => (module.to_string(), Some(version.to_string())),
+                None => continue,
+            },
+            toml::Value::Table(t) => {
+                let module = match (t.get("module"), t.get("group"), t.get("name")) {
+                    (Some(module), _, _) => module.as_str().unwrap_or_default().to_string(),
+                    (None, Some(group), Some(name)) => format!(
+                        "{}:{}",
+                        group.as_str().unwrap_or_default(),
+                        name.as_str().unwrap_or_default()
+                    ),
+                    _ => continue,
+                };
+                let version = match t.get("version") {
+                    Some(toml::Value::String(v)) => Some(v.clone()),
+                    Some(toml::Value::Table(v)) => v
+                        .get("ref")
+                        .and_then(|r| r.as_str())
+                        .and_then(|r| versions?.get(r)?.as_str())
+                        .map(|v| v.to_string()),
+                    _ => None,
+                };
+                (module, version)
+            }
+            _ => continue,
+        };
+        if let Some((group, artifact)) = coordinates.split_once(':') {
+            deps.push(java_dependency(
+                group,
+                artifact,
+                version.as_deref().unwrap_or("*"),
+                false,
+            ));
+        }
+    }
+    deps
+}
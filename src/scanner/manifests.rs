@@ -0,0 +1,921 @@
+use crate::managers::pip::normalize_name;
+use crate::models::{Dependency, Package, PackageManager, Project, ProjectRef, StaleHook, Venv};
+use crate::utils::paths::home_dir;
+use crate::utils::version::{compare_versions, satisfies};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Extras / dependency groups that only matter while developing
+const DEV_GROUPS: [&str; 6] = ["dev", "test", "tests", "lint", "docs", "typing"];
+
+/// requirements files that hold development-only dependencies
+const DEV_REQUIREMENTS: [&str; 4] = [
+    "requirements-dev.txt",
+    "dev-requirements.txt",
+    "requirements-test.txt",
+    "requirements/dev.txt",
+];
+
+/// Project-root directory names that hold a virtualenv
+const VENV_DIRS: [&str; 2] = [".venv", "venv"];
+
+pub(super) fn dependency(
+    name: &str,
+    manager: PackageManager,
+    constraint: &str,
+    is_dev: bool,
+) -> Dependency {
+    Dependency {
+        package_name: name.to_string(),
+        manager,
+        version_constraint: constraint.to_string(),
+        is_dev,
+        locked_version: None,
+        latest_version: None,
+    }
+}
+
+/// "requests[socks]>=2.0 ; python_version > '3.8'" -> ("requests", ">=2.0")
+fn parse_requirement(line: &str) -> Option<(String, String)> {
+    let line = line.split('#').next()?.split(';').next()?.trim();
+    if line.is_empty() || line.starts_with('-') {
+        return None;
+    }
+    let end = line
+        .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_' || c == '.'))
+        .unwrap_or(line.len());
+    let name = &line[..end];
+    if name.is_empty() {
+        return None;
+    }
+    let mut rest = line[end..].trim();
+    // Skip extras: "requests[socks]"
+    if rest.starts_with('[') {
+        rest = rest
+            .find(']')
+            .map(|idx| rest[idx + 1..].trim())
+            .unwrap_or("");
+    }
+    let constraint = if rest.is_empty() { "*" } else { rest };
+    Some((normalize_name(name), constraint.to_string()))
+}
+
+fn parse_package_json(path: &Path) -> Vec<Dependency> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return Vec::new();
+    };
+
+    let mut deps = Vec::new();
+    for (section, is_dev) in [
+        ("dependencies", false),
+        ("peerDependencies", false),
+        ("optionalDependencies", false),
+        ("devDependencies", true),
+    ] {
+        if let Some(map) = json[section].as_object() {
+            for (name, constraint) in map {
+                deps.push(dependency(
+                    name,
+                    PackageManager::Npm,
+                    constraint.as_str().unwrap_or("*"),
+                    is_dev,
+                ));
+            }
+        }
+    }
+    deps
+}
+
+fn cargo_table_deps(table: Option<&toml::Value>, is_dev: bool, deps: &mut Vec<Dependency>) {
+    let Some(table) = table.and_then(|t| t.as_table()) else {
+        return;
+    };
+    for (name, spec) in table {
+        // `foo = "1.0"` or `foo = { version = "1.0", package = "real-name" }`
+        let (name, constraint) = match spec {
+            toml::Value::String(v) => (name.as_str(), v.as_str()),
+            toml::Value::Table(t) => (
+                t.get("package").and_then(|p| p.as_str()).unwrap_or(name),
+                t.get("version").and_then(|v| v.as_str()).unwrap_or("*"),
+            ),
+            _ => (name.as_str(), "*"),
+        };
+        deps.push(dependency(name, PackageManager::Cargo, constraint, is_dev));
+    }
+}
+
+fn parse_cargo_toml(path: &Path) -> Vec<Dependency> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let Ok(manifest) = content.parse::<toml::Value>() else {
+        return Vec::new();
+    };
+
+    let mut deps = Vec::new();
+    let mut tables = vec![&manifest];
+    // [target.'cfg(...)'.dependencies] has the same shape as the top level
+    if let Some(targets) = manifest.get("target").and_then(|t| t.as_table()) {
+        tables.extend(targets.values());
+    }
+    for table in tables {
+        cargo_table_deps(table.get("dependencies"), false, &mut deps);
+        cargo_table_deps(table.get("build-dependencies"), false, &mut deps);
+        cargo_table_deps(table.get("dev-dependencies"), true, &mut deps);
+    }
+    deps
+}
+
+/// PEP 508 strings: `["requests>=2", "rich"]`
+fn push_requirement_list(list: Option<&toml::Value>, is_dev: bool, deps: &mut Vec<Dependency>) {
+    for item in list.and_then(|l| l.as_array()).into_iter().flatten() {
+        if let Some((name, constraint)) = item.as_str().and_then(parse_requirement) {
+            deps.push(dependency(&name, PackageManager::Pip, &constraint, is_dev));
+        }
+    }
+}
+
+/// Poetry and Pipfile tables: `requests = "^2.0"` or `requests = { version = "^2.0" }`
+fn python_table_deps(table: Option<&toml::Value>, is_dev: bool, deps: &mut Vec<Dependency>) {
+    for (name, spec) in table.and_then(|t| t.as_table()).into_iter().flatten() {
+        if name == "python" {
+            continue;
+        }
+        let constraint = match spec {
+            toml::Value::String(v) => v.as_str(),
+            toml::Value::Table(t) => t.get("version").and_then(|v| v.as_str()).unwrap_or("*"),
+            _ => "*",
+        };
+        deps.push(dependency(
+            &normalize_name(name),
+            PackageManager::Pip,
+            constraint,
+            is_dev,
+        ));
+    }
+}
+
+fn parse_pyproject(path: &Path) -> Vec<Dependency> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let Ok(manifest) = content.parse::<toml::Value>() else {
+        return Vec::new();
+    };
+
+    let mut deps = Vec::new();
+
+    // PEP 621
+    let project = manifest.get("project");
+    push_requirement_list(
+        project.and_then(|p| p.get("dependencies")),
+        false,
+        &mut deps,
+    );
+    if let Some(extras) = project
+        .and_then(|p| p.get("optional-dependencies"))
+        .and_then(|e| e.as_table())
+    {
+        for (group, list) in extras {
+            push_requirement_list(Some(list), DEV_GROUPS.contains(&group.as_str()), &mut deps);
+        }
+    }
+
+    // PEP 735 dependency groups are development-only by definition
+    if let Some(groups) = manifest.get("dependency-groups").and_then(|g| g.as_table()) {
+        for list in groups.values() {
+            push_requirement_list(Some(list), true, &mut deps);
+        }
+    }
+
+    // uv keeps dev-only requirements outside PEP 621
+    push_requirement_list(
+        manifest
+            .get("tool")
+            .and_then(|t| t.get("uv"))
+            .and_then(|u| u.get("dev-dependencies")),
+        true,
+        &mut deps,
+    );
+
+    // Poetry
+    let poetry = manifest.get("tool").and_then(|t| t.get("poetry"));
+    python_table_deps(poetry.and_then(|p| p.get("dependencies")), false, &mut deps);
+    python_table_deps(
+        poetry.and_then(|p| p.get("dev-dependencies")),
+        true,
+        &mut deps,
+    );
+    if let Some(groups) = poetry
+        .and_then(|p| p.get("group"))
+        .and_then(|g| g.as_table())
+    {
+        for (group, spec) in groups {
+            let is_dev = group != "main";
+            python_table_deps(spec.get("dependencies"), is_dev, &mut deps);
+        }
+    }
+
+    deps
+}
+
+fn parse_pipfile(path: &Path) -> Vec<Dependency> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let Ok(manifest) = content.parse::<toml::Value>() else {
+        return Vec::new();
+    };
+
+    let mut deps = Vec::new();
+    python_table_deps(manifest.get("packages"), false, &mut deps);
+    python_table_deps(manifest.get("dev-packages"), true, &mut deps);
+    deps
+}
+
+/// Locked versions by normalized name from uv.lock / poetry.lock (`[[package]]` tables)
+/// or Pipfile.lock (`{"default": {"requests": {"version": "==2.31.0"}}}`)
+fn parse_python_lock(dir: &Path) -> HashMap<String, String> {
+    let mut locked = HashMap::new();
+
+    for file in ["uv.lock", "poetry.lock"] {
+        let Some(lock) = std::fs::read_to_string(dir.join(file))
+            .ok()
+            .and_then(|c| c.parse::<toml::Value>().ok())
+        else {
+            continue;
+        };
+        for package in lock
+            .get("package")
+            .and_then(|p| p.as_array())
+            .into_iter()
+            .flatten()
+        {
+            if let (Some(name), Some(version)) = (
+                package.get("name").and_then(|n| n.as_str()),
+                package.get("version").and_then(|v| v.as_str()),
+            ) {
+                locked.insert(normalize_name(name), version.to_string());
+            }
+        }
+    }
+
+    if let Some(lock) = std::fs::read_to_string(dir.join("Pipfile.lock"))
+        .ok()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+    {
+        for section in ["default", "develop"] {
+            for (name, spec) in lock[section].as_object().into_iter().flatten() {
+                if let Some(version) = spec["version"].as_str() {
+                    locked.insert(
+                        normalize_name(name),
+                        version.trim_start_matches("==").to_string(),
+                    );
+                }
+            }
+        }
+    }
+
+    locked
+}
+
+/// One entry per Python package, since pyproject, requirements files and Pipfile often
+/// repeat each other: runtime beats dev, and a real constraint beats "*"
+fn dedup_python_dependencies(deps: &mut Vec<Dependency>) {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut kept: Vec<Dependency> = Vec::new();
+    for dep in deps.drain(..) {
+        if dep.manager != PackageManager::Pip {
+            kept.push(dep);
+            continue;
+        }
+        match seen.get(&dep.package_name) {
+            Some(&idx) => {
+                let existing = &mut kept[idx];
+                existing.is_dev &= dep.is_dev;
+                if existing.version_constraint == "*" {
+                    existing.version_constraint = dep.version_constraint;
+                }
+            }
+            None => {
+                seen.insert(dep.package_name.clone(), kept.len());
+                kept.push(dep);
+            }
+        }
+    }
+    *deps = kept;
+}
+
+/// Direct `require`s of a go.mod; go.mod pins exact versions, so they double as locked ones
+fn parse_go_mod(path: &Path) -> Vec<Dependency> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+
+    let mut deps = Vec::new();
+    let mut in_block = false;
+    for line in content.lines() {
+        let line = line.trim();
+        let spec = if in_block {
+            if line == ")" {
+                in_block = false;
+                continue;
+            }
+            line
+        } else if line == "require (" {
+            in_block = true;
+            continue;
+        } else if let Some(rest) = line.strip_prefix("require ") {
+            rest
+        } else {
+            continue;
+        };
+        if spec.starts_with("//") || spec.contains("// indirect") {
+            continue;
+        }
+        let mut parts = spec.split_whitespace();
+        if let (Some(module), Some(version)) = (parts.next(), parts.next()) {
+            let mut dep = dependency(module, PackageManager::Go, version, false);
+            dep.locked_version = Some(version.to_string());
+            deps.push(dep);
+        }
+    }
+    deps
+}
+
+fn parse_requirements(path: &Path, is_dev: bool) -> Vec<Dependency> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .filter_map(parse_requirement)
+        .map(|(name, constraint)| dependency(&name, PackageManager::Pip, &constraint, is_dev))
+        .collect()
+}
+
+/// Read the manifests in one directory, or None if it isn't a project
+pub fn read_project(dir: &Path) -> Option<Project> {
+    let mut project = Project::new(dir.to_path_buf());
+
+    if dir.join("package.json").exists() {
+        project.package_managers.push(PackageManager::Npm);
+        project
+            .dependencies
+            .extend(parse_package_json(&dir.join("package.json")));
+    }
+    if dir.join("Cargo.toml").exists() {
+        project.package_managers.push(PackageManager::Cargo);
+        project
+            .dependencies
+            .extend(parse_cargo_toml(&dir.join("Cargo.toml")));
+    }
+    if dir.join("go.mod").exists() {
+        project.package_managers.push(PackageManager::Go);
+        project
+            .dependencies
+            .extend(parse_go_mod(&dir.join("go.mod")));
+    }
+
+    if dir.join("pubspec.yaml").exists() {
+        project.package_managers.push(PackageManager::Pub);
+        project.dependencies.extend(super::dart::parse_pubspec(dir));
+    }
+    if dir.join("Package.swift").exists() {
+        project.package_managers.push(PackageManager::Swift);
+        project
+            .dependencies
+            .extend(super::swift::parse_package_swift(dir));
+    }
+
+    let mut is_java = false;
+    if dir.join("pom.xml").exists() {
+        is_java = true;
+        project
+            .dependencies
+            .extend(super::java::parse_pom(&dir.join("pom.xml")));
+    }
+    for file in ["build.gradle", "build.gradle.kts"] {
+        if dir.join(file).exists() {
+            is_java = true;
+            project
+                .dependencies
+                .extend(super::java::parse_gradle(&dir.join(file)));
+        }
+    }
+    if dir.join("gradle/libs.versions.toml").exists() {
+        is_java = true;
+        project
+            .dependencies
+            .extend(super::java::parse_version_catalog(
+                &dir.join("gradle/libs.versions.toml"),
+            ));
+    }
+    if is_java {
+        project.package_managers.push(PackageManager::Maven);
+    }
+
+    let mut is_python = false;
+    if dir.join("pyproject.toml").exists() {
+        is_python = true;
+        project
+            .dependencies
+            .extend(parse_pyproject(&dir.join("pyproject.toml")));
+    }
+    if dir.join("requirements.txt").exists() {
+        is_python = true;
+        project
+            .dependencies
+            .extend(parse_requirements(&dir.join("requirements.txt"), false));
+    }
+    for file in DEV_REQUIREMENTS {
+        if dir.join(file).exists() {
+            is_python = true;
+            project
+                .dependencies
+                .extend(parse_requirements(&dir.join(file), true));
+        }
+    }
+    if dir.join("Pipfile").exists() {
+        is_python = true;
+        project
+            .dependencies
+            .extend(parse_pipfile(&dir.join("Pipfile")));
+    }
+    if is_python {
+        let locked = parse_python_lock(dir);
+        for dep in project
+            .dependencies
+            .iter_mut()
+            .filter(|d| d.manager == PackageManager::Pip)
+        {
+            dep.locked_version = locked.get(&dep.package_name).cloned();
+        }
+        dedup_python_dependencies(&mut project.dependencies);
+    }
+    for name in VENV_DIRS {
+        let venv = dir.join(name);
+        if venv.join("pyvenv.cfg").exists() {
+            is_python = true;
+            project.venvs.push(Venv {
+                path: venv,
+                packages: Vec::new(),
+                error: None,
+            });
+        }
+    }
+    if is_python {
+        project.package_managers.push(PackageManager::Pip);
+    }
+
+    project.tool_usages = super::precommit::read_precommit_hooks(dir);
+    for usage in super::workflows::read_workflow_tools(dir) {
+        if !project.tool_usages.iter().any(|u| u.tool == usage.tool) {
+            project.tool_usages.push(usage);
+        }
+    }
+
+    project.setup_tools = super::bootstrap::read_setup_tools(dir);
+
+    if project.package_managers.is_empty()
+        && project.tool_usages.is_empty()
+        && project.setup_tools.is_empty()
+    {
+        return None;
+    }
+    project.tool_requirements = super::constraints::read_tool_requirements(dir);
+
+    if let Ok(modified) = std::fs::metadata(dir).and_then(|m| m.modified()) {
+        project.last_modified = modified.into();
+    }
+    Some(project)
+}
+
+/// How many levels below a scan root projects are looked for
+pub(super) const SCAN_DEPTH: usize = 4;
+
+/// Hidden folders, dependency installs and build output are never searched for projects
+pub(super) fn is_walked(name: &str) -> bool {
+    !name.starts_with('.')
+        && !matches!(
+            name,
+            "node_modules" | "target" | "dist" | "build" | "__pycache__" | "venv"
+        )
+}
+
+/// Find projects under the scan directories and parse their declared dependencies
+pub fn scan_projects(scan_dirs: &[PathBuf]) -> Vec<Project> {
+    let mut projects = Vec::new();
+
+    for base_dir in scan_dirs {
+        if !base_dir.exists() {
+            continue;
+        }
+
+        for entry in WalkDir::new(base_dir)
+            .max_depth(SCAN_DEPTH)
+            .into_iter()
+            .filter_entry(|e| is_walked(&e.file_name().to_string_lossy()))
+            .filter_map(|e| e.ok())
+        {
+            if entry.file_type().is_dir() {
+                if let Some(project) = read_project(entry.path()) {
+                    projects.push(project);
+                }
+            }
+        }
+    }
+
+    println!("[DEBUG] Parsed manifests of {} projects", projects.len());
+    projects
+}
+
+/// A project's dependency with a newer release available
+pub struct OutdatedDependency {
+    pub name: String,
+    pub current: String, // What the project installs or pins
+    pub latest: String,
+}
+
+/// Python dependencies behind their latest release, judged by what the project's virtualenv
+/// has installed or, failing that, the version its lock file pins; Go, Maven, Swift and
+/// pub dependencies by their pinned version against the registry's latest
+pub fn outdated_dependencies(project: &Project, packages: &[Package]) -> Vec<OutdatedDependency> {
+    let mut outdated = Vec::new();
+    for dep in &project.dependencies {
+        let (current, latest) = match dep.manager {
+            PackageManager::Go
+            | PackageManager::Maven
+            | PackageManager::Swift
+            | PackageManager::Pub => match &dep.locked_version {
+                Some(pinned) => (pinned.clone(), dep.latest_version.clone()),
+                None => continue,
+            },
+            PackageManager::Pip => {
+                let in_venv = project
+                    .venvs
+                    .iter()
+                    .flat_map(|v| &v.packages)
+                    .find(|p| normalize_name(&p.name) == dep.package_name);
+                match (in_venv, &dep.locked_version) {
+                    (Some(pkg), _) => (pkg.installed_version.clone(), pkg.latest_version.clone()),
+                    (None, Some(locked)) => {
+                        let global = packages.iter().find(|p| {
+                            p.manager == PackageManager::Pip
+                                && normalize_name(&p.name) == dep.package_name
+                        });
+                        let latest = global.map(|p| {
+                            p.latest_version
+                                .clone()
+                                .unwrap_or_else(|| p.installed_version.clone())
+                        });
+                        (locked.clone(), latest)
+                    }
+                    (None, None) => continue,
+                }
+            }
+            _ => continue,
+        };
+        if let Some(latest) = latest {
+            if compare_versions(&current, &latest) == Ordering::Less {
+                outdated.push(OutdatedDependency {
+                    name: dep.package_name.clone(),
+                    current,
+                    latest,
+                });
+            }
+        }
+    }
+    outdated
+}
+
+/// Installed packages a project declares, matched the way `apply_dependency_usage` does
+pub fn installed_dependencies<'a>(project: &Project, packages: &'a [Package]) -> Vec<&'a Package> {
+    packages
+        .iter()
+        .filter(|pkg| {
+            let name = match pkg.manager {
+                PackageManager::Pip => normalize_name(&pkg.name),
+                PackageManager::Npm | PackageManager::Cargo => pkg.name.clone(),
+                _ => return false,
+            };
+            project
+                .dependencies
+                .iter()
+                .any(|d| d.manager == pkg.manager && d.package_name == name)
+        })
+        .collect()
+}
+
+/// Mark npm/Cargo/pip packages as used by the projects that declare them.
+/// A package is "dev only" in a project when it only appears in dev sections there.
+pub fn apply_dependency_usage(packages: &mut [Package], projects: &[Project]) {
+    // (manager, name) -> project path -> declared as runtime anywhere in that project
+    let mut usage: HashMap<(PackageManager, String), HashMap<String, bool>> = HashMap::new();
+    for project in projects {
+        let path = project.path.to_string_lossy().to_string();
+        for dep in &project.dependencies {
+            let runtime = usage
+                .entry((dep.manager.clone(), dep.package_name.clone()))
+                .or_default()
+                .entry(path.clone())
+                .or_insert(false);
+            *runtime |= !dep.is_dev;
+        }
+    }
+
+    for pkg in packages.iter_mut() {
+        let name = match pkg.manager {
+            PackageManager::Pip => normalize_name(&pkg.name),
+            PackageManager::Npm | PackageManager::Cargo => pkg.name.clone(),
+            _ => continue,
+        };
+
+        pkg.used_in.clear();
+        pkg.dev_only_in.clear();
+        if let Some(projects) = usage.get(&(pkg.manager.clone(), name)) {
+            let mut paths: Vec<&String> = projects.keys().collect();
+            paths.sort();
+            for path in paths {
+                pkg.used_in.push(path.clone());
+                if !projects[path] {
+                    pkg.dev_only_in.push(path.clone());
+                }
+            }
+        }
+    }
+}
+
+/// A pin is "very old" when it's outside the caret range of the version we'd
+/// expect it to run (1.x vs 2.x, 0.4 vs 0.6, 23.x vs 24.x for calendar versions)
+fn is_stale_pin(rev: &str, current: &str) -> bool {
+    compare_versions(rev, current) == Ordering::Less
+        && satisfies(current, &format!("^{}", rev.trim_start_matches('v'))) == Some(false)
+}
+
+/// Count tools run by project tooling (pre-commit hooks, CI workflows) as dev usage of any
+/// installed package with that name, and flag pins far behind the available version.
+pub fn apply_tool_usage(packages: &mut [Package], projects: &[Project]) {
+    let mut stale = 0;
+    for pkg in packages.iter_mut() {
+        pkg.stale_hooks.clear();
+        let current = pkg
+            .latest_version
+            .clone()
+            .unwrap_or_else(|| pkg.installed_version.clone());
+
+        for project in projects {
+            let path = project.path.to_string_lossy().to_string();
+            for usage in project.tool_usages.iter().filter(|u| u.tool == pkg.name) {
+                // Where each version has its own row, a pinned version uses only its row
+                if pkg.manager.lists_each_version()
+                    && usage
+                        .rev
+                        .as_deref()
+                        .is_some_and(|rev| satisfies(&pkg.installed_version, rev) == Some(false))
+                {
+                    continue;
+                }
+                // Already used at runtime there; tooling doesn't make it dev only
+                if !pkg.used_in.contains(&path) {
+                    pkg.used_in.push(path.clone());
+                    pkg.dev_only_in.push(path.clone());
+                }
+                if let Some(rev) = &usage.rev {
+                    if is_stale_pin(rev, &current) {
+                        pkg.stale_hooks.push(StaleHook {
+                            project: path.clone(),
+                            rev: rev.clone(),
+                        });
+                        stale += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    println!("[DEBUG] Found {} stale pre-commit hook pins", stale);
+}
+
+/// The git repository holding a project (the nearest folder up, below the home folder,
+/// with a `.git`) and the project's path inside it
+pub fn project_ref(path: &Path) -> ProjectRef {
+    let folder_name = |p: &Path| {
+        p.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| p.display().to_string())
+    };
+    let home = home_dir();
+    let root = path
+        .ancestors()
+        .take_while(|dir| *dir != home)
+        .find(|dir| dir.join(".git").exists());
+    let (repo, member) = match root {
+        Some(root) => {
+            let member = path
+                .strip_prefix(root)
+                .map(|rest| {
+                    rest.components()
+                        .map(|c| c.as_os_str().to_string_lossy().to_string())
+                        .collect::<Vec<_>>()
+                        .join("/")
+                })
+                .unwrap_or_default();
+            (folder_name(root), member)
+        }
+        None => (folder_name(path), String::new()),
+    };
+    ProjectRef {
+        path: path.to_string_lossy().to_string(),
+        repo,
+        member,
+    }
+}
+
+/// Resolve every package's `used_in` paths to repository/member references for display
+pub fn apply_project_refs(packages: &mut [Package]) {
+    let mut refs: HashMap<String, ProjectRef> = HashMap::new();
+    for pkg in packages.iter_mut() {
+        pkg.project_refs = pkg
+            .used_in
+            .iter()
+            .map(|path| {
+                refs.entry(path.clone())
+                    .or_insert_with(|| project_ref(Path::new(path)))
+                    .clone()
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ToolUsage;
+    use crate::utils::test_dir::TestDir;
+
+    #[test]
+    fn pipfile_with_uv_lock() {
+        let dir = TestDir::new("manifests");
+        std::fs::write(
+            dir.join("Pipfile"),
+            "[packages]\nRequests = \"*\"\n\n[dev-packages]\npytest = { version = \">=7\" }\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("requirements.txt"), "requests>=2.0\n").unwrap();
+        std::fs::write(
+            dir.join("uv.lock"),
+            "[[package]]\nname = \"requests\"\nversion = \"2.31.0\"\n",
+        )
+        .unwrap();
+
+        let project = read_project(&dir).unwrap();
+
+        assert_eq!(project.dependencies.len(), 2);
+        let requests = &project.dependencies[0];
+        assert_eq!(requests.package_name, "requests");
+        assert_eq!(requests.version_constraint, ">=2.0");
+        assert_eq!(requests.locked_version.as_deref(), Some("2.31.0"));
+        assert!(!requests.is_dev);
+        assert!(project.dependencies[1].is_dev);
+    }
+
+    /// One project per row: its manifest files, then (name, constraint, locked, dev) of
+    /// each dependency read from them
+    #[test]
+    fn project_manifests() {
+        type Files<'a> = &'a [(&'a str, &'a str)];
+        type Dependency<'a> = (&'a str, &'a str, Option<&'a str>, bool);
+        let cases: [(Files, &[Dependency]); 4] = [
+            (
+                &[(
+                    "go.mod",
+                    "module example.com/app\n\ngo 1.22\n\nrequire github.com/spf13/cobra v1.8.0\n\nrequire (\n\tgolang.org/x/sys v0.20.0\n\tgithub.com/inconshreveable/mousetrap v1.1.0 // indirect\n)\n",
+                )],
+                &[
+                    ("github.com/spf13/cobra", "v1.8.0", Some("v1.8.0"), false),
+                    ("golang.org/x/sys", "v0.20.0", Some("v0.20.0"), false),
+                ],
+            ),
+            (
+                &[(
+                    "pom.xml",
+                    "<project><properties><junit.version>5.10.0</junit.version></properties>\
+                     <dependencies>\
+                     <dependency><groupId>com.google.guava</groupId><artifactId>guava</artifactId><version>32.1.2-jre</version></dependency>\
+                     <dependency><groupId>org.junit.jupiter</groupId><artifactId>junit-jupiter</artifactId><version>${junit.version}</version><scope>test</scope></dependency>\
+                     </dependencies></project>",
+                )],
+                &[
+                    ("com.google.guava:guava", "32.1.2-jre", Some("32.1.2-jre"), false),
+                    ("org.junit.jupiter:junit-jupiter", "5.10.0", Some("5.10.0"), true),
+                ],
+            ),
+            (
+                &[(
+                    "build.gradle.kts",
+                    "dependencies {\n    implementation(\"org.slf4j:slf4j-api:2.0.9\")\n    testImplementation 'io.mockk:mockk:1.13.8'\n}\n",
+                )],
+                &[
+                    ("org.slf4j:slf4j-api", "2.0.9", Some("2.0.9"), false),
+                    ("io.mockk:mockk", "1.13.8", Some("1.13.8"), true),
+                ],
+            ),
+            (
+                &[
+                    (
+                        "pubspec.yaml",
+                        "name: app\nenvironment:\n  sdk: ^3.2.0\n\ndependencies:\n  flutter:\n    sdk: flutter\n  http: ^1.1.0\n  provider:\n    version: '>=6.0.0 <7.0.0'\n\ndev_dependencies:\n  lints: ^3.0.0 # linter rules\n",
+                    ),
+                    (
+                        "pubspec.lock",
+                        "packages:\n  http:\n    dependency: \"direct main\"\n    source: hosted\n    version: \"1.1.2\"\nsdks:\n  dart: \">=3.2.0 <4.0.0\"\n",
+                    ),
+                ],
+                &[
+                    ("http", "^1.1.0", Some("1.1.2"), false),
+                    ("provider", ">=6.0.0 <7.0.0", None, false),
+                    ("lints", "^3.0.0", None, true),
+                ],
+            ),
+        ];
+
+        for (files, expected) in cases {
+            let dir = TestDir::new("manifest");
+            for (name, content) in files {
+                dir.write(name, content);
+            }
+            let project = read_project(&dir).unwrap();
+            let found: Vec<Dependency> = project
+                .dependencies
+                .iter()
+                .map(|d| {
+                    (
+                        d.package_name.as_str(),
+                        d.version_constraint.as_str(),
+                        d.locked_version.as_deref(),
+                        d.is_dev,
+                    )
+                })
+                .collect();
+            assert_eq!(found, expected, "{}", files[0].0);
+        }
+    }
+
+    #[test]
+    fn installed_dependencies_match_normalized_names() {
+        let mut project = Project::new(PathBuf::from("/tmp/app"));
+        project.dependencies = vec![
+            dependency("requests-oauthlib", PackageManager::Pip, "*", false),
+            dependency("serde", PackageManager::Cargo, "1", false),
+        ];
+        let packages = vec![
+            Package::new("Requests_OAuthlib", PackageManager::Pip, "1.3.1"),
+            Package::new("serde", PackageManager::Npm, "0.1.0"),
+            Package::new("ripgrep", PackageManager::Cargo, "14.1.0"),
+        ];
+
+        let used = installed_dependencies(&project, &packages);
+        assert_eq!(used.len(), 1);
+        assert_eq!(used[0].name, "Requests_OAuthlib");
+    }
+
+    #[test]
+    fn pinned_tools_use_only_their_version_row() {
+        let mut project = Project::new(PathBuf::from("/tmp/infra"));
+        project.tool_usages = vec![ToolUsage {
+            tool: "terraform".to_string(),
+            source: ".github/workflows/plan.yml".to_string(),
+            rev: Some("1.5.7".to_string()),
+        }];
+        let mut packages = vec![
+            Package::new("terraform", PackageManager::Tfenv, "1.5.7"),
+            Package::new("terraform", PackageManager::Tfenv, "1.9.0"),
+        ];
+
+        apply_tool_usage(&mut packages, &[project]);
+        assert_eq!(packages[0].used_in, ["/tmp/infra"]);
+        assert!(packages[1].used_in.is_empty());
+    }
+
+    #[test]
+    fn members_are_labelled_by_repository() {
+        let root = TestDir::new("refs");
+        let repo = root.join("shop");
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+        std::fs::create_dir_all(repo.join("packages").join("web")).unwrap();
+        std::fs::create_dir_all(root.join("scratch")).unwrap();
+
+        assert_eq!(
+            project_ref(&repo.join("packages").join("web")).label(),
+            "shop/packages/web"
+        );
+        assert_eq!(project_ref(&repo).label(), "shop");
+        assert_eq!(project_ref(&root.join("scratch")).label(), "scratch");
+    }
+}
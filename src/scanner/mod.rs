@@ -1,5 +1,27 @@
+pub mod bootstrap;
+pub mod budget;
+pub mod cloud;
+pub mod constraints;
+pub mod dart;
+pub mod index;
+pub mod java;
+pub mod manifests;
+pub mod precommit;
 pub mod project_scanner;
+pub mod swift;
+pub mod workflows;
 
-pub use project_scanner::{get_scan_directories, scan_homebrew_tool_usage};
+pub use bootstrap::{install_plan, plan_script, PlanAction};
+pub use budget::ScanBudget;
+pub use constraints::check_tool_requirements;
+pub use index::{scan_projects_indexed, ScanReport};
+pub use manifests::{
+    apply_dependency_usage, apply_project_refs, apply_tool_usage, installed_dependencies,
+    outdated_dependencies, scan_projects,
+};
+pub use project_scanner::{
+    add_scan_root, added_scan_roots, get_scan_directories, remove_scan_root,
+    scan_homebrew_tool_usage, set_added_scan_roots,
+};
 
 // Removed scan_package_usage - unused dead code. Using scan_homebrew_tool_usage instead.
@@ -0,0 +1,86 @@
+use crate::models::ToolUsage;
+use std::path::Path;
+
+/// pre-commit hook ids -> the tool they run
+const HOOK_TOOLS: [(&str, &str); 11] = [
+    ("shellcheck", "shellcheck"),
+    ("black", "black"),
+    ("black-jupyter", "black"),
+    ("ruff", "ruff"),
+    ("ruff-check", "ruff"),
+    ("ruff-format", "ruff"),
+    ("prettier", "prettier"),
+    ("mypy", "mypy"),
+    ("isort", "isort"),
+    ("flake8", "flake8"),
+    ("eslint", "eslint"),
+];
+
+/// One `- repo:` entry: its pinned rev and (hook id, entry) pairs
+type RepoHooks = (Option<String>, Vec<(String, Option<String>)>);
+
+fn yaml_value(line: &str, key: &str) -> Option<String> {
+    let value = line.strip_prefix(key)?.strip_prefix(':')?;
+    let value = value.split(" #").next()?.trim();
+    Some(value.trim_matches(['"', '\'']).to_string())
+}
+
+/// A hook id we know, or for `repo: local` hooks the program named in `entry:`
+fn hook_tool(id: &str, entry: Option<&str>) -> Option<String> {
+    if let Some((_, tool)) = HOOK_TOOLS.iter().find(|(hook, _)| *hook == id) {
+        return Some(tool.to_string());
+    }
+    let program = entry?.split_whitespace().next()?;
+    let program = program.rsplit('/').next()?;
+    HOOK_TOOLS
+        .iter()
+        .find(|(_, tool)| *tool == program)
+        .map(|(_, tool)| tool.to_string())
+}
+
+/// Hooks from `.pre-commit-config.yaml`. The file is flat enough that tracking
+/// `repo:`/`rev:`/`id:`/`entry:` lines is sufficient without a YAML parser.
+pub fn read_precommit_hooks(dir: &Path) -> Vec<ToolUsage> {
+    let Ok(content) = std::fs::read_to_string(dir.join(".pre-commit-config.yaml")) else {
+        return Vec::new();
+    };
+
+    // rev may come after the hooks list, so collect per repo first
+    let mut repos: Vec<RepoHooks> = Vec::new();
+    for line in content.lines() {
+        let line = line.trim_start();
+        let line = line.strip_prefix("- ").unwrap_or(line).trim_start();
+        if yaml_value(line, "repo").is_some() {
+            repos.push((None, Vec::new()));
+        } else if let Some((rev, hooks)) = repos.last_mut() {
+            if let Some(value) = yaml_value(line, "rev") {
+                *rev = Some(value);
+            } else if let Some(id) = yaml_value(line, "id") {
+                hooks.push((id, None));
+            } else if let Some(entry) = yaml_value(line, "entry") {
+                if let Some((_, hook_entry)) = hooks.last_mut() {
+                    *hook_entry = Some(entry);
+                }
+            }
+        }
+    }
+
+    let mut usages: Vec<ToolUsage> = Vec::new();
+    for (rev, hooks) in repos {
+        for (id, entry) in hooks {
+            let Some(tool) = hook_tool(&id, entry.as_deref()) else {
+                continue;
+            };
+            // ruff + ruff-format from the same repo is one usage
+            if usages.iter().any(|u| u.tool == tool) {
+                continue;
+            }
+            usages.push(ToolUsage {
+                tool,
+                source: ".pre-commit-config.yaml".to_string(),
+                rev: rev.clone(),
+            });
+        }
+    }
+    usages
+}
@@ -1,10 +1,19 @@
+use super::cloud::is_dataless;
+use super::ScanBudget;
 use crate::models::Package;
+use crate::utils::paths::{data_dir, expand_home, home_dir};
+use std::cmp::Reverse;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
 use walkdir::WalkDir;
 
 // Scan projects and determine which Homebrew tools they actually use
-pub fn scan_homebrew_tool_usage(packages: &mut [Package], scan_dirs: &[PathBuf]) {
+pub fn scan_homebrew_tool_usage(
+    packages: &mut [Package],
+    scan_dirs: &[PathBuf],
+    budget: &ScanBudget,
+) {
     println!("[DEBUG] Scanning projects for Homebrew tool usage...");
 
     // Build a map of tool name -> projects using it
@@ -16,15 +25,20 @@ pub fn scan_homebrew_tool_usage(packages: &mut [Package], scan_dirs: &[PathBuf])
         }
 
         println!("[DEBUG] Scanning directory: {}", base_dir.display());
+        let mut meter = budget.meter();
 
-        // Walk through directories to find projects
+        // Walk through directories to find projects, recently modified ones first
         for entry in WalkDir::new(base_dir)
             .max_depth(4)
+            .follow_links(true) // Symlink loops come back as errors and are dropped
+            .sort_by_key(|e| Reverse(e.metadata().ok().and_then(|m| m.modified().ok())))
             .into_iter()
             .filter_entry(|e| {
-                // Skip common directories we don't care about
+                // Skip common directories we don't care about, and iCloud/OneDrive placeholders
+                // that would be downloaded just to be listed
                 let name = e.file_name().to_string_lossy();
-                !name.starts_with('.')
+                !e.metadata().is_ok_and(|m| is_dataless(&m))
+                    && !name.starts_with('.')
                     && name != "node_modules"
                     && name != "target"
                     && name != "dist"
@@ -33,6 +47,13 @@ pub fn scan_homebrew_tool_usage(packages: &mut [Package], scan_dirs: &[PathBuf])
             })
             .filter_map(|e| e.ok())
         {
+            if !meter.spend(1) {
+                println!(
+                    "[SCAN] Budget spent in {}, skipping older folders",
+                    base_dir.display()
+                );
+                break;
+            }
             let path = entry.path();
 
             if !path.is_dir() {
@@ -146,8 +167,9 @@ pub fn scan_homebrew_tool_usage(packages: &mut [Package], scan_dirs: &[PathBuf])
                     .push(project_path.clone());
             }
 
-            // Database tools - check for config files
-            if path.join("package.json").exists() {
+            // Database tools - check for config files, unless that means downloading one
+            let package_json = std::fs::metadata(path.join("package.json"));
+            if package_json.is_ok_and(|m| !is_dataless(&m)) {
                 // Read package.json to check for database dependencies
                 if let Ok(content) = std::fs::read_to_string(path.join("package.json")) {
                     if content.contains("postgres") || content.contains("pg") {
@@ -210,10 +232,9 @@ pub fn scan_homebrew_tool_usage(packages: &mut [Package], scan_dirs: &[PathBuf])
 
 // Get common development directories to scan
 pub fn get_scan_directories() -> Vec<PathBuf> {
-    let home = std::env::var("HOME").unwrap_or_else(|_| "/Users".to_string());
-    let home_path = PathBuf::from(home);
+    let home_path = home_dir();
 
-    vec![
+    let mut dirs = vec![
         home_path.join("Desktop"),
         home_path.join("Documents"),
         home_path.join("projects"),
@@ -221,5 +242,59 @@ pub fn get_scan_directories() -> Vec<PathBuf> {
         home_path.join("Developer"),
         home_path.join("code"),
         home_path.join("workspace"),
-    ]
+    ];
+    dirs.extend(added_scan_roots());
+    dirs
+}
+
+fn added_roots_path() -> PathBuf {
+    data_dir().join("scan_roots.json")
+}
+
+/// scan_roots.json as last read or written, so panels can ask every frame
+static ADDED_ROOTS: LazyLock<Mutex<Option<Vec<PathBuf>>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Folders the user chose to keep scanning after a one-off folder scan
+pub fn added_scan_roots() -> Vec<PathBuf> {
+    let mut cached = ADDED_ROOTS.lock().unwrap_or_else(|e| e.into_inner());
+    cached
+        .get_or_insert_with(|| {
+            std::fs::read_to_string(added_roots_path())
+                .ok()
+                .and_then(|content| serde_json::from_str::<Vec<PathBuf>>(&content).ok())
+                .unwrap_or_default()
+                .iter()
+                .map(|root| expand_home(root))
+                .collect()
+        })
+        .clone()
+}
+
+/// Replace the added scan roots (importing a configuration); "~/code" is stored expanded
+pub fn set_added_scan_roots(roots: &[PathBuf]) {
+    let roots: Vec<PathBuf> = roots.iter().map(|root| expand_home(root)).collect();
+    *ADDED_ROOTS.lock().unwrap_or_else(|e| e.into_inner()) = Some(roots.clone());
+    let path = added_roots_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(&roots) {
+        if let Err(e) = std::fs::write(&path, json) {
+            eprintln!("[ERROR] Failed to write {}: {}", path.display(), e);
+        }
+    }
+}
+
+pub fn add_scan_root(dir: &Path) {
+    let mut roots = added_scan_roots();
+    if !roots.iter().any(|r| r == dir) {
+        roots.push(dir.to_path_buf());
+        set_added_scan_roots(&roots);
+    }
+}
+
+pub fn remove_scan_root(dir: &Path) {
+    let mut roots = added_scan_roots();
+    roots.retain(|r| r != dir);
+    set_added_scan_roots(&roots);
 }
@@ -0,0 +1,143 @@
+use super::manifests::dependency;
+use crate::models::{Dependency, PackageManager};
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// `.package(url: "https://github.com/apple/swift-argument-parser", from: "1.2.0")`
+static PACKAGE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\.package\(\s*(?:name:\s*"[^"]*"\s*,\s*)?url:\s*"([^"]+)""#).unwrap()
+});
+static FROM_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"from:\s*"([^"]+)""#).unwrap());
+static EXACT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"exact:\s*"([^"]+)""#).unwrap());
+static RANGE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#""([^"]+)"\s*\.\.<\s*"([^"]+)""#).unwrap());
+static BRANCH_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"branch:\s*"([^"]+)""#).unwrap());
+
+/// "https://github.com/apple/swift-nio.git" -> "github.com/apple/swift-nio", which is
+/// unambiguous and turns back into a clonable URL
+fn repo_name(url: &str) -> String {
+    let name = url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("git@")
+        .replacen(':', "/", 1);
+    name.trim_end_matches('/')
+        .trim_end_matches(".git")
+        .to_lowercase()
+}
+
+/// Version requirement of one `.package(...)` call, in the crate's constraint syntax
+fn requirement(args: &str) -> String {
+    if let Some(c) = EXACT_RE.captures(args) {
+        format!("={}", &c[1])
+    } else if let Some(c) = RANGE_RE.captures(args) {
+        format!(">={}, <{}", &c[1], &c[2])
+    } else if let Some(c) = FROM_RE.captures(args) {
+        // `from:` and `.upToNextMajor(from:)` are both next-major ranges
+        if args.contains("upToNextMinor") {
+            format!("~{}", &c[1])
+        } else {
+            format!("^{}", &c[1])
+        }
+    } else if let Some(c) = BRANCH_RE.captures(args) {
+        format!("branch {}", &c[1])
+    } else {
+        "*".to_string()
+    }
+}
+
+/// Pinned versions by repo name; Package.resolved v1 nests pins under "object" and calls
+/// the URL "repositoryURL", v2+ uses "location"
+fn resolved_versions(path: &Path) -> HashMap<String, String> {
+    let Some(json) = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+    else {
+        return HashMap::new();
+    };
+    let pins = json["pins"]
+        .as_array()
+        .or_else(|| json["object"]["pins"].as_array());
+
+    pins.into_iter()
+        .flatten()
+        .filter_map(|pin| {
+            let url = pin["location"]
+                .as_str()
+                .or_else(|| pin["repositoryURL"].as_str())?;
+            let version = pin["state"]["version"].as_str()?;
+            Some((repo_name(url), version.to_string()))
+        })
+        .collect()
+}
+
+/// Dependencies declared in Package.swift, with versions pinned by Package.resolved
+pub fn parse_package_swift(dir: &Path) -> Vec<Dependency> {
+    let Ok(manifest) = std::fs::read_to_string(dir.join("Package.swift")) else {
+        return Vec::new();
+    };
+    let resolved = resolved_versions(&dir.join("Package.resolved"));
+
+    let matches: Vec<_> = PACKAGE_RE.captures_iter(&manifest).collect();
+    matches
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            // Arguments run until the next `.package(` call
+            let start = c.get(0).unwrap().end();
+            let end = matches
+                .get(i + 1)
+                .map(|next| next.get(0).unwrap().start())
+                .unwrap_or(manifest.len());
+            let name = repo_name(&c[1]);
+            let mut dep = dependency(
+                &name,
+                PackageManager::Swift,
+                &requirement(&manifest[start..end]),
+                false,
+            );
+            dep.locked_version = resolved.get(&name).cloned();
+            dep
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_dir::TestDir;
+
+    #[test]
+    fn package_swift_with_resolved_pins() {
+        let dir = TestDir::new("swift");
+        std::fs::write(
+            dir.join("Package.swift"),
+            r#"let package = Package(
+    name: "Tool",
+    dependencies: [
+        .package(url: "https://github.com/apple/swift-argument-parser", from: "1.2.0"),
+        .package(url: "https://github.com/apple/swift-nio.git", exact: "2.60.0"),
+    ]
+)"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("Package.resolved"),
+            r#"{"pins": [{"identity": "swift-argument-parser", "location": "https://github.com/apple/swift-argument-parser", "state": {"version": "1.3.0"}}], "version": 2}"#,
+        )
+        .unwrap();
+
+        let deps = parse_package_swift(&dir);
+
+        assert_eq!(
+            deps[0].package_name,
+            "github.com/apple/swift-argument-parser"
+        );
+        assert_eq!(deps[0].version_constraint, "^1.2.0");
+        assert_eq!(deps[0].locked_version.as_deref(), Some("1.3.0"));
+        assert_eq!(deps[1].package_name, "github.com/apple/swift-nio");
+        assert_eq!(deps[1].version_constraint, "=2.60.0");
+    }
+}
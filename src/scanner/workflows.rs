@@ -0,0 +1,127 @@
+use crate::models::ToolUsage;
+use std::path::Path;
+
+/// Setup actions -> the tool they provide (matched on "owner/repo" before the "@ref")
+const SETUP_ACTIONS: [(&str, &str); 12] = [
+    ("actions/setup-node", "node"),
+    ("actions/setup-python", "python"),
+    ("actions/setup-go", "go"),
+    ("actions/setup-java", "openjdk"),
+    ("ruby/setup-ruby", "ruby"),
+    ("dtolnay/rust-toolchain", "rust"),
+    ("actions-rs/toolchain", "rust"),
+    ("pnpm/action-setup", "pnpm"),
+    ("hashicorp/setup-terraform", "terraform"),
+    ("azure/setup-helm", "helm"),
+    ("azure/setup-kubectl", "kubectl"),
+    ("rhysd/actionlint", "actionlint"),
+];
+
+fn yaml_value<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let value = line.strip_prefix(key)?.strip_prefix(':')?;
+    Some(value.trim().trim_matches(['"', '\'']))
+}
+
+/// Programs invoked by one shell line: "FOO=1 cargo test && gh pr view | jq ." -> cargo, gh, jq
+fn commands_in(line: &str) -> Vec<String> {
+    line.split(['&', '|', ';'])
+        .filter_map(|segment| {
+            segment
+                .split_whitespace()
+                .find(|word| !word.contains('='))
+                .filter(|word| !word.starts_with(['#', '$', '-', '(', '"', '\'']))
+                .map(|word| word.rsplit('/').next().unwrap_or(word).to_string())
+        })
+        .collect()
+}
+
+/// Tools from one workflow file: setup actions plus the programs `run:` steps call
+fn read_workflow(content: &str) -> Vec<String> {
+    let mut tools = Vec::new();
+    // Indentation of the `run: |` key while inside its block
+    let mut run_block: Option<usize> = None;
+
+    for raw in content.lines() {
+        let indent = raw.len() - raw.trim_start().len();
+        let line = raw.trim_start();
+        let line = line.strip_prefix("- ").unwrap_or(line).trim_start();
+
+        if let Some(block_indent) = run_block {
+            if line.is_empty() || indent > block_indent {
+                tools.extend(commands_in(line));
+                continue;
+            }
+            run_block = None;
+        }
+
+        if let Some(action) = yaml_value(line, "uses") {
+            // "docker://rhysd/actionlint:1.7" and "owner/repo/sub/dir@v1" name the same action
+            let action = action.trim_start_matches("docker://").to_lowercase();
+            let action = action.split(['@', ':']).next().unwrap_or_default();
+            let action = action.splitn(3, '/').take(2).collect::<Vec<_>>().join("/");
+            if let Some((_, tool)) = SETUP_ACTIONS.iter().find(|(a, _)| action == *a) {
+                tools.push(tool.to_string());
+            }
+        } else if let Some(run) = yaml_value(line, "run") {
+            if run.starts_with(['|', '>']) {
+                run_block = Some(indent);
+            } else {
+                tools.extend(commands_in(run));
+            }
+        }
+    }
+    tools
+}
+
+/// Tools used by `.github/workflows/*.yml`. CI-only tools installed locally
+/// (gh, actionlint) would otherwise look unused.
+pub fn read_workflow_tools(dir: &Path) -> Vec<ToolUsage> {
+    let Ok(entries) = std::fs::read_dir(dir.join(".github").join("workflows")) else {
+        return Vec::new();
+    };
+
+    let mut usages: Vec<ToolUsage> = Vec::new();
+    let mut push = |tool: String, source: &str| {
+        if !usages.iter().any(|u| u.tool == tool) {
+            usages.push(ToolUsage {
+                tool,
+                source: source.to_string(),
+                rev: None,
+            });
+        }
+    };
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if !matches!(
+            path.extension().and_then(|e| e.to_str()),
+            Some("yml" | "yaml")
+        ) {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let source = format!(
+            ".github/workflows/{}",
+            path.file_name().unwrap_or_default().to_string_lossy()
+        );
+        for tool in read_workflow(&content) {
+            push(tool, &source);
+        }
+    }
+    usages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tools_come_from_steps_not_from_having_workflows() {
+        let workflow = "jobs:\n  lint:\n    steps:\n      - uses: actions/setup-node@v4\n      - uses: docker://rhysd/actionlint:1.7.0\n      - run: |\n          npm ci\n          gh pr view | jq .\n      - run: cargo test\n";
+        let tools = read_workflow(workflow);
+        assert_eq!(tools, ["node", "actionlint", "npm", "gh", "jq", "cargo"]);
+        assert!(!read_workflow("on: push\n").contains(&"act".to_string()));
+    }
+}
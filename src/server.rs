@@ -0,0 +1,242 @@
+use crate::config::{AutoUpdateSettings, BackgroundSettings, Settings};
+use crate::models::{Package, Project};
+use crate::utils::metrics;
+use anyhow::{anyhow, Result};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{watch, Notify, RwLock};
+
+/// Requests are tiny GETs; anything bigger than this isn't for us
+const MAX_REQUEST_BYTES: usize = 8192;
+
+/// Inventory kept by a long-running `--serve`/`--daemon` process
+pub struct ScanState {
+    pub inventory: RwLock<Vec<Package>>,
+    pub projects: RwLock<Vec<Project>>,
+    rescan: Notify,
+    scans: watch::Sender<u64>, // completed scans, so callers can wait for the next one
+}
+
+impl ScanState {
+    fn new() -> Self {
+        Self {
+            inventory: RwLock::new(Vec::new()),
+            projects: RwLock::new(Vec::new()),
+            rescan: Notify::new(),
+            scans: watch::channel(0).0,
+        }
+    }
+
+    /// Start a scan now instead of at the next interval and wait for it to finish
+    pub async fn rescan(&self) {
+        let mut scans = self.scans.subscribe();
+        scans.borrow_and_update();
+        self.rescan.notify_one();
+        let _ = scans.changed().await;
+    }
+
+    /// The first scan after startup, when nothing has been scanned yet
+    pub async fn wait_for_first_scan(&self) {
+        let mut scans = self.scans.subscribe();
+        let _ = scans.wait_for(|count| *count > 0).await;
+    }
+}
+
+/// Long-running headless mode: rescan on an interval, serving `/metrics` (Prometheus),
+/// `/v1/scan` (versioned JSON), `/inventory` and `/healthz` over HTTP when `http` is set and the local
+/// socket for the GUI and CLI when `ipc` is (`--daemon`)
+pub fn run_server(settings: &Settings, http: bool, ipc: bool) -> i32 {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+
+    rt.block_on(async {
+        let state = Arc::new(ScanState::new());
+        let interval =
+            Duration::from_secs(u64::from(settings.server.scan_interval_minutes.max(1)) * 60);
+
+        if ipc {
+            match crate::daemon::bind().await {
+                Ok(listener) => {
+                    tokio::spawn(crate::daemon::serve(listener, Arc::clone(&state)));
+                }
+                Err(e) => {
+                    eprintln!("[ERROR] Can't start the daemon: {}", e);
+                    return 1;
+                }
+            }
+        }
+
+        if http {
+            match TcpListener::bind(&settings.server.listen).await {
+                Ok(listener) => {
+                    println!("[SERVER] Listening on http://{}", settings.server.listen);
+                    tokio::spawn(serve_http(listener, Arc::clone(&state)));
+                }
+                Err(e) => {
+                    eprintln!("[ERROR] Can't listen on {}: {}", settings.server.listen, e);
+                    return 1;
+                }
+            }
+        }
+
+        scan_loop(state, interval, &settings.background, &settings.auto_update).await;
+        0
+    })
+}
+
+async fn scan_loop(
+    state: Arc<ScanState>,
+    interval: Duration,
+    background: &BackgroundSettings,
+    auto_update: &AutoUpdateSettings,
+) {
+    let mut window = crate::operations::auto_update::WindowState::default();
+    loop {
+        let managers = crate::managers::detect_available_managers().await;
+        let crate::inventory::Scan {
+            packages, projects, ..
+        } = crate::inventory::collect_scan(&managers).await;
+
+        for manager in &managers {
+            let labels = [("manager", manager.name())];
+            let of_manager = || packages.iter().filter(move |p| &p.manager == manager);
+            metrics::set_gauge("depmgr_packages", &labels, of_manager().count() as f64);
+            metrics::set_gauge(
+                "depmgr_outdated_packages",
+                &labels,
+                of_manager().filter(|p| p.is_outdated).count() as f64,
+            );
+            metrics::set_gauge(
+                "depmgr_vulnerable_packages",
+                &labels,
+                of_manager()
+                    .filter(|p| !p.vulnerabilities.is_empty())
+                    .count() as f64,
+            );
+        }
+        metrics::set_gauge(
+            "depmgr_last_scan_timestamp_seconds",
+            &[],
+            chrono::Utc::now().timestamp() as f64,
+        );
+        println!("[SERVER] Scan complete: {} packages", packages.len());
+        let changed =
+            crate::operations::auto_update::run_due_updates(auto_update, &packages, &mut window)
+                .await;
+        *state.inventory.write().await = packages;
+        *state.projects.write().await = projects;
+        state.scans.send_modify(|count| *count += 1);
+
+        // Rescan right away so the inventory shows what the maintenance window changed
+        if changed > 0 {
+            continue;
+        }
+
+        wait_for_next_scan(&state, interval, background).await;
+    }
+}
+
+/// Sleep until the next scheduled scan, pushing it back while on battery or a metered
+/// connection; a requested rescan goes ahead regardless
+async fn wait_for_next_scan(
+    state: &ScanState,
+    interval: Duration,
+    background: &BackgroundSettings,
+) {
+    let retry = Duration::from_secs(u64::from(background.retry_minutes.max(1)) * 60).min(interval);
+    let mut wait = interval;
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = state.rescan.notified() => {
+                println!("[SERVER] Rescan requested");
+                return;
+            }
+        }
+        match crate::utils::power::defer_reason(background).await {
+            Some(reason) => {
+                println!("[SERVER] Deferring the scheduled scan: {}", reason);
+                metrics::inc_counter("depmgr_deferred_scans_total", &[("reason", reason)]);
+                wait = retry;
+            }
+            None => return,
+        }
+    }
+}
+
+async fn serve_http(listener: TcpListener, state: Arc<ScanState>) {
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(e) => {
+                eprintln!("[SERVER] Accept failed: {}", e);
+                continue;
+            }
+        };
+        let state = Arc::clone(&state);
+        tokio::spawn(async move {
+            if let Err(e) = handle(stream, state).await {
+                eprintln!("[SERVER] Request from {} failed: {}", peer, e);
+            }
+        });
+    }
+}
+
+async fn handle(mut stream: TcpStream, state: Arc<ScanState>) -> Result<()> {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = tokio::time::timeout(Duration::from_secs(5), stream.read(&mut buf)).await??;
+        if n == 0 {
+            break;
+        }
+        request.extend_from_slice(&buf[..n]);
+        if request.len() > MAX_REQUEST_BYTES {
+            return Err(anyhow!("request too large"));
+        }
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let mut request_line = request.lines().next().unwrap_or("").split_whitespace();
+    let method = request_line.next().unwrap_or("");
+    let path = request_line.next().unwrap_or("");
+
+    let (status, content_type, body) = match (method, path) {
+        ("GET", "/metrics") => ("200 OK", "text/plain; version=0.0.4", metrics::render()),
+        ("GET", "/v1/scan") => {
+            let output = crate::report::schema::ScanOutput::new(
+                &state.inventory.read().await,
+                &state.projects.read().await,
+            );
+            (
+                "200 OK",
+                "application/json",
+                serde_json::to_string(&output)?,
+            )
+        }
+        ("GET", "/inventory") => (
+            "200 OK",
+            "application/json",
+            serde_json::to_string(&*state.inventory.read().await)?,
+        ),
+        ("GET", "/healthz") => ("200 OK", "text/plain", "ok\n".to_string()),
+        ("GET", _) => ("404 Not Found", "text/plain", "not found\n".to_string()),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "method not allowed\n".to_string(),
+        ),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
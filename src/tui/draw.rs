@@ -0,0 +1,293 @@
+use super::Mode;
+use crate::app::DepMgrApp;
+use crate::models::Package;
+use crate::utils::format::{format_relative, format_size};
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Wrap};
+use ratatui::Frame;
+use std::sync::atomic::Ordering;
+
+const KEYS: &str =
+    "↑↓ move  / search  o outdated  Tab view  u update  U update all  p pin  r rescan  q quit";
+
+/// Target, view, counts and what's running
+fn header(app: &DepMgrApp, mode: Mode) -> Line<'static> {
+    let (total, outdated, unused) = app.stats();
+    let mut spans = vec![
+        Span::styled(
+            format!(" DepMgr · {} ", app.target.label()),
+            Style::new().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!("· {} ", app.view.label())),
+        Span::raw(format!(
+            "· {} packages, {} outdated, {} unused ",
+            total, outdated, unused
+        )),
+    ];
+    if app.show_outdated_only {
+        spans.push(Span::styled(
+            "· outdated only ",
+            Style::new().fg(Color::Yellow),
+        ));
+    }
+    if mode == Mode::Search || !app.search_query.is_empty() {
+        let cursor = if mode == Mode::Search { "_" } else { "" };
+        spans.push(Span::styled(
+            format!("· /{}{} ", app.search_query, cursor),
+            Style::new().fg(Color::Cyan),
+        ));
+    }
+    let status = app.get_update_status();
+    if app.is_scanning.load(Ordering::Relaxed) {
+        spans.push(Span::styled("· scanning… ", Style::new().fg(Color::Cyan)));
+    } else if status.is_empty() {
+        if let Some(scanned) = app.last_scan_time() {
+            spans.push(Span::raw(format!(
+                "· scanned {} ",
+                format_relative(scanned)
+            )));
+        }
+    }
+    if !status.is_empty() {
+        spans.push(Span::styled(
+            format!("· {} ", status),
+            Style::new().fg(Color::Cyan),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// The row's most pressing state, like the GUI's status icons
+fn status(app: &DepMgrApp, pkg: &Package) -> (&'static str, Style) {
+    if app.is_updating(&pkg.name) {
+        ("updating", Style::new().fg(Color::Cyan))
+    } else if !pkg.vulnerabilities.is_empty() {
+        ("vulnerable", Style::new().fg(Color::Red))
+    } else if app.package_flags.is_pinned(pkg) {
+        ("pinned", Style::new().add_modifier(Modifier::DIM))
+    } else if app.package_flags.needs_attention(pkg) {
+        ("outdated", Style::new().fg(Color::Yellow))
+    } else {
+        ("", Style::new())
+    }
+}
+
+fn package_table(frame: &mut Frame, area: Rect, app: &DepMgrApp, rows: &[Package]) {
+    let header = Row::new([
+        "Name",
+        "Manager",
+        "Installed",
+        "Latest",
+        "Used in",
+        "Size",
+        "Status",
+    ])
+    .style(Style::new().add_modifier(Modifier::BOLD));
+    let table_rows: Vec<Row> = rows
+        .iter()
+        .map(|pkg| {
+            let (label, style) = status(app, pkg);
+            Row::new([
+                Cell::from(pkg.name.clone()),
+                Cell::from(pkg.manager.name()),
+                Cell::from(pkg.installed_version.clone()),
+                Cell::from(
+                    pkg.latest_version
+                        .clone()
+                        .filter(|_| pkg.is_outdated)
+                        .unwrap_or_default(),
+                ),
+                Cell::from(match pkg.used_in.len() {
+                    0 => "-".to_string(),
+                    n => n.to_string(),
+                }),
+                Cell::from(
+                    pkg.size
+                        .map(|size| format_size(size, app.size_units))
+                        .unwrap_or_default(),
+                ),
+                Cell::from(label).style(style),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Fill(1),
+        Constraint::Length(14),
+        Constraint::Length(14),
+        Constraint::Length(14),
+        Constraint::Length(7),
+        Constraint::Length(9),
+        Constraint::Length(10),
+    ];
+    let empty = rows.is_empty();
+    let table = Table::new(table_rows, widths)
+        .header(header)
+        .block(Block::new().borders(Borders::TOP | Borders::BOTTOM))
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    let mut state = TableState::new().with_selected(rows.iter().position(|p| app.is_selected(p)));
+    frame.render_stateful_widget(table, area, &mut state);
+
+    if empty && !app.is_scanning.load(Ordering::Relaxed) {
+        let [_, message] =
+            Layout::vertical([Constraint::Length(2), Constraint::Length(1)]).areas(area);
+        frame.render_widget(
+            Paragraph::new("  No packages match the filters").style(Style::new().fg(Color::Gray)),
+            message,
+        );
+    }
+}
+
+/// Description, usage and advisories of the selected row
+fn details(app: &DepMgrApp, rows: &[Package]) -> Vec<Line<'static>> {
+    let Some(pkg) = rows.iter().find(|p| app.is_selected(p)) else {
+        return Vec::new();
+    };
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            format!(" {} ", pkg.name),
+            Style::new().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(pkg.description.clone().unwrap_or_default()),
+    ])];
+    let usage = match pkg.project_refs.len().max(pkg.used_in.len()) {
+        0 => " Not used by any scanned project".to_string(),
+        _ if !pkg.project_refs.is_empty() => format!(
+            " Used in {}",
+            pkg.project_refs
+                .iter()
+                .map(|r| r.label())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        _ => format!(" Used in {}", pkg.used_in.join(", ")),
+    };
+    lines.push(Line::raw(usage));
+    if !pkg.vulnerabilities.is_empty() {
+        lines.push(Line::styled(
+            format!(" Advisories: {}", pkg.vulnerabilities.join(", ")),
+            Style::new().fg(Color::Red),
+        ));
+    }
+    lines
+}
+
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let [area] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
+
+/// The first update held for confirmation, as the GUI's dialogs show it
+fn held_dialog(app: &DepMgrApp) -> Option<(&'static str, Vec<Line<'static>>)> {
+    if let Some(pending) = app.pending_critical.first() {
+        return Some((
+            " Critical package ",
+            vec![
+                Line::raw(format!(
+                    "{} ({}) is marked critical. Anything running on it may be disrupted.",
+                    pending.name,
+                    pending.manager.name()
+                )),
+                Line::raw(""),
+                Line::raw(format!(
+                    "Type {} and press Enter to {} it (Esc cancels):",
+                    pending.name,
+                    pending.action.label()
+                )),
+                Line::styled(
+                    format!("> {}_", app.critical_input),
+                    Style::new().fg(Color::Cyan),
+                ),
+            ],
+        ));
+    }
+    if let Some(pending) = app.pending_conflicts.first() {
+        let mut lines = vec![Line::raw(format!(
+            "To {} {} ({}), these installed packages get in the way:",
+            pending.action.label(),
+            pending.package.name,
+            pending.package.manager.name()
+        ))];
+        for conflict in &pending.conflicts {
+            lines.push(Line::raw(format!(
+                "  {} ({}): {}",
+                conflict.name,
+                conflict.manager.name(),
+                conflict.reason
+            )));
+        }
+        lines.push(Line::raw(""));
+        lines.push(Line::raw("y: go ahead anyway   n: cancel"));
+        return Some((" Conflicts ", lines));
+    }
+    if let Some(pending) = app.pending_running.blocking_read().first() {
+        let mut lines = vec![
+            Line::raw(format!(
+                "Updating {} ({}) replaces binaries that are in use:",
+                pending.name,
+                pending.manager.name()
+            )),
+            Line::styled(
+                format!("  {}", pending.processes.summary()),
+                Style::new().fg(Color::Yellow),
+            ),
+        ];
+        if let Some(service) = pending.service.as_ref().filter(|_| pending.restart_service) {
+            lines.push(Line::raw(format!(
+                "The {} service is stopped first and started again after.",
+                service
+            )));
+        } else {
+            lines.push(Line::raw(
+                "Running processes keep the old version until they're restarted.",
+            ));
+        }
+        lines.push(Line::raw(""));
+        lines.push(Line::raw("y: update anyway   n: cancel"));
+        return Some((" Package in use ", lines));
+    }
+    None
+}
+
+pub fn draw(frame: &mut Frame, app: &DepMgrApp, mode: Mode, rows: &[Package]) {
+    let [top, table, bottom, keys] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Fill(1),
+        Constraint::Length(3),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    frame.render_widget(Paragraph::new(header(app, mode)), top);
+    package_table(frame, table, app, rows);
+    frame.render_widget(
+        Paragraph::new(details(app, rows)).wrap(Wrap { trim: false }),
+        bottom,
+    );
+    frame.render_widget(
+        Paragraph::new(format!(" {}", KEYS)).style(Style::new().add_modifier(Modifier::DIM)),
+        keys,
+    );
+
+    if let Some((title, lines)) = held_dialog(app) {
+        let area = centered(
+            frame.area(),
+            frame.area().width.min(80),
+            lines.len() as u16 + 2,
+        );
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(Block::bordered().title(title)),
+            area,
+        );
+    }
+}
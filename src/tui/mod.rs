@@ -0,0 +1,237 @@
+mod draw;
+
+use crate::app::{DepMgrApp, View};
+use crate::deep_link::DeepLink;
+use ratatui::backend::CrosstermBackend;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::{execute, terminal};
+use ratatui::Terminal;
+use std::io::Write;
+use std::time::Duration;
+
+/// How often the screen is redrawn while no key is pressed, so scan and update progress
+/// show up
+const TICK: Duration = Duration::from_millis(250);
+
+/// What keys go to: the table, or the search being typed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Normal,
+    Search,
+}
+
+/// Terminal-only state; everything else lives in `DepMgrApp`, as in the GUI
+struct TuiState {
+    mode: Mode,
+    quit: bool,
+}
+
+/// Every view that is a package table, in Tab order: the sidebar's smart views, then one
+/// per manager
+fn table_views(app: &DepMgrApp) -> Vec<View> {
+    let mut views = vec![View::All, View::Vulnerable, View::Unused, View::Pinned];
+    views.extend(app.available_managers.iter().cloned().map(View::Manager));
+    views
+}
+
+fn cycle_view(app: &mut DepMgrApp, delta: isize) {
+    let views = table_views(app);
+    let current = views.iter().position(|v| *v == app.view).unwrap_or(0) as isize;
+    let next = (current + delta).rem_euclid(views.len() as isize) as usize;
+    app.view = views[next].clone();
+}
+
+/// Keys while an update is held for confirmation: the same questions the GUI's dialogs
+/// ask, answered with y/n (or the package's name, for critical ones)
+fn handle_held_key(app: &mut DepMgrApp, key: KeyEvent) -> bool {
+    if let Some(pending) = app.pending_critical.first() {
+        let name = pending.name.clone();
+        match key.code {
+            KeyCode::Enter if app.critical_input.trim() == name => app.confirm_critical(),
+            KeyCode::Esc => {
+                app.pending_critical.remove(0);
+                app.critical_input.clear();
+            }
+            KeyCode::Backspace => {
+                app.critical_input.pop();
+            }
+            KeyCode::Char(c) => app.critical_input.push(c),
+            _ => {}
+        }
+        return true;
+    }
+
+    let proceed = matches!(key.code, KeyCode::Char('y') | KeyCode::Enter);
+    let cancel = matches!(key.code, KeyCode::Char('n') | KeyCode::Esc);
+    if !app.pending_conflicts.is_empty() {
+        if proceed {
+            app.proceed_despite_conflicts();
+        } else if cancel {
+            app.pending_conflicts.remove(0);
+        }
+        return true;
+    }
+    if !app.pending_running.blocking_read().is_empty() {
+        if proceed {
+            app.proceed_despite_running();
+        } else if cancel {
+            app.pending_running.blocking_write().remove(0);
+        }
+        return true;
+    }
+    false
+}
+
+fn handle_key(app: &mut DepMgrApp, tui: &mut TuiState, key: KeyEvent) {
+    if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        tui.quit = true;
+        return;
+    }
+    if handle_held_key(app, key) {
+        return;
+    }
+
+    if tui.mode == Mode::Search {
+        match key.code {
+            KeyCode::Enter => tui.mode = Mode::Normal,
+            KeyCode::Esc => {
+                app.search_query.clear();
+                tui.mode = Mode::Normal;
+            }
+            KeyCode::Backspace => {
+                app.search_query.pop();
+            }
+            KeyCode::Char(c) => app.search_query.push(c),
+            _ => {}
+        }
+        return;
+    }
+
+    let rows = app.filtered_packages();
+    let selected = rows.iter().find(|p| app.is_selected(p)).cloned();
+    match key.code {
+        KeyCode::Char('q') => tui.quit = true,
+        KeyCode::Up | KeyCode::Char('k') => app.move_selection(&rows, -1),
+        KeyCode::Down | KeyCode::Char('j') => app.move_selection(&rows, 1),
+        KeyCode::PageUp => app.move_selection(&rows, -10),
+        KeyCode::PageDown => app.move_selection(&rows, 10),
+        KeyCode::Home => app.move_selection(&rows, -(rows.len() as isize)),
+        KeyCode::End => app.move_selection(&rows, rows.len() as isize),
+        KeyCode::Char('/') => tui.mode = Mode::Search,
+        KeyCode::Esc => app.search_query.clear(),
+        KeyCode::Char('o') => app.show_outdated_only = !app.show_outdated_only,
+        KeyCode::Tab => cycle_view(app, 1),
+        KeyCode::BackTab => cycle_view(app, -1),
+        KeyCode::Char('r') => app.request_refresh(),
+        KeyCode::Char('u') => {
+            if let Some(pkg) = selected.filter(|p| p.is_outdated && !app.is_updating(&p.name)) {
+                app.update_package(pkg.name, pkg.manager);
+            }
+        }
+        KeyCode::Char('U') => app.update_all_outdated(),
+        KeyCode::Char('p') => {
+            if let Some(pkg) = selected {
+                app.toggle_pinned(&pkg);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Leaves the terminal as it was found, also when the TUI panics
+struct TerminalGuard<W: Write> {
+    terminal: Terminal<CrosstermBackend<W>>,
+}
+
+impl<W: Write> Drop for TerminalGuard<W> {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+        let _ = execute!(self.terminal.backend_mut(), terminal::LeaveAlternateScreen);
+        let _ = self.terminal.show_cursor();
+    }
+}
+
+#[cfg(unix)]
+fn screen() -> std::io::Result<std::fs::File> {
+    std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+}
+
+#[cfg(not(unix))]
+fn screen() -> std::io::Result<std::io::Stdout> {
+    Ok(std::io::stdout())
+}
+
+fn run_loop<W: Write>(app: &mut DepMgrApp, guard: &mut TerminalGuard<W>) -> std::io::Result<()> {
+    let mut tui = TuiState {
+        mode: Mode::Normal,
+        quit: false,
+    };
+    while !tui.quit {
+        app.handle_refresh();
+        let rows = app.filtered_packages();
+        // Keep a row selected as filters change what's shown
+        if !rows.iter().any(|p| app.is_selected(p)) {
+            app.selected_package = None;
+            app.move_selection(&rows, 0);
+        }
+        guard
+            .terminal
+            .draw(|frame| draw::draw(frame, app, tui.mode, &rows))?;
+
+        if event::poll(TICK)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    handle_key(app, &mut tui, key);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Full-screen terminal frontend (`depmgr tui`) on the GUI's state: the same scans,
+/// filters, update queue and confirmations, for machines without a display
+pub fn run(link: Option<DeepLink>) -> i32 {
+    // Scans and commands log to stdout and stderr, which would scribble over the
+    // screen: while the TUI runs they go to a log file
+    #[cfg(unix)]
+    let redirect = {
+        let log_path = crate::utils::paths::data_dir().join("tui.log");
+        match crate::utils::log_redirect::LogRedirect::to_file(&log_path) {
+            Ok(redirect) => redirect,
+            Err(e) => {
+                eprintln!("[ERROR] Can't open {}: {}", log_path.display(), e);
+                return 1;
+            }
+        }
+    };
+
+    let mut app = DepMgrApp::start();
+    if let Some(link) = &link {
+        app.open_link(link);
+    }
+
+    let result = screen().and_then(|screen| {
+        terminal::enable_raw_mode()?;
+        let mut backend = CrosstermBackend::new(screen);
+        execute!(backend, terminal::EnterAlternateScreen)?;
+        let mut guard = TerminalGuard {
+            terminal: Terminal::new(backend)?,
+        };
+        run_loop(&mut app, &mut guard)
+    });
+    app.shutdown();
+
+    #[cfg(unix)]
+    drop(redirect);
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("[ERROR] Terminal UI failed: {}", e);
+            1
+        }
+    }
+}
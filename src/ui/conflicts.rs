@@ -0,0 +1,52 @@
+use crate::app::DepMgrApp;
+use eframe::egui;
+
+/// Explains why an install/update was held back, instead of letting the command fail
+pub fn show_conflict_dialog(ctx: &egui::Context, app: &mut DepMgrApp) {
+    let Some(pending) = app.pending_conflicts.first() else {
+        return;
+    };
+
+    let mut proceed = false;
+    let mut cancel = false;
+    egui::Window::new("Possible conflict")
+        .id(egui::Id::new("conflict_dialog"))
+        .collapsible(false)
+        .resizable(true)
+        .default_width(440.0)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.label(format!(
+                "Before you {} {} ({}), note that it conflicts with:",
+                pending.action.label(),
+                pending.package.name,
+                pending.package.manager.name()
+            ));
+            ui.separator();
+            for conflict in &pending.conflicts {
+                ui.horizontal(|ui| {
+                    ui.colored_label(egui::Color32::from_rgb(255, 90, 0), "⚠");
+                    ui.strong(format!("{} ({})", conflict.name, conflict.manager.name()));
+                });
+                ui.label(&conflict.reason);
+            }
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui
+                    .button(format!("Continue with {}", pending.action.label()))
+                    .clicked()
+                {
+                    proceed = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+        });
+
+    if proceed {
+        app.proceed_despite_conflicts();
+    } else if cancel {
+        app.pending_conflicts.remove(0);
+    }
+}
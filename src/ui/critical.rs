@@ -0,0 +1,55 @@
+use crate::app::DepMgrApp;
+use eframe::egui;
+
+/// Typed confirmation before a critical package is updated or removed
+pub fn show_critical_dialog(ctx: &egui::Context, app: &mut DepMgrApp) {
+    let Some(pending) = app.pending_critical.first() else {
+        return;
+    };
+    let name = pending.name.clone();
+
+    let mut proceed = false;
+    let mut cancel = false;
+    egui::Window::new("Critical package")
+        .id(egui::Id::new("critical_dialog"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.label(format!(
+                "🛡 {} ({}) is marked critical. Anything running on it may be disrupted.",
+                name,
+                pending.manager.name()
+            ));
+            ui.label(format!("Type {} to {} it:", name, pending.action.label()));
+            let response = ui.text_edit_singleline(&mut app.critical_input);
+            let matches = app.critical_input.trim() == name;
+            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) && matches {
+                proceed = true;
+            }
+            ui.horizontal(|ui| {
+                let label = format!("{} {}", capitalize(pending.action.label()), name);
+                if ui.add_enabled(matches, egui::Button::new(label)).clicked() {
+                    proceed = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+        });
+
+    if proceed {
+        app.confirm_critical();
+    } else if cancel {
+        app.pending_critical.remove(0);
+        app.critical_input.clear();
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -1,28 +1,236 @@
-use crate::app::DepMgrApp;
+use crate::app::{DepMgrApp, View};
+use crate::managers::releases::has_release_dates;
+use crate::models::{Category, ChangeKind, PackageManager, RiskLevel};
+use crate::ui::conflicts::show_conflict_dialog;
+use crate::ui::critical::show_critical_dialog;
+use crate::ui::details::show_package_details;
+use crate::ui::diagnostics::{manager_chip_for, show_manager_diagnostics};
+use crate::ui::folder_scan::{
+    handle_dropped_folders, show_folder_scan_window, show_scan_folder_input,
+};
+use crate::ui::fonts::{font_name_cell, show_font_actions};
+use crate::ui::log::show_log_window;
+use crate::ui::palette::show_command_palette;
+use crate::ui::path_report::show_path_report_window;
+use crate::ui::repo_audit::{show_repo_audit_input, show_repo_audit_window};
+use crate::ui::running::show_running_dialog;
+use crate::ui::runtime_tools::{show_runtime_tools_window, show_stranded_banner};
+use crate::ui::settings::show_settings_window;
+use crate::ui::status_bar::show_status_bar;
+use crate::ui::triage::show_triage_window;
+use crate::ui::update_everything::show_update_everything_window;
+use crate::ui::weekly::show_weekly_summary;
+use crate::utils::format::{
+    format_date, format_datetime, format_number, format_relative, format_size, SizeUnits,
+};
+use crate::utils::prompt::{pending_prompts, reply_to_prompt, PromptReply};
+use crate::utils::target::ExecTarget;
+use chrono::{DateTime, Utc};
 use eframe::egui;
 use egui_extras::{Column, TableBuilder};
 
+/// Hover text for casks marked `auto_updates`
+pub const AUTO_UPDATES_NOTE: &str = "The app updates itself, so brew's version is often behind. \
+    It isn't counted as outdated or included in Update All unless \
+    include_auto_updates is set under [homebrew] in config.toml.";
+
+/// The environment score in large type, its trend since the last day with a score,
+/// and what each part contributed
+fn show_score(
+    ui: &mut egui::Ui,
+    score: &crate::report::score::EnvironmentScore,
+    previous: Option<&crate::report::score::ScoreEntry>,
+) {
+    let total = score.total();
+    let color = match total {
+        90.. => egui::Color32::from_rgb(0, 200, 0),
+        70..=89 => egui::Color32::from_rgb(255, 165, 0),
+        _ => egui::Color32::from_rgb(255, 60, 60),
+    };
+    ui.horizontal(|ui| {
+        ui.label(
+            egui::RichText::new(total.to_string())
+                .size(32.0)
+                .strong()
+                .color(color),
+        )
+        .on_hover_text("Environment score out of 100");
+        if let Some(previous) = previous {
+            let (arrow, arrow_color) = match total.cmp(&previous.score) {
+                std::cmp::Ordering::Greater => ("⏶", egui::Color32::from_rgb(0, 200, 0)),
+                std::cmp::Ordering::Less => ("⏷", egui::Color32::from_rgb(255, 60, 60)),
+                std::cmp::Ordering::Equal => ("=", egui::Color32::GRAY),
+            };
+            let delta = total as i64 - previous.score as i64;
+            ui.label(egui::RichText::new(format!("{} {:+}", arrow, delta)).color(arrow_color))
+                .on_hover_text(format!(
+                    "{} on {}",
+                    previous.score,
+                    previous.date.format("%b %-d")
+                ));
+        }
+    });
+    for part in &score.parts {
+        ui.label(format!("{}: {}/{}", part.label, part.points, part.max))
+            .on_hover_text(&part.detail);
+    }
+}
+
 pub fn show_dashboard(ctx: &egui::Context, app: &mut DepMgrApp) {
+    // Commands can start waiting for input at any time, so poll even when idle
+    ctx.request_repaint_after(std::time::Duration::from_secs(1));
+    show_pending_prompts(ctx, app);
+    show_critical_dialog(ctx, app);
+    show_conflict_dialog(ctx, app);
+    show_running_dialog(ctx, app);
+    show_update_everything_window(ctx, app);
+    show_manager_diagnostics(ctx, app);
+    show_command_palette(ctx, app);
+    show_triage_window(ctx, app);
+    show_path_report_window(ctx, app);
+    show_runtime_tools_window(ctx, app);
+    show_log_window(ctx, app);
+    show_folder_scan_window(ctx, app);
+    show_repo_audit_window(ctx, app);
+    show_settings_window(ctx, app);
+    show_weekly_summary(ctx, app);
+    handle_dropped_folders(ctx, app);
+    show_status_bar(ctx, app);
+
     egui::CentralPanel::default().show(ctx, |_ui| {
         // Sidebar
         egui::SidePanel::left("sidebar")
             .resizable(true)
             .default_width(200.0)
             .show(ctx, |ui| {
-                ui.heading("Package Managers");
+                // Host selector, only when there's somewhere else to look
+                if !app.settings.remote.hosts.is_empty() || !app.target.is_local() {
+                    let mut choice = app.target.clone();
+                    egui::ComboBox::from_label("Host")
+                        .selected_text(choice.label())
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut choice,
+                                ExecTarget::Local,
+                                ExecTarget::Local.label(),
+                            );
+                            for host in &app.settings.remote.hosts {
+                                // Hosts that look like ssh options aren't offered
+                                let Ok(target) = ExecTarget::ssh(host) else {
+                                    continue;
+                                };
+                                let label = target.label();
+                                ui.selectable_value(&mut choice, target, label);
+                            }
+                            if app.target.is_container() || app.target.is_demo() {
+                                let label = app.target.label();
+                                ui.selectable_value(&mut choice, app.target.clone(), label);
+                            }
+                        });
+                    if choice != app.target {
+                        app.switch_target(choice);
+                    }
+                    ui.separator();
+                }
+
+                show_scan_folder_input(ui, app);
+                show_repo_audit_input(ui, app);
+                ui.collapsing("🐳 Audit container", |ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut app.container_input)
+                            .hint_text("image or container, e.g. python:3.12-slim"),
+                    );
+                    if ui.button("Audit").clicked() {
+                        app.open_container(app.container_input.clone());
+                    }
+                });
                 ui.separator();
 
-                // Manager filters
-                for manager in &app.available_managers {
-                    let is_selected = app.selected_managers.contains(manager);
+                ui.heading("Browse");
+                ui.separator();
+
+                view_node(ui, app, View::All, "📦");
+
+                // One node per manager; the checkbox decides what's scanned and listed
+                // under All packages, the name opens the manager on its own
+                let manager_stats = app.manager_stats();
+                for manager in app.available_managers.clone() {
+                    ui.horizontal(|ui| {
+                        ui.add_space(12.0);
+                        let is_selected = app.selected_managers.contains(&manager);
+                        if ui
+                            .checkbox(&mut app.selected_managers.contains(&manager), "")
+                            .on_hover_text("Scan and include in All packages")
+                            .clicked()
+                        {
+                            if is_selected {
+                                app.selected_managers.remove(&manager);
+                            } else {
+                                app.selected_managers.insert(manager.clone());
+                            }
+                        }
+                        let view = View::Manager(manager.clone());
+                        if ui
+                            .selectable_label(app.view == view, manager.name())
+                            .clicked()
+                        {
+                            app.view = view;
+                        }
+                        let (total, outdated, unused) =
+                            manager_stats.get(&manager).copied().unwrap_or_default();
+                        manager_badges(ui, total, outdated, unused);
+                        manager_chip_for(ui, app, &manager);
+                    });
+                    if manager == PackageManager::Fonts {
+                        show_font_actions(ui, app);
+                    }
+
+                    // Ancient manager versions cause a lot of confusing failures
+                    if let Some(latest) = app.manager_health(&manager).self_outdated {
+                        ui.horizontal(|ui| {
+                            ui.colored_label(
+                                egui::Color32::from_rgb(255, 165, 0),
+                                format!("⚠ {} itself is outdated", manager.command()),
+                            )
+                            .on_hover_text(format!("{} is available", latest));
+                            if ui
+                                .small_button("⬆️")
+                                .on_hover_text(format!("Update {}", manager.name()))
+                                .clicked()
+                            {
+                                app.self_update_manager(manager.clone());
+                            }
+                        });
+                    }
+                }
+
+                view_node(ui, app, View::Projects, "📁");
+                view_node(ui, app, View::Vulnerable, "🛡");
+                view_node(ui, app, View::Unused, "🗑");
+                view_node(ui, app, View::Pinned, "📌");
+                view_node(ui, app, View::History, "🕘");
+                app.refresh_recent_changes();
+                view_node(ui, app, View::Recent, "📅");
+                view_node(ui, app, View::ScanReport, "📊");
+
+                ui.separator();
+                ui.heading("Categories");
+
+                let counts = app.category_counts();
+                for category in Category::ALL {
+                    let count = counts.get(&category).copied().unwrap_or(0);
+                    if count == 0 {
+                        continue;
+                    }
+                    let mut checked = app.selected_categories.contains(&category);
                     if ui
-                        .checkbox(&mut app.selected_managers.contains(manager), manager.name())
-                        .clicked()
+                        .checkbox(&mut checked, format!("{} ({})", category.label(), count))
+                        .changed()
                     {
-                        if is_selected {
-                            app.selected_managers.remove(manager);
+                        if checked {
+                            app.selected_categories.insert(category);
                         } else {
-                            app.selected_managers.insert(manager.clone());
+                            app.selected_categories.remove(&category);
                         }
                     }
                 }
@@ -30,16 +238,82 @@ pub fn show_dashboard(ctx: &egui::Context, app: &mut DepMgrApp) {
                 ui.separator();
                 ui.heading("Stats");
 
+                if let Some(score) = app.environment_score() {
+                    show_score(ui, &score, app.previous_score.as_ref());
+                }
+
                 let (total, outdated, unused) = app.stats();
-                ui.label(format!("Total: {}", total));
-                ui.label(format!("Outdated: {}", outdated));
-                ui.label(format!("Unused: {}", unused));
+                ui.label(format!("Total: {}", format_number(total as u64)));
+                ui.label(format!("Outdated: {}", format_number(outdated as u64)));
+                ui.label(format!("Unused: {}", format_number(unused as u64)));
+                let vulnerable = app.vulnerable_count();
+                if vulnerable > 0 {
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "Vulnerable: {}",
+                            format_number(vulnerable as u64)
+                        ))
+                        .color(egui::Color32::from_rgb(255, 0, 0)),
+                    );
+                }
+
+                match app.last_scan_time() {
+                    Some(when) => {
+                        ui.label(format!("Last scan: {}", format_relative(when)))
+                            .on_hover_text(format_datetime(when));
+                    }
+                    None => {
+                        ui.label("Last scan: never");
+                    }
+                }
+
+                let ages = app.metadata_ages();
+                if !ages.is_empty() {
+                    ui.separator();
+                    ui.label(egui::RichText::new("Data as of").strong());
+                    for (manager, when) in ages {
+                        ui.label(format!(
+                            "{}: {}",
+                            manager.name(),
+                            when.with_timezone(&chrono::Local).format("%H:%M")
+                        ))
+                        .on_hover_text(format!(
+                            "{} ({})",
+                            format_datetime(when),
+                            format_relative(when)
+                        ));
+                    }
+                    if ui
+                        .small_button("⟳ Refresh metadata")
+                        .on_hover_text("Run `brew update` so updates match what's shown here")
+                        .clicked()
+                    {
+                        app.refresh_metadata_now();
+                    }
+                }
+
+                ui.separator();
+
+                egui::ComboBox::from_label("Sizes")
+                    .selected_text(app.size_units.label())
+                    .show_ui(ui, |ui| {
+                        for units in [SizeUnits::Binary, SizeUnits::Decimal] {
+                            ui.selectable_value(&mut app.size_units, units, units.label());
+                        }
+                    });
 
                 ui.separator();
 
                 if ui.button("🔄 Refresh").clicked() {
                     app.request_refresh();
                 }
+                if ui
+                    .small_button("🗑 Clear cache")
+                    .on_hover_text("Forget cached API responses and fetch everything again")
+                    .clicked()
+                {
+                    app.clear_cache();
+                }
 
                 ui.separator();
 
@@ -48,11 +322,92 @@ pub fn show_dashboard(ctx: &egui::Context, app: &mut DepMgrApp) {
                 {
                     app.update_all_outdated();
                 }
+
+                if outdated > 0
+                    && ui
+                        .button("☰ Triage")
+                        .on_hover_text("Review outdated packages one at a time")
+                        .clicked()
+                {
+                    app.open_triage();
+                }
+
+                if app.target.is_local()
+                    && ui
+                        .button("🔀 PATH")
+                        .on_hover_text("Which copy of each command runs, and stale ones hiding newer copies")
+                        .clicked()
+                {
+                    app.open_path_report();
+                }
+
+                if ui
+                    .button("📜 Log")
+                    .on_hover_text("Every command run, with failures in red")
+                    .clicked()
+                {
+                    app.log_open = true;
+                }
+
+                if ui
+                    .button("🚀 Update Everything")
+                    .on_hover_text(
+                        "Self-update every package manager, then upgrade all outdated packages",
+                    )
+                    .clicked()
+                {
+                    app.open_update_everything();
+                }
+
+                if ui.button("⚙ Settings").clicked() {
+                    app.open_settings();
+                }
+
+                if app.settings.sync.is_configured() {
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        if ui
+                            .button("⬇ Pull")
+                            .on_hover_text("Merge pins, ignores, favorites and categories from your other machines")
+                            .clicked()
+                        {
+                            app.sync_metadata(false);
+                        }
+                        if ui
+                            .button("⬆ Push")
+                            .on_hover_text("Merge, then publish this machine's pins, ignores, favorites and categories")
+                            .clicked()
+                        {
+                            app.sync_metadata(true);
+                        }
+                    });
+                }
             });
 
+        show_package_details(ctx, app);
+
         // Main content area
         egui::CentralPanel::default().show(ctx, |ui| {
-            ui.heading("Packages");
+            ui.heading(app.view.label());
+            match app.view {
+                View::Projects => {
+                    crate::ui::projects::show_projects(ui, app);
+                    return;
+                }
+                View::History => {
+                    show_history(ui, app);
+                    return;
+                }
+                View::Recent => {
+                    crate::ui::recent::show_recent(ui, app);
+                    return;
+                }
+                View::ScanReport => {
+                    crate::ui::scan_report::show_scan_report(ui, app);
+                    return;
+                }
+                _ => {}
+            }
 
             // Search and filter bar
             ui.horizontal(|ui| {
@@ -63,69 +418,48 @@ pub fn show_dashboard(ctx: &egui::Context, app: &mut DepMgrApp) {
 
                 ui.checkbox(&mut app.show_outdated_only, "Outdated Only");
                 ui.checkbox(&mut app.show_orphaned_only, "Orphaned Only");
+
+                ui.separator();
+
+                if ui
+                    .button("📋 Copy as Markdown")
+                    .on_hover_text("Copy the current view as a Markdown table")
+                    .clicked()
+                {
+                    ctx.copy_text(app.filtered_markdown_report());
+                }
+                if ui
+                    .button("💾 Save report")
+                    .on_hover_text("Save the current view as a Markdown report in ~/Downloads")
+                    .clicked()
+                {
+                    app.save_markdown_report();
+                }
             });
+            ui.label(
+                egui::RichText::new(
+                    "⬆/⬇ select · p pin · i ignore · u update · d details · ⌘-click to mark rows",
+                )
+                .small()
+                .weak(),
+            );
 
             ui.separator();
 
-            // Show scanning status
             let is_scanning = app.is_scanning.load(std::sync::atomic::Ordering::Relaxed);
-            if is_scanning {
-                ui.horizontal(|ui| {
-                    ui.spinner();
-                    ui.label("Scanning packages...");
-                });
-                ui.separator();
-                // Request continuous repaints while scanning to show updates immediately
-                ctx.request_repaint();
-            }
-
-            // Show update status - full width, natural wrapping
-            let update_status = app.get_update_status();
-            if !update_status.is_empty() {
-                ui.horizontal(|ui| {
-                    if update_status.contains("...") {
-                        ui.spinner();
-                    }
-
-                    // Use full available width for status messages
-                    ui.with_layout(
-                        egui::Layout::left_to_right(egui::Align::Center).with_main_wrap(true),
-                        |ui| {
-                            ui.set_width(ui.available_width());
 
-                            // Color based on message type
-                            if update_status.contains("Failed") || update_status.contains("failed")
-                            {
-                                ui.label(
-                                    egui::RichText::new(&update_status)
-                                        .color(egui::Color32::from_rgb(255, 0, 0)),
-                                );
-                            } else if update_status.contains("removed")
-                                || update_status.contains("updated")
-                                || update_status.contains("reinstalled")
-                            {
-                                ui.label(
-                                    egui::RichText::new(&update_status)
-                                        .color(egui::Color32::from_rgb(0, 200, 0)),
-                                );
-                            } else {
-                                ui.label(&update_status);
-                            }
-                        },
-                    );
-                });
-                ui.separator();
-                ctx.request_repaint();
-            }
+            show_changes_panel(ui, app);
+            show_stranded_banner(ui, app);
 
             // Package table - show even while scanning
-            let filtered = app.filtered_packages();
+            let rows = app.filtered_indices();
+            handle_triage_keys(ctx, app);
 
-            if filtered.is_empty() && !is_scanning {
+            if rows.is_empty() && !is_scanning {
                 ui.centered_and_justified(|ui| {
                     ui.label("No packages found");
                 });
-            } else if !filtered.is_empty() {
+            } else if !rows.is_empty() {
                 // Wrap table in scroll area for both vertical and horizontal scrolling
                 egui::ScrollArea::both()
                     .auto_shrink([false, false])
@@ -139,6 +473,7 @@ pub fn show_dashboard(ctx: &egui::Context, app: &mut DepMgrApp) {
                             .column(Column::initial(80.0).at_least(60.0).resizable(true)) // Manager
                             .column(Column::initial(80.0).at_least(60.0).resizable(true)) // Installed
                             .column(Column::initial(80.0).at_least(60.0).resizable(true)) // Latest
+                            .column(Column::initial(70.0).at_least(50.0).resizable(true)) // Size
                             .column(Column::initial(300.0).at_least(100.0).resizable(true)) // Description (wider)
                             .column(Column::initial(200.0).at_least(80.0).resizable(true)) // Usage (wider)
                             .column(Column::initial(80.0).at_least(60.0).resizable(true)) // Status
@@ -156,6 +491,9 @@ pub fn show_dashboard(ctx: &egui::Context, app: &mut DepMgrApp) {
                                 header.col(|ui| {
                                     ui.strong("Latest");
                                 });
+                                header.col(|ui| {
+                                    ui.strong("Size");
+                                });
                                 header.col(|ui| {
                                     ui.strong("Description");
                                 });
@@ -169,22 +507,128 @@ pub fn show_dashboard(ctx: &egui::Context, app: &mut DepMgrApp) {
                                     ui.strong("Action");
                                 });
                             })
-                            .body(|mut body| {
-                                for pkg in filtered {
-                                    body.row(18.0, |mut row| {
+                            .body(|body| {
+                                body.rows(18.0, rows.len(), |mut row| {
+                                    // Only the rows scrolled into view are cloned out of the inventory
+                                    let packages = app.packages.blocking_read();
+                                    let Some(pkg) = packages.get(rows[row.index()]).cloned() else {
+                                        return;
+                                    };
+                                    drop(packages);
                                         row.col(|ui| {
-                                            ui.label(&pkg.name);
+                                            if pkg.manager == PackageManager::Fonts {
+                                                font_name_cell(ui, app, &pkg);
+                                                return;
+                                            }
+                                            let favorite = app.package_flags.is_favorite(&pkg);
+                                            let star = egui::RichText::new(if favorite {
+                                                "★"
+                                            } else {
+                                                "☆"
+                                            })
+                                            .color(if favorite {
+                                                egui::Color32::from_rgb(255, 190, 0)
+                                            } else {
+                                                egui::Color32::GRAY
+                                            });
+                                            if ui
+                                                .add(egui::Label::new(star).sense(egui::Sense::click()))
+                                                .on_hover_text(if favorite {
+                                                    "Remove from favorites"
+                                                } else {
+                                                    "Favorite: always list at the top"
+                                                })
+                                                .clicked()
+                                            {
+                                                app.package_flags.toggle_favorite(&pkg);
+                                            }
+                                            let selected =
+                                                app.is_selected(&pkg) || app.is_marked(&pkg);
+                                            if let Some(change) = app.change_of(&pkg) {
+                                                ui.label(
+                                                    egui::RichText::new("⏺")
+                                                        .small()
+                                                        .color(change_color(&change)),
+                                                )
+                                                .on_hover_text(format!(
+                                                    "Since the last scan: {}",
+                                                    change.describe()
+                                                ));
+                                            }
+                                            if pkg.needs_rosetta {
+                                                ui.label(
+                                                    egui::RichText::new("x86")
+                                                        .small()
+                                                        .color(egui::Color32::from_rgb(255, 165, 0)),
+                                                )
+                                                .on_hover_text("Intel-only, runs under Rosetta");
+                                            }
+                                            if pkg.auto_updates {
+                                                ui.label(egui::RichText::new("auto").small().weak())
+                                                    .on_hover_text(AUTO_UPDATES_NOTE);
+                                            }
+                                            if ui.selectable_label(selected, &pkg.name).clicked() {
+                                                if ui.input(|i| i.modifiers.command) {
+                                                    app.toggle_marked(&pkg);
+                                                } else {
+                                                    app.select_package(&pkg);
+                                                }
+                                            }
                                         });
                                         row.col(|ui| {
-                                            ui.label(pkg.manager.name());
+                                            let response = ui.label(pkg.manager.name());
+                                            if response.hovered() {
+                                                let listed = match app.listed_at(&pkg.manager) {
+                                                    Some(when) => format!(
+                                                        "Listed {} ({})",
+                                                        format_relative(when),
+                                                        format_datetime(when)
+                                                    ),
+                                                    None => "Not scanned yet".to_string(),
+                                                };
+                                                response.on_hover_text(listed);
+                                            }
                                         });
                                         row.col(|ui| {
-                                            ui.label(&pkg.installed_version);
+                                            let response = ui.label(&pkg.installed_version);
+                                            if response.hovered() {
+                                                let dates = app.release_dates(&pkg);
+                                                response.on_hover_text(version_tooltip(
+                                                    "Installed",
+                                                    &pkg.installed_version,
+                                                    &pkg.manager,
+                                                    dates.map(|d| d.installed),
+                                                ));
+                                            }
                                         });
 
                                         row.col(|ui| {
                                             if let Some(latest) = &pkg.latest_version {
-                                                ui.label(latest);
+                                                let response = ui.label(latest);
+                                                if response.hovered() {
+                                                    let dates = app.release_dates(&pkg);
+                                                    response.on_hover_text(version_tooltip(
+                                                        "Latest",
+                                                        latest,
+                                                        &pkg.manager,
+                                                        dates.map(|d| d.latest),
+                                                    ));
+                                                }
+                                                if pkg.is_outdated {
+                                                    risk_badge(ui, &app.risk_of(&pkg));
+                                                }
+                                            } else {
+                                                ui.label("-");
+                                            }
+                                        });
+
+                                        row.col(|ui| {
+                                            if let Some(size) = pkg.size {
+                                                ui.label(format_size(size, app.size_units))
+                                                    .on_hover_text(format!(
+                                                        "{} bytes",
+                                                        format_number(size)
+                                                    ));
                                             } else {
                                                 ui.label("-");
                                             }
@@ -193,8 +637,11 @@ pub fn show_dashboard(ctx: &egui::Context, app: &mut DepMgrApp) {
                                         // Description - no truncation, resizable column
                                         row.col(|ui| {
                                             if let Some(desc) = &pkg.description {
-                                                ui.label(desc);
+                                                ui.label(desc).on_hover_text(desc);
                                             } else {
+                                                if ui.is_rect_visible(ui.max_rect()) {
+                                                    app.request_description(&pkg);
+                                                }
                                                 ui.label("-");
                                             }
                                         });
@@ -207,29 +654,80 @@ pub fn show_dashboard(ctx: &egui::Context, app: &mut DepMgrApp) {
                                                         .color(egui::Color32::from_rgb(200, 0, 0)),
                                                 );
                                             } else {
-                                                // Extract folder names
+                                                // "repo/web" rather than a bare "web"
                                                 let folder_names: Vec<String> = pkg
                                                     .used_in
                                                     .iter()
-                                                    .filter_map(|path| {
-                                                        std::path::Path::new(path)
-                                                            .file_name()
-                                                            .and_then(|n| n.to_str())
-                                                            .map(|s| s.to_string())
-                                                    })
+                                                    .map(|path| pkg.usage_label(path))
                                                     .collect();
 
-                                                let display_text = folder_names.join(", ");
+                                                let mut display_text = folder_names.join(", ");
+                                                // Dev-only usage is a weaker reason to keep a package around
+                                                let color = match pkg.usage_kind() {
+                                                    Some("dev only") => {
+                                                        egui::Color32::from_rgb(0, 120, 200)
+                                                    }
+                                                    _ => egui::Color32::from_rgb(0, 150, 0),
+                                                };
+                                                if let Some(kind) = pkg.usage_kind() {
+                                                    display_text = format!(
+                                                        "used ({}): {}",
+                                                        kind, display_text
+                                                    );
+                                                }
                                                 ui.label(
-                                                    egui::RichText::new(display_text)
-                                                        .color(egui::Color32::from_rgb(0, 150, 0)),
-                                                );
+                                                    egui::RichText::new(display_text).color(color),
+                                                )
+                                                .on_hover_text(usage_tooltip(&pkg));
                                             }
                                         });
 
                                         // Status
                                         row.col(|ui| {
-                                            if pkg.is_outdated {
+                                            if !pkg.broken.is_empty() {
+                                                ui.label(
+                                                    egui::RichText::new("Broken")
+                                                        .color(egui::Color32::from_rgb(255, 0, 0)),
+                                                )
+                                                .on_hover_text(
+                                                    pkg.broken
+                                                        .iter()
+                                                        .map(|b| b.describe())
+                                                        .collect::<Vec<_>>()
+                                                        .join("\n"),
+                                                );
+                                            } else if !pkg.vulnerabilities.is_empty() {
+                                                ui.label(
+                                                    egui::RichText::new(format!(
+                                                        "Vulnerable ({})",
+                                                        pkg.vulnerabilities.len()
+                                                    ))
+                                                    .color(egui::Color32::from_rgb(255, 0, 0)),
+                                                )
+                                                .on_hover_text(pkg.vulnerabilities.join("\n"));
+                                            } else if !pkg.constraint_mismatches.is_empty() {
+                                                ui.label(
+                                                    egui::RichText::new("Too old")
+                                                        .color(egui::Color32::from_rgb(255, 90, 0)),
+                                                )
+                                                .on_hover_text(constraint_warning(&pkg));
+                                            } else if pkg.is_outdated
+                                                && app.package_flags.is_ignored(&pkg)
+                                            {
+                                                ui.label(egui::RichText::new("Ignored").weak())
+                                                    .on_hover_text("Press i to stop ignoring");
+                                            } else if pkg.is_outdated
+                                                && app.package_flags.is_pinned(&pkg)
+                                            {
+                                                ui.label(
+                                                    egui::RichText::new("📌 Pinned").color(
+                                                        egui::Color32::from_rgb(180, 140, 0),
+                                                    ),
+                                                )
+                                                .on_hover_text(
+                                                    "Outdated, but kept at this version (press p to unpin)",
+                                                );
+                                            } else if pkg.is_outdated {
                                                 ui.label(
                                                     egui::RichText::new("Outdated").color(
                                                         egui::Color32::from_rgb(255, 165, 0),
@@ -246,14 +744,25 @@ pub fn show_dashboard(ctx: &egui::Context, app: &mut DepMgrApp) {
                                         // Action buttons
                                         row.col(|ui| {
                                             ui.horizontal(|ui| {
-                                                let is_updating = app.is_updating(&pkg.name);
-                                                let is_removed = app.is_removed(&pkg.name);
+                                                let is_updating = app.is_row_busy(&pkg);
+                                                let is_removed = app.is_removed(&pkg);
 
                                                 if is_updating {
                                                     ui.spinner();
+                                                } else if !pkg.broken.is_empty() {
+                                                    if ui
+                                                        .button("🔧 Repair")
+                                                        .on_hover_text(
+                                                            "Relink, reinstall or forget (details panel)",
+                                                        )
+                                                        .clicked()
+                                                    {
+                                                        app.select_package(&pkg);
+                                                    }
                                                 } else {
                                                     if pkg.is_outdated
                                                         && !is_removed
+                                                        && !app.package_flags.is_pinned(&pkg)
                                                         && ui.button("Update").clicked()
                                                     {
                                                         app.update_package(
@@ -265,28 +774,317 @@ pub fn show_dashboard(ctx: &egui::Context, app: &mut DepMgrApp) {
                                                     if is_removed {
                                                         // Show "Reinstall" for removed packages
                                                         if ui.button("Reinstall").clicked() {
-                                                            app.reinstall_package(
-                                                                pkg.name.clone(),
-                                                                pkg.manager.clone(),
-                                                            );
+                                                            if pkg.manager.lists_each_version() {
+                                                                app.restore_version(&pkg);
+                                                            } else {
+                                                                app.reinstall_package(
+                                                                    pkg.name.clone(),
+                                                                    pkg.manager.clone(),
+                                                                );
+                                                            }
                                                         }
                                                     } else {
                                                         // Show "Remove" for installed packages
                                                         if ui.button("Remove").clicked() {
-                                                            app.uninstall_package(
-                                                                pkg.name.clone(),
-                                                                pkg.manager.clone(),
-                                                            );
+                                                            app.uninstall_package(&pkg);
                                                         }
                                                     }
                                                 }
                                             });
                                         });
                                     });
-                                }
                             });
                     });
             }
         });
     });
 }
+
+/// Single-key triage on the selected (or ⌘-clicked) rows, unless a text field has focus
+fn handle_triage_keys(ctx: &egui::Context, app: &mut DepMgrApp) {
+    if app.palette_open || ctx.wants_keyboard_input() {
+        return;
+    }
+    let pressed = |key: egui::Key| ctx.input(|i| i.key_pressed(key) && i.modifiers.is_none());
+
+    if pressed(egui::Key::ArrowDown) || pressed(egui::Key::J) {
+        app.move_selection(&app.filtered_packages(), 1);
+    }
+    if pressed(egui::Key::ArrowUp) || pressed(egui::Key::K) {
+        app.move_selection(&app.filtered_packages(), -1);
+    }
+    if pressed(egui::Key::P) {
+        app.toggle_pin_targets();
+    }
+    if pressed(egui::Key::I) {
+        app.toggle_ignore_targets();
+    }
+    if pressed(egui::Key::U) {
+        app.update_targets();
+    }
+    if pressed(egui::Key::D) {
+        app.details_hidden = !app.details_hidden;
+    }
+    if pressed(egui::Key::Escape) {
+        app.marked_packages.clear();
+    }
+}
+
+fn change_color(kind: &ChangeKind) -> egui::Color32 {
+    match kind {
+        ChangeKind::Installed => egui::Color32::from_rgb(0, 200, 0),
+        ChangeKind::Removed => egui::Color32::from_rgb(150, 150, 150),
+        ChangeKind::VersionChanged { .. } => egui::Color32::from_rgb(0, 140, 255),
+        ChangeKind::NewlyOutdated { .. } => egui::Color32::from_rgb(255, 165, 0),
+    }
+}
+
+/// Top-level sidebar entry with its count; selecting it switches the main pane
+fn view_node(ui: &mut egui::Ui, app: &mut DepMgrApp, view: View, icon: &str) {
+    let count = app.view_count(&view);
+    let text = format!(
+        "{} {} ({})",
+        icon,
+        view.label(),
+        format_number(count as u64)
+    );
+    if ui.selectable_label(app.view == view, text).clicked() {
+        app.view = view;
+    }
+}
+
+/// Everything the last refresh changed, kept after the banner is dismissed
+fn show_history(ui: &mut egui::Ui, app: &DepMgrApp) {
+    let changes = app.scan_changes.blocking_read().clone();
+    if changes.is_empty() {
+        ui.label("Nothing changed since the previous scan.");
+        return;
+    }
+    ui.label(
+        egui::RichText::new(format!("{} changes since the previous scan", changes.len())).weak(),
+    );
+    ui.separator();
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        egui::Grid::new("history")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for change in &changes {
+                    ui.label(egui::RichText::new("⏺").color(change_color(&change.kind)));
+                    ui.label(format!("{} ({})", change.name, change.manager.name()));
+                    ui.label(egui::RichText::new(change.kind.describe()).weak());
+                    ui.end_row();
+                }
+            });
+    });
+}
+
+/// Dismissible summary of what the last refresh found different
+fn show_changes_panel(ui: &mut egui::Ui, app: &mut DepMgrApp) {
+    if app
+        .changes_dismissed
+        .load(std::sync::atomic::Ordering::Relaxed)
+    {
+        return;
+    }
+    let changes = app.scan_changes.blocking_read().clone();
+    if changes.is_empty() {
+        return;
+    }
+
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            ui.strong("What changed");
+            // Sorted by kind, so each run is one count
+            for group in changes.chunk_by(|a, b| a.kind.label() == b.kind.label()) {
+                let kind = &group[0].kind;
+                ui.label(
+                    egui::RichText::new(format!("{} {}", group.len(), kind.label()))
+                        .color(change_color(kind)),
+                );
+            }
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.small_button("🗙").on_hover_text("Dismiss").clicked() {
+                    app.changes_dismissed
+                        .store(true, std::sync::atomic::Ordering::Relaxed);
+                }
+            });
+        });
+        egui::CollapsingHeader::new(format!("{} packages", changes.len()))
+            .id_salt("what_changed")
+            .show(ui, |ui| {
+                egui::ScrollArea::vertical()
+                    .max_height(150.0)
+                    .show(ui, |ui| {
+                        for change in &changes {
+                            ui.horizontal(|ui| {
+                                ui.label(
+                                    egui::RichText::new("⏺").color(change_color(&change.kind)),
+                                );
+                                ui.label(format!("{} ({})", change.name, change.manager.name()));
+                                ui.label(egui::RichText::new(change.kind.describe()).weak());
+                            });
+                        }
+                    });
+            });
+    });
+    ui.separator();
+}
+
+/// Full version string and, where the registry says, when it was published
+fn version_tooltip(
+    label: &str,
+    version: &str,
+    manager: &PackageManager,
+    released: Option<Option<DateTime<Utc>>>, // None while the lookup is running
+) -> String {
+    let mut text = format!("{}: {}", label, version);
+    match released {
+        Some(Some(when)) => text.push_str(&format!(
+            "\nReleased {} ({})",
+            format_date(when),
+            format_relative(when)
+        )),
+        None if has_release_dates(manager) => text.push_str("\nLooking up the release date…"),
+        _ => {}
+    }
+    text
+}
+
+/// Every project using the package with its full path, dev-only ones marked
+fn usage_tooltip(pkg: &crate::models::Package) -> String {
+    pkg.used_in
+        .iter()
+        .map(|path| {
+            let dev = if pkg.dev_only_in.contains(path) {
+                " (dev)"
+            } else {
+                ""
+            };
+            format!("{}{}\n    {}", pkg.usage_label(path), dev, path)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Colored dot for an update's risk, reasons on hover
+pub fn risk_badge(ui: &mut egui::Ui, risk: &crate::models::Risk) {
+    let color = match risk.level() {
+        RiskLevel::Low => egui::Color32::from_rgb(0, 170, 0),
+        RiskLevel::Medium => egui::Color32::from_rgb(255, 165, 0),
+        RiskLevel::High => egui::Color32::from_rgb(220, 0, 0),
+    };
+    let reasons = if risk.reasons.is_empty() {
+        "patch release of a leaf tool".to_string()
+    } else {
+        risk.reasons.join("\n")
+    };
+    ui.label(egui::RichText::new("⏺").color(color))
+        .on_hover_text(format!(
+            "{} ({}/10)\n{}",
+            risk.level().label(),
+            risk.score,
+            reasons
+        ));
+}
+
+/// Count badges next to a manager: total, outdated (orange), unused (red)
+fn manager_badges(ui: &mut egui::Ui, total: usize, outdated: usize, unused: usize) {
+    ui.label(
+        egui::RichText::new(format_number(total as u64))
+            .small()
+            .weak(),
+    )
+    .on_hover_text("Installed");
+    if outdated > 0 {
+        ui.label(
+            egui::RichText::new(format_number(outdated as u64))
+                .small()
+                .color(egui::Color32::from_rgb(255, 165, 0)),
+        )
+        .on_hover_text("Outdated");
+    }
+    if unused > 0 {
+        ui.label(
+            egui::RichText::new(format_number(unused as u64))
+                .small()
+                .color(egui::Color32::from_rgb(200, 0, 0)),
+        )
+        .on_hover_text("Unused");
+    }
+}
+
+/// "project X needs newer version" lines plus the suggested fix
+pub fn constraint_warning(pkg: &crate::models::Package) -> String {
+    let mut lines: Vec<String> = pkg
+        .constraint_mismatches
+        .iter()
+        .map(|m| {
+            let project = std::path::Path::new(&m.project)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| m.project.clone());
+            format!(
+                "{} needs a newer version ({} {})",
+                project, pkg.name, m.constraint
+            )
+        })
+        .collect();
+    lines.push(match pkg.suggested_update() {
+        Some(latest) => format!("Suggested: update to {}", latest),
+        None => "No available update satisfies every project".to_string(),
+    });
+    lines.join("\n")
+}
+
+/// One window per command that appears to be blocked waiting for input
+fn show_pending_prompts(ctx: &egui::Context, app: &mut DepMgrApp) {
+    let prompts = pending_prompts();
+
+    // Forget typed answers for prompts that went away on their own
+    app.prompt_inputs
+        .retain(|id, _| prompts.iter().any(|p| p.id == *id));
+
+    for prompt in prompts {
+        egui::Window::new("Input required")
+            .id(egui::Id::new(("pending_prompt", prompt.id)))
+            .collapsible(false)
+            .resizable(true)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.label(
+                    egui::RichText::new(&prompt.command)
+                        .monospace()
+                        .color(egui::Color32::from_rgb(255, 165, 0)),
+                );
+                ui.label(format!(
+                    "Waiting for input for {}s:",
+                    prompt.detected_at.elapsed().as_secs()
+                ));
+                ui.separator();
+                ui.label(egui::RichText::new(&prompt.prompt).monospace());
+                ui.separator();
+
+                let input = app.prompt_inputs.entry(prompt.id).or_default();
+                let response = ui.add(
+                    egui::TextEdit::singleline(input)
+                        .password(prompt.is_secret())
+                        .hint_text("Response")
+                        .desired_width(f32::INFINITY),
+                );
+                let submitted =
+                    response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+                ui.horizontal(|ui| {
+                    if ui.button("Send").clicked() || submitted {
+                        let answer = app.prompt_inputs.remove(&prompt.id).unwrap_or_default();
+                        reply_to_prompt(prompt.id, PromptReply::Respond(answer));
+                    }
+                    if ui.button("Abort").clicked() {
+                        app.prompt_inputs.remove(&prompt.id);
+                        reply_to_prompt(prompt.id, PromptReply::Abort);
+                    }
+                });
+            });
+    }
+}
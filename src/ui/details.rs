@@ -0,0 +1,347 @@
+use crate::app::DepMgrApp;
+use crate::models::{Category, Package, PackageFlags, PackageManager};
+use crate::operations::supports_native_reinstall;
+use crate::ui::dashboard::constraint_warning;
+use crate::utils::broken::MISSING_VERSION;
+use crate::utils::format::{format_date, format_datetime, format_size};
+use eframe::egui;
+
+/// Everything known about the selected package: a right-hand panel, or its own
+/// window once popped out
+pub fn show_package_details(ctx: &egui::Context, app: &mut DepMgrApp) {
+    let Some(pkg) = app.detail_package() else {
+        return;
+    };
+    app.request_description(&pkg);
+
+    if app.details_detached {
+        let builder = egui::ViewportBuilder::default()
+            .with_title(format!("{} ({})", pkg.name, pkg.manager.name()))
+            .with_inner_size([360.0, 560.0]);
+        ctx.show_viewport_immediate(
+            egui::ViewportId::from_hash_of("package_details"),
+            builder,
+            |ctx, class| {
+                if class == egui::ViewportClass::Embedded {
+                    // No native windows on this backend
+                    egui::Window::new(&pkg.name)
+                        .id(egui::Id::new("package_details_window"))
+                        .show(ctx, |ui| show_details_body(ui, app, &pkg));
+                } else {
+                    egui::CentralPanel::default().show(ctx, |ui| show_details_body(ui, app, &pkg));
+                }
+                // Closing the window closes the details; the next one opens detached too
+                if ctx.input(|i| i.viewport().close_requested()) {
+                    app.selected_package = None;
+                }
+            },
+        );
+        return;
+    }
+
+    egui::SidePanel::right("package_details")
+        .resizable(true)
+        .default_width(280.0)
+        .show(ctx, |ui| show_details_body(ui, app, &pkg));
+}
+
+fn show_details_body(ui: &mut egui::Ui, app: &mut DepMgrApp, pkg: &Package) {
+    ui.horizontal(|ui| {
+        ui.heading(&pkg.name);
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui.small_button("✖").clicked() {
+                app.selected_package = None;
+            }
+            let (icon, hint) = if app.details_detached {
+                ("⊟", "Dock into the main window")
+            } else {
+                ("⬈", "Open in its own window")
+            };
+            if ui.small_button(icon).on_hover_text(hint).clicked() {
+                app.details_detached = !app.details_detached;
+            }
+            if ui
+                .small_button("📋")
+                .on_hover_text("Copy a depmgr:// link to this package")
+                .clicked()
+            {
+                ui.ctx()
+                    .copy_text(crate::deep_link::DeepLink::to_package(pkg));
+            }
+        });
+    });
+    ui.label(pkg.manager.name());
+    if let Some(desc) = &pkg.description {
+        ui.label(desc);
+    }
+    if let Some(url) = pkg.registry_url() {
+        ui.hyperlink_to("Registry page", url);
+    }
+    ui.separator();
+
+    egui::Grid::new("package_details_grid")
+        .num_columns(2)
+        .spacing([12.0, 4.0])
+        .show(ui, |ui| {
+            ui.strong("Installed");
+            ui.label(&pkg.installed_version);
+            ui.end_row();
+            ui.strong("Latest");
+            ui.label(pkg.latest_version.as_deref().unwrap_or("-"));
+            ui.end_row();
+            ui.strong("Size");
+            ui.label(
+                pkg.size
+                    .map(|s| format_size(s, app.size_units))
+                    .unwrap_or_else(|| "-".to_string()),
+            );
+            ui.end_row();
+            if let Some(arch) = pkg.arch {
+                ui.strong("Architecture");
+                ui.label(arch.label());
+                ui.end_row();
+            }
+            if let Some(download) = pkg.download_size {
+                ui.strong("Download");
+                ui.label(format_size(download, app.size_units));
+                ui.end_row();
+            }
+            if !pkg.build_dependencies.is_empty() {
+                ui.strong("Build deps");
+                ui.label(pkg.build_dependencies.join(", "))
+                    .on_hover_text("Only needed when building from source");
+                ui.end_row();
+            }
+            if !pkg.conflicts.is_empty() {
+                ui.strong("Conflicts");
+                ui.label(pkg.conflicts.join(", "));
+                ui.end_row();
+            }
+        });
+
+    ui.horizontal(|ui| {
+        let favorite = app.package_flags.is_favorite(pkg);
+        if ui
+            .selectable_label(favorite, "⭐ Favorite")
+            .on_hover_text("Always list this at the top of the table")
+            .clicked()
+        {
+            app.package_flags.toggle_favorite(pkg);
+        }
+        let pinned = app.package_flags.is_pinned(pkg);
+        if ui
+            .selectable_label(pinned, "📌 Pin")
+            .on_hover_text("Keep this version; batch updates skip it (p)")
+            .clicked()
+        {
+            app.toggle_pinned(pkg);
+        }
+        let ignored = app.package_flags.is_ignored(pkg);
+        if ui
+            .selectable_label(ignored, "🙈 Ignore")
+            .on_hover_text("Stop counting this as outdated (i)")
+            .clicked()
+        {
+            app.package_flags.toggle_ignored(pkg);
+        }
+        let critical = app.package_flags.is_critical(pkg);
+        if ui
+            .selectable_label(critical, "🛡 Critical")
+            .on_hover_text("Leave out of Update All; type its name to update or remove it")
+            .clicked()
+        {
+            app.package_flags.toggle_critical(pkg);
+        }
+    });
+
+    ui.horizontal(|ui| {
+        ui.strong("Category");
+        let current = app.category_overrides.category_of(pkg);
+        let mut choice = current;
+        egui::ComboBox::from_id_salt("package_category")
+            .selected_text(current.label())
+            .show_ui(ui, |ui| {
+                for category in Category::ALL {
+                    ui.selectable_value(&mut choice, category, category.label());
+                }
+            });
+        if choice != current {
+            app.category_overrides.set(pkg, choice);
+        }
+        if app.category_overrides.is_overridden(pkg) {
+            ui.label(egui::RichText::new("set by you").small().weak());
+        }
+    });
+
+    let channels = PackageFlags::channels_for(&pkg.manager);
+    if !channels.is_empty() {
+        ui.horizontal(|ui| {
+            ui.strong("Channel");
+            let current = app.package_flags.channel(pkg).map(String::from);
+            let mut choice = current.clone();
+            egui::ComboBox::from_id_salt("package_channel")
+                .selected_text(current.as_deref().unwrap_or("stable"))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut choice, None, "stable");
+                    // Keep a tag set by hand (or synced from another machine) selectable
+                    let custom = current.as_deref().filter(|c| !channels.contains(c));
+                    for channel in channels.iter().copied().chain(custom) {
+                        ui.selectable_value(&mut choice, Some(channel.to_string()), channel);
+                    }
+                })
+                .response
+                .on_hover_text("Which releases the Latest column and updates follow");
+            if choice != current {
+                app.set_channel(pkg, choice);
+            }
+        });
+    }
+
+    if !pkg.vulnerabilities.is_empty() {
+        ui.separator();
+        ui.colored_label(
+            egui::Color32::from_rgb(255, 0, 0),
+            format!("{} known vulnerabilities", pkg.vulnerabilities.len()),
+        );
+        for id in &pkg.vulnerabilities {
+            ui.hyperlink_to(id, format!("https://osv.dev/vulnerability/{}", id));
+        }
+    }
+
+    if !pkg.constraint_mismatches.is_empty() {
+        ui.separator();
+        ui.colored_label(
+            egui::Color32::from_rgb(255, 90, 0),
+            format!("⚠ {}", constraint_warning(pkg)),
+        );
+        if pkg.suggested_update().is_some()
+            && !app.is_updating(&pkg.name)
+            && ui.button("⬆️ Update").clicked()
+        {
+            app.update_package(pkg.name.clone(), pkg.manager.clone());
+        }
+    }
+
+    if !pkg.broken.is_empty() {
+        ui.separator();
+        ui.colored_label(egui::Color32::from_rgb(255, 0, 0), "Broken binaries");
+        for broken in &pkg.broken {
+            ui.label(egui::RichText::new(broken.describe()).monospace().small());
+        }
+        if !app.is_updating(&pkg.name) {
+            let missing = pkg.installed_version == MISSING_VERSION;
+            ui.horizontal(|ui| {
+                if pkg.manager == PackageManager::Homebrew
+                    && !missing
+                    && ui
+                        .button("Relink")
+                        .on_hover_text("brew link --overwrite")
+                        .clicked()
+                {
+                    app.relink_package(pkg);
+                }
+                if ui.button("Reinstall").clicked() {
+                    app.reinstall_package(pkg.name.clone(), pkg.manager.clone());
+                }
+                if ui
+                    .button("Forget")
+                    .on_hover_text(if pkg.manager == PackageManager::Cargo {
+                        "Drop the crate from cargo's install records"
+                    } else {
+                        "Delete the dangling symlinks"
+                    })
+                    .clicked()
+                {
+                    app.forget_broken(pkg);
+                }
+            });
+        }
+    }
+
+    if pkg.auto_updates {
+        ui.separator();
+        ui.label("Updates itself");
+        ui.weak(crate::ui::dashboard::AUTO_UPDATES_NOTE);
+    }
+
+    if pkg.needs_rosetta {
+        ui.separator();
+        ui.colored_label(
+            egui::Color32::from_rgb(255, 165, 0),
+            "Intel-only: runs under Rosetta on this Mac",
+        );
+        if supports_native_reinstall(pkg)
+            && !app.is_updating(&pkg.name)
+            && ui.button("Reinstall as native").clicked()
+        {
+            app.reinstall_native(pkg.name.clone(), pkg.manager.clone());
+        }
+    }
+
+    let conflicts = app.conflicts_of(pkg);
+    if !conflicts.is_empty() {
+        ui.separator();
+        ui.colored_label(
+            egui::Color32::from_rgb(255, 90, 0),
+            "⚠ Conflicts with installed packages",
+        );
+        for conflict in &conflicts {
+            ui.label(format!("{} ({})", conflict.name, conflict.manager.name()))
+                .on_hover_text(&conflict.reason);
+        }
+    }
+
+    if !pkg.stale_hooks.is_empty() {
+        ui.separator();
+        ui.colored_label(
+            egui::Color32::from_rgb(255, 165, 0),
+            "pre-commit hooks pinned to old revisions",
+        );
+        for hook in &pkg.stale_hooks {
+            ui.label(format!("{} @ {}", hook.project, hook.rev));
+        }
+    }
+
+    let history = app.package_history(pkg);
+    if !history.is_empty() {
+        ui.separator();
+        egui::CollapsingHeader::new(format!("History ({})", history.len()))
+            .id_salt("package_history")
+            .show(ui, |ui| {
+                for entry in &history {
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            egui::RichText::new(format_date(entry.timestamp))
+                                .monospace()
+                                .weak(),
+                        )
+                        .on_hover_text(format_datetime(entry.timestamp));
+                        let text = egui::RichText::new(entry.describe());
+                        let label = ui.label(if entry.success { text } else { text.weak() });
+                        if let Some(message) = &entry.message {
+                            label.on_hover_text(message);
+                        }
+                    });
+                }
+            });
+    }
+
+    ui.separator();
+    match pkg.usage_kind() {
+        Some(kind) => ui.strong(format!("Used ({})", kind)),
+        None if pkg.used_in.is_empty() => ui.strong("Unused"),
+        None => ui.strong("Used"),
+    };
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        for path in &pkg.used_in {
+            let dev_only = pkg.dev_only_in.contains(path);
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(pkg.usage_label(path)).monospace())
+                    .on_hover_text(path);
+                if dev_only {
+                    ui.label(egui::RichText::new("dev").small().weak());
+                }
+            });
+        }
+    });
+}
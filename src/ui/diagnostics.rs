@@ -0,0 +1,111 @@
+use crate::app::DepMgrApp;
+use crate::managers::health::ManagerHealth;
+use crate::models::PackageManager;
+use crate::utils::format::{format_datetime, format_number, format_relative};
+use eframe::egui;
+
+/// Compact status chip for a manager: version, colored by health. Returns true when clicked.
+pub fn manager_chip(ui: &mut egui::Ui, health: &ManagerHealth) -> bool {
+    let (dot_color, tooltip) = if let Some(error) = &health.last_error {
+        (
+            egui::Color32::from_rgb(255, 0, 0),
+            format!("Last scan failed: {}", error),
+        )
+    } else if let Some(latest) = &health.self_outdated {
+        (
+            egui::Color32::from_rgb(255, 165, 0),
+            format!("Outdated, {} is available", latest),
+        )
+    } else if health.last_scan.is_some() {
+        (egui::Color32::from_rgb(0, 200, 0), "Healthy".to_string())
+    } else {
+        (egui::Color32::GRAY, "Not scanned yet".to_string())
+    };
+
+    let scanned = match health.last_scan {
+        Some(when) => format!("\nLast scan: {}", format_relative(when)),
+        None => String::new(),
+    };
+
+    let text = egui::RichText::new(format!("⏺ {}", health.version.as_deref().unwrap_or("?")))
+        .small()
+        .color(dot_color);
+
+    ui.add(egui::Label::new(text).sense(egui::Sense::click()))
+        .on_hover_text(format!("{}{}\nClick for diagnostics", tooltip, scanned))
+        .clicked()
+}
+
+/// Details for one manager: where the binary lives, what it reports, how the last scan went
+pub fn show_manager_diagnostics(ctx: &egui::Context, app: &mut DepMgrApp) {
+    let Some(manager) = app.diagnostics_manager.clone() else {
+        return;
+    };
+    let health = app.manager_health(&manager);
+    let mut open = true;
+
+    egui::Window::new(format!("{} diagnostics", manager.name()))
+        .id(egui::Id::new("manager_diagnostics"))
+        .open(&mut open)
+        .resizable(true)
+        .default_width(420.0)
+        .show(ctx, |ui| {
+            egui::Grid::new("manager_diagnostics_grid")
+                .num_columns(2)
+                .spacing([12.0, 6.0])
+                .show(ui, |ui| {
+                    row(ui, "Command", manager.command());
+                    row(
+                        ui,
+                        "Binary",
+                        health.binary_path.as_deref().unwrap_or("not found on PATH"),
+                    );
+                    row(
+                        ui,
+                        "Version",
+                        health.version_output.as_deref().unwrap_or("unknown"),
+                    );
+                    if let Some(latest) = &health.self_outdated {
+                        row(ui, "Latest version", latest);
+                    }
+                    row(
+                        ui,
+                        "Last scan",
+                        &health
+                            .last_scan
+                            .map(|t| format!("{} ({})", format_datetime(t), format_relative(t)))
+                            .unwrap_or_else(|| "never".to_string()),
+                    );
+                    row(ui, "Packages", &format_number(health.package_count as u64));
+                });
+
+            if let Some(error) = &health.last_error {
+                ui.separator();
+                ui.colored_label(egui::Color32::from_rgb(255, 0, 0), "Last error:");
+                ui.label(egui::RichText::new(error).monospace());
+            }
+
+            ui.separator();
+            if ui.button("🔄 Rescan").clicked() {
+                app.request_refresh();
+            }
+        });
+
+    if !open {
+        app.diagnostics_manager = None;
+    }
+}
+
+fn row(ui: &mut egui::Ui, label: &str, value: &str) {
+    ui.strong(label);
+    ui.label(value);
+    ui.end_row();
+}
+
+/// Keeps the sidebar code readable: chip + click-through for one manager
+pub fn manager_chip_for(ui: &mut egui::Ui, app: &mut DepMgrApp, manager: &PackageManager) {
+    let health = app.manager_health(manager);
+    if manager_chip(ui, &health) {
+        app.diagnostics_manager = Some(manager.clone());
+    }
+}
@@ -0,0 +1,247 @@
+use crate::app::DepMgrApp;
+use crate::models::{Package, PackageManager, Project};
+use crate::scanner::bootstrap::PlanStep;
+use crate::scanner::{install_plan, plan_script, PlanAction};
+use crate::ui::projects::show_project;
+use eframe::egui;
+
+/// Folders dropped anywhere on the window are scanned on their own; files are ignored
+pub fn handle_dropped_folders(ctx: &egui::Context, app: &mut DepMgrApp) {
+    let hovering = ctx.input(|i| !i.raw.hovered_files.is_empty());
+    if hovering {
+        let screen = ctx.content_rect();
+        let painter = ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Foreground,
+            egui::Id::new("folder_drop"),
+        ));
+        painter.rect_filled(screen, 0.0, egui::Color32::from_black_alpha(160));
+        painter.text(
+            screen.center(),
+            egui::Align2::CENTER_CENTER,
+            "Drop a project folder to scan it",
+            egui::FontId::proportional(24.0),
+            egui::Color32::WHITE,
+        );
+    }
+
+    let dropped = ctx.input(|i| {
+        i.raw
+            .dropped_files
+            .iter()
+            .filter_map(|f| f.path.clone())
+            .find(|p| p.is_dir())
+    });
+    if let Some(path) = dropped {
+        app.scan_folder(path);
+    }
+}
+
+/// Sidebar entry for typing or pasting a folder path instead of dropping it
+pub fn show_scan_folder_input(ui: &mut egui::Ui, app: &mut DepMgrApp) {
+    ui.collapsing("📂 Scan folder…", |ui| {
+        ui.add(
+            egui::TextEdit::singleline(&mut app.folder_input)
+                .hint_text("~/src/my-app, or drop a folder on the window"),
+        );
+        if ui.button("Scan").clicked() {
+            let input = std::path::Path::new(app.folder_input.trim());
+            let path = crate::utils::paths::expand_home(input);
+            app.scan_folder(path);
+        }
+    });
+}
+
+/// Projects in the last scanned folder: the installed packages they use and their
+/// outdated dependencies, with the option to keep scanning the folder
+pub fn show_folder_scan_window(ctx: &egui::Context, app: &mut DepMgrApp) {
+    let guard = app.folder_scan.blocking_read();
+    let Some(scan) = guard.as_ref() else {
+        return;
+    };
+    let path = scan.path.clone();
+    let title = format!(
+        "📂 {}",
+        path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string())
+    );
+    let packages = app.packages.blocking_read();
+    let venvs_enabled = app.settings.scan.project_venvs;
+    let added = crate::scanner::added_scan_roots().contains(&path);
+    let covered = !added
+        && crate::scanner::get_scan_directories()
+            .iter()
+            .any(|root| path.starts_with(root));
+
+    let mut open = true;
+    let mut keep: Option<bool> = None;
+    let mut installs: Vec<PlanStep> = Vec::new();
+    egui::Window::new(title)
+        .id(egui::Id::new("folder_scan"))
+        .open(&mut open)
+        .default_size([520.0, 420.0])
+        .show(ctx, |ui| {
+            ui.label(egui::RichText::new(path.display().to_string()).weak());
+            ui.horizontal(|ui| {
+                if covered {
+                    ui.label(
+                        egui::RichText::new("Already inside a scan directory")
+                            .small()
+                            .weak(),
+                    );
+                } else if added {
+                    ui.label(egui::RichText::new("✔ Scanned on every refresh").small());
+                    if ui.small_button("Stop scanning").clicked() {
+                        keep = Some(false);
+                    }
+                } else if ui
+                    .button("➕ Add to scan directories")
+                    .on_hover_text("Include this folder's projects in every refresh")
+                    .clicked()
+                {
+                    keep = Some(true);
+                }
+            });
+            ui.separator();
+
+            let Some(projects) = &scan.projects else {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Reading manifests...");
+                });
+                return;
+            };
+            if projects.is_empty() {
+                ui.label("No project manifests found in this folder");
+                return;
+            }
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for project in projects {
+                    show_project(ui, project, &packages, venvs_enabled);
+                    show_setup_plan(ui, project, &packages, &mut installs);
+                    let used = crate::scanner::installed_dependencies(project, &packages);
+                    if !used.is_empty() {
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(format!("Uses {} installed packages:", used.len()));
+                            for pkg in used {
+                                let text = egui::RichText::new(&pkg.name);
+                                if pkg.is_outdated {
+                                    ui.label(text.color(egui::Color32::from_rgb(255, 165, 0)))
+                                        .on_hover_text(format!(
+                                            "{} installed, {} available",
+                                            pkg.installed_version,
+                                            pkg.latest_version.as_deref().unwrap_or("?")
+                                        ));
+                                } else {
+                                    ui.label(text);
+                                }
+                            }
+                        });
+                    }
+                    ui.separator();
+                }
+            });
+        });
+    drop(packages);
+    drop(guard);
+
+    for step in installs {
+        let constraint = step.constraint();
+        match (&step.action, step.formula.clone(), step.versioned_formula()) {
+            (PlanAction::Upgrade { package, .. }, Some(formula), None) if *package == formula => {
+                app.update_package(formula, PackageManager::Homebrew);
+            }
+            (_, Some(formula), _) => app.install_missing_tool(step.tool, formula, constraint),
+            (_, None, _) => {}
+        }
+    }
+    match keep {
+        Some(true) => crate::scanner::add_scan_root(&path),
+        Some(false) => crate::scanner::remove_scan_root(&path),
+        None => {}
+    }
+    if !open {
+        *app.folder_scan.blocking_write() = None;
+    }
+}
+
+/// Tools the project's mise/devcontainer/flake/README ask for against what's installed,
+/// with installs for the gaps
+fn show_setup_plan(
+    ui: &mut egui::Ui,
+    project: &Project,
+    packages: &[Package],
+    installs: &mut Vec<PlanStep>,
+) {
+    let plan = install_plan(project, packages);
+    if plan.is_empty() {
+        return;
+    }
+    let gaps: Vec<&PlanStep> = plan.iter().filter(|s| s.command().is_some()).collect();
+    let title = format!(
+        "🛠 Setup · {} tools, {} to install or upgrade",
+        plan.len(),
+        gaps.len()
+    );
+    egui::CollapsingHeader::new(title)
+        .id_salt(("setup", &project.path))
+        .default_open(!gaps.is_empty())
+        .show(ui, |ui| {
+            egui::Grid::new(("setup", &project.path))
+                .striped(true)
+                .show(ui, |ui| {
+                    for step in &plan {
+                        ui.label(&step.tool);
+                        ui.label(step.version.as_deref().unwrap_or("any"));
+                        ui.label(egui::RichText::new(&step.source).weak());
+                        match &step.action {
+                            PlanAction::Satisfied { installed } => {
+                                ui.label(format!("✔ {}", installed));
+                            }
+                            PlanAction::Upgrade { installed, .. } => {
+                                ui.colored_label(
+                                    egui::Color32::from_rgb(255, 165, 0),
+                                    format!("{} installed", installed),
+                                );
+                            }
+                            PlanAction::Install => {
+                                ui.colored_label(
+                                    egui::Color32::from_rgb(255, 90, 0),
+                                    "not installed",
+                                );
+                            }
+                        }
+                        match step.command() {
+                            Some(command) if step.formula.is_some() => {
+                                if ui.small_button("Run").on_hover_text(command).clicked() {
+                                    installs.push(step.clone());
+                                }
+                            }
+                            _ => {
+                                ui.label("");
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+            if !gaps.is_empty() {
+                ui.horizontal(|ui| {
+                    if ui.button("⬇ Run all").clicked() {
+                        installs.extend(
+                            gaps.iter()
+                                .filter(|s| s.formula.is_some())
+                                .map(|s| (*s).clone()),
+                        );
+                    }
+                    if ui
+                        .button("📋 Copy as script")
+                        .on_hover_text("brew commands for every gap")
+                        .clicked()
+                    {
+                        ui.ctx().copy_text(plan_script(&plan));
+                    }
+                });
+            }
+        });
+}
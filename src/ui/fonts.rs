@@ -0,0 +1,94 @@
+use crate::app::DepMgrApp;
+use crate::models::{Package, PackageManager};
+use eframe::egui;
+
+/// Font family for rendering a font cask's name in itself. Fonts are loaded lazily
+/// the first time their row is drawn; egui only picks them up on the next pass.
+fn preview_family(
+    ctx: &egui::Context,
+    app: &mut DepMgrApp,
+    pkg: &Package,
+) -> Option<egui::FontFamily> {
+    let file = pkg.preview_font.as_ref()?;
+    let family = egui::FontFamily::Name(pkg.name.clone().into());
+
+    if ctx.fonts(|f| f.definitions().families.contains_key(&family)) {
+        return Some(family);
+    }
+    if app.loaded_fonts.insert(pkg.name.clone()) {
+        match std::fs::read(file) {
+            Ok(bytes) => {
+                ctx.add_font(egui::epaint::text::FontInsert::new(
+                    &pkg.name,
+                    egui::FontData::from_owned(bytes),
+                    vec![egui::epaint::text::InsertFontFamily {
+                        family,
+                        priority: egui::epaint::text::FontPriority::Highest,
+                    }],
+                ));
+                ctx.request_repaint();
+            }
+            Err(e) => eprintln!("[FONTS] Can't read {}: {}", file, e),
+        }
+    }
+    None
+}
+
+/// Name cell for font casks: a checkbox for batch removal and the name in its own typeface
+pub fn font_name_cell(ui: &mut egui::Ui, app: &mut DepMgrApp, pkg: &Package) {
+    let mut checked = app.font_selection.contains(&pkg.name);
+    if ui.checkbox(&mut checked, "").changed() {
+        if checked {
+            app.font_selection.insert(pkg.name.clone());
+        } else {
+            app.font_selection.remove(&pkg.name);
+        }
+    }
+
+    let display = pkg.name.trim_start_matches("font-");
+    let mut text = egui::RichText::new(display);
+    if let Some(family) = preview_family(ui.ctx(), app, pkg) {
+        text = text.family(family);
+    }
+    let selected = app.is_selected(pkg);
+    if ui
+        .selectable_label(selected, text)
+        .on_hover_text(&pkg.name)
+        .clicked()
+    {
+        app.select_package(pkg);
+    }
+}
+
+/// Batch actions under the Fonts entry in the sidebar
+pub fn show_font_actions(ui: &mut egui::Ui, app: &mut DepMgrApp) {
+    let outdated = app
+        .packages
+        .blocking_read()
+        .iter()
+        .filter(|p| {
+            p.manager == PackageManager::Fonts && app.package_flags.can_update_unattended(p)
+        })
+        .count();
+    let selected = app.font_selection.len();
+    if outdated == 0 && selected == 0 {
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        if outdated > 0
+            && ui
+                .small_button(format!("⬆️ Update fonts ({})", outdated))
+                .clicked()
+        {
+            app.update_fonts();
+        }
+        if selected > 0
+            && ui
+                .small_button(format!("🗑 Remove selected ({})", selected))
+                .clicked()
+        {
+            app.remove_selected_fonts();
+        }
+    });
+}
@@ -0,0 +1,91 @@
+use crate::app::DepMgrApp;
+use crate::utils::activity;
+use eframe::egui;
+
+/// Every command DepMgr ran, newest at the bottom; a floating window that can be
+/// popped out into its own OS window and left open beside the table
+pub fn show_log_window(ctx: &egui::Context, app: &mut DepMgrApp) {
+    if !app.log_open {
+        return;
+    }
+
+    if !app.log_detached {
+        let mut open = true;
+        egui::Window::new("📜 Command log")
+            .open(&mut open)
+            .default_size([560.0, 320.0])
+            .show(ctx, |ui| show_log_body(ui, app));
+        app.log_open &= open;
+        return;
+    }
+
+    let builder = egui::ViewportBuilder::default()
+        .with_title("DepMgr command log")
+        .with_inner_size([640.0, 400.0]);
+    ctx.show_viewport_immediate(
+        egui::ViewportId::from_hash_of("command_log"),
+        builder,
+        |ctx, class| {
+            if class == egui::ViewportClass::Embedded {
+                egui::Window::new("📜 Command log").show(ctx, |ui| show_log_body(ui, app));
+            } else {
+                egui::CentralPanel::default().show(ctx, |ui| show_log_body(ui, app));
+            }
+            if ctx.input(|i| i.viewport().close_requested()) {
+                app.log_open = false;
+            }
+            // New commands show up without waiting for input in this window
+            ctx.request_repaint_after(std::time::Duration::from_secs(1));
+        },
+    );
+}
+
+fn show_log_body(ui: &mut egui::Ui, app: &mut DepMgrApp) {
+    let entries = activity::log_entries();
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(format!("{} commands", entries.len())).weak());
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            let (icon, hint) = if app.log_detached {
+                ("⊟", "Dock into the main window")
+            } else {
+                ("⬈", "Open in its own window")
+            };
+            if ui.small_button(icon).on_hover_text(hint).clicked() {
+                app.log_detached = !app.log_detached;
+            }
+            if ui.small_button("Clear").clicked() {
+                activity::clear_log();
+            }
+        });
+    });
+    ui.separator();
+
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .stick_to_bottom(true)
+        .show(ui, |ui| {
+            for entry in &entries {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(
+                        egui::RichText::new(
+                            entry
+                                .at
+                                .with_timezone(&chrono::Local)
+                                .format("%H:%M:%S")
+                                .to_string(),
+                        )
+                        .monospace()
+                        .weak(),
+                    );
+                    ui.label(egui::RichText::new(format!("$ {}", entry.command)).monospace());
+                });
+                if let Some(outcome) = &entry.outcome {
+                    ui.label(
+                        egui::RichText::new(format!("  {}", outcome))
+                            .monospace()
+                            .color(egui::Color32::from_rgb(255, 0, 0)),
+                    );
+                }
+            }
+        });
+}
@@ -1,3 +1,24 @@
+pub mod conflicts;
+pub mod critical;
 pub mod dashboard;
+pub mod details;
+pub mod diagnostics;
+pub mod folder_scan;
+pub mod fonts;
+pub mod log;
+pub mod palette;
+pub mod path_report;
+pub mod projects;
+pub mod recent;
+pub mod repo_audit;
+pub mod running;
+pub mod runtime_tools;
+pub mod scan_report;
+pub mod settings;
+pub mod status_bar;
+pub mod triage;
+pub mod update_everything;
+pub mod weekly;
+mod window;
 
 pub use dashboard::show_dashboard;
@@ -0,0 +1,256 @@
+use crate::app::{DepMgrApp, View};
+use crate::models::PackageManager;
+use eframe::egui;
+
+#[derive(Debug, Clone)]
+enum PaletteAction {
+    Refresh,
+    DeepScan,
+    RefreshMetadata,
+    ClearCache,
+    UpdateAll,
+    UpdateEverything,
+    Triage,
+    PathReport,
+    CommandLog,
+    UpdatePackage(PackageManager, String),
+    ShowDetails(PackageManager, String),
+    ToggleOutdatedOnly,
+    ToggleOrphanedOnly,
+    OnlyManager(PackageManager),
+    AllManagers,
+    GoTo(View),
+    CopyMarkdown,
+    SaveReport,
+    OpenSettings,
+    EditConfigFile,
+    SyncPull,
+    SyncPush,
+}
+
+/// Everything the palette can do right now, labelled the way users search for it
+fn commands(app: &DepMgrApp) -> Vec<(String, PaletteAction)> {
+    let mut commands = vec![
+        ("Refresh packages".to_string(), PaletteAction::Refresh),
+        (
+            "Deep scan (ignore the scan budget)".to_string(),
+            PaletteAction::DeepScan,
+        ),
+        (
+            "Refresh metadata (brew update)".to_string(),
+            PaletteAction::RefreshMetadata,
+        ),
+        ("Clear cache".to_string(), PaletteAction::ClearCache),
+        ("Update all outdated".to_string(), PaletteAction::UpdateAll),
+        (
+            "Triage outdated packages".to_string(),
+            PaletteAction::Triage,
+        ),
+        (
+            "Analyze PATH shadowing".to_string(),
+            PaletteAction::PathReport,
+        ),
+        ("Show command log".to_string(), PaletteAction::CommandLog),
+        (
+            "Update everything".to_string(),
+            PaletteAction::UpdateEverything,
+        ),
+        (
+            "View: toggle outdated only".to_string(),
+            PaletteAction::ToggleOutdatedOnly,
+        ),
+        (
+            "View: toggle orphaned only".to_string(),
+            PaletteAction::ToggleOrphanedOnly,
+        ),
+        ("View: all managers".to_string(), PaletteAction::AllManagers),
+        (
+            "Export: copy as Markdown".to_string(),
+            PaletteAction::CopyMarkdown,
+        ),
+        ("Export: save report".to_string(), PaletteAction::SaveReport),
+        ("Open settings".to_string(), PaletteAction::OpenSettings),
+        (
+            "Edit config.toml".to_string(),
+            PaletteAction::EditConfigFile,
+        ),
+    ];
+
+    if app.settings.sync.is_configured() {
+        commands.push(("Sync: pull metadata".to_string(), PaletteAction::SyncPull));
+        commands.push(("Sync: push metadata".to_string(), PaletteAction::SyncPush));
+    }
+
+    for view in [
+        View::All,
+        View::Projects,
+        View::Vulnerable,
+        View::Unused,
+        View::Pinned,
+        View::History,
+        View::Recent,
+        View::ScanReport,
+    ] {
+        commands.push((
+            format!("Go to: {}", view.label()),
+            PaletteAction::GoTo(view),
+        ));
+    }
+
+    for manager in &app.available_managers {
+        commands.push((
+            format!("View: only {}", manager.name()),
+            PaletteAction::OnlyManager(manager.clone()),
+        ));
+    }
+
+    for pkg in app.packages.blocking_read().iter() {
+        if app.package_flags.can_update(pkg) {
+            commands.push((
+                format!("Update {} ({})", pkg.name, pkg.manager.name()),
+                PaletteAction::UpdatePackage(pkg.manager.clone(), pkg.name.clone()),
+            ));
+        }
+        commands.push((
+            format!("Show {} ({})", pkg.name, pkg.manager.name()),
+            PaletteAction::ShowDetails(pkg.manager.clone(), pkg.name.clone()),
+        ));
+    }
+
+    commands
+}
+
+/// Subsequence match: every query character must appear in order. Consecutive
+/// matches and matches at word starts score higher, shorter labels win ties.
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut previous: Option<usize> = None;
+
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let idx = pos + text[pos..].iter().position(|&c| c == q)?;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == idx) {
+            score += 5;
+        }
+        if idx == 0 || !text[idx - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(idx);
+        pos = idx + 1;
+    }
+    Some(score * 100 - text.len() as i32)
+}
+
+fn run(ctx: &egui::Context, app: &mut DepMgrApp, action: PaletteAction) {
+    match action {
+        PaletteAction::Refresh => app.request_refresh(),
+        PaletteAction::DeepScan => app.request_deep_scan(),
+        PaletteAction::RefreshMetadata => app.refresh_metadata_now(),
+        PaletteAction::ClearCache => app.clear_cache(),
+        PaletteAction::UpdateAll => app.update_all_outdated(),
+        PaletteAction::UpdateEverything => app.open_update_everything(),
+        PaletteAction::Triage => app.open_triage(),
+        PaletteAction::PathReport => app.open_path_report(),
+        PaletteAction::CommandLog => app.log_open = true,
+        PaletteAction::SyncPull => app.sync_metadata(false),
+        PaletteAction::SyncPush => app.sync_metadata(true),
+        PaletteAction::UpdatePackage(manager, name) => app.update_package(name, manager),
+        PaletteAction::ShowDetails(manager, name) => {
+            app.selected_package = Some((manager, name));
+        }
+        PaletteAction::ToggleOutdatedOnly => app.show_outdated_only = !app.show_outdated_only,
+        PaletteAction::ToggleOrphanedOnly => app.show_orphaned_only = !app.show_orphaned_only,
+        PaletteAction::OnlyManager(manager) => {
+            app.selected_managers = std::iter::once(manager).collect();
+        }
+        PaletteAction::AllManagers => {
+            app.selected_managers = app.available_managers.iter().cloned().collect();
+        }
+        PaletteAction::GoTo(view) => app.view = view,
+        PaletteAction::CopyMarkdown => ctx.copy_text(app.filtered_markdown_report()),
+        PaletteAction::SaveReport => app.save_markdown_report(),
+        PaletteAction::OpenSettings => app.open_settings(),
+        PaletteAction::EditConfigFile => app.open_settings_file(),
+    }
+}
+
+/// Cmd+K (Ctrl+K elsewhere) palette: type to filter, arrows to pick, Enter to run
+pub fn show_command_palette(ctx: &egui::Context, app: &mut DepMgrApp) {
+    if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::K)) {
+        app.palette_open = !app.palette_open;
+        app.palette_query.clear();
+        app.palette_index = 0;
+    }
+    if !app.palette_open {
+        return;
+    }
+
+    let mut matches: Vec<(i32, String, PaletteAction)> = commands(app)
+        .into_iter()
+        .filter_map(|(label, action)| {
+            fuzzy_score(&app.palette_query, &label).map(|score| (score, label, action))
+        })
+        .collect();
+    matches.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    matches.truncate(12);
+
+    let (up, down, enter, escape) = ctx.input_mut(|i| {
+        (
+            i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+            i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+            i.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+            i.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+        )
+    });
+    if escape {
+        app.palette_open = false;
+        return;
+    }
+    if down {
+        app.palette_index = (app.palette_index + 1).min(matches.len().saturating_sub(1));
+    }
+    if up {
+        app.palette_index = app.palette_index.saturating_sub(1);
+    }
+    app.palette_index = app.palette_index.min(matches.len().saturating_sub(1));
+
+    let mut chosen = enter
+        .then(|| matches.get(app.palette_index).map(|m| m.2.clone()))
+        .flatten();
+
+    egui::Window::new("Command Palette")
+        .title_bar(false)
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 80.0])
+        .fixed_size([420.0, 0.0])
+        .show(ctx, |ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut app.palette_query)
+                    .hint_text("Type a command or package…")
+                    .desired_width(f32::INFINITY),
+            );
+            response.request_focus();
+            if response.changed() {
+                app.palette_index = 0;
+            }
+            ui.separator();
+
+            if matches.is_empty() {
+                ui.label(egui::RichText::new("No matching commands").weak());
+            }
+            for (idx, (_, label, action)) in matches.iter().enumerate() {
+                if ui
+                    .selectable_label(idx == app.palette_index, label)
+                    .clicked()
+                {
+                    chosen = Some(action.clone());
+                }
+            }
+        });
+
+    if let Some(action) = chosen {
+        app.palette_open = false;
+        run(ctx, app, action);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::app::DepMgrApp;
+use eframe::egui;
+
+/// Commands found in several PATH directories: which copy runs, what it hides, and how
+/// to fix the ones where an older copy wins
+pub fn show_path_report_window(ctx: &egui::Context, app: &mut DepMgrApp) {
+    if !app.show_path_report {
+        return;
+    }
+
+    let mut open = true;
+    let mut reanalyze = false;
+    egui::Window::new("PATH shadowing")
+        .open(&mut open)
+        .resizable(true)
+        .default_width(620.0)
+        .default_height(420.0)
+        .show(ctx, |ui| {
+            let report = app.path_report.blocking_read();
+            let Some(report) = report.as_ref() else {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Analyzing PATH...");
+                });
+                ctx.request_repaint();
+                return;
+            };
+
+            ui.horizontal(|ui| {
+                let stale = report.iter().filter(|s| s.stale).count();
+                ui.label(format!(
+                    "{} commands on PATH more than once, {} where an older copy wins",
+                    report.len(),
+                    stale
+                ));
+                if ui.button("🔄 Re-analyze").clicked() {
+                    reanalyze = true;
+                }
+            });
+            ui.label(
+                egui::RichText::new("Using the PATH your login shell sets up")
+                    .small()
+                    .weak(),
+            );
+            ui.separator();
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for shadowing in report {
+                    ui.horizontal(|ui| {
+                        if shadowing.stale {
+                            ui.colored_label(egui::Color32::from_rgb(255, 165, 0), "⚠");
+                        }
+                        ui.strong(&shadowing.command);
+                    });
+                    ui.label(format!("runs:   {}", shadowing.winner.describe()));
+                    for hidden in &shadowing.hidden {
+                        ui.label(
+                            egui::RichText::new(format!("hidden: {}", hidden.describe())).weak(),
+                        );
+                    }
+                    if let Some(suggestion) = &shadowing.suggestion {
+                        ui.horizontal_wrapped(|ui| {
+                            ui.colored_label(egui::Color32::from_rgb(255, 165, 0), suggestion);
+                            if ui.small_button("📋").on_hover_text("Copy").clicked() {
+                                ctx.copy_text(suggestion.clone());
+                            }
+                        });
+                    }
+                    ui.separator();
+                }
+            });
+        });
+
+    if reanalyze {
+        app.open_path_report();
+    }
+    if !open {
+        app.show_path_report = false;
+    }
+}
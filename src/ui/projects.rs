@@ -0,0 +1,259 @@
+use crate::app::DepMgrApp;
+use crate::models::{Package, Project, Venv};
+use crate::report::toolchain::{project_tools, render_brewfile, render_mise_toml};
+use eframe::egui;
+use std::path::PathBuf;
+
+/// Projects found under the scan directories, with the packages in their virtualenvs
+pub fn show_projects(ui: &mut egui::Ui, app: &mut DepMgrApp) {
+    let projects = app.projects.blocking_read();
+    let packages = app.packages.blocking_read();
+    let venvs_enabled = app.settings.scan.project_venvs;
+
+    if projects.is_empty() {
+        if app.is_scanning.load(std::sync::atomic::Ordering::Relaxed) {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label("Looking for projects...");
+            });
+        } else {
+            ui.label("No projects found under the scan directories");
+        }
+        return;
+    }
+
+    ui.label(format!("{} projects", projects.len()));
+    let mut deep_scan = false;
+    let report = app.scan_report.blocking_read();
+    let cut_short: Vec<String> = report
+        .cut_short()
+        .iter()
+        .map(|r| r.display().to_string())
+        .collect();
+    let mut open_report = false;
+    if !cut_short.is_empty() || !report.skipped.is_empty() {
+        ui.horizontal(|ui| {
+            if !cut_short.is_empty() {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Scan budget reached in {}: older projects may be missing",
+                        cut_short.join(", ")
+                    ))
+                    .small()
+                    .weak(),
+                );
+                deep_scan = ui
+                    .small_button("🔍 Deep scan")
+                    .on_hover_text("Rescan every folder, however long it takes")
+                    .clicked();
+            }
+            if !report.skipped.is_empty() {
+                open_report = ui
+                    .small_button(format!("{} folders skipped", report.skipped.len()))
+                    .on_hover_text("See the scan report")
+                    .clicked();
+            }
+        });
+    }
+    drop(report);
+    if !venvs_enabled && projects.iter().any(|p| !p.venvs.is_empty()) {
+        ui.label(
+            egui::RichText::new(
+                "Set project_venvs = true under [scan] in the config file to list virtualenv packages",
+            )
+            .small()
+            .weak(),
+        );
+    }
+    ui.separator();
+
+    let mut saves = Vec::new();
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        for project in projects.iter() {
+            show_project(ui, project, &packages, venvs_enabled);
+            show_export_menu(ui, project, &packages, &mut saves);
+            ui.separator();
+        }
+    });
+    drop(projects);
+    drop(packages);
+
+    for (path, content) in saves {
+        app.save_project_manifest(path, content);
+    }
+    if deep_scan {
+        app.request_deep_scan();
+    }
+    if open_report {
+        app.view = crate::app::View::ScanReport;
+    }
+}
+
+/// Brewfile / mise.toml with the system tools a project relies on, for teammates to
+/// reproduce its toolchain: copied, or written into the project when it has none yet
+fn show_export_menu(
+    ui: &mut egui::Ui,
+    project: &Project,
+    packages: &[Package],
+    saves: &mut Vec<(PathBuf, String)>,
+) {
+    ui.menu_button("📤 Export tools", |ui| {
+        let tools = project_tools(project, packages);
+        if tools.is_empty() {
+            ui.label("No system tools found for this project");
+            return;
+        }
+        let names: Vec<&str> = tools.iter().map(|t| t.name.as_str()).collect();
+        ui.label(egui::RichText::new(names.join(", ")).weak());
+        ui.separator();
+
+        for (file, content) in [
+            ("Brewfile", render_brewfile(project, &tools)),
+            ("mise.toml", render_mise_toml(project, &tools)),
+        ] {
+            ui.horizontal(|ui| {
+                ui.label(file);
+                if ui.small_button("📋 Copy").clicked() {
+                    ui.ctx().copy_text(content.clone());
+                    ui.close();
+                }
+                let path = project.path.join(file);
+                let exists = path.exists();
+                if ui
+                    .add_enabled(!exists, egui::Button::new("💾 Save to project").small())
+                    .on_disabled_hover_text(format!("{} already has a {}", project.name, file))
+                    .clicked()
+                {
+                    saves.push((path, content));
+                    ui.close();
+                }
+            });
+        }
+    });
+}
+
+/// One project: its managers, declared dependencies and virtualenvs
+pub fn show_project(
+    ui: &mut egui::Ui,
+    project: &Project,
+    packages: &[Package],
+    venvs_enabled: bool,
+) {
+    ui.horizontal(|ui| {
+        ui.strong(&project.name);
+        let managers: Vec<&str> = project.package_managers.iter().map(|m| m.name()).collect();
+        ui.label(egui::RichText::new(managers.join(", ")).weak());
+        ui.label(format!("{} dependencies", project.dependencies.len()));
+    });
+    ui.label(
+        egui::RichText::new(project.path.display().to_string())
+            .small()
+            .weak(),
+    );
+
+    show_dependencies(ui, project, packages);
+    for venv in &project.venvs {
+        show_venv(ui, venv, venvs_enabled);
+    }
+}
+
+/// Declared dependencies with their lock-file versions and which are behind
+fn show_dependencies(ui: &mut egui::Ui, project: &Project, packages: &[Package]) {
+    if project.dependencies.is_empty() {
+        return;
+    }
+    let outdated = crate::scanner::outdated_dependencies(project, packages);
+    let dev = project.dependencies.iter().filter(|d| d.is_dev).count();
+    let title = format!(
+        "📄 Dependencies · {} runtime, {} dev, {} outdated",
+        project.dependencies.len() - dev,
+        dev,
+        outdated.len()
+    );
+    egui::CollapsingHeader::new(title)
+        .id_salt(("deps", &project.path))
+        .show(ui, |ui| {
+            egui::Grid::new(("deps", &project.path))
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("Package");
+                    ui.strong("Constraint");
+                    ui.strong("Locked");
+                    ui.strong("Latest");
+                    ui.end_row();
+                    for dep in &project.dependencies {
+                        ui.horizontal(|ui| {
+                            ui.label(&dep.package_name);
+                            if dep.is_dev {
+                                ui.label(egui::RichText::new("dev").small().weak());
+                            }
+                        });
+                        ui.label(&dep.version_constraint);
+                        ui.label(dep.locked_version.as_deref().unwrap_or("—"));
+                        match outdated.iter().find(|o| o.name == dep.package_name) {
+                            Some(o) => {
+                                ui.colored_label(egui::Color32::from_rgb(255, 165, 0), &o.latest)
+                                    .on_hover_text(format!("{} in the project", o.current));
+                            }
+                            None => {
+                                ui.label("");
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+fn show_venv(ui: &mut egui::Ui, venv: &Venv, enabled: bool) {
+    let dir = venv
+        .path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    if !enabled {
+        ui.label(format!("🐍 {}", dir));
+        return;
+    }
+    if let Some(error) = &venv.error {
+        ui.horizontal(|ui| {
+            ui.label(format!("🐍 {}", dir));
+            ui.colored_label(egui::Color32::from_rgb(255, 80, 80), error);
+        });
+        return;
+    }
+
+    let outdated = venv.packages.iter().filter(|p| p.is_outdated).count();
+    let title = format!(
+        "🐍 {} · {} packages, {} outdated",
+        dir,
+        venv.packages.len(),
+        outdated
+    );
+    egui::CollapsingHeader::new(title)
+        .id_salt(&venv.path)
+        .show(ui, |ui| {
+            egui::Grid::new(("venv", &venv.path))
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("Package");
+                    ui.strong("Installed");
+                    ui.strong("Latest");
+                    ui.end_row();
+                    for pkg in &venv.packages {
+                        ui.label(&pkg.name);
+                        ui.label(&pkg.installed_version);
+                        match &pkg.latest_version {
+                            Some(latest) if pkg.is_outdated => {
+                                ui.colored_label(egui::Color32::from_rgb(255, 165, 0), latest);
+                            }
+                            _ => {
+                                ui.label(egui::RichText::new("up to date").weak());
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+}
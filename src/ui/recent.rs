@@ -0,0 +1,63 @@
+use crate::app::DepMgrApp;
+use crate::operations::recent::RecentKind;
+use crate::utils::format::{format_datetime, format_relative};
+use eframe::egui;
+
+/// Packages installed, updated or removed in the last few days, by DepMgr or outside it
+pub fn show_recent(ui: &mut egui::Ui, app: &mut DepMgrApp) {
+    ui.horizontal(|ui| {
+        ui.label("Last");
+        for days in [1, 7, 30, 90] {
+            let label = if days == 1 {
+                "day".to_string()
+            } else {
+                format!("{} days", days)
+            };
+            ui.selectable_value(&mut app.recent_days, days, label);
+        }
+    });
+    app.refresh_recent_changes();
+    ui.separator();
+
+    if app.recent_changes.is_empty() {
+        ui.label("No packages were installed, updated or removed in this period.");
+        return;
+    }
+
+    let mut show = None;
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        egui::Grid::new("recent_changes")
+            .num_columns(5)
+            .striped(true)
+            .show(ui, |ui| {
+                for change in &app.recent_changes {
+                    ui.label(format_relative(change.timestamp))
+                        .on_hover_text(format_datetime(change.timestamp));
+                    let color = match change.kind {
+                        RecentKind::Installed => egui::Color32::from_rgb(0, 200, 0),
+                        RecentKind::Updated => egui::Color32::from_rgb(0, 140, 255),
+                        RecentKind::Removed => egui::Color32::from_rgb(150, 150, 150),
+                    };
+                    ui.label(egui::RichText::new("⏺").color(color));
+                    if ui
+                        .link(format!("{} ({})", change.package, change.manager.name()))
+                        .clicked()
+                    {
+                        show = Some((change.manager.clone(), change.package.clone()));
+                    }
+                    ui.label(change.describe());
+                    let source = if change.external {
+                        "outside DepMgr"
+                    } else {
+                        "DepMgr"
+                    };
+                    ui.label(egui::RichText::new(source).weak());
+                    ui.end_row();
+                }
+            });
+    });
+
+    if show.is_some() {
+        app.selected_package = show;
+    }
+}
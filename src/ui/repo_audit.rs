@@ -0,0 +1,123 @@
+use crate::app::{DepMgrApp, RepoAuditState};
+use crate::models::PackageManager;
+use eframe::egui;
+
+/// Sidebar entry: paste a path or git URL to see what working on it would need
+pub fn show_repo_audit_input(ui: &mut egui::Ui, app: &mut DepMgrApp) {
+    ui.collapsing("🔍 Audit repo", |ui| {
+        ui.add(
+            egui::TextEdit::singleline(&mut app.repo_input)
+                .hint_text("https://github.com/owner/repo or ~/src/repo"),
+        );
+        if ui
+            .button("Audit")
+            .on_hover_text("Clone (shallow) or read it and list the tools it needs")
+            .clicked()
+        {
+            app.audit_repo(app.repo_input.clone());
+        }
+    });
+}
+
+/// Tools the audited repo needs, with installs for the missing ones
+pub fn show_repo_audit_window(ctx: &egui::Context, app: &mut DepMgrApp) {
+    let guard = app.repo_audit.blocking_read();
+    let Some(state) = guard.as_ref() else {
+        return;
+    };
+    let can_install = app.available_managers.contains(&PackageManager::Homebrew);
+
+    let mut open = true;
+    let mut installs: Vec<(String, String)> = Vec::new();
+    egui::Window::new("🔍 Repo audit")
+        .open(&mut open)
+        .default_size([520.0, 380.0])
+        .show(ctx, |ui| match state {
+            RepoAuditState::Running(source) => {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(format!("Reading {}...", source));
+                });
+            }
+            RepoAuditState::Failed(error) => {
+                ui.colored_label(egui::Color32::from_rgb(255, 0, 0), error);
+            }
+            RepoAuditState::Ready(audit) => {
+                ui.label(egui::RichText::new(&audit.source).strong());
+                let names: Vec<&str> = audit.projects.iter().map(|p| p.name.as_str()).collect();
+                ui.label(
+                    egui::RichText::new(format!("{} projects: {}", names.len(), names.join(", ")))
+                        .weak(),
+                );
+                ui.separator();
+
+                let missing: Vec<_> = audit.tools.iter().filter(|t| !t.installed).collect();
+                if missing.is_empty() {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(0, 200, 0),
+                        "✔ Every tool it needs is installed",
+                    );
+                } else {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{} tools missing", missing.len()));
+                        let installable: Vec<_> =
+                            missing.iter().filter(|t| t.formula.is_some()).collect();
+                        if can_install
+                            && installable.len() > 1
+                            && ui.button("⬇ Install all").clicked()
+                        {
+                            for tool in installable {
+                                if let Some(formula) = &tool.formula {
+                                    installs.push((tool.command.clone(), formula.clone()));
+                                }
+                            }
+                        }
+                    });
+                }
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("repo_audit_tools")
+                        .num_columns(3)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for tool in &audit.tools {
+                                if tool.installed {
+                                    ui.label(format!("✔ {}", tool.command));
+                                } else {
+                                    ui.colored_label(
+                                        egui::Color32::from_rgb(255, 90, 0),
+                                        format!("✖ {}", tool.command),
+                                    );
+                                }
+                                ui.label(egui::RichText::new(tool.reasons.join(", ")).weak());
+                                match &tool.formula {
+                                    Some(formula) if !tool.installed && can_install => {
+                                        if app.is_updating(formula) {
+                                            ui.spinner();
+                                        } else if ui
+                                            .small_button("Install")
+                                            .on_hover_text(format!("brew install {}", formula))
+                                            .clicked()
+                                        {
+                                            installs.push((tool.command.clone(), formula.clone()));
+                                        }
+                                    }
+                                    _ => {
+                                        ui.label("");
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            }
+        });
+    drop(guard);
+
+    for (command, formula) in installs {
+        app.install_missing_tool(command, formula, None);
+    }
+    if !open {
+        *app.repo_audit.blocking_write() = None;
+    }
+}
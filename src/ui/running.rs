@@ -0,0 +1,63 @@
+use crate::app::DepMgrApp;
+use eframe::egui;
+use std::sync::Arc;
+
+/// Warns that an update will replace binaries that are in use right now
+pub fn show_running_dialog(ctx: &egui::Context, app: &mut DepMgrApp) {
+    let pending_running = Arc::clone(&app.pending_running);
+    let mut pending_running = pending_running.blocking_write();
+    let Some(pending) = pending_running.first_mut() else {
+        return;
+    };
+
+    let mut proceed = false;
+    let mut cancel = false;
+    egui::Window::new("Package in use")
+        .id(egui::Id::new("running_dialog"))
+        .collapsible(false)
+        .resizable(false)
+        .default_width(440.0)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.label(format!(
+                "Updating {} ({}) replaces binaries that are in use:",
+                pending.name,
+                pending.manager.name()
+            ));
+            ui.horizontal(|ui| {
+                ui.colored_label(egui::Color32::from_rgb(255, 90, 0), "⚠");
+                ui.label(pending.processes.summary());
+            });
+            match &pending.service {
+                Some(service) => {
+                    ui.checkbox(
+                        &mut pending.restart_service,
+                        format!(
+                            "Stop the {} service first and start it again after",
+                            service
+                        ),
+                    );
+                }
+                None => {
+                    ui.weak("Running processes keep the old version until they're restarted.");
+                }
+            }
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("Update anyway").clicked() {
+                    proceed = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+        });
+
+    if cancel {
+        pending_running.remove(0);
+    }
+    drop(pending_running);
+    if proceed {
+        app.proceed_despite_running();
+    }
+}
@@ -0,0 +1,93 @@
+use crate::app::DepMgrApp;
+use crate::ui::update_everything::show_steps;
+use eframe::egui;
+
+/// Banner when global tools were left behind by a Python/Node upgrade
+pub fn show_stranded_banner(ui: &mut egui::Ui, app: &mut DepMgrApp) {
+    let (stranded, details) = {
+        let tools = app.stranded_tools.blocking_read();
+        let details: Vec<String> = tools
+            .iter()
+            .map(|t| format!("{} ({}) in {}", t.name, t.old_runtime, t.location.display()))
+            .collect();
+        (tools.len(), details.join("\n"))
+    };
+    if stranded == 0 {
+        return;
+    }
+    let mut open = false;
+    ui.horizontal(|ui| {
+        ui.colored_label(
+            egui::Color32::from_rgb(255, 165, 0),
+            format!(
+                "⚠ {} global tools were installed for an older Python or Node and no longer run",
+                stranded
+            ),
+        )
+        .on_hover_text(details);
+        if ui.button("Reinstall all…").clicked() {
+            open = true;
+        }
+    });
+    ui.separator();
+    if open {
+        app.open_runtime_tools();
+    }
+}
+
+/// Reinstall every stranded tool against the current runtime, one step per tool
+pub fn show_runtime_tools_window(ctx: &egui::Context, app: &mut DepMgrApp) {
+    if !app.show_runtime_tools {
+        return;
+    }
+
+    let mut open = true;
+    let mut start = false;
+    egui::Window::new("Reinstall tools for the new runtime")
+        .open(&mut open)
+        .resizable(true)
+        .default_width(520.0)
+        .show(ctx, |ui| {
+            let run = app.runtime_reinstall.blocking_read();
+            if run.steps.is_empty() {
+                ui.label("No tools left behind by a runtime upgrade.");
+                return;
+            }
+
+            ui.label(
+                egui::RichText::new(
+                    "These were installed with pip/npm for an older Python or Node. Each one \
+                     is installed again with the current runtime; the old copies are left alone.",
+                )
+                .small()
+                .weak(),
+            );
+            show_steps(ui, &run, "runtime_reinstall_steps");
+            ui.separator();
+
+            let failed = run.failed_steps();
+            if run.running {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Running...");
+                });
+                ctx.request_repaint();
+            } else if run.completed_steps() == 0 {
+                if ui.button("▶ Reinstall all").clicked() {
+                    start = true;
+                }
+            } else if failed > 0 {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 0, 0),
+                    format!("{} failed (hover ✖ for the error)", failed),
+                );
+            } else {
+                ui.colored_label(egui::Color32::from_rgb(0, 200, 0), "All tools reinstalled");
+            }
+        });
+
+    app.show_runtime_tools = open;
+    if start {
+        app.start_runtime_reinstall();
+    }
+}
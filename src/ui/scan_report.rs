@@ -0,0 +1,112 @@
+use crate::app::DepMgrApp;
+use crate::scanner::index::RootStatus;
+use crate::utils::format::format_number;
+use eframe::egui;
+
+/// What the last project scan covered under each scan root, for tuning the scan settings
+pub fn show_scan_report(ui: &mut egui::Ui, app: &mut DepMgrApp) {
+    // Read in place rather than cloned; released before anything below can rescan
+    let scan_report = std::sync::Arc::clone(&app.scan_report);
+    let report = scan_report.blocking_read();
+    if report.roots.is_empty() {
+        ui.label("No project scan has finished yet.");
+        return;
+    }
+
+    let mut deep_scan = false;
+    let mut stop_scanning = None;
+    let added = crate::scanner::added_scan_roots();
+    let budget = &app.settings.scan;
+    ui.horizontal(|ui| {
+        let limit = |value: u64, unit: &str| match value {
+            0 => "no limit".to_string(),
+            n => format!("{} {}", format_number(n), unit),
+        };
+        ui.label(
+            egui::RichText::new(format!(
+                "Budget per scan root: {}, {} (max_files and max_seconds_per_dir under [scan])",
+                limit(budget.max_files as u64, "files"),
+                limit(budget.max_seconds_per_dir, "s"),
+            ))
+            .weak(),
+        );
+        deep_scan = ui
+            .small_button("🔍 Deep scan")
+            .on_hover_text("Rescan every folder, however long it takes")
+            .clicked();
+    });
+    ui.separator();
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        egui::Grid::new("scan_report")
+            .num_columns(9)
+            .striped(true)
+            .show(ui, |ui| {
+                for header in [
+                    "Scan root",
+                    "Status",
+                    "Folders",
+                    "Projects",
+                    "Manifests",
+                    "Excluded",
+                    "Changed",
+                    "Time",
+                    "",
+                ] {
+                    ui.strong(header);
+                }
+                ui.end_row();
+
+                for root in &report.roots {
+                    ui.label(root.path.display().to_string());
+                    let status = egui::RichText::new(root.status.label());
+                    ui.label(match root.status {
+                        RootStatus::Scanned => status,
+                        RootStatus::CutShort => status.color(egui::Color32::from_rgb(255, 165, 0)),
+                        _ => status.weak(),
+                    });
+                    for count in [
+                        root.folders,
+                        root.projects,
+                        root.manifests,
+                        root.excluded,
+                        root.reread,
+                    ] {
+                        ui.label(format_number(count as u64));
+                    }
+                    ui.label(format!("{:.1}s", root.elapsed.as_secs_f64()));
+                    if added.contains(&root.path) {
+                        if ui
+                            .small_button("Stop scanning")
+                            .on_hover_text("Remove this folder from the scan roots")
+                            .clicked()
+                        {
+                            stop_scanning = Some(root.path.clone());
+                        }
+                    } else {
+                        ui.label("");
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if !report.skipped.is_empty() {
+            ui.add_space(8.0);
+            ui.strong(format!("{} folders skipped", report.skipped.len()));
+            for (path, reason) in &report.skipped {
+                ui.label(
+                    egui::RichText::new(format!("{} ({})", path.display(), reason.label())).weak(),
+                );
+            }
+        }
+    });
+    drop(report);
+
+    if deep_scan {
+        app.request_deep_scan();
+    }
+    if let Some(path) = stop_scanning {
+        crate::scanner::remove_scan_root(&path);
+        app.request_refresh();
+    }
+}
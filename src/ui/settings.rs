@@ -0,0 +1,127 @@
+use crate::app::DepMgrApp;
+use eframe::egui;
+
+/// Scan directories, concurrency limits and cache lifetimes; everything else is in
+/// config.toml, one click away
+pub fn show_settings_window(ctx: &egui::Context, app: &mut DepMgrApp) {
+    let Some(draft) = app.settings_draft.as_mut() else {
+        return;
+    };
+
+    let mut open = true;
+    let mut save = false;
+    let mut cancel = false;
+    let mut edit_file = false;
+    egui::Window::new("Settings")
+        .id(egui::Id::new("settings_window"))
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .default_width(460.0)
+        .show(ctx, |ui| {
+            ui.strong("Scan directories");
+            for dir in &draft.built_in_roots {
+                ui.label(egui::RichText::new(dir.display().to_string()).weak())
+                    .on_hover_text("Always scanned when it exists");
+            }
+            let mut remove = None;
+            for (index, dir) in draft.scan_roots.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.label(dir.display().to_string());
+                    if ui.small_button("Remove").clicked() {
+                        remove = Some(index);
+                    }
+                });
+            }
+            if let Some(index) = remove {
+                draft.scan_roots.remove(index);
+            }
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut draft.root_input)
+                        .hint_text("/path/to/projects")
+                        .desired_width(300.0),
+                );
+                let path = std::path::PathBuf::from(draft.root_input.trim());
+                let valid = path.is_dir() && !draft.scan_roots.contains(&path);
+                if ui.add_enabled(valid, egui::Button::new("Add")).clicked() {
+                    draft.scan_roots.push(path);
+                    draft.root_input.clear();
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Entries per directory");
+                ui.add(egui::DragValue::new(&mut draft.settings.scan.max_files).speed(1000))
+                    .on_hover_text("0: no limit");
+                ui.label("Seconds per directory");
+                ui.add(
+                    egui::DragValue::new(&mut draft.settings.scan.max_seconds_per_dir)
+                        .range(0..=600),
+                )
+                .on_hover_text("0: no limit");
+            });
+
+            ui.separator();
+            ui.strong("Concurrency");
+            egui::Grid::new("settings_limits").show(ui, |ui| {
+                ui.label("Registry lookups at once");
+                ui.add(
+                    egui::DragValue::new(&mut draft.settings.limits.registry_lookups).range(1..=64),
+                );
+                ui.end_row();
+                ui.label("Description lookups at once");
+                ui.add(
+                    egui::DragValue::new(&mut draft.settings.descriptions.batch_size).range(1..=64),
+                );
+                ui.end_row();
+            });
+
+            ui.separator();
+            ui.strong("Cache");
+            egui::Grid::new("settings_cache").show(ui, |ui| {
+                ui.label("Latest versions (minutes)");
+                ui.add(
+                    egui::DragValue::new(&mut draft.settings.cache.registry_minutes)
+                        .range(1..=7 * 24 * 60),
+                );
+                ui.end_row();
+                ui.label("Advisories and releases (hours)");
+                ui.add(
+                    egui::DragValue::new(&mut draft.settings.cache.release_hours)
+                        .range(1..=30 * 24),
+                );
+                ui.end_row();
+            });
+            ui.label(
+                egui::RichText::new("New lifetimes apply to responses fetched from now on")
+                    .small()
+                    .weak(),
+            );
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("Save").clicked() {
+                    save = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    cancel = true;
+                }
+                if ui
+                    .button("Edit config.toml")
+                    .on_hover_text("Every other setting lives there")
+                    .clicked()
+                {
+                    edit_file = true;
+                }
+            });
+        });
+
+    if edit_file {
+        app.open_settings_file();
+    }
+    if save {
+        app.save_settings();
+    } else if cancel || !open {
+        app.settings_draft = None;
+    }
+}
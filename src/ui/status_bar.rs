@@ -0,0 +1,90 @@
+use crate::app::DepMgrApp;
+use crate::utils::activity;
+use eframe::egui;
+use std::sync::atomic::Ordering;
+
+/// Bottom bar: what's running in the background, and the last operation's outcome
+pub fn show_status_bar(ctx: &egui::Context, app: &DepMgrApp) {
+    let tasks = activity::tasks();
+    let is_scanning = app.is_scanning.load(Ordering::Relaxed);
+    let update_status = app.get_update_status();
+    if !tasks.is_empty() || is_scanning {
+        // Keep the spinner and elapsed times moving
+        ctx.request_repaint();
+    }
+
+    egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+        ui.horizontal(|ui| {
+            let busy = !tasks.is_empty() || is_scanning || update_status.contains("...");
+            if busy {
+                ui.spinner();
+            }
+            // The oldest task is the phase everything else hangs off
+            let phase = match tasks.first() {
+                Some(task) => task.label.clone(),
+                None if is_scanning => "Scanning packages".to_string(),
+                None => "Idle".to_string(),
+            };
+            ui.label(phase);
+
+            if !tasks.is_empty() {
+                ui.separator();
+                ui.menu_button(format!("{} tasks", tasks.len()), |ui| {
+                    egui::Grid::new("activity_tasks")
+                        .num_columns(3)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (label, count, started) in activity::grouped(&tasks) {
+                                ui.label(label);
+                                ui.label(if count > 1 {
+                                    format!("×{}", count)
+                                } else {
+                                    String::new()
+                                });
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "{}s",
+                                        started.elapsed().as_secs()
+                                    ))
+                                    .weak(),
+                                );
+                                ui.end_row();
+                            }
+                        });
+                });
+            }
+
+            let requests = activity::requests_in_flight();
+            let commands = activity::commands_running();
+            if requests > 0 || commands > 0 {
+                ui.separator();
+                ui.label(
+                    egui::RichText::new(format!(
+                        "🌐 {} requests · ⚙ {} commands",
+                        requests, commands
+                    ))
+                    .weak(),
+                )
+                .on_hover_text("Registry requests in flight and package manager commands running");
+            }
+
+            if !update_status.is_empty() {
+                ui.separator();
+                let text = egui::RichText::new(&update_status);
+                let text = if update_status.contains("Failed") || update_status.contains("failed") {
+                    text.color(egui::Color32::from_rgb(255, 0, 0))
+                } else if update_status.contains("removed")
+                    || update_status.contains("updated")
+                    || update_status.contains("installed")
+                    || update_status.contains("saved")
+                {
+                    text.color(egui::Color32::from_rgb(0, 200, 0))
+                } else {
+                    text
+                };
+                ui.add(egui::Label::new(text).truncate())
+                    .on_hover_text(&update_status);
+            }
+        });
+    });
+}
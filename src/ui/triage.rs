@@ -0,0 +1,137 @@
+use crate::app::{ChangelogState, DepMgrApp};
+use crate::ui::dashboard::risk_badge;
+use eframe::egui;
+
+/// One outdated package at a time: what changed, whether it's vulnerable, who uses it,
+/// then Update / Skip / Pin and on to the next
+pub fn show_triage_window(ctx: &egui::Context, app: &mut DepMgrApp) {
+    if !app.show_triage {
+        return;
+    }
+
+    let mut open = true;
+    let current = app.triage_current();
+    if let Some(pkg) = &current {
+        app.load_changelog(pkg);
+    }
+
+    egui::Window::new("Triage")
+        .open(&mut open)
+        .resizable(true)
+        .default_width(560.0)
+        .default_height(480.0)
+        .show(ctx, |ui| {
+            let total = app.triage_queue.len();
+            let Some(pkg) = current else {
+                ui.label(if total == 0 {
+                    "Nothing to triage: no outdated packages.".to_string()
+                } else {
+                    format!("All caught up: reviewed {} packages.", total)
+                });
+                return;
+            };
+
+            ui.label(egui::RichText::new(format!("{} of {}", app.triage_index + 1, total)).weak());
+            ui.heading(format!("{} ({})", pkg.name, pkg.manager.name()));
+            ui.label(format!(
+                "{} ➡ {}",
+                pkg.installed_version,
+                pkg.latest_version.as_deref().unwrap_or("latest")
+            ));
+            let risk = app.risk_of(&pkg);
+            ui.horizontal(|ui| {
+                risk_badge(ui, &risk);
+                ui.label(format!("{} ({}/10)", risk.level().label(), risk.score));
+                if !risk.reasons.is_empty() {
+                    ui.label(egui::RichText::new(risk.reasons.join(", ")).weak());
+                }
+            });
+            if let Some(desc) = &pkg.description {
+                ui.label(egui::RichText::new(desc).weak());
+            }
+
+            ui.separator();
+            if pkg.vulnerabilities.is_empty() {
+                ui.label("No known vulnerabilities in the installed version");
+            } else {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 0, 0),
+                    format!("{} known vulnerabilities", pkg.vulnerabilities.len()),
+                );
+                ui.horizontal_wrapped(|ui| {
+                    for id in &pkg.vulnerabilities {
+                        ui.hyperlink_to(id, format!("https://osv.dev/vulnerability/{}", id));
+                    }
+                });
+            }
+
+            if pkg.used_in.is_empty() {
+                ui.label("Not used by any scanned project");
+            } else {
+                ui.label(format!("Used in {} projects:", pkg.used_in.len()))
+                    .on_hover_text(pkg.used_in.join("\n"));
+                let names: Vec<String> = pkg.used_in.iter().map(|p| pkg.usage_label(p)).collect();
+                ui.label(egui::RichText::new(names.join(", ")).monospace());
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                let updating = app.is_updating(&pkg.name);
+                if ui
+                    .add_enabled(!updating, egui::Button::new("⬆️ Update"))
+                    .clicked()
+                {
+                    app.update_package(pkg.name.clone(), pkg.manager.clone());
+                    app.triage_next();
+                }
+                if ui.button("⏭ Skip").clicked() {
+                    app.triage_next();
+                }
+                if ui
+                    .button("📌 Pin")
+                    .on_hover_text("Keep this version and stop suggesting it")
+                    .clicked()
+                {
+                    app.toggle_pinned(&pkg);
+                    app.triage_next();
+                }
+            });
+
+            ui.separator();
+            ui.strong("Changelog");
+            let changelogs = app.changelogs.blocking_read();
+            match changelogs.get(&pkg.key()) {
+                None | Some(ChangelogState::Loading) => {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Fetching release notes...");
+                    });
+                }
+                Some(ChangelogState::Failed(e)) => {
+                    ui.colored_label(egui::Color32::from_rgb(255, 0, 0), e);
+                }
+                Some(ChangelogState::Ready(None)) => {
+                    ui.label("No GitHub repository found for this package");
+                }
+                Some(ChangelogState::Ready(Some(changelog))) => {
+                    ui.hyperlink_to("All releases", &changelog.releases_url);
+                    if changelog.notes.is_empty() {
+                        ui.label("No release notes between these versions");
+                    }
+                    egui::ScrollArea::vertical()
+                        .id_salt("triage_changelog")
+                        .show(ui, |ui| {
+                            for note in &changelog.notes {
+                                ui.collapsing(&note.tag, |ui| {
+                                    ui.label(&note.body);
+                                });
+                            }
+                        });
+                }
+            }
+        });
+
+    if !open {
+        app.show_triage = false;
+    }
+}
@@ -0,0 +1,135 @@
+use crate::app::DepMgrApp;
+use crate::operations::update_everything::{StepStatus, UpdateEverythingRun};
+use crate::operations::{supports_rollback, JournalAction};
+use crate::utils::format::format_size;
+use eframe::egui;
+
+/// Step-by-step view of an "Update Everything" run, with rollback for finished upgrades
+pub fn show_update_everything_window(ctx: &egui::Context, app: &mut DepMgrApp) {
+    if !app.show_update_everything {
+        return;
+    }
+
+    let mut open = true;
+    let mut start = false;
+    let mut rollback = None;
+
+    egui::Window::new("Update Everything")
+        .open(&mut open)
+        .resizable(true)
+        .default_width(520.0)
+        .show(ctx, |ui| {
+            let run = app.update_everything.blocking_read();
+
+            if run.steps.is_empty() {
+                ui.label("Everything is up to date.");
+                return;
+            }
+
+            let done = run.completed_steps();
+            show_steps(ui, &run, "update_everything_steps");
+            ui.separator();
+
+            if run.running {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Running...");
+                });
+                ctx.request_repaint();
+            } else if done == 0 {
+                let (bytes, sized) = run.download_estimate(&app.packages.blocking_read());
+                if sized > 0 {
+                    ui.label(format!(
+                        "Estimated download: {} for {} of {} upgrades",
+                        format_size(bytes, app.size_units),
+                        sized,
+                        run.steps.len()
+                    ))
+                    .on_hover_text("Homebrew bottle sizes; other managers aren't included");
+                }
+                if ui.button("▶ Start").clicked() {
+                    start = true;
+                }
+            }
+
+            // Rollback journal for this run: only successful upgrades with a known
+            // previous version on managers that can install exact versions
+            let reversible: Vec<_> = run
+                .journal
+                .iter()
+                .filter(|e| e.action == JournalAction::Update && e.success)
+                .filter(|e| e.from_version.is_some() && supports_rollback(&e.manager))
+                .collect();
+
+            if !reversible.is_empty() {
+                ui.separator();
+                egui::CollapsingHeader::new(format!("Rollback journal ({})", reversible.len()))
+                    .show(ui, |ui| {
+                        for entry in reversible {
+                            ui.horizontal(|ui| {
+                                ui.label(format!(
+                                    "{} {}: {} ➡ {}",
+                                    entry.manager.name(),
+                                    entry.package,
+                                    entry.from_version.as_deref().unwrap_or("?"),
+                                    entry.to_version.as_deref().unwrap_or("latest")
+                                ));
+                                if !run.running
+                                    && !app.is_updating(&entry.package)
+                                    && ui.small_button("↩ Roll back").clicked()
+                                {
+                                    rollback = Some(entry.clone());
+                                }
+                            });
+                        }
+                    });
+            }
+        });
+
+    app.show_update_everything = open;
+    if start {
+        app.start_update_everything();
+    }
+    if let Some(entry) = rollback {
+        app.rollback(entry);
+    }
+}
+
+/// Progress bar and per-step status of a batch run; failures show their error on hover
+pub fn show_steps(ui: &mut egui::Ui, run: &UpdateEverythingRun, id_salt: &str) {
+    let done = run.completed_steps();
+    ui.add(
+        egui::ProgressBar::new(done as f32 / run.steps.len() as f32).text(format!(
+            "{}/{} steps",
+            done,
+            run.steps.len()
+        )),
+    );
+    ui.separator();
+
+    egui::ScrollArea::vertical()
+        .max_height(300.0)
+        .id_salt(id_salt)
+        .show(ui, |ui| {
+            for step in &run.steps {
+                ui.horizontal(|ui| {
+                    match &step.status {
+                        StepStatus::Pending => {
+                            ui.label("○");
+                        }
+                        StepStatus::Running => {
+                            ui.spinner();
+                        }
+                        StepStatus::Done => {
+                            ui.colored_label(egui::Color32::from_rgb(0, 200, 0), "✔");
+                        }
+                        StepStatus::Failed(message) => {
+                            ui.colored_label(egui::Color32::from_rgb(255, 0, 0), "✖")
+                                .on_hover_text(message);
+                        }
+                    }
+                    ui.label(&step.label);
+                });
+            }
+        });
+}
@@ -0,0 +1,85 @@
+use crate::app::{DepMgrApp, View};
+use crate::utils::format::{format_date, format_size};
+use eframe::egui;
+
+enum WeeklyAction {
+    Triage,
+    Show(View),
+    Dismiss,
+}
+
+/// Once a week after the startup scan: what piled up since last week and where to start
+pub fn show_weekly_summary(ctx: &egui::Context, app: &mut DepMgrApp) {
+    let summary = app.weekly_summary.blocking_read().clone();
+    let Some(summary) = summary else {
+        return;
+    };
+
+    let mut action = None;
+    egui::Window::new("This week")
+        .id(egui::Id::new("weekly_summary"))
+        .collapsible(false)
+        .resizable(false)
+        .default_width(420.0)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.label(format!("Since {}:", format_date(summary.since)));
+            ui.add_space(4.0);
+            let stat = |ui: &mut egui::Ui, value: String, label: &str| {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(value).size(20.0).strong());
+                    ui.label(label);
+                });
+            };
+            stat(ui, summary.new_updates.to_string(), "new updates");
+            stat(
+                ui,
+                summary.vulnerable.to_string(),
+                "packages with known vulnerabilities",
+            );
+            stat(
+                ui,
+                format_size(summary.reclaimable, app.size_units),
+                "reclaimable from unused packages",
+            );
+
+            if !summary.biggest_changes.is_empty() {
+                ui.separator();
+                ui.strong("Biggest changes");
+                for change in &summary.biggest_changes {
+                    ui.label(format!(
+                        "{} ({}): {}",
+                        change.name,
+                        change.manager.name(),
+                        change.kind.describe()
+                    ));
+                }
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if summary.new_updates > 0 && ui.button("☰ Triage updates").clicked() {
+                    action = Some(WeeklyAction::Triage);
+                }
+                if summary.vulnerable > 0 && ui.button("Show vulnerable").clicked() {
+                    action = Some(WeeklyAction::Show(View::Vulnerable));
+                }
+                if summary.reclaimable > 0 && ui.button("Show unused").clicked() {
+                    action = Some(WeeklyAction::Show(View::Unused));
+                }
+                if ui.button("Dismiss").clicked() {
+                    action = Some(WeeklyAction::Dismiss);
+                }
+            });
+        });
+
+    let Some(action) = action else {
+        return;
+    };
+    *app.weekly_summary.blocking_write() = None;
+    match action {
+        WeeklyAction::Triage => app.open_triage(),
+        WeeklyAction::Show(view) => app.view = view,
+        WeeklyAction::Dismiss => {}
+    }
+}
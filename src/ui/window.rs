@@ -0,0 +1,68 @@
+use crate::app::DepMgrApp;
+use eframe::egui;
+
+impl eframe::App for DepMgrApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Handle refresh requests
+        self.handle_refresh();
+        // `depmgr open` run again while this window is open
+        let launches = std::mem::take(&mut *self.handed_over.blocking_write());
+        if !launches.is_empty() {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+        }
+        for link in launches.into_iter().flatten() {
+            self.open_link(&link);
+        }
+        update_window_title(ctx, self);
+        track_window_layout(ctx, self);
+
+        super::show_dashboard(ctx, self);
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.shutdown();
+    }
+}
+
+/// Put the outdated count in the window title and Dock badge so it's visible in
+/// the background. The dashboard repaints every second, which keeps this current.
+fn update_window_title(ctx: &egui::Context, app: &mut DepMgrApp) {
+    let (_, outdated, _) = app.stats();
+    if app.shown_outdated == Some(outdated) {
+        return;
+    }
+    app.shown_outdated = Some(outdated);
+
+    let title = if outdated > 0 {
+        format!("Dependency Manager ({} outdated)", outdated)
+    } else {
+        "Dependency Manager".to_string()
+    };
+    ctx.send_viewport_cmd(egui::ViewportCommand::Title(title));
+    crate::utils::dock::set_badge(&if outdated > 0 {
+        outdated.to_string()
+    } else {
+        String::new()
+    });
+}
+
+/// Remember where the window is, unless it's minimized or fullscreen
+fn track_window_layout(ctx: &egui::Context, app: &mut DepMgrApp) {
+    let layout = ctx.input(|i| {
+        let viewport = i.viewport();
+        if viewport.minimized == Some(true) || viewport.fullscreen == Some(true) {
+            return None;
+        }
+        let outer = viewport.outer_rect?;
+        let inner = viewport.inner_rect?;
+        Some(crate::config::WindowLayout {
+            x: outer.min.x,
+            y: outer.min.y,
+            width: inner.width(),
+            height: inner.height(),
+        })
+    });
+    if layout.is_some() {
+        app.window_layout = layout;
+    }
+}
@@ -0,0 +1,160 @@
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::Instant;
+
+/// Commands kept for the log window; a full scan runs a few hundred
+const LOG_LINES: usize = 500;
+
+/// Background work in flight, for the status bar
+static TASKS: LazyLock<Mutex<Vec<Task>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+static REQUESTS: AtomicUsize = AtomicUsize::new(0);
+static COMMANDS: AtomicUsize = AtomicUsize::new(0);
+static LOG: LazyLock<Mutex<VecDeque<LogEntry>>> = LazyLock::new(|| Mutex::new(VecDeque::new()));
+
+#[derive(Debug, Clone)]
+pub struct Task {
+    id: u64,
+    pub label: String,
+    pub started: Instant,
+}
+
+/// Removes its task from the list when dropped
+#[must_use]
+pub struct ActivityGuard(u64);
+
+impl Drop for ActivityGuard {
+    fn drop(&mut self) {
+        TASKS.lock().unwrap().retain(|t| t.id != self.0);
+    }
+}
+
+/// Register a task ("Listing npm", "Updating ripgrep") for as long as the guard lives
+pub fn start(label: impl Into<String>) -> ActivityGuard {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    TASKS.lock().unwrap().push(Task {
+        id,
+        label: label.into(),
+        started: Instant::now(),
+    });
+    ActivityGuard(id)
+}
+
+/// Tasks in flight, oldest first
+pub fn tasks() -> Vec<Task> {
+    TASKS.lock().unwrap().clone()
+}
+
+/// Decrements its counter when dropped
+pub struct InFlight(&'static AtomicUsize);
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+fn enter(counter: &'static AtomicUsize) -> InFlight {
+    counter.fetch_add(1, Ordering::Relaxed);
+    InFlight(counter)
+}
+
+/// Count an HTTP request as in flight until the guard drops
+pub fn request() -> InFlight {
+    enter(&REQUESTS)
+}
+
+/// Count a command as running until the guard drops
+pub fn command() -> InFlight {
+    enter(&COMMANDS)
+}
+
+pub fn requests_in_flight() -> usize {
+    REQUESTS.load(Ordering::Relaxed)
+}
+
+pub fn commands_running() -> usize {
+    COMMANDS.load(Ordering::Relaxed)
+}
+
+/// One command run, for the log window
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub at: DateTime<Utc>,
+    pub command: String,
+    pub outcome: Option<String>, // None: exited 0
+}
+
+/// Record a finished command and how it went (the exit status and last line of stderr
+/// when it failed)
+pub fn log_command(cmd: &str, args: &[&str], result: &anyhow::Result<std::process::Output>) {
+    let outcome = match result {
+        Ok(output) if output.status.success() => None,
+        Ok(output) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let last = stderr.lines().rev().find(|l| !l.trim().is_empty());
+            Some(match (output.status.code(), last) {
+                (Some(code), Some(line)) => format!("exit {}: {}", code, line.trim()),
+                (Some(code), None) => format!("exit {}", code),
+                (None, _) => "killed by a signal".to_string(),
+            })
+        }
+        Err(e) => Some(e.to_string()),
+    };
+    let mut log = LOG.lock().unwrap();
+    if log.len() == LOG_LINES {
+        log.pop_front();
+    }
+    log.push_back(LogEntry {
+        at: Utc::now(),
+        command: std::iter::once(cmd)
+            .chain(args.iter().copied())
+            .collect::<Vec<_>>()
+            .join(" "),
+        outcome,
+    });
+}
+
+/// Logged commands, oldest first
+pub fn log_entries() -> Vec<LogEntry> {
+    LOG.lock().unwrap().iter().cloned().collect()
+}
+
+pub fn clear_log() {
+    LOG.lock().unwrap().clear();
+}
+
+/// Identical labels collapsed into (label, count, oldest start), oldest first
+pub fn grouped(tasks: &[Task]) -> Vec<(String, usize, Instant)> {
+    let mut groups: Vec<(String, usize, Instant)> = Vec::new();
+    for task in tasks {
+        match groups.iter_mut().find(|(label, _, _)| *label == task.label) {
+            Some(group) => group.1 += 1,
+            None => groups.push((task.label.clone(), 1, task.started)),
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guards_remove_their_tasks() {
+        let scan = start("Listing npm");
+        let lookups = [start("Description lookup"), start("Description lookup")];
+        let groups = grouped(&tasks());
+        assert!(groups
+            .iter()
+            .any(|(label, count, _)| label == "Description lookup" && *count == 2));
+
+        drop(lookups);
+        drop(scan);
+        assert!(!tasks()
+            .iter()
+            .any(|t| t.label == "Listing npm" || t.label == "Description lookup"));
+    }
+}
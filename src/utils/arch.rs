@@ -0,0 +1,115 @@
+use crate::models::{BinaryArch, Package, PackageManager};
+use crate::utils::run_command_with_timeout;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// True on Apple Silicon, even when we are ourselves running under Rosetta
+pub async fn is_apple_silicon() -> bool {
+    if !cfg!(target_os = "macos") {
+        return false;
+    }
+    match run_command_with_timeout(
+        "sysctl",
+        &["-n", "hw.optional.arm64"],
+        Duration::from_secs(5),
+    )
+    .await
+    {
+        Ok(output) => String::from_utf8_lossy(&output.stdout).trim() == "1",
+        Err(_) => false,
+    }
+}
+
+/// `lipo -archs` output, e.g. "x86_64 arm64"
+fn parse_archs(output: &str) -> Option<BinaryArch> {
+    let archs: Vec<&str> = output.split_whitespace().collect();
+    let arm = archs.iter().any(|a| a.starts_with("arm64"));
+    let intel = archs.contains(&"x86_64") || archs.contains(&"x86_64h");
+    match (arm, intel) {
+        (true, true) => Some(BinaryArch::Universal),
+        (true, false) => Some(BinaryArch::Arm64),
+        (false, true) => Some(BinaryArch::X86_64),
+        (false, false) => None,
+    }
+}
+
+async fn binary_arch(path: &Path) -> Option<BinaryArch> {
+    let path = path.to_string_lossy();
+    let output = run_command_with_timeout("lipo", &["-archs", &path], Duration::from_secs(5))
+        .await
+        .ok()
+        .filter(|o| o.status.success())?;
+    parse_archs(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// First regular file in a formula's `bin` (symlinks there point into other kegs)
+fn homebrew_binary(cellar: &Path, pkg: &Package) -> Option<PathBuf> {
+    let bin = cellar
+        .join(&pkg.name)
+        .join(&pkg.installed_version)
+        .join("bin");
+    let mut files: Vec<PathBuf> = std::fs::read_dir(bin)
+        .ok()?
+        .flatten()
+        .filter(|e| e.file_type().map(|t| t.is_file()).unwrap_or(false))
+        .map(|e| e.path())
+        .collect();
+    files.sort();
+    files.into_iter().next()
+}
+
+/// Architecture of each Homebrew formula's and cargo crate's binary, flagging Intel-only
+/// ones that run under Rosetta. macOS only; elsewhere there is only one architecture.
+pub async fn add_architectures(packages: &mut [Package]) {
+    if !cfg!(target_os = "macos") {
+        return;
+    }
+    let apple_silicon = is_apple_silicon().await;
+
+    let cellar =
+        match run_command_with_timeout("brew", &["--cellar"], Duration::from_secs(10)).await {
+            Ok(output) if output.status.success() => Some(PathBuf::from(
+                String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            )),
+            _ => None,
+        };
+    let cargo_bins = crate::managers::cargo::installed_binaries();
+
+    let start = std::time::Instant::now();
+    let mut measured = 0;
+    for pkg in packages.iter_mut() {
+        let binary = match pkg.manager {
+            PackageManager::Homebrew => cellar.as_deref().and_then(|c| homebrew_binary(c, pkg)),
+            PackageManager::Cargo => cargo_bins.get(&pkg.name).and_then(|b| b.first().cloned()),
+            _ => None,
+        };
+        let Some(binary) = binary else {
+            continue;
+        };
+        pkg.arch = binary_arch(&binary).await;
+        pkg.needs_rosetta = apple_silicon && pkg.arch == Some(BinaryArch::X86_64);
+        measured += 1;
+    }
+
+    let rosetta = packages.iter().filter(|p| p.needs_rosetta).count();
+    println!(
+        "[ARCH] Checked {} binaries in {:?}, {} run under Rosetta",
+        measured,
+        start.elapsed(),
+        rosetta
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lipo_archs() {
+        assert_eq!(parse_archs("arm64\n"), Some(BinaryArch::Arm64));
+        assert_eq!(parse_archs("x86_64\n"), Some(BinaryArch::X86_64));
+        assert_eq!(parse_archs("x86_64 arm64\n"), Some(BinaryArch::Universal));
+        assert_eq!(parse_archs("arm64e"), Some(BinaryArch::Arm64));
+        assert_eq!(parse_archs(""), None);
+    }
+}
@@ -0,0 +1,159 @@
+use crate::models::{BrokenBinary, Package, PackageManager};
+use crate::utils::run_command_with_timeout;
+use anyhow::Result;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+/// Installed version shown for packages that only exist as broken links
+pub const MISSING_VERSION: &str = "missing";
+
+/// The path a command prints, like `brew --prefix`
+pub async fn command_path(cmd: &str, args: &[&str]) -> Option<PathBuf> {
+    let output = run_command_with_timeout(cmd, args, Duration::from_secs(10))
+        .await
+        .ok()
+        .filter(|o| o.status.success())?;
+    Some(PathBuf::from(
+        String::from_utf8_lossy(&output.stdout).trim().to_string(),
+    ))
+}
+
+/// Symlinks in `dir` whose target no longer exists, with the package they belong to
+/// (the path component after `anchor`, e.g. `Cellar/<name>/` or `node_modules/<name>/`)
+fn dangling_links(dir: &Path, anchor: &str) -> Vec<(String, BrokenBinary)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dangling = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(target) = std::fs::read_link(&path) else {
+            continue; // not a symlink
+        };
+        if path.exists() {
+            continue; // exists() follows the link
+        }
+        let mut parts = target.components().skip_while(|c| match c {
+            Component::Normal(part) => *part != anchor,
+            _ => true,
+        });
+        parts.next();
+        let Some(Component::Normal(first)) = parts.next() else {
+            continue;
+        };
+        let mut name = first.to_string_lossy().to_string();
+        if name.starts_with('@') {
+            if let Some(Component::Normal(scoped)) = parts.next() {
+                name = format!("{}/{}", name, scoped.to_string_lossy());
+            }
+        }
+        dangling.push((
+            name,
+            BrokenBinary {
+                path: path.display().to_string(),
+                dangling_target: Some(target.display().to_string()),
+            },
+        ));
+    }
+    dangling
+}
+
+/// Find commands of this manager that no longer work: dangling symlinks in Homebrew's and
+/// npm's bin directories, and cargo binaries deleted by hand. Links of packages the manager
+/// no longer lists become rows of their own so they can be cleaned up.
+pub async fn add_broken_binaries(manager: &PackageManager, packages: &mut Vec<Package>) {
+    let found: Vec<(String, BrokenBinary)> = match manager {
+        PackageManager::Homebrew => match command_path("brew", &["--prefix"]).await {
+            Some(prefix) => ["bin", "sbin"]
+                .iter()
+                .flat_map(|dir| dangling_links(&prefix.join(dir), "Cellar"))
+                .collect(),
+            None => Vec::new(),
+        },
+        PackageManager::Npm => match command_path("npm", &["prefix", "-g"]).await {
+            Some(prefix) => dangling_links(&prefix.join("bin"), "node_modules"),
+            None => Vec::new(),
+        },
+        PackageManager::Cargo => crate::managers::cargo::installed_binaries()
+            .into_iter()
+            .flat_map(|(name, bins)| {
+                bins.into_iter().filter(|b| !b.exists()).map(move |b| {
+                    let broken = BrokenBinary {
+                        path: b.display().to_string(),
+                        dangling_target: None,
+                    };
+                    (name.clone(), broken)
+                })
+            })
+            .collect(),
+        _ => return,
+    };
+
+    for pkg in packages.iter_mut() {
+        pkg.broken.clear();
+    }
+    for (name, broken) in found {
+        match packages.iter_mut().find(|p| p.name == name) {
+            Some(pkg) => pkg.broken.push(broken),
+            None => packages.push(Package {
+                broken: vec![broken],
+                ..Package::new(name, manager.clone(), MISSING_VERSION)
+            }),
+        }
+    }
+
+    let count = packages.iter().filter(|p| !p.broken.is_empty()).count();
+    if count > 0 {
+        println!(
+            "[BROKEN] {} {} packages have broken binaries",
+            count,
+            manager.name()
+        );
+    }
+}
+
+/// Stop tracking what's broken: delete the dangling symlinks, and drop cargo's record of
+/// crates whose binaries were deleted
+pub async fn forget(pkg: &Package) -> Result<()> {
+    for broken in &pkg.broken {
+        let path = Path::new(&broken.path);
+        // Still a symlink, still pointing nowhere (a relink may have fixed it)
+        if broken.dangling_target.is_some() && path.symlink_metadata().is_ok() && !path.exists() {
+            println!("[BROKEN] Removing dangling symlink {}", broken.path);
+            std::fs::remove_file(&broken.path)?;
+        }
+    }
+    if pkg.manager == PackageManager::Cargo
+        && pkg.broken.iter().any(|b| b.dangling_target.is_none())
+    {
+        crate::managers::cargo::uninstall_cargo_package(pkg.name.clone()).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_dir::TestDir;
+
+    #[cfg(unix)]
+    #[test]
+    fn dangling_links_name_their_package() {
+        let dir = TestDir::new("broken");
+        let link = |target: &str, name: &str| {
+            std::os::unix::fs::symlink(target, dir.join(name)).unwrap();
+        };
+        link("../Cellar/wget/1.24.5/bin/wget", "wget");
+        link("../lib/node_modules/@vue/cli/bin/vue.js", "vue");
+        link(dir.to_str().unwrap(), "working"); // points somewhere that exists
+
+        let mut found: Vec<String> = dangling_links(&dir, "Cellar")
+            .into_iter()
+            .chain(dangling_links(&dir, "node_modules"))
+            .map(|(name, _)| name)
+            .collect();
+        found.sort();
+
+        assert_eq!(found, ["@vue/cli", "wget"]);
+    }
+}
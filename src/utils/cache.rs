@@ -1,5 +1,8 @@
+use crate::utils::target::ExecTarget;
+use chrono::{DateTime, Utc};
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -28,7 +31,8 @@ impl<T> CacheEntry<T> {
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs();
-        now - self.timestamp > self.ttl_seconds
+        // An entry from "the future" (the clock moved back) counts as fresh
+        now.saturating_sub(self.timestamp) > self.ttl_seconds
     }
 }
 
@@ -36,16 +40,88 @@ impl<T> CacheEntry<T> {
 pub static MEMORY_CACHE: LazyLock<DashMap<String, CacheEntry<String>>> =
     LazyLock::new(DashMap::new);
 
-pub fn get_cached<T: for<'de> Deserialize<'de>>(key: &str) -> Option<T> {
+/// Entries also live on disk so a restart doesn't refetch them, one directory per
+/// target since listings differ between machines. Demo mode fetches nothing.
+fn disk_dir() -> Option<PathBuf> {
+    let scope = match crate::utils::target::current_target() {
+        ExecTarget::Local => "local".to_string(),
+        ExecTarget::Ssh(host) => format!("ssh-{}", file_name(&host)),
+        ExecTarget::Container { name, .. } => format!("docker-{}", file_name(&name)),
+        ExecTarget::Demo => return None,
+    };
+    Some(crate::utils::paths::cache_dir().join(scope))
+}
+
+/// Keys contain ':' and '/'; anything that isn't safe in a file name is %-escaped
+fn file_name(key: &str) -> String {
+    key.bytes()
+        .map(|b| {
+            if b.is_ascii_alphanumeric() || b"._-@".contains(&b) {
+                (b as char).to_string()
+            } else {
+                format!("%{:02X}", b)
+            }
+        })
+        .collect()
+}
+
+/// Run disk I/O without stalling the runtime: lookups come from async code all over, so on
+/// a multi-threaded runtime the worker hands its other tasks off while it blocks
+fn blocking<R>(io: impl FnOnce() -> R) -> R {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(io)
+        }
+        _ => io(),
+    }
+}
+
+fn disk_path(dir: &Path, key: &str) -> PathBuf {
+    dir.join(format!("{}.json", file_name(key)))
+}
+
+/// A still-valid entry from disk; expired or unreadable files are removed
+fn read_disk(dir: &Path, key: &str) -> Option<CacheEntry<String>> {
+    let path = disk_path(dir, key);
+    let content = std::fs::read_to_string(&path).ok()?;
+    match serde_json::from_str::<CacheEntry<String>>(&content) {
+        Ok(entry) if !entry.is_expired() => Some(entry),
+        _ => {
+            let _ = std::fs::remove_file(&path);
+            None
+        }
+    }
+}
+
+fn write_disk(dir: &Path, key: &str, entry: &CacheEntry<String>) {
+    let path = disk_path(dir, key);
+    let result = std::fs::create_dir_all(dir)
+        .and_then(|_| serde_json::to_string(entry).map_err(std::io::Error::other))
+        .and_then(|json| std::fs::write(&path, json));
+    if let Err(e) = result {
+        eprintln!("[CACHE] Failed to write {}: {}", path.display(), e);
+    }
+}
+
+/// A valid entry from memory, or from disk (kept in memory from then on)
+fn lookup(key: &str) -> Option<CacheEntry<String>> {
     if let Some(entry) = MEMORY_CACHE.get(key) {
         if !entry.is_expired() {
-            if let Ok(data) = serde_json::from_str(&entry.data) {
-                println!("[CACHE HIT] {}", key);
-                return Some(data);
-            }
-        } else {
-            // Remove expired entry
-            MEMORY_CACHE.remove(key);
+            return Some(entry.clone());
+        }
+    }
+    MEMORY_CACHE.remove(key);
+    let dir = disk_dir()?;
+    let entry = blocking(|| read_disk(&dir, key))?;
+    MEMORY_CACHE.insert(key.to_string(), entry.clone());
+    Some(entry)
+}
+
+pub fn get_cached<T: for<'de> Deserialize<'de>>(key: &str) -> Option<T> {
+    if let Some(entry) = lookup(key) {
+        if let Ok(data) = serde_json::from_str(&entry.data) {
+            println!("[CACHE HIT] {}", key);
+            return Some(data);
         }
     }
     println!("[CACHE MISS] {}", key);
@@ -54,6 +130,97 @@ pub fn get_cached<T: for<'de> Deserialize<'de>>(key: &str) -> Option<T> {
 
 pub fn set_cached<T: Serialize>(key: String, data: &T, ttl_seconds: u64) {
     if let Ok(json) = serde_json::to_string(data) {
-        MEMORY_CACHE.insert(key, CacheEntry::new(json, ttl_seconds));
+        let entry = CacheEntry::new(json, ttl_seconds);
+        if let Some(dir) = disk_dir() {
+            blocking(|| write_disk(&dir, &key, &entry));
+        }
+        MEMORY_CACHE.insert(key, entry);
+    }
+}
+
+/// When a still-valid entry was stored, i.e. how old the data behind it is
+pub fn cached_at(key: &str) -> Option<DateTime<Utc>> {
+    lookup(key).and_then(|entry| DateTime::from_timestamp(entry.timestamp as i64, 0))
+}
+
+pub fn invalidate(key: &str) {
+    MEMORY_CACHE.remove(key);
+    if let Some(dir) = disk_dir() {
+        let _ = blocking(|| std::fs::remove_file(disk_path(&dir, key)));
+    }
+}
+
+/// What the disk cache holds for every target
+#[derive(Debug, Default)]
+pub struct DiskCacheStats {
+    pub entries: usize,
+    pub bytes: u64,
+    /// Expired or unreadable entries, which would be fetched again anyway
+    pub stale: usize,
+}
+
+pub fn disk_cache_stats() -> DiskCacheStats {
+    let mut stats = DiskCacheStats::default();
+    let files = walkdir::WalkDir::new(crate::utils::paths::cache_dir())
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file());
+    for file in files {
+        stats.entries += 1;
+        stats.bytes += file.metadata().map(|m| m.len()).unwrap_or(0);
+        let valid = std::fs::read_to_string(file.path())
+            .ok()
+            .and_then(|content| serde_json::from_str::<CacheEntry<String>>(&content).ok())
+            .is_some_and(|entry| !entry.is_expired());
+        if !valid {
+            stats.stale += 1;
+        }
+    }
+    stats
+}
+
+/// Drop everything cached, in memory and on disk for every target; returns the bytes freed
+pub fn clear_cache() -> u64 {
+    MEMORY_CACHE.clear();
+    let dir = crate::utils::paths::cache_dir();
+    let freed = crate::utils::disk::dir_size(&dir);
+    if let Err(e) = std::fs::remove_dir_all(&dir) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            eprintln!("[CACHE] Failed to remove {}: {}", dir.display(), e);
+        }
+    }
+    println!("[CACHE] Cleared ({} bytes on disk)", freed);
+    freed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_dir::TestDir;
+
+    #[test]
+    fn entries_survive_on_disk_until_they_expire() {
+        let dir = TestDir::new("cache");
+        let key = "self_latest:npm/@scope/pkg";
+        assert_eq!(
+            disk_path(&dir, key).file_name().unwrap(),
+            "self_latest%3Anpm%2F@scope%2Fpkg.json"
+        );
+
+        write_disk(&dir, key, &CacheEntry::new("\"1.2.3\"".to_string(), 3600));
+        assert_eq!(read_disk(&dir, key).unwrap().data, "\"1.2.3\"");
+
+        let mut stale = CacheEntry::new("\"1.0.0\"".to_string(), 60);
+        stale.timestamp -= 120;
+        write_disk(&dir, key, &stale);
+        assert!(read_disk(&dir, key).is_none());
+        assert!(!disk_path(&dir, key).exists());
+    }
+
+    #[test]
+    fn entries_from_the_future_are_fresh() {
+        let mut entry = CacheEntry::new(String::new(), 60);
+        entry.timestamp += 3600;
+        assert!(!entry.is_expired());
     }
 }
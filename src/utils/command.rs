@@ -1,36 +1,239 @@
+use crate::utils::executor::current_executor;
+use crate::utils::prompt::{
+    looks_like_prompt, prompt_excerpt, prompts_on_terminal, prompts_shown, register_prompt,
+    unregister_prompt, PromptReply,
+};
+use crate::utils::target::current_target;
 use anyhow::{anyhow, Result};
 use std::process::Stdio;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
 use tokio::process::Command;
+use tokio::sync::mpsc::UnboundedReceiver;
 
+/// How long a command may sit silent before we check whether it's asking for input
+const PROMPT_IDLE_THRESHOLD: Duration = Duration::from_secs(3);
+
+/// How long a command may wait on an unanswered prompt before it's killed
+const PROMPT_ANSWER_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// How much interleaved stdout/stderr to keep around for showing a prompt
+const OUTPUT_TAIL_BYTES: usize = 4096;
+
+/// Output captured so far, plus when we last saw any
+struct OutputCapture {
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+    combined: Vec<u8>, // rolling tail of both streams, in arrival order
+    last_output: Instant,
+}
+
+fn spawn_reader<R>(
+    mut reader: R,
+    capture: Arc<Mutex<OutputCapture>>,
+    is_stderr: bool,
+) -> tokio::task::JoinHandle<()>
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut buf = [0u8; 4096];
+        loop {
+            match reader.read(&mut buf).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    let mut capture = capture.lock().unwrap();
+                    if is_stderr {
+                        capture.stderr.extend_from_slice(&buf[..n]);
+                    } else {
+                        capture.stdout.extend_from_slice(&buf[..n]);
+                    }
+                    capture.combined.extend_from_slice(&buf[..n]);
+                    // Only the tail matters for prompt detection
+                    if capture.combined.len() > OUTPUT_TAIL_BYTES {
+                        let excess = capture.combined.len() - OUTPUT_TAIL_BYTES;
+                        capture.combined.drain(..excess);
+                    }
+                    capture.last_output = Instant::now();
+                }
+            }
+        }
+    })
+}
+
+/// Every command the managers run goes through here, on the current executor
+/// (normally spawned for real; recorded or replayed via `utils::executor`)
 pub async fn run_command_with_timeout(
     cmd: &str,
     args: &[&str],
     timeout: Duration,
 ) -> Result<std::process::Output> {
-    let mut child = Command::new(cmd)
-        .args(args)
+    let backend = crate::managers::backend::current();
+    if !backend.runs_commands() {
+        return Err(anyhow!(
+            "'{}' not run: {} backend doesn't run commands",
+            cmd,
+            backend.name()
+        ));
+    }
+    let _running = crate::utils::activity::command();
+    let result = current_executor().run(cmd, args, timeout).await;
+    crate::utils::activity::log_command(cmd, args, &result);
+    result
+}
+
+/// Spawn `cmd` on the current target, answering prompts from the UI, within `timeout`
+pub(crate) async fn spawn_with_timeout(
+    cmd: &str,
+    args: &[&str],
+    timeout: Duration,
+) -> Result<std::process::Output> {
+    let (program, program_args) = current_target().wrap(cmd, args);
+
+    // stdin is piped only when the UI can answer a question; otherwise the terminal's is
+    // shared, or the command reads EOF instead of hanging until its timeout
+    let stdin = if prompts_shown() {
+        Stdio::piped()
+    } else if prompts_on_terminal() {
+        Stdio::inherit()
+    } else {
+        Stdio::null()
+    };
+    let mut child = Command::new(&program)
+        .args(&program_args)
+        .stdin(stdin)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
         .map_err(|e| anyhow!("Failed to spawn {}: {}", cmd, e))?;
 
+    let capture = Arc::new(Mutex::new(OutputCapture {
+        stdout: Vec::new(),
+        stderr: Vec::new(),
+        combined: Vec::new(),
+        last_output: Instant::now(),
+    }));
+
+    let mut stdin = child.stdin.take();
+    let mut readers = Vec::new();
+    if let Some(stdout) = child.stdout.take() {
+        readers.push(spawn_reader(stdout, Arc::clone(&capture), false));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        readers.push(spawn_reader(stderr, Arc::clone(&capture), true));
+    }
+
+    let command_line = format!("{} {}", cmd, args.join(" "));
     let start = Instant::now();
+    // Time spent waiting on the user doesn't count against the command's timeout
+    let mut paused = Duration::ZERO;
+    let mut pending_prompt: Option<(u64, Instant, UnboundedReceiver<PromptReply>)> = None;
+    // A prompt shown on the terminal, answered through the inherited stdin
+    let mut terminal_prompt: Option<Instant> = None;
+    // When the last prompt was raised: output from before then has been asked about
+    let mut last_asked: Option<Instant> = None;
 
     // Poll for completion with timeout
-    loop {
+    let status = loop {
         match child.try_wait() {
-            Ok(Some(_status)) => {
-                // Process completed, collect output
-                let output = child
-                    .wait_with_output()
-                    .await
-                    .map_err(|e| anyhow!("Failed to read output: {}", e))?;
-                return Ok(output);
-            }
+            Ok(Some(status)) => break status,
             Ok(None) => {
+                let (idle, output_since_prompt) = {
+                    let capture = capture.lock().unwrap();
+                    let since_prompt = pending_prompt
+                        .as_ref()
+                        .map(|(_, asked_at, _)| *asked_at)
+                        .or(terminal_prompt)
+                        .is_some_and(|asked_at| capture.last_output > asked_at);
+                    (capture.last_output.elapsed(), since_prompt)
+                };
+
+                if let Some(asked_at) = terminal_prompt {
+                    if output_since_prompt {
+                        // Answered, or it carried on by itself
+                        terminal_prompt = None;
+                        paused += asked_at.elapsed();
+                    } else if asked_at.elapsed() > PROMPT_ANSWER_TIMEOUT {
+                        let _ = child.kill().await;
+                        let _ = child.wait().await; // Clean up
+                        return Err(anyhow!(
+                            "Command '{}' got no answer to its prompt within {:?}",
+                            cmd,
+                            PROMPT_ANSWER_TIMEOUT
+                        ));
+                    }
+                } else if let Some((id, asked_at, mut reply_rx)) = pending_prompt.take() {
+                    if output_since_prompt {
+                        // The command carried on by itself, so it wasn't waiting on us
+                        unregister_prompt(id);
+                        paused += asked_at.elapsed();
+                    } else {
+                        match reply_rx.try_recv() {
+                            Ok(PromptReply::Respond(answer)) => {
+                                paused += asked_at.elapsed();
+                                if let Some(stdin) = stdin.as_mut() {
+                                    let line = format!("{}\n", answer);
+                                    if let Err(e) = stdin.write_all(line.as_bytes()).await {
+                                        eprintln!("[PROMPT] Failed to write to {}: {}", cmd, e);
+                                    }
+                                    let _ = stdin.flush().await;
+                                }
+                            }
+                            Ok(PromptReply::Abort) => {
+                                let _ = child.kill().await;
+                                let _ = child.wait().await; // Clean up
+                                return Err(anyhow!("Command '{}' aborted by user", cmd));
+                            }
+                            Err(_) if asked_at.elapsed() > PROMPT_ANSWER_TIMEOUT => {
+                                unregister_prompt(id);
+                                let _ = child.kill().await;
+                                let _ = child.wait().await; // Clean up
+                                return Err(anyhow!(
+                                    "Command '{}' got no answer to its prompt within {:?}",
+                                    cmd,
+                                    PROMPT_ANSWER_TIMEOUT
+                                ));
+                            }
+                            Err(_) => pending_prompt = Some((id, asked_at, reply_rx)),
+                        }
+                    }
+                } else if idle > PROMPT_IDLE_THRESHOLD && (prompts_shown() || prompts_on_terminal())
+                {
+                    let (output_tail, new_output) = {
+                        let capture = capture.lock().unwrap();
+                        let new_output = last_asked.is_none_or(|at| capture.last_output > at);
+                        (
+                            String::from_utf8_lossy(&capture.combined).to_string(),
+                            new_output,
+                        )
+                    };
+                    // A tail that was already asked about (and answered) isn't asked again
+                    if new_output && looks_like_prompt(&output_tail) {
+                        let now = Instant::now();
+                        last_asked = Some(now);
+                        if prompts_shown() {
+                            let (id, reply_rx) =
+                                register_prompt(command_line.clone(), prompt_excerpt(&output_tail));
+                            pending_prompt = Some((id, now, reply_rx));
+                        } else {
+                            // Its output is captured, so the question is repeated where
+                            // the user will see it; the answer goes to the shared stdin
+                            eprint!(
+                                "[PROMPT] '{}' is waiting for input:\n{} ",
+                                command_line,
+                                prompt_excerpt(&output_tail)
+                            );
+                            terminal_prompt = Some(now);
+                        }
+                    }
+                }
+
                 // Still running, check timeout
-                if start.elapsed() > timeout {
+                if pending_prompt.is_none()
+                    && terminal_prompt.is_none()
+                    && start.elapsed().saturating_sub(paused) > timeout
+                {
                     // Timeout exceeded, kill the process
                     let _ = child.kill().await;
                     let _ = child.wait().await; // Clean up
@@ -41,14 +244,40 @@ pub async fn run_command_with_timeout(
             }
             Err(e) => {
                 let _ = child.kill().await;
+                if let Some((id, _, _)) = pending_prompt {
+                    unregister_prompt(id);
+                }
                 return Err(anyhow!("Error waiting for command: {}", e));
             }
         }
+    };
+
+    if let Some((id, _, _)) = pending_prompt {
+        unregister_prompt(id);
     }
+
+    // Process completed, collect remaining output
+    drop(stdin);
+    for reader in readers {
+        let _ = reader.await;
+    }
+
+    let capture = capture.lock().unwrap();
+    Ok(std::process::Output {
+        status,
+        stdout: capture.stdout.clone(),
+        stderr: capture.stderr.clone(),
+    })
 }
 
 pub async fn command_exists(cmd: &str) -> bool {
-    if let Ok(output) = run_command_with_timeout("which", &[cmd], Duration::from_secs(2)).await {
+    // `where` is the Windows counterpart of `which`
+    let which = if cfg!(windows) && current_target().is_local() {
+        "where"
+    } else {
+        "which"
+    };
+    if let Ok(output) = run_command_with_timeout(which, &[cmd], Duration::from_secs(2)).await {
         output.status.success()
     } else {
         false
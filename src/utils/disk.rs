@@ -0,0 +1,13 @@
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// Total size of all regular files under a directory (symlinks are not followed)
+pub fn dir_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
@@ -0,0 +1,24 @@
+/// Show `label` on the Dock icon; an empty label clears it. Needs the main thread,
+/// which is where eframe calls `update`.
+#[cfg(target_os = "macos")]
+pub fn set_badge(label: &str) {
+    use objc2::MainThreadMarker;
+    use objc2_app_kit::NSApplication;
+    use objc2_foundation::NSString;
+
+    let Some(mtm) = MainThreadMarker::new() else {
+        return;
+    };
+    let label = (!label.is_empty()).then(|| NSString::from_str(label));
+    // Older objc2-app-kit releases mark these as unsafe, newer ones don't
+    #[allow(unused_unsafe)]
+    unsafe {
+        NSApplication::sharedApplication(mtm)
+            .dockTile()
+            .setBadgeLabel(label.as_deref());
+    }
+}
+
+/// No badge outside macOS; the window title carries the count there
+#[cfg(not(target_os = "macos"))]
+pub fn set_badge(_label: &str) {}
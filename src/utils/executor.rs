@@ -0,0 +1,275 @@
+use anyhow::{anyhow, Result};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Output};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+
+/// Set to a .jsonl path to append every command and its output there
+pub const RECORD_ENV: &str = "DEPMGR_RECORD_COMMANDS";
+/// Set to a .jsonl recording to answer commands from it instead of running them
+pub const REPLAY_ENV: &str = "DEPMGR_REPLAY_COMMANDS";
+
+/// Runs one command for `run_command_with_timeout`. The system executor spawns it;
+/// the recording and replay ones let manager parsing be tested without the tools installed.
+pub trait CommandExecutor: Send + Sync {
+    fn run<'a>(
+        &'a self,
+        cmd: &'a str,
+        args: &'a [&'a str],
+        timeout: Duration,
+    ) -> BoxFuture<'a, Result<Output>>;
+}
+
+/// Spawns the command on the current target
+pub struct SystemExecutor;
+
+impl CommandExecutor for SystemExecutor {
+    fn run<'a>(
+        &'a self,
+        cmd: &'a str,
+        args: &'a [&'a str],
+        timeout: Duration,
+    ) -> BoxFuture<'a, Result<Output>> {
+        Box::pin(super::command::spawn_with_timeout(cmd, args, timeout))
+    }
+}
+
+/// One line of a recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedCommand {
+    pub cmd: String,
+    pub args: Vec<String>,
+    pub status: i32,
+    pub stdout: String,
+    #[serde(default)]
+    pub stderr: String,
+}
+
+impl RecordedCommand {
+    fn matches(&self, cmd: &str, args: &[&str]) -> bool {
+        self.cmd == cmd
+            && self
+                .args
+                .iter()
+                .map(String::as_str)
+                .eq(args.iter().copied())
+    }
+
+    fn to_output(&self) -> Output {
+        Output {
+            status: exit_status(self.status),
+            stdout: self.stdout.clone().into_bytes(),
+            stderr: self.stderr.clone().into_bytes(),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn exit_status(code: i32) -> ExitStatus {
+    use std::os::unix::process::ExitStatusExt;
+    ExitStatus::from_raw(code << 8) // wait status layout: exit code in the high byte
+}
+
+#[cfg(windows)]
+fn exit_status(code: i32) -> ExitStatus {
+    use std::os::windows::process::ExitStatusExt;
+    ExitStatus::from_raw(code as u32)
+}
+
+/// Runs commands for real and appends each one with its output to a .jsonl file.
+/// Commands that fail to spawn or time out aren't recorded.
+pub struct RecordingExecutor {
+    path: PathBuf,
+    file_lock: Mutex<()>, // commands finish concurrently, lines must not interleave
+}
+
+impl RecordingExecutor {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            file_lock: Mutex::new(()),
+        }
+    }
+
+    fn append(&self, recorded: &RecordedCommand) -> Result<()> {
+        let _guard = self.file_lock.lock().unwrap();
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(recorded)?)?;
+        Ok(())
+    }
+}
+
+impl CommandExecutor for RecordingExecutor {
+    fn run<'a>(
+        &'a self,
+        cmd: &'a str,
+        args: &'a [&'a str],
+        timeout: Duration,
+    ) -> BoxFuture<'a, Result<Output>> {
+        Box::pin(async move {
+            let output = super::command::spawn_with_timeout(cmd, args, timeout).await?;
+            let recorded = RecordedCommand {
+                cmd: cmd.to_string(),
+                args: args.iter().map(|a| a.to_string()).collect(),
+                status: output.status.code().unwrap_or(-1),
+                stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            };
+            if let Err(e) = self.append(&recorded) {
+                eprintln!("[RECORD] Failed to write {}: {}", self.path.display(), e);
+            }
+            Ok(output)
+        })
+    }
+}
+
+/// Answers commands from a recording. Repeated commands get the recorded answers in
+/// order, then the last one again; anything never recorded is an error.
+pub struct ReplayExecutor {
+    recordings: Vec<RecordedCommand>,
+    used: Mutex<Vec<bool>>,
+}
+
+impl ReplayExecutor {
+    pub fn parse(jsonl: &str) -> Result<Self> {
+        let recordings = jsonl
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<Vec<RecordedCommand>, _>>()?;
+        Ok(Self {
+            used: Mutex::new(vec![false; recordings.len()]),
+            recordings,
+        })
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let jsonl = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(&jsonl)
+    }
+
+    fn answer(&self, cmd: &str, args: &[&str]) -> Option<Output> {
+        let mut used = self.used.lock().unwrap();
+        let matching: Vec<usize> = (0..self.recordings.len())
+            .filter(|&i| self.recordings[i].matches(cmd, args))
+            .collect();
+        let idx = matching
+            .iter()
+            .copied()
+            .find(|&i| !used[i])
+            .or_else(|| matching.last().copied())?;
+        used[idx] = true;
+        Some(self.recordings[idx].to_output())
+    }
+}
+
+impl CommandExecutor for ReplayExecutor {
+    fn run<'a>(
+        &'a self,
+        cmd: &'a str,
+        args: &'a [&'a str],
+        _timeout: Duration,
+    ) -> BoxFuture<'a, Result<Output>> {
+        Box::pin(async move {
+            self.answer(cmd, args).ok_or_else(|| {
+                anyhow!(
+                    "No recorded output for '{} {}' (replaying)",
+                    cmd,
+                    args.join(" ")
+                )
+            })
+        })
+    }
+}
+
+/// Process-wide executor, picked once from the environment
+static GLOBAL_EXECUTOR: LazyLock<Arc<dyn CommandExecutor>> = LazyLock::new(|| {
+    if let Some(path) = std::env::var_os(REPLAY_ENV) {
+        let path = PathBuf::from(path);
+        match ReplayExecutor::from_file(&path) {
+            Ok(replay) => {
+                println!("[REPLAY] Answering commands from {}", path.display());
+                return Arc::new(replay);
+            }
+            Err(e) => eprintln!("[ERROR] Can't replay {}: {}", path.display(), e),
+        }
+    }
+    if let Some(path) = std::env::var_os(RECORD_ENV) {
+        let path = PathBuf::from(path);
+        println!("[RECORD] Recording commands to {}", path.display());
+        return Arc::new(RecordingExecutor::new(path));
+    }
+    Arc::new(SystemExecutor)
+});
+
+tokio::task_local! {
+    /// Per-task override so tests can replay in parallel without touching the global
+    static TASK_EXECUTOR: Arc<dyn CommandExecutor>;
+}
+
+pub fn current_executor() -> Arc<dyn CommandExecutor> {
+    TASK_EXECUTOR
+        .try_with(Arc::clone)
+        .unwrap_or_else(|_| Arc::clone(&GLOBAL_EXECUTOR))
+}
+
+/// Run `future` with every command answered by `executor`. Doesn't reach tasks it
+/// spawns with `tokio::spawn`, which fall back to the global executor.
+#[cfg(test)]
+pub async fn with_executor<F: std::future::Future>(
+    executor: Arc<dyn CommandExecutor>,
+    future: F,
+) -> F::Output {
+    TASK_EXECUTOR.scope(executor, future).await
+}
+
+/// Replay `fixtures/commands/<name>.jsonl` for a test
+#[cfg(test)]
+pub fn replay_fixture(name: &str) -> Arc<dyn CommandExecutor> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures/commands")
+        .join(format!("{}.jsonl", name));
+    Arc::new(ReplayExecutor::from_file(&path).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECORDING: &str = r#"
+{"cmd":"brew","args":["--version"],"status":0,"stdout":"Homebrew 4.3.0\n"}
+{"cmd":"brew","args":["--version"],"status":0,"stdout":"Homebrew 4.4.0\n"}
+{"cmd":"npm","args":["outdated"],"status":1,"stdout":"{}"}
+"#;
+
+    #[tokio::test]
+    async fn replays_in_order_then_repeats_the_last_answer() {
+        let replay = ReplayExecutor::parse(RECORDING).unwrap();
+        let timeout = Duration::from_secs(1);
+        for expected in ["Homebrew 4.3.0\n", "Homebrew 4.4.0\n", "Homebrew 4.4.0\n"] {
+            let output = replay.run("brew", &["--version"], timeout).await.unwrap();
+            assert_eq!(String::from_utf8_lossy(&output.stdout), expected);
+            assert!(output.status.success());
+        }
+        let output = replay.run("npm", &["outdated"], timeout).await.unwrap();
+        assert_eq!(output.status.code(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn unrecorded_commands_fail() {
+        let replay: Arc<dyn CommandExecutor> = Arc::new(ReplayExecutor::parse(RECORDING).unwrap());
+        let result = with_executor(
+            replay,
+            super::super::run_command_with_timeout("brew", &["upgrade"], Duration::from_secs(1)),
+        )
+        .await;
+        assert!(result.is_err());
+    }
+}
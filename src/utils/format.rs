@@ -0,0 +1,245 @@
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+/// Number and date conventions for the user's locale (from LC_ALL / LC_NUMERIC / LANG)
+#[derive(Debug, Clone)]
+pub struct Locale {
+    pub group_separator: &'static str,
+    pub decimal_separator: &'static str,
+    pub date_format: &'static str,
+}
+
+impl Locale {
+    pub fn detect() -> Self {
+        let tag = ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+            .unwrap_or_else(|| "en_US".to_string());
+
+        Self::from_tag(&tag)
+    }
+
+    /// Parse tags like "de_DE.UTF-8" or "en-GB"
+    pub fn from_tag(tag: &str) -> Self {
+        let tag = tag.split('.').next().unwrap_or(tag).replace('-', "_");
+        let mut parts = tag.split('_');
+        let language = parts.next().unwrap_or("en").to_lowercase();
+        let region = parts.next().unwrap_or("").to_uppercase();
+
+        match (language.as_str(), region.as_str()) {
+            ("en", "US") | ("en", "") => Self {
+                group_separator: ",",
+                decimal_separator: ".",
+                date_format: "%b %-d, %Y",
+            },
+            ("en", _) => Self {
+                group_separator: ",",
+                decimal_separator: ".",
+                date_format: "%-d %b %Y",
+            },
+            ("de", "CH") => Self {
+                group_separator: "'",
+                decimal_separator: ".",
+                date_format: "%d.%m.%Y",
+            },
+            ("de", _) | ("da", _) | ("nb", _) | ("tr", _) => Self {
+                group_separator: ".",
+                decimal_separator: ",",
+                date_format: "%d.%m.%Y",
+            },
+            ("es", _) | ("it", _) | ("nl", _) | ("pt", _) | ("id", _) => Self {
+                group_separator: ".",
+                decimal_separator: ",",
+                date_format: "%d/%m/%Y",
+            },
+            ("fr", _) | ("ru", _) | ("pl", _) | ("cs", _) | ("sv", _) | ("fi", _) | ("uk", _) => {
+                Self {
+                    group_separator: "\u{202f}", // narrow no-break space
+                    decimal_separator: ",",
+                    date_format: "%d/%m/%Y",
+                }
+            }
+            ("ja", _) | ("zh", _) | ("ko", _) => Self {
+                group_separator: ",",
+                decimal_separator: ".",
+                date_format: "%Y/%m/%d",
+            },
+            _ => Self {
+                group_separator: ",",
+                decimal_separator: ".",
+                date_format: "%Y-%m-%d",
+            },
+        }
+    }
+}
+
+/// Locale is read once; changing LANG requires a restart like most desktop apps
+pub static LOCALE: LazyLock<Locale> = LazyLock::new(Locale::detect);
+
+/// KiB/MiB (1024) vs kB/MB (1000)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SizeUnits {
+    #[default]
+    Binary,
+    Decimal,
+}
+
+impl SizeUnits {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SizeUnits::Binary => "Binary (KiB, MiB)",
+            SizeUnits::Decimal => "Decimal (kB, MB)",
+        }
+    }
+}
+
+/// 1234567 -> "1,234,567" (or "1.234.567", "1 234 567" depending on locale)
+pub fn format_number(value: u64) -> String {
+    let digits = value.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push_str(LOCALE.group_separator);
+        }
+        out.push(ch);
+    }
+    out
+}
+
+/// Human readable size using the chosen unit system and the locale's decimal separator
+pub fn format_size(bytes: u64, units: SizeUnits) -> String {
+    let (base, suffixes): (f64, [&str; 5]) = match units {
+        SizeUnits::Binary => (1024.0, ["B", "KiB", "MiB", "GiB", "TiB"]),
+        SizeUnits::Decimal => (1000.0, ["B", "kB", "MB", "GB", "TB"]),
+    };
+
+    if (bytes as f64) < base {
+        return format!("{} {}", bytes, suffixes[0]);
+    }
+
+    let mut value = bytes as f64;
+    let mut idx = 0;
+    while value >= base && idx < suffixes.len() - 1 {
+        value /= base;
+        idx += 1;
+    }
+
+    let precision = if value < 10.0 { 1 } else { 0 };
+    let formatted = format!("{:.*}", precision, value).replace('.', LOCALE.decimal_separator);
+    format!("{} {}", formatted, suffixes[idx])
+}
+
+/// Absolute date in the user's local timezone and locale order
+pub fn format_date(when: DateTime<Utc>) -> String {
+    when.with_timezone(&Local)
+        .format(LOCALE.date_format)
+        .to_string()
+}
+
+/// Absolute date and time, for tooltips next to relative times
+pub fn format_datetime(when: DateTime<Utc>) -> String {
+    format!(
+        "{} {}",
+        format_date(when),
+        when.with_timezone(&Local).format("%H:%M")
+    )
+}
+
+/// Units for `format_relative`, in seconds (a month and year are their average lengths)
+const RELATIVE_UNITS: [(u64, &str); 6] = [
+    (60, "minute"),
+    (3_600, "hour"),
+    (86_400, "day"),
+    (604_800, "week"),
+    (2_629_800, "month"),
+    (31_557_600, "year"),
+];
+
+/// "just now", "5 minutes ago", "2 days ago", "in 3 hours"
+pub fn format_relative(when: DateTime<Utc>) -> String {
+    let delta = Utc::now().signed_duration_since(when);
+    let future = delta.num_seconds() < 0;
+    let seconds = delta.num_seconds().unsigned_abs();
+
+    if seconds < 45 {
+        return "just now".to_string();
+    }
+
+    // Round within a unit, moving up one when that would read "60 minutes" or "24 hours"
+    let (amount, unit) = RELATIVE_UNITS
+        .iter()
+        .enumerate()
+        .find_map(|(i, &(size, unit))| {
+            let amount = (seconds + size / 2) / size;
+            let next = RELATIVE_UNITS
+                .get(i + 1)
+                .map_or(u64::MAX, |&(next, _)| next);
+            (amount.saturating_mul(size) < next).then_some((amount, unit))
+        })
+        .unwrap_or((1, "year"));
+    let amount = amount.max(1);
+    let plural = if amount == 1 { "" } else { "s" };
+
+    if future {
+        format!("in {} {}{}", amount, unit, plural)
+    } else {
+        format!("{} {}{} ago", amount, unit, plural)
+    }
+}
+
+/// %XX-escape everything outside RFC 3986's unreserved characters, so the text comes
+/// back whole from `percent_decode` whatever URL part it's put in
+pub fn percent_encode(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    out
+}
+
+/// Undo %XX escapes (URLs from a browser or `open`, userinfo in index URLs)
+pub fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_times_carry_into_the_next_unit() {
+        let ago = |seconds: i64| format_relative(Utc::now() - chrono::Duration::seconds(seconds));
+        assert_eq!(ago(10), "just now");
+        assert_eq!(ago(90), "2 minutes ago");
+        assert_eq!(ago(3_590), "1 hour ago");
+        assert_eq!(ago(86_000), "1 day ago");
+        assert_eq!(ago(6 * 86_400 + 50_000), "1 week ago");
+        assert_eq!(ago(31_000_000), "1 year ago");
+        assert_eq!(ago(5 * 3_600), "5 hours ago");
+    }
+}
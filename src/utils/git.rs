@@ -0,0 +1,47 @@
+use anyhow::{anyhow, Result};
+
+/// Transports a clone of a user-supplied URL may use: the `GIT_ALLOW_PROTOCOL=
+/// https:ssh:git:file` policy, passed as `-c protocol.*` options so it also holds when the
+/// command runs over ssh or in a container, where depmgr's environment doesn't follow.
+/// Keeps `ext::` (which runs a command) and other remote helpers out.
+const ALLOWED_PROTOCOLS: [&str; 4] = ["https", "ssh", "git", "file"];
+
+/// Arguments for `git clone <options> -- <url> <target>`. The URL comes after `--` so it
+/// can't be read as an option; one starting with `-` is refused outright.
+pub fn clone_args(url: &str, target: &str, options: &[&str]) -> Result<Vec<String>> {
+    if url.starts_with('-') {
+        return Err(anyhow!("Refusing to clone '{}': not a repository URL", url));
+    }
+    let mut args = vec!["-c".to_string(), "protocol.allow=never".to_string()];
+    for protocol in ALLOWED_PROTOCOLS {
+        args.push("-c".to_string());
+        args.push(format!("protocol.{}.allow=always", protocol));
+    }
+    args.push("clone".to_string());
+    args.extend(options.iter().map(|o| o.to_string()));
+    args.extend(["--".to_string(), url.to_string(), target.to_string()]);
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn urls_cannot_become_options() {
+        assert!(clone_args("--upload-pack=touch /tmp/x", "/tmp/t", &[]).is_err());
+        let args = clone_args("https://github.com/a/b", "/tmp/t", &["--depth", "1"]).unwrap();
+        assert_eq!(args[..2], ["-c", "protocol.allow=never"]);
+        assert_eq!(
+            args[args.len() - 6..],
+            [
+                "clone",
+                "--depth",
+                "1",
+                "--",
+                "https://github.com/a/b",
+                "/tmp/t"
+            ]
+        );
+    }
+}
@@ -1,6 +1,15 @@
-use reqwest::Client;
+use crate::utils::cache::{get_cached, set_cached};
+use crate::utils::metrics::SendTimed;
+use anyhow::{anyhow, Result};
+use reqwest::header::{HeaderValue, AUTHORIZATION, ETAG, IF_NONE_MATCH};
+use reqwest::{redirect, Client, Request, RequestBuilder, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
+/// How long a response is kept for revalidation, well past the callers' own freshness TTLs
+const REVALIDATE_TTL: u64 = 30 * 24 * 3600;
+
 /// Create a high-performance HTTP client with connection pooling
 pub fn create_http_client() -> Client {
     Client::builder()
@@ -8,6 +17,112 @@ pub fn create_http_client() -> Client {
         .pool_idle_timeout(Duration::from_secs(90))
         .timeout(Duration::from_secs(30))
         .gzip(true) // Enable compression
+        .redirect(redirect::Policy::custom(|attempt| {
+            // A token sent over https must not follow a redirect onto plain http
+            let downgrade = attempt.url().scheme() != "https"
+                && attempt.previous().iter().any(|url| url.scheme() == "https");
+            if downgrade {
+                attempt.error("refusing to follow a redirect from https to http")
+            } else if attempt.previous().len() >= 10 {
+                attempt.error("too many redirects")
+            } else {
+                attempt.follow()
+            }
+        }))
         .build()
         .expect("Failed to create HTTP client")
 }
+
+/// Host whose credentials may go with a request to `url`: https only, so a token
+/// never crosses the network in the clear
+fn credential_host(url: &Url) -> Option<&str> {
+    (url.scheme() == "https").then(|| url.host_str()).flatten()
+}
+
+/// Add the host's keychain token (`utils::secrets`) or the credentials its package
+/// manager is configured with (`utils::registries`), unless the request has its own
+pub async fn authorize(request: &mut Request) {
+    if request.headers().contains_key(AUTHORIZATION) {
+        return;
+    }
+    let Some(host) = credential_host(request.url()).map(String::from) else {
+        return;
+    };
+    let auth = match crate::utils::secrets::token_for(&host).await {
+        Some(token) => Some(format!("Bearer {}", token)),
+        None => crate::utils::registries::auth_for(&host).map(String::from),
+    };
+    if let Some(Ok(mut value)) = auth.map(|auth| HeaderValue::from_str(&auth)) {
+        value.set_sensitive(true);
+        request.headers_mut().insert(AUTHORIZATION, value);
+    }
+}
+
+/// A response body along with the ETag it was served with
+#[derive(Serialize, Deserialize)]
+struct Validated<T> {
+    etag: String,
+    data: T,
+}
+
+/// The body last stored under `key` by `get_json_revalidated`, without asking the server
+pub fn revalidated<T: DeserializeOwned>(key: &str) -> Option<T> {
+    get_cached::<Validated<T>>(key).map(|stored| stored.data)
+}
+
+/// GET a JSON body, sending `If-None-Match` with the ETag stored under `key` so an
+/// unchanged resource comes back as a bodiless 304 (which GitHub doesn't count against
+/// the rate limit). None on 404.
+pub async fn get_json_revalidated<T>(request: RequestBuilder, key: &str) -> Result<Option<T>>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let stored = get_cached::<Validated<T>>(key);
+    let request = match &stored {
+        Some(stored) => request.header(IF_NONE_MATCH, &stored.etag),
+        None => request,
+    };
+    let response = request.send_timed().await?;
+    match response.status() {
+        StatusCode::NOT_MODIFIED if stored.is_some() => {
+            println!("[HTTP] {} not modified", response.url());
+            return Ok(stored.map(|stored| stored.data));
+        }
+        StatusCode::NOT_FOUND => return Ok(None),
+        status if !status.is_success() => {
+            return Err(anyhow!("{} returned {}", response.url(), status));
+        }
+        _ => {}
+    }
+
+    let etag = response
+        .headers()
+        .get(ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+    let data: T = response.json().await?;
+    if let Some(etag) = etag {
+        set_cached(
+            key.to_string(),
+            &Validated { etag, data: &data },
+            REVALIDATE_TTL,
+        );
+    }
+    Ok(Some(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn credentials_only_go_over_https() {
+        let url = |s: &str| Url::parse(s).unwrap();
+        assert_eq!(
+            credential_host(&url("https://npm.corp.example/pkg")),
+            Some("npm.corp.example")
+        );
+        assert_eq!(credential_host(&url("http://npm.corp.example/pkg")), None);
+        assert_eq!(credential_host(&url("file:///tmp/pkg")), None);
+    }
+}
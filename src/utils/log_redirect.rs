@@ -0,0 +1,71 @@
+//! Scans and commands log with `println!`. Output that other programs read (the
+//! TUI's screen, `--format csv`) needs stdout to itself, so the logs are moved aside
+//! while it's in use.
+
+#[cfg(unix)]
+use std::io::Write;
+
+/// Puts stdout (and stderr) back as they were when dropped
+pub struct LogRedirect {
+    #[cfg(unix)]
+    saved: Vec<(i32, i32)>, // descriptor, and a copy of what it pointed at
+}
+
+#[cfg(unix)]
+impl LogRedirect {
+    /// Point `fds` at `target`, keeping copies of what they were
+    fn redirect(target: i32, fds: &[i32]) -> Self {
+        let _ = std::io::stdout().flush();
+        // SAFETY: plain descriptor juggling on descriptors this process owns
+        let saved = unsafe {
+            fds.iter()
+                .map(|&fd| {
+                    let saved = libc::dup(fd);
+                    libc::dup2(target, fd);
+                    (fd, saved)
+                })
+                .collect()
+        };
+        Self { saved }
+    }
+
+    /// Both stdout and stderr into a log file, appended to
+    pub fn to_file(path: &std::path::Path) -> std::io::Result<Self> {
+        use std::os::fd::AsRawFd;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        Ok(Self::redirect(file.as_raw_fd(), &[1, 2]))
+    }
+
+    /// Logs printed to stdout go to stderr instead
+    pub fn to_stderr() -> Self {
+        Self::redirect(2, &[1])
+    }
+}
+
+/// No descriptors to swap here; logs stay on stdout
+#[cfg(not(unix))]
+impl LogRedirect {
+    pub fn to_stderr() -> Self {
+        Self {}
+    }
+}
+
+#[cfg(unix)]
+impl Drop for LogRedirect {
+    fn drop(&mut self) {
+        let _ = std::io::stdout().flush();
+        // SAFETY: restores the descriptors saved in `redirect`
+        unsafe {
+            for &(fd, saved) in &self.saved {
+                libc::dup2(saved, fd);
+                libc::close(saved);
+            }
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+/// Upper bounds in seconds; registry calls land in the low buckets, compiles and big
+/// bottle downloads in the high ones
+const BUCKETS: [f64; 12] = [
+    0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0,
+];
+
+/// Name, type and help text of every metric, in the order they're exposed
+const METRICS: [(&str, &str, &str); 11] = [
+    (
+        "depmgr_scan_duration_seconds",
+        "histogram",
+        "Time to list one manager's packages and check them for updates",
+    ),
+    (
+        "depmgr_scan_failures_total",
+        "counter",
+        "Manager scans that failed",
+    ),
+    (
+        "depmgr_registry_request_duration_seconds",
+        "histogram",
+        "Latency of HTTP requests to registries and advisory APIs",
+    ),
+    (
+        "depmgr_registry_request_failures_total",
+        "counter",
+        "Registry requests that errored or returned a non-2xx status",
+    ),
+    (
+        "depmgr_operation_duration_seconds",
+        "histogram",
+        "Time taken by update/install/uninstall/self-update operations",
+    ),
+    (
+        "depmgr_operations_total",
+        "counter",
+        "Operations run, by outcome",
+    ),
+    ("depmgr_packages", "gauge", "Installed packages per manager"),
+    (
+        "depmgr_outdated_packages",
+        "gauge",
+        "Outdated packages per manager",
+    ),
+    (
+        "depmgr_vulnerable_packages",
+        "gauge",
+        "Packages with known vulnerabilities per manager",
+    ),
+    (
+        "depmgr_deferred_scans_total",
+        "counter",
+        "Scheduled scans put off because of battery power or a metered connection",
+    ),
+    (
+        "depmgr_last_scan_timestamp_seconds",
+        "gauge",
+        "Unix time the last full scan finished",
+    ),
+];
+
+#[derive(Default)]
+struct Histogram {
+    counts: [u64; BUCKETS.len()], // per bucket, not cumulative
+    count: u64,
+    sum: f64,
+}
+
+/// Series keyed by metric name, then by rendered label set (`{manager="npm"}`)
+#[derive(Default)]
+struct Registry {
+    values: BTreeMap<&'static str, BTreeMap<String, f64>>, // counters and gauges
+    histograms: BTreeMap<&'static str, BTreeMap<String, Histogram>>,
+}
+
+static REGISTRY: LazyLock<Mutex<Registry>> = LazyLock::new(Mutex::default);
+
+fn label_set(labels: &[(&str, &str)]) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+    let pairs: Vec<String> = labels
+        .iter()
+        .map(|(k, v)| {
+            let v = v
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            format!("{}=\"{}\"", k, v)
+        })
+        .collect();
+    format!("{{{}}}", pairs.join(","))
+}
+
+pub fn inc_counter(name: &'static str, labels: &[(&str, &str)]) {
+    let mut registry = REGISTRY.lock().unwrap();
+    *registry
+        .values
+        .entry(name)
+        .or_default()
+        .entry(label_set(labels))
+        .or_default() += 1.0;
+}
+
+pub fn set_gauge(name: &'static str, labels: &[(&str, &str)], value: f64) {
+    let mut registry = REGISTRY.lock().unwrap();
+    registry
+        .values
+        .entry(name)
+        .or_default()
+        .insert(label_set(labels), value);
+}
+
+pub fn observe(name: &'static str, labels: &[(&str, &str)], elapsed: Duration) {
+    let seconds = elapsed.as_secs_f64();
+    let mut registry = REGISTRY.lock().unwrap();
+    let histogram = registry
+        .histograms
+        .entry(name)
+        .or_default()
+        .entry(label_set(labels))
+        .or_default();
+    if let Some(idx) = BUCKETS.iter().position(|b| seconds <= *b) {
+        histogram.counts[idx] += 1;
+    }
+    histogram.count += 1;
+    histogram.sum += seconds;
+}
+
+/// Time an update/install/uninstall and count it as succeeded or failed
+pub async fn time_operation<T>(
+    operation: &str,
+    manager: &str,
+    future: impl Future<Output = anyhow::Result<T>>,
+) -> anyhow::Result<T> {
+    let start = Instant::now();
+    let result = future.await;
+    let labels = [("operation", operation), ("manager", manager)];
+    observe(
+        "depmgr_operation_duration_seconds",
+        &labels,
+        start.elapsed(),
+    );
+    let outcome = if result.is_ok() { "success" } else { "failure" };
+    inc_counter(
+        "depmgr_operations_total",
+        &[
+            ("operation", operation),
+            ("manager", manager),
+            ("result", outcome),
+        ],
+    );
+    result
+}
+
+/// `.send_timed()` in place of `.send()` records latency and failures per host, and
+/// sends the host's credentials over https (`http_client::authorize`)
+pub trait SendTimed {
+    fn send_timed(self) -> impl Future<Output = reqwest::Result<reqwest::Response>> + Send;
+}
+
+impl SendTimed for reqwest::RequestBuilder {
+    async fn send_timed(self) -> reqwest::Result<reqwest::Response> {
+        let (client, request) = self.build_split();
+        let mut request = request?;
+        let host = request.url().host_str().unwrap_or("unknown").to_string();
+        crate::utils::http_client::authorize(&mut request).await;
+        let labels = [("host", host.as_str())];
+
+        let _in_flight = crate::utils::activity::request();
+        let start = Instant::now();
+        let result = client.execute(request).await;
+        observe(
+            "depmgr_registry_request_duration_seconds",
+            &labels,
+            start.elapsed(),
+        );
+        if !matches!(&result, Ok(response) if response.status().is_success()) {
+            inc_counter("depmgr_registry_request_failures_total", &labels);
+        }
+        result
+    }
+}
+
+/// Everything recorded so far in the Prometheus text exposition format
+pub fn render() -> String {
+    let registry = REGISTRY.lock().unwrap();
+    let mut out = String::new();
+
+    for (name, kind, help) in METRICS {
+        out.push_str(&format!(
+            "# HELP {} {}\n# TYPE {} {}\n",
+            name, help, name, kind
+        ));
+        if let Some(series) = registry.values.get(name) {
+            for (labels, value) in series {
+                out.push_str(&format!("{}{} {}\n", name, labels, value));
+            }
+        }
+        if let Some(series) = registry.histograms.get(name) {
+            for (labels, histogram) in series {
+                // `le` goes alongside the series' own labels
+                let with_le = |le: &str| match labels.strip_suffix('}') {
+                    Some(inner) => format!("{},le=\"{}\"}}", inner, le),
+                    None => format!("{{le=\"{}\"}}", le),
+                };
+                let mut cumulative = 0;
+                for (bound, count) in BUCKETS.iter().zip(histogram.counts) {
+                    cumulative += count;
+                    out.push_str(&format!(
+                        "{}_bucket{} {}\n",
+                        name,
+                        with_le(&bound.to_string()),
+                        cumulative
+                    ));
+                }
+                out.push_str(&format!(
+                    "{}_bucket{} {}\n",
+                    name,
+                    with_le("+Inf"),
+                    histogram.count
+                ));
+                out.push_str(&format!("{}_sum{} {}\n", name, labels, histogram.sum));
+                out.push_str(&format!("{}_count{} {}\n", name, labels, histogram.count));
+            }
+        }
+    }
+
+    out
+}
@@ -1,5 +1,25 @@
+pub mod activity;
+pub mod arch;
+pub mod broken;
 pub mod cache;
 pub mod command;
+pub mod disk;
+pub mod dock;
+pub mod executor;
+pub mod format;
+pub mod git;
 pub mod http_client;
+pub mod log_redirect;
+pub mod metrics;
+pub mod paths;
+pub mod power;
+pub mod processes;
+pub mod prompt;
+pub mod registries;
+pub mod secrets;
+pub mod target;
+#[cfg(test)]
+pub mod test_dir;
+pub mod version;
 
 pub use command::{command_exists, run_command_with_timeout};
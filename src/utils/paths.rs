@@ -0,0 +1,63 @@
+use std::path::{Path, PathBuf};
+
+/// The user's home directory: $HOME, or %USERPROFILE% on Windows where HOME is rarely set
+pub fn home_dir() -> PathBuf {
+    std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("/Users"))
+}
+
+/// A leading `~` as the home directory: "~/code" is $HOME/code
+pub fn expand_home(path: &Path) -> PathBuf {
+    match path.strip_prefix("~") {
+        Ok(rest) => home_dir().join(rest),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// ~/.config/depmgr (or $XDG_CONFIG_HOME/depmgr)
+pub fn config_dir() -> PathBuf {
+    std::env::var("XDG_CONFIG_HOME")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home_dir().join(".config"))
+        .join("depmgr")
+}
+
+/// ~/.local/share/depmgr (or $XDG_DATA_HOME/depmgr) - state the app writes itself
+pub fn data_dir() -> PathBuf {
+    std::env::var("XDG_DATA_HOME")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home_dir().join(".local").join("share"))
+        .join("depmgr")
+}
+
+/// ~/.cache/depmgr (or $XDG_CACHE_HOME/depmgr) - safe to delete at any time
+pub fn cache_dir() -> PathBuf {
+    std::env::var("XDG_CACHE_HOME")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home_dir().join(".cache"))
+        .join("depmgr")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_a_leading_tilde_only() {
+        assert_eq!(expand_home(Path::new("~/code")), home_dir().join("code"));
+        assert_eq!(expand_home(Path::new("~")), home_dir());
+        assert_eq!(expand_home(Path::new("/srv/~")), PathBuf::from("/srv/~"));
+        assert_eq!(
+            expand_home(Path::new("~bob/code")),
+            PathBuf::from("~bob/code")
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use crate::config::BackgroundSettings;
+use crate::utils::run_command_with_timeout;
+use std::time::Duration;
+
+/// What the machine is running on right now, as far as we can tell
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Conditions {
+    pub on_battery: bool,
+    pub metered: bool,
+}
+
+#[cfg(target_os = "macos")]
+async fn on_battery() -> bool {
+    // "Now drawing from 'Battery Power'" / "'AC Power'"
+    match run_command_with_timeout("pmset", &["-g", "batt"], Duration::from_secs(5)).await {
+        Ok(output) => String::from_utf8_lossy(&output.stdout).contains("'Battery Power'"),
+        Err(_) => false,
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+async fn on_battery() -> bool {
+    // Batteries present and no mains adapter online; desktops have no battery at all
+    let Ok(entries) = std::fs::read_dir("/sys/class/power_supply") else {
+        return false;
+    };
+    let mut has_battery = false;
+    let mut mains_online = false;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let read = |file: &str| std::fs::read_to_string(path.join(file)).unwrap_or_default();
+        match read("type").trim() {
+            "Battery" => has_battery = true,
+            "Mains" | "USB" => mains_online |= read("online").trim() == "1",
+            _ => {}
+        }
+    }
+    has_battery && !mains_online
+}
+
+#[cfg(target_os = "macos")]
+async fn metered() -> bool {
+    // macOS doesn't expose Low Data Mode to the command line, so recognize the usual
+    // hotspot: an iPhone's Personal Hotspot hands out 172.20.10.0/28 with itself at .1
+    match run_command_with_timeout("route", &["-n", "get", "default"], Duration::from_secs(5)).await
+    {
+        Ok(output) => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .any(|l| l.trim() == "gateway: 172.20.10.1"),
+        Err(_) => false,
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+async fn metered() -> bool {
+    // NetworkManager tracks this per device: "GENERAL.METERED:yes" or "yes (guessed)"
+    match run_command_with_timeout(
+        "nmcli",
+        &["-t", "-f", "GENERAL.METERED", "dev", "show"],
+        Duration::from_secs(5),
+    )
+    .await
+    {
+        Ok(output) => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .any(|l| l.starts_with("GENERAL.METERED:yes")),
+        Err(_) => false,
+    }
+}
+
+pub async fn current_conditions() -> Conditions {
+    Conditions {
+        on_battery: on_battery().await,
+        metered: metered().await,
+    }
+}
+
+/// Why background work should wait, or None to go ahead
+pub async fn defer_reason(settings: &BackgroundSettings) -> Option<&'static str> {
+    if !settings.defer_on_battery && !settings.defer_on_metered {
+        return None;
+    }
+    let conditions = current_conditions().await;
+    if settings.defer_on_metered && conditions.metered {
+        Some("on a metered connection")
+    } else if settings.defer_on_battery && conditions.on_battery {
+        Some("on battery power")
+    } else {
+        None
+    }
+}
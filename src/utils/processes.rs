@@ -0,0 +1,244 @@
+use crate::models::{Package, PackageManager};
+use crate::utils::paths::home_dir;
+use crate::utils::run_command_with_timeout;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+/// Processes on this machine started from one of a package's binaries
+#[derive(Debug, Clone)]
+pub struct RunningProcesses {
+    /// Process name -> (lowest PID, how many are running)
+    pub by_name: BTreeMap<String, (u32, usize)>,
+}
+
+impl RunningProcesses {
+    /// "postgres (PID 812) is running with 3 more processes"
+    pub fn summary(&self) -> String {
+        self.by_name
+            .iter()
+            .map(|(name, (pid, count))| match count {
+                1 => format!("{} (PID {}) is running", name, pid),
+                n => format!(
+                    "{} (PID {}) is running with {} more processes",
+                    name,
+                    pid,
+                    n - 1
+                ),
+            })
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// A running process: its executable, and any absolute paths on its command line (the
+/// script an interpreter or shim is running)
+struct ProcessInfo {
+    pid: u32,
+    name: String,
+    paths: Vec<PathBuf>,
+}
+
+type Snapshot = Option<(Instant, Arc<Vec<ProcessInfo>>)>;
+
+/// The last process listing, reused for a few seconds so repeated checks stay cheap
+static SNAPSHOT: LazyLock<Mutex<Snapshot>> = LazyLock::new(|| Mutex::new(None));
+const SNAPSHOT_TTL: Duration = Duration::from_secs(5);
+
+fn take_snapshot() -> Vec<ProcessInfo> {
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing()
+            .with_exe(UpdateKind::OnlyIfNotSet)
+            .with_cmd(UpdateKind::OnlyIfNotSet),
+    );
+    system
+        .processes()
+        .values()
+        .map(|process| ProcessInfo {
+            pid: process.pid().as_u32(),
+            name: process.name().to_string_lossy().to_string(),
+            paths: process
+                .exe()
+                .map(Path::to_path_buf)
+                .into_iter()
+                .chain(
+                    process
+                        .cmd()
+                        .iter()
+                        .skip(1)
+                        .map(PathBuf::from)
+                        .filter(|arg| arg.is_absolute()),
+                )
+                .collect(),
+        })
+        .collect()
+}
+
+/// Every process on this machine, listed off the async threads
+async fn snapshot() -> Arc<Vec<ProcessInfo>> {
+    if let Some((taken, processes)) = SNAPSHOT.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        if taken.elapsed() < SNAPSHOT_TTL {
+            return Arc::clone(processes);
+        }
+    }
+    let processes = Arc::new(
+        tokio::task::spawn_blocking(take_snapshot)
+            .await
+            .unwrap_or_default(),
+    );
+    *SNAPSHOT.lock().unwrap_or_else(|e| e.into_inner()) =
+        Some((Instant::now(), Arc::clone(&processes)));
+    processes
+}
+
+/// Where a package's files live, as path fragments: Homebrew keeps each formula under
+/// Cellar/<name> (opt/<name> links there), npm, pipx and uv give each its own directory.
+/// Cargo binaries are known by path; other managers fall back to a binary named like the
+/// package.
+enum Install {
+    Dirs(Vec<String>),
+    Binaries(Vec<PathBuf>),
+    Named(String),
+}
+
+impl Install {
+    fn of(pkg: &Package) -> Self {
+        let name = &pkg.name;
+        match pkg.manager {
+            PackageManager::Homebrew => Self::Dirs(vec![
+                format!("/Cellar/{}/", name),
+                format!("/opt/{}/", name),
+            ]),
+            PackageManager::Npm => Self::Dirs(vec![format!("/node_modules/{}/", name)]),
+            PackageManager::Pipx => Self::Dirs(vec![format!("/pipx/venvs/{}/", name)]),
+            PackageManager::Uv => Self::Dirs(vec![format!("/uv/tools/{}/", name)]),
+            PackageManager::Cargo => Self::Binaries(
+                crate::managers::cargo::installed_binaries()
+                    .remove(name)
+                    .unwrap_or_default(),
+            ),
+            _ => Self::Named(name.clone()),
+        }
+    }
+
+    /// Whether this executable or script is one of the package's files
+    fn contains(&self, path: &Path) -> bool {
+        match self {
+            Self::Dirs(dirs) => {
+                let path = path.to_string_lossy().replace('\\', "/");
+                dirs.iter().any(|dir| path.contains(dir.as_str()))
+            }
+            Self::Binaries(binaries) => binaries.iter().any(|binary| binary == path),
+            Self::Named(name) => path
+                .file_stem()
+                .is_some_and(|stem| stem.to_string_lossy() == *name),
+        }
+    }
+}
+
+/// What's running from the package right now, or None if nothing is
+pub async fn running_from(pkg: &Package) -> Option<RunningProcesses> {
+    let install = Install::of(pkg);
+    let mut by_name: BTreeMap<String, (u32, usize)> = BTreeMap::new();
+    for process in snapshot().await.iter() {
+        if !process.paths.iter().any(|path| install.contains(path)) {
+            continue;
+        }
+        let entry = by_name
+            .entry(process.name.clone())
+            .or_insert((process.pid, 0));
+        entry.0 = entry.0.min(process.pid);
+        entry.1 += 1;
+    }
+
+    (!by_name.is_empty()).then_some(RunningProcesses { by_name })
+}
+
+/// The first of these apps that's running, ignoring case: by process name, or by the
+/// executable's name for apps whose process renames itself
+pub async fn first_running(names: &[String]) -> Option<String> {
+    let processes = snapshot().await;
+    names
+        .iter()
+        .find(|name| {
+            processes.iter().any(|process| {
+                process.name.eq_ignore_ascii_case(name)
+                    || process
+                        .paths
+                        .first()
+                        .and_then(|exe| exe.file_stem())
+                        .is_some_and(|stem| stem.to_string_lossy().eq_ignore_ascii_case(name))
+            })
+        })
+        .cloned()
+}
+
+/// Whether `brew services` has the formula started for this user (it writes a launchd
+/// agent on macOS and a systemd user unit on Linux, and removes it on stop)
+pub fn brew_service_started(formula: &str) -> bool {
+    let home = home_dir();
+    home.join(format!(
+        "Library/LaunchAgents/homebrew.mxcl.{}.plist",
+        formula
+    ))
+    .exists()
+        || home
+            .join(format!(".config/systemd/user/homebrew.{}.service", formula))
+            .exists()
+}
+
+/// `brew services stop|start <formula>`; failures are logged, not fatal to the update
+pub async fn brew_service(formula: &str, action: &str) {
+    println!("[SERVICES] {} {}", action, formula);
+    match run_command_with_timeout(
+        "brew",
+        &["services", action, formula],
+        Duration::from_secs(60),
+    )
+    .await
+    {
+        Ok(output) if output.status.success() => {}
+        Ok(output) => eprintln!(
+            "[SERVICES] Failed to {} {}: {}",
+            action,
+            formula,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+        Err(e) => eprintln!("[SERVICES] Failed to {} {}: {}", action, formula, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binaries_and_scripts_are_matched_by_install_dir() {
+        let postgres = Package::new("postgresql@16", PackageManager::Homebrew, "16.3");
+        let install = Install::of(&postgres);
+        assert!(install.contains(Path::new(
+            "/opt/homebrew/Cellar/postgresql@16/16.3/bin/postgres"
+        )));
+        assert!(!install.contains(Path::new(
+            "/opt/homebrew/Cellar/postgresql@15/15.7/bin/postgres"
+        )));
+
+        // `node .../typescript/bin/tsc` is typescript running, whatever the process is called
+        let typescript = Install::of(&Package::new("typescript", PackageManager::Npm, "5.4.5"));
+        assert!(typescript.contains(Path::new("/usr/local/lib/node_modules/typescript/bin/tsc")));
+        assert!(!typescript.contains(Path::new("/usr/local/bin/node")));
+
+        let running = RunningProcesses {
+            by_name: BTreeMap::from([("postgres".to_string(), (812, 4))]),
+        };
+        assert_eq!(
+            running.summary(),
+            "postgres (PID 812) is running with 3 more processes"
+        );
+    }
+}
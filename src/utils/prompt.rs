@@ -0,0 +1,157 @@
+use dashmap::DashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::LazyLock;
+use std::time::Instant;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+/// What the user decided to do with a command that is waiting for input
+#[derive(Debug, Clone)]
+pub enum PromptReply {
+    Respond(String),
+    Abort,
+}
+
+/// A running command that looks like it is blocked on stdin
+#[derive(Debug, Clone)]
+pub struct PendingPrompt {
+    pub id: u64,
+    pub command: String,
+    pub prompt: String,
+    pub detected_at: Instant,
+    reply_tx: UnboundedSender<PromptReply>,
+}
+
+impl PendingPrompt {
+    /// Password prompts get a masked input field in the UI
+    pub fn is_secret(&self) -> bool {
+        let lower = self.prompt.to_lowercase();
+        lower.contains("password") || lower.contains("passphrase")
+    }
+}
+
+/// Commands currently waiting for user input, keyed by prompt id.
+/// Written by the command runner (Tokio tasks), read by the GUI thread.
+static PENDING_PROMPTS: LazyLock<DashMap<u64, PendingPrompt>> = LazyLock::new(DashMap::new);
+
+static NEXT_PROMPT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Whether a frontend shows pending prompts. Without one nobody can answer, so commands
+/// aren't held for input and their timeout runs as usual.
+static PROMPTS_SHOWN: AtomicBool = AtomicBool::new(false);
+
+/// Called by the GUI, which lists pending prompts with an answer field
+pub fn show_prompts() {
+    PROMPTS_SHOWN.store(true, Ordering::Relaxed);
+}
+
+pub fn prompts_shown() -> bool {
+    PROMPTS_SHOWN.load(Ordering::Relaxed)
+}
+
+/// Whether commands share the terminal's stdin, so the user can answer them there.
+/// Off for the daemon, the server and the TUI: their commands get EOF and fail fast.
+static PROMPTS_ON_TERMINAL: AtomicBool = AtomicBool::new(false);
+
+/// Called by the headless CLI commands, which run in the user's terminal
+pub fn answer_prompts_on_terminal() {
+    PROMPTS_ON_TERMINAL.store(true, Ordering::Relaxed);
+}
+
+pub fn prompts_on_terminal() -> bool {
+    PROMPTS_ON_TERMINAL.load(Ordering::Relaxed)
+}
+
+/// Register a prompt and get the channel the UI's answer will arrive on
+pub fn register_prompt(command: String, prompt: String) -> (u64, UnboundedReceiver<PromptReply>) {
+    let id = NEXT_PROMPT_ID.fetch_add(1, Ordering::Relaxed);
+    let (reply_tx, reply_rx) = unbounded_channel();
+
+    println!("[PROMPT] '{}' is waiting for input: {}", command, prompt);
+
+    PENDING_PROMPTS.insert(
+        id,
+        PendingPrompt {
+            id,
+            command,
+            prompt,
+            detected_at: Instant::now(),
+            reply_tx,
+        },
+    );
+
+    (id, reply_rx)
+}
+
+pub fn unregister_prompt(id: u64) {
+    PENDING_PROMPTS.remove(&id);
+}
+
+/// Snapshot of all pending prompts, oldest first
+pub fn pending_prompts() -> Vec<PendingPrompt> {
+    let mut prompts: Vec<PendingPrompt> = PENDING_PROMPTS
+        .iter()
+        .map(|entry| entry.value().clone())
+        .collect();
+    prompts.sort_by_key(|p| p.id);
+    prompts
+}
+
+/// Send a reply to a waiting command. The runner removes the prompt once handled.
+pub fn reply_to_prompt(id: u64, reply: PromptReply) {
+    if let Some((_, prompt)) = PENDING_PROMPTS.remove(&id) {
+        let _ = prompt.reply_tx.send(reply);
+    }
+}
+
+/// Heuristic: does the tail of a command's output look like it is asking something?
+pub fn looks_like_prompt(output: &str) -> bool {
+    // An unterminated last line is the strongest signal ("Password:", "Continue? ")
+    if !output.ends_with('\n') && !output.trim().is_empty() {
+        return true;
+    }
+
+    let last_line = match output.lines().rev().find(|l| !l.trim().is_empty()) {
+        Some(line) => line.trim().to_lowercase(),
+        None => return false,
+    };
+
+    last_line.ends_with('?')
+        || last_line.ends_with(':')
+        || last_line.contains("[y/n]")
+        || last_line.contains("(y/n)")
+        || last_line.contains("[yes/no]")
+}
+
+/// Last few lines of output, which is what the user needs to see to answer
+pub fn prompt_excerpt(output: &str) -> String {
+    let lines: Vec<&str> = output.lines().filter(|l| !l.trim().is_empty()).collect();
+    let start = lines.len().saturating_sub(5);
+    lines[start..].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prompts_are_questions_left_hanging() {
+        assert!(looks_like_prompt("Password:"));
+        assert!(looks_like_prompt("Proceed with installation? "));
+        assert!(looks_like_prompt("Removing 3 packages\nContinue [y/N]? \n"));
+        assert!(looks_like_prompt("Overwrite existing file? (y/n)\n"));
+        assert!(looks_like_prompt("Enter passphrase for key:\n"));
+
+        assert!(!looks_like_prompt(""));
+        assert!(!looks_like_prompt("\n\n"));
+        assert!(!looks_like_prompt(
+            "==> Upgrading jq\n==> Pouring jq.bottle.tar.gz\n"
+        ));
+        assert!(!looks_like_prompt("added 1 package in 2s\n"));
+    }
+
+    #[test]
+    fn excerpt_keeps_the_last_lines() {
+        let output = "1\n2\n\n3\n4\n5\n6\nPassword:";
+        assert_eq!(prompt_excerpt(output), "3\n4\n5\n6\nPassword:");
+    }
+}
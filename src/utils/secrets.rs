@@ -0,0 +1,207 @@
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::LazyLock;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+/// Keychain service name every token is stored under, with the host as the account
+const SERVICE: &str = "depmgr";
+
+/// Run a keychain tool on this machine (never the audited target), feeding `input`
+/// on stdin so secrets stay out of the process list where the tool allows it
+async fn keychain(cmd: &str, args: &[&str], input: Option<&str>) -> Result<String> {
+    let mut child = tokio::process::Command::new(cmd)
+        .args(args)
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| anyhow!("Failed to run {}: {}", cmd, e))?;
+    if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+        stdin.write_all(input.as_bytes()).await?;
+    }
+    let output = tokio::time::timeout(Duration::from_secs(30), child.wait_with_output())
+        .await
+        .map_err(|_| anyhow!("{} timed out (is the keychain locked?)", cmd))??;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .trim_end()
+            .to_string())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow!("{} failed: {}", cmd, stderr.trim()))
+    }
+}
+
+/// PowerShell for the Windows Credential Manager (through the WinRT password vault)
+#[cfg(windows)]
+const VAULT: &str = "[void][Windows.Security.Credentials.PasswordVault,Windows.Security.Credentials,ContentType=WindowsRuntime]; $v = New-Object Windows.Security.Credentials.PasswordVault;";
+
+/// Token stored for `host`, or None if there isn't one
+#[cfg(target_os = "macos")]
+async fn load(host: &str) -> Result<Option<String>> {
+    let args = ["find-generic-password", "-s", SERVICE, "-a", host, "-w"];
+    // Exit status 44: no such item
+    Ok(keychain("security", &args, None).await.ok())
+}
+
+#[cfg(target_os = "macos")]
+async fn store(host: &str, token: &str) -> Result<()> {
+    // A trailing `-w` with no value makes `security` prompt for the password (and again to
+    // confirm), which it reads from stdin without a terminal, keeping it off the command line
+    let args = [
+        "add-generic-password",
+        "-U",
+        "-s",
+        SERVICE,
+        "-a",
+        host,
+        "-w",
+    ];
+    let input = format!("{}\n{}\n", token, token);
+    keychain("security", &args, Some(&input)).await.map(|_| ())
+}
+
+#[cfg(target_os = "macos")]
+async fn delete(host: &str) -> Result<()> {
+    let args = ["delete-generic-password", "-s", SERVICE, "-a", host];
+    keychain("security", &args, None).await.map(|_| ())
+}
+
+/// The host is read from stdin as the script's first line rather than pasted into it,
+/// so no host name can change what the script does
+#[cfg(windows)]
+const READ_HOST: &str = "$h = [Console]::In.ReadLine();";
+
+#[cfg(windows)]
+async fn load(host: &str) -> Result<Option<String>> {
+    let script = format!(
+        "{} {} try {{ $c = $v.Retrieve('{}', $h); $c.RetrievePassword(); $c.Password }} catch {{}}",
+        VAULT, READ_HOST, SERVICE
+    );
+    let input = format!("{}\n", host);
+    let token = keychain(
+        "powershell",
+        &["-NoProfile", "-Command", &script],
+        Some(&input),
+    )
+    .await?;
+    Ok(Some(token).filter(|t| !t.is_empty()))
+}
+
+#[cfg(windows)]
+async fn store(host: &str, token: &str) -> Result<()> {
+    let script = format!(
+        "{} {} $v.Add((New-Object Windows.Security.Credentials.PasswordCredential('{}', $h, [Console]::In.ReadLine())))",
+        VAULT, READ_HOST, SERVICE
+    );
+    let input = format!("{}\n{}\n", host, token);
+    keychain(
+        "powershell",
+        &["-NoProfile", "-Command", &script],
+        Some(&input),
+    )
+    .await
+    .map(|_| ())
+}
+
+#[cfg(windows)]
+async fn delete(host: &str) -> Result<()> {
+    let script = format!(
+        "{} {} $v.Remove($v.Retrieve('{}', $h))",
+        VAULT, READ_HOST, SERVICE
+    );
+    let input = format!("{}\n", host);
+    keychain(
+        "powershell",
+        &["-NoProfile", "-Command", &script],
+        Some(&input),
+    )
+    .await
+    .map(|_| ())
+}
+
+/// Secret Service (GNOME Keyring, KWallet) through libsecret's `secret-tool`
+#[cfg(not(any(target_os = "macos", windows)))]
+async fn load(host: &str) -> Result<Option<String>> {
+    let args = ["lookup", "service", SERVICE, "account", host];
+    // Exits 1 with no output when nothing matches
+    Ok(keychain("secret-tool", &args, None)
+        .await
+        .ok()
+        .filter(|token| !token.is_empty()))
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+async fn store(host: &str, token: &str) -> Result<()> {
+    let label = format!("DepMgr token for {}", host);
+    let args = [
+        "store", "--label", &label, "service", SERVICE, "account", host,
+    ];
+    keychain("secret-tool", &args, Some(token))
+        .await
+        .map(|_| ())
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+async fn delete(host: &str) -> Result<()> {
+    let args = ["clear", "service", SERVICE, "account", host];
+    keychain("secret-tool", &args, None).await.map(|_| ())
+}
+
+/// Save a token in the OS keychain and list its host under `[secrets]` in config.toml
+pub async fn set_token(host: &str, token: &str) -> Result<()> {
+    store(host, token).await?;
+    let mut settings = crate::config::Settings::load();
+    if !settings.secrets.hosts.iter().any(|h| h == host) {
+        settings.secrets.hosts.push(host.to_string());
+        settings.save()?;
+    }
+    Ok(())
+}
+
+/// Delete a host's token from the keychain and config.toml
+pub async fn remove_token(host: &str) -> Result<()> {
+    let mut settings = crate::config::Settings::load();
+    settings.secrets.hosts.retain(|h| h != host);
+    settings.save()?;
+    delete(host).await
+}
+
+/// Tokens looked up so far this run, by host (None: listed but missing from the keychain)
+static TOKENS: LazyLock<tokio::sync::Mutex<HashMap<String, Option<String>>>> =
+    LazyLock::new(|| tokio::sync::Mutex::new(HashMap::new()));
+
+/// Hosts with a stored token, read from config.toml once
+static HOSTS: LazyLock<Vec<String>> =
+    LazyLock::new(|| crate::config::Settings::load().secrets.hosts);
+
+/// The token for requests to `host`. Only hosts listed in config.toml reach the
+/// keychain, each at most once per run, so there's a single unlock prompt at most.
+pub async fn token_for(host: &str) -> Option<String> {
+    if !HOSTS.iter().any(|h| h == host) {
+        return None;
+    }
+    let mut tokens = TOKENS.lock().await;
+    if let Some(token) = tokens.get(host) {
+        return token.clone();
+    }
+    let token = match load(host).await {
+        Ok(token) => token,
+        Err(e) => {
+            eprintln!("[SECRETS] Couldn't read the token for {}: {}", host, e);
+            None
+        }
+    };
+    if token.is_none() {
+        eprintln!("[SECRETS] No token for {} in the keychain", host);
+    }
+    tokens.insert(host.to_string(), token.clone());
+    token
+}
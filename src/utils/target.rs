@@ -0,0 +1,204 @@
+use anyhow::{anyhow, Result};
+use std::process::Stdio;
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
+
+/// Where package manager commands run. Everything that shells out goes through
+/// `run_command_with_timeout`, which wraps the command for the current target.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ExecTarget {
+    #[default]
+    Local,
+    /// `ssh <host>`; the host can be anything ssh accepts (alias from ~/.ssh/config, user@host)
+    Ssh(String),
+    /// `docker exec <id>`. `name` is what the user asked for (container or image);
+    /// `ephemeral` containers were started from an image by us and are stopped afterwards.
+    Container {
+        name: String,
+        id: String,
+        ephemeral: bool,
+    },
+    /// `--demo`: the bundled fixture inventory, nothing is spawned or fetched
+    Demo,
+}
+
+impl ExecTarget {
+    pub fn label(&self) -> String {
+        match self {
+            ExecTarget::Local => "This machine".to_string(),
+            ExecTarget::Ssh(host) => format!("ssh: {}", host),
+            ExecTarget::Container { name, .. } => format!("docker: {}", name),
+            ExecTarget::Demo => "Demo data".to_string(),
+        }
+    }
+
+    pub fn is_container(&self) -> bool {
+        matches!(self, ExecTarget::Container { .. })
+    }
+
+    pub fn is_demo(&self) -> bool {
+        matches!(self, ExecTarget::Demo)
+    }
+
+    /// Local-only phases (project scan, disk sizes) read this machine's filesystem
+    pub fn is_local(&self) -> bool {
+        matches!(self, ExecTarget::Local)
+    }
+
+    /// An ssh target, refusing hosts ssh would read as one of its own options
+    /// (`-oProxyCommand=...` runs a local command)
+    pub fn ssh(host: &str) -> Result<Self> {
+        if host.starts_with('-') {
+            return Err(anyhow!("'{}' is not an ssh host", host));
+        }
+        Ok(ExecTarget::Ssh(host.to_string()))
+    }
+
+    /// Program and arguments that run `cmd args` on this target
+    pub fn wrap(&self, cmd: &str, args: &[&str]) -> (String, Vec<String>) {
+        match self {
+            ExecTarget::Local => (
+                cmd.to_string(),
+                args.iter().map(|a| a.to_string()).collect(),
+            ),
+            ExecTarget::Ssh(host) => {
+                let command_line = std::iter::once(cmd)
+                    .chain(args.iter().copied())
+                    .map(shell_quote)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                // A login shell picks up PATH from the remote profile (Homebrew, nvm, cargo)
+                let remote = format!("sh -lc {}", shell_quote(&command_line));
+                (
+                    "ssh".to_string(),
+                    vec![
+                        "-o".to_string(),
+                        "BatchMode=yes".to_string(), // key auth only, never hang on a password
+                        "-o".to_string(),
+                        "ConnectTimeout=10".to_string(),
+                        host.clone(),
+                        "--".to_string(),
+                        remote,
+                    ],
+                )
+            }
+            ExecTarget::Container { id, .. } => {
+                let mut wrapped = vec!["exec".to_string(), "-i".to_string(), id.clone()];
+                wrapped.push(cmd.to_string());
+                wrapped.extend(args.iter().map(|a| a.to_string()));
+                ("docker".to_string(), wrapped)
+            }
+            // run_command_with_timeout refuses to run anything in demo mode
+            ExecTarget::Demo => unreachable!("demo mode never spawns commands"),
+        }
+    }
+}
+
+pub(crate) fn shell_quote(arg: &str) -> String {
+    if !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=@:+,".contains(c))
+    {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+static CURRENT_TARGET: LazyLock<RwLock<ExecTarget>> =
+    LazyLock::new(|| RwLock::new(ExecTarget::Local));
+
+pub fn current_target() -> ExecTarget {
+    CURRENT_TARGET.read().unwrap().clone()
+}
+
+/// Switch every subsequent command to another machine. Cached listings belong to the
+/// previous target, so the in-memory cache is dropped.
+pub fn set_target(target: ExecTarget) {
+    println!("[TARGET] Commands now run on {}", target.label());
+    *CURRENT_TARGET.write().unwrap() = target;
+    crate::utils::cache::MEMORY_CACHE.clear();
+}
+
+/// Docker commands that manage the container itself always run on this machine,
+/// whatever the current target is
+async fn docker(args: &[&str], timeout: Duration) -> Result<String> {
+    let output = tokio::time::timeout(
+        timeout,
+        tokio::process::Command::new("docker")
+            .args(args)
+            .stdin(Stdio::null())
+            .output(),
+    )
+    .await
+    .map_err(|_| anyhow!("docker {} timed out", args.join(" ")))??;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "docker {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Resolve a running container by name/id, or start a throwaway one from an image
+pub async fn open_container(reference: &str) -> Result<ExecTarget> {
+    // docker would read it as one of its own options
+    if reference.starts_with('-') {
+        return Err(anyhow!("'{}' is not a container or image name", reference));
+    }
+    if let Ok(id) = docker(
+        &["container", "inspect", "-f", "{{.Id}}", "--", reference],
+        Duration::from_secs(10),
+    )
+    .await
+    {
+        println!("[TARGET] Using running container {}", reference);
+        return Ok(ExecTarget::Container {
+            name: reference.to_string(),
+            id,
+            ephemeral: false,
+        });
+    }
+
+    // Keep the image's own environment but replace its entrypoint with something idle,
+    // so we can exec the listing commands into it. Pulling can take a while.
+    println!("[TARGET] Starting a container from image {}", reference);
+    let id = docker(
+        &[
+            "run",
+            "-d",
+            "--rm",
+            "--entrypoint",
+            "tail",
+            reference,
+            "-f",
+            "/dev/null",
+        ],
+        Duration::from_secs(600),
+    )
+    .await?;
+
+    Ok(ExecTarget::Container {
+        name: reference.to_string(),
+        id,
+        ephemeral: true,
+    })
+}
+
+/// Stop a container we started from an image (`--rm` removes it)
+pub async fn close_target(target: &ExecTarget) {
+    if let ExecTarget::Container {
+        id,
+        ephemeral: true,
+        ..
+    } = target
+    {
+        if let Err(e) = docker(&["stop", "-t", "1", id], Duration::from_secs(30)).await {
+            eprintln!("[ERROR] Failed to stop container {}: {}", id, e);
+        }
+    }
+}
@@ -0,0 +1,453 @@
+use crate::models::PackageManager;
+use std::cmp::Ordering;
+
+/// One dot/dash separated piece of a pre- or post-release tag: "rc1" -> ["rc", 1]
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Ident {
+    Num(u64),
+    Word(String),
+}
+
+impl Ident {
+    /// Python and most projects order dev < alpha < beta < rc; unknown words sort after
+    /// them, alphabetically as semver does
+    fn rank(word: &str) -> u8 {
+        match word {
+            "dev" | "snapshot" | "nightly" | "canary" => 0,
+            "alpha" | "a" => 1,
+            "beta" | "b" => 2,
+            "pre" | "preview" | "rc" | "c" => 3,
+            _ => 4,
+        }
+    }
+
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Ident::Num(a), Ident::Num(b)) => a.cmp(b),
+            (Ident::Num(_), Ident::Word(_)) => Ordering::Less,
+            (Ident::Word(_), Ident::Num(_)) => Ordering::Greater,
+            (Ident::Word(a), Ident::Word(b)) => (Self::rank(a), a).cmp(&(Self::rank(b), b)),
+        }
+    }
+}
+
+/// What follows the release numbers
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Stage {
+    Pre,   // "-beta.2", "rc1", Debian's "~rc1": before the release
+    Final, // the release itself
+    Post,  // ".post1", "p1", openssl's "1.1.1w", apt's "-1": after it
+}
+
+/// A version split into the parts that order it:
+/// `[epoch:]release[-pre|post][+build][,pkg-version][_revision]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Version {
+    epoch: u64,            // Debian "2:", PEP 440 "1!"
+    release: Vec<u64>,     // "1.2.3" -> [1, 2, 3]
+    stage: Stage,          // where the tag puts it relative to the release
+    tag: Vec<Ident>,       // the pre/post-release identifiers
+    pkg_version: Vec<u64>, // Homebrew cask build after the comma: "4.28.1,151"
+    revision: u64,         // Homebrew formula rebuild: "1.2.3_1"
+}
+
+fn idents(tag: &str) -> Vec<Ident> {
+    let mut idents = Vec::new();
+    for part in tag.split(['.', '-', '_', '~']).filter(|p| !p.is_empty()) {
+        // "rc1" -> "rc", 1
+        let mut rest = part;
+        while !rest.is_empty() {
+            let digits = rest.starts_with(|c: char| c.is_ascii_digit());
+            let end = rest
+                .find(|c: char| c.is_ascii_digit() != digits)
+                .unwrap_or(rest.len());
+            let (piece, tail) = rest.split_at(end);
+            idents.push(match piece.parse() {
+                Ok(n) if digits => Ident::Num(n),
+                _ => Ident::Word(piece.to_ascii_lowercase()),
+            });
+            rest = tail;
+        }
+    }
+    idents
+}
+
+impl Version {
+    /// `system`: apt/apk versions, where a numeric "-1" / "-r1" is the distro's package
+    /// revision rather than a semver pre-release
+    fn parse(version: &str, system: bool) -> Self {
+        let mut rest = version.trim().trim_start_matches(['v', '=']);
+
+        let mut epoch = 0;
+        if let Some((prefix, tail)) = rest.split_once([':', '!']) {
+            if let Ok(n) = prefix.parse() {
+                epoch = n;
+                rest = tail;
+            }
+        }
+
+        let mut revision = 0;
+        if let Some((head, tail)) = rest.rsplit_once('_') {
+            if let Ok(n) = tail.parse() {
+                revision = n;
+                rest = head;
+            }
+        }
+
+        let mut pkg_version = Vec::new();
+        if let Some((head, tail)) = rest.split_once(',') {
+            pkg_version = tail
+                .split([',', '.'])
+                .map_while(|p| p.parse().ok())
+                .collect();
+            rest = head;
+        }
+
+        // Build metadata doesn't order versions
+        let rest = rest.split('+').next().unwrap_or("");
+
+        let release_end = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        let (numbers, suffix) = rest.split_at(release_end);
+        let release = numbers
+            .split('.')
+            .map_while(|part| part.parse().ok())
+            .collect();
+
+        let tag = idents(suffix);
+        let stage = match tag.first() {
+            None => Stage::Final,
+            // Debian's "~" sorts before anything, the release included: "1.2.3~rc1-1"
+            _ if suffix.starts_with('~') => Stage::Pre,
+            // A lone letter right after the numbers is a letter release: openssl's "1.1.1a"
+            // follows 1.1.1, while "1.0a1" and "1.0-b2" stay pre-releases
+            _ if suffix.len() == 1 && suffix.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                Stage::Post
+            }
+            Some(Ident::Word(word)) if matches!(word.as_str(), "post" | "p" | "pl" | "patch") => {
+                Stage::Post
+            }
+            // Debian/Alpine package revision: "1.2.3-1", "2.30-0.1", "2.34-1ubuntu1",
+            // "8.5.0-r0"
+            _ if system
+                && suffix
+                    .strip_prefix("-r")
+                    .or_else(|| suffix.strip_prefix('-'))
+                    .is_some_and(|r| {
+                        r.starts_with(|c: char| c.is_ascii_digit())
+                            && r.chars().all(|c| c.is_ascii_alphanumeric() || c == '.')
+                    }) =>
+            {
+                Stage::Post
+            }
+            // Semver: anything else after a dash is a pre-release
+            _ if suffix.starts_with('-') => Stage::Pre,
+            Some(Ident::Word(word)) if Ident::rank(word) < 4 => Stage::Pre,
+            _ => Stage::Post,
+        };
+
+        Self {
+            epoch,
+            release,
+            stage,
+            tag,
+            pkg_version,
+            revision,
+        }
+    }
+}
+
+/// Missing trailing numbers count as zero ("1.2" == "1.2.0")
+fn cmp_numbers(a: &[u64], b: &[u64]) -> Ordering {
+    (0..a.len().max(b.len()))
+        .map(|i| a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Semver precedence: compare identifiers in order, a shorter list sorting first
+fn cmp_idents(a: &[Ident], b: &[Ident]) -> Ordering {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| a.cmp(b))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or_else(|| a.len().cmp(&b.len()))
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| cmp_numbers(&self.release, &other.release))
+            .then_with(|| self.stage.cmp(&other.stage))
+            .then_with(|| cmp_idents(&self.tag, &other.tag))
+            .then_with(|| cmp_numbers(&self.pkg_version, &other.pkg_version))
+            .then_with(|| self.revision.cmp(&other.revision))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Release numbers of a version: "v1.2.3-beta" -> [1, 2, 3]
+fn components(version: &str) -> Vec<u64> {
+    Version::parse(version, false).release
+}
+
+/// Semver-aware comparison that also understands epochs ("1:2.0"), Python pre/post
+/// releases ("3.13.0rc1", "2.0.post1"), Homebrew revisions ("1.2.3_1") and cask
+/// pkg-versions ("4.28.1,151")
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    Version::parse(a, false).cmp(&Version::parse(b, false))
+}
+
+/// `compare_versions` for versions a manager reported, which for apt and apk end in a
+/// package revision ("1.2.3-1", "8.5.0-r0") that sorts after the release
+pub fn compare_package_versions(manager: &PackageManager, a: &str, b: &str) -> Ordering {
+    let system = matches!(manager, PackageManager::Apt | PackageManager::Apk);
+    Version::parse(a, system).cmp(&Version::parse(b, system))
+}
+
+/// Whether `latest` is a newer release than `installed`. Strings without any version
+/// numbers (commit hashes, "latest") can only be told apart, not ordered.
+pub fn is_newer(latest: &str, installed: &str) -> bool {
+    let (latest_parsed, installed_parsed) = (
+        Version::parse(latest, false),
+        Version::parse(installed, false),
+    );
+    if latest_parsed.release.is_empty() || installed_parsed.release.is_empty() {
+        return latest.trim() != installed.trim();
+    }
+    latest_parsed > installed_parsed
+}
+
+/// How big a jump from one version to another is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bump {
+    Major,
+    Minor,
+    Patch,
+}
+
+/// Semver-style bump size; below 1.0 a minor bump counts as major ("0.3" -> "0.4")
+pub fn bump_kind(from: &str, to: &str) -> Bump {
+    let (from, to) = (components(from), components(to));
+    let part = |v: &[u64], i: usize| v.get(i).copied().unwrap_or(0);
+    if part(&from, 0) != part(&to, 0) || (part(&from, 0) == 0 && part(&from, 1) != part(&to, 1)) {
+        Bump::Major
+    } else if part(&from, 1) != part(&to, 1) {
+        Bump::Minor
+    } else {
+        Bump::Patch
+    }
+}
+
+/// Highest release-looking tag ("v1.2.3" / "1.2") among git ref names
+pub fn highest_tag<'a>(tags: impl Iterator<Item = &'a str>) -> Option<String> {
+    tags.map(|t| t.trim_start_matches('v'))
+        .filter(|t| {
+            let mut parts = t.split('.');
+            parts.next().is_some_and(|p| p.parse::<u64>().is_ok())
+                && parts.all(|p| p.parse::<u64>().is_ok())
+                && t.contains('.')
+        })
+        .max_by(|a, b| compare_versions(a, b))
+        .map(|t| t.to_string())
+}
+
+/// Upper bound for a "^"/"~" style range: bump the component at `idx` and drop the rest
+fn bump(parts: &[u64], idx: usize) -> String {
+    let mut upper: Vec<u64> = parts[..=idx].to_vec();
+    upper[idx] += 1;
+    upper
+        .iter()
+        .map(|p| p.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// One comparator like ">=20", "^1.2", "~=3.10", "18.x". None if we can't make sense of it.
+fn satisfies_comparator(version: &str, comparator: &str) -> Option<bool> {
+    let comparator = comparator.trim();
+    if comparator.is_empty() || comparator == "*" || comparator.eq_ignore_ascii_case("latest") {
+        return Some(true);
+    }
+
+    let op_len = comparator
+        .find(|c: char| c.is_ascii_digit() || c == 'v')
+        .unwrap_or(comparator.len());
+    let (op, target) = comparator.split_at(op_len);
+    let target = target.trim();
+
+    // x-ranges: "18.x", "3.*"
+    if target.contains(['x', 'X', '*']) {
+        let fixed: Vec<&str> = target
+            .split('.')
+            .take_while(|p| !matches!(*p, "x" | "X" | "*"))
+            .collect();
+        let installed = components(version);
+        return Some(
+            fixed
+                .iter()
+                .enumerate()
+                .all(|(i, p)| p.parse::<u64>().ok() == installed.get(i).copied()),
+        );
+    }
+
+    let parts = components(target);
+    if parts.is_empty() {
+        return None;
+    }
+    let cmp = compare_versions(version, target);
+
+    let result = match op.trim() {
+        ">=" => cmp != Ordering::Less,
+        ">" => cmp == Ordering::Greater,
+        "<=" => cmp != Ordering::Greater,
+        "<" => cmp == Ordering::Less,
+        "" | "=" | "==" => {
+            // A bare "20" or "3.12" means that release line
+            let installed = components(version);
+            parts
+                .iter()
+                .enumerate()
+                .all(|(i, p)| installed.get(i) == Some(p))
+        }
+        "!=" => cmp != Ordering::Equal,
+        "^" => {
+            // Caret: the first non-zero component must match
+            let idx = parts
+                .iter()
+                .position(|p| *p != 0)
+                .unwrap_or(parts.len() - 1);
+            cmp != Ordering::Less && compare_versions(version, &bump(&parts, idx)) == Ordering::Less
+        }
+        "~" => {
+            let idx = if parts.len() > 1 { 1 } else { 0 };
+            cmp != Ordering::Less && compare_versions(version, &bump(&parts, idx)) == Ordering::Less
+        }
+        "~=" => {
+            // PEP 440 compatible release: ~=3.10 means >=3.10, ==3.*
+            let idx = parts.len().saturating_sub(2);
+            cmp != Ordering::Less && compare_versions(version, &bump(&parts, idx)) == Ordering::Less
+        }
+        _ => return None,
+    };
+    Some(result)
+}
+
+/// Check an installed version against an npm/Cargo/PEP 440 style constraint.
+/// Supports "||" alternatives and comma/space separated comparators.
+/// Returns None when the constraint uses syntax we don't understand.
+pub fn satisfies(version: &str, constraint: &str) -> Option<bool> {
+    for alternative in constraint.split("||") {
+        // ">= 20" is written with a space surprisingly often
+        let normalized = alternative
+            .replace(">= ", ">=")
+            .replace("<= ", "<=")
+            .replace("> ", ">")
+            .replace("< ", "<")
+            .replace("^ ", "^")
+            .replace("~ ", "~");
+        let mut all = true;
+        for comparator in normalized.split([',', ' ']).filter(|c| !c.is_empty()) {
+            match satisfies_comparator(version, comparator) {
+                Some(true) => {}
+                Some(false) => all = false,
+                None => return None,
+            }
+        }
+        if all {
+            return Some(true);
+        }
+    }
+    Some(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn homebrew_revisions_and_pkg_versions() {
+        // A rebuild of the same release isn't older than the release
+        assert!(!is_newer("1.2.3", "1.2.3_1"));
+        assert!(is_newer("1.2.3_2", "1.2.3_1"));
+        assert!(is_newer("1.2.4", "1.2.3_1"));
+        assert!(is_newer("4.28.1,152", "4.28.1,151"));
+        assert!(!is_newer("4.28.1,151", "4.28.1,151"));
+        assert!(is_newer("4.29.0,12", "4.28.1,151"));
+    }
+
+    #[test]
+    fn pre_releases_sort_before_the_release() {
+        assert!(is_newer("1.0.0", "1.0.0-rc.1"));
+        assert!(!is_newer("1.0.0-rc.1", "1.0.0"));
+        assert!(is_newer("1.0.0-beta.2", "1.0.0-beta.1"));
+        assert!(is_newer("1.0.0-rc.1", "1.0.0-beta.11"));
+        assert!(is_newer("1.0.0-beta.11", "1.0.0-beta.2"));
+        assert!(is_newer("1.0.0-alpha.1", "1.0.0-alpha"));
+        assert!(is_newer("3.13.0", "3.13.0rc1"));
+        assert!(is_newer("3.13.0rc1", "3.13.0b4"));
+        assert!(is_newer("3.13.0a1", "3.13.0.dev2"));
+        assert_eq!(compare_versions("1.2.3+build.5", "1.2.3"), Ordering::Equal);
+    }
+
+    #[test]
+    fn post_releases_sort_after_the_release() {
+        assert!(is_newer("2.0.post1", "2.0"));
+        assert!(is_newer("9.6p1", "9.6"));
+        assert!(is_newer("1.1.1w", "1.1.1v"));
+        assert!(!is_newer("1.1.1", "1.1.1w"));
+        assert!(is_newer("1.1.1a", "1.1.1"));
+        assert!(is_newer("1.1.1b", "1.1.1a"));
+        assert!(is_newer("1.1.1d", "1.1.1c"));
+        assert!(is_newer("1.0", "1.0a1"));
+        assert!(is_newer("1.0", "1.0-b2"));
+    }
+
+    #[test]
+    fn epochs_outrank_release_numbers() {
+        assert!(is_newer("1:1.0", "9.9"));
+        assert!(is_newer("2:8.2.3995-1", "1:9.0.0-1"));
+        assert!(!is_newer("1!1.0", "1!1.0"));
+        assert!(is_newer("1:2.30-2", "1:2.30-1"));
+    }
+
+    #[test]
+    fn system_revisions_sort_after_the_release() {
+        let apt = |a, b| compare_package_versions(&PackageManager::Apt, a, b);
+        assert_eq!(apt("1.2.3-1", "1.2.3"), Ordering::Greater);
+        assert_eq!(apt("1.2.3-10", "1.2.3-9"), Ordering::Greater);
+        assert_eq!(apt("1.2.4", "1.2.3-1"), Ordering::Greater);
+        assert_eq!(apt("2.30-1.1", "2.30-1"), Ordering::Greater);
+        assert_eq!(apt("2.34-1ubuntu1", "2.34-1"), Ordering::Greater);
+        assert_eq!(apt("2.34-2", "2.34-1ubuntu1"), Ordering::Greater);
+        assert_eq!(apt("1.2.3-1", "1.2.3~rc1-1"), Ordering::Greater);
+        assert_eq!(apt("1.2.3~rc2-1", "1.2.3~rc1-1"), Ordering::Greater);
+        // A word after the dash is still a pre-release
+        assert_eq!(apt("1.2.3", "1.2.3-beta1"), Ordering::Greater);
+        let apk = |a, b| compare_package_versions(&PackageManager::Apk, a, b);
+        assert_eq!(apk("8.5.0-r1", "8.5.0-r0"), Ordering::Greater);
+        assert_eq!(apk("8.5.0-r0", "8.5.0"), Ordering::Greater);
+
+        // Semver: a numeric pre-release is still before the release
+        assert!(is_newer("1.0.0", "1.0.0-1"));
+        assert!(is_newer("2.0.0", "2.0.0-0"));
+        let npm = |a, b| compare_package_versions(&PackageManager::Npm, a, b);
+        assert_eq!(npm("1.0.0-1", "1.0.0"), Ordering::Less);
+    }
+
+    #[test]
+    fn plain_and_unversioned_strings() {
+        assert_eq!(compare_versions("v1.2", "1.2.0"), Ordering::Equal);
+        assert!(is_newer("1.10.0", "1.9.9"));
+        assert!(is_newer("0a1b2c3", "latest"));
+        assert!(!is_newer("latest", "latest"));
+        assert_eq!(bump_kind("3.12.9", "3.13.0rc1"), Bump::Minor);
+    }
+}